@@ -4,7 +4,7 @@ mod services;
 mod update;
 mod view_model;
 
-use iced::widget::{column, row, stack, text, text_editor};
+use iced::widget::{column, pane_grid, row, stack, text, text_editor};
 use iced::{Element, Fill, Subscription, Task, Theme as IcedTheme, event, time, window};
 use std::cell::RefCell;
 use std::sync::Arc;
@@ -15,16 +15,19 @@ use futures::{StreamExt, stream};
 use iced::keyboard;
 
 use crate::config::{
-    HistoryConfig, HostsConfig, SettingsConfig, SnippetHistoryConfig, SnippetsConfig,
+    HistoryConfig, Host, HostsConfig, MacrosConfig, SettingsConfig, SftpBookmarksConfig,
+    SnippetHistoryConfig, SnippetsConfig, TerminalProfilesConfig,
 };
 use crate::hub::sync::{ConflictChoice, SyncConflict};
 use crate::hub::vault::HubVaultConfig;
-use crate::keybindings::KeybindingsConfig;
+use crate::keybindings::{AppAction, KeybindingsConfig};
+use crate::local::LaunchTarget;
 use crate::message::{
-    Message, SessionId, SessionMessage, SettingsTab, SidebarMenuItem, UiMessage, VncMessage,
+    Message, SessionId, SessionMessage, SettingsTab, SidebarMenuItem, SwitcherTarget, UiMessage,
+    VncMessage,
 };
 use crate::terminal::metrics::TerminalMetrics;
-use crate::theme::{ScaledFonts, ThemeId, get_theme};
+use crate::theme::{ScaledFonts, Theme, ThemeColorField, ThemeId, theme_for};
 use crate::views::command_palette::{available_commands, command_palette_view};
 use crate::views::components::dropzone_overlay;
 use crate::views::dialogs::about_dialog::about_dialog_view;
@@ -34,18 +37,23 @@ use crate::views::dialogs::host_dialog::host_dialog_view;
 use crate::views::dialogs::host_key_dialog::host_key_dialog_view;
 use crate::views::dialogs::passphrase_dialog::passphrase_dialog_view;
 use crate::views::dialogs::password_dialog::password_dialog_view;
+use crate::views::dialogs::port_forwards_dialog::port_forwards_dialog_view;
 use crate::views::dialogs::portal_hub_dialogs::{
     portal_hub_conflict_dialog_view, portal_hub_disable_sync_dialog_view,
     portal_hub_onboarding_dialog_view,
 };
 use crate::views::dialogs::quick_connect_dialog::quick_connect_dialog_view;
+use crate::views::dialogs::quit_confirm_dialog::quit_confirm_dialog_view;
 use crate::views::dialogs::session_choice_dialog::session_choice_dialog_view;
+use crate::views::dialogs::snippet_params_dialog::snippet_params_dialog_view;
 use crate::views::dialogs::vnc_cleartext_dialog::vnc_cleartext_dialog_view;
 use crate::views::file_viewer::file_viewer_view;
 use crate::views::history_view::history_view;
+use crate::views::host_context_menu::{HostContextMenuState, host_context_menu_overlay};
 use crate::views::host_details_sheet::host_details_sheet_view;
 use crate::views::host_grid::{calculate_columns, host_grid_view, search_input_id};
 use crate::views::proxy_sessions::proxy_sessions_view;
+use crate::views::quick_switcher::{available_items, quick_switcher_view};
 use crate::views::settings_page::{SettingsPageContext, settings_page_view};
 use crate::views::sftp::{
     dual_pane_sftp_view, has_actions_menu_open, sftp_actions_menu_dismiss_overlay,
@@ -53,9 +61,13 @@ use crate::views::sftp::{
 };
 use crate::views::sidebar::sidebar_view;
 use crate::views::snippet_grid::{SnippetPageContext, snippet_page_view};
+use crate::views::snippet_palette::snippet_palette_view;
 use crate::views::tab_context_menu::{TabContextMenuState, tab_context_menu_overlay};
 use crate::views::tabs::{Tab, tab_bar_view};
-use crate::views::terminal_view::terminal_view_with_status;
+use crate::views::terminal_status_bar::ForwardSummary;
+use crate::views::terminal_view::{
+    SplitPane, split_pane_title_bar, split_terminal_area, terminal_pane, terminal_view_with_status,
+};
 use crate::views::toast::{ToastManager, toast_overlay_view};
 use crate::views::vault_page::{
     VaultPageContext, vault_add_key_dialog_view, vault_edit_key_dialog_view, vault_page_view,
@@ -64,8 +76,10 @@ use crate::views::vnc_view::vnc_viewer_view;
 
 pub use self::managers::ActiveSession;
 use self::managers::{
-    ActiveDialog, DialogManager, FileViewerManager, ProxySessionsState, SessionManager,
-    SftpManager, SnippetExecutionManager, TransferManager, VncActiveSession,
+    ActiveDialog, DialogManager, FileViewerManager, FileWatchManager, ProxySessionsState,
+    ReachabilityManager, SessionBackend, SessionManager, SessionRestoreManager, SftpManager,
+    SnippetExecutionManager, TerminalPaneLayout, TerminalPaneManager, TransferManager,
+    VncActiveSession,
 };
 use self::view_model::HostGridCache;
 
@@ -212,9 +226,21 @@ mod tests {
 pub struct UiState {
     pub active_view: View,
     pub search_query: String,
+    /// Tags a host must all carry to show in the host grid
+    pub host_tag_filter: Vec<String>,
     host_grid_cache: RefCell<HostGridCache>,
     pub command_palette_open: bool,
     pub command_palette_query: String,
+    pub quick_switcher_open: bool,
+    pub quick_switcher_query: String,
+    /// Quick switcher picks, most recent first
+    pub quick_switcher_recent: Vec<SwitcherTarget>,
+    pub snippet_palette_open: bool,
+    pub snippet_palette_query: String,
+    /// Highlighted row among the snippet palette matches
+    pub snippet_palette_selected: usize,
+    /// WSL distributions and containers found when the palette was opened
+    pub local_launch_targets: Vec<LaunchTarget>,
     pub host_details_sheet: Option<Uuid>,
     pub sidebar_state: SidebarState,
     pub sidebar_state_before_session: Option<SidebarState>, // Saved state before hiding for terminal
@@ -227,10 +253,17 @@ pub struct UiState {
     pub tab_focus_index: usize,
     pub host_grid_focus_index: Option<usize>,
     pub history_focus_index: Option<usize>,
+    /// Search and facets of the history view
+    pub history_filter: crate::config::HistoryFilter,
     pub terminal_captured: bool,
     pub terminal_focus_token: u64,
+    /// Blink phase of the terminal cursor, flipped by the blink timer
+    pub cursor_blink_visible: bool,
     pub window_focused: bool,
+    /// Modifier keys currently held down
+    pub keyboard_modifiers: keyboard::Modifiers,
     pub tab_context_menu: TabContextMenuState,
+    pub host_context_menu: HostContextMenuState,
     pub portal_hub_status: Option<crate::proxy::ProxyStatus>,
     pub portal_hub_status_error: Option<String>,
     pub portal_hub_status_loading: bool,
@@ -251,7 +284,14 @@ pub struct UiState {
     pub hub_prompt_dismissed: bool,
     /// Host card currently under the pointer (reveals per-card actions).
     pub hovered_host_card: Option<Uuid>,
+    /// Host or group being dragged in the host grid
+    pub host_grid_drag: Option<crate::views::host_grid::HostGridDrag>,
     pub hovered_drop_files: Vec<std::path::PathBuf>,
+    /// Hex text typed into the custom theme editor, kept while it does not
+    /// parse as a color
+    pub theme_color_inputs: std::collections::HashMap<ThemeColorField, String>,
+    /// Action whose shortcut is being recorded on the settings page
+    pub keybinding_capture: Option<AppAction>,
 }
 
 /// State for the Portal Hub onboarding Defaults step.
@@ -273,34 +313,66 @@ pub struct PortalHubWizardState {
 #[derive(Debug)]
 pub struct PreferencesState {
     pub theme_id: ThemeId,
+    pub custom_themes: Vec<crate::theme::CustomTheme>,
     pub system_ui_scale: f32,           // Detected at startup, read-only
     pub ui_scale_override: Option<f32>, // User override from settings
     pub terminal_font_size: f32,
     pub terminal_scroll_speed: f32,
+    pub scrollback_lines: usize,
     pub terminal_font: crate::fonts::TerminalFont,
     pub terminal_metric_adjustments: crate::config::settings::TerminalMetricAdjustments,
+    pub terminal_text_style: crate::config::settings::TerminalTextStyle,
+    pub cursor_blink: bool,
+    pub cursor_blink_interval_ms: u64,
+    pub terminal_bell: crate::config::settings::TerminalBell,
+    pub terminal_clipboard: crate::config::settings::TerminalClipboardAccess,
     pub sftp_column_widths: crate::views::sftp::ColumnWidths,
+    pub sftp_show_hidden: bool,
+    pub sftp_verify_transfers: bool,
+    pub sftp_resume_transfers: bool,
+    pub viewer_word_wrap: bool,
     pub vnc_settings: crate::config::settings::VncSettings,
     pub portal_hub: crate::config::settings::PortalHubSettings,
     pub auto_reconnect: bool,
+    pub confirm_quit: bool,
+    pub restore_sessions: bool,
     pub reconnect_max_attempts: u32,
     pub reconnect_base_delay_ms: u64,
     pub reconnect_max_delay_ms: u64,
+    pub connect_timeout: u64,
+    pub keepalive_interval: u64,
+    pub keepalive_count_max: u32,
     pub allow_agent_forwarding: bool,
+    pub host_reachability: bool,
     pub credential_timeout: u64,
     pub session_logging_enabled: bool,
     pub session_log_dir: Option<std::path::PathBuf>,
     pub session_log_format: crate::config::settings::SessionLogFormat,
+    pub session_log_input: bool,
+    pub session_log_max_size_mb: u64,
     pub security_audit_enabled: bool,
     pub security_audit_dir: Option<std::path::PathBuf>,
     pub keybindings: KeybindingsConfig,
 }
 
+impl PreferencesState {
+    /// Global SSH keepalive settings, before any per-host override
+    pub fn keepalive(&self) -> crate::ssh::Keepalive {
+        crate::ssh::Keepalive {
+            interval_secs: self.keepalive_interval,
+            count_max: self.keepalive_count_max,
+        }
+    }
+}
+
 /// Configuration-backed state.
 #[derive(Debug)]
 pub struct ConfigState {
     pub hosts: HostsConfig,
     pub snippets: SnippetsConfig,
+    pub macros: MacrosConfig,
+    pub sftp_bookmarks: SftpBookmarksConfig,
+    pub terminal_profiles: TerminalProfilesConfig,
     pub history: HistoryConfig,
     pub snippet_history: SnippetHistoryConfig,
     pub vault: HubVaultConfig,
@@ -366,7 +438,7 @@ pub struct SnippetUiState {
     pub viewed_history_entry: Option<Uuid>,
 }
 
-/// State for editing a snippet (name, command, description, selected hosts)
+/// State for editing a snippet (name, command, description, selected hosts, OS filter)
 #[derive(Debug, Clone)]
 pub struct SnippetEditState {
     /// ID of snippet being edited (None = creating new)
@@ -379,6 +451,8 @@ pub struct SnippetEditState {
     pub description: String,
     /// Selected host IDs for execution
     pub selected_hosts: std::collections::HashSet<Uuid>,
+    /// Only suggest the snippet for hosts running this OS
+    pub os_filter: Option<crate::config::DetectedOs>,
 }
 
 impl SnippetEditState {
@@ -390,6 +464,7 @@ impl SnippetEditState {
             command: String::new(),
             description: String::new(),
             selected_hosts: std::collections::HashSet::new(),
+            os_filter: None,
         }
     }
 
@@ -401,6 +476,7 @@ impl SnippetEditState {
             command: snippet.command.clone(),
             description: snippet.description.clone().unwrap_or_default(),
             selected_hosts: snippet.host_ids.iter().copied().collect(),
+            os_filter: snippet.os_filter.clone(),
         }
     }
 
@@ -428,14 +504,22 @@ pub struct Portal {
     sessions: SessionManager,
     sftp: SftpManager,
     transfers: TransferManager,
+    file_watches: FileWatchManager,
     file_viewers: FileViewerManager,
     proxy_sessions: ProxySessionsState,
+    host_reachability: ReachabilityManager,
     dialogs: DialogManager,
     pending_connect: Option<PendingConnect>,
     pre_session_terminal_output: std::collections::HashMap<SessionId, PreSessionTerminalOutput>,
 
     // VNC sessions (separate from terminal sessions)
     pub(crate) vnc_sessions: std::collections::HashMap<SessionId, VncActiveSession>,
+    // Terminal sessions whose input is mirrored to each other
+    broadcast_group: std::collections::HashSet<SessionId>,
+    // Split layouts of terminal tabs
+    terminal_panes: TerminalPaneManager,
+    // Tabs saved for and restored from the previous run
+    session_restore: SessionRestoreManager,
 
     // Preferences and config
     prefs: PreferencesState,
@@ -454,14 +538,22 @@ pub struct Portal {
 pub(crate) struct PendingConnect {
     session_id: SessionId,
     draft_tab_id: Option<Uuid>,
+    /// Saved host being connected to, for recording a failed attempt
+    host_id: Option<Uuid>,
     handle: iced::task::Handle,
 }
 
 impl PendingConnect {
-    fn new(session_id: SessionId, draft_tab_id: Option<Uuid>, handle: iced::task::Handle) -> Self {
+    fn new(
+        session_id: SessionId,
+        draft_tab_id: Option<Uuid>,
+        host_id: Option<Uuid>,
+        handle: iced::task::Handle,
+    ) -> Self {
         Self {
             session_id,
             draft_tab_id,
+            host_id,
             handle,
         }
     }
@@ -523,6 +615,9 @@ fn scaled_vnc_dimension(logical_size: f32, scale: f32, min: f32, max: f32) -> u1
 impl Portal {
     /// Create new application with initial state
     pub fn new() -> (Self, Task<Message>) {
+        // Clear temp and backup files left by interrupted config writes
+        crate::config::sweep_config_dirs();
+
         // Load hosts from config file
         let hosts_config = match HostsConfig::load() {
             Ok(config) => {
@@ -547,6 +642,49 @@ impl Portal {
             }
         };
 
+        // Load terminal input macros from config file
+        let macros_config = match MacrosConfig::load() {
+            Ok(config) => {
+                tracing::info!("Loaded {} macros from config", config.macros.len());
+                config
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load macros config: {}, using empty config", e);
+                MacrosConfig::default()
+            }
+        };
+
+        // Load SFTP bookmarks, forgetting those of deleted hosts
+        let sftp_bookmarks = match SftpBookmarksConfig::load() {
+            Ok(mut config) => {
+                config.retain_known_hosts(|host_id| hosts_config.find_host(host_id).is_some());
+                tracing::info!("Loaded {} SFTP bookmarks", config.bookmarks.len());
+                config
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load SFTP bookmarks: {}, using empty config", e);
+                SftpBookmarksConfig::default()
+            }
+        };
+
+        // Load local terminal profiles from config file
+        let terminal_profiles = match TerminalProfilesConfig::load() {
+            Ok(config) => {
+                tracing::info!(
+                    "Loaded {} terminal profiles from config",
+                    config.profiles.len()
+                );
+                config
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load terminal profiles config: {}, using empty config",
+                    e
+                );
+                TerminalProfilesConfig::default()
+            }
+        };
+
         // Load history from config file
         let history_config = match HistoryConfig::load() {
             Ok(config) => {
@@ -612,9 +750,17 @@ impl Portal {
             ui: UiState {
                 active_view: View::HostGrid,
                 search_query: String::new(),
+                host_tag_filter: Vec::new(),
                 host_grid_cache: RefCell::new(HostGridCache::default()),
                 command_palette_open: false,
                 command_palette_query: String::new(),
+                quick_switcher_open: false,
+                quick_switcher_query: String::new(),
+                quick_switcher_recent: Vec::new(),
+                snippet_palette_open: false,
+                snippet_palette_query: String::new(),
+                snippet_palette_selected: 0,
+                local_launch_targets: Vec::new(),
                 host_details_sheet: None,
                 sidebar_state: SidebarState::Expanded,
                 sidebar_state_before_session: None,
@@ -628,10 +774,14 @@ impl Portal {
                 tab_focus_index: 0,
                 host_grid_focus_index: None,
                 history_focus_index: None,
+                history_filter: crate::config::HistoryFilter::default(),
                 terminal_captured: false,
                 terminal_focus_token: 0,
+                cursor_blink_visible: true,
                 window_focused: true,
+                keyboard_modifiers: keyboard::Modifiers::default(),
                 tab_context_menu: TabContextMenuState::default(),
+                host_context_menu: HostContextMenuState::default(),
                 portal_hub_status: None,
                 portal_hub_status_error: None,
                 portal_hub_status_loading: false,
@@ -650,39 +800,66 @@ impl Portal {
                 portal_hub_wizard: PortalHubWizardState::default(),
                 hub_prompt_dismissed: false,
                 hovered_host_card: None,
+                host_grid_drag: None,
                 hovered_drop_files: Vec::new(),
+                theme_color_inputs: std::collections::HashMap::new(),
+                keybinding_capture: None,
             },
             tabs: Vec::new(),
             active_tab: None,
             sessions: SessionManager::new(),
             sftp: SftpManager::new(),
             transfers: TransferManager::new(),
+            file_watches: FileWatchManager::new(),
             file_viewers: FileViewerManager::new(),
             proxy_sessions: ProxySessionsState::new(),
+            host_reachability: ReachabilityManager::new(),
             dialogs: DialogManager::new(),
             pending_connect: None,
             pre_session_terminal_output: std::collections::HashMap::new(),
             vnc_sessions: std::collections::HashMap::new(),
+            broadcast_group: std::collections::HashSet::new(),
+            terminal_panes: TerminalPaneManager::new(),
+            session_restore: SessionRestoreManager::new(),
             prefs: PreferencesState {
                 theme_id: settings_config.theme,
+                custom_themes: settings_config.custom_themes.clone(),
                 system_ui_scale,
                 ui_scale_override: settings_config.ui_scale,
                 terminal_font_size: settings_config.terminal_font_size,
                 terminal_scroll_speed: settings_config.terminal_scroll_speed,
+                scrollback_lines: settings_config.scrollback_lines,
                 terminal_font: settings_config.terminal_font,
                 terminal_metric_adjustments: settings_config.terminal_metric_adjustments,
+                terminal_text_style: settings_config.terminal_text_style(),
+                cursor_blink: settings_config.cursor_blink,
+                cursor_blink_interval_ms: settings_config.cursor_blink_interval_ms,
+                terminal_bell: settings_config.terminal_bell,
+                terminal_clipboard: settings_config.terminal_clipboard,
                 sftp_column_widths: settings_config.sftp_column_widths,
+                sftp_show_hidden: settings_config.sftp_show_hidden,
+                sftp_verify_transfers: settings_config.sftp_verify_transfers,
+                sftp_resume_transfers: settings_config.sftp_resume_transfers,
+                viewer_word_wrap: settings_config.viewer_word_wrap,
                 vnc_settings: settings_config.vnc.apply_env_overrides(),
                 portal_hub: settings_config.portal_hub,
                 auto_reconnect: settings_config.auto_reconnect,
+                confirm_quit: settings_config.confirm_quit,
+                restore_sessions: settings_config.restore_sessions,
                 reconnect_max_attempts: settings_config.reconnect_max_attempts,
                 reconnect_base_delay_ms: settings_config.reconnect_base_delay_ms,
                 reconnect_max_delay_ms: settings_config.reconnect_max_delay_ms,
+                connect_timeout: settings_config.connect_timeout,
+                keepalive_interval: settings_config.keepalive_interval,
+                keepalive_count_max: settings_config.keepalive_count_max,
                 allow_agent_forwarding: settings_config.allow_agent_forwarding,
+                host_reachability: settings_config.host_reachability,
                 credential_timeout: settings_config.credential_timeout,
                 session_logging_enabled: settings_config.session_logging_enabled,
                 session_log_dir: settings_config.session_log_dir,
                 session_log_format: settings_config.session_log_format,
+                session_log_input: settings_config.session_log_input,
+                session_log_max_size_mb: settings_config.session_log_max_size_mb,
                 security_audit_enabled: settings_config.security_audit_enabled,
                 security_audit_dir: settings_config.security_audit_dir.clone(),
                 keybindings: settings_config.keybindings.clone(),
@@ -690,6 +867,9 @@ impl Portal {
             config: ConfigState {
                 hosts: hosts_config,
                 snippets: snippets_config,
+                macros: macros_config,
+                sftp_bookmarks,
+                terminal_profiles,
                 history: history_config,
                 snippet_history,
                 vault: vault_config,
@@ -705,9 +885,12 @@ impl Portal {
             },
             vault_ui: VaultUiState::default(),
         };
+        app.terminal_panes
+            .set_custom_themes(&app.prefs.custom_themes);
 
         // Initialize the global passphrase cache with the configured timeout
         services::connection::init_passphrase_cache(settings_config.credential_timeout);
+        services::connection::set_connect_timeout(settings_config.connect_timeout);
 
         // Initialize security audit logging if enabled
         if settings_config.security_audit_enabled {
@@ -739,6 +922,8 @@ impl Portal {
             app.ui.portal_hub_auth_user = Some(format!("Authenticated @ {}", hub_url));
         }
 
+        update::ui::restore::offer_on_startup(&mut app);
+
         // Focus the search input on startup and check for offline local sync changes.
         let mut startup_tasks = vec![iced::widget::operation::focus(search_input_id())];
         if app.ui.portal_hub_auth_user.is_some() && app.prefs.portal_hub.sync_configured() {
@@ -749,7 +934,7 @@ impl Portal {
 
     /// Handle messages - dispatches to specialized handlers
     pub fn update(&mut self, message: Message) -> Task<Message> {
-        match message {
+        let task = match message {
             Message::Session(msg) => update::handle_session(self, msg),
             Message::Sftp(msg) => update::handle_sftp(self, msg),
             Message::FileViewer(msg) => update::handle_file_viewer(self, msg),
@@ -763,12 +948,14 @@ impl Portal {
             Message::Vault(msg) => update::handle_vault(self, msg),
             Message::Ui(msg) => update::handle_ui(self, msg),
             Message::Noop => Task::none(),
-        }
+        };
+        update::ui::restore::save_open_sessions(self);
+        task
     }
 
     /// Build the view
     pub fn view(&self) -> Element<'_, Message> {
-        let theme = get_theme(self.prefs.theme_id);
+        let theme = self.current_theme();
         let fonts = ScaledFonts::new(self.effective_ui_scale());
 
         // Sidebar (new collapsible icon menu)
@@ -790,7 +977,13 @@ impl Portal {
                     current_theme: self.prefs.theme_id,
                     terminal_font_size: self.prefs.terminal_font_size,
                     terminal_scroll_speed: self.prefs.terminal_scroll_speed,
+                    scrollback_lines: self.prefs.scrollback_lines,
                     terminal_font: self.prefs.terminal_font,
+                    terminal_text_style: self.prefs.terminal_text_style,
+                    cursor_blink: self.prefs.cursor_blink,
+                    cursor_blink_interval_ms: self.prefs.cursor_blink_interval_ms,
+                    terminal_bell: self.prefs.terminal_bell,
+                    terminal_clipboard: self.prefs.terminal_clipboard,
                     active_tab: self.ui.settings_tab,
                     snippet_history_enabled: self.config.snippet_history.enabled,
                     snippet_store_command: self.config.snippet_history.store_command,
@@ -798,14 +991,28 @@ impl Portal {
                     snippet_redact_output: self.config.snippet_history.redact_output,
                     vnc_settings: self.prefs.vnc_settings.clone(),
                     auto_reconnect: self.prefs.auto_reconnect,
+                    confirm_quit: self.prefs.confirm_quit,
+                    restore_sessions: self.prefs.restore_sessions,
                     reconnect_max_attempts: self.prefs.reconnect_max_attempts,
                     reconnect_base_delay_ms: self.prefs.reconnect_base_delay_ms,
                     reconnect_max_delay_ms: self.prefs.reconnect_max_delay_ms,
+                    connect_timeout: self.prefs.connect_timeout,
+                    keepalive_interval: self.prefs.keepalive_interval,
+                    keepalive_count_max: self.prefs.keepalive_count_max,
                     allow_agent_forwarding: self.prefs.allow_agent_forwarding,
+                    host_reachability: self.prefs.host_reachability,
+                    sftp_verify_transfers: self.prefs.sftp_verify_transfers,
+                    sftp_resume_transfers: self.prefs.sftp_resume_transfers,
                     ui_scale: self.effective_ui_scale(),
                     system_ui_scale: self.prefs.system_ui_scale,
                     has_ui_scale_override: self.has_ui_scale_override(),
+                    custom_themes: self.prefs.custom_themes.clone(),
+                    theme_color_inputs: self.ui.theme_color_inputs.clone(),
+                    keybindings: self.prefs.keybindings.clone(),
+                    keybinding_capture: self.ui.keybinding_capture,
                     session_logging_enabled: self.prefs.session_logging_enabled,
+                    session_log_input: self.prefs.session_log_input,
+                    history_redact_export: self.config.history.redact_export,
                     portal_hub: self.prefs.portal_hub.clone(),
                     portal_hub_status: self.ui.portal_hub_status.clone(),
                     portal_hub_status_error: self.ui.portal_hub_status_error.clone(),
@@ -884,7 +1091,39 @@ impl Portal {
                         }
                     });
 
-                    let status_message = reconnect_message.or(status_message);
+                    let recording_message = session
+                        .macro_recording
+                        .as_ref()
+                        .map(|steps| format!("Recording macro ({} inputs)", steps.len()));
+                    let status_message = reconnect_message.or(status_message).or(recording_message);
+                    let auth_method = match &session.backend {
+                        SessionBackend::Ssh(ssh) => ssh.auth_method(),
+                        _ => None,
+                    };
+                    let forwards = match &session.backend {
+                        SessionBackend::Ssh(_) => session
+                            .host_id
+                            .and_then(|host_id| self.config.hosts.find_host(host_id))
+                            .filter(|host| !host.port_forwards.is_empty())
+                            .map(|host| ForwardSummary {
+                                active: session.active_forward_count(),
+                                total: host.port_forwards.len(),
+                                socks_port: session.socks_port(&host.port_forwards),
+                            }),
+                        _ => None,
+                    };
+                    let broadcast = self
+                        .broadcast_group
+                        .contains(&session_id)
+                        .then_some(self.broadcast_group.len());
+                    let split_area = self
+                        .active_tab
+                        .and_then(|tab_id| self.terminal_panes.layout(tab_id))
+                        .map(|layout| {
+                            split_terminal_area(theme, &layout.grid, session_id, |pane_id| {
+                                self.split_terminal_pane(pane_id, layout, theme, fonts)
+                            })
+                        });
 
                     terminal_view_with_status(
                         theme,
@@ -893,12 +1132,20 @@ impl Portal {
                         &session.terminal,
                         session.session_start,
                         &session.host_name,
+                        auth_method,
                         status_message,
-                        self.prefs.terminal_font_size,
+                        session.last_command,
+                        forwards,
+                        broadcast,
+                        split_area,
+                        session.font_size.unwrap_or(self.prefs.terminal_font_size),
                         self.prefs.terminal_scroll_speed,
                         self.prefs.terminal_font,
                         self.prefs.terminal_metric_adjustments,
+                        self.prefs.terminal_text_style,
+                        self.ui.cursor_blink_visible,
                         self.prefs.keybindings.clone(),
+                        self.config.macros.keybindings(),
                         self.ui.terminal_focus_token,
                         &session.search,
                         move |_sid, bytes| {
@@ -927,14 +1174,33 @@ impl Portal {
                         .map(|h| (h.id, h.name.clone()))
                         .collect();
                     let transfers = self.transfers.for_tab(*tab_id);
-                    dual_pane_sftp_view(state, available_hosts, transfers, theme, fonts)
+                    let watches = self.file_watches.for_tab(*tab_id);
+                    let bookmarks = [&state.left_pane, &state.right_pane].map(|pane| {
+                        self.sftp.bookmark_host(&pane.source).map(|host_id| {
+                            self.config
+                                .sftp_bookmarks
+                                .for_host(host_id)
+                                .cloned()
+                                .collect()
+                        })
+                    });
+                    dual_pane_sftp_view(
+                        state,
+                        available_hosts,
+                        bookmarks,
+                        transfers,
+                        watches,
+                        self.ui.keyboard_modifiers,
+                        theme,
+                        fonts,
+                    )
                 } else {
                     text("File browser not found").into()
                 }
             }
             View::FileViewer(viewer_id) => {
                 if let Some(state) = self.file_viewers.get(*viewer_id) {
-                    file_viewer_view(state, theme, fonts)
+                    file_viewer_view(state, theme, fonts, self.prefs.viewer_word_wrap)
                 } else {
                     text("File viewer not found").into()
                 }
@@ -1016,11 +1282,16 @@ impl Portal {
                 let mut host_grid_cache = self.ui.host_grid_cache.borrow_mut();
                 let host_grid_cards = host_grid_cache.cards(
                     &self.ui.search_query,
+                    &self.ui.host_tag_filter,
                     &self.config.hosts,
                     &self.prefs.portal_hub,
                 );
 
                 let live_session_counts = self.sessions.session_counts_by_host();
+                let host_reachability = self
+                    .prefs
+                    .host_reachability
+                    .then(|| self.host_reachability.statuses());
                 // Prompt when signed in to the Hub, the Hub default is off,
                 // and eligible hosts still connect directly.
                 let hub_prompt_direct_count = if self.ui.portal_hub_auth_user.is_some()
@@ -1061,18 +1332,23 @@ impl Portal {
                             &self.ui.search_query,
                             &host_grid_cards.groups,
                             &host_grid_cards.hosts,
+                            &host_grid_cards.tags,
+                            &self.ui.host_tag_filter,
                             column_count,
                             theme,
                             fonts,
                             self.ui.focus_section,
                             self.ui.host_grid_focus_index,
                             self.ui.hovered_host_card,
+                            self.ui.host_grid_drag,
                             &live_session_counts,
+                            host_reachability,
                             hub_prompt_direct_count,
                         )
                     }
                     SidebarMenuItem::History => history_view(
                         &self.config.history,
+                        &self.ui.history_filter,
                         &self.config.hosts,
                         theme,
                         fonts,
@@ -1087,13 +1363,17 @@ impl Portal {
                             &self.ui.search_query,
                             &host_grid_cards.groups,
                             &host_grid_cards.hosts,
+                            &host_grid_cards.tags,
+                            &self.ui.host_tag_filter,
                             column_count,
                             theme,
                             fonts,
                             self.ui.focus_section,
                             self.ui.host_grid_focus_index,
                             self.ui.hovered_host_card,
+                            self.ui.host_grid_drag,
                             &live_session_counts,
+                            host_reachability,
                             hub_prompt_direct_count,
                         )
                     }
@@ -1123,6 +1403,7 @@ impl Portal {
             self.ui.tab_focus_index,
             &self.ui.active_view,
             &self.config.hosts,
+            &self.broadcast_group,
         );
 
         // In VNC fullscreen mode, skip sidebar and tab bar
@@ -1246,6 +1527,33 @@ impl Portal {
                 let dialog = vnc_cleartext_dialog_view(cleartext_state, theme, fonts);
                 stack![main_layout, dialog].into()
             }
+            ActiveDialog::QuitConfirm(quit_state) => {
+                let dialog = quit_confirm_dialog_view(quit_state, theme, fonts);
+                stack![main_layout, dialog].into()
+            }
+            ActiveDialog::SnippetParams(params_state) => {
+                let dialog = snippet_params_dialog_view(params_state, theme, fonts);
+                stack![main_layout, dialog].into()
+            }
+            ActiveDialog::PortForwards(forwards_state) => {
+                let session = self.sessions.get(forwards_state.session_id);
+                let host_id = session.and_then(|session| session.host_id);
+                let forwards = host_id
+                    .and_then(|host_id| self.config.hosts.find_host(host_id))
+                    .map(|host| host.port_forwards.as_slice())
+                    .unwrap_or_default();
+                let empty_status = std::collections::HashMap::new();
+                let status = session.map_or(&empty_status, |session| &session.forward_status);
+                let dialog = port_forwards_dialog_view(
+                    forwards_state,
+                    host_id,
+                    forwards,
+                    status,
+                    theme,
+                    fonts,
+                );
+                stack![main_layout, dialog].into()
+            }
             ActiveDialog::None => main_layout,
         };
 
@@ -1290,9 +1598,18 @@ impl Portal {
         let with_context_menu: Element<'_, Message> = if let Some(tab_id) = self.active_tab {
             if let Some(sftp_state) = self.sftp.get_tab(tab_id) {
                 if sftp_state.context_menu.visible {
+                    let watching = sftp_state
+                        .watch_candidate(sftp_state.context_menu.target_pane)
+                        .is_some_and(|entry| self.file_watches.find(tab_id, &entry.path).is_some());
                     stack![
                         with_actions_dismiss,
-                        sftp_context_menu_overlay(sftp_state, theme, fonts, self.ui.window_size)
+                        sftp_context_menu_overlay(
+                            sftp_state,
+                            watching,
+                            theme,
+                            fonts,
+                            self.ui.window_size
+                        )
                     ]
                     .into()
                 } else {
@@ -1315,6 +1632,12 @@ impl Portal {
             (false, false)
         };
 
+        let in_broadcast = self
+            .ui
+            .tab_context_menu
+            .target_tab
+            .is_some_and(|tab_id| self.broadcast_group.contains(&tab_id));
+
         let with_tab_context_menu: Element<'_, Message> = if self.ui.tab_context_menu.visible {
             stack![
                 with_context_menu,
@@ -1324,7 +1647,8 @@ impl Portal {
                     fonts,
                     self.ui.window_size,
                     has_log_file,
-                    has_log_dir
+                    has_log_dir,
+                    in_broadcast
                 )
             ]
             .into()
@@ -1332,6 +1656,21 @@ impl Portal {
             with_context_menu
         };
 
+        let with_host_context_menu: Element<'_, Message> = if self.ui.host_context_menu.visible {
+            stack![
+                with_tab_context_menu,
+                host_context_menu_overlay(
+                    &self.ui.host_context_menu,
+                    theme,
+                    fonts,
+                    self.ui.window_size
+                )
+            ]
+            .into()
+        } else {
+            with_tab_context_menu
+        };
+
         let with_host_sheet: Element<'_, Message> =
             if let Some(host_id) = self.ui.host_details_sheet {
                 if let Some(host) = self.config.hosts.find_host(host_id) {
@@ -1344,15 +1683,15 @@ impl Portal {
                             .map(|group| group.name.as_str())
                     });
                     stack![
-                        with_tab_context_menu,
+                        with_host_context_menu,
                         host_details_sheet_view(host, group_name, theme, fonts)
                     ]
                     .into()
                 } else {
-                    with_tab_context_menu
+                    with_host_context_menu
                 }
             } else {
-                with_tab_context_menu
+                with_host_context_menu
             };
 
         let with_dropzone: Element<'_, Message> = if !self.ui.hovered_drop_files.is_empty()
@@ -1371,6 +1710,10 @@ impl Portal {
             let commands = available_commands(
                 &self.config.hosts,
                 &self.config.snippets,
+                self.active_session_host(),
+                &self.config.macros,
+                &self.config.terminal_profiles,
+                &self.ui.local_launch_targets,
                 self.prefs.portal_hub.sync_configured(),
             );
             stack![
@@ -1382,15 +1725,58 @@ impl Portal {
             with_dropzone
         };
 
+        let with_quick_switcher: Element<'_, Message> = if self.ui.quick_switcher_open {
+            let items = available_items(
+                &self.tabs,
+                &self.config.hosts,
+                &self.config.snippets,
+                self.active_session_host(),
+            );
+            stack![
+                with_command_palette,
+                quick_switcher_view(
+                    &self.ui.quick_switcher_query,
+                    &items,
+                    &self.ui.quick_switcher_recent,
+                    theme,
+                    fonts
+                )
+            ]
+            .into()
+        } else {
+            with_command_palette
+        };
+
+        let with_snippet_palette: Element<'_, Message> = if self.ui.snippet_palette_open {
+            let items = crate::views::snippet_palette::available_items(
+                &self.config.snippets,
+                self.active_session_host(),
+                &self.config.snippet_history,
+            );
+            stack![
+                with_quick_switcher,
+                snippet_palette_view(
+                    &self.ui.snippet_palette_query,
+                    &items,
+                    self.ui.snippet_palette_selected,
+                    theme,
+                    fonts
+                )
+            ]
+            .into()
+        } else {
+            with_quick_switcher
+        };
+
         // Overlay toast notifications on top of everything
         let final_content = if self.toast_manager.has_toasts() {
             stack![
-                with_command_palette,
+                with_snippet_palette,
                 toast_overlay_view(&self.toast_manager, theme, fonts)
             ]
             .into()
         } else {
-            with_command_palette
+            with_snippet_palette
         };
 
         // Wrap everything in a container with our background color
@@ -1404,10 +1790,76 @@ impl Portal {
             .into()
     }
 
+    /// One pane of a split terminal tab
+    fn split_terminal_pane<'a>(
+        &'a self,
+        session_id: SessionId,
+        layout: &TerminalPaneLayout,
+        theme: Theme,
+        fonts: ScaledFonts,
+    ) -> SplitPane<'a> {
+        let Some(session) = self.sessions.get(session_id) else {
+            return SplitPane {
+                body: text("Session not found").into(),
+                title_bar: pane_grid::TitleBar::new(text("")),
+                background: theme.terminal.background,
+            };
+        };
+        let prefs = layout.view_prefs(session_id);
+        let font_size = prefs
+            .font_size
+            .or(session.font_size)
+            .unwrap_or(self.prefs.terminal_font_size);
+        let pane_theme = Theme {
+            terminal: prefs.colors.unwrap_or(theme.terminal),
+            ..theme
+        };
+        let body = terminal_pane(
+            pane_theme,
+            fonts,
+            session_id,
+            &session.terminal,
+            font_size,
+            self.prefs.terminal_scroll_speed,
+            self.prefs.terminal_font,
+            self.prefs.terminal_metric_adjustments,
+            self.prefs.terminal_text_style,
+            self.ui.cursor_blink_visible,
+            self.prefs.keybindings.clone(),
+            self.config.macros.keybindings(),
+            layout.focus_token(session_id, self.ui.terminal_focus_token),
+            session_id == layout.focused(),
+            &session.search,
+            |id, bytes| Message::Session(SessionMessage::Input(id, bytes)),
+            |id, cols, rows| Message::Session(SessionMessage::Resize(id, cols, rows)),
+            |id| Message::Session(SessionMessage::Paste(id)),
+            |id, link| Message::Session(SessionMessage::OpenLink(id, link)),
+        );
+        let title_bar = split_pane_title_bar(
+            theme,
+            fonts,
+            session_id,
+            &session.host_name,
+            font_size,
+            prefs,
+            self.terminal_panes.color_schemes(),
+        );
+        SplitPane {
+            body,
+            title_bar,
+            background: pane_theme.terminal.background,
+        }
+    }
+
+    /// Colors of the selected theme, built-in or custom
+    pub fn current_theme(&self) -> Theme {
+        theme_for(self.prefs.theme_id, &self.prefs.custom_themes)
+    }
+
     /// Theme based on theme_id preference
     pub fn theme(&self) -> IcedTheme {
-        let theme = get_theme(self.prefs.theme_id);
-        if self.prefs.theme_id.is_dark() {
+        let theme = self.current_theme();
+        if theme.is_dark() {
             let palette = iced::theme::Palette {
                 background: theme.background,
                 text: theme.text_primary,
@@ -1447,9 +1899,14 @@ impl Portal {
     /// emitted its first exact resize. Use the same font metrics as the widget
     /// and the post-connection layout, where terminal sessions hide the sidebar.
     pub fn terminal_initial_size(&self) -> (u16, u16) {
+        self.terminal_size_for_font(self.prefs.terminal_font_size)
+    }
+
+    /// Best-effort grid size of a full-window terminal at `font_size`
+    pub fn terminal_size_for_font(&self, font_size: f32) -> (u16, u16) {
         let metrics = TerminalMetrics::for_font_with_adjustments(
             self.prefs.terminal_font,
-            self.prefs.terminal_font_size,
+            font_size,
             self.prefs.terminal_metric_adjustments,
         );
         let fonts = ScaledFonts::new(self.effective_ui_scale());
@@ -1487,21 +1944,38 @@ impl Portal {
         let mut settings = SettingsConfig::default();
         settings.terminal_font_size = self.prefs.terminal_font_size;
         settings.terminal_scroll_speed = self.prefs.terminal_scroll_speed;
+        settings.scrollback_lines = self.prefs.scrollback_lines;
         settings.terminal_font = self.prefs.terminal_font;
         settings.terminal_metric_adjustments = self.prefs.terminal_metric_adjustments;
+        settings.set_terminal_text_style(self.prefs.terminal_text_style);
+        settings.cursor_blink = self.prefs.cursor_blink;
+        settings.cursor_blink_interval_ms = self.prefs.cursor_blink_interval_ms;
+        settings.terminal_bell = self.prefs.terminal_bell;
+        settings.terminal_clipboard = self.prefs.terminal_clipboard;
         settings.theme = self.prefs.theme_id;
+        settings.custom_themes = self.prefs.custom_themes.clone();
         settings.ui_scale = self.prefs.ui_scale_override;
         settings.vnc = self.prefs.vnc_settings.clone();
         settings.portal_hub = self.prefs.portal_hub.clone();
         settings.auto_reconnect = self.prefs.auto_reconnect;
+        settings.confirm_quit = self.prefs.confirm_quit;
+        settings.restore_sessions = self.prefs.restore_sessions;
         settings.reconnect_max_attempts = self.prefs.reconnect_max_attempts;
         settings.reconnect_base_delay_ms = self.prefs.reconnect_base_delay_ms;
         settings.reconnect_max_delay_ms = self.prefs.reconnect_max_delay_ms;
+        settings.connect_timeout = self.prefs.connect_timeout;
+        settings.keepalive_interval = self.prefs.keepalive_interval;
+        settings.keepalive_count_max = self.prefs.keepalive_count_max;
         settings.allow_agent_forwarding = self.prefs.allow_agent_forwarding;
+        settings.host_reachability = self.prefs.host_reachability;
+        settings.sftp_verify_transfers = self.prefs.sftp_verify_transfers;
+        settings.sftp_resume_transfers = self.prefs.sftp_resume_transfers;
         settings.credential_timeout = self.prefs.credential_timeout;
         settings.session_logging_enabled = self.prefs.session_logging_enabled;
         settings.session_log_dir = self.prefs.session_log_dir.clone();
         settings.session_log_format = self.prefs.session_log_format;
+        settings.session_log_input = self.prefs.session_log_input;
+        settings.session_log_max_size_mb = self.prefs.session_log_max_size_mb;
         settings.security_audit_enabled = self.prefs.security_audit_enabled;
         settings.security_audit_dir = self.prefs.security_audit_dir.clone();
         settings.keybindings = self.prefs.keybindings.clone();
@@ -1523,6 +1997,8 @@ impl Portal {
             event::listen_with(|event, status, _id| subscription_message(event, status)),
             // Window resize events
             window::resize_events().map(|(_id, size)| Message::Ui(UiMessage::WindowResized(size))),
+            // Close requests are confirmed when sessions are still running
+            window::close_requests().map(|_id| Message::Ui(UiMessage::WindowCloseRequested)),
         ];
 
         // Toast tick timer (only when toasts are visible)
@@ -1541,6 +2017,27 @@ impl Portal {
             );
         }
 
+        // Text copied locally reaches the server without a paste shortcut.
+        // iced has no clipboard change events, so poll while focused.
+        if matches!(self.ui.active_view, View::VncViewer(_))
+            && self.ui.window_focused
+            && self.prefs.vnc_settings.clipboard_sharing
+            && self.prefs.vnc_settings.clipboard_auto_send
+        {
+            subscriptions.push(
+                time::every(Duration::from_secs(1))
+                    .map(|_| Message::Vnc(VncMessage::ClipboardPollTick)),
+            );
+        }
+
+        // Host reachability probes (only while the host grid is on screen)
+        if matches!(self.ui.active_view, View::HostGrid) && self.prefs.host_reachability {
+            subscriptions.push(
+                time::every(Duration::from_secs(5))
+                    .map(|_| Message::Host(HostMessage::ReachabilityTick)),
+            );
+        }
+
         // Session duration tick (only when viewing a terminal)
         if matches!(self.ui.active_view, View::Terminal(_)) && !self.sessions.is_empty() {
             subscriptions.push(
@@ -1549,6 +2046,32 @@ impl Portal {
             );
         }
 
+        // Cursor blink only runs while a focused window shows a terminal.
+        if self.prefs.cursor_blink
+            && self.ui.window_focused
+            && matches!(self.ui.active_view, View::Terminal(_))
+        {
+            subscriptions.push(
+                time::every(Duration::from_millis(self.prefs.cursor_blink_interval_ms))
+                    .map(|_| Message::Ui(UiMessage::CursorBlinkTick)),
+            );
+        }
+
+        // Expired credentials are dropped from memory, not just ignored.
+        if services::connection::has_cached_credentials() {
+            subscriptions.push(
+                time::every(Duration::from_secs(15))
+                    .map(|_| Message::Ui(UiMessage::CredentialCacheTick)),
+            );
+        }
+
+        if self.sessions.has_bell_flash() {
+            subscriptions.push(
+                time::every(Duration::from_millis(50))
+                    .map(|_| Message::Ui(UiMessage::BellFlashTick)),
+            );
+        }
+
         if self.sessions.has_pending_output() {
             subscriptions.push(
                 time::every(Duration::from_millis(16))
@@ -1574,6 +2097,13 @@ impl Portal {
             );
         }
 
+        if self.session_restore.is_restoring() {
+            subscriptions.push(
+                time::every(Duration::from_millis(500))
+                    .map(|_| Message::Ui(UiMessage::SessionRestoreTick)),
+            );
+        }
+
         if self.active_delete_hold_tab().is_some() {
             subscriptions.push(
                 time::every(Duration::from_millis(16))
@@ -1604,6 +2134,15 @@ impl Portal {
         Subscription::batch(subscriptions)
     }
 
+    /// Saved host of the terminal session being viewed
+    fn active_session_host(&self) -> Option<&Host> {
+        let View::Terminal(session_id) = self.ui.active_view else {
+            return None;
+        };
+        let host_id = self.sessions.get(session_id)?.host_id?;
+        self.config.hosts.find_host(host_id)
+    }
+
     fn active_delete_hold_tab(&self) -> Option<SessionId> {
         self.sftp
             .tab_values()
@@ -1648,6 +2187,9 @@ fn subscription_message(event: iced::Event, status: event::Status) -> Option<Mes
         iced::Event::Keyboard(keyboard::Event::KeyReleased { key, modifiers, .. }) => {
             Some(Message::Ui(UiMessage::KeyReleased(key, modifiers)))
         }
+        iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+            Some(Message::Ui(UiMessage::ModifiersChanged(modifiers)))
+        }
         iced::Event::Window(window::Event::Unfocused) => {
             Some(Message::Ui(UiMessage::WindowUnfocused))
         }
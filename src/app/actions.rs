@@ -6,33 +6,54 @@ use std::time::{Duration, Instant};
 
 use futures::stream;
 use iced::Task;
+use iced::widget::pane_grid;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-use crate::config::{AuthMethod, Host};
+use crate::config::Host;
+use crate::config::settings::{TERMINAL_FONT_SIZE_MAX, TERMINAL_FONT_SIZE_MIN};
 #[cfg(unix)]
 use crate::fs_utils::set_private_dir_permissions_no_follow;
 use crate::fs_utils::{
     cleanup_temp_dir, copy_dir_recursive, copy_regular_file, count_items_in_dir, sync_parent_dir,
 };
 use crate::keybindings::AppAction;
-use crate::local::{LocalEvent, LocalSession};
+use crate::local::{LocalEvent, LocalSession, LocalSpawnSpec};
 use crate::local_fs::list_local_dir;
-use crate::message::{Message, SessionId, SessionMessage, SftpMessage, VncMessage};
-use crate::sftp::{SharedSftpSession, is_safe_sftp_entry_name};
+use crate::message::{
+    FileViewerMessage, Message, SessionId, SessionMessage, SftpMessage, UiMessage, VncMessage,
+};
+use crate::sftp::chmod::{
+    ChmodProgress, Ownership, RecursiveMode, TreeChange, owner_change_error, walk_tree,
+};
+use crate::sftp::dir_size::walk_dir_size;
+use crate::sftp::properties::{local_properties, needs_account_lookup, resolve_ownership};
+use crate::sftp::sync::{self, SyncItem};
+use crate::sftp::{
+    DirSize, FileEntry, SharedSftpSession, SudoCommand, SyncOptions, SyncPlan, TransferResume,
+    format_size, is_safe_sftp_entry_name, sudo,
+};
+use crate::ssh::reconnect::ReconnectPolicy;
 use crate::views::dialogs::password_dialog::PasswordDialogState;
-use crate::views::file_viewer::{FileSource, FileType};
+use crate::views::file_viewer::{
+    FileSource, FileType, FileViewerState, MAX_DIFF_BYTES, ViewerContent, diff_text, side_by_side,
+};
 use crate::views::sftp::{ContextMenuAction, PaneId, PaneSource, PermissionBits, SftpDialogType};
 use crate::views::tabs::{Tab, TabType};
-use crate::views::toast::Toast;
+use crate::views::toast::{Toast, ToastAction};
 
 use super::managers::{
-    SessionBackend, TransferDirection, TransferItem, TransferItemInit, TransferProgress,
+    EditedCopy, FileWatch, SessionBackend, TransferDirection, TransferItem, TransferItemInit,
+    TransferProgress,
 };
-use super::services::{connection, file_viewer, history};
+use super::services::{connection, file_viewer, file_watch, history, open_with};
 use super::{FocusSection, Portal, View};
 
 const TRANSFER_PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+/// Largest file "Copy Contents" / "Copy as Base64" will put on the clipboard
+const MAX_CLIPBOARD_COPY_BYTES: u64 = 1024 * 1024;
+/// Font size change per zoom shortcut, in pixels
+const TERMINAL_ZOOM_STEP: f32 = 1.0;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ConnectionLaunchMode {
@@ -63,6 +84,15 @@ struct SftpTransferRequest {
     source: SftpTransferEndpoint,
     target: SftpTransferEndpoint,
     entries: Vec<SftpTransferEntry>,
+    /// Target paths removed after all entries were copied (sync mode)
+    deletions: Vec<SftpTransferEntry>,
+    /// Compare SHA-256 checksums of each copied file with its source
+    verify: bool,
+    /// Whether single files continue from a partial copy left by an
+    /// interrupted attempt
+    resume: TransferResume,
+    /// Backoff for retrying failed files (auto-reconnect enabled)
+    retry: Option<ReconnectPolicy>,
 }
 
 impl SftpTransferRequest {
@@ -94,16 +124,229 @@ impl SftpTransferRequest {
     }
 
     fn label(&self) -> String {
-        match self.entries.as_slice() {
-            [entry] => entry.name.clone(),
-            entries => format!("{} items", entries.len()),
+        match (self.entries.as_slice(), self.deletions.len()) {
+            ([entry], 0) => entry.name.clone(),
+            (entries, 0) => format!("{} items", entries.len()),
+            (entries, deletions) => {
+                format!("{} items, {} removals", entries.len(), deletions)
+            }
         }
     }
 }
 
+/// Describe a file dropped from the OS as a transfer source
+fn dropped_transfer_entry(path: &std::path::Path) -> Result<SftpTransferEntry, String> {
+    let metadata = std::fs::symlink_metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if metadata.file_type().is_symlink() {
+        return Err(format!("Cannot upload symbolic link {}", path.display()));
+    }
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Cannot upload unnamed path {}", path.display()))?
+        .to_string_lossy()
+        .into_owned();
+    Ok(SftpTransferEntry {
+        name,
+        path: path.to_path_buf(),
+        is_dir: metadata.is_dir(),
+        is_symlink: false,
+        size: metadata.len(),
+    })
+}
+
+/// Walk a folder in the background, reporting the running total at most every
+/// `TRANSFER_PROGRESS_EMIT_INTERVAL` and then the final result
+fn sftp_dir_size_task(
+    tab_id: SessionId,
+    pane_id: PaneId,
+    walk_id: Uuid,
+    path: std::path::PathBuf,
+    sftp: Option<SharedSftpSession>,
+    cancel_requested: Arc<AtomicBool>,
+) -> Task<Message> {
+    Task::run(
+        async_stream::stream! {
+            let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<DirSize>();
+            let root = path.clone();
+            let mut walk = tokio::spawn(async move {
+                let on_progress = move |size| {
+                    let _ = progress_tx.send(size);
+                };
+                let is_cancelled = move || cancel_requested.load(Ordering::Relaxed);
+                match sftp {
+                    Some(sftp) => sftp
+                        .dir_size(&root, on_progress, is_cancelled)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    None => {
+                        walk_dir_size(
+                            &root,
+                            |dir| async move { list_local_dir(&dir).await },
+                            on_progress,
+                            is_cancelled,
+                        )
+                        .await
+                    }
+                }
+            });
+
+            let mut progress_open = true;
+            let mut last_progress_emit = Instant::now();
+            let result = loop {
+                tokio::select! {
+                    progress = progress_rx.recv(), if progress_open => {
+                        if let Some(size) = progress {
+                            let now = Instant::now();
+                            if now.duration_since(last_progress_emit) >= TRANSFER_PROGRESS_EMIT_INTERVAL {
+                                last_progress_emit = now;
+                                yield Message::Sftp(SftpMessage::DirSizeUpdated {
+                                    tab_id,
+                                    pane_id,
+                                    walk_id,
+                                    path: path.clone(),
+                                    result: Ok(size),
+                                });
+                            }
+                        } else {
+                            progress_open = false;
+                        }
+                    }
+                    result = &mut walk => {
+                        break match result {
+                            Ok(walk_result) => walk_result,
+                            Err(error) => Err(error.to_string()),
+                        };
+                    }
+                }
+            };
+
+            yield Message::Sftp(SftpMessage::DirSizeUpdated {
+                tab_id,
+                pane_id,
+                walk_id,
+                path,
+                result,
+            });
+        },
+        |message| message,
+    )
+}
+
+/// Recursive change as submitted from a dialog; typed owner and group are
+/// resolved to ids inside the background task
+#[derive(Debug, Clone)]
+enum TreeChangeRequest {
+    Mode(RecursiveMode),
+    Owner { owner: String, group: String },
+}
+
+/// Change permissions or ownership on a folder tree in the background,
+/// reporting the running count at most every
+/// `TRANSFER_PROGRESS_EMIT_INTERVAL` and then the final report
+fn sftp_chmod_task(
+    tab_id: SessionId,
+    pane_id: PaneId,
+    walk_id: Uuid,
+    path: std::path::PathBuf,
+    request: TreeChangeRequest,
+    sftp: Option<SharedSftpSession>,
+    cancel_requested: Arc<AtomicBool>,
+) -> Task<Message> {
+    Task::run(
+        async_stream::stream! {
+            let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<ChmodProgress>();
+            let mut walk = tokio::spawn(async move {
+                let change = match request {
+                    TreeChangeRequest::Mode(mode) => TreeChange::Mode(mode),
+                    TreeChangeRequest::Owner { owner, group } => TreeChange::Owner(match &sftp {
+                        Some(sftp) => sftp
+                            .resolve_ownership(&owner, &group)
+                            .await
+                            .map_err(|e| e.to_string())?,
+                        None => tokio::task::spawn_blocking(move || {
+                            resolve_local_ownership(&owner, &group)
+                        })
+                        .await
+                        .map_err(|e| e.to_string())??,
+                    }),
+                };
+                let on_progress = move |progress| {
+                    let _ = progress_tx.send(progress);
+                };
+                let is_cancelled = move || cancel_requested.load(Ordering::Relaxed);
+                match sftp {
+                    Some(sftp) => sftp
+                        .apply_recursive(&path, change, on_progress, is_cancelled)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    None => {
+                        walk_tree(
+                            &path,
+                            |dir| async move { list_local_dir(&dir).await },
+                            |entry, is_dir| async move {
+                                tokio::task::spawn_blocking(move || match change {
+                                    TreeChange::Mode(mode) => {
+                                        set_local_permissions(&entry, mode.for_entry(is_dir))
+                                    }
+                                    TreeChange::Owner(ownership) => {
+                                        set_local_owner(&entry, ownership)
+                                    }
+                                })
+                                .await
+                                .map_err(|e| e.to_string())?
+                            },
+                            on_progress,
+                            is_cancelled,
+                        )
+                        .await
+                    }
+                }
+            });
+
+            let mut progress_open = true;
+            let mut last_progress_emit = Instant::now();
+            let result = loop {
+                tokio::select! {
+                    progress = progress_rx.recv(), if progress_open => {
+                        if let Some(progress) = progress {
+                            let now = Instant::now();
+                            if now.duration_since(last_progress_emit) >= TRANSFER_PROGRESS_EMIT_INTERVAL {
+                                last_progress_emit = now;
+                                yield Message::Sftp(SftpMessage::PermissionsProgress {
+                                    tab_id,
+                                    walk_id,
+                                    progress,
+                                });
+                            }
+                        } else {
+                            progress_open = false;
+                        }
+                    }
+                    result = &mut walk => {
+                        break match result {
+                            Ok(walk_result) => walk_result,
+                            Err(error) => Err(error.to_string()),
+                        };
+                    }
+                }
+            };
+
+            yield Message::Sftp(SftpMessage::PermissionsRecursiveResult {
+                tab_id,
+                pane_id,
+                walk_id,
+                result,
+            });
+        },
+        |message| message,
+    )
+}
+
 fn sftp_transfer_task(
     transfer_id: Uuid,
     request: SftpTransferRequest,
+    sudo_password: Option<secrecy::SecretString>,
     cancel_requested: Arc<AtomicBool>,
 ) -> Task<Message> {
     let total_files = request.entries.len();
@@ -130,6 +373,7 @@ fn sftp_transfer_task(
                             transfer_id,
                             tab_id,
                             target_pane_id,
+                            verified: false,
                             result: Err(error),
                         });
                         return;
@@ -164,62 +408,157 @@ fn sftp_transfer_task(
                 }));
 
                 let target_path = request.target_dir.join(&entry.name);
-                let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<u64>();
-                let source = request.source.clone();
-                let target = request.target.clone();
-                let temp_dir_for_task = temp_dir.clone();
-                let entry_for_task = entry.clone();
-                let target_path_for_task = target_path.clone();
-                let cancel_for_task = cancel_requested.clone();
-                let mut item_task = tokio::spawn(async move {
-                    transfer_one_sftp_entry(
-                        source,
-                        target,
-                        temp_dir_for_task,
-                        entry_for_task,
-                        target_path_for_task,
-                        cancel_for_task,
-                        move |bytes| {
-                            let _ = progress_tx.send(bytes);
-                        },
-                    )
-                    .await
-                });
-
-                let mut progress_open = true;
-                let mut last_progress_emit = Instant::now()
-                    .checked_sub(TRANSFER_PROGRESS_EMIT_INTERVAL)
-                    .unwrap_or_else(Instant::now);
+                let resume = if request.direction() == TransferDirection::LocalToLocal {
+                    TransferResume::Off
+                } else {
+                    request.resume
+                };
+                let retry = request.retry.filter(|_| resume.is_enabled());
+                let mut attempt = 0u32;
                 let item_result = loop {
-                    tokio::select! {
-                        progress = progress_rx.recv(), if progress_open => {
-                            if let Some(item_bytes) = progress {
-                                let item_bytes = item_bytes.min(entry.size);
-                                let now = Instant::now();
-                                if now.duration_since(last_progress_emit) >= TRANSFER_PROGRESS_EMIT_INTERVAL
-                                    || item_bytes >= entry.size
-                                {
-                                    last_progress_emit = now;
-                                    yield Message::Sftp(SftpMessage::TransferProgress(TransferProgress {
-                                        transfer_id,
-                                        current_item: Some(entry.name.clone()),
-                                        completed_files,
-                                        total_files,
-                                        completed_bytes: completed_bytes.saturating_add(item_bytes),
-                                        total_bytes,
-                                    }));
+                    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<u64>();
+                    let source = request.source.clone();
+                    let target = request.target.clone();
+                    let temp_dir_for_task = temp_dir.clone();
+                    let entry_for_task = entry.clone();
+                    let target_path_for_task = target_path.clone();
+                    let cancel_for_task = cancel_requested.clone();
+                    let mut item_task = tokio::spawn(async move {
+                        transfer_one_sftp_entry(
+                            source,
+                            target,
+                            temp_dir_for_task,
+                            entry_for_task,
+                            target_path_for_task,
+                            resume,
+                            cancel_for_task,
+                            move |bytes| {
+                                let _ = progress_tx.send(bytes);
+                            },
+                        )
+                        .await
+                    });
+
+                    let mut progress_open = true;
+                    let mut last_progress_emit = Instant::now()
+                        .checked_sub(TRANSFER_PROGRESS_EMIT_INTERVAL)
+                        .unwrap_or_else(Instant::now);
+                    let attempt_result = loop {
+                        tokio::select! {
+                            progress = progress_rx.recv(), if progress_open => {
+                                if let Some(item_bytes) = progress {
+                                    let item_bytes = item_bytes.min(entry.size);
+                                    let now = Instant::now();
+                                    if now.duration_since(last_progress_emit) >= TRANSFER_PROGRESS_EMIT_INTERVAL
+                                        || item_bytes >= entry.size
+                                    {
+                                        last_progress_emit = now;
+                                        yield Message::Sftp(SftpMessage::TransferProgress(TransferProgress {
+                                            transfer_id,
+                                            current_item: Some(entry.name.clone()),
+                                            completed_files,
+                                            total_files,
+                                            completed_bytes: completed_bytes.saturating_add(item_bytes),
+                                            total_bytes,
+                                        }));
+                                    }
+                                } else {
+                                    progress_open = false;
                                 }
-                            } else {
-                                progress_open = false;
+                            }
+                            result = &mut item_task => {
+                                break match result {
+                                    Ok(item_result) => item_result,
+                                    Err(error) => Err(error.to_string()),
+                                };
                             }
                         }
-                        result = &mut item_task => {
-                            break match result {
-                                Ok(item_result) => item_result,
-                                Err(error) => Err(error.to_string()),
-                            };
-                        }
+                    };
+
+                    let retry_delay = match &attempt_result {
+                        Err(error) => transfer_retry_delay(retry, &entry, error, attempt),
+                        Ok(_) => None,
+                    };
+                    let Some(delay) = retry_delay else {
+                        break attempt_result.map_err(|error| with_resume_hint(resume, &entry, error));
+                    };
+                    let error = attempt_result.err().unwrap_or_default();
+                    attempt += 1;
+                    tracing::warn!(
+                        "Transfer of {} failed ({}), retrying in {:?}",
+                        entry.name,
+                        error,
+                        delay
+                    );
+                    yield Message::Sftp(SftpMessage::TransferProgress(TransferProgress {
+                        transfer_id,
+                        current_item: Some(format!("Retrying {} ({})", entry.name, attempt)),
+                        completed_files,
+                        total_files,
+                        completed_bytes,
+                        total_bytes,
+                    }));
+                    let retry_at = Instant::now() + delay;
+                    while Instant::now() < retry_at && !cancel_requested.load(Ordering::Relaxed) {
+                        tokio::time::sleep(Duration::from_millis(250)).await;
+                    }
+                    if cancel_requested.load(Ordering::Relaxed) {
+                        break Err("Transfer cancelled".to_string());
+                    }
+                    if let Err(reopen_error) = reopen_transfer_endpoints(&request).await {
+                        tracing::warn!("Cannot retry transfer of {}: {}", entry.name, reopen_error);
+                        break Err(with_resume_hint(resume, &entry, error));
+                    }
+                };
+
+                // A refused upload of a single file is copied into place
+                // through sudo when the target connection has a cached password
+                let item_result = match (
+                    item_result,
+                    &request.source,
+                    &request.target,
+                    &sudo_password,
+                ) {
+                    (
+                        Err(error),
+                        SftpTransferEndpoint::Local,
+                        SftpTransferEndpoint::Remote(target_sftp),
+                        Some(password),
+                    ) if !entry.is_dir && sudo::is_permission_denied(&error) => {
+                        tracing::debug!(
+                            "Retrying upload of {} with cached sudo password",
+                            entry.name
+                        );
+                        target_sftp
+                            .upload_with_sudo(&entry.path, &target_path, password)
+                            .await
+                            .map(|_| 1)
+                            .map_err(|error| error.to_string())
+                    }
+                    (other, ..) => other,
+                };
+
+                let item_result = match item_result {
+                    Ok(count) if request.verify => {
+                        yield Message::Sftp(SftpMessage::TransferProgress(TransferProgress {
+                            transfer_id,
+                            current_item: Some(format!("Verifying {}", entry.name)),
+                            completed_files,
+                            total_files,
+                            completed_bytes,
+                            total_bytes,
+                        }));
+                        verify_sftp_transfer(
+                            &request.source,
+                            &request.target,
+                            &entry,
+                            &target_path,
+                            &cancel_requested,
+                        )
+                        .await
+                        .map(|()| count)
                     }
+                    other => other,
                 };
 
                 match item_result {
@@ -245,6 +584,29 @@ fn sftp_transfer_task(
                 }
             }
 
+            if result.is_ok() {
+                for entry in request.deletions.iter() {
+                    if cancel_requested.load(Ordering::Relaxed) {
+                        result = Err("Transfer cancelled".to_string());
+                        break;
+                    }
+                    yield Message::Sftp(SftpMessage::TransferProgress(TransferProgress {
+                        transfer_id,
+                        current_item: Some(format!("Removing {}", entry.name)),
+                        completed_files,
+                        total_files,
+                        completed_bytes,
+                        total_bytes,
+                    }));
+                    if let Err(error) =
+                        remove_sftp_transfer_target(&request.target, entry.path.clone()).await
+                    {
+                        result = Err(error);
+                        break;
+                    }
+                }
+            }
+
             if let Some(temp_dir) = temp_dir {
                 let _ = cleanup_temp_dir(&temp_dir).await;
             }
@@ -257,6 +619,7 @@ fn sftp_transfer_task(
                 transfer_id,
                 tab_id,
                 target_pane_id,
+                verified: request.verify,
                 result,
             });
         },
@@ -270,6 +633,7 @@ async fn transfer_one_sftp_entry<P>(
     temp_dir: Option<std::path::PathBuf>,
     entry: SftpTransferEntry,
     target_path: std::path::PathBuf,
+    resume: TransferResume,
     cancel_requested: Arc<AtomicBool>,
     mut on_progress: P,
 ) -> Result<usize, String>
@@ -305,9 +669,13 @@ where
                     .map_err(|error| error.to_string())
             } else {
                 target_sftp
-                    .upload_with_progress(&entry.path, &target_path, &mut on_progress, || {
-                        cancel_requested.load(Ordering::Relaxed)
-                    })
+                    .upload_with_progress(
+                        &entry.path,
+                        &target_path,
+                        resume,
+                        &mut on_progress,
+                        || cancel_requested.load(Ordering::Relaxed),
+                    )
                     .await
                     .map_err(|error| error.to_string())
                     .map(|_| 1)
@@ -321,9 +689,13 @@ where
                     .map_err(|error| error.to_string())
             } else {
                 source_sftp
-                    .download_with_progress(&entry.path, &target_path, &mut on_progress, || {
-                        cancel_requested.load(Ordering::Relaxed)
-                    })
+                    .download_with_progress(
+                        &entry.path,
+                        &target_path,
+                        resume,
+                        &mut on_progress,
+                        || cancel_requested.load(Ordering::Relaxed),
+                    )
                     .await
                     .map_err(|error| error.to_string())
                     .map(|_| 1)
@@ -348,6 +720,7 @@ where
                     .download_with_progress(
                         &entry.path,
                         &temp_path,
+                        resume,
                         |bytes| on_progress(bytes.min(entry.size) / 2),
                         || cancel_requested.load(Ordering::Relaxed),
                     )
@@ -357,6 +730,7 @@ where
                     .upload_with_progress(
                         &temp_path,
                         &target_path,
+                        resume,
                         |bytes| {
                             on_progress(first_phase_bytes.saturating_add(bytes.min(entry.size) / 2))
                         },
@@ -370,21 +744,215 @@ where
     }
 }
 
+async fn remove_sftp_transfer_target(
+    target: &SftpTransferEndpoint,
+    path: std::path::PathBuf,
+) -> Result<(), String> {
+    match target {
+        SftpTransferEndpoint::Local => tokio::task::spawn_blocking(move || {
+            delete_local_path(&path)
+                .map_err(|error| format!("Failed to remove {}: {}", path.display(), error))
+        })
+        .await
+        .map_err(|error| error.to_string())?,
+        SftpTransferEndpoint::Remote(sftp) => sftp
+            .remove_recursive(&path)
+            .await
+            .map_err(|error| error.to_string()),
+    }
+}
+
+async fn collect_sync_tree(
+    endpoint: &SftpTransferEndpoint,
+    root: &std::path::Path,
+) -> Result<Vec<FileEntry>, String> {
+    match endpoint {
+        SftpTransferEndpoint::Local => sync::collect_local_tree(root).await,
+        SftpTransferEndpoint::Remote(sftp) => sync::collect_remote_tree(sftp, root).await,
+    }
+}
+
+async fn sync_checksum(
+    endpoint: &SftpTransferEndpoint,
+    path: &std::path::Path,
+) -> Result<[u8; 32], String> {
+    match endpoint {
+        SftpTransferEndpoint::Local => sync::local_sha256(path).await,
+        SftpTransferEndpoint::Remote(sftp) => {
+            sftp.checksum(path).await.map_err(|error| error.to_string())
+        }
+    }
+}
+
+/// How long to wait before retrying a failed file, if at all. Directories
+/// are copied recursively and cannot resume, so they are never retried, and
+/// neither are errors another attempt would hit again.
+fn transfer_retry_delay(
+    retry: Option<ReconnectPolicy>,
+    entry: &SftpTransferEntry,
+    error: &str,
+    attempt: u32,
+) -> Option<Duration> {
+    let policy = retry?;
+    if entry.is_dir
+        || error == "Transfer cancelled"
+        || is_permanent_transfer_error(error)
+        || attempt >= policy.max_attempts
+    {
+        return None;
+    }
+    Some(policy.delay_with_jitter(attempt))
+}
+
+/// Failures that do not go away by reconnecting, such as missing permissions
+fn is_permanent_transfer_error(error: &str) -> bool {
+    let lower = error.to_ascii_lowercase();
+    [
+        "permission denied",
+        "no such file",
+        "read-only file system",
+        "no space left",
+        "quota exceeded",
+        "is a directory",
+        "already exists",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+/// Point out that a failed file can be resumed by copying it again
+fn with_resume_hint(resume: TransferResume, entry: &SftpTransferEntry, error: String) -> String {
+    if resume.is_enabled() && !entry.is_dir && error != "Transfer cancelled" {
+        format!("{error} (partial file kept; copy again to resume)")
+    } else {
+        error
+    }
+}
+
+/// Reopen the SFTP channels of a transfer's remote sides before a retry
+async fn reopen_transfer_endpoints(request: &SftpTransferRequest) -> Result<(), String> {
+    for endpoint in [&request.source, &request.target] {
+        if let SftpTransferEndpoint::Remote(sftp) = endpoint {
+            sftp.reopen_channel()
+                .await
+                .map_err(|error| error.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Check that a copied file matches its source byte for byte
+async fn verify_transferred_file(
+    source: &SftpTransferEndpoint,
+    source_path: &std::path::Path,
+    target: &SftpTransferEndpoint,
+    target_path: &std::path::Path,
+) -> Result<(), String> {
+    let (source_digest, target_digest) = futures::try_join!(
+        sync_checksum(source, source_path),
+        sync_checksum(target, target_path)
+    )?;
+    if source_digest != target_digest {
+        return Err(format!(
+            "Checksum mismatch: {} does not match {}",
+            target_path.display(),
+            source_path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Check every regular file of a copied entry against its source.
+/// Symlinks are skipped, as the copy itself skips them.
+async fn verify_sftp_transfer(
+    source: &SftpTransferEndpoint,
+    target: &SftpTransferEndpoint,
+    entry: &SftpTransferEntry,
+    target_path: &std::path::Path,
+    cancel_requested: &AtomicBool,
+) -> Result<(), String> {
+    if !entry.is_dir {
+        return verify_transferred_file(source, &entry.path, target, target_path).await;
+    }
+    let source_tree = collect_sync_tree(source, &entry.path).await?;
+    for file in source_tree
+        .iter()
+        .filter(|file| !file.is_dir && !file.is_symlink)
+    {
+        if cancel_requested.load(Ordering::Relaxed) {
+            return Err("Transfer cancelled".to_string());
+        }
+        let Ok(relative) = file.path.strip_prefix(&entry.path) else {
+            continue;
+        };
+        verify_transferred_file(source, &file.path, target, &target_path.join(relative)).await?;
+    }
+    Ok(())
+}
+
+/// Walk both directories and work out what a sync would change.
+async fn scan_sftp_sync(
+    source: SftpTransferEndpoint,
+    source_root: std::path::PathBuf,
+    target: SftpTransferEndpoint,
+    target_root: std::path::PathBuf,
+    options: SyncOptions,
+) -> Result<SyncPlan, String> {
+    let (source_tree, target_tree) = futures::try_join!(
+        collect_sync_tree(&source, &source_root),
+        collect_sync_tree(&target, &target_root)
+    )?;
+    let mut plan = sync::diff_trees(
+        &source_root,
+        &source_tree,
+        &target_root,
+        &target_tree,
+        options,
+    );
+
+    if !plan.to_verify.is_empty() {
+        let mut changed = std::collections::HashSet::new();
+        for (source_item, target_item) in &plan.to_verify {
+            let (source_sum, target_sum) = futures::try_join!(
+                sync_checksum(&source, &source_item.path),
+                sync_checksum(&target, &target_item.path)
+            )?;
+            if source_sum != target_sum {
+                changed.insert(source_item.relative_path.clone());
+            }
+        }
+        plan.resolve_verified(&changed);
+    }
+
+    Ok(plan)
+}
+
+/// Relative path in `/`-separated form, so it joins onto local and remote
+/// targets alike.
+fn sync_relative_name(path: &std::path::Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 impl Portal {
     pub(super) fn begin_connecting(
         &mut self,
         host_name: String,
         protocol: &str,
         session_id: SessionId,
+        host_id: Option<Uuid>,
         task: Task<Message>,
     ) -> Task<Message> {
         self.dialogs.open_connecting(host_name, protocol);
-        self.track_pending_connect(session_id, task)
+        self.track_pending_connect(session_id, host_id, task)
     }
 
     pub(super) fn track_pending_connect(
         &mut self,
         session_id: SessionId,
+        host_id: Option<Uuid>,
         task: Task<Message>,
     ) -> Task<Message> {
         let draft_tab_id = self.active_new_connection_tab_id();
@@ -397,11 +965,20 @@ impl Portal {
         self.pending_connect = Some(crate::app::PendingConnect::new(
             session_id,
             draft_tab_id,
+            host_id,
             handle,
         ));
         task
     }
 
+    /// Saved host of the connection attempt for `session_id`, if pending
+    pub(super) fn pending_connect_host_for(&self, session_id: SessionId) -> Option<Uuid> {
+        self.pending_connect
+            .as_ref()
+            .filter(|pending| pending.is_for(session_id))
+            .and_then(|pending| pending.host_id)
+    }
+
     pub(super) fn pending_connect_draft_for(&self, session_id: SessionId) -> Option<Uuid> {
         self.pending_connect
             .as_ref()
@@ -429,6 +1006,7 @@ impl Portal {
     }
 
     pub(super) fn cancel_pending_connect(&mut self) {
+        self.terminal_panes.cancel_pending();
         if let Some(pending) = self.pending_connect.take() {
             pending.handle.abort();
             self.pre_session_terminal_output.remove(&pending.session_id);
@@ -474,12 +1052,23 @@ impl Portal {
             .map(|tab| tab.id)
     }
 
+    /// Show a terminal tab. Given the id of a split tab this shows its focused
+    /// pane; given a session inside a split it focuses that pane.
     pub(super) fn enter_terminal_view(&mut self, tab_id: Uuid, auto_hide_sidebar: bool) {
+        let (tab_id, session_id) = match self.terminal_panes.focused_session(tab_id) {
+            Some(focused) => (tab_id, focused),
+            None => (
+                self.terminal_panes
+                    .focus(tab_id, self.ui.terminal_focus_token)
+                    .unwrap_or(tab_id),
+                tab_id,
+            ),
+        };
         self.active_tab = Some(tab_id);
         if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == tab_id) {
-            tab.needs_attention = false;
+            tab.mark_seen();
         }
-        self.ui.active_view = View::Terminal(tab_id);
+        self.ui.active_view = View::Terminal(session_id);
         self.ui.terminal_captured = true;
         self.ui.terminal_focus_token = self.ui.terminal_focus_token.wrapping_add(1);
         self.ui.focus_section = crate::app::FocusSection::Content;
@@ -491,7 +1080,7 @@ impl Portal {
     pub(super) fn enter_sftp_view(&mut self, tab_id: Uuid) {
         self.active_tab = Some(tab_id);
         if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == tab_id) {
-            tab.needs_attention = false;
+            tab.mark_seen();
         }
         self.ui.active_view = View::DualSftp(tab_id);
         self.ui.terminal_captured = false;
@@ -500,7 +1089,7 @@ impl Portal {
     pub(super) fn enter_file_viewer_view(&mut self, tab_id: Uuid) {
         self.active_tab = Some(tab_id);
         if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == tab_id) {
-            tab.needs_attention = false;
+            tab.mark_seen();
         }
         self.ui.active_view = View::FileViewer(tab_id);
         self.ui.terminal_captured = false;
@@ -509,7 +1098,7 @@ impl Portal {
     pub(super) fn enter_vnc_view(&mut self, tab_id: Uuid) {
         self.active_tab = Some(tab_id);
         if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == tab_id) {
-            tab.needs_attention = false;
+            tab.mark_seen();
         }
         self.ui.active_view = View::VncViewer(tab_id);
         self.ui.terminal_captured = false;
@@ -557,34 +1146,15 @@ impl Portal {
             self.cancel_pending_connect();
         }
         self.transfers.cancel_for_tab(tab_id);
+        self.file_watches.remove_for_tab(tab_id);
         let sftp_sessions_to_close = self.sftp.remove_tab_and_collect_sessions(tab_id);
         let mut history_changed = false;
 
         self.tabs.retain(|t| t.id != tab_id);
-        if let Some(session) = self.sessions.remove(tab_id) {
-            if history::mark_entry_disconnected(&mut self.config.history, session.history_entry_id)
-            {
-                history_changed = true;
-            }
-
-            let ssh_session_to_cleanup = match &session.backend {
-                SessionBackend::Ssh(ssh_session) => Some(ssh_session.clone()),
-                SessionBackend::Local(_) => None,
-                SessionBackend::Proxy(_) => None,
-            };
-
-            if let Some(logger) = session.logger {
-                tokio::spawn(async move {
-                    logger.shutdown().await;
-                });
-            }
-
-            if let Some(ssh_session) = ssh_session_to_cleanup {
-                tokio::spawn(async move {
-                    ssh_session.stop_all_forwards().await;
-                });
-            }
+        for session_id in self.terminal_panes.remove_tab(tab_id) {
+            history_changed |= self.shutdown_terminal_session(session_id);
         }
+        history_changed |= self.shutdown_terminal_session(tab_id);
         if let Some(vnc) = self.vnc_sessions.remove(&tab_id) {
             if history::mark_entry_disconnected(&mut self.config.history, vnc.history_entry_id) {
                 history_changed = true;
@@ -632,6 +1202,7 @@ impl Portal {
         if history_changed && let Err(e) = self.config.history.save() {
             tracing::error!("Failed to save history config: {}", e);
         }
+        self.save_remembered_sftp_paths();
 
         if self.active_tab == Some(tab_id) {
             if let Some(last_tab) = self.tabs.last() {
@@ -646,6 +1217,67 @@ impl Portal {
         }
     }
 
+    /// Close one pane of a split terminal tab. The other panes take over its
+    /// space; a session that is not part of a split closes its whole tab.
+    pub(super) fn close_terminal_pane(&mut self, session_id: SessionId) {
+        let Some(closed) = self.terminal_panes.close(session_id) else {
+            self.close_tab(session_id);
+            return;
+        };
+
+        if self.shutdown_terminal_session(session_id)
+            && let Err(e) = self.config.history.save()
+        {
+            tracing::error!("Failed to save history config: {}", e);
+        }
+
+        // The tab id follows a live session, so re-key it when its own pane
+        // was the one closed.
+        if closed.tab_id != closed.old_tab_id {
+            if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == closed.old_tab_id) {
+                tab.id = closed.tab_id;
+            }
+            if self.active_tab == Some(closed.old_tab_id) {
+                self.active_tab = Some(closed.tab_id);
+            }
+        }
+
+        if self.active_tab == Some(closed.tab_id) {
+            self.enter_terminal_view(closed.focused, false);
+        }
+    }
+
+    /// Drop a terminal session, stopping its logger and port forwards.
+    /// Returns whether its history entry changed.
+    fn shutdown_terminal_session(&mut self, session_id: SessionId) -> bool {
+        self.broadcast_group.remove(&session_id);
+        let Some(session) = self.sessions.remove(session_id) else {
+            return false;
+        };
+        let history_changed =
+            history::mark_entry_disconnected(&mut self.config.history, session.history_entry_id);
+
+        let ssh_session_to_cleanup = match &session.backend {
+            SessionBackend::Ssh(ssh_session) => Some(ssh_session.clone()),
+            SessionBackend::Local(_) => None,
+            SessionBackend::Proxy(_) => None,
+        };
+
+        if let Some(logger) = session.logger {
+            tokio::spawn(async move {
+                logger.shutdown().await;
+            });
+        }
+
+        if let Some(ssh_session) = ssh_session_to_cleanup {
+            tokio::spawn(async move {
+                ssh_session.stop_all_forwards().await;
+            });
+        }
+
+        history_changed
+    }
+
     pub(super) fn close_active_tab(&mut self) {
         if let Some(tab_id) = self.active_tab {
             self.close_tab(tab_id);
@@ -708,7 +1340,14 @@ impl Portal {
                 Task::none()
             }
             AppAction::CloseSession => {
-                self.close_active_tab();
+                match self.ui.active_view {
+                    View::Terminal(session_id)
+                        if self.terminal_panes.tab_for_session(session_id).is_some() =>
+                    {
+                        self.close_terminal_pane(session_id)
+                    }
+                    _ => self.close_active_tab(),
+                }
                 Task::none()
             }
             AppAction::NewTab => {
@@ -728,6 +1367,10 @@ impl Portal {
                 View::VncViewer(_) => Task::done(Message::Vnc(VncMessage::ToggleFullscreen)),
                 _ => Task::none(),
             },
+            AppAction::VncViewOnly => match self.ui.active_view {
+                View::VncViewer(_) => Task::done(Message::Vnc(VncMessage::ToggleViewOnly)),
+                _ => Task::none(),
+            },
             AppAction::TerminalSearch => match self.ui.active_view {
                 View::Terminal(session_id) if self.sessions.contains(session_id) => {
                     Task::done(Message::Session(SessionMessage::Search(
@@ -736,14 +1379,102 @@ impl Portal {
                 }
                 _ => Task::none(),
             },
-        }
-    }
-
-    /// Connect to a VNC host.
-    ///
-    /// Non-tunneled targets are first checked for cleartext exposure: the
-    /// hostname is resolved asynchronously and, when any resolved address is
-    /// non-private, a warning dialog is shown before any credentials are
+            AppAction::ClearScrollback => match self.ui.active_view {
+                View::Terminal(session_id) if self.sessions.contains(session_id) => Task::done(
+                    Message::Session(SessionMessage::ClearScrollback(session_id)),
+                ),
+                _ => Task::none(),
+            },
+            AppAction::ResetTerminal => match self.ui.active_view {
+                View::Terminal(session_id) if self.sessions.contains(session_id) => {
+                    Task::done(Message::Session(SessionMessage::ResetTerminal(session_id)))
+                }
+                _ => Task::none(),
+            },
+            AppAction::SplitRight | AppAction::SplitDown => match self.ui.active_view {
+                View::Terminal(session_id) if self.sessions.contains(session_id) => {
+                    let axis = if action == AppAction::SplitRight {
+                        pane_grid::Axis::Vertical
+                    } else {
+                        pane_grid::Axis::Horizontal
+                    };
+                    Task::done(Message::Session(SessionMessage::SplitPane(
+                        session_id, axis,
+                    )))
+                }
+                _ => Task::none(),
+            },
+            AppAction::FocusNextPane => match self.ui.active_view {
+                View::Terminal(session_id) if self.sessions.contains(session_id) => {
+                    Task::done(Message::Session(SessionMessage::FocusNextPane(session_id)))
+                }
+                _ => Task::none(),
+            },
+            AppAction::SnippetPalette => match self.ui.active_view {
+                View::Terminal(session_id) if self.sessions.contains(session_id) => {
+                    Task::done(Message::Ui(UiMessage::SnippetPaletteToggle))
+                }
+                _ => Task::none(),
+            },
+            AppAction::ZoomIn | AppAction::ZoomOut | AppAction::ZoomReset => {
+                match self.ui.active_view {
+                    View::Terminal(session_id) if self.sessions.contains(session_id) => {
+                        self.zoom_terminal(session_id, action)
+                    }
+                    _ => Task::none(),
+                }
+            }
+        }
+    }
+
+    /// Change the font size of one terminal session. The grid is re-measured
+    /// at the new size and the resize reaches the server as a window change.
+    fn zoom_terminal(&mut self, session_id: SessionId, action: AppAction) -> Task<Message> {
+        let default_size = self.prefs.terminal_font_size;
+        let Some(session) = self.sessions.get_mut(session_id) else {
+            return Task::none();
+        };
+
+        // A pane of a split tab zooms on its own, through its override
+        if let Some(prefs) = self.terminal_panes.view_prefs_mut(session_id) {
+            let current = prefs
+                .font_size
+                .or(session.font_size)
+                .unwrap_or(default_size);
+            let size = match action {
+                AppAction::ZoomIn => Some(current + TERMINAL_ZOOM_STEP),
+                AppAction::ZoomOut => Some(current - TERMINAL_ZOOM_STEP),
+                _ => {
+                    session.font_size = None;
+                    None
+                }
+            };
+            return self.update(Message::Session(SessionMessage::PaneFontSize(
+                session_id, size,
+            )));
+        }
+
+        let current = session.font_size.unwrap_or(default_size);
+        let size = match action {
+            AppAction::ZoomIn => current + TERMINAL_ZOOM_STEP,
+            AppAction::ZoomOut => current - TERMINAL_ZOOM_STEP,
+            _ => default_size,
+        }
+        .clamp(TERMINAL_FONT_SIZE_MIN, TERMINAL_FONT_SIZE_MAX);
+        session.font_size = (size != default_size).then_some(size);
+        session.status_message = Some((format!("Font size {}px", size), Instant::now()));
+
+        let (cols, rows) = self.terminal_size_for_font(size);
+        Task::done(Message::Session(SessionMessage::Resize(
+            session_id, cols, rows,
+        )))
+    }
+
+    /// Connect to a VNC host.
+    ///
+    /// Non-tunneled targets are first checked for cleartext exposure: the
+    /// hostname is resolved asynchronously and, when any resolved address is
+    /// non-private, a warning dialog is shown before any credentials are
     /// requested (see `HostMessage::VncCleartextCheckDone`).
     pub(super) fn connect_vnc_host(&mut self, host: &Host) -> Task<Message> {
         let tunneled = host.vnc_via_ssh_host_id.is_some();
@@ -894,7 +1625,7 @@ impl Portal {
                         let params = crate::ssh::tunnel::TunnelParams::new(
                             connection::shared_known_hosts_manager(),
                             ssh_event_tx,
-                            Duration::from_secs(30),
+                            connection::connect_timeout(),
                         );
                         match crate::ssh::tunnel::open_tunneled_stream(
                             &params,
@@ -994,6 +1725,7 @@ impl Portal {
             dialog_host_name,
             &protocol_label,
             session_id,
+            Some(host_id),
             Task::batch(tasks),
         )
     }
@@ -1029,11 +1761,18 @@ impl Portal {
                 terminal_size,
             );
 
-            return self.begin_connecting(dialog_host_name, "Portal Hub", session_id, task);
+            return self.begin_connecting(
+                dialog_host_name,
+                "Portal Hub",
+                session_id,
+                Some(host_id),
+                task,
+            );
         }
 
-        // Check if password authentication is configured
-        if matches!(host.auth, AuthMethod::Password) {
+        // A primary password is collected up front; a password fallback is asked
+        // for only once the methods ahead of it are rejected
+        if host.needs_password() {
             // Show password dialog
             let password_dialog = PasswordDialogState::new_ssh(
                 host.name.clone(),
@@ -1068,10 +1807,17 @@ impl Portal {
             terminal_size,
             should_detect_os,
             self.prefs.allow_agent_forwarding,
+            self.prefs.keepalive(),
             jump_chain,
         );
 
-        self.begin_connecting(dialog_host_name, &protocol_label, session_id, task)
+        self.begin_connecting(
+            dialog_host_name,
+            &protocol_label,
+            session_id,
+            Some(host_id),
+            task,
+        )
     }
 
     /// Resolve the jump-host chain for `host`, surfacing configuration errors
@@ -1087,7 +1833,11 @@ impl Portal {
     }
 
     /// Spawn a local terminal session
-    pub(super) fn spawn_local_terminal(&mut self) -> Task<Message> {
+    pub(super) fn spawn_local_terminal(
+        &mut self,
+        spec: LocalSpawnSpec,
+        title: String,
+    ) -> Task<Message> {
         let session_id = Uuid::new_v4();
 
         // Create event channel for local PTY events
@@ -1110,12 +1860,13 @@ impl Portal {
         // Spawn the local terminal with a best-effort size. The first render
         // still sends the exact grid size.
         let (cols, rows) = self.terminal_initial_size();
-        match LocalSession::spawn(cols, rows, event_tx) {
+        match LocalSession::spawn(&spec, cols, rows, event_tx) {
             Ok(local_session) => {
                 let local_session = Arc::new(local_session);
                 let spawn_task = Task::done(Message::Session(SessionMessage::LocalConnected {
                     session_id,
                     local_session,
+                    title,
                 }));
 
                 Task::batch([event_listener, spawn_task])
@@ -1139,48 +1890,115 @@ impl Portal {
     ) -> Task<Message> {
         if let Some(tab_state) = self.sftp.get_tab(tab_id) {
             let pane = tab_state.pane(pane_id);
-            let path = pane.current_path.clone();
-
-            match &pane.source {
-                PaneSource::Local => {
-                    // Load local directory
-                    let requested_source = pane.source.clone();
-                    let requested_path = path.clone();
-                    Task::perform(async move { list_local_dir(&path).await }, move |result| {
-                        Message::Sftp(SftpMessage::PaneListResult(
-                            tab_id,
-                            pane_id,
-                            requested_source.clone(),
-                            requested_path.clone(),
-                            result,
-                        ))
+            let requested_source = pane.source.clone();
+            let requested_path = pane.current_path.clone();
+            self.list_pane_path(&pane.source, pane.current_path.clone(), move |result| {
+                Message::Sftp(SftpMessage::PaneListResult(
+                    tab_id,
+                    pane_id,
+                    requested_source,
+                    requested_path,
+                    result,
+                ))
+            })
+        } else {
+            Task::none()
+        }
+    }
+
+    /// List a directory the user typed into a pane's path bar. The pane only
+    /// moves there once the listing succeeds.
+    pub(super) fn load_typed_pane_path(
+        &self,
+        tab_id: SessionId,
+        pane_id: PaneId,
+        path: std::path::PathBuf,
+    ) -> Task<Message> {
+        if let Some(tab_state) = self.sftp.get_tab(tab_id) {
+            let source = tab_state.pane(pane_id).source.clone();
+            let requested_source = source.clone();
+            let requested_path = path.clone();
+            self.list_pane_path(&source, path, move |result| {
+                Message::Sftp(SftpMessage::PathSubmitResult(
+                    tab_id,
+                    pane_id,
+                    requested_source,
+                    requested_path,
+                    result,
+                ))
+            })
+        } else {
+            Task::none()
+        }
+    }
+
+    /// List `path` on a pane source, local or over SFTP
+    fn list_pane_path(
+        &self,
+        source: &PaneSource,
+        path: std::path::PathBuf,
+        on_result: impl FnOnce(Result<Vec<FileEntry>, String>) -> Message + Send + 'static,
+    ) -> Task<Message> {
+        match source {
+            PaneSource::Local => {
+                Task::perform(async move { list_local_dir(&path).await }, on_result)
+            }
+            PaneSource::Remote { session_id, .. } => {
+                if let Some(sftp) = self.sftp.get_connection(*session_id) {
+                    let sftp = sftp.clone();
+                    Task::perform(async move { sftp.list_dir(&path).await }, move |result| {
+                        on_result(result.map_err(|e| e.to_string()))
                     })
-                }
-                PaneSource::Remote { session_id, .. } => {
-                    // Load remote directory via SFTP
-                    if let Some(sftp) = self.sftp.get_connection(*session_id) {
-                        let sftp = sftp.clone();
-                        let requested_source = pane.source.clone();
-                        let requested_path = path.clone();
-                        Task::perform(async move { sftp.list_dir(&path).await }, move |result| {
-                            Message::Sftp(SftpMessage::PaneListResult(
-                                tab_id,
-                                pane_id,
-                                requested_source.clone(),
-                                requested_path.clone(),
-                                result.map_err(|e| e.to_string()),
-                            ))
-                        })
-                    } else {
-                        Task::none()
-                    }
+                } else {
+                    Task::none()
                 }
             }
-        } else {
-            Task::none()
         }
     }
 
+    /// Open a file from an SFTP pane in the in-app file viewer
+    pub(super) fn open_pane_file_viewer(
+        &mut self,
+        source: &PaneSource,
+        file_name: String,
+        file_path: std::path::PathBuf,
+    ) -> Task<Message> {
+        let file_type = FileType::from_path(&file_path);
+
+        // Create a new file viewer
+        let viewer_id = Uuid::new_v4();
+
+        let (viewer_state, load_task) = match source {
+            PaneSource::Local => {
+                file_viewer::build_local_viewer(viewer_id, file_name.clone(), file_path, file_type)
+            }
+            PaneSource::Remote { session_id, .. } => {
+                if let Some(sftp) = self.sftp.get_connection(*session_id) {
+                    file_viewer::build_remote_viewer(
+                        viewer_id,
+                        file_name.clone(),
+                        file_path,
+                        *session_id,
+                        sftp.clone(),
+                        file_type,
+                    )
+                } else {
+                    return Task::none();
+                }
+            }
+        };
+
+        // Add viewer to manager
+        self.file_viewers.insert(viewer_state);
+
+        // Create tab
+        let tab = Tab::new_file_viewer(viewer_id, file_name);
+        self.tabs.push(tab);
+        self.enter_file_viewer_view(viewer_id);
+
+        load_task
+    }
+
     /// Connect to an SFTP host for use in a dual-pane browser
     pub(super) fn connect_sftp_for_pane(
         &mut self,
@@ -1189,7 +2007,7 @@ impl Portal {
         host: &Host,
     ) -> Task<Message> {
         // Check if password authentication is configured
-        if matches!(host.auth, AuthMethod::Password) {
+        if host.needs_password() {
             self.sftp
                 .set_pending_connection(Some((tab_id, pane_id, host.id)));
             // Show password dialog for SFTP
@@ -1250,57 +2068,80 @@ impl Portal {
                         self.toast_manager.push(Toast::error(error));
                         return Task::none();
                     }
-                    let file_type = FileType::from_path(&file_path);
-
-                    // Check if file type is viewable
-                    if !file_type.is_viewable() {
-                        self.toast_manager.push(Toast::warning(
-                            "Binary files are not supported in the viewer.",
-                        ));
-                        return Task::none();
+                    let source = pane.source.clone();
+                    return self.open_pane_file_viewer(&source, file_name, file_path);
+                }
+            }
+            ContextMenuAction::OpenWith => {
+                if let [entry] = selected_entries.as_slice()
+                    && !entry.is_dir
+                    && !entry.is_parent()
+                {
+                    let (name, path) = (entry.name.clone(), entry.path.clone());
+                    if let Some(tab_state) = self.sftp.get_tab_mut(tab_id) {
+                        tab_state.show_open_with_dialog(name, path);
                     }
-
-                    // Create a new file viewer
-                    let viewer_id = Uuid::new_v4();
-
-                    let (viewer_state, load_task) = match &pane.source {
-                        PaneSource::Local => file_viewer::build_local_viewer(
-                            viewer_id,
-                            file_name.clone(),
-                            file_path,
-                            file_type,
-                        ),
-                        PaneSource::Remote { session_id, .. } => {
-                            if let Some(sftp) = self.sftp.get_connection(*session_id) {
-                                file_viewer::build_remote_viewer(
-                                    viewer_id,
-                                    file_name.clone(),
-                                    file_path,
-                                    *session_id,
-                                    sftp.clone(),
-                                    file_type,
-                                )
-                            } else {
-                                return Task::none();
-                            }
-                        }
-                    };
-
-                    // Add viewer to manager
-                    self.file_viewers.insert(viewer_state);
-
-                    // Create tab
-                    let tab = Tab::new_file_viewer(viewer_id, file_name);
-                    self.tabs.push(tab);
-                    self.enter_file_viewer_view(viewer_id);
-
-                    return load_task;
                 }
             }
             ContextMenuAction::CopyToTarget => {
                 // Copy selected files to the target (other) pane
                 return Task::done(Message::Sftp(SftpMessage::CopyToTarget(tab_id)));
             }
+            ContextMenuAction::Duplicate => {
+                return self.handle_duplicate(tab_id);
+            }
+            ContextMenuAction::SyncToTarget => {
+                return self.open_sftp_sync_dialog(tab_id);
+            }
+            ContextMenuAction::ToggleWatch => {
+                return self.toggle_sftp_file_watch(tab_id);
+            }
+            ContextMenuAction::CalculateSize => {
+                return self.toggle_sftp_dir_sizes(tab_id);
+            }
+            ContextMenuAction::Compare => {
+                return self.compare_sftp_files(tab_id);
+            }
+            ContextMenuAction::CopyLinkTarget => {
+                if let [entry] = selected_entries.as_slice()
+                    && let Some(target) = &entry.symlink_target
+                {
+                    let target = target.target.clone();
+                    self.toast_manager
+                        .push(Toast::success("Copied link target to clipboard"));
+                    return iced::clipboard::write(target);
+                }
+            }
+            ContextMenuAction::RevealInFileManager => {
+                if let [entry] = selected_entries.as_slice()
+                    && matches!(pane.source, PaneSource::Local)
+                {
+                    let folder = entry
+                        .path
+                        .parent()
+                        .map(|parent| parent.to_path_buf())
+                        .unwrap_or_else(|| pane.current_path.clone());
+                    if let Err(error) = open::that(&folder) {
+                        self.toast_manager
+                            .push(Toast::error(format!("Failed to open folder: {}", error)));
+                    }
+                }
+            }
+            ContextMenuAction::CopyPath => {
+                if let [entry] = selected_entries.as_slice()
+                    && matches!(pane.source, PaneSource::Local)
+                {
+                    let path =
+                        std::path::absolute(&entry.path).unwrap_or_else(|_| entry.path.clone());
+                    self.toast_manager
+                        .push(Toast::success("Copied path to clipboard"));
+                    return iced::clipboard::write(path.display().to_string());
+                }
+            }
+            ContextMenuAction::CopyContents | ContextMenuAction::CopyBase64 => {
+                let as_base64 = action == ContextMenuAction::CopyBase64;
+                return self.copy_sftp_file_contents(tab_id, as_base64);
+            }
             ContextMenuAction::Rename => {
                 // Show the Rename dialog for single selection
                 if let Some(entry) = selected_entries.first()
@@ -1345,6 +2186,11 @@ impl Portal {
                     tab_state.show_new_folder_dialog();
                 }
             }
+            ContextMenuAction::NewFile => {
+                if let Some(tab_state) = self.sftp.get_tab_mut(tab_id) {
+                    tab_state.show_new_file_dialog();
+                }
+            }
             ContextMenuAction::EditPermissions => {
                 // Show permissions dialog for single selection
                 if let Some(entry) = selected_entries.first()
@@ -1376,16 +2222,51 @@ impl Portal {
                         }
                     };
 
+                    let is_dir = entry.is_dir;
                     if let Some(tab_state) = self.sftp.get_tab_mut(tab_id) {
-                        tab_state.show_permissions_dialog(name, path, permissions);
+                        tab_state.show_permissions_dialog(name, path, permissions, is_dir);
                     }
                 }
             }
+            ContextMenuAction::Properties => {
+                if let Some(entry) = selected_entries.first()
+                    && !entry.is_parent()
+                {
+                    return self.show_sftp_properties(
+                        tab_id,
+                        entry.name.clone(),
+                        entry.path.clone(),
+                    );
+                }
+            }
         }
 
         Task::none()
     }
 
+    /// Write remembered SFTP directories to hosts.toml if any changed
+    pub(super) fn save_remembered_sftp_paths(&mut self) {
+        if self.sftp.take_unsaved_last_paths()
+            && let Err(e) = self.config.hosts.save()
+        {
+            tracing::error!("Failed to save hosts config: {}", e);
+        }
+    }
+
+    /// Write SFTP bookmarks to disk, reporting a failure with a toast.
+    /// Returns true when they were saved.
+    pub(super) fn save_sftp_bookmarks(&mut self) -> bool {
+        match self.config.sftp_bookmarks.save() {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!("Failed to save SFTP bookmarks: {}", e);
+                self.toast_manager
+                    .push(Toast::error("Failed to save bookmarks"));
+                false
+            }
+        }
+    }
+
     /// Handle dialog submission (New Folder or Rename)
     pub(super) fn handle_sftp_dialog_submit(&mut self, tab_id: SessionId) -> Task<Message> {
         let Some(tab_state) = self.sftp.get_tab(tab_id) else {
@@ -1401,6 +2282,90 @@ impl Portal {
         let current_path = pane.current_path.clone();
         let input_value = dialog.input_value.trim().to_string();
 
+        // A sudo password means the plain SFTP attempt was refused; run the
+        // remote operation through sudo instead. Cache it like passphrases.
+        let sudo_password = dialog.sudo_password.clone();
+        if let (Some(password), Some(session_id)) = (&sudo_password, pane.source.session_id()) {
+            connection::shared_sudo_cache().store(session_id, password.clone());
+        }
+
+        if let SftpDialogType::Sync {
+            source_pane,
+            plan: Some(plan),
+            ..
+        } = &dialog.dialog_type
+        {
+            let (source_pane, plan) = (*source_pane, plan.clone());
+            return self.start_sftp_sync(tab_id, source_pane, pane_id, plan);
+        }
+
+        if let SftpDialogType::EditPermissions {
+            path,
+            permissions,
+            is_dir: true,
+            recursive: true,
+            searchable_dirs,
+            ..
+        } = &dialog.dialog_type
+        {
+            let path = path.clone();
+            let mode = RecursiveMode {
+                mode: permissions.to_mode(),
+                searchable_dirs: *searchable_dirs,
+            };
+            return self.start_recursive_change(
+                tab_id,
+                pane_id,
+                path,
+                TreeChangeRequest::Mode(mode),
+            );
+        }
+
+        // Without sudo, a recursive chown walks the tree like chmod; sudo
+        // runs a single `chown -R` instead
+        if let SftpDialogType::ChangeOwner {
+            path,
+            is_dir: true,
+            recursive: true,
+            owner,
+            group,
+            ..
+        } = &dialog.dialog_type
+            && sudo_password.is_none()
+        {
+            let path = path.clone();
+            let request = TreeChangeRequest::Owner {
+                owner: owner.trim().to_string(),
+                group: group.trim().to_string(),
+            };
+            return self.start_recursive_change(tab_id, pane_id, path, request);
+        }
+
+        if let SftpDialogType::OpenWith { name, path } = &dialog.dialog_type {
+            let (name, path) = (name.clone(), path.clone());
+            let source = pane.source.clone();
+            return self.open_sftp_entry_with(tab_id, source, name, path, input_value);
+        }
+
+        // Properties are read-only; submitting just closes them
+        if let SftpDialogType::Properties { .. } = &dialog.dialog_type {
+            if let Some(tab_state) = self.sftp.get_tab_mut(tab_id) {
+                tab_state.close_dialog();
+            }
+            return Task::none();
+        }
+
+        if let SftpDialogType::RenameBookmark { bookmark_id } = &dialog.dialog_type {
+            let bookmark_id = *bookmark_id;
+            if self.config.sftp_bookmarks.rename(bookmark_id, input_value) {
+                self.save_sftp_bookmarks();
+            }
+            if let Some(tab_state) = self.sftp.get_tab_mut(tab_id) {
+                tab_state.close_dialog();
+            }
+            return Task::none();
+        }
+
         match &dialog.dialog_type {
             SftpDialogType::NewFolder => {
                 let child_name = match validated_sftp_child_name(&input_value) {
@@ -1437,9 +2402,17 @@ impl Portal {
                             let sftp = sftp.clone();
                             Task::perform(
                                 async move {
-                                    sftp.create_dir(&new_folder_path)
-                                        .await
-                                        .map_err(|e| e.to_string())
+                                    match sudo_password {
+                                        Some(password) => {
+                                            sftp.run_sudo(
+                                                &SudoCommand::CreateDir(new_folder_path),
+                                                &password,
+                                            )
+                                            .await
+                                        }
+                                        None => sftp.create_dir(&new_folder_path).await,
+                                    }
+                                    .map_err(|e| e.to_string())
                                 },
                                 move |result| {
                                     Message::Sftp(SftpMessage::NewFolderResult(
@@ -1453,6 +2426,69 @@ impl Portal {
                     }
                 }
             }
+            SftpDialogType::NewFile { .. } => {
+                let child_name = match validated_sftp_child_name(&input_value) {
+                    Ok(name) => name,
+                    Err(error) => {
+                        return Task::done(Message::Sftp(SftpMessage::NewFileResult(
+                            tab_id,
+                            pane_id,
+                            Err(error),
+                        )));
+                    }
+                };
+                let new_file_path = current_path.join(&child_name);
+
+                match &pane.source {
+                    PaneSource::Local => Task::perform(
+                        async move {
+                            let path = new_file_path.clone();
+                            tokio::task::spawn_blocking(move || {
+                                std::fs::OpenOptions::new()
+                                    .write(true)
+                                    .create_new(true)
+                                    .open(&path)
+                                    .map(|_| ())
+                                    .map_err(|e| e.to_string())
+                            })
+                            .await
+                            .map_err(|e| e.to_string())??;
+                            Ok(new_file_path)
+                        },
+                        move |result| {
+                            Message::Sftp(SftpMessage::NewFileResult(tab_id, pane_id, result))
+                        },
+                    ),
+                    PaneSource::Remote { session_id, .. } => {
+                        if let Some(sftp) = self.sftp.get_connection(*session_id) {
+                            let sftp = sftp.clone();
+                            Task::perform(
+                                async move {
+                                    match sudo_password {
+                                        Some(password) => {
+                                            sftp.run_sudo(
+                                                &SudoCommand::CreateFile(new_file_path.clone()),
+                                                &password,
+                                            )
+                                            .await
+                                        }
+                                        None => sftp.create_file(&new_file_path).await,
+                                    }
+                                    .map(|()| new_file_path)
+                                    .map_err(|e| e.to_string())
+                                },
+                                move |result| {
+                                    Message::Sftp(SftpMessage::NewFileResult(
+                                        tab_id, pane_id, result,
+                                    ))
+                                },
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
+                }
+            }
             SftpDialogType::Rename { original_name } => {
                 let old_path = current_path.join(original_name);
                 let child_name = match validated_sftp_child_name(&input_value) {
@@ -1490,9 +2526,20 @@ impl Portal {
                             let sftp = sftp.clone();
                             Task::perform(
                                 async move {
-                                    sftp.rename(&old_path, &new_path)
-                                        .await
-                                        .map_err(|e| e.to_string())
+                                    match sudo_password {
+                                        Some(password) => {
+                                            sftp.run_sudo(
+                                                &SudoCommand::Rename {
+                                                    from: old_path,
+                                                    to: new_path,
+                                                },
+                                                &password,
+                                            )
+                                            .await
+                                        }
+                                        None => sftp.rename(&old_path, &new_path).await,
+                                    }
+                                    .map_err(|e| e.to_string())
                                 },
                                 move |result| {
                                     Message::Sftp(SftpMessage::RenameResult(
@@ -1547,7 +2594,16 @@ impl Portal {
                                 async move {
                                     let mut deleted_count = 0;
                                     for (_, path, is_dir) in entries {
-                                        let result = if is_dir {
+                                        let result = if let Some(password) = &sudo_password {
+                                            sftp.run_sudo(
+                                                &SudoCommand::Remove {
+                                                    path: path.clone(),
+                                                    recursive: is_dir,
+                                                },
+                                                password,
+                                            )
+                                            .await
+                                        } else if is_dir {
                                             sftp.remove_recursive(&path).await
                                         } else {
                                             sftp.remove_file(&path).await
@@ -1607,9 +2663,17 @@ impl Portal {
                             let sftp = sftp.clone();
                             Task::perform(
                                 async move {
-                                    sftp.set_permissions(&path, mode)
-                                        .await
-                                        .map_err(|e| e.to_string())
+                                    match sudo_password {
+                                        Some(password) => {
+                                            sftp.run_sudo(
+                                                &SudoCommand::SetPermissions { path, mode },
+                                                &password,
+                                            )
+                                            .await
+                                        }
+                                        None => sftp.set_permissions(&path, mode).await,
+                                    }
+                                    .map_err(|e| e.to_string())
                                 },
                                 move |result| {
                                     Message::Sftp(SftpMessage::PermissionsResult(
@@ -1623,6 +2687,80 @@ impl Portal {
                     }
                 }
             }
+            SftpDialogType::ChangeOwner {
+                path,
+                owner,
+                group,
+                recursive,
+                ..
+            } => {
+                let path = path.clone();
+                let (owner, group) = (owner.trim().to_string(), group.trim().to_string());
+                let recursive = *recursive;
+
+                match &pane.source {
+                    PaneSource::Local => Task::perform(
+                        async move {
+                            tokio::task::spawn_blocking(move || {
+                                let ownership = resolve_local_ownership(&owner, &group)?;
+                                set_local_owner(&path, ownership)
+                            })
+                            .await
+                            .map_err(|e| e.to_string())?
+                        },
+                        move |result| {
+                            Message::Sftp(SftpMessage::OwnerResult(tab_id, pane_id, result))
+                        },
+                    ),
+                    PaneSource::Remote { session_id, .. } => {
+                        if let Some(sftp) = self.sftp.get_connection(*session_id) {
+                            let sftp = sftp.clone();
+                            Task::perform(
+                                async move {
+                                    match sudo_password {
+                                        Some(password) => {
+                                            // chown takes names or ids as typed
+                                            let owner = if group.is_empty() {
+                                                owner
+                                            } else {
+                                                format!("{}:{}", owner, group)
+                                            };
+                                            sftp.run_sudo(
+                                                &SudoCommand::SetOwner {
+                                                    path,
+                                                    owner,
+                                                    recursive,
+                                                },
+                                                &password,
+                                            )
+                                            .await
+                                        }
+                                        None => {
+                                            match sftp.resolve_ownership(&owner, &group).await {
+                                                Ok(ownership) => {
+                                                    sftp.set_owner(&path, ownership).await
+                                                }
+                                                Err(error) => Err(error),
+                                            }
+                                        }
+                                    }
+                                    .map_err(|e| e.to_string())
+                                },
+                                move |result| {
+                                    Message::Sftp(SftpMessage::OwnerResult(tab_id, pane_id, result))
+                                },
+                            )
+                        } else {
+                            Task::none()
+                        }
+                    }
+                }
+            }
+            // Still scanning; the submit button is disabled until a plan exists.
+            SftpDialogType::Sync { .. }
+            | SftpDialogType::RenameBookmark { .. }
+            | SftpDialogType::OpenWith { .. }
+            | SftpDialogType::Properties { .. } => Task::none(),
         }
     }
 
@@ -1659,23 +2797,11 @@ impl Portal {
             return Task::none();
         }
 
-        let source = match &source_pane.source {
-            PaneSource::Local => SftpTransferEndpoint::Local,
-            PaneSource::Remote { session_id, .. } => {
-                let Some(sftp) = self.sftp.get_connection(*session_id).cloned() else {
-                    return Task::none();
-                };
-                SftpTransferEndpoint::Remote(sftp)
-            }
+        let Some(source) = self.sftp_transfer_endpoint(&source_pane.source) else {
+            return Task::none();
         };
-        let target = match &target_pane.source {
-            PaneSource::Local => SftpTransferEndpoint::Local,
-            PaneSource::Remote { session_id, .. } => {
-                let Some(sftp) = self.sftp.get_connection(*session_id).cloned() else {
-                    return Task::none();
-                };
-                SftpTransferEndpoint::Remote(sftp)
-            }
+        let Some(target) = self.sftp_transfer_endpoint(&target_pane.source) else {
+            return Task::none();
         };
 
         let request = SftpTransferRequest {
@@ -1685,7 +2811,151 @@ impl Portal {
             source,
             target,
             entries: entries_to_copy,
+            deletions: Vec::new(),
+            verify: self.prefs.sftp_verify_transfers,
+            resume: self.sftp_transfer_resume(),
+            retry: self.sftp_transfer_retry(),
         };
+        self.start_sftp_transfer(request)
+    }
+
+    /// Copy the active pane's selection into the same directory under
+    /// collision-free " copy" names, tracked like Copy to Target
+    fn handle_duplicate(&mut self, tab_id: SessionId) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab(tab_id) else {
+            return Task::none();
+        };
+        let pane_id = tab_state.active_pane;
+        let pane = tab_state.pane(pane_id);
+
+        let mut taken: std::collections::HashSet<String> =
+            pane.entries.iter().map(|e| e.name.clone()).collect();
+        // The transfer names each copy by `name` relative to the target dir
+        let entries: Vec<_> = pane
+            .selected_entries()
+            .into_iter()
+            .filter(|e| !e.is_parent())
+            .map(|e| {
+                let name = duplicate_name(&e.name, e.is_dir, &taken);
+                taken.insert(name.clone());
+                SftpTransferEntry {
+                    name,
+                    path: e.path.clone(),
+                    is_dir: e.is_dir,
+                    is_symlink: e.is_symlink,
+                    size: e.size,
+                }
+            })
+            .collect();
+        if entries.is_empty() {
+            return Task::none();
+        }
+
+        let Some(endpoint) = self.sftp_transfer_endpoint(&pane.source) else {
+            return Task::none();
+        };
+
+        let request = SftpTransferRequest {
+            tab_id,
+            target_pane_id: pane_id,
+            target_dir: pane.current_path.clone(),
+            source: endpoint.clone(),
+            target: endpoint,
+            entries,
+            deletions: Vec::new(),
+            verify: self.prefs.sftp_verify_transfers,
+            resume: self.sftp_transfer_resume(),
+            retry: self.sftp_transfer_retry(),
+        };
+        self.start_sftp_transfer(request)
+    }
+
+    /// Upload files dropped from the OS into the active pane's directory (a
+    /// plain copy when that pane is local), tracked like Copy to Target
+    pub(super) fn handle_dropped_files(
+        &mut self,
+        tab_id: SessionId,
+        paths: Vec<std::path::PathBuf>,
+    ) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab(tab_id) else {
+            return Task::none();
+        };
+        let target_pane_id = tab_state.active_pane;
+        let target_pane = tab_state.pane(target_pane_id);
+        let target_dir = target_pane.current_path.clone();
+        let Some(target) = self.sftp_transfer_endpoint(&target_pane.source) else {
+            return Task::none();
+        };
+
+        let entries = match paths
+            .iter()
+            .map(|path| dropped_transfer_entry(path))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(entries) => entries,
+            Err(error) => {
+                self.toast_manager.push(Toast::error(error));
+                return Task::none();
+            }
+        };
+        if entries.is_empty() {
+            return Task::none();
+        }
+
+        let request = SftpTransferRequest {
+            tab_id,
+            target_pane_id,
+            target_dir,
+            source: SftpTransferEndpoint::Local,
+            target,
+            entries,
+            deletions: Vec::new(),
+            verify: self.prefs.sftp_verify_transfers,
+            resume: self.sftp_transfer_resume(),
+            retry: self.sftp_transfer_retry(),
+        };
+        self.start_sftp_transfer(request)
+    }
+
+    fn sftp_transfer_endpoint(&self, source: &PaneSource) -> Option<SftpTransferEndpoint> {
+        match source {
+            PaneSource::Local => Some(SftpTransferEndpoint::Local),
+            PaneSource::Remote { session_id, .. } => self
+                .sftp
+                .get_connection(*session_id)
+                .cloned()
+                .map(SftpTransferEndpoint::Remote),
+        }
+    }
+
+    /// Resume mode for new transfers; the tail of a partial file is checked
+    /// first when transfers are verified anyway
+    fn sftp_transfer_resume(&self) -> TransferResume {
+        match (
+            self.prefs.sftp_resume_transfers,
+            self.prefs.sftp_verify_transfers,
+        ) {
+            (false, _) => TransferResume::Off,
+            (true, false) => TransferResume::Append,
+            (true, true) => TransferResume::AppendVerified,
+        }
+    }
+
+    /// Failed files are retried with the reconnect backoff when
+    /// auto-reconnect is on and they can pick up where they stopped
+    fn sftp_transfer_retry(&self) -> Option<ReconnectPolicy> {
+        (self.prefs.auto_reconnect && self.prefs.sftp_resume_transfers).then(|| {
+            ReconnectPolicy::new(
+                self.prefs.reconnect_base_delay_ms,
+                self.prefs.reconnect_max_delay_ms,
+                self.prefs.reconnect_max_attempts,
+            )
+        })
+    }
+
+    fn start_sftp_transfer(&mut self, request: SftpTransferRequest) -> Task<Message> {
+        let tab_id = request.tab_id;
+        let target_pane_id = request.target_pane_id;
         let transfer_id = Uuid::new_v4();
         let cancel_requested = Arc::new(AtomicBool::new(false));
         let transfer = TransferItem::new(TransferItemInit {
@@ -1700,10 +2970,584 @@ impl Portal {
         });
         self.transfers.insert(transfer);
 
-        let task = sftp_transfer_task(transfer_id, request, cancel_requested);
+        let sudo_password = self
+            .sftp
+            .get_tab(tab_id)
+            .and_then(|tab_state| tab_state.pane(target_pane_id).source.session_id())
+            .and_then(|session_id| connection::shared_sudo_cache().get(&session_id));
+        let task = sftp_transfer_task(transfer_id, request, sudo_password, cancel_requested);
         let (task, _handle) = task.abortable();
         task
     }
+
+    /// Read the selected small file and put it on the clipboard, as text or
+    /// base64
+    fn copy_sftp_file_contents(&mut self, tab_id: SessionId, as_base64: bool) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        tab_state.hide_context_menu();
+        let pane = tab_state.pane(tab_state.active_pane);
+        let selected_entries = pane.selected_entries();
+        let [entry] = selected_entries.as_slice() else {
+            return Task::none();
+        };
+        if entry.is_dir || entry.is_parent() {
+            return Task::none();
+        }
+        if let Err(error) = reject_symlink_open(&entry.name, entry.is_symlink) {
+            self.toast_manager.push(Toast::error(error));
+            return Task::none();
+        }
+        if entry.size > MAX_CLIPBOARD_COPY_BYTES {
+            self.toast_manager.push(Toast::warning(format!(
+                "{} is too large to copy to the clipboard (limit {})",
+                entry.name,
+                format_size(MAX_CLIPBOARD_COPY_BYTES)
+            )));
+            return Task::none();
+        }
+
+        let file_name = entry.name.clone();
+        let path = entry.path.clone();
+        let sftp = match pane.source.session_id() {
+            None => None,
+            Some(session_id) => {
+                let Some(sftp) = self.sftp.get_connection(session_id).cloned() else {
+                    return Task::none();
+                };
+                Some(sftp)
+            }
+        };
+
+        let label = file_name.clone();
+        Task::perform(
+            async move {
+                let bytes = read_pane_file(sftp, path, MAX_CLIPBOARD_COPY_BYTES).await?;
+                clipboard_text_for(&label, bytes, as_base64)
+            },
+            move |result| Message::Sftp(SftpMessage::ContentsLoaded(file_name.clone(), result)),
+        )
+    }
+
+    /// Open a viewer tab with a side-by-side diff of the file selected in
+    /// each pane
+    fn compare_sftp_files(&mut self, tab_id: SessionId) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        tab_state.hide_context_menu();
+        let Some((left, right)) = tab_state.comparison_files() else {
+            return Task::none();
+        };
+        if let Some(entry) = [left, right]
+            .into_iter()
+            .find(|entry| entry.size > MAX_DIFF_BYTES)
+        {
+            self.toast_manager.push(Toast::warning(format!(
+                "{} is too large to compare (limit {})",
+                entry.name,
+                format_size(MAX_DIFF_BYTES)
+            )));
+            return Task::none();
+        }
+
+        let sides = [(PaneId::Left, left), (PaneId::Right, right)].map(|(pane_id, entry)| {
+            (
+                tab_state.pane(pane_id).source.session_id(),
+                entry.name.clone(),
+                entry.path.clone(),
+            )
+        });
+        let [
+            (left_session, left_name, left_path),
+            (right_session, right_name, right_path),
+        ] = sides;
+        // `None` for a local pane, `Some(None)` when the remote connection is gone
+        let connection = |session_id: Option<SessionId>| match session_id {
+            None => Some(None),
+            Some(session_id) => self.sftp.get_connection(session_id).cloned().map(Some),
+        };
+        let (Some(left_sftp), Some(right_sftp)) =
+            (connection(left_session), connection(right_session))
+        else {
+            return Task::none();
+        };
+
+        let viewer_id = Uuid::new_v4();
+        let title = format!("{} \u{2194} {}", left_name, right_name);
+        self.file_viewers.insert(FileViewerState::new_comparison(
+            viewer_id,
+            title.clone(),
+            FileSource::Local {
+                path: left_path.clone(),
+            },
+        ));
+        self.tabs.push(Tab::new_file_viewer(viewer_id, title));
+        self.enter_file_viewer_view(viewer_id);
+
+        Task::perform(
+            async move {
+                let left_label = left_path.display().to_string();
+                let right_label = right_path.display().to_string();
+                let (left_bytes, right_bytes) = futures::try_join!(
+                    read_pane_file(left_sftp, left_path, MAX_DIFF_BYTES),
+                    read_pane_file(right_sftp, right_path, MAX_DIFF_BYTES),
+                )?;
+                let left_text = diff_text(&left_name, left_bytes)?;
+                let right_text = diff_text(&right_name, right_bytes)?;
+                let rows =
+                    tokio::task::spawn_blocking(move || side_by_side(&left_text, &right_text))
+                        .await
+                        .map_err(|e| format!("Diff task failed: {}", e))?;
+                Ok(ViewerContent::Diff {
+                    left_label,
+                    right_label,
+                    rows,
+                })
+            },
+            move |result: Result<ViewerContent, String>| match result {
+                Ok(content) => {
+                    Message::FileViewer(FileViewerMessage::ContentLoaded { viewer_id, content })
+                }
+                Err(error) => Message::FileViewer(FileViewerMessage::LoadError(viewer_id, error)),
+            },
+        )
+    }
+
+    /// Apply the permissions or owner dialog's change to a whole folder tree
+    fn start_recursive_change(
+        &mut self,
+        tab_id: SessionId,
+        pane_id: PaneId,
+        path: std::path::PathBuf,
+        request: TreeChangeRequest,
+    ) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab(tab_id) else {
+            return Task::none();
+        };
+        if tab_state.permission_progress().is_some() {
+            return Task::none();
+        }
+        let sftp = match tab_state.pane(pane_id).source.session_id() {
+            None => None,
+            Some(session_id) => {
+                let Some(sftp) = self.sftp.get_connection(session_id).cloned() else {
+                    self.toast_manager
+                        .push(Toast::error("The pane is not connected"));
+                    return Task::none();
+                };
+                Some(sftp)
+            }
+        };
+
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        if let Some(dialog) = tab_state.dialog.as_mut() {
+            dialog.error = None;
+        }
+        let (walk_id, cancel_requested) = tab_state.start_permission_walk();
+        sftp_chmod_task(
+            tab_id,
+            pane_id,
+            walk_id,
+            path,
+            request,
+            sftp,
+            cancel_requested,
+        )
+    }
+
+    /// Open the Properties dialog for an entry of the active pane and read
+    /// its metadata
+    fn show_sftp_properties(
+        &mut self,
+        tab_id: SessionId,
+        name: String,
+        path: std::path::PathBuf,
+    ) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab(tab_id) else {
+            return Task::none();
+        };
+        let sftp = match tab_state.pane(tab_state.active_pane).source.session_id() {
+            Some(session_id) => match self.sftp.get_connection(session_id) {
+                Some(sftp) => Some(sftp.clone()),
+                None => return Task::none(),
+            },
+            None => None,
+        };
+        if let Some(tab_state) = self.sftp.get_tab_mut(tab_id) {
+            tab_state.show_properties_dialog(name, path.clone());
+        }
+        let read_path = path.clone();
+        Task::perform(
+            async move {
+                match sftp {
+                    Some(sftp) => sftp.properties(&read_path).await.map_err(|e| e.to_string()),
+                    None => tokio::task::spawn_blocking(move || local_properties(&read_path))
+                        .await
+                        .map_err(|e| e.to_string())?,
+                }
+            },
+            move |result| {
+                Message::Sftp(SftpMessage::PropertiesLoaded(tab_id, path.clone(), result))
+            },
+        )
+    }
+
+    /// Start calculating the size of the selected folders, or stop if they
+    /// are all being calculated already
+    fn toggle_sftp_dir_sizes(&mut self, tab_id: SessionId) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        tab_state.hide_context_menu();
+        let pane_id = tab_state.active_pane;
+        let pane = tab_state.pane(pane_id);
+        let paths: Vec<std::path::PathBuf> = pane
+            .selected_entries()
+            .iter()
+            .filter(|entry| entry.is_dir && !entry.is_symlink && !entry.is_parent())
+            .map(|entry| entry.path.clone())
+            .collect();
+        if paths.is_empty() {
+            return Task::none();
+        }
+
+        if paths.iter().all(|path| pane.is_calculating_size(path)) {
+            let pane = tab_state.pane_mut(pane_id);
+            for path in &paths {
+                pane.cancel_dir_size(path);
+            }
+            return Task::none();
+        }
+
+        let sftp = match pane.source.session_id() {
+            None => None,
+            Some(session_id) => {
+                let Some(sftp) = self.sftp.get_connection(session_id).cloned() else {
+                    self.toast_manager
+                        .push(Toast::error("The pane is not connected"));
+                    return Task::none();
+                };
+                Some(sftp)
+            }
+        };
+
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        let pane = tab_state.pane_mut(pane_id);
+        let mut tasks = Vec::new();
+        for path in paths {
+            if pane.is_calculating_size(&path) {
+                continue;
+            }
+            let (walk_id, cancel_requested) = pane.start_dir_size(path.clone());
+            tasks.push(sftp_dir_size_task(
+                tab_id,
+                pane_id,
+                walk_id,
+                path,
+                sftp.clone(),
+                cancel_requested,
+            ));
+        }
+        Task::batch(tasks)
+    }
+
+    /// Start or stop uploading the selected local file whenever it changes
+    fn toggle_sftp_file_watch(&mut self, tab_id: SessionId) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        tab_state.hide_context_menu();
+        let Some(entry) = tab_state.watch_candidate(tab_state.active_pane) else {
+            return Task::none();
+        };
+        let local_path = entry.path.clone();
+        let file_name = entry.name.clone();
+        let target_pane = tab_state.pane(match tab_state.active_pane {
+            PaneId::Left => PaneId::Right,
+            PaneId::Right => PaneId::Left,
+        });
+        let remote_path = target_pane.current_path.join(&file_name);
+        let remote_session = target_pane.source.session_id();
+
+        if let Some(watch_id) = self
+            .file_watches
+            .find(tab_id, &local_path)
+            .map(|watch| watch.id)
+        {
+            self.file_watches.remove(watch_id);
+            self.toast_manager
+                .push(Toast::success(format!("Stopped watching {}", file_name)));
+            return Task::none();
+        }
+
+        let Some(sftp) =
+            remote_session.and_then(|session_id| self.sftp.get_connection(session_id).cloned())
+        else {
+            self.toast_manager
+                .push(Toast::error("The remote pane is not connected"));
+            return Task::none();
+        };
+
+        let watch_id = Uuid::new_v4();
+        let (task, handle) =
+            file_watch::watch_and_upload(watch_id, local_path.clone(), remote_path.clone(), sftp)
+                .abortable();
+        tracing::info!(
+            "Watching {} for changes, uploading to {}",
+            local_path.display(),
+            remote_path.display()
+        );
+        self.file_watches.insert(FileWatch::new(
+            watch_id,
+            tab_id,
+            local_path,
+            remote_path.clone(),
+            handle,
+        ));
+        self.toast_manager.push(Toast::success(format!(
+            "Watching {} - changes upload to {}",
+            file_name,
+            remote_path.display()
+        )));
+        task
+    }
+
+    /// Run `command` on a file from an SFTP pane. Remote files are
+    /// downloaded to a temp folder first and the copy is watched so edits
+    /// can be uploaded back.
+    fn open_sftp_entry_with(
+        &mut self,
+        tab_id: SessionId,
+        source: PaneSource,
+        name: String,
+        path: std::path::PathBuf,
+        command: String,
+    ) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        let checked = match source {
+            PaneSource::Local => open_with::launch(&command, &path),
+            PaneSource::Remote { .. } => open_with::split_command(&command).map(|_| ()),
+        };
+        if let Err(error) = checked {
+            if let Some(dialog) = tab_state.dialog.as_mut() {
+                dialog.error = Some(error);
+            }
+            return Task::none();
+        }
+        tab_state.close_dialog();
+
+        let PaneSource::Remote { session_id, .. } = source else {
+            return Task::none();
+        };
+        let Some(sftp) = self.sftp.get_connection(session_id).cloned() else {
+            self.toast_manager
+                .push(Toast::error("The remote pane is not connected"));
+            return Task::none();
+        };
+
+        let edit_id = Uuid::new_v4();
+        Task::perform(
+            open_with::download_for_edit(sftp, path.clone(), name, edit_id),
+            move |result| {
+                Message::Sftp(SftpMessage::OpenWithDownloaded {
+                    tab_id,
+                    edit_id,
+                    session_id,
+                    remote_path: path.clone(),
+                    command: command.clone(),
+                    result,
+                })
+            },
+        )
+    }
+
+    /// Launch the program on a downloaded copy and start watching it for
+    /// edits to upload back
+    pub(super) fn start_open_with_edit(
+        &mut self,
+        tab_id: SessionId,
+        edit_id: Uuid,
+        session_id: SessionId,
+        remote_path: std::path::PathBuf,
+        command: &str,
+        local_path: std::path::PathBuf,
+    ) -> Task<Message> {
+        let dir = open_with::edit_temp_dir(edit_id);
+        // The tab may have closed while the file was downloading
+        if self.sftp.get_tab(tab_id).is_none() {
+            open_with::remove_edit_dir(&dir);
+            return Task::none();
+        }
+        if let Err(error) = open_with::launch(command, &local_path) {
+            open_with::remove_edit_dir(&dir);
+            self.toast_manager.push(Toast::error(error));
+            return Task::none();
+        }
+
+        let (task, handle) = file_watch::watch_for_edits(edit_id, local_path.clone()).abortable();
+        let watch = FileWatch::edit(
+            edit_id,
+            tab_id,
+            local_path,
+            remote_path,
+            EditedCopy { session_id, dir },
+            handle,
+        );
+        self.toast_manager.push(Toast::success(format!(
+            "Opened {} - saved changes can be uploaded back",
+            watch.label()
+        )));
+        self.file_watches.insert(watch);
+        task
+    }
+
+    /// Ask to upload an Open With copy that changed on disk
+    pub(super) fn prompt_edited_file_upload(&mut self, watch_id: Uuid) {
+        let Some(watch) = self.file_watches.record_change(watch_id) else {
+            return;
+        };
+        let toast = Toast::warning(format!(
+            "{} changed - click to upload it to {}",
+            watch.label(),
+            watch.remote_path.display()
+        ))
+        .persistent()
+        .action(ToastAction::UploadEditedFile(watch_id));
+        self.toast_manager.push_or_refresh(toast);
+    }
+
+    /// Upload an Open With copy back to the remote file it came from
+    pub(super) fn upload_edited_file(&mut self, watch_id: Uuid) -> Task<Message> {
+        self.toast_manager
+            .dismiss_action(ToastAction::UploadEditedFile(watch_id));
+        let Some(watch) = self.file_watches.get(watch_id) else {
+            return Task::none();
+        };
+        let Some(copy) = &watch.edited_copy else {
+            return Task::none();
+        };
+        let (local_path, remote_path) = (watch.local_path.clone(), watch.remote_path.clone());
+        let Some(sftp) = self.sftp.get_connection(copy.session_id).cloned() else {
+            let error = format!("Not connected to the server {} came from", watch.label());
+            self.toast_manager.push(Toast::error(error.clone()));
+            self.file_watches.record_upload(watch_id, Err(error));
+            return Task::none();
+        };
+
+        Task::perform(
+            async move {
+                sftp.upload(&local_path, &remote_path)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::Sftp(SftpMessage::WatchUploaded(watch_id, result)),
+        )
+    }
+
+    /// Open the sync preview for the active pane into the other pane
+    fn open_sftp_sync_dialog(&mut self, tab_id: SessionId) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab_mut(tab_id) else {
+            return Task::none();
+        };
+        if !tab_state.can_sync_panes() {
+            self.toast_manager
+                .push(Toast::warning("Sync needs one local and one remote pane"));
+            return Task::none();
+        }
+        tab_state.show_sync_dialog(SyncOptions::default());
+        self.plan_sftp_sync(tab_id)
+    }
+
+    /// Scan both panes for the open sync dialog (dry run)
+    pub(super) fn plan_sftp_sync(&mut self, tab_id: SessionId) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab(tab_id) else {
+            return Task::none();
+        };
+        let Some(dialog) = tab_state.dialog.as_ref() else {
+            return Task::none();
+        };
+        let SftpDialogType::Sync {
+            source_pane,
+            options,
+            ..
+        } = dialog.dialog_type
+        else {
+            return Task::none();
+        };
+
+        let source_pane = tab_state.pane(source_pane);
+        let target_pane = tab_state.pane(dialog.target_pane);
+        let (Some(source), Some(target)) = (
+            self.sftp_transfer_endpoint(&source_pane.source),
+            self.sftp_transfer_endpoint(&target_pane.source),
+        ) else {
+            return Task::none();
+        };
+        let source_root = source_pane.current_path.clone();
+        let target_root = target_pane.current_path.clone();
+
+        Task::perform(
+            scan_sftp_sync(source, source_root, target, target_root, options),
+            move |result| Message::Sftp(SftpMessage::SyncPlanned(tab_id, options, result)),
+        )
+    }
+
+    /// Run a previewed sync through the regular transfer queue
+    fn start_sftp_sync(
+        &mut self,
+        tab_id: SessionId,
+        source_pane_id: PaneId,
+        target_pane_id: PaneId,
+        plan: SyncPlan,
+    ) -> Task<Message> {
+        let Some(tab_state) = self.sftp.get_tab(tab_id) else {
+            return Task::none();
+        };
+        let (Some(source), Some(target)) = (
+            self.sftp_transfer_endpoint(&tab_state.pane(source_pane_id).source),
+            self.sftp_transfer_endpoint(&tab_state.pane(target_pane_id).source),
+        ) else {
+            return Task::none();
+        };
+
+        let to_transfer_entry = |item: &SyncItem| SftpTransferEntry {
+            name: sync_relative_name(&item.relative_path),
+            path: item.path.clone(),
+            is_dir: item.is_dir,
+            is_symlink: false,
+            size: item.size,
+        };
+        let request = SftpTransferRequest {
+            tab_id,
+            target_pane_id,
+            target_dir: tab_state.pane(target_pane_id).current_path.clone(),
+            source,
+            target,
+            entries: plan
+                .copies
+                .iter()
+                .map(|(item, _)| to_transfer_entry(item))
+                .collect(),
+            deletions: plan.deletions.iter().map(to_transfer_entry).collect(),
+            verify: self.prefs.sftp_verify_transfers,
+            resume: self.sftp_transfer_resume(),
+            retry: self.sftp_transfer_retry(),
+        };
+
+        if let Some(tab_state) = self.sftp.get_tab_mut(tab_id) {
+            tab_state.close_dialog();
+        }
+        self.start_sftp_transfer(request)
+    }
 }
 
 /// Label for the connecting dialog: "SSH" or "SSH via <jump chain>".
@@ -1723,6 +3567,15 @@ fn reject_symlink_copy(name: &str, is_symlink: bool) -> Result<(), String> {
     }
 }
 
+/// Text placed on the clipboard for "Copy Contents" / "Copy as Base64"
+fn clipboard_text_for(file_name: &str, bytes: Vec<u8>, as_base64: bool) -> Result<String, String> {
+    if as_base64 {
+        return Ok(data_encoding::BASE64.encode(&bytes));
+    }
+    String::from_utf8(bytes)
+        .map_err(|_| format!("{} is not a text file; use Copy as Base64", file_name))
+}
+
 fn reject_symlink_open(name: &str, is_symlink: bool) -> Result<(), String> {
     if is_symlink {
         Err(format!("Cannot open symbolic link {}", name))
@@ -1797,6 +3650,43 @@ async fn prepare_sftp_transfer_temp_dir(path: &std::path::Path) -> Result<(), St
     result
 }
 
+/// Name for a copy of `name` in the same directory: "notes copy.txt", then
+/// "notes copy 2.txt" and so on until it is not in `taken`. Folders and
+/// dotfiles keep the suffix at the end.
+fn duplicate_name(name: &str, is_dir: bool, taken: &std::collections::HashSet<String>) -> String {
+    let (stem, extension) = match name.rfind('.') {
+        Some(dot) if !is_dir && dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    };
+    (1..)
+        .map(|n| match n {
+            1 => format!("{stem} copy{extension}"),
+            n => format!("{stem} copy {n}{extension}"),
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_else(|| format!("{stem} copy{extension}"))
+}
+
+/// Read a small file from a pane: over SFTP when `sftp` is set, otherwise
+/// from the local file system
+async fn read_pane_file(
+    sftp: Option<SharedSftpSession>,
+    path: std::path::PathBuf,
+    limit: u64,
+) -> Result<Vec<u8>, String> {
+    match sftp {
+        Some(sftp) => sftp
+            .read_limited(&path, limit)
+            .await
+            .map_err(|e| e.to_string()),
+        None => tokio::task::spawn_blocking(move || {
+            crate::fs_utils::read_regular_file_limited(&path, limit, "File")
+        })
+        .await
+        .map_err(|e| format!("File read task failed: {}", e))?,
+    }
+}
+
 fn validated_sftp_child_name(input: &str) -> Result<String, String> {
     let name = input.trim();
     if name.is_empty() {
@@ -1900,6 +3790,36 @@ fn set_local_permissions(path: &std::path::Path, mode: u32) -> Result<(), String
     }
 }
 
+/// Turn typed owner and group into ids using the local account databases
+fn resolve_local_ownership(owner: &str, group: &str) -> Result<Ownership, String> {
+    let read = |path: &str| {
+        needs_account_lookup(owner, group)
+            .then(|| std::fs::read_to_string(path).ok())
+            .flatten()
+    };
+    resolve_ownership(
+        owner,
+        group,
+        read("/etc/passwd").as_deref(),
+        read("/etc/group").as_deref(),
+    )
+}
+
+fn set_local_owner(path: &std::path::Path, ownership: Ownership) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        let file = open_local_permissions_target(path, "change the owner of")?;
+        std::os::unix::fs::fchown(&file, ownership.uid, ownership.gid)
+            .map_err(|e| owner_change_error(&path.display().to_string(), &e.to_string()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (path, ownership);
+        Err("Owners are only supported on Unix systems".to_string())
+    }
+}
+
 fn read_local_permissions(path: &std::path::Path) -> Result<PermissionBits, String> {
     #[cfg(unix)]
     {
@@ -1970,12 +3890,15 @@ fn delete_local_path(path: &std::path::Path) -> std::io::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::{
-        delete_entry_is_recursive, delete_local_path, prepare_sftp_transfer_temp_dir,
-        read_local_permissions, reject_symlink_open, rename_local_path, set_local_permissions,
-        validated_sftp_child_name,
+        SftpTransferEndpoint, SftpTransferEntry, delete_entry_is_recursive, delete_local_path,
+        dropped_transfer_entry, prepare_sftp_transfer_temp_dir, read_local_permissions,
+        reject_symlink_open, rename_local_path, set_local_permissions, transfer_retry_delay,
+        validated_sftp_child_name, verify_transferred_file, with_resume_hint,
     };
     use crate::app::PendingConnect;
     use crate::message::Message;
+    use crate::sftp::TransferResume;
+    use crate::ssh::reconnect::ReconnectPolicy;
     use iced::Task;
     use std::io::ErrorKind;
     use uuid::Uuid;
@@ -2000,7 +3923,7 @@ mod tests {
         let session_id = Uuid::new_v4();
         let draft_tab_id = Uuid::new_v4();
         let (_task, handle) = Task::<Message>::none().abortable();
-        let pending = PendingConnect::new(session_id, Some(draft_tab_id), handle);
+        let pending = PendingConnect::new(session_id, Some(draft_tab_id), None, handle);
 
         assert!(pending.is_for(session_id));
         assert!(!pending.is_for(Uuid::new_v4()));
@@ -2021,6 +3944,23 @@ mod tests {
         assert!(error.contains("symbolic link"));
     }
 
+    #[test]
+    fn clipboard_text_for_rejects_binary_unless_base64() {
+        assert_eq!(
+            clipboard_text_for("a.conf", b"key = 1\n".to_vec(), false).unwrap(),
+            "key = 1\n"
+        );
+        assert!(
+            clipboard_text_for("a.bin", vec![0xff, 0x00], false)
+                .unwrap_err()
+                .contains("Copy as Base64")
+        );
+        assert_eq!(
+            clipboard_text_for("a.bin", vec![0xff, 0x00], true).unwrap(),
+            "/wA="
+        );
+    }
+
     #[test]
     fn delete_entry_is_recursive_only_for_real_directories() {
         assert!(delete_entry_is_recursive(true, false));
@@ -2029,6 +3969,79 @@ mod tests {
         assert!(!delete_entry_is_recursive(true, true));
     }
 
+    fn transfer_entry(is_dir: bool) -> SftpTransferEntry {
+        SftpTransferEntry {
+            name: "backup.tar".to_string(),
+            path: std::path::PathBuf::from("/srv/backup.tar"),
+            is_dir,
+            is_symlink: false,
+            size: 1024,
+        }
+    }
+
+    #[test]
+    fn failed_files_are_retried_up_to_the_reconnect_limit() {
+        let policy = Some(ReconnectPolicy::new(100, 1_000, 2));
+        let file = transfer_entry(false);
+
+        assert!(transfer_retry_delay(policy, &file, "Connection reset", 0).is_some());
+        assert!(transfer_retry_delay(policy, &file, "Connection reset", 1).is_some());
+        assert!(transfer_retry_delay(policy, &file, "Connection reset", 2).is_none());
+        assert!(transfer_retry_delay(policy, &file, "Transfer cancelled", 0).is_none());
+        assert!(
+            transfer_retry_delay(
+                policy,
+                &file,
+                "Failed to open remote staging file /srv/.backup.tar.portal-resume: Permission denied",
+                0
+            )
+            .is_none()
+        );
+        assert!(
+            transfer_retry_delay(policy, &transfer_entry(true), "Connection reset", 0).is_none()
+        );
+        assert!(transfer_retry_delay(None, &file, "Connection reset", 0).is_none());
+    }
+
+    #[test]
+    fn resume_hint_only_for_resumable_failures() {
+        let file = transfer_entry(false);
+        let hinted = with_resume_hint(TransferResume::Append, &file, "Timed out".to_string());
+        assert!(hinted.contains("copy again to resume"));
+        assert_eq!(
+            with_resume_hint(TransferResume::Off, &file, "Timed out".to_string()),
+            "Timed out"
+        );
+        assert_eq!(
+            with_resume_hint(
+                TransferResume::Append,
+                &file,
+                "Transfer cancelled".to_string()
+            ),
+            "Transfer cancelled"
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_transferred_file_reports_checksum_mismatch() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source.bin");
+        let copy = temp.path().join("copy.bin");
+        let corrupt = temp.path().join("corrupt.bin");
+        std::fs::write(&source, b"payload").unwrap();
+        std::fs::write(&copy, b"payload").unwrap();
+        std::fs::write(&corrupt, b"paylaod").unwrap();
+        let local = SftpTransferEndpoint::Local;
+
+        verify_transferred_file(&local, &source, &local, &copy)
+            .await
+            .expect("identical files should verify");
+        let error = verify_transferred_file(&local, &source, &local, &corrupt)
+            .await
+            .expect_err("different contents should not verify");
+        assert!(error.contains("Checksum mismatch"));
+    }
+
     #[tokio::test]
     async fn prepare_sftp_transfer_temp_dir_creates_directory() {
         let temp = tempfile::tempdir().unwrap();
@@ -2089,6 +4102,50 @@ mod tests {
         assert!(std::fs::read_dir(target).unwrap().next().is_none());
     }
 
+    #[test]
+    fn dropped_transfer_entry_describes_files_and_directories() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("notes.txt");
+        std::fs::write(&file, "hello").unwrap();
+
+        let entry = dropped_transfer_entry(&file).unwrap();
+        assert_eq!(entry.name, "notes.txt");
+        assert!(!entry.is_dir);
+        assert_eq!(entry.size, 5);
+
+        assert!(dropped_transfer_entry(temp.path()).unwrap().is_dir);
+        assert!(dropped_transfer_entry(&temp.path().join("missing")).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn dropped_transfer_entry_rejects_symlinks() {
+        let temp = tempfile::tempdir().unwrap();
+        let link = temp.path().join("link");
+        std::os::unix::fs::symlink(temp.path(), &link).unwrap();
+
+        let error = dropped_transfer_entry(&link).unwrap_err();
+        assert!(error.contains("symbolic link"));
+    }
+
+    #[test]
+    fn duplicate_name_keeps_extension_and_skips_taken_names() {
+        let taken: std::collections::HashSet<String> =
+            ["config.bak", "config copy.bak", "config copy 2.bak", "site"]
+                .into_iter()
+                .map(String::from)
+                .collect();
+
+        assert_eq!(
+            duplicate_name("config.bak", false, &taken),
+            "config copy 3.bak"
+        );
+        assert_eq!(duplicate_name("site", true, &taken), "site copy");
+        assert_eq!(duplicate_name("v1.2", true, &taken), "v1.2 copy");
+        assert_eq!(duplicate_name(".bashrc", false, &taken), ".bashrc copy");
+        assert_eq!(duplicate_name("Makefile", false, &taken), "Makefile copy");
+    }
+
     #[test]
     fn validated_sftp_child_name_accepts_plain_name_and_trims() {
         let name = validated_sftp_child_name("  folder  ").unwrap();
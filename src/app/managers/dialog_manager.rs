@@ -10,8 +10,11 @@ use crate::views::dialogs::host_dialog::HostDialogState;
 use crate::views::dialogs::host_key_dialog::HostKeyDialogState;
 use crate::views::dialogs::passphrase_dialog::PassphraseDialogState;
 use crate::views::dialogs::password_dialog::PasswordDialogState;
+use crate::views::dialogs::port_forwards_dialog::PortForwardsDialogState;
 use crate::views::dialogs::quick_connect_dialog::QuickConnectDialogState;
+use crate::views::dialogs::quit_confirm_dialog::QuitConfirmDialogState;
 use crate::views::dialogs::session_choice_dialog::SessionChoiceDialogState;
+use crate::views::dialogs::snippet_params_dialog::SnippetParamsDialogState;
 use crate::views::dialogs::vnc_cleartext_dialog::VncCleartextDialogState;
 
 /// The currently active dialog, if any
@@ -41,6 +44,12 @@ pub enum ActiveDialog {
     SessionChoice(SessionChoiceDialogState),
     /// Warning before connecting VNC to a non-private target without a tunnel
     VncCleartextWarning(VncCleartextDialogState),
+    /// Confirmation before quitting with running sessions or transfers
+    QuitConfirm(QuitConfirmDialogState),
+    /// Runtime port forward panel for an SSH session
+    PortForwards(PortForwardsDialogState),
+    /// Values for a snippet's placeholders before it runs
+    SnippetParams(SnippetParamsDialogState),
     /// Portal Hub onboarding wizard
     PortalHubOnboarding,
     /// Portal Hub sync conflict resolver
@@ -55,6 +64,9 @@ pub struct DialogManager {
     active: ActiveDialog,
     /// Current focused field index in host dialog (for Tab navigation)
     pub host_dialog_focus: usize,
+    /// Host dialog set aside while its connection test waits on a host key
+    /// or keyboard-interactive dialog; restored when that dialog closes
+    suspended_host: Option<HostDialogState>,
 }
 
 impl DialogManager {
@@ -63,6 +75,7 @@ impl DialogManager {
         Self {
             active: ActiveDialog::None,
             host_dialog_focus: 0,
+            suspended_host: None,
         }
     }
 
@@ -71,9 +84,13 @@ impl DialogManager {
         !matches!(self.active, ActiveDialog::None)
     }
 
-    /// Close any open dialog
+    /// Close any open dialog, returning to a host dialog that was set aside
+    /// for its connection test
     pub fn close(&mut self) {
-        self.active = ActiveDialog::None;
+        self.active = match self.suspended_host.take() {
+            Some(state) => ActiveDialog::Host(state),
+            None => ActiveDialog::None,
+        };
     }
 
     /// Get a reference to the active dialog
@@ -85,6 +102,7 @@ impl DialogManager {
 
     /// Open the host dialog with the given state
     pub fn open_host(&mut self, state: HostDialogState) {
+        self.suspended_host = None;
         self.active = ActiveDialog::Host(state);
         self.host_dialog_focus = 0; // Reset focus to first field
     }
@@ -97,10 +115,21 @@ impl DialogManager {
         }
     }
 
+    /// Keep a host dialog whose connection test is running so the prompt
+    /// the test raised does not discard the form
+    fn suspend_testing_host(&mut self) {
+        if matches!(&self.active, ActiveDialog::Host(state) if state.is_testing())
+            && let ActiveDialog::Host(state) = std::mem::take(&mut self.active)
+        {
+            self.suspended_host = Some(state);
+        }
+    }
+
     // ---- Host key dialog operations ----
 
     /// Open the host key dialog with the given state
     pub fn open_host_key(&mut self, state: HostKeyDialogState) {
+        self.suspend_testing_host();
         self.active = ActiveDialog::HostKey(state);
     }
 
@@ -116,6 +145,7 @@ impl DialogManager {
 
     /// Open the keyboard-interactive auth prompt dialog
     pub fn open_auth_prompt(&mut self, state: AuthPromptDialogState) {
+        self.suspend_testing_host();
         self.active = ActiveDialog::AuthPrompt(state);
     }
 
@@ -229,6 +259,43 @@ impl DialogManager {
         }
     }
 
+    // ---- Quit confirmation dialog operations ----
+
+    /// Open the quit confirmation dialog
+    pub fn open_quit_confirm(&mut self, state: QuitConfirmDialogState) {
+        self.active = ActiveDialog::QuitConfirm(state);
+    }
+
+    /// Get mutable quit confirmation dialog state if it is active
+    pub fn quit_confirm_mut(&mut self) -> Option<&mut QuitConfirmDialogState> {
+        match &mut self.active {
+            ActiveDialog::QuitConfirm(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    // ---- Snippet parameter dialog operations ----
+
+    /// Open the snippet parameter dialog
+    pub fn open_snippet_params(&mut self, state: SnippetParamsDialogState) {
+        self.active = ActiveDialog::SnippetParams(state);
+    }
+
+    /// Get mutable snippet parameter dialog state if it is active
+    pub fn snippet_params_mut(&mut self) -> Option<&mut SnippetParamsDialogState> {
+        match &mut self.active {
+            ActiveDialog::SnippetParams(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    // ---- Port forwards panel operations ----
+
+    /// Open the port forwards panel for a session
+    pub fn open_port_forwards(&mut self, state: PortForwardsDialogState) {
+        self.active = ActiveDialog::PortForwards(state);
+    }
+
     /// Open the Portal Hub onboarding wizard.
     pub fn open_portal_hub_onboarding(&mut self) {
         self.active = ActiveDialog::PortalHubOnboarding;
@@ -267,6 +334,7 @@ impl DialogManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::views::dialogs::host_dialog::ConnectionTestStatus;
 
     #[test]
     fn test_new_manager_has_no_dialog() {
@@ -310,4 +378,46 @@ mod tests {
         manager.close();
         assert!(!manager.is_open());
     }
+
+    fn host_key_dialog() -> HostKeyDialogState {
+        HostKeyDialogState {
+            host: "example.com".to_string(),
+            port: 22,
+            key_type: "ssh-ed25519".to_string(),
+            fingerprint: "SHA256:test".to_string(),
+            old_fingerprint: None,
+            is_changed_host: false,
+            responder: None,
+        }
+    }
+
+    #[test]
+    fn host_key_prompt_returns_to_a_testing_host_dialog() {
+        let mut manager = DialogManager::new();
+        let mut state = HostDialogState::new_host();
+        state.name = "prod".to_string();
+        state.connection_test = Some(ConnectionTestStatus::Running(uuid::Uuid::new_v4()));
+        manager.open_host(state);
+
+        manager.open_host_key(host_key_dialog());
+        assert!(matches!(manager.active(), ActiveDialog::HostKey(_)));
+
+        manager.close();
+        assert_eq!(
+            manager.host_mut().map(|state| state.name.as_str()),
+            Some("prod")
+        );
+        manager.close();
+        assert!(!manager.is_open());
+    }
+
+    #[test]
+    fn host_key_prompt_replaces_an_idle_host_dialog() {
+        let mut manager = DialogManager::new();
+        manager.open_host(HostDialogState::new_host());
+        manager.open_host_key(host_key_dialog());
+
+        manager.close();
+        assert!(!manager.is_open());
+    }
 }
@@ -0,0 +1,273 @@
+//! Watched local files that are uploaded whenever they change
+//!
+//! Each watch owns the abort handle of its polling task, so removing a watch
+//! (or closing its SFTP tab) stops the uploads. Watches of remote files
+//! opened with an external program also own the folder holding the
+//! downloaded copy, which is deleted with them.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use uuid::Uuid;
+
+use crate::app::services::open_with;
+use crate::message::SessionId;
+
+/// Remote file downloaded to a temp folder and opened with an external
+/// program
+pub struct EditedCopy {
+    /// SFTP connection the file was downloaded over
+    pub session_id: SessionId,
+    /// Folder holding the copy, deleted when the watch stops
+    pub dir: PathBuf,
+}
+
+/// A local file kept in sync with a remote path
+pub struct FileWatch {
+    pub id: Uuid,
+    /// SFTP tab the watch was started from
+    pub tab_id: SessionId,
+    pub local_path: PathBuf,
+    pub remote_path: PathBuf,
+    /// When the last upload finished
+    pub last_synced: Option<DateTime<Local>>,
+    /// Error from the most recent upload, cleared by the next success
+    pub last_error: Option<String>,
+    /// Set for a remote file opened with an external program; changes to
+    /// its copy are only uploaded when the user confirms
+    pub edited_copy: Option<EditedCopy>,
+    /// The edited copy changed since it was last uploaded
+    pub pending_change: bool,
+    handle: iced::task::Handle,
+}
+
+impl FileWatch {
+    pub fn new(
+        id: Uuid,
+        tab_id: SessionId,
+        local_path: PathBuf,
+        remote_path: PathBuf,
+        handle: iced::task::Handle,
+    ) -> Self {
+        Self {
+            id,
+            tab_id,
+            local_path,
+            remote_path,
+            last_synced: None,
+            last_error: None,
+            edited_copy: None,
+            pending_change: false,
+            handle,
+        }
+    }
+
+    /// Watch of a downloaded copy that was opened with an external program
+    pub fn edit(
+        id: Uuid,
+        tab_id: SessionId,
+        local_path: PathBuf,
+        remote_path: PathBuf,
+        edited_copy: EditedCopy,
+        handle: iced::task::Handle,
+    ) -> Self {
+        Self {
+            edited_copy: Some(edited_copy),
+            ..Self::new(id, tab_id, local_path, remote_path, handle)
+        }
+    }
+
+    /// Changes wait for the user before being uploaded
+    pub fn is_edit(&self) -> bool {
+        self.edited_copy.is_some()
+    }
+
+    fn stop(&self) {
+        self.handle.abort();
+        if let Some(copy) = &self.edited_copy {
+            open_with::remove_edit_dir(&copy.dir);
+        }
+    }
+
+    /// File name shown in the watch list
+    pub fn label(&self) -> String {
+        self.local_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.local_path.display().to_string())
+    }
+}
+
+/// Tracks active file watches across SFTP tabs
+#[derive(Default)]
+pub struct FileWatchManager {
+    watches: Vec<FileWatch>,
+}
+
+impl FileWatchManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, watch: FileWatch) {
+        self.watches.push(watch);
+    }
+
+    /// Stop a watch; returns it if it existed
+    pub fn remove(&mut self, id: Uuid) -> Option<FileWatch> {
+        let index = self.watches.iter().position(|watch| watch.id == id)?;
+        let watch = self.watches.remove(index);
+        watch.stop();
+        Some(watch)
+    }
+
+    /// Stop every watch started from an SFTP tab
+    pub fn remove_for_tab(&mut self, tab_id: SessionId) {
+        self.watches.retain(|watch| {
+            if watch.tab_id == tab_id {
+                watch.stop();
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Stop every watch before quitting, deleting downloaded copies
+    pub fn clear(&mut self) {
+        for watch in self.watches.drain(..) {
+            watch.stop();
+        }
+    }
+
+    /// The watch for a local file in a tab, if any
+    pub fn find(&self, tab_id: SessionId, local_path: &Path) -> Option<&FileWatch> {
+        self.watches
+            .iter()
+            .find(|watch| watch.tab_id == tab_id && watch.local_path == local_path)
+    }
+
+    pub fn for_tab(&self, tab_id: SessionId) -> Vec<&FileWatch> {
+        self.watches
+            .iter()
+            .filter(|watch| watch.tab_id == tab_id)
+            .collect()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&FileWatch> {
+        self.watches.iter().find(|watch| watch.id == id)
+    }
+
+    /// Note that an edited copy changed; returns the watch when it waits
+    /// for the user to upload
+    pub fn record_change(&mut self, id: Uuid) -> Option<&FileWatch> {
+        let watch = self.watches.iter_mut().find(|watch| watch.id == id)?;
+        if !watch.is_edit() {
+            return None;
+        }
+        watch.pending_change = true;
+        Some(watch)
+    }
+
+    /// Record the outcome of an upload triggered by a change
+    pub fn record_upload(&mut self, id: Uuid, result: Result<(), String>) {
+        let Some(watch) = self.watches.iter_mut().find(|watch| watch.id == id) else {
+            return;
+        };
+        match result {
+            Ok(()) => {
+                watch.last_synced = Some(Local::now());
+                watch.last_error = None;
+                watch.pending_change = false;
+            }
+            Err(error) => watch.last_error = Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Message;
+    use iced::Task;
+
+    fn watch(tab_id: SessionId, local: &str) -> FileWatch {
+        let (_task, handle) = Task::<Message>::none().abortable();
+        FileWatch::new(
+            Uuid::new_v4(),
+            tab_id,
+            PathBuf::from(local),
+            PathBuf::from("/etc/app.conf"),
+            handle,
+        )
+    }
+
+    #[test]
+    fn remove_for_tab_keeps_other_tabs() {
+        let tab_a = Uuid::new_v4();
+        let tab_b = Uuid::new_v4();
+        let mut manager = FileWatchManager::new();
+        manager.insert(watch(tab_a, "/tmp/a.conf"));
+        manager.insert(watch(tab_b, "/tmp/b.conf"));
+
+        manager.remove_for_tab(tab_a);
+
+        assert!(manager.for_tab(tab_a).is_empty());
+        assert!(manager.find(tab_b, Path::new("/tmp/b.conf")).is_some());
+    }
+
+    #[test]
+    fn removing_an_edit_deletes_its_downloaded_copy() {
+        let tab_id = Uuid::new_v4();
+        let dir = tempfile::tempdir().unwrap();
+        let edit_dir = dir.path().join("edit");
+        std::fs::create_dir(&edit_dir).unwrap();
+        std::fs::write(edit_dir.join("app.conf"), "port = 80").unwrap();
+        let (_task, handle) = Task::<Message>::none().abortable();
+        let edit = FileWatch::edit(
+            Uuid::new_v4(),
+            tab_id,
+            edit_dir.join("app.conf"),
+            PathBuf::from("/etc/app.conf"),
+            EditedCopy {
+                session_id: Uuid::new_v4(),
+                dir: edit_dir.clone(),
+            },
+            handle,
+        );
+        let id = edit.id;
+        let mut manager = FileWatchManager::new();
+        manager.insert(edit);
+        manager.insert(watch(tab_id, "/tmp/b.conf"));
+
+        assert!(manager.record_change(id).is_some());
+        assert!(manager.get(id).unwrap().pending_change);
+        let plain_id = manager.find(tab_id, Path::new("/tmp/b.conf")).unwrap().id;
+        assert!(manager.record_change(plain_id).is_none());
+
+        manager.record_upload(id, Ok(()));
+        assert!(!manager.get(id).unwrap().pending_change);
+
+        manager.remove_for_tab(tab_id);
+        assert!(!edit_dir.exists());
+    }
+
+    #[test]
+    fn record_upload_tracks_last_sync_and_error() {
+        let tab_id = Uuid::new_v4();
+        let mut manager = FileWatchManager::new();
+        let entry = watch(tab_id, "/tmp/app.conf");
+        let id = entry.id;
+        manager.insert(entry);
+
+        manager.record_upload(id, Err("Permission denied".to_string()));
+        let current = manager.find(tab_id, Path::new("/tmp/app.conf")).unwrap();
+        assert_eq!(current.last_error.as_deref(), Some("Permission denied"));
+        assert!(current.last_synced.is_none());
+
+        manager.record_upload(id, Ok(()));
+        let current = manager.find(tab_id, Path::new("/tmp/app.conf")).unwrap();
+        assert!(current.last_error.is_none());
+        assert!(current.last_synced.is_some());
+    }
+}
@@ -5,22 +5,34 @@
 
 mod dialog_manager;
 mod file_viewer_manager;
+mod file_watch_manager;
 mod proxy_sessions_manager;
+mod reachability_manager;
 pub mod session_manager;
+mod session_restore_manager;
 mod sftp_manager;
 mod snippet_execution_manager;
+mod terminal_pane_manager;
 mod transfer_manager;
 
 pub use dialog_manager::{ActiveDialog, DialogManager};
 pub use file_viewer_manager::FileViewerManager;
+pub use file_watch_manager::{EditedCopy, FileWatch, FileWatchManager};
 pub use proxy_sessions_manager::{ProxySessionCard, ProxySessionsState};
+pub use reachability_manager::{Reachability, ReachabilityManager};
 pub use session_manager::{
-    ActiveSession, SessionBackend, SessionManager, TerminalPreviewHandle, VncActiveSession,
+    ActiveSession, ForwardStatus, LastCommand, SessionBackend, SessionManager,
+    TerminalPreviewHandle, VncActiveSession, ZmodemFile, ZmodemTransfer,
 };
+pub use session_restore_manager::{RestoreStep, SessionRestoreManager};
 pub use sftp_manager::SftpManager;
 pub use snippet_execution_manager::{
     ExecutionStatus, HostResult, SnippetExecution, SnippetExecutionManager,
 };
+pub use terminal_pane_manager::{
+    PaneClosed, PaneColorScheme, PaneViewPrefs, PendingSplit, TerminalPaneLayout,
+    TerminalPaneManager,
+};
 pub use transfer_manager::{
     TransferDirection, TransferItem, TransferItemInit, TransferManager, TransferProgress,
     TransferStatus,
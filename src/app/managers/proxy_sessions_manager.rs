@@ -280,11 +280,19 @@ mod tests {
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
             auth: AuthMethod::Agent,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Hub,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at: now,
@@ -300,6 +308,7 @@ mod tests {
         let hosts = HostsConfig {
             hosts: vec![host.clone()],
             groups: Vec::new(),
+            ..Default::default()
         };
         let session = listed_session("192.0.2.206", 22, "john");
 
@@ -315,6 +324,7 @@ mod tests {
         let hosts = HostsConfig {
             hosts: vec![host.clone()],
             groups: Vec::new(),
+            ..Default::default()
         };
         let mut session = listed_session("192.0.2.206", 22, "john");
         session.display_name = Some("Production deploy".to_string());
@@ -389,6 +399,7 @@ mod tests {
         let mut hosts = HostsConfig {
             hosts: vec![host.clone()],
             groups: Vec::new(),
+            ..Default::default()
         };
         let session = listed_session("192.0.2.206", 22, "john");
         let mut state = ProxySessionsState::new();
@@ -0,0 +1,123 @@
+//! Reachability of hosts shown in the host grid
+//!
+//! Results come from background TCP probes and are trusted for
+//! `REACHABILITY_TTL`, so scrolling or re-rendering the grid never re-probes
+//! a host that was checked moments ago.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// How long a probe result is trusted before the host is probed again
+pub const REACHABILITY_TTL: Duration = Duration::from_secs(60);
+
+/// Outcome of the last reachability probe for a host
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Reachability {
+    /// Not probed yet, or not probed directly (jump host, Portal Hub)
+    #[default]
+    Unknown,
+    Reachable,
+    Unreachable,
+}
+
+/// Tracks probe results and in-flight probes by host
+#[derive(Default)]
+pub struct ReachabilityManager {
+    statuses: HashMap<Uuid, Reachability>,
+    checked_at: HashMap<Uuid, Instant>,
+    in_flight: HashSet<Uuid>,
+}
+
+impl ReachabilityManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn statuses(&self) -> &HashMap<Uuid, Reachability> {
+        &self.statuses
+    }
+
+    /// Claim the hosts whose result is missing or stale and that are not
+    /// already being probed. Claimed hosts stay in flight until `record`.
+    pub fn claim_due(
+        &mut self,
+        host_ids: impl IntoIterator<Item = Uuid>,
+        now: Instant,
+    ) -> Vec<Uuid> {
+        let mut due = Vec::new();
+        for host_id in host_ids {
+            let fresh = self
+                .checked_at
+                .get(&host_id)
+                .is_some_and(|checked| now.duration_since(*checked) < REACHABILITY_TTL);
+            if fresh || !self.in_flight.insert(host_id) {
+                continue;
+            }
+            due.push(host_id);
+        }
+        due
+    }
+
+    /// Store a probe result. Results for probes that are no longer in flight
+    /// (e.g. started before probing was turned off) are dropped.
+    pub fn record(&mut self, host_id: Uuid, reachable: bool, now: Instant) {
+        if !self.in_flight.remove(&host_id) {
+            return;
+        }
+        let status = if reachable {
+            Reachability::Reachable
+        } else {
+            Reachability::Unreachable
+        };
+        self.statuses.insert(host_id, status);
+        self.checked_at.insert(host_id, now);
+    }
+
+    /// Forget every result, e.g. when probing is turned off
+    pub fn clear(&mut self) {
+        self.statuses.clear();
+        self.checked_at.clear();
+        self.in_flight.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_and_in_flight_hosts_are_not_probed_again() {
+        let mut manager = ReachabilityManager::new();
+        let up = Uuid::new_v4();
+        let pending = Uuid::new_v4();
+        let now = Instant::now();
+
+        assert_eq!(manager.claim_due([up, pending], now), vec![up, pending]);
+        assert!(manager.claim_due([up, pending], now).is_empty());
+
+        manager.record(up, true, now);
+        assert_eq!(manager.statuses().get(&up), Some(&Reachability::Reachable));
+        assert!(
+            manager
+                .claim_due([up], now + Duration::from_secs(1))
+                .is_empty()
+        );
+        assert_eq!(manager.claim_due([up], now + REACHABILITY_TTL), vec![up]);
+    }
+
+    #[test]
+    fn results_after_clear_are_dropped() {
+        let mut manager = ReachabilityManager::new();
+        let host = Uuid::new_v4();
+        let now = Instant::now();
+
+        manager.claim_due([host], now);
+        manager.clear();
+        manager.record(host, false, now);
+
+        assert!(manager.statuses().is_empty());
+        assert_eq!(manager.claim_due([host], now), vec![host]);
+    }
+}
@@ -9,6 +9,8 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+use crate::config::settings::VncKeyboardMode;
+use crate::config::{PortForward, PortForwardKind};
 use crate::local::LocalSession;
 use crate::message::SessionId;
 use crate::proxy::ProxySession;
@@ -16,6 +18,7 @@ use crate::ssh::SshSession;
 use crate::terminal::backend::EventProxy;
 use crate::terminal::logger::SessionLogger;
 use crate::terminal::search::TerminalSearchState;
+use crate::terminal::zmodem::ZmodemReceiver;
 use crate::views::terminal_view::TerminalSession;
 use crate::vnc::VncSession;
 use alacritty_terminal::term::Term;
@@ -71,6 +74,8 @@ pub struct ActiveSession {
     pub terminal_agent_turn_started_at: Option<(String, Instant)>,
     /// Last desktop notification emitted for this terminal session.
     pub last_terminal_notification_at: Option<Instant>,
+    /// Last terminal bell acted on, for debouncing bell floods.
+    pub last_bell_at: Option<Instant>,
     /// Short guard used after seeding a resumed Portal Hub screen snapshot.
     pub resume_snapshot_protected_until: Option<Instant>,
     /// Working directory reported by the shell via OSC 7, used to resolve
@@ -80,6 +85,90 @@ pub struct ActiveSession {
     pub logger: Option<SessionLogger>,
     /// Scrollback search (find-in-buffer) state for this session's terminal.
     pub search: TerminalSearchState,
+    /// Active ZMODEM (`sz`) receive; terminal output goes to it instead of the
+    /// terminal while set.
+    pub zmodem: Option<ZmodemTransfer>,
+    /// Result of the last command reported by shell integration (OSC 133);
+    /// `None` for shells without integration.
+    pub last_command: Option<LastCommand>,
+    /// Input captured while a macro is being recorded, one entry per write
+    pub macro_recording: Option<Vec<Vec<u8>>>,
+    /// Runtime state of the host's port forwards, keyed by forward id
+    pub forward_status: HashMap<Uuid, ForwardStatus>,
+    /// Font size set with the zoom shortcuts; `None` follows the setting
+    pub font_size: Option<f32>,
+}
+
+impl ActiveSession {
+    /// Number of running forwards, for the status bar summary
+    pub fn active_forward_count(&self) -> usize {
+        self.forward_status
+            .values()
+            .filter(|status| matches!(status, ForwardStatus::Active(_)))
+            .count()
+    }
+
+    /// Bound port of the first running dynamic (SOCKS) forward
+    pub fn socks_port(&self, forwards: &[PortForward]) -> Option<u16> {
+        forwards
+            .iter()
+            .filter(|forward| forward.kind == PortForwardKind::Dynamic)
+            .find_map(|forward| match self.forward_status.get(&forward.id) {
+                Some(ForwardStatus::Active(port)) => Some(*port),
+                _ => None,
+            })
+    }
+}
+
+/// Runtime state of a single port forward on an SSH session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForwardStatus {
+    /// Listener is bound on the given port and accepting connections
+    Active(u16),
+    /// Forward is not running (disabled, or stopped from the panel)
+    Stopped,
+    /// Forward could not be established, e.g. the port is already in use
+    Failed(String),
+}
+
+/// Exit status and wall-clock time of a finished shell command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastCommand {
+    pub exit_status: Option<i32>,
+    pub duration: Duration,
+}
+
+/// A ZMODEM receive in progress on a terminal session
+pub struct ZmodemTransfer {
+    pub receiver: ZmodemReceiver,
+    /// Folder files are saved to; `None` while the user is being asked
+    pub save_dir: Option<PathBuf>,
+    /// Session output received before a folder was chosen
+    pub pending: Vec<u8>,
+    /// File currently being written
+    pub current: Option<ZmodemFile>,
+    /// Files received completely
+    pub completed: Vec<PathBuf>,
+}
+
+impl ZmodemTransfer {
+    pub fn new(initial: Vec<u8>) -> Self {
+        Self {
+            receiver: ZmodemReceiver::new(),
+            save_dir: None,
+            pending: initial,
+            current: None,
+            completed: Vec::new(),
+        }
+    }
+}
+
+/// Destination of the file currently being received over ZMODEM
+pub struct ZmodemFile {
+    pub path: PathBuf,
+    pub writer: std::io::BufWriter<std::fs::File>,
+    pub received: u64,
+    pub size: Option<u64>,
 }
 
 /// Active VNC session
@@ -105,14 +194,17 @@ pub struct VncActiveSession {
     pub status_text: String,
     /// Whether fullscreen mode is active
     pub fullscreen: bool,
-    /// Whether keyboard passthrough is active (all keys go to VNC)
-    pub keyboard_passthrough: bool,
+    /// How key presses become keysyms; passthrough sends all keys to VNC
+    pub keyboard_mode: VncKeyboardMode,
     /// Whether local input forwarding is disabled
     pub view_only: bool,
     /// Whether the local cursor position dot is drawn over the framebuffer
     pub show_cursor_dot: bool,
     /// Whether to show detailed VNC stats over the framebuffer
     pub show_stats_overlay: bool,
+    /// Clipboard text last exchanged with the server, so the auto-send
+    /// check neither repeats it nor echoes back what the server sent
+    pub shared_clipboard: Option<String>,
     /// History entry ID for marking disconnection
     pub history_entry_id: uuid::Uuid,
 }
@@ -160,6 +252,11 @@ impl SessionManager {
         self.sessions.is_empty()
     }
 
+    /// Number of open sessions
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
     /// Check if any session has pending output to process
     pub fn has_pending_output(&self) -> bool {
         self.sessions
@@ -167,6 +264,13 @@ impl SessionManager {
             .any(|session| session.pending_output_bytes > 0)
     }
 
+    /// Whether any terminal is showing a visual bell flash.
+    pub fn has_bell_flash(&self) -> bool {
+        self.sessions
+            .values()
+            .any(|session| session.terminal.is_bell_flashing())
+    }
+
     /// Count active terminal sessions per host.
     pub fn session_counts_by_host(&self) -> std::collections::HashMap<Uuid, usize> {
         let mut counts = std::collections::HashMap::new();
@@ -203,12 +307,9 @@ impl SessionManager {
 
     /// Get the log file path for a session if logging is enabled
     pub fn log_path(&self, id: SessionId) -> Option<PathBuf> {
-        self.sessions.get(&id).and_then(|session| {
-            session
-                .logger
-                .as_ref()
-                .map(|logger| logger.path().to_path_buf())
-        })
+        self.sessions
+            .get(&id)
+            .and_then(|session| session.logger.as_ref().map(|logger| logger.path()))
     }
 }
 
@@ -248,10 +349,16 @@ mod tests {
             last_backlog_warning_at: None,
             terminal_agent_turn_started_at: None,
             last_terminal_notification_at: None,
+            last_bell_at: None,
             resume_snapshot_protected_until: None,
             cwd: None,
             logger: None,
             search: TerminalSearchState::default(),
+            zmodem: None,
+            last_command: None,
+            macro_recording: None,
+            forward_status: HashMap::new(),
+            font_size: None,
         }
     }
 
@@ -558,4 +665,49 @@ mod tests {
 
         assert!(manager.get(session_id).unwrap().status_message.is_none());
     }
+
+    #[test]
+    fn test_active_forward_count_ignores_stopped_and_failed() {
+        let mut session = create_test_session("test");
+        session
+            .forward_status
+            .insert(Uuid::new_v4(), ForwardStatus::Active(8080));
+        session
+            .forward_status
+            .insert(Uuid::new_v4(), ForwardStatus::Stopped);
+        session.forward_status.insert(
+            Uuid::new_v4(),
+            ForwardStatus::Failed("address in use".to_string()),
+        );
+
+        assert_eq!(session.active_forward_count(), 1);
+    }
+
+    #[test]
+    fn test_socks_port_reports_running_dynamic_forward() {
+        let mut session = create_test_session("test");
+        let forward = |kind| PortForward {
+            id: Uuid::new_v4(),
+            kind,
+            bind_host: "localhost".to_string(),
+            bind_port: 0,
+            target_host: String::new(),
+            target_port: 0,
+            enabled: true,
+            description: None,
+        };
+        let local = forward(PortForwardKind::Local);
+        let dynamic = forward(PortForwardKind::Dynamic);
+        session
+            .forward_status
+            .insert(local.id, ForwardStatus::Active(8080));
+        let forwards = [local, dynamic.clone()];
+
+        assert_eq!(session.socks_port(&forwards), None);
+
+        session
+            .forward_status
+            .insert(dynamic.id, ForwardStatus::Active(1080));
+        assert_eq!(session.socks_port(&forwards), Some(1080));
+    }
 }
@@ -0,0 +1,244 @@
+//! Restoring the tabs that were open when Portal last exited
+//!
+//! The open tabs are written to `open_sessions.toml` as they change. On the
+//! next start the user is offered to reopen them; accepting queues one step
+//! per connection, and steps run one at a time because only a single SSH or
+//! SFTP connect can be pending.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::config::{OpenSessionsState, SavedSftpPane, SavedSftpTab};
+use crate::message::SessionId;
+use crate::views::sftp::PaneId;
+
+/// Time on top of the configured connect timeout to wait on an SFTP pane
+/// connect before moving on. Failed connects do not clear the pending
+/// connection, so this bounds the wait.
+const SFTP_CONNECT_MARGIN: Duration = Duration::from_secs(5);
+
+/// How long to wait on an SFTP pane connect that gives up after
+/// `connect_timeout`
+fn sftp_connect_wait(connect_timeout: Duration) -> Duration {
+    connect_timeout.saturating_add(SFTP_CONNECT_MARGIN)
+}
+
+/// One unit of work while restoring
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreStep {
+    /// Open a terminal to the host
+    Terminal(Uuid),
+    /// Open an SFTP tab and set up its panes
+    SftpTab(SavedSftpTab),
+    /// Connect a pane of a restored SFTP tab
+    SftpConnect {
+        tab_id: SessionId,
+        pane_id: PaneId,
+        host_id: Uuid,
+    },
+    /// Open the saved directory once the pane has connected
+    SftpNavigate {
+        tab_id: SessionId,
+        pane_id: PaneId,
+        host_id: Uuid,
+        path: PathBuf,
+    },
+}
+
+/// Tracks the saved tab list and a restore in progress
+#[derive(Default)]
+pub struct SessionRestoreManager {
+    /// Tabs from the previous run waiting for the user to accept the offer
+    offer: Option<OpenSessionsState>,
+    queue: VecDeque<RestoreStep>,
+    /// SFTP pane connect the next step waits for, and when to stop waiting
+    awaiting_sftp: Option<(SessionId, PaneId, Uuid, Instant)>,
+    last_saved: Option<OpenSessionsState>,
+}
+
+impl SessionRestoreManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_offer(&mut self, state: OpenSessionsState) {
+        self.offer = Some(state);
+    }
+
+    pub fn has_offer(&self) -> bool {
+        self.offer.is_some()
+    }
+
+    pub fn withdraw_offer(&mut self) {
+        self.offer = None;
+    }
+
+    /// Accept the offer and queue its steps; returns the number of tabs
+    pub fn accept_offer(&mut self) -> usize {
+        let Some(state) = self.offer.take() else {
+            return 0;
+        };
+        let count = state.tab_count();
+        self.queue
+            .extend(state.terminals.into_iter().map(RestoreStep::Terminal));
+        self.queue
+            .extend(state.sftp_tabs.into_iter().map(RestoreStep::SftpTab));
+        count
+    }
+
+    pub fn is_restoring(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Queue the connect and navigate steps of a freshly opened SFTP tab
+    /// ahead of the remaining tabs.
+    pub fn queue_sftp_panes(&mut self, tab_id: SessionId, panes: [(PaneId, &SavedSftpPane); 2]) {
+        for (pane_id, pane) in panes.into_iter().rev() {
+            let Some(host_id) = pane.host_id else {
+                continue;
+            };
+            if !pane.path.as_os_str().is_empty() {
+                self.queue.push_front(RestoreStep::SftpNavigate {
+                    tab_id,
+                    pane_id,
+                    host_id,
+                    path: pane.path.clone(),
+                });
+            }
+            self.queue.push_front(RestoreStep::SftpConnect {
+                tab_id,
+                pane_id,
+                host_id,
+            });
+        }
+    }
+
+    /// Remember the SFTP pane connect that was just started with the given
+    /// connect timeout
+    pub fn await_sftp(
+        &mut self,
+        tab_id: SessionId,
+        pane_id: PaneId,
+        host_id: Uuid,
+        connect_timeout: Duration,
+    ) {
+        let deadline = Instant::now() + sftp_connect_wait(connect_timeout);
+        self.awaiting_sftp = Some((tab_id, pane_id, host_id, deadline));
+    }
+
+    /// Take the next step once `is_pending` says the awaited SFTP connect is
+    /// no longer in flight (or it has taken too long).
+    pub fn next_step(
+        &mut self,
+        is_pending: impl Fn(SessionId, PaneId, Uuid) -> bool,
+    ) -> Option<RestoreStep> {
+        if let Some((tab_id, pane_id, host_id, deadline)) = self.awaiting_sftp {
+            if is_pending(tab_id, pane_id, host_id) && Instant::now() < deadline {
+                return None;
+            }
+            self.awaiting_sftp = None;
+        }
+        self.queue.pop_front()
+    }
+
+    /// Whether `state` differs from what was last written; records it if so
+    pub fn should_save(&mut self, state: &OpenSessionsState) -> bool {
+        if self.last_saved.as_ref() == Some(state) {
+            return false;
+        }
+        self.last_saved = Some(state.clone());
+        true
+    }
+
+    /// Forget what was last written, e.g. after the file was cleared
+    pub fn reset_saved(&mut self) {
+        self.last_saved = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(host_id: Option<Uuid>, path: &str) -> SavedSftpPane {
+        SavedSftpPane {
+            host_id,
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn accepted_offer_runs_terminals_then_sftp_panes() {
+        let host = Uuid::new_v4();
+        let tab = SavedSftpTab {
+            left: pane(None, "/home/me"),
+            right: pane(Some(host), "/var/log"),
+        };
+        let mut manager = SessionRestoreManager::new();
+        manager.set_offer(OpenSessionsState {
+            terminals: vec![host],
+            sftp_tabs: vec![tab.clone()],
+        });
+
+        assert_eq!(manager.accept_offer(), 2);
+        assert!(!manager.has_offer());
+        assert_eq!(
+            manager.next_step(|_, _, _| false),
+            Some(RestoreStep::Terminal(host))
+        );
+        assert_eq!(
+            manager.next_step(|_, _, _| false),
+            Some(RestoreStep::SftpTab(tab.clone()))
+        );
+
+        let tab_id = Uuid::new_v4();
+        manager.queue_sftp_panes(
+            tab_id,
+            [(PaneId::Left, &tab.left), (PaneId::Right, &tab.right)],
+        );
+        assert_eq!(
+            manager.next_step(|_, _, _| false),
+            Some(RestoreStep::SftpConnect {
+                tab_id,
+                pane_id: PaneId::Right,
+                host_id: host,
+            })
+        );
+        manager.await_sftp(tab_id, PaneId::Right, host, Duration::from_secs(30));
+        assert_eq!(manager.next_step(|_, _, _| true), None);
+        assert!(matches!(
+            manager.next_step(|_, _, _| false),
+            Some(RestoreStep::SftpNavigate { .. })
+        ));
+        assert!(!manager.is_restoring());
+    }
+
+    #[test]
+    fn sftp_connect_wait_follows_the_connect_timeout() {
+        assert_eq!(
+            sftp_connect_wait(Duration::from_secs(10)),
+            Duration::from_secs(10) + SFTP_CONNECT_MARGIN
+        );
+        assert_eq!(
+            sftp_connect_wait(Duration::from_secs(120)),
+            Duration::from_secs(120) + SFTP_CONNECT_MARGIN
+        );
+    }
+
+    #[test]
+    fn should_save_skips_unchanged_state() {
+        let mut manager = SessionRestoreManager::new();
+        let state = OpenSessionsState {
+            terminals: vec![Uuid::new_v4()],
+            sftp_tabs: Vec::new(),
+        };
+
+        assert!(manager.should_save(&state));
+        assert!(!manager.should_save(&state));
+        manager.reset_saved();
+        assert!(manager.should_save(&state));
+    }
+}
@@ -17,9 +17,14 @@ pub struct SftpManager {
     connections: HashMap<SessionId, SharedSftpSession>,
     /// History entry IDs for SFTP sessions
     history_entries: HashMap<SessionId, Uuid>,
+    /// Host each dual-pane SFTP connection was opened for
+    connection_hosts: HashMap<SessionId, Uuid>,
     /// Pending dual-pane SFTP connection (tab_id, pane_id, host_id)
     /// Used to track which pane is waiting for connection after host key verification
     pending_connection: Option<(SessionId, PaneId, Uuid)>,
+    /// Whether a remembered SFTP directory changed since hosts.toml was
+    /// last written
+    unsaved_last_paths: bool,
 }
 
 impl SftpManager {
@@ -29,7 +34,9 @@ impl SftpManager {
             tabs: HashMap::new(),
             connections: HashMap::new(),
             history_entries: HashMap::new(),
+            connection_hosts: HashMap::new(),
             pending_connection: None,
+            unsaved_last_paths: false,
         }
     }
 
@@ -102,9 +109,40 @@ impl SftpManager {
 
     /// Remove an SFTP connection by ID
     pub fn remove_connection(&mut self, id: SessionId) -> Option<SharedSftpSession> {
+        self.connection_hosts.remove(&id);
         self.connections.remove(&id)
     }
 
+    /// Remember which host an SFTP connection belongs to
+    pub fn insert_connection_host(&mut self, id: SessionId, host_id: Uuid) {
+        self.connection_hosts.insert(id, host_id);
+    }
+
+    /// Host an SFTP connection was opened for
+    pub fn connection_host(&self, id: SessionId) -> Option<Uuid> {
+        self.connection_hosts.get(&id).copied()
+    }
+
+    /// Note that a host's remembered SFTP directory changed in memory
+    pub fn mark_last_paths_unsaved(&mut self) {
+        self.unsaved_last_paths = true;
+    }
+
+    /// Whether remembered SFTP directories need writing, clearing the flag
+    pub fn take_unsaved_last_paths(&mut self) -> bool {
+        std::mem::take(&mut self.unsaved_last_paths)
+    }
+
+    /// Bookmark bucket for a pane source: `Some(None)` for the local file
+    /// system, `Some(Some(host))` for a remote host, and `None` when the
+    /// remote host is not known
+    pub fn bookmark_host(&self, source: &PaneSource) -> Option<Option<Uuid>> {
+        match source {
+            PaneSource::Local => Some(None),
+            PaneSource::Remote { session_id, .. } => self.connection_host(*session_id).map(Some),
+        }
+    }
+
     /// Check if a connection is still used by any tab
     pub fn is_connection_in_use(&self, session_id: SessionId) -> bool {
         self.tabs.values().any(|state| {
@@ -394,6 +432,30 @@ mod tests {
         assert!(manager.remove_connection(Uuid::new_v4()).is_none());
     }
 
+    #[test]
+    fn connection_host_is_forgotten_with_connection() {
+        let mut manager = SftpManager::new();
+        let session_id = Uuid::new_v4();
+        let host_id = Uuid::new_v4();
+
+        manager.insert_connection_host(session_id, host_id);
+        assert_eq!(manager.connection_host(session_id), Some(host_id));
+
+        manager.remove_connection(session_id);
+        assert_eq!(manager.connection_host(session_id), None);
+    }
+
+    #[test]
+    fn unsaved_last_paths_are_taken_once() {
+        let mut manager = SftpManager::new();
+        assert!(!manager.take_unsaved_last_paths());
+
+        manager.mark_last_paths_unsaved();
+        manager.mark_last_paths_unsaved();
+        assert!(manager.take_unsaved_last_paths());
+        assert!(!manager.take_unsaved_last_paths());
+    }
+
     // ---- Connection in use tests (critical for cleanup) ----
 
     #[test]
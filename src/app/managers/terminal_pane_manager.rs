@@ -0,0 +1,462 @@
+//! Split-pane layouts for terminal tabs
+//!
+//! A terminal tab normally shows one session and its id is that session's id.
+//! Splitting a tab builds a pane grid where every pane holds its own session.
+//! The tab keeps the id of one of its sessions, so lookups by tab id still
+//! find a live session after the original pane is closed.
+
+use std::collections::HashMap;
+
+use iced::widget::pane_grid::{self, Axis, Node, Pane, Split};
+use uuid::Uuid;
+
+use crate::message::SessionId;
+use crate::theme::{CustomTheme, TerminalColors, ThemeId, get_theme};
+
+/// A split waiting for its new session to finish connecting
+#[derive(Debug, Clone, Copy)]
+pub struct PendingSplit {
+    pub tab_id: Uuid,
+    /// Session whose pane is split
+    pub target: SessionId,
+    pub axis: Axis,
+    /// Host the new session connects to (`None` for a local shell)
+    pub host_id: Option<Uuid>,
+}
+
+/// Result of closing one pane of a split tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneClosed {
+    /// Id the tab had before the pane was closed
+    pub old_tab_id: Uuid,
+    /// Id the tab has now; differs when the closed pane owned the tab id
+    pub tab_id: Uuid,
+    /// Session focused after the close
+    pub focused: SessionId,
+}
+
+/// Look of one pane that differs from the rest of the tab. `None` fields
+/// follow the session's zoom and the theme.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PaneViewPrefs {
+    pub font_size: Option<f32>,
+    pub colors: Option<TerminalColors>,
+}
+
+/// Colour scheme a split pane can use instead of the theme's
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaneColorScheme {
+    pub name: String,
+    /// `None` follows the theme
+    pub colors: Option<TerminalColors>,
+}
+
+impl std::fmt::Display for PaneColorScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Schemes offered by a pane header: the current theme, then the terminal
+/// colours of every built-in and custom theme
+fn color_schemes(custom: &[CustomTheme]) -> Vec<PaneColorScheme> {
+    let built_in = ThemeId::all().iter().map(|id| PaneColorScheme {
+        name: id.display_name().to_string(),
+        colors: Some(get_theme(*id).terminal),
+    });
+    let custom = custom.iter().map(|theme| PaneColorScheme {
+        name: theme.name.clone(),
+        colors: Some(theme.colors.terminal),
+    });
+    std::iter::once(PaneColorScheme {
+        name: "Theme colors".to_string(),
+        colors: None,
+    })
+    .chain(built_in)
+    .chain(custom)
+    .collect()
+}
+
+/// Pane grid of one split terminal tab
+pub struct TerminalPaneLayout {
+    pub grid: pane_grid::State<SessionId>,
+    focused: SessionId,
+    /// Last focus token each unfocused pane saw while it had focus, so it is
+    /// not refocused when the focused pane gets a new token.
+    focus_tokens: HashMap<SessionId, u64>,
+    /// Per-pane overrides; they last as long as the split does
+    view_prefs: HashMap<SessionId, PaneViewPrefs>,
+}
+
+impl TerminalPaneLayout {
+    fn new(session_id: SessionId) -> Self {
+        let (grid, _) = pane_grid::State::new(session_id);
+        Self {
+            grid,
+            focused: session_id,
+            focus_tokens: HashMap::new(),
+            view_prefs: HashMap::new(),
+        }
+    }
+
+    /// Font size and colours the pane overrides
+    pub fn view_prefs(&self, session_id: SessionId) -> PaneViewPrefs {
+        self.view_prefs
+            .get(&session_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn focused(&self) -> SessionId {
+        self.focused
+    }
+
+    /// Focus token to hand the pane's terminal widget
+    pub fn focus_token(&self, session_id: SessionId, current: u64) -> u64 {
+        if session_id == self.focused {
+            current
+        } else {
+            self.focus_tokens
+                .get(&session_id)
+                .copied()
+                .unwrap_or_default()
+        }
+    }
+
+    /// Sessions in reading order: left to right, top to bottom
+    pub fn sessions(&self) -> Vec<SessionId> {
+        fn collect(node: &Node, panes: &mut Vec<Pane>) {
+            match node {
+                Node::Split { a, b, .. } => {
+                    collect(a, panes);
+                    collect(b, panes);
+                }
+                Node::Pane(pane) => panes.push(*pane),
+            }
+        }
+
+        let mut panes = Vec::new();
+        collect(self.grid.layout(), &mut panes);
+        panes
+            .into_iter()
+            .filter_map(|pane| self.grid.get(pane).copied())
+            .collect()
+    }
+
+    fn contains(&self, session_id: SessionId) -> bool {
+        self.pane_of(session_id).is_some()
+    }
+
+    fn pane_of(&self, session_id: SessionId) -> Option<Pane> {
+        self.grid
+            .iter()
+            .find(|(_, id)| **id == session_id)
+            .map(|(pane, _)| *pane)
+    }
+
+    fn set_focus(&mut self, session_id: SessionId, current_token: u64) {
+        if session_id != self.focused {
+            self.focus_tokens.insert(self.focused, current_token);
+            self.focus_tokens.remove(&session_id);
+            self.focused = session_id;
+        }
+    }
+}
+
+/// Tracks split layouts of terminal tabs
+pub struct TerminalPaneManager {
+    layouts: HashMap<Uuid, TerminalPaneLayout>,
+    pending: Option<PendingSplit>,
+    /// Built once rather than on every render of a pane header
+    color_schemes: Vec<PaneColorScheme>,
+}
+
+impl Default for TerminalPaneManager {
+    fn default() -> Self {
+        Self {
+            layouts: HashMap::new(),
+            pending: None,
+            color_schemes: color_schemes(&[]),
+        }
+    }
+}
+
+impl TerminalPaneManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Colour schemes a pane can switch to
+    pub fn color_schemes(&self) -> &[PaneColorScheme] {
+        &self.color_schemes
+    }
+
+    /// Rebuild the colour schemes after custom themes were added, edited or
+    /// removed
+    pub fn set_custom_themes(&mut self, custom: &[CustomTheme]) {
+        self.color_schemes = color_schemes(custom);
+    }
+
+    /// Layout of a split tab; `None` while the tab shows a single session
+    pub fn layout(&self, tab_id: Uuid) -> Option<&TerminalPaneLayout> {
+        self.layouts.get(&tab_id)
+    }
+
+    /// Tab a session is shown in, when it is part of a split
+    pub fn tab_for_session(&self, session_id: SessionId) -> Option<Uuid> {
+        self.layouts
+            .iter()
+            .find(|(_, layout)| layout.contains(session_id))
+            .map(|(tab_id, _)| *tab_id)
+    }
+
+    /// Overrides of a pane, for changing them; `None` when the session is
+    /// not part of a split
+    pub fn view_prefs_mut(&mut self, session_id: SessionId) -> Option<&mut PaneViewPrefs> {
+        let tab_id = self.tab_for_session(session_id)?;
+        let layout = self.layouts.get_mut(&tab_id)?;
+        Some(layout.view_prefs.entry(session_id).or_default())
+    }
+
+    pub fn focused_session(&self, tab_id: Uuid) -> Option<SessionId> {
+        self.layouts.get(&tab_id).map(TerminalPaneLayout::focused)
+    }
+
+    /// Remember a split; the next session opened for `host_id` fills it
+    pub fn request_split(&mut self, split: PendingSplit) {
+        self.pending = Some(split);
+    }
+
+    pub fn take_pending(&mut self, host_id: Option<Uuid>) -> Option<PendingSplit> {
+        self.pending.take_if(|pending| pending.host_id == host_id)
+    }
+
+    /// Forget the requested split after its connection failed or was
+    /// cancelled, so a later session for the same host opens its own tab
+    pub fn cancel_pending(&mut self) {
+        self.pending = None;
+    }
+
+    /// Add a connected session next to the split target and focus it
+    pub fn attach(&mut self, split: PendingSplit, session_id: SessionId, current_token: u64) {
+        let layout = self
+            .layouts
+            .entry(split.tab_id)
+            .or_insert_with(|| TerminalPaneLayout::new(split.tab_id));
+        let Some(target) = layout
+            .pane_of(split.target)
+            .or_else(|| layout.pane_of(layout.focused))
+        else {
+            return;
+        };
+        if layout.grid.split(split.axis, target, session_id).is_some() {
+            layout.set_focus(session_id, current_token);
+        }
+    }
+
+    /// Focus a pane; returns the tab it belongs to
+    pub fn focus(&mut self, session_id: SessionId, current_token: u64) -> Option<Uuid> {
+        let tab_id = self.tab_for_session(session_id)?;
+        if let Some(layout) = self.layouts.get_mut(&tab_id) {
+            layout.set_focus(session_id, current_token);
+        }
+        Some(tab_id)
+    }
+
+    /// Pane after the focused one, wrapping around
+    pub fn next_session(&self, tab_id: Uuid) -> Option<SessionId> {
+        let layout = self.layouts.get(&tab_id)?;
+        let sessions = layout.sessions();
+        let index = sessions.iter().position(|id| *id == layout.focused)?;
+        sessions.get((index + 1) % sessions.len()).copied()
+    }
+
+    pub fn resize(&mut self, tab_id: Uuid, split: Split, ratio: f32) {
+        if let Some(layout) = self.layouts.get_mut(&tab_id) {
+            layout.grid.resize(split, ratio);
+        }
+    }
+
+    /// Close the pane of a session. The sibling takes over its space, the
+    /// remaining splits are evened out, and a tab left with one pane goes back
+    /// to a plain single-session tab.
+    pub fn close(&mut self, session_id: SessionId) -> Option<PaneClosed> {
+        let old_tab_id = self.tab_for_session(session_id)?;
+        let layout = self.layouts.get_mut(&old_tab_id)?;
+        let pane = layout.pane_of(session_id)?;
+        let (_, sibling) = layout.grid.close(pane)?;
+        layout.focus_tokens.remove(&session_id);
+        layout.view_prefs.remove(&session_id);
+        if layout.focused == session_id
+            && let Some(sibling_session) = layout.grid.get(sibling).copied()
+        {
+            layout.focused = sibling_session;
+        }
+        let splits: Vec<Split> = layout.grid.layout().splits().copied().collect();
+        for split in splits {
+            layout.grid.resize(split, 0.5);
+        }
+        let layout = self.layouts.remove(&old_tab_id)?;
+
+        if self
+            .pending
+            .is_some_and(|pending| pending.tab_id == old_tab_id)
+        {
+            self.pending = None;
+        }
+
+        let tab_id = if old_tab_id == session_id {
+            layout.focused
+        } else {
+            old_tab_id
+        };
+        let focused = layout.focused;
+        if layout.grid.len() > 1 {
+            self.layouts.insert(tab_id, layout);
+        }
+
+        Some(PaneClosed {
+            old_tab_id,
+            tab_id,
+            focused,
+        })
+    }
+
+    /// Forget a tab's layout; returns the sessions of its other panes
+    pub fn remove_tab(&mut self, tab_id: Uuid) -> Vec<SessionId> {
+        if self.pending.is_some_and(|pending| pending.tab_id == tab_id) {
+            self.pending = None;
+        }
+        self.layouts
+            .remove(&tab_id)
+            .map(|layout| {
+                layout
+                    .sessions()
+                    .into_iter()
+                    .filter(|id| *id != tab_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(tab_id: Uuid, target: SessionId, axis: Axis) -> PendingSplit {
+        PendingSplit {
+            tab_id,
+            target,
+            axis,
+            host_id: None,
+        }
+    }
+
+    #[test]
+    fn attach_builds_layout_and_focuses_new_pane() {
+        let tab = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let mut manager = TerminalPaneManager::new();
+
+        manager.attach(split(tab, tab, Axis::Vertical), second, 7);
+
+        let layout = manager.layout(tab).unwrap();
+        assert_eq!(layout.sessions(), vec![tab, second]);
+        assert_eq!(layout.focused(), second);
+        assert_eq!(layout.focus_token(second, 8), 8);
+        assert_eq!(layout.focus_token(tab, 8), 7);
+        assert_eq!(manager.tab_for_session(second), Some(tab));
+    }
+
+    #[test]
+    fn next_session_cycles_in_reading_order() {
+        let tab = Uuid::new_v4();
+        let right = Uuid::new_v4();
+        let below = Uuid::new_v4();
+        let mut manager = TerminalPaneManager::new();
+        manager.attach(split(tab, tab, Axis::Vertical), right, 1);
+        manager.attach(split(tab, right, Axis::Horizontal), below, 2);
+
+        assert_eq!(manager.next_session(tab), Some(tab));
+        manager.focus(tab, 3);
+        assert_eq!(manager.next_session(tab), Some(right));
+    }
+
+    #[test]
+    fn pending_split_only_matches_its_host() {
+        let tab = Uuid::new_v4();
+        let host = Uuid::new_v4();
+        let mut manager = TerminalPaneManager::new();
+        manager.request_split(PendingSplit {
+            host_id: Some(host),
+            ..split(tab, tab, Axis::Vertical)
+        });
+
+        assert!(manager.take_pending(None).is_none());
+        assert!(manager.take_pending(Some(host)).is_some());
+        assert!(manager.take_pending(Some(host)).is_none());
+
+        manager.request_split(PendingSplit {
+            host_id: Some(host),
+            ..split(tab, tab, Axis::Vertical)
+        });
+        manager.cancel_pending();
+        assert!(manager.take_pending(Some(host)).is_none());
+    }
+
+    #[test]
+    fn view_prefs_belong_to_each_pane_of_a_split() {
+        let tab = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let third = Uuid::new_v4();
+        let mut manager = TerminalPaneManager::new();
+        assert!(manager.view_prefs_mut(tab).is_none());
+        manager.attach(split(tab, tab, Axis::Vertical), second, 1);
+        manager.attach(split(tab, second, Axis::Horizontal), third, 2);
+
+        manager.view_prefs_mut(second).unwrap().font_size = Some(9.0);
+        let layout = manager.layout(tab).unwrap();
+        assert_eq!(layout.view_prefs(second).font_size, Some(9.0));
+        assert_eq!(layout.view_prefs(third), PaneViewPrefs::default());
+
+        manager.close(second);
+        let layout = manager.layout(tab).unwrap();
+        assert_eq!(layout.view_prefs(second), PaneViewPrefs::default());
+    }
+
+    #[test]
+    fn closing_last_split_collapses_layout() {
+        let tab = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let mut manager = TerminalPaneManager::new();
+        manager.attach(split(tab, tab, Axis::Vertical), second, 1);
+
+        let closed = manager.close(second).unwrap();
+
+        assert_eq!(closed.tab_id, tab);
+        assert_eq!(closed.focused, tab);
+        assert!(manager.layout(tab).is_none());
+    }
+
+    #[test]
+    fn closing_tab_owner_rekeys_layout() {
+        let tab = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let third = Uuid::new_v4();
+        let mut manager = TerminalPaneManager::new();
+        manager.attach(split(tab, tab, Axis::Vertical), second, 1);
+        manager.attach(split(tab, second, Axis::Horizontal), third, 2);
+
+        let closed = manager.close(tab).unwrap();
+
+        assert_eq!(closed.old_tab_id, tab);
+        assert_eq!(closed.tab_id, third);
+        assert!(manager.layout(tab).is_none());
+        assert_eq!(
+            manager.layout(third).unwrap().sessions(),
+            vec![second, third]
+        );
+        assert_eq!(manager.remove_tab(third), vec![second]);
+    }
+}
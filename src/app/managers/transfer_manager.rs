@@ -204,12 +204,20 @@ impl TransferManager {
         true
     }
 
-    pub fn cancel_for_tab(&mut self, tab_id: SessionId) {
+    /// Cancel every running transfer of a tab, returning how many were newly
+    /// asked to stop.
+    pub fn cancel_for_tab(&mut self, tab_id: SessionId) -> usize {
+        let mut cancelled = 0;
         for transfer in &mut self.transfers {
-            if transfer.tab_id == tab_id {
+            if transfer.tab_id == tab_id
+                && !transfer.status.is_finished()
+                && transfer.status != TransferStatus::Cancelling
+            {
                 transfer.cancel();
+                cancelled += 1;
             }
         }
+        cancelled
     }
 
     pub fn clear_finished(&mut self) {
@@ -231,6 +239,13 @@ impl TransferManager {
             .any(|transfer| transfer.tab_id == tab_id && !transfer.status.is_finished())
     }
 
+    pub fn active_count(&self) -> usize {
+        self.transfers
+            .iter()
+            .filter(|transfer| !transfer.status.is_finished())
+            .count()
+    }
+
     fn prune_finished(&mut self) {
         let mut finished_seen = 0usize;
         self.transfers.retain(|transfer| {
@@ -286,4 +301,44 @@ mod tests {
         assert_eq!(transfer.status, TransferStatus::Cancelling);
         assert!(cancel.load(Ordering::Relaxed));
     }
+
+    #[test]
+    fn cancel_for_tab_only_touches_running_transfers_of_that_tab() {
+        let tab_id = Uuid::new_v4();
+        let transfer = |tab_id| {
+            TransferItem::new(TransferItemInit {
+                id: Uuid::new_v4(),
+                tab_id,
+                target_pane: PaneId::Right,
+                direction: TransferDirection::LocalToRemote,
+                label: "Upload".to_string(),
+                total_files: 1,
+                total_bytes: None,
+                cancel_requested: Arc::new(AtomicBool::new(false)),
+            })
+        };
+        let mut manager = TransferManager::new();
+        let running = transfer(tab_id);
+        let finished = transfer(tab_id);
+        let other_tab = transfer(Uuid::new_v4());
+        let (running_id, finished_id, other_id) = (running.id, finished.id, other_tab.id);
+        manager.insert(running);
+        manager.insert(finished);
+        manager.insert(other_tab);
+        manager.finish(finished_id, TransferStatus::Completed);
+
+        assert_eq!(manager.cancel_for_tab(tab_id), 1);
+
+        let status = |id| {
+            manager
+                .transfers
+                .iter()
+                .find(|transfer| transfer.id == id)
+                .map(|transfer| transfer.status.clone())
+        };
+        assert_eq!(status(running_id), Some(TransferStatus::Cancelling));
+        assert_eq!(status(finished_id), Some(TransferStatus::Completed));
+        assert_eq!(status(other_id), Some(TransferStatus::Running));
+        assert_eq!(manager.cancel_for_tab(tab_id), 0);
+    }
 }
@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
@@ -8,24 +9,29 @@ use tokio::sync::{Mutex, mpsc};
 use uuid::Uuid;
 
 use crate::config::hosts::HubRouting;
-use crate::config::settings::PortalHubSettings;
-use crate::config::{DetectedOs, Host, PortForwardKind};
+use crate::config::settings::{
+    DEFAULT_CONNECT_TIMEOUT_SECS, MAX_CONNECT_TIMEOUT_SECS, PortalHubSettings,
+};
+use crate::config::{DetectedOs, Host, PortForward, PortForwardKind};
 use crate::message::{
     AuthPromptRequestWrapper, DialogMessage, Message, PassphraseRequest, PassphraseSftpContext,
-    SessionId, SessionMessage, SftpMessage, VerificationRequestWrapper,
+    PasswordFallbackRequest, SessionId, SessionMessage, SftpMessage, VerificationRequestWrapper,
 };
 use crate::proxy::{ListedProxySession, ProxyEvent, ProxySession, ProxySessionTarget};
 use crate::sftp::SftpClient;
 use crate::ssh::known_hosts::KnownHostsManager;
 use crate::ssh::passphrase_cache::PassphraseCache;
-use crate::ssh::{SshClient, SshEvent};
+use crate::ssh::{Keepalive, SshClient, SshEvent, SshSession};
+use crate::views::dialogs::host_dialog::ConnectionTestStatus;
 use crate::views::sftp::PaneId;
 
 pub const SSH_EVENT_CHANNEL_CAPACITY: usize = 1024;
 const SSH_DATA_COALESCE_LIMIT: usize = 256 * 1024;
 const SSH_KEEPALIVE_INTERVAL_SECS: u64 = 60;
+const REACHABILITY_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
 
 static KNOWN_HOSTS_MANAGER: OnceLock<Arc<Mutex<KnownHostsManager>>> = OnceLock::new();
+static CONNECT_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_CONNECT_TIMEOUT_SECS);
 
 enum SshAuth {
     None,
@@ -71,10 +77,53 @@ pub fn shared_passphrase_cache() -> Arc<PassphraseCache> {
     crate::ssh::passphrase_cache::shared_cache()
 }
 
+/// Get the shared sudo password cache used for privilege-elevated SFTP
+/// operations.
+pub fn shared_sudo_cache() -> Arc<PassphraseCache<SessionId>> {
+    crate::ssh::passphrase_cache::shared_sudo_cache()
+}
+
 /// Initialize the passphrase cache with a custom timeout
 pub fn init_passphrase_cache(timeout_seconds: u64) {
     // Apply updates at runtime; this only affects new entries.
     shared_passphrase_cache().set_timeout(timeout_seconds);
+    shared_sudo_cache().set_timeout(timeout_seconds);
+}
+
+/// Set the timeout applied to new SSH, SFTP and jump-host connections
+pub fn set_connect_timeout(timeout_seconds: u64) {
+    CONNECT_TIMEOUT_SECS.store(
+        timeout_seconds.clamp(1, MAX_CONNECT_TIMEOUT_SECS),
+        Ordering::Relaxed,
+    );
+}
+
+pub fn connect_timeout() -> Duration {
+    Duration::from_secs(CONNECT_TIMEOUT_SECS.load(Ordering::Relaxed))
+}
+
+/// Whether a TCP connection to `hostname:port` opens within a few seconds
+pub async fn probe_host_reachable(hostname: String, port: u16) -> bool {
+    crate::ssh::client::connect_tcp(&hostname, port, REACHABILITY_PROBE_TIMEOUT)
+        .await
+        .is_ok()
+}
+
+/// Whether any key passphrase or sudo password is held in memory
+pub fn has_cached_credentials() -> bool {
+    !shared_passphrase_cache().is_empty() || !shared_sudo_cache().is_empty()
+}
+
+/// Drop cached credentials whose timeout has passed
+pub fn purge_expired_credentials() {
+    shared_passphrase_cache().cleanup_expired();
+    shared_sudo_cache().cleanup_expired();
+}
+
+/// Drop every cached key passphrase and sudo password
+pub fn forget_cached_credentials() {
+    shared_passphrase_cache().clear();
+    shared_sudo_cache().clear();
 }
 
 pub fn should_detect_os(detected_os: Option<&DetectedOs>) -> bool {
@@ -321,6 +370,7 @@ fn ssh_connect_tasks_with_auth(
     terminal_size: (u16, u16),
     should_detect_os: bool,
     allow_agent_forwarding: bool,
+    keepalive: Keepalive,
     jump_chain: Vec<Host>,
     auth: SshAuth,
 ) -> Task<Message> {
@@ -328,9 +378,10 @@ fn ssh_connect_tasks_with_auth(
     let event_listener = ssh_event_listener(session_id, event_rx);
 
     let known_hosts = shared_known_hosts_manager();
-    let ssh_client = SshClient::with_known_hosts(SSH_KEEPALIVE_INTERVAL_SECS, known_hosts);
+    let ssh_client = SshClient::with_keepalive(host_keepalive(&host, keepalive), known_hosts);
     let host_for_task = Arc::clone(&host);
     let (password, passphrase) = auth.split();
+    let password_supplied = password.is_some();
     let connect_task = Task::perform(
         async move {
             let result = ssh_client
@@ -339,7 +390,7 @@ fn ssh_connect_tasks_with_auth(
                     &jump_chain,
                     terminal_size,
                     event_tx,
-                    Duration::from_secs(30),
+                    connect_timeout(),
                     password,
                     passphrase,
                     should_detect_os,
@@ -348,24 +399,14 @@ fn ssh_connect_tasks_with_auth(
                 .await;
             let result = match result {
                 Ok((session, detected_os)) => {
+                    let mut forward_results = Vec::new();
                     for forward in host_for_task
                         .port_forwards
                         .iter()
                         .filter(|forward| forward.enabled)
                     {
-                        let creation_result = match forward.kind {
-                            PortForwardKind::Local => {
-                                session.create_local_forward(forward.clone()).await
-                            }
-                            PortForwardKind::Remote => {
-                                session.create_remote_forward(forward.clone()).await
-                            }
-                            PortForwardKind::Dynamic => {
-                                session.create_dynamic_forward(forward.clone()).await
-                            }
-                        };
-
-                        if let Err(e) = creation_result {
+                        let creation_result = start_port_forward(&session, forward.clone()).await;
+                        if let Err(e) = &creation_result {
                             tracing::warn!(
                                 "Failed to create port forward {} on {}: {}",
                                 forward.id,
@@ -373,29 +414,61 @@ fn ssh_connect_tasks_with_auth(
                                 e
                             );
                         }
+                        forward_results.push((forward.id, creation_result));
                     }
-                    Ok((session, detected_os))
+                    Ok((session, detected_os, forward_results))
                 }
                 Err(e) => Err(e),
             };
 
             (session_id, host_id, host_for_task, result, should_detect_os)
         },
-        |(session_id, host_id, host, result, should_detect_os)| match result {
-            Ok((ssh_session, detected_os)) => Message::Session(SessionMessage::Connected {
+        move |(session_id, host_id, host, result, should_detect_os)| match result {
+            Ok((ssh_session, detected_os, forward_results)) => {
+                Message::Session(SessionMessage::Connected {
+                    session_id,
+                    host_name: host.name.clone(),
+                    ssh_session,
+                    host_id,
+                    detected_os,
+                    forward_results,
+                })
+            }
+            Err(e) => map_ssh_connect_error(
                 session_id,
-                host_name: host.name.clone(),
-                ssh_session,
                 host_id,
-                detected_os,
-            }),
-            Err(e) => map_ssh_connect_error(session_id, host_id, &host, should_detect_os, e),
+                &host,
+                should_detect_os,
+                password_supplied,
+                e,
+            ),
         },
     );
 
     Task::batch([event_listener, connect_task])
 }
 
+/// Keepalive for a host: per-host overrides from hosts.toml win over the
+/// global settings
+fn host_keepalive(host: &Host, defaults: Keepalive) -> Keepalive {
+    Keepalive {
+        interval_secs: host.keepalive_interval.unwrap_or(defaults.interval_secs),
+        count_max: host.keepalive_count_max.unwrap_or(defaults.count_max),
+    }
+}
+
+/// Establish one configured port forward on a connected session, returning
+/// the port it ended up bound to
+pub async fn start_port_forward(session: &SshSession, forward: PortForward) -> Result<u16, String> {
+    let result = match forward.kind {
+        PortForwardKind::Local => session.create_local_forward(forward).await,
+        PortForwardKind::Remote => session.create_remote_forward(forward).await,
+        PortForwardKind::Dynamic => session.create_dynamic_forward(forward).await,
+    };
+    result.map_err(|e| e.to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn ssh_connect_tasks(
     host: Arc<Host>,
     session_id: SessionId,
@@ -403,6 +476,7 @@ pub fn ssh_connect_tasks(
     terminal_size: (u16, u16),
     should_detect_os: bool,
     allow_agent_forwarding: bool,
+    keepalive: Keepalive,
     jump_chain: Vec<Host>,
 ) -> Task<Message> {
     ssh_connect_tasks_with_auth(
@@ -412,6 +486,7 @@ pub fn ssh_connect_tasks(
         terminal_size,
         should_detect_os,
         allow_agent_forwarding,
+        keepalive,
         jump_chain,
         SshAuth::None,
     )
@@ -426,6 +501,7 @@ pub fn ssh_connect_tasks_with_password(
     terminal_size: (u16, u16),
     should_detect_os: bool,
     allow_agent_forwarding: bool,
+    keepalive: Keepalive,
     jump_chain: Vec<Host>,
     password: SecretString,
 ) -> Task<Message> {
@@ -436,6 +512,7 @@ pub fn ssh_connect_tasks_with_password(
         terminal_size,
         should_detect_os,
         allow_agent_forwarding,
+        keepalive,
         jump_chain,
         SshAuth::Password(password),
     )
@@ -450,6 +527,7 @@ pub fn ssh_connect_tasks_with_passphrase(
     terminal_size: (u16, u16),
     should_detect_os: bool,
     allow_agent_forwarding: bool,
+    keepalive: Keepalive,
     jump_chain: Vec<Host>,
     passphrase: SecretString,
 ) -> Task<Message> {
@@ -460,11 +538,78 @@ pub fn ssh_connect_tasks_with_passphrase(
         terminal_size,
         should_detect_os,
         allow_agent_forwarding,
+        keepalive,
         jump_chain,
         SshAuth::Passphrase(passphrase),
     )
 }
 
+/// Try a host's login for the host dialog's "Test" button without opening a
+/// session. Host key and keyboard-interactive prompts use the usual dialogs;
+/// `secret` is the password for password logins, otherwise the key
+/// passphrase.
+pub fn ssh_test_connection_task(
+    host: Host,
+    jump_chain: Vec<Host>,
+    keepalive: Keepalive,
+    secret: Option<SecretString>,
+    test_id: Uuid,
+) -> Task<Message> {
+    let (event_tx, event_rx) = mpsc::channel::<SshEvent>(SSH_EVENT_CHANNEL_CAPACITY);
+    let event_listener = ssh_dialog_event_listener(event_rx);
+
+    let known_hosts = shared_known_hosts_manager();
+    let ssh_client = SshClient::with_keepalive(host_keepalive(&host, keepalive), known_hosts);
+    let auth = match secret {
+        Some(secret) if host.needs_password() => SshAuth::Password(secret),
+        Some(secret) => SshAuth::Passphrase(secret),
+        None => SshAuth::None,
+    };
+    let (password, passphrase) = auth.split();
+    let test_task = Task::perform(
+        async move {
+            ssh_client
+                .test_connection(
+                    &host,
+                    &jump_chain,
+                    event_tx,
+                    connect_timeout(),
+                    password,
+                    passphrase,
+                )
+                .await
+        },
+        move |result| {
+            Message::Dialog(DialogMessage::ConnectionTestFinished(
+                test_id,
+                connection_test_status(result),
+            ))
+        },
+    );
+
+    Task::batch([event_listener, test_task])
+}
+
+fn connection_test_status(result: Result<String, crate::error::SshError>) -> ConnectionTestStatus {
+    use crate::error::SshError;
+
+    match result {
+        Ok(auth_method) => {
+            ConnectionTestStatus::Succeeded(format!("Signed in with {}", auth_method))
+        }
+        Err(SshError::KeyFilePassphraseRequired(path)) => {
+            ConnectionTestStatus::PassphraseRequired(format!(
+                "{} is encrypted; enter its passphrase to test",
+                path.display()
+            ))
+        }
+        Err(SshError::KeyFilePassphraseInvalid(path)) => ConnectionTestStatus::PassphraseRequired(
+            format!("Incorrect passphrase for {}", path.display()),
+        ),
+        Err(error) => ConnectionTestStatus::Failed(connect_failure_message(&error)),
+    }
+}
+
 pub fn sftp_connect_tasks(
     host: Arc<Host>,
     tab_id: SessionId,
@@ -542,6 +687,7 @@ fn sftp_connect_tasks_with_auth(
 
     let host_for_task = Arc::clone(&host);
     let (password, passphrase) = auth.split();
+    let password_supplied = password.is_some();
     let connect_task = Task::perform(
         async move {
             let result = sftp_client
@@ -549,24 +695,57 @@ fn sftp_connect_tasks_with_auth(
                     &host_for_task,
                     &jump_chain,
                     event_tx,
-                    Duration::from_secs(30),
+                    connect_timeout(),
                     password,
                     passphrase,
                 )
                 .await;
+            let result = match result {
+                Ok(sftp_session) => {
+                    // An explicit start directory wins over the remembered one
+                    let last_path = host_for_task
+                        .last_sftp_path
+                        .clone()
+                        .filter(|_| host_for_task.sftp_start_path.is_none());
+                    let (initial_path, missing_last_path) = match last_path {
+                        Some(last) if sftp_session.is_dir(&last).await => (last, None),
+                        Some(last) => (sftp_session.home_dir().to_path_buf(), Some(last)),
+                        None => (
+                            sftp_session
+                                .resolve_start_dir(host_for_task.sftp_start_path.as_deref())
+                                .await,
+                            None,
+                        ),
+                    };
+                    Ok((sftp_session, initial_path, missing_last_path))
+                }
+                Err(e) => Err(e),
+            };
 
             (tab_id, pane_id, sftp_session_id, host_for_task, result)
         },
         move |(tab_id, pane_id, sftp_session_id, host, result)| match result {
-            Ok(sftp_session) => Message::Sftp(SftpMessage::Connected {
+            Ok((sftp_session, initial_path, missing_last_path)) => {
+                Message::Sftp(SftpMessage::Connected {
+                    tab_id,
+                    pane_id,
+                    sftp_session_id,
+                    host_id,
+                    host_name: host.name.clone(),
+                    sftp_session,
+                    initial_path,
+                    missing_last_path,
+                })
+            }
+            Err(e) => map_sftp_connect_error(
                 tab_id,
                 pane_id,
                 sftp_session_id,
                 host_id,
-                host_name: host.name.clone(),
-                sftp_session,
-            }),
-            Err(e) => map_sftp_connect_error(tab_id, pane_id, sftp_session_id, host_id, &host, e),
+                &host,
+                password_supplied,
+                e,
+            ),
         },
     );
 
@@ -578,9 +757,21 @@ fn map_ssh_connect_error(
     host_id: Uuid,
     host: &Host,
     should_detect_os: bool,
+    password_supplied: bool,
     error: crate::error::SshError,
 ) -> Message {
     match error {
+        crate::error::SshError::AuthenticationFailed(_)
+            if !password_supplied && host.has_password_fallback() =>
+        {
+            Message::Dialog(DialogMessage::PasswordFallbackRequired(
+                PasswordFallbackRequest {
+                    host_id,
+                    session_id: Some(session_id),
+                    sftp_context: None,
+                },
+            ))
+        }
         crate::error::SshError::KeyFilePassphraseRequired(path) => {
             Message::Dialog(DialogMessage::PassphraseRequired(PassphraseRequest {
                 host_id,
@@ -613,20 +804,60 @@ fn map_ssh_connect_error(
         }
         _ => Message::Session(SessionMessage::ConnectFailed {
             session_id,
-            error: format!("Connection failed: {}", error),
+            error: connect_failure_message(&error),
         }),
     }
 }
 
+/// Toast text for a failed SSH connect, with a next step where one is known
+fn connect_failure_message(error: &crate::error::SshError) -> String {
+    use crate::error::SshError;
+
+    let hint = match error {
+        SshError::DnsResolution { .. } => Some("Check the hostname and your network."),
+        SshError::ConnectionRefused { .. } => {
+            Some("Check that an SSH server is listening on that port.")
+        }
+        SshError::Timeout(_) => {
+            Some("The host may be offline or firewalled; the timeout can be raised in Settings.")
+        }
+        SshError::HandshakeFailed { .. } => Some("Check that the port belongs to an SSH server."),
+        _ => None,
+    };
+
+    match hint {
+        Some(hint) => format!("Connection failed: {}. {}", error, hint),
+        None => format!("Connection failed: {}", error),
+    }
+}
+
 fn map_sftp_connect_error(
     tab_id: SessionId,
     pane_id: PaneId,
     sftp_session_id: SessionId,
     host_id: Uuid,
     host: &Host,
+    password_supplied: bool,
     error: crate::error::SftpError,
 ) -> Message {
     match error {
+        crate::error::SftpError::ConnectionFailed(ref reason)
+            if reason.starts_with("Authentication failed")
+                && !password_supplied
+                && host.has_password_fallback() =>
+        {
+            Message::Dialog(DialogMessage::PasswordFallbackRequired(
+                PasswordFallbackRequest {
+                    host_id,
+                    session_id: None,
+                    sftp_context: Some(PassphraseSftpContext {
+                        tab_id,
+                        pane_id,
+                        sftp_session_id,
+                    }),
+                },
+            ))
+        }
         crate::error::SftpError::KeyFilePassphraseRequired(path) => {
             Message::Dialog(DialogMessage::PassphraseRequired(PassphraseRequest {
                 host_id,
@@ -776,11 +1007,19 @@ mod tests {
             vnc_password_id: None,
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Auto,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: vec![],
             created_at: now,
@@ -792,7 +1031,7 @@ mod tests {
         let host_id = host.id;
         let error = SshError::KeyFilePassphraseRequired(PathBuf::from("/tmp/id_ed25519"));
 
-        let message = map_ssh_connect_error(session_id, host_id, &host, true, error);
+        let message = map_ssh_connect_error(session_id, host_id, &host, true, false, error);
         match message {
             Message::Dialog(DialogMessage::PassphraseRequired(request)) => {
                 assert!(request.is_ssh);
@@ -804,6 +1043,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rejected_key_login_asks_for_the_fallback_password() {
+        let mut host = proxy_test_host(AuthMethod::Agent);
+        host.auth_fallbacks = vec![AuthMethod::Password];
+        let session_id = Uuid::new_v4();
+        let rejected = || SshError::AuthenticationFailed("rejected".to_string());
+
+        match map_ssh_connect_error(session_id, host.id, &host, false, false, rejected()) {
+            Message::Dialog(DialogMessage::PasswordFallbackRequired(request)) => {
+                assert_eq!(request.session_id, Some(session_id));
+                assert!(request.sftp_context.is_none());
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert!(matches!(
+            map_ssh_connect_error(session_id, host.id, &host, false, true, rejected()),
+            Message::Session(SessionMessage::ConnectFailed { .. })
+        ));
+
+        let rejected = SftpError::ConnectionFailed("Authentication failed: rejected".to_string());
+        match map_sftp_connect_error(
+            Uuid::new_v4(),
+            PaneId::Left,
+            Uuid::new_v4(),
+            host.id,
+            &host,
+            false,
+            rejected,
+        ) {
+            Message::Dialog(DialogMessage::PasswordFallbackRequired(request)) => {
+                assert!(request.session_id.is_none());
+                assert!(request.sftp_context.is_some());
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn connect_failures_explain_the_next_step() {
+        let refused = connect_failure_message(&crate::error::SshError::ConnectionRefused {
+            host: "example.com".to_string(),
+            port: 2222,
+        });
+        assert!(refused.starts_with("Connection failed: Connection refused by example.com:2222."));
+        assert!(refused.contains("SSH server is listening"));
+
+        let timeout =
+            connect_failure_message(&crate::error::SshError::Timeout("example.com:22".into()));
+        assert!(timeout.contains("Settings"));
+
+        let auth = connect_failure_message(&crate::error::SshError::AuthenticationFailed(
+            "denied".to_string(),
+        ));
+        assert_eq!(auth, "Connection failed: Authentication failed: denied");
+    }
+
+    #[test]
+    fn connection_tests_ask_again_for_encrypted_keys() {
+        assert_eq!(
+            connection_test_status(Ok("publickey".to_string())),
+            ConnectionTestStatus::Succeeded("Signed in with publickey".to_string())
+        );
+        assert!(matches!(
+            connection_test_status(Err(SshError::KeyFilePassphraseInvalid(PathBuf::from(
+                "/tmp/id_ed25519"
+            )))),
+            ConnectionTestStatus::PassphraseRequired(message) if message.starts_with("Incorrect")
+        ));
+        assert!(matches!(
+            connection_test_status(Err(SshError::HostKeyVerification("rejected".to_string()))),
+            ConnectionTestStatus::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn host_keepalive_prefers_host_overrides() {
+        let now = Utc::now();
+        let mut host = Host {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            hostname: "example.com".to_string(),
+            port: 22,
+            username: "user".to_string(),
+            auth: AuthMethod::Agent,
+            protocol: crate::config::Protocol::Ssh,
+            vnc_port: None,
+            vnc_password_id: None,
+            vnc_via_ssh_host_id: None,
+            allow_cleartext_vnc: false,
+            auth_fallbacks: Vec::new(),
+            agent_forwarding: false,
+            port_forwards: Vec::new(),
+            hub_routing: HubRouting::Auto,
+            jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
+            group_id: None,
+            order: 0,
+            notes: None,
+            tags: vec![],
+            created_at: now,
+            updated_at: now,
+            detected_os: None,
+            last_connected: None,
+        };
+        let defaults = Keepalive {
+            interval_secs: 60,
+            count_max: 3,
+        };
+        assert_eq!(host_keepalive(&host, defaults), defaults);
+
+        host.keepalive_interval = Some(0);
+        host.keepalive_count_max = Some(6);
+        assert_eq!(
+            host_keepalive(&host, defaults),
+            Keepalive {
+                interval_secs: 0,
+                count_max: 6,
+            }
+        );
+    }
+
     #[test]
     fn sftp_connect_error_requests_passphrase() {
         let now = Utc::now();
@@ -822,11 +1187,19 @@ mod tests {
             vnc_password_id: None,
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Auto,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: vec![],
             created_at: now,
@@ -840,8 +1213,15 @@ mod tests {
         let host_id = host.id;
         let error = SftpError::KeyFilePassphraseRequired(PathBuf::from("/tmp/id_ed25519"));
 
-        let message =
-            map_sftp_connect_error(tab_id, pane_id, sftp_session_id, host_id, &host, error);
+        let message = map_sftp_connect_error(
+            tab_id,
+            pane_id,
+            sftp_session_id,
+            host_id,
+            &host,
+            false,
+            error,
+        );
         match message {
             Message::Dialog(DialogMessage::PassphraseRequired(request)) => {
                 assert!(!request.is_ssh);
@@ -867,11 +1247,19 @@ mod tests {
             vnc_password_id: None,
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Hub,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: vec![],
             created_at: now,
@@ -1,18 +1,20 @@
 use iced::Task;
-use image::{GenericImageView, ImageEncoder};
+use iced::widget::scrollable::AbsoluteOffset;
+use image::ImageEncoder;
 use pdfium_render::prelude::{PdfRenderConfig, Pdfium};
 use std::path::{Path, PathBuf};
 
 const MAX_TEXT_BYTES: u64 = 2 * 1024 * 1024;
 const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
 const MAX_PDF_BYTES: u64 = 50 * 1024 * 1024;
+const MAX_HEX_BYTES: u64 = 16 * 1024 * 1024;
 
 fn file_type_limit(file_type: &FileType) -> u64 {
     match file_type {
         FileType::Text { .. } | FileType::Markdown => MAX_TEXT_BYTES,
         FileType::Image => MAX_IMAGE_BYTES,
         FileType::Pdf => MAX_PDF_BYTES,
-        FileType::Binary => 0,
+        FileType::Binary => MAX_HEX_BYTES,
     }
 }
 
@@ -21,7 +23,10 @@ use crate::fs_utils::{
 };
 use crate::message::{FileViewerMessage, Message, SessionId};
 use crate::sftp::SharedSftpSession;
-use crate::views::file_viewer::{FileSource, FileType, FileViewerState, ViewerContent};
+use crate::views::file_viewer::{
+    FileSource, FileType, FileViewerState, ImageMetadata, PdfPageText, PdfTextRect, RasterImage,
+    ViewerContent,
+};
 
 /// Load file content from local path based on file type
 pub async fn load_local_file(path: PathBuf, file_type: FileType) -> Result<ViewerContent, String> {
@@ -38,24 +43,14 @@ pub async fn load_local_file(path: PathBuf, file_type: FileType) -> Result<Viewe
                 content: iced::widget::text_editor::Content::with_text(&text),
                 raw_text: text,
                 preview_mode: false,
+                preview: Vec::new(),
             })
         }
         FileType::Image => {
             let data = read_local_file_bytes(path.clone(), MAX_IMAGE_BYTES, "Image").await?;
-            let path_for_parse = path.clone();
-            let data_for_parse = data.clone();
-            let (width, height, is_svg) = tokio::task::spawn_blocking(move || {
-                parse_image_dimensions(&path_for_parse, &data_for_parse)
-            })
-            .await
-            .map_err(|e| format!("Image decode task failed: {}", e))??;
-            Ok(ViewerContent::Image {
-                data,
-                zoom: 1.0,
-                width,
-                height,
-                is_svg,
-            })
+            tokio::task::spawn_blocking(move || decode_image(&path, data))
+                .await
+                .map_err(|e| format!("Image decode task failed: {}", e))?
         }
         FileType::Pdf => {
             enforce_local_size(&path, MAX_PDF_BYTES, "PDF").await?;
@@ -64,7 +59,10 @@ pub async fn load_local_file(path: PathBuf, file_type: FileType) -> Result<Viewe
                 .await
                 .map_err(|e| format!("PDF inspect task failed: {}", e))?
         }
-        FileType::Binary => Err("Binary files cannot be viewed".to_string()),
+        FileType::Binary => {
+            let bytes = read_local_file_bytes(path, MAX_HEX_BYTES, "Binary").await?;
+            Ok(ViewerContent::Hex { bytes, offset: 0 })
+        }
     }
 }
 
@@ -88,20 +86,106 @@ async fn read_local_file_bytes(
         .map_err(|e| format!("{} read task failed: {}", label, e))?
 }
 
-fn parse_image_dimensions(path: &Path, data: &[u8]) -> Result<(u32, u32, bool), String> {
+/// Decode an image, turning photos upright by their EXIF orientation. SVG
+/// is kept as-is for the SVG renderer.
+fn decode_image(path: &Path, data: Vec<u8>) -> Result<ViewerContent, String> {
     let is_svg = path
         .extension()
         .and_then(|ext| ext.to_str())
         .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
 
     if is_svg {
-        return Ok((0, 0, true));
+        return Ok(ViewerContent::Image {
+            data,
+            zoom: 1.0,
+            fit_to_window: false,
+            scroll: AbsoluteOffset::default(),
+            width: 0,
+            height: 0,
+            is_svg: true,
+            raster: None,
+        });
+    }
+
+    let decode_error =
+        |e: image::ImageError| format!("Failed to decode image {}: {}", path.display(), e);
+    let format = image::guess_format(&data).map_err(decode_error)?;
+    let mut image = image::load_from_memory_with_format(&data, format).map_err(decode_error)?;
+
+    let mut metadata = read_exif_metadata(&data);
+    metadata.format = format!("{:?}", format).to_uppercase();
+    if let Some(orientation) = metadata
+        .orientation
+        .and_then(|value| u8::try_from(value).ok())
+        .and_then(image::metadata::Orientation::from_exif)
+    {
+        image.apply_orientation(orientation);
+    }
+
+    let pixels = image.into_rgba8();
+    Ok(ViewerContent::Image {
+        data,
+        zoom: 1.0,
+        fit_to_window: false,
+        scroll: AbsoluteOffset::default(),
+        width: pixels.width(),
+        height: pixels.height(),
+        is_svg: false,
+        raster: Some(RasterImage::new(pixels, metadata)),
+    })
+}
+
+/// Orientation, camera and capture date from the image's EXIF block, if any
+fn read_exif_metadata(data: &[u8]) -> ImageMetadata {
+    let Ok(exif) = exif::Reader::new().read_from_container(&mut std::io::Cursor::new(data)) else {
+        return ImageMetadata::default();
+    };
+
+    let field = |tag| exif.get_field(tag, exif::In::PRIMARY);
+    let ascii = |tag| match &field(tag)?.value {
+        exif::Value::Ascii(values) => values
+            .first()
+            .map(|value| {
+                String::from_utf8_lossy(value)
+                    .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+                    .to_string()
+            })
+            .filter(|value| !value.is_empty()),
+        _ => None,
+    };
+
+    ImageMetadata {
+        format: String::new(),
+        orientation: field(exif::Tag::Orientation).and_then(|field| field.value.get_uint(0)),
+        camera: camera_name(ascii(exif::Tag::Make), ascii(exif::Tag::Model)),
+        taken: ascii(exif::Tag::DateTimeOriginal)
+            .or_else(|| ascii(exif::Tag::DateTime))
+            .map(|value| exif_date(&value)),
+    }
+}
+
+/// Camera make and model, without repeating the make when the model
+/// already starts with it (e.g. "Canon" + "Canon EOS R5")
+fn camera_name(make: Option<String>, model: Option<String>) -> Option<String> {
+    match (make, model) {
+        (Some(make), Some(model)) => {
+            let brand = make.split_whitespace().next().unwrap_or_default();
+            if model.to_lowercase().starts_with(&brand.to_lowercase()) {
+                Some(model)
+            } else {
+                Some(format!("{} {}", make, model))
+            }
+        }
+        (make, model) => make.or(model),
     }
+}
 
-    let image = image::load_from_memory(data)
-        .map_err(|e| format!("Failed to decode image {}: {}", path.display(), e))?;
-    let (width, height) = image.dimensions();
-    Ok((width, height, false))
+/// EXIF writes dates as "YYYY:MM:DD HH:MM:SS"; show the date with dashes
+fn exif_date(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((date, time)) if date.len() == 10 => format!("{} {}", date.replace(':', "-"), time),
+        _ => value.to_string(),
+    }
 }
 
 fn inspect_pdf(path: &Path) -> Result<ViewerContent, String> {
@@ -172,7 +256,7 @@ async fn ensure_private_dir(path: &Path) -> Result<(), String> {
     .map_err(|e| format!("Directory preparation task failed: {}", e))?
 }
 
-async fn prepare_remote_viewer_temp_dir(temp_dir: &Path) -> Result<(), String> {
+pub(super) async fn prepare_remote_viewer_temp_dir(temp_dir: &Path) -> Result<(), String> {
     let base_dir = temp_dir
         .parent()
         .ok_or_else(|| format!("Cannot determine temp base for {}", temp_dir.display()))?;
@@ -230,6 +314,79 @@ fn render_pdf_page_sync(path: &Path, page_index: usize) -> Result<Vec<u8>, Strin
     Ok(png)
 }
 
+/// Read the text layer of every page with each character's bounds, for search
+pub async fn extract_pdf_text(source: FileSource) -> Result<Vec<PdfPageText>, String> {
+    let path = match source {
+        FileSource::Local { path } => path,
+        FileSource::Remote { temp_path, .. } => temp_path,
+    };
+
+    tokio::task::spawn_blocking(move || extract_pdf_text_sync(&path))
+        .await
+        .map_err(|e| format!("PDF text task failed: {}", e))?
+}
+
+fn extract_pdf_text_sync(path: &Path) -> Result<Vec<PdfPageText>, String> {
+    ensure_regular_file_sync(path, "PDF")?;
+
+    let bindings = Pdfium::bind_to_system_library()
+        .map_err(|e| format!("PDF rendering unavailable: {}", e))?;
+    let pdfium = Pdfium::new(bindings);
+
+    let document = pdfium
+        .load_pdf_from_file(path, None)
+        .map_err(|e| format!("Failed to open PDF {}: {}", path.display(), e))?;
+
+    let mut pages = Vec::new();
+    for (page_index, page) in document.pages().iter().enumerate() {
+        let width = page.width().value;
+        let height = page.height().value;
+        let text = page
+            .text()
+            .map_err(|e| format!("Failed to read text of PDF page {}: {}", page_index + 1, e))?;
+
+        let chars = text
+            .chars()
+            .iter()
+            .filter_map(|ch| {
+                let c = ch.unicode_char()?;
+                // PDF coordinates start at the bottom left of the page.
+                let rect = ch
+                    .loose_bounds()
+                    .ok()
+                    .filter(|_| width > 0.0 && height > 0.0)
+                    .map(|bounds| PdfTextRect {
+                        x: bounds.left().value / width,
+                        y: (height - bounds.top().value) / height,
+                        width: (bounds.right().value - bounds.left().value) / width,
+                        height: (bounds.top().value - bounds.bottom().value) / height,
+                    });
+                Some((c, rect))
+            })
+            .collect();
+        pages.push(PdfPageText { chars });
+    }
+
+    Ok(pages)
+}
+
+/// Load the viewer's local copy again as its current content type, e.g. after
+/// switching hex mode.
+pub fn reload_viewer(viewer: &FileViewerState) -> Task<Message> {
+    let viewer_id = viewer.viewer_id;
+    let path = viewer.file_source.local_path().to_path_buf();
+    let file_type = viewer.content_type();
+    Task::perform(
+        async move { load_local_file(path, file_type).await },
+        move |result| match result {
+            Ok(content) => {
+                Message::FileViewer(FileViewerMessage::ContentLoaded { viewer_id, content })
+            }
+            Err(e) => Message::FileViewer(FileViewerMessage::LoadError(viewer_id, e)),
+        },
+    )
+}
+
 pub fn build_local_viewer(
     viewer_id: SessionId,
     file_name: String,
@@ -308,7 +465,7 @@ pub fn build_remote_viewer(
     (viewer_state, task)
 }
 
-fn safe_temp_file_name(file_name: &str) -> String {
+pub(super) fn safe_temp_file_name(file_name: &str) -> String {
     let mut safe = String::with_capacity(file_name.len());
     for ch in file_name.chars() {
         if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
@@ -329,8 +486,8 @@ fn safe_temp_file_name(file_name: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::{
-        MAX_TEXT_BYTES, enforce_local_size, ensure_regular_file_sync, load_local_file,
-        prepare_remote_viewer_temp_dir, safe_temp_file_name,
+        MAX_TEXT_BYTES, camera_name, decode_image, enforce_local_size, ensure_regular_file_sync,
+        exif_date, load_local_file, prepare_remote_viewer_temp_dir, safe_temp_file_name,
     };
     use crate::views::file_viewer::{FileType, ViewerContent};
 
@@ -344,6 +501,65 @@ mod tests {
         assert_eq!(safe_temp_file_name(".."), "remote_file");
     }
 
+    #[test]
+    fn camera_name_does_not_repeat_the_make() {
+        let name = |make: Option<&str>, model: Option<&str>| {
+            camera_name(make.map(str::to_string), model.map(str::to_string))
+        };
+        assert_eq!(
+            name(Some("Canon"), Some("Canon EOS R5")).as_deref(),
+            Some("Canon EOS R5")
+        );
+        assert_eq!(
+            name(Some("NIKON CORPORATION"), Some("NIKON D750")).as_deref(),
+            Some("NIKON D750")
+        );
+        assert_eq!(
+            name(Some("Apple"), Some("iPhone 15")).as_deref(),
+            Some("Apple iPhone 15")
+        );
+        assert_eq!(name(None, Some("X100V")).as_deref(), Some("X100V"));
+        assert_eq!(name(None, None), None);
+    }
+
+    #[test]
+    fn exif_date_uses_dashes_in_the_date() {
+        assert_eq!(exif_date("2024:06:01 18:42:07"), "2024-06-01 18:42:07");
+        assert_eq!(exif_date("unknown"), "unknown");
+    }
+
+    #[test]
+    fn decode_image_reads_format_and_size_and_skips_svg() {
+        let mut png = Vec::new();
+        image::RgbaImage::new(3, 2)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let ViewerContent::Image {
+            width,
+            height,
+            is_svg,
+            raster: Some(raster),
+            ..
+        } = decode_image(std::path::Path::new("dot.png"), png).unwrap()
+        else {
+            panic!("expected a raster image");
+        };
+        assert_eq!((width, height, is_svg), (3, 2, false));
+        assert_eq!(raster.metadata.format, "PNG");
+        assert_eq!(raster.metadata.orientation, None);
+
+        let svg = decode_image(std::path::Path::new("logo.SVG"), b"<svg/>".to_vec()).unwrap();
+        assert!(matches!(
+            svg,
+            ViewerContent::Image {
+                is_svg: true,
+                raster: None,
+                ..
+            }
+        ));
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn enforce_local_size_rejects_symlinks() {
@@ -390,6 +606,25 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn load_local_file_reads_binary_as_hex() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("blob.bin");
+        std::fs::write(&path, [0u8, 159, 146, 150]).unwrap();
+
+        let content = load_local_file(path, FileType::Binary)
+            .await
+            .expect("binary file should load");
+
+        match content {
+            ViewerContent::Hex { bytes, offset } => {
+                assert_eq!(bytes, vec![0, 159, 146, 150]);
+                assert_eq!(offset, 0);
+            }
+            other => panic!("expected hex content, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn load_local_file_rejects_text_symlink() {
@@ -0,0 +1,102 @@
+//! Polling watcher that uploads a local file whenever it changes, or only
+//! reports the change for files opened with an external program
+//!
+//! Uses metadata polling rather than OS notifications: editors save through
+//! rename-and-replace, which breaks inotify-style watches on the old inode,
+//! and a single file does not need anything faster.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use futures::{Stream, StreamExt};
+use iced::Task;
+use uuid::Uuid;
+
+use crate::message::{Message, SftpMessage};
+use crate::sftp::SharedSftpSession;
+
+/// How often the watched file's metadata is checked
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// The file must stay unchanged this long before it is uploaded, so a save
+/// that writes in several steps is sent once
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// Size and modification time; `None` while the file is missing (for
+/// example in the middle of an editor's atomic replace)
+type Fingerprint = Option<(u64, SystemTime)>;
+
+async fn fingerprint(path: &Path) -> Fingerprint {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+/// Yields once each time `local_path` changes and then stays unchanged
+/// for [`WATCH_DEBOUNCE`]
+fn settled_changes(local_path: PathBuf) -> impl Stream<Item = ()> + Send + 'static {
+    async_stream::stream! {
+        let mut last_seen = fingerprint(&local_path).await;
+        loop {
+            tokio::time::sleep(WATCH_POLL_INTERVAL).await;
+            let current = fingerprint(&local_path).await;
+            if current.is_none() || current == last_seen {
+                continue;
+            }
+
+            let mut settled = current;
+            loop {
+                tokio::time::sleep(WATCH_DEBOUNCE).await;
+                let next = fingerprint(&local_path).await;
+                if next == settled {
+                    break;
+                }
+                settled = next;
+            }
+            if settled.is_none() {
+                continue;
+            }
+            last_seen = settled;
+            yield ();
+        }
+    }
+}
+
+/// Watch `local_path` and upload it to `remote_path` after every change.
+/// Runs until the returned task is aborted.
+pub fn watch_and_upload(
+    watch_id: Uuid,
+    local_path: PathBuf,
+    remote_path: PathBuf,
+    sftp: SharedSftpSession,
+) -> Task<Message> {
+    Task::run(
+        async_stream::stream! {
+            let changes = settled_changes(local_path.clone());
+            futures::pin_mut!(changes);
+            while changes.next().await.is_some() {
+                tracing::debug!(
+                    "Watched file {} changed, uploading to {}",
+                    local_path.display(),
+                    remote_path.display()
+                );
+                let result = sftp
+                    .upload(&local_path, &remote_path)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+                yield Message::Sftp(SftpMessage::WatchUploaded(watch_id, result));
+            }
+        },
+        |message| message,
+    )
+}
+
+/// Watch `local_path` and report each change without uploading it, so the
+/// user can decide. Runs until the returned task is aborted.
+pub fn watch_for_edits(watch_id: Uuid, local_path: PathBuf) -> Task<Message> {
+    Task::run(settled_changes(local_path), move |()| {
+        Message::Sftp(SftpMessage::WatchChanged(watch_id))
+    })
+}
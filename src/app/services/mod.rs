@@ -1,3 +1,5 @@
 pub mod connection;
 pub mod file_viewer;
+pub mod file_watch;
 pub mod history;
+pub mod open_with;
@@ -0,0 +1,150 @@
+//! "Open With" for SFTP entries: run a user-chosen program on a file
+//!
+//! Remote files are downloaded to a private temp folder first; the caller
+//! watches that copy so edits can be uploaded back.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use uuid::Uuid;
+
+use crate::sftp::SharedSftpSession;
+
+use super::file_viewer::{prepare_remote_viewer_temp_dir, safe_temp_file_name};
+
+/// Split a command line into program and arguments. Whitespace separates
+/// words; single or double quotes keep spaces inside one word.
+pub fn split_command(command: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+
+    for ch in command.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => word.push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote = Some(ch);
+                in_word = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            None => {
+                word.push(ch);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("The command has an unclosed quote".to_string());
+    }
+    if in_word {
+        words.push(word);
+    }
+    if words.is_empty() {
+        return Err("Enter a command to open with".to_string());
+    }
+    Ok(words)
+}
+
+/// Start `command` with `path` as its last argument, without waiting for
+/// it to exit
+pub fn launch(command: &str, path: &Path) -> Result<(), String> {
+    let words = split_command(command)?;
+    let (program, args) = words
+        .split_first()
+        .expect("split_command never returns empty");
+
+    let mut child = Command::new(program)
+        .args(args)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("Failed to run {}: {}", program, error))?;
+
+    // Reap the program when it exits so it does not linger as a zombie
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Private folder holding the downloaded copy for an Open With edit
+pub fn edit_temp_dir(edit_id: Uuid) -> PathBuf {
+    std::env::temp_dir()
+        .join("portal_open_with")
+        .join(edit_id.to_string())
+}
+
+/// Delete an edit's temp folder and the copy in it
+pub fn remove_edit_dir(dir: &Path) {
+    if let Err(error) = std::fs::remove_dir_all(dir)
+        && error.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove {}: {}", dir.display(), error);
+    }
+}
+
+/// Download `remote_path` into the edit's temp folder; returns the copy
+pub async fn download_for_edit(
+    sftp: SharedSftpSession,
+    remote_path: PathBuf,
+    file_name: String,
+    edit_id: Uuid,
+) -> Result<PathBuf, String> {
+    let temp_dir = edit_temp_dir(edit_id);
+    prepare_remote_viewer_temp_dir(&temp_dir).await?;
+    let local_path = temp_dir.join(safe_temp_file_name(&file_name));
+    if let Err(error) = sftp.download(&remote_path, &local_path).await {
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        return Err(format!("Failed to download {}: {}", file_name, error));
+    }
+    Ok(local_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_command;
+
+    #[test]
+    fn split_command_separates_program_and_arguments() {
+        assert_eq!(
+            split_command("  code --wait  -n "),
+            Ok(vec![
+                "code".to_string(),
+                "--wait".to_string(),
+                "-n".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn split_command_keeps_quoted_spaces() {
+        assert_eq!(
+            split_command(r#""/Applications/Sublime Text.app/bin/subl" -w 'a b'"#),
+            Ok(vec![
+                "/Applications/Sublime Text.app/bin/subl".to_string(),
+                "-w".to_string(),
+                "a b".to_string()
+            ])
+        );
+        assert_eq!(
+            split_command(r#"edit """#),
+            Ok(vec!["edit".to_string(), String::new()])
+        );
+    }
+
+    #[test]
+    fn split_command_rejects_blank_and_unclosed_quotes() {
+        assert!(split_command("   ").is_err());
+        assert!(split_command(r#"code "unclosed"#).is_err());
+    }
+}
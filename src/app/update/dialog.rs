@@ -5,16 +5,18 @@ use crate::app::services::connection;
 use crate::config::hosts::HubRouting;
 use crate::config::{AuthMethod, Host, PortForwardKind};
 use crate::message::{
-    DialogMessage, HostDialogField, Message, PortForwardField, QuickConnectField,
+    DialogMessage, HostDialogField, Message, PortForwardField, QuickConnectField, SessionMessage,
+    SnippetMessage,
 };
 use crate::security_log;
 use crate::ssh::host_key_verification::HostKeyVerificationResponse;
 use crate::views::dialogs::auth_prompt_dialog::AuthPromptDialogState;
 use crate::views::dialogs::host_dialog::{
-    AuthMethodChoice, KeySourceChoice, PortForwardEditorState,
+    AuthMethodChoice, ConnectionTestStatus, KeySourceChoice, PortForwardEditorState,
 };
 use crate::views::dialogs::host_key_dialog::HostKeyDialogState;
 use crate::views::dialogs::passphrase_dialog::PassphraseDialogState;
+use crate::views::dialogs::snippet_params_dialog::SnippetParamsTarget;
 use crate::views::toast::Toast;
 use iced::Task;
 use secrecy::{ExposeSecret, SecretString};
@@ -45,6 +47,14 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                     };
 
                     let is_edit = editing_id.is_some();
+                    if !is_edit
+                        && dialog_state.duplicate_of.is_none()
+                        && let Some(existing) = portal.config.hosts.find_duplicate(&host)
+                    {
+                        // Ask once; saving again adds the host anyway
+                        dialog_state.duplicate_of = Some((existing.id, existing.name.clone()));
+                        return Task::none();
+                    }
                     if is_edit {
                         if let Err(e) = portal.config.hosts.update_host(host.clone()) {
                             tracing::error!("Failed to update host: {}", e);
@@ -112,6 +122,15 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
         DialogMessage::FieldChanged(field, value) => {
             if let Some(dialog_state) = portal.dialogs.host_mut() {
                 dialog_state.delete_requested = false;
+                dialog_state.duplicate_of = None;
+                dialog_state.connection_test = None;
+                let edited_field = match field {
+                    HostDialogField::Name => Some("name"),
+                    HostDialogField::Hostname => Some("hostname"),
+                    HostDialogField::Port => Some("port"),
+                    HostDialogField::Username => Some("username"),
+                    _ => None,
+                };
                 match field {
                     HostDialogField::Name => dialog_state.name = value,
                     HostDialogField::Hostname => dialog_state.hostname = value,
@@ -128,6 +147,10 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                     HostDialogField::VaultKeyId => {
                         dialog_state.vault_key_id = value.parse().ok();
                     }
+                    HostDialogField::AgentIdentity => {
+                        dialog_state.agent_identity =
+                            (!value.trim().is_empty()).then(|| value.trim().to_string());
+                    }
                     HostDialogField::VncPasswordId => {
                         dialog_state.vnc_password_id = if value.trim().is_empty() {
                             None
@@ -146,6 +169,13 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                         dialog_state.agent_forwarding =
                             matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes");
                     }
+                    HostDialogField::SessionLogging => {
+                        dialog_state.session_logging = match value.as_str() {
+                            "Always" => Some(true),
+                            "Never" => Some(false),
+                            _ => None,
+                        };
+                    }
                     HostDialogField::HubRouting => {
                         dialog_state.hub_routing = match value.as_str() {
                             "Auto" => HubRouting::Auto,
@@ -162,6 +192,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                         };
                     }
                     HostDialogField::Tags => dialog_state.tags = value,
+                    HostDialogField::SftpStartPath => dialog_state.sftp_start_path = value,
                     HostDialogField::Notes => dialog_state.notes = value,
                     HostDialogField::AuthMethod => {
                         dialog_state.auth_method = match value.as_str() {
@@ -186,6 +217,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                                     dialog_state.port = "5900".to_string();
                                 }
                                 dialog_state.agent_forwarding = false;
+                                dialog_state.session_logging = None;
                                 dialog_state.hub_routing = HubRouting::Auto;
                                 ProtocolChoice::Vnc
                             }
@@ -193,6 +225,98 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                         };
                     }
                 }
+                if let Some(edited_field) = edited_field {
+                    dialog_state.validate_field(edited_field);
+                }
+                // Other edits can fix an error shown by the last submit
+                // (a new port from the protocol switch, a chosen vault key)
+                let shown: Vec<String> = dialog_state.validation_errors.keys().cloned().collect();
+                for field in shown {
+                    dialog_state.validate_field(&field);
+                }
+            }
+            Task::none()
+        }
+        DialogMessage::AgentIdentitiesLoad => {
+            let Some(dialog_state) = portal.dialogs.host_mut() else {
+                return Task::none();
+            };
+            dialog_state.agent_identities_loading = true;
+            Task::perform(
+                async {
+                    crate::ssh::auth::list_agent_identities()
+                        .await
+                        .map_err(|e| e.to_string())
+                },
+                |result| Message::Dialog(DialogMessage::AgentIdentitiesLoaded(result)),
+            )
+        }
+        DialogMessage::AgentIdentitiesLoaded(result) => {
+            if let Some(dialog_state) = portal.dialogs.host_mut() {
+                dialog_state.agent_identities_loading = false;
+                dialog_state.agent_identities = Some(result);
+            }
+            Task::none()
+        }
+        DialogMessage::ConnectionTest => {
+            let Some(dialog_state) = portal.dialogs.host_mut() else {
+                return Task::none();
+            };
+            if dialog_state.is_testing() {
+                return Task::none();
+            }
+            let editing_id = dialog_state.editing_id;
+            let Some(host) = dialog_state.to_host() else {
+                return Task::none();
+            };
+            let host = match editing_id.and_then(|id| portal.config.hosts.find_host(id)) {
+                Some(existing) => preserve_existing_host_metadata(host, existing),
+                None => host,
+            };
+
+            let secret = dialog_state
+                .test_secret_label()
+                .map(|_| dialog_state.test_secret.clone())
+                .filter(|secret| !secret.expose_secret().is_empty());
+            if host.needs_password() && secret.is_none() {
+                dialog_state.connection_test = Some(ConnectionTestStatus::Failed(
+                    "Enter the password to test with".to_string(),
+                ));
+                return Task::none();
+            }
+            let jump_chain =
+                match crate::ssh::tunnel::resolve_jump_chain(&portal.config.hosts.hosts, &host) {
+                    Ok(chain) => chain,
+                    Err(error) => {
+                        dialog_state.connection_test =
+                            Some(ConnectionTestStatus::Failed(error.to_string()));
+                        return Task::none();
+                    }
+                };
+
+            let test_id = Uuid::new_v4();
+            dialog_state.connection_test = Some(ConnectionTestStatus::Running(test_id));
+            tracing::info!("Testing connection to {}:{}", host.hostname, host.port);
+            connection::ssh_test_connection_task(
+                host,
+                jump_chain,
+                portal.prefs.keepalive(),
+                secret,
+                test_id,
+            )
+        }
+        DialogMessage::ConnectionTestSecretChanged(secret) => {
+            if let Some(dialog_state) = portal.dialogs.host_mut() {
+                dialog_state.test_secret = secret;
+            }
+            Task::none()
+        }
+        DialogMessage::ConnectionTestFinished(test_id, status) => {
+            // Results of a test the form has moved on from are dropped
+            if let Some(dialog_state) = portal.dialogs.host_mut()
+                && dialog_state.connection_test == Some(ConnectionTestStatus::Running(test_id))
+            {
+                dialog_state.connection_test = Some(status);
             }
             Task::none()
         }
@@ -296,33 +420,29 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
             Task::none()
         }
         DialogMessage::ImportFromSshConfig => {
-            match portal.config.hosts.import_from_ssh_config() {
-                Ok(count) => {
-                    if count > 0
-                        && let Err(e) = portal.config.hosts.save()
-                    {
-                        tracing::error!("Failed to save hosts after SSH import: {}", e);
-                        portal
-                            .toast_manager
-                            .push(Toast::error("Failed to save imported hosts"));
-                        return Task::none();
-                    }
-                    let message = format!("Imported {} host(s) from SSH config", count);
-                    if count > 0 {
-                        super::ui::settings::queue_portal_hub_local_sync(portal);
-                        portal.toast_manager.push(Toast::success(message));
-                    } else {
-                        portal.toast_manager.push(Toast::warning(message));
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to import hosts from SSH config: {}", e);
-                    portal
-                        .toast_manager
-                        .push(Toast::error("Failed to import SSH config"));
+            let result = portal.config.hosts.import_from_ssh_config();
+            finish_ssh_config_import(portal, result, "SSH config")
+        }
+        DialogMessage::ImportSshConfigFile => Task::perform(
+            async {
+                let mut dialog = rfd::FileDialog::new().set_title("Import SSH config");
+                if let Some(dir) = crate::config::paths::ssh_dir() {
+                    dialog = dialog.set_directory(dir);
                 }
-            }
-            Task::none()
+                dialog.pick_file()
+            },
+            |path| match path {
+                Some(path) => Message::Dialog(DialogMessage::SshConfigFileChosen(path)),
+                None => Message::Noop,
+            },
+        ),
+        DialogMessage::SshConfigFileChosen(path) => {
+            let result = portal.config.hosts.import_ssh_config(&path);
+            let source = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            finish_ssh_config_import(portal, result, &source)
         }
         DialogMessage::AuthPrompt(mut wrapper) => {
             if let Some(request) = wrapper.0.take() {
@@ -395,6 +515,65 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
             portal.dialogs.close();
             Task::none()
         }
+        DialogMessage::QuitConfirmDontAskToggled(value) => {
+            if let Some(dialog) = portal.dialogs.quit_confirm_mut() {
+                dialog.dont_ask_again = value;
+            }
+            Task::none()
+        }
+        DialogMessage::QuitConfirmQuit => {
+            let dont_ask_again = portal
+                .dialogs
+                .quit_confirm_mut()
+                .is_some_and(|dialog| dialog.dont_ask_again);
+            portal.dialogs.close();
+            if dont_ask_again {
+                portal.prefs.confirm_quit = false;
+                portal.save_settings();
+            }
+            tracing::info!("Quitting with running sessions (user confirmed)");
+            portal.save_remembered_sftp_paths();
+            portal.file_watches.clear();
+            iced::exit()
+        }
+        DialogMessage::QuitConfirmCancel => {
+            portal.dialogs.close();
+            Task::none()
+        }
+        DialogMessage::SnippetParamChanged(index, value) => {
+            if let Some(dialog) = portal.dialogs.snippet_params_mut() {
+                dialog.set_value(index, value);
+            }
+            Task::none()
+        }
+        DialogMessage::SnippetParamsSubmit => {
+            let Some(dialog) = portal.dialogs.snippet_params_mut() else {
+                return Task::none();
+            };
+            match dialog.resolved_command() {
+                Ok(command) => {
+                    let snippet_id = dialog.snippet_id;
+                    let target = dialog.target;
+                    portal.dialogs.close();
+                    match target {
+                        SnippetParamsTarget::Run => portal.update(Message::Snippet(
+                            SnippetMessage::RunResolved(snippet_id, command),
+                        )),
+                        SnippetParamsTarget::Insert(session_id) => portal.update(Message::Session(
+                            SessionMessage::InsertText(session_id, command),
+                        )),
+                    }
+                }
+                Err(error) => {
+                    dialog.error = Some(error);
+                    Task::none()
+                }
+            }
+        }
+        DialogMessage::SnippetParamsCancel => {
+            portal.dialogs.close();
+            Task::none()
+        }
         DialogMessage::HostKeyVerification(mut wrapper) => {
             if let Some(request) = wrapper.0.take() {
                 portal
@@ -494,6 +673,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                                 portal.terminal_initial_size(),
                                 should_detect_os,
                                 portal.prefs.allow_agent_forwarding,
+                                portal.prefs.keepalive(),
                                 jump_chain,
                                 password,
                             );
@@ -501,6 +681,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                                 dialog_host_name,
                                 &protocol_label,
                                 session_id,
+                                Some(host_id),
                                 task,
                             );
                         }
@@ -568,12 +749,63 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                 {
                     portal.sftp.clear_pending_connection();
                 }
+                if dialog.connection_kind
+                    == crate::views::dialogs::password_dialog::PasswordConnectionKind::Ssh
+                {
+                    portal.terminal_panes.cancel_pending();
+                }
                 // Clear password for security
                 dialog.clear_password();
             }
             portal.dialogs.close();
             Task::none()
         }
+        DialogMessage::PasswordFallbackRequired(request) => {
+            use crate::views::dialogs::password_dialog::PasswordDialogState;
+
+            let Some(host) = portal.config.hosts.find_host(request.host_id).cloned() else {
+                return Task::none();
+            };
+            let mut dialog = if let Some(session_id) = request.session_id {
+                // Reconnects keep their existing retry handling
+                if portal.sessions.contains(session_id) {
+                    return Task::done(Message::Session(SessionMessage::ConnectFailed {
+                        session_id,
+                        error: "Connection failed: Authentication failed".to_string(),
+                    }));
+                }
+                if !portal.finish_pending_connect_for(session_id) {
+                    return Task::none();
+                }
+                portal.pre_session_terminal_output.remove(&session_id);
+                PasswordDialogState::new_ssh(
+                    host.name.clone(),
+                    host.hostname.clone(),
+                    host.port,
+                    host.effective_username(),
+                    host.id,
+                )
+            } else if let Some(ctx) = request.sftp_context {
+                portal
+                    .sftp
+                    .set_pending_connection(Some((ctx.tab_id, ctx.pane_id, host.id)));
+                PasswordDialogState::new_sftp(
+                    host.name.clone(),
+                    host.hostname.clone(),
+                    host.port,
+                    host.effective_username(),
+                    host.id,
+                    ctx.tab_id,
+                    ctx.pane_id,
+                )
+            } else {
+                return Task::none();
+            };
+            dialog.error =
+                Some("The other sign-in methods were rejected; enter the password".to_string());
+            portal.dialogs.open_password(dialog);
+            Task::none()
+        }
         DialogMessage::PassphraseRequired(request) => {
             // Check passphrase cache first
             let cache = connection::shared_passphrase_cache();
@@ -602,6 +834,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                                     portal.terminal_initial_size(),
                                     request.should_detect_os,
                                     portal.prefs.allow_agent_forwarding,
+                                    portal.prefs.keepalive(),
                                     jump_chain,
                                     cached_passphrase,
                                 );
@@ -609,6 +842,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                                     request.host_name.clone(),
                                     &protocol_label,
                                     session_id,
+                                    Some(request.host_id),
                                     task,
                                 );
                             }
@@ -694,6 +928,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                                 portal.terminal_initial_size(),
                                 should_detect_os,
                                 portal.prefs.allow_agent_forwarding,
+                                portal.prefs.keepalive(),
                                 jump_chain,
                                 passphrase,
                             );
@@ -701,6 +936,7 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                                 dialog_host_name,
                                 &protocol_label,
                                 session_id,
+                                Some(host_id),
                                 task,
                             );
                         }
@@ -784,11 +1020,19 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
                     vnc_via_ssh_host_id: None,
                     allow_cleartext_vnc: false,
                     auth,
+                    auth_fallbacks: Vec::new(),
                     agent_forwarding: false,
                     port_forwards: Vec::new(),
                     hub_routing: HubRouting::Auto,
                     jump_host_id: None,
+                    keepalive_interval: None,
+                    keepalive_count_max: None,
+                    session_logging: None,
+                    agent_identity: None,
+                    sftp_start_path: None,
+                    last_sftp_path: None,
                     group_id: None,
+                    order: 0,
                     notes: None,
                     tags: vec![],
                     created_at: now,
@@ -806,14 +1050,55 @@ pub fn handle_dialog(portal: &mut Portal, msg: DialogMessage) -> Task<Message> {
     }
 }
 
+/// Save the hosts after an SSH config import and report how many were added
+fn finish_ssh_config_import(
+    portal: &mut Portal,
+    result: Result<usize, crate::error::ConfigError>,
+    source: &str,
+) -> Task<Message> {
+    match result {
+        Ok(count) => {
+            if count > 0
+                && let Err(e) = portal.config.hosts.save()
+            {
+                tracing::error!("Failed to save hosts after SSH import: {}", e);
+                portal
+                    .toast_manager
+                    .push(Toast::error("Failed to save imported hosts"));
+                return Task::none();
+            }
+            let message = format!("Imported {} host(s) from {}", count, source);
+            if count > 0 {
+                super::ui::settings::queue_portal_hub_local_sync(portal);
+                portal.toast_manager.push(Toast::success(message));
+            } else {
+                portal.toast_manager.push(Toast::warning(message));
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to import hosts from {}: {}", source, e);
+            portal
+                .toast_manager
+                .push(Toast::error(format!("Failed to import {}", source)));
+        }
+    }
+    Task::none()
+}
+
 fn preserve_existing_host_metadata(mut host: Host, existing: &Host) -> Host {
     host.created_at = existing.created_at;
     host.group_id = existing.group_id;
+    host.order = existing.order;
     host.detected_os = existing.detected_os.clone();
     host.last_connected = existing.last_connected;
     // "Don't warn again" for cleartext VNC is not edited in the dialog;
     // keep the user's earlier decision when the host is edited.
     host.allow_cleartext_vnc = existing.allow_cleartext_vnc;
+    // Fallback auth methods are only edited in hosts.toml.
+    host.auth_fallbacks = existing.auth_fallbacks.clone();
+    host.keepalive_interval = existing.keepalive_interval;
+    host.keepalive_count_max = existing.keepalive_count_max;
+    host.last_sftp_path = existing.last_sftp_path.clone();
     host
 }
 
@@ -873,11 +1158,19 @@ mod tests {
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
             auth: AuthMethod::Agent,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Auto,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: Some(Uuid::new_v4()),
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at: now - Duration::days(2),
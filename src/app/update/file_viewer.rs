@@ -1,17 +1,33 @@
 //! File viewer message handler
 
 use iced::Task;
-use iced::widget::text_editor;
+use secrecy::{ExposeSecret, SecretString};
 use std::path::Path;
 
 use crate::app::Portal;
-use crate::app::services::file_viewer;
+use crate::app::services::{connection, file_viewer};
+use crate::config::SettingsConfig;
 use crate::fs_utils::write_regular_file;
-use crate::message::{FileViewerMessage, Message, TabMessage};
-use crate::sftp::SharedSftpSession;
-use crate::views::file_viewer::FileSource;
+use crate::message::{FileViewerMessage, Message, SessionId, TabMessage};
+use crate::sftp::{SharedSftpSession, sudo};
+use crate::views::file_viewer::{
+    FileSource, GotoLineState, file_viewer_editor_id, goto_line_input_id, goto_line_target,
+    image_scroll_id,
+};
 use crate::views::toast::{Toast, ToastType};
 
+/// Select the next or previous PDF search match and show its page
+fn step_pdf_search(app: &mut Portal, viewer_id: SessionId, forward: bool) -> Task<Message> {
+    match app
+        .file_viewers
+        .get_mut(viewer_id)
+        .and_then(|viewer| viewer.pdf_search.step(forward))
+    {
+        Some(page) => handle_file_viewer(app, FileViewerMessage::PdfPageChange(viewer_id, page)),
+        None => Task::none(),
+    }
+}
+
 /// Handle file viewer messages
 pub fn handle_file_viewer(app: &mut Portal, msg: FileViewerMessage) -> Task<Message> {
     match msg {
@@ -19,7 +35,7 @@ pub fn handle_file_viewer(app: &mut Portal, msg: FileViewerMessage) -> Task<Mess
             if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
                 viewer.content = content;
                 if let Some(goto_line) = viewer.pending_goto_line.take() {
-                    scroll_viewer_to_line(viewer, goto_line);
+                    viewer.go_to_line(goto_line);
                 }
                 if let crate::views::file_viewer::ViewerContent::Pdf {
                     pages,
@@ -84,16 +100,26 @@ pub fn handle_file_viewer(app: &mut Portal, msg: FileViewerMessage) -> Task<Mess
                 if let Some(text) = viewer.get_text() {
                     viewer.is_saving = true;
                     let source = viewer.file_source.clone();
+                    // A sudo password means the plain save was refused
+                    let sudo_password = viewer
+                        .sudo_password
+                        .clone()
+                        .filter(|password| !password.expose_secret().is_empty());
 
                     // Get SFTP session if this is a remote file
                     let sftp_session = if let FileSource::Remote { session_id, .. } = &source {
+                        if let Some(password) = &sudo_password {
+                            connection::shared_sudo_cache().store(*session_id, password.clone());
+                        }
                         app.sftp.get_connection(*session_id).cloned()
                     } else {
                         None
                     };
 
                     return Task::perform(
-                        async move { save_file_content(source, text, sftp_session).await },
+                        async move {
+                            save_file_content(source, text, sftp_session, sudo_password).await
+                        },
                         move |result| {
                             Message::FileViewer(FileViewerMessage::SaveResult(viewer_id, result))
                         },
@@ -114,6 +140,28 @@ pub fn handle_file_viewer(app: &mut Portal, msg: FileViewerMessage) -> Task<Mess
                         return Task::done(Message::Tab(TabMessage::Close(viewer_id)));
                     }
                     Err(e) => {
+                        // A refused remote save can be retried through sudo,
+                        // with the connection's cached password if there is one
+                        if let FileSource::Remote { session_id, .. } = &viewer.file_source {
+                            if sudo::is_auth_failure(&e) {
+                                connection::shared_sudo_cache().remove(session_id);
+                                viewer.prompt_for_sudo();
+                            } else if viewer.sudo_password.is_none()
+                                && sudo::is_permission_denied(&e)
+                            {
+                                if let Some(password) =
+                                    connection::shared_sudo_cache().get(session_id)
+                                {
+                                    tracing::debug!("Retrying save with cached sudo password");
+                                    viewer.sudo_password = Some(password);
+                                    return Task::done(Message::FileViewer(
+                                        FileViewerMessage::Save(viewer_id),
+                                    ));
+                                }
+                                viewer.prompt_for_sudo();
+                            }
+                        }
+                        // The buffer stays modified so the edits can be saved again
                         app.toast_manager.push(Toast::new(
                             format!("Failed to save: {}", e),
                             ToastType::Error,
@@ -123,6 +171,18 @@ pub fn handle_file_viewer(app: &mut Portal, msg: FileViewerMessage) -> Task<Mess
             }
             Task::none()
         }
+        FileViewerMessage::SudoPasswordChanged(viewer_id, password) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.sudo_password = Some(password);
+            }
+            Task::none()
+        }
+        FileViewerMessage::SudoCancel(viewer_id) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.sudo_password = None;
+            }
+            Task::none()
+        }
         FileViewerMessage::PdfPageChange(viewer_id, page) => {
             if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
                 viewer.set_pdf_page(page);
@@ -188,39 +248,173 @@ pub fn handle_file_viewer(app: &mut Portal, msg: FileViewerMessage) -> Task<Mess
             }
             Task::none()
         }
+        FileViewerMessage::PdfSearchToggle(viewer_id) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                let search = &mut viewer.pdf_search;
+                search.open = !search.open;
+                if search.open && search.pages.is_none() && search.error.is_none() {
+                    let source = viewer.file_source.clone();
+                    return Task::perform(
+                        async move { file_viewer::extract_pdf_text(source).await },
+                        move |result| {
+                            Message::FileViewer(FileViewerMessage::PdfTextLoaded(viewer_id, result))
+                        },
+                    );
+                }
+            }
+            Task::none()
+        }
+        FileViewerMessage::PdfSearchChanged(viewer_id, query) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                let from_page = viewer.pdf_page().unwrap_or(0);
+                viewer.pdf_search.query = query;
+                if let Some(page) = viewer.pdf_search.refresh(from_page) {
+                    return handle_file_viewer(
+                        app,
+                        FileViewerMessage::PdfPageChange(viewer_id, page),
+                    );
+                }
+            }
+            Task::none()
+        }
+        FileViewerMessage::GotoLineOpen(viewer_id) => {
+            let Some(viewer) = app.file_viewers.get_mut(viewer_id) else {
+                return Task::none();
+            };
+            if viewer.editor_content().is_none() {
+                return Task::none();
+            }
+            viewer.goto_line.open = true;
+            viewer.goto_line.error = None;
+            iced::widget::operation::focus(goto_line_input_id())
+        }
+        FileViewerMessage::GotoLineChanged(viewer_id, input) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.goto_line.input = input;
+                viewer.goto_line.error = None;
+            }
+            Task::none()
+        }
+        FileViewerMessage::GotoLineSubmit(viewer_id) => {
+            let Some(viewer) = app.file_viewers.get_mut(viewer_id) else {
+                return Task::none();
+            };
+            let Some(line_count) = viewer.editor_content().map(|content| content.line_count())
+            else {
+                return Task::none();
+            };
+            match goto_line_target(&viewer.goto_line.input, line_count) {
+                Ok(line) => {
+                    viewer.go_to_line(line);
+                    viewer.goto_line.open = false;
+                    viewer.goto_line.error = None;
+                    viewer.goto_line.input.clear();
+                    iced::widget::operation::focus(file_viewer_editor_id())
+                }
+                Err(error) => {
+                    viewer.goto_line.error = Some(error);
+                    Task::none()
+                }
+            }
+        }
+        FileViewerMessage::GotoLineClose(viewer_id) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.goto_line = GotoLineState::default();
+            }
+            iced::widget::operation::focus(file_viewer_editor_id())
+        }
+        FileViewerMessage::ToggleWordWrap => {
+            app.prefs.viewer_word_wrap = !app.prefs.viewer_word_wrap;
+            let mut settings = SettingsConfig::load().unwrap_or_default();
+            settings.viewer_word_wrap = app.prefs.viewer_word_wrap;
+            if let Err(e) = settings.save() {
+                tracing::error!("Failed to save word wrap preference: {}", e);
+            }
+            Task::none()
+        }
+        FileViewerMessage::PdfSearchNext(viewer_id) => step_pdf_search(app, viewer_id, true),
+        FileViewerMessage::PdfSearchPrevious(viewer_id) => step_pdf_search(app, viewer_id, false),
+        FileViewerMessage::PdfTextLoaded(viewer_id, result) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                match result {
+                    Ok(pages) => {
+                        let from_page = viewer.pdf_page().unwrap_or(0);
+                        viewer.pdf_search.pages = Some(pages);
+                        if let Some(page) = viewer.pdf_search.refresh(from_page) {
+                            return handle_file_viewer(
+                                app,
+                                FileViewerMessage::PdfPageChange(viewer_id, page),
+                            );
+                        }
+                    }
+                    Err(e) => viewer.pdf_search.error = Some(e),
+                }
+            }
+            Task::none()
+        }
         FileViewerMessage::MarkdownTogglePreview(viewer_id) => {
             if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
                 viewer.toggle_preview();
             }
             Task::none()
         }
+        FileViewerMessage::OpenLink(url) => {
+            super::session::open_external_url(app, &url);
+            Task::none()
+        }
         FileViewerMessage::ImageZoom(viewer_id, zoom) => {
             if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
                 viewer.set_zoom(zoom);
             }
             Task::none()
         }
-    }
-}
-
-/// Scroll a freshly loaded text/markdown viewer so the target line sits near
-/// the top of the view, with a few lines of context above it.
-fn scroll_viewer_to_line(viewer: &mut crate::views::file_viewer::FileViewerState, line: usize) {
-    const GOTO_LINE_CONTEXT: usize = 3;
-
-    let scroll = line.saturating_sub(1 + GOTO_LINE_CONTEXT);
-    if scroll == 0 {
-        return;
-    }
-    let action = text_editor::Action::Scroll {
-        lines: scroll.min(i32::MAX as usize) as i32,
-    };
-    match &mut viewer.content {
-        crate::views::file_viewer::ViewerContent::Text { content } => content.perform(action),
-        crate::views::file_viewer::ViewerContent::Markdown { content, .. } => {
-            content.perform(action)
+        FileViewerMessage::ImageFitToWindow(viewer_id) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.fit_image_to_window();
+            }
+            Task::none()
+        }
+        FileViewerMessage::ImageZoomAt(viewer_id, zoom, offset) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.set_zoom(zoom);
+                viewer.set_image_scroll(offset);
+            }
+            iced::widget::operation::scroll_to(image_scroll_id(), offset)
+        }
+        FileViewerMessage::ImageScrolled(viewer_id, offset) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.set_image_scroll(offset);
+            }
+            Task::none()
+        }
+        FileViewerMessage::ImageRotateLeft(viewer_id) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.rotate_image(false);
+            }
+            Task::none()
+        }
+        FileViewerMessage::ImageRotateRight(viewer_id) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.rotate_image(true);
+            }
+            Task::none()
+        }
+        FileViewerMessage::ToggleHex(viewer_id) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id)
+                && viewer.can_toggle_hex()
+            {
+                viewer.hex_mode = !viewer.hex_mode;
+                viewer.content = crate::views::file_viewer::ViewerContent::Loading;
+                return file_viewer::reload_viewer(viewer);
+            }
+            Task::none()
+        }
+        FileViewerMessage::HexOffsetChange(viewer_id, offset) => {
+            if let Some(viewer) = app.file_viewers.get_mut(viewer_id) {
+                viewer.set_hex_offset(offset);
+            }
+            Task::none()
         }
-        _ => {}
     }
 }
 
@@ -229,6 +423,7 @@ async fn save_file_content(
     source: FileSource,
     text: String,
     sftp_session: Option<SharedSftpSession>,
+    sudo_password: Option<SecretString>,
 ) -> Result<(), String> {
     match source {
         FileSource::Local { path } => {
@@ -240,20 +435,60 @@ async fn save_file_content(
             remote_path,
             ..
         } => {
-            // Save to temp path first
-            write_text_file(&temp_path, text, "temp file").await?;
-
-            // Upload to remote via SFTP
-            let sftp = sftp_session.ok_or_else(|| "SFTP connection not available".to_string())?;
-            sftp.upload(&temp_path, &remote_path)
-                .await
-                .map_err(|e| format!("Failed to upload file: {}", e))?;
+            let sftp = sftp_session.ok_or_else(|| {
+                "The SFTP connection this file was opened from is closed".to_string()
+            })?;
+            match &sudo_password {
+                Some(password) => sftp
+                    .write_with_sudo(text.as_bytes(), &remote_path, password)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())?,
+                None => sftp
+                    .write_file_atomic(&remote_path, text.as_bytes())
+                    .await
+                    .map_err(|e| {
+                        remote_save_error(&remote_path.to_string_lossy(), &e.to_string())
+                    })?,
+            }
 
+            // Keep the downloaded copy in step so reloading shows the saved text
+            if let Err(error) = write_text_file(&temp_path, text, "temp file").await {
+                tracing::warn!("Failed to refresh {}: {}", temp_path.display(), error);
+            }
             Ok(())
         }
     }
 }
 
+/// Error for a failed remote save, naming the likely cause when the server
+/// gives one
+fn remote_save_error(path: &str, error: &str) -> String {
+    let lower = error.to_ascii_lowercase();
+    if sudo::is_permission_denied(error) {
+        format!(
+            "Permission denied: you cannot write {} or create files in its folder",
+            path
+        )
+    } else if lower.contains("in place") {
+        // Hard-linked files are rewritten in place, so the original is gone
+        format!(
+            "The write stopped part-way and {} may be incomplete; save again to retry",
+            path
+        )
+    } else if lower.contains("no space") || lower.contains("quota") || lower.contains("disk full") {
+        format!("The server is out of disk space, {} was not changed", path)
+    } else if lower.contains("failed to write") && lower.trim_end().ends_with("failure") {
+        // OpenSSH reports a full disk as a bare "Failure" status
+        format!(
+            "The server refused the write, its disk may be full; {} was not changed",
+            path
+        )
+    } else {
+        error.to_string()
+    }
+}
+
 async fn write_text_file(path: &Path, text: String, label: &str) -> Result<(), String> {
     let path = path.to_path_buf();
     let label = label.to_string();
@@ -265,7 +500,43 @@ async fn write_text_file(path: &Path, text: String, label: &str) -> Result<(), S
 
 #[cfg(test)]
 mod tests {
-    use super::write_text_file;
+    use super::{remote_save_error, write_text_file};
+
+    #[test]
+    fn remote_save_error_names_common_causes() {
+        assert_eq!(
+            remote_save_error(
+                "/etc/hosts",
+                "Transfer failed: Failed to create /etc/.hosts.portal-part-1 in the folder of /etc/hosts: Permission denied: Permission denied"
+            ),
+            "Permission denied: you cannot write /etc/hosts or create files in its folder"
+        );
+        assert!(
+            remote_save_error(
+                "/srv/a.txt",
+                "Transfer failed: Failed to write /srv/a.txt: Failure: Failure"
+            )
+            .contains("disk may be full")
+        );
+        assert!(
+            remote_save_error(
+                "/srv/a.txt",
+                "Failed to write /srv/a.txt: No space left on device"
+            )
+            .contains("out of disk space")
+        );
+        assert!(
+            remote_save_error(
+                "/srv/a.txt",
+                "Transfer failed: Failed to rewrite /srv/a.txt in place, it may be incomplete: No space left on device"
+            )
+            .contains("may be incomplete")
+        );
+        assert_eq!(
+            remote_save_error("/srv/a.txt", "Transfer failed: Timeout"),
+            "Transfer failed: Timeout"
+        );
+    }
 
     #[tokio::test]
     async fn write_text_file_updates_regular_file() {
@@ -3,7 +3,9 @@
 use iced::Task;
 
 use crate::app::Portal;
-use crate::message::{HistoryMessage, Message};
+use crate::config::{ExportFormat, HistoryEntry, SessionType, write_atomic};
+use crate::message::{HistoryMessage, Message, SessionId};
+use crate::views::toast::Toast;
 
 /// Handle history messages
 pub fn handle_history(portal: &mut Portal, msg: HistoryMessage) -> Task<Message> {
@@ -24,5 +26,100 @@ pub fn handle_history(portal: &mut Portal, msg: HistoryMessage) -> Task<Message>
             }
             Task::none()
         }
+        HistoryMessage::SearchChanged(query) => {
+            portal.ui.history_filter.query = query;
+            portal.ui.history_focus_index = None;
+            Task::none()
+        }
+        HistoryMessage::HostFilterChanged(host_id) => {
+            portal.ui.history_filter.host_id = host_id;
+            portal.ui.history_focus_index = None;
+            Task::none()
+        }
+        HistoryMessage::SessionTypeFilterChanged(session_type) => {
+            portal.ui.history_filter.session_type = session_type;
+            portal.ui.history_focus_index = None;
+            Task::none()
+        }
+        HistoryMessage::DateRangeChanged(date_range) => {
+            portal.ui.history_filter.date_range = date_range;
+            portal.ui.history_focus_index = None;
+            Task::none()
+        }
+        HistoryMessage::SortChanged(sort) => {
+            portal.ui.history_filter.sort = sort;
+            portal.ui.history_focus_index = None;
+            Task::none()
+        }
+        HistoryMessage::ClearFilters => {
+            // Keep the chosen order; only the narrowing is reset
+            portal.ui.history_filter = crate::config::HistoryFilter {
+                sort: portal.ui.history_filter.sort,
+                ..Default::default()
+            };
+            portal.ui.history_focus_index = None;
+            Task::none()
+        }
+        HistoryMessage::Export => {
+            let history = portal.config.history.clone();
+            Task::perform(
+                async move {
+                    let path = rfd::FileDialog::new()
+                        .set_title("Export connection history")
+                        .set_file_name("portal-history.csv")
+                        .add_filter("CSV", &["csv"])
+                        .add_filter("JSON", &["json"])
+                        .save_file()?;
+                    let content = history.export(ExportFormat::for_path(&path));
+                    Some(
+                        write_atomic(&path, &content)
+                            .map(|()| path)
+                            .map_err(|e| format!("Failed to export history: {}", e)),
+                    )
+                },
+                |result| match result {
+                    Some(result) => Message::History(HistoryMessage::Exported(result)),
+                    None => Message::Noop,
+                },
+            )
+        }
+        HistoryMessage::Exported(result) => {
+            match result {
+                Ok(path) => portal.toast_manager.push(Toast::success(format!(
+                    "Exported history to {}",
+                    path.display()
+                ))),
+                Err(e) => portal.toast_manager.push(Toast::error(e)),
+            }
+            Task::none()
+        }
+    }
+}
+
+/// Record a failed attempt for the saved host `session_id` was connecting
+/// to. Call before the pending connection is finished.
+pub(super) fn record_failed_connection(
+    portal: &mut Portal,
+    session_id: SessionId,
+    session_type: SessionType,
+    error: &str,
+) {
+    let Some(host) = portal
+        .pending_connect_host_for(session_id)
+        .and_then(|host_id| portal.config.hosts.find_host(host_id))
+    else {
+        return;
+    };
+    let entry = HistoryEntry::failed(
+        host.id,
+        host.name.clone(),
+        host.hostname.clone(),
+        host.effective_username(),
+        session_type,
+        error.to_string(),
+    );
+    portal.config.history.add_entry(entry);
+    if let Err(e) = portal.config.history.save() {
+        tracing::error!("Failed to save history: {}", e);
     }
 }
@@ -1,12 +1,16 @@
 //! Host management message handlers
 
+use std::time::Instant;
+
 use iced::Task;
 use uuid::Uuid;
 
 use crate::app::Portal;
 use crate::app::services::connection;
-use crate::config::{Host, Protocol};
-use crate::message::{HostMessage, Message};
+use crate::config::{Host, Protocol, paths, ssh_config, write_atomic};
+use crate::error::ConfigError;
+use crate::local::LocalSpawnSpec;
+use crate::message::{HostContextMenuAction, HostMessage, Message};
 use crate::proxy;
 use crate::proxy::ListedProxySession;
 use crate::views::dialogs::host_dialog::HostDialogState;
@@ -14,6 +18,7 @@ use crate::views::dialogs::session_choice_dialog::{
     DetachedProxySessionChoice, LocalSessionChoice, SessionChoiceDialogState, SessionThumbnail,
 };
 use crate::views::dialogs::vnc_cleartext_dialog::VncCleartextDialogState;
+use crate::views::host_grid::hidden_host_groups;
 use crate::views::toast::Toast;
 
 /// Handle host management messages
@@ -74,7 +79,7 @@ pub fn handle_host(portal: &mut Portal, msg: HostMessage) -> Task<Message> {
                 display_name.clone(),
                 terminal_size,
             );
-            portal.begin_connecting(display_name, "Portal Hub", session_id, task)
+            portal.begin_connecting(display_name, "Portal Hub", session_id, host_id, task)
         }
         HostMessage::DetachedProxySessionsLoaded { host_id, result } => {
             handle_detached_proxy_sessions_loaded(portal, host_id, result)
@@ -111,13 +116,73 @@ pub fn handle_host(portal: &mut Portal, msg: HostMessage) -> Task<Message> {
             portal.ui.host_details_sheet = None;
             Task::none()
         }
+        HostMessage::CopySshCommand(id) => {
+            copy_ssh_export(portal, id, "SSH command", ssh_config::host_to_ssh_command)
+        }
+        HostMessage::CopySshConfig(id) => copy_ssh_export(
+            portal,
+            id,
+            "SSH config block",
+            ssh_config::host_to_ssh_config_block,
+        ),
+        HostMessage::ExportSshConfig => {
+            let content = portal.config.hosts.export_ssh_config();
+            Task::perform(
+                async move {
+                    let mut dialog = rfd::FileDialog::new()
+                        .set_title("Export SSH config")
+                        .set_file_name("portal.conf");
+                    if let Some(dir) = paths::ssh_dir() {
+                        dialog = dialog.set_directory(dir);
+                    }
+                    let path = dialog.save_file()?;
+                    Some(
+                        write_atomic(&path, &content)
+                            .map(|()| path)
+                            .map_err(|e| format!("Failed to write SSH config: {}", e)),
+                    )
+                },
+                |result| match result {
+                    Some(result) => Message::Host(HostMessage::SshConfigExported(result)),
+                    None => Message::Noop,
+                },
+            )
+        }
+        HostMessage::SshConfigExported(result) => {
+            match result {
+                Ok(path) => portal.toast_manager.push(Toast::success(format!(
+                    "Exported SSH config to {}",
+                    path.display()
+                ))),
+                Err(e) => portal.toast_manager.push(Toast::error(e)),
+            }
+            Task::none()
+        }
         HostMessage::QuickConnect => {
             portal.dialogs.open_quick_connect();
             Task::none()
         }
         HostMessage::LocalTerminal => {
             tracing::info!("Spawning local terminal");
-            portal.spawn_local_terminal()
+            let (spec, title) = match portal.config.terminal_profiles.default_profile() {
+                Some(profile) => (LocalSpawnSpec::from_profile(profile), profile.name.clone()),
+                None => (LocalSpawnSpec::default(), "Local Terminal".to_string()),
+            };
+            portal.spawn_local_terminal(spec, title)
+        }
+        HostMessage::LocalTerminalProfile(id) => {
+            let Some(profile) = portal.config.terminal_profiles.find_profile(id).cloned() else {
+                portal
+                    .toast_manager
+                    .push(Toast::error("Terminal profile no longer exists"));
+                return Task::none();
+            };
+            tracing::info!("Spawning local terminal from profile");
+            portal.spawn_local_terminal(LocalSpawnSpec::from_profile(&profile), profile.name)
+        }
+        HostMessage::LocalTerminalTarget(target) => {
+            tracing::info!("Spawning local terminal for discovered target");
+            portal.spawn_local_terminal(target.spec, target.name)
         }
         HostMessage::VncCleartextCheckDone { host_id, warn } => {
             let Some(host) = portal.config.hosts.find_host(host_id).cloned() else {
@@ -136,7 +201,135 @@ pub fn handle_host(portal: &mut Portal, msg: HostMessage) -> Task<Message> {
             }
             portal.connect_vnc_host_unchecked(&host)
         }
+        HostMessage::ReachabilityTick => probe_visible_hosts(portal),
+        HostMessage::ReachabilityProbed { host_id, reachable } => {
+            portal
+                .host_reachability
+                .record(host_id, reachable, Instant::now());
+            Task::none()
+        }
+        HostMessage::ShowContextMenu(host_id, x, y) => {
+            if portal.config.hosts.find_host(host_id).is_some() {
+                portal.ui.host_context_menu.show(host_id, x, y);
+            }
+            Task::none()
+        }
+        HostMessage::HideContextMenu => {
+            portal.ui.host_context_menu.hide();
+            Task::none()
+        }
+        HostMessage::ContextMenuAction(host_id, action) => {
+            portal.ui.host_context_menu.hide();
+            let message = match action {
+                HostContextMenuAction::Connect => HostMessage::Connect(host_id),
+                HostContextMenuAction::Edit => HostMessage::Edit(host_id),
+                HostContextMenuAction::Duplicate => HostMessage::Duplicate(host_id),
+                HostContextMenuAction::Details => HostMessage::DetailsOpen(host_id),
+            };
+            handle_host(portal, message)
+        }
+        HostMessage::Duplicate(host_id) => {
+            match portal.config.hosts.duplicate_host(host_id) {
+                Ok(copy_id) => {
+                    if let Err(e) = portal.config.hosts.save() {
+                        tracing::error!("Failed to save config: {}", e);
+                        portal.toast_manager.push(Toast::error(
+                            "Duplicated host, but failed to save hosts config",
+                        ));
+                    } else if let Some(copy) = portal.config.hosts.find_host(copy_id) {
+                        portal
+                            .toast_manager
+                            .push(Toast::success(format!("Added {}", copy.name)));
+                    }
+                    super::ui::settings::queue_portal_hub_local_sync(portal);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to duplicate host: {}", e);
+                    portal
+                        .toast_manager
+                        .push(Toast::error("Host could not be duplicated"));
+                }
+            }
+            Task::none()
+        }
+        HostMessage::HostMoved {
+            host_id,
+            group_id,
+            index,
+        } => {
+            let result = portal.config.hosts.move_host(host_id, group_id, index);
+            save_host_arrangement(portal, result);
+            Task::none()
+        }
+        HostMessage::GroupReordered {
+            group_id,
+            parent_id,
+            index,
+        } => {
+            let result = portal.config.hosts.move_group(group_id, parent_id, index);
+            save_host_arrangement(portal, result);
+            Task::none()
+        }
+    }
+}
+
+/// Persist a host or group move, or say why it was refused
+fn save_host_arrangement(portal: &mut Portal, result: Result<(), ConfigError>) {
+    if let Err(e) = result.and_then(|()| portal.config.hosts.save()) {
+        tracing::error!("Failed to move host or group: {}", e);
+        portal.toast_manager.push(Toast::error(e.to_string()));
+    }
+}
+
+/// Start a background probe for each host card on screen whose reachability
+/// is unknown or stale. Hosts behind a jump host or routed through Portal Hub
+/// are left unknown, since a direct probe says nothing about them.
+fn probe_visible_hosts(portal: &mut Portal) -> Task<Message> {
+    if !portal.prefs.host_reachability {
+        return Task::none();
     }
+
+    let targets: Vec<(Uuid, String, u16)> = {
+        let mut cache = portal.ui.host_grid_cache.borrow_mut();
+        let cards = cache.cards(
+            &portal.ui.search_query,
+            &portal.ui.host_tag_filter,
+            &portal.config.hosts,
+            &portal.prefs.portal_hub,
+        );
+        let collapsed = hidden_host_groups(&cards.groups);
+        cards
+            .hosts
+            .iter()
+            .filter(|card| !card.via_hub)
+            .filter(|card| card.group_id.is_none_or(|id| !collapsed.contains(&id)))
+            .filter_map(|card| portal.config.hosts.find_host(card.id))
+            .filter(|host| host.jump_host_id.is_none() && !host.hostname.is_empty())
+            .map(|host| {
+                let port = match host.protocol {
+                    Protocol::Ssh => host.port,
+                    Protocol::Vnc => host.effective_vnc_port(),
+                };
+                (host.id, host.hostname.clone(), port)
+            })
+            .collect()
+    };
+
+    let due = portal
+        .host_reachability
+        .claim_due(targets.iter().map(|(id, _, _)| *id), Instant::now());
+    let probes = targets
+        .into_iter()
+        .filter(|(id, _, _)| due.contains(id))
+        .map(|(host_id, hostname, port)| {
+            Task::perform(
+                connection::probe_host_reachable(hostname, port),
+                move |reachable| {
+                    Message::Host(HostMessage::ReachabilityProbed { host_id, reachable })
+                },
+            )
+        });
+    Task::batch(probes)
 }
 
 fn choose_or_connect_ssh_host(portal: &mut Portal, host: &Host) -> Task<Message> {
@@ -241,6 +434,31 @@ fn proxy_session_matches_host(session: &ListedProxySession, host: &Host) -> bool
         && session.target_user == host.effective_username()
 }
 
+/// Render a host as plain OpenSSH text and put it on the clipboard.
+fn copy_ssh_export(
+    portal: &mut Portal,
+    host_id: Uuid,
+    label: &str,
+    render: fn(&Host, &[Host]) -> String,
+) -> Task<Message> {
+    let Some(host) = portal.config.hosts.find_host(host_id) else {
+        return Task::none();
+    };
+    let jump_chain = match crate::ssh::tunnel::resolve_jump_chain(&portal.config.hosts.hosts, host)
+    {
+        Ok(chain) => chain,
+        Err(error) => {
+            portal.toast_manager.push(Toast::error(error.to_string()));
+            return Task::none();
+        }
+    };
+    let text = render(host, &jump_chain);
+    portal
+        .toast_manager
+        .push(Toast::success(format!("{} copied to clipboard", label)));
+    iced::clipboard::write(text)
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::Utc;
@@ -264,11 +482,19 @@ mod tests {
             vnc_password_id: None,
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Hub,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at: now,
@@ -1,27 +1,35 @@
 //! Terminal session message handlers
 
+mod broadcast;
+mod macros;
+mod panes;
+mod port_forwards;
+mod zmodem;
+
 use chrono::{DateTime, Utc};
 use futures::stream;
 use iced::Task;
 use iced::clipboard;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use crate::app::managers::{ActiveSession, SessionBackend};
+use crate::app::managers::{ActiveSession, LastCommand, SessionBackend};
 use crate::app::services::{connection, file_viewer, history};
 use crate::app::{Portal, Tab, View};
 use crate::config::AuthMethod;
+use crate::config::settings::{TerminalBell, TerminalClipboardAccess};
 use crate::message::{Message, ResolvedLinkFile, SearchMessage, SessionId, SessionMessage};
 use crate::platform;
 use crate::sftp::session::SftpSession;
 use crate::ssh::reconnect::ReconnectPolicy;
-use crate::terminal::backend::{TerminalEvent, paste_bytes_for_mode};
+use crate::terminal::backend::{OSC52_MAX_BYTES, TerminalEvent, paste_bytes_for_mode};
 use crate::terminal::links::TerminalLink;
 use crate::terminal::logger::SessionLogger;
 use crate::terminal::search::{self as terminal_search, TerminalSearchState};
+use crate::terminal::zmodem as terminal_zmodem;
 use crate::terminal_paste::{self, TerminalPastePayload};
 use crate::views::file_viewer::FileType;
 use crate::views::tabs::{
@@ -52,6 +60,9 @@ const BACKLOG_AGE_WARNING: Duration = Duration::from_millis(500);
 const BACKLOG_WARNING_INTERVAL: Duration = Duration::from_secs(5);
 const TERMINAL_AGENT_TITLE_NOTIFICATION_THRESHOLD: Duration = Duration::from_secs(5);
 const TERMINAL_NOTIFICATION_COOLDOWN: Duration = Duration::from_secs(10);
+/// Bells closer together than this are dropped, so a flood of `BEL` bytes
+/// flashes or notifies only a couple of times a second.
+const TERMINAL_BELL_DEBOUNCE: Duration = Duration::from_millis(500);
 const MAX_PRE_SESSION_OUTPUT_BYTES: usize = 4 * 1024 * 1024;
 const PROXY_RESUME_SNAPSHOT_PROTECTION: Duration = Duration::from_millis(750);
 
@@ -72,6 +83,45 @@ fn should_defer_for_output_coalesce(session: &ActiveSession, now: Instant) -> bo
             .is_some_and(|t| now.duration_since(t) < OUTPUT_COALESCE_DELAY)
 }
 
+/// Send raw bytes to a terminal session's backend.
+fn write_to_backend(backend: &SessionBackend, bytes: Vec<u8>) -> Task<Message> {
+    match backend {
+        SessionBackend::Ssh(ssh_session) => {
+            let ssh_session = ssh_session.clone();
+            Task::perform(
+                async move {
+                    if let Err(e) = ssh_session.send(&bytes).await {
+                        tracing::error!("Failed to send to SSH: {}", e);
+                    }
+                },
+                |_| Message::Noop,
+            )
+        }
+        SessionBackend::Local(local_session) => {
+            let local_session = local_session.clone();
+            Task::perform(
+                async move {
+                    if let Err(e) = local_session.send(&bytes).await {
+                        tracing::error!("Failed to send to local PTY: {}", e);
+                    }
+                },
+                |_| Message::Noop,
+            )
+        }
+        SessionBackend::Proxy(proxy_session) => {
+            let proxy_session = proxy_session.clone();
+            Task::perform(
+                async move {
+                    if let Err(e) = proxy_session.send(&bytes).await {
+                        tracing::error!("Failed to send to Portal Hub: {}", e);
+                    }
+                },
+                |_| Message::Noop,
+            )
+        }
+    }
+}
+
 fn queue_terminal_output(session: &mut ActiveSession, data: Vec<u8>, now: Instant) {
     if session.pending_output_bytes == 0 {
         session.pending_output_started_at = Some(now);
@@ -297,14 +347,20 @@ fn process_terminal_output_tick(session: &mut ActiveSession, now: Instant) {
 }
 
 fn start_session_logger(portal: &mut Portal, session_id: SessionId) {
-    if !portal.prefs.session_logging_enabled {
-        return;
-    }
-
     let Some(session) = portal.sessions.get_mut(session_id) else {
         return;
     };
 
+    // A host's own setting wins over the global one
+    let enabled = session
+        .host_id
+        .and_then(|host_id| portal.config.hosts.find_host(host_id))
+        .and_then(|host| host.session_logging)
+        .unwrap_or(portal.prefs.session_logging_enabled);
+    if !enabled {
+        return;
+    }
+
     if let Some(logger) = session.logger.take() {
         tokio::spawn(async move {
             logger.shutdown().await;
@@ -315,7 +371,18 @@ fn start_session_logger(portal: &mut Portal, session_id: SessionId) {
         return;
     };
 
-    match SessionLogger::start(&session.host_name, log_dir, portal.prefs.session_log_format) {
+    let max_bytes = (portal.prefs.session_log_max_size_mb > 0).then(|| {
+        portal
+            .prefs
+            .session_log_max_size_mb
+            .saturating_mul(1024 * 1024)
+    });
+    match SessionLogger::start(
+        &session.host_name,
+        log_dir,
+        portal.prefs.session_log_format,
+        max_bytes,
+    ) {
         Ok(logger) => {
             session.logger = Some(logger);
         }
@@ -389,7 +456,8 @@ fn start_terminal_session(
 ) -> Task<Message> {
     // Create terminal session
     let (cols, rows) = portal.terminal_initial_size();
-    let (terminal, terminal_events) = TerminalSession::new_with_size(&host_name, cols, rows);
+    let (terminal, terminal_events) =
+        TerminalSession::new_with_size(&host_name, cols, rows, portal.prefs.scrollback_lines);
     let resume_snapshot_protected_until = if !start.resume_preview.is_empty() {
         Some(Instant::now() + PROXY_RESUME_SNAPSHOT_PROTECTION)
     } else {
@@ -424,24 +492,42 @@ fn start_terminal_session(
             last_backlog_warning_at: None,
             terminal_agent_turn_started_at: None,
             last_terminal_notification_at: None,
+            last_bell_at: None,
             resume_snapshot_protected_until,
             cwd: None,
             logger: None,
             search: TerminalSearchState::default(),
+            zmodem: None,
+            last_command: None,
+            macro_recording: None,
+            forward_status: HashMap::new(),
+            font_size: None,
         },
     );
 
-    // Create a new tab for this session
-    let session_number = next_terminal_session_number(&portal.tabs, &host_name);
-    let tab = Tab::new_terminal(session_id, host_name, host_id, session_number);
-    if !draft_tab_id.is_some_and(|draft_tab_id| {
-        promote_connection_tab(&mut portal.tabs, draft_tab_id, tab.clone())
-    }) {
-        portal.tabs.push(tab);
-    }
+    if let Some(split) = portal
+        .terminal_panes
+        .take_pending(host_id)
+        .filter(|split| portal.sessions.contains(split.target))
+    {
+        // Fill the requested split instead of opening a tab
+        portal
+            .terminal_panes
+            .attach(split, session_id, portal.ui.terminal_focus_token);
+        portal.enter_terminal_view(session_id, false);
+    } else {
+        // Create a new tab for this session
+        let session_number = next_terminal_session_number(&portal.tabs, &host_name);
+        let tab = Tab::new_terminal(session_id, host_name, host_id, session_number);
+        if !draft_tab_id.is_some_and(|draft_tab_id| {
+            promote_connection_tab(&mut portal.tabs, draft_tab_id, tab.clone())
+        }) {
+            portal.tabs.push(tab);
+        }
 
-    // Switch to terminal view and hide sidebar
-    portal.enter_terminal_view(session_id, true);
+        // Switch to terminal view and hide sidebar
+        portal.enter_terminal_view(session_id, true);
+    }
 
     start_session_logger(portal, session_id);
     flush_pre_session_terminal_output(portal, session_id);
@@ -461,7 +547,7 @@ fn finalize_disconnection(portal: &mut Portal, session_id: SessionId) {
     {
         tracing::error!("Failed to save history config: {}", e);
     }
-    portal.close_tab(session_id);
+    portal.close_terminal_pane(session_id);
 }
 
 fn terminal_notification_name(portal: &Portal, session_id: SessionId) -> String {
@@ -480,6 +566,27 @@ fn terminal_notification_name(portal: &Portal, session_id: SessionId) -> String
         .to_string()
 }
 
+/// Title a program set, cleaned up for the tab bar; `None` when it is blank
+fn program_tab_title(title: &str) -> Option<String> {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    let title = title.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Show the title a terminal program set (OSC 0/2) on its tab, unless the
+/// user named the tab. A blank title restores the host name.
+fn set_tab_program_title(portal: &mut Portal, session_id: SessionId, title: &str) {
+    let tab_id = portal
+        .terminal_panes
+        .tab_for_session(session_id)
+        .unwrap_or(session_id);
+    if let Some(tab) = portal.tabs.iter_mut().find(|tab| tab.id == tab_id)
+        && !tab.renamed
+    {
+        tab.program_title = program_tab_title(title);
+    }
+}
+
 fn next_terminal_session_number(tabs: &[Tab], title: &str) -> usize {
     let mut used: Vec<usize> = tabs
         .iter()
@@ -504,11 +611,30 @@ fn mark_terminal_attention(portal: &mut Portal, session_id: SessionId) {
         return;
     }
 
-    if let Some(tab) = portal.tabs.iter_mut().find(|tab| tab.id == session_id) {
+    let tab_id = portal
+        .terminal_panes
+        .tab_for_session(session_id)
+        .unwrap_or(session_id);
+    if let Some(tab) = portal.tabs.iter_mut().find(|tab| tab.id == tab_id) {
         tab.needs_attention = true;
     }
 }
 
+/// Flag output from terminals that are not on screen so the tab shows a dot.
+fn mark_terminal_unseen_output(portal: &mut Portal, session_id: SessionId) {
+    if matches!(portal.ui.active_view, View::Terminal(active_id) if active_id == session_id) {
+        return;
+    }
+
+    let tab_id = portal
+        .terminal_panes
+        .tab_for_session(session_id)
+        .unwrap_or(session_id);
+    if let Some(tab) = portal.tabs.iter_mut().find(|tab| tab.id == tab_id) {
+        tab.has_unseen_output = true;
+    }
+}
+
 fn terminal_is_visible_and_focused(portal: &Portal, session_id: SessionId) -> bool {
     portal.ui.window_focused
         && matches!(portal.ui.active_view, View::Terminal(active_id) if active_id == session_id)
@@ -540,6 +666,48 @@ fn send_terminal_desktop_notification(
     platform::send_desktop_notification(summary.into(), body.into());
 }
 
+/// Whether a bell at `now` should be acted on; records it if so.
+fn accept_terminal_bell(last_bell_at: &mut Option<Instant>, now: Instant) -> bool {
+    if last_bell_at.is_some_and(|last| now.duration_since(last) < TERMINAL_BELL_DEBOUNCE) {
+        return false;
+    }
+    *last_bell_at = Some(now);
+    true
+}
+
+fn handle_terminal_bell(portal: &mut Portal, session_id: SessionId) {
+    let bell = portal.prefs.terminal_bell;
+    if bell == TerminalBell::Off {
+        return;
+    }
+    let now = Instant::now();
+    let Some(session) = portal.sessions.get_mut(session_id) else {
+        return;
+    };
+    if !accept_terminal_bell(&mut session.last_bell_at, now) {
+        return;
+    }
+
+    mark_terminal_attention(portal, session_id);
+    match bell {
+        TerminalBell::Visual => {
+            if let Some(session) = portal.sessions.get_mut(session_id) {
+                session.terminal.flash_bell(now);
+            }
+        }
+        TerminalBell::Notification => {
+            if terminal_is_visible_and_focused(portal, session_id) {
+                portal
+                    .toast_manager
+                    .push_or_refresh(Toast::warning("Terminal bell"));
+            } else {
+                notify_terminal_bell(portal, session_id);
+            }
+        }
+        TerminalBell::Off => {}
+    }
+}
+
 fn notify_terminal_bell(portal: &mut Portal, session_id: SessionId) {
     let terminal_name = terminal_notification_name(portal, session_id);
     send_terminal_desktop_notification(
@@ -847,6 +1015,24 @@ fn schedule_reconnect(portal: &mut Portal, session_id: SessionId) -> Task<Messag
     )
 }
 
+/// Send input to the session backend.
+fn write_input(portal: &mut Portal, session_id: SessionId, bytes: Vec<u8>) -> Task<Message> {
+    let Some(session) = portal.sessions.get_mut(session_id) else {
+        return Task::none();
+    };
+    session.resume_snapshot_protected_until = None;
+    if session.zmodem.is_some() {
+        // Typing during a transfer cancels it, like Ctrl+C in rz.
+        return zmodem::cancel(portal, session_id, "ZMODEM transfer cancelled");
+    }
+    if portal.prefs.session_log_input
+        && let Some(logger) = session.logger.as_ref()
+    {
+        logger.write_input(&bytes);
+    }
+    write_to_backend(&session.backend, bytes)
+}
+
 fn paste_text_into_session(
     portal: &mut Portal,
     session_id: SessionId,
@@ -936,6 +1122,7 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
             ssh_session,
             host_id,
             detected_os,
+            forward_results,
         } => {
             tracing::info!("SSH connected");
             let existing_session = portal.sessions.contains(session_id);
@@ -970,6 +1157,7 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 session.reconnect_attempts = 0;
                 session.reconnect_next_attempt = None;
                 session.status_message = Some(("Reconnected".to_string(), Instant::now()));
+                port_forwards::apply_connect_results(portal, session_id, host_id, forward_results);
                 start_session_logger(portal, session_id);
                 return Task::none();
             }
@@ -1014,7 +1202,7 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 Uuid::new_v4()
             };
 
-            start_terminal_session(
+            let task = start_terminal_session(
                 portal,
                 session_id,
                 draft_tab_id,
@@ -1022,11 +1210,14 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 host_name,
                 Some(host_id),
                 TerminalSessionStart::new(history_entry_id, Instant::now()),
-            )
+            );
+            port_forwards::apply_connect_results(portal, session_id, host_id, forward_results);
+            task
         }
         SessionMessage::LocalConnected {
             session_id,
             local_session,
+            title,
         } => {
             tracing::info!("Local terminal session started");
             let draft_tab_id = portal.active_new_connection_tab_id();
@@ -1044,7 +1235,7 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 session_id,
                 draft_tab_id,
                 SessionBackend::Local(local_session),
-                "Local Terminal".to_string(),
+                title,
                 None,
                 TerminalSessionStart::new(history_entry_id, Instant::now()),
             )
@@ -1166,16 +1357,31 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
             };
 
             if !data.is_empty() {
+                if session.zmodem.is_some() {
+                    return zmodem::receive(portal, session_id, data);
+                }
                 if should_drop_resume_attach_redraw(session, &data, now) {
                     return Task::none();
                 }
-                if let Some(logger) = session.logger.as_ref() {
-                    logger.write(&data);
+                let mut data = data;
+                let transfer =
+                    terminal_zmodem::find_start(&data).map(|start| data.split_off(start));
+                if !data.is_empty() {
+                    if let Some(logger) = session.logger.as_ref() {
+                        logger.write(&data);
+                    }
+                    queue_terminal_output(session, data, now);
+                    mark_terminal_unseen_output(portal, session_id);
+                }
+                if let Some(transfer) = transfer {
+                    return zmodem::start(portal, session_id, transfer);
                 }
-                queue_terminal_output(session, data, now);
             }
             Task::none()
         }
+        SessionMessage::ZmodemSaveDirChosen(session_id, dir) => {
+            zmodem::save_dir_chosen(portal, session_id, dir)
+        }
         SessionMessage::ProcessOutputTick => {
             let now = Instant::now();
             for session in portal.sessions.values_mut() {
@@ -1187,6 +1393,21 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
             Task::none()
         }
         SessionMessage::Search(msg) => handle_search(portal, msg),
+        SessionMessage::OpenPortForwards(session_id) => {
+            port_forwards::open_panel(portal, session_id)
+        }
+        SessionMessage::StartPortForward(session_id, forward_id) => {
+            port_forwards::start(portal, session_id, forward_id)
+        }
+        SessionMessage::StopPortForward(session_id, forward_id) => {
+            port_forwards::stop(portal, session_id, forward_id)
+        }
+        SessionMessage::PortForwardStarted(session_id, forward_id, result) => {
+            port_forwards::started(portal, session_id, forward_id, result)
+        }
+        SessionMessage::PortForwardStopped(session_id, forward_id, result) => {
+            port_forwards::stopped(portal, session_id, forward_id, result)
+        }
         SessionMessage::OpenLink(session_id, link) => handle_open_link(portal, session_id, link),
         SessionMessage::LinkFileResolved {
             session_id,
@@ -1330,12 +1551,14 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 terminal_size,
                 should_detect_os,
                 portal.prefs.allow_agent_forwarding,
+                portal.prefs.keepalive(),
                 jump_chain,
             )
         }
         SessionMessage::Error(error) => {
             tracing::error!("Session error: {}", error);
             portal.finish_pending_connect();
+            portal.terminal_panes.cancel_pending();
             portal.toast_manager.push(Toast::error(error));
             Task::none()
         }
@@ -1344,6 +1567,12 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
             if portal.sessions.contains(session_id) {
                 return schedule_reconnect(portal, session_id);
             }
+            super::history::record_failed_connection(
+                portal,
+                session_id,
+                crate::config::SessionType::Ssh,
+                &error,
+            );
             if !portal.finish_pending_connect_for(session_id) {
                 tracing::warn!(
                     "Ignoring stale connection failure for session {}: {}",
@@ -1353,6 +1582,7 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 return Task::none();
             }
             portal.pre_session_terminal_output.remove(&session_id);
+            portal.terminal_panes.cancel_pending();
             portal.toast_manager.push(Toast::error(error));
             Task::none()
         }
@@ -1362,31 +1592,33 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                     return Task::none();
                 }
                 handle_terminal_agent_title_state(portal, session_id, &title);
+                set_tab_program_title(portal, session_id, &title);
                 Task::none()
             }
             TerminalEvent::Bell => {
-                if !portal.sessions.contains(session_id) {
-                    return Task::none();
-                }
-                mark_terminal_attention(portal, session_id);
-                notify_terminal_bell(portal, session_id);
-                portal
-                    .toast_manager
-                    .push_or_refresh(Toast::warning("Terminal bell"));
+                handle_terminal_bell(portal, session_id);
                 Task::none()
             }
             TerminalEvent::ClipboardStore(contents) => {
-                if !portal.sessions.contains(session_id) {
+                if !portal.sessions.contains(session_id)
+                    || portal.prefs.terminal_clipboard == TerminalClipboardAccess::Off
+                {
                     return Task::none();
                 }
                 clipboard::write::<Message>(contents)
             }
-            TerminalEvent::ClipboardLoad => {
-                if !portal.sessions.contains(session_id) {
+            TerminalEvent::ClipboardLoad(responder) => {
+                if !portal.sessions.contains(session_id)
+                    || portal.prefs.terminal_clipboard != TerminalClipboardAccess::CopyPaste
+                {
                     return Task::none();
                 }
                 clipboard::read().map(move |contents| {
-                    Message::Session(SessionMessage::ClipboardLoaded(session_id, contents))
+                    Message::Session(SessionMessage::ClipboardLoaded(
+                        session_id,
+                        responder.clone(),
+                        contents,
+                    ))
                 })
             }
             TerminalEvent::Notification { title, body } => {
@@ -1397,6 +1629,18 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 notify_terminal_osc(portal, session_id, title, body);
                 Task::none()
             }
+            TerminalEvent::CommandStatus {
+                exit_status,
+                duration,
+            } => {
+                if let Some(session) = portal.sessions.get_mut(session_id) {
+                    session.last_command = Some(LastCommand {
+                        exit_status,
+                        duration,
+                    });
+                }
+                Task::none()
+            }
             TerminalEvent::CommandFinished {
                 exit_status,
                 duration,
@@ -1414,9 +1658,9 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
                 }
                 Task::none()
             }
-            TerminalEvent::PtyWrite(bytes) => {
-                handle_session(portal, SessionMessage::Input(session_id, bytes))
-            }
+            // Terminal replies (cursor reports etc.) are not user input, so
+            // they bypass macro recording.
+            TerminalEvent::PtyWrite(bytes) => write_input(portal, session_id, bytes),
             TerminalEvent::Exit => handle_session(
                 portal,
                 SessionMessage::Disconnected {
@@ -1426,11 +1670,22 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
             ),
             TerminalEvent::Wakeup => Task::none(),
         },
-        SessionMessage::ClipboardLoaded(session_id, contents) => {
-            if let Some(text) = contents {
-                return paste_text_into_session(portal, session_id, text);
-            }
-            Task::none()
+        SessionMessage::ClipboardLoaded(session_id, responder, contents) => {
+            // Answer with an empty clipboard rather than leave the program
+            // waiting when there is no text or too much of it.
+            let text = contents
+                .filter(|text| text.len() <= OSC52_MAX_BYTES)
+                .unwrap_or_default();
+            write_input(portal, session_id, responder.reply(&text))
+        }
+        SessionMessage::InsertText(session_id, text) => {
+            // A trailing newline would run the command instead of leaving it
+            // at the prompt.
+            let text = text.trim_end_matches(['\r', '\n']).to_string();
+            // Hand keyboard focus back from the palette so the command can be
+            // edited before running it.
+            portal.ui.terminal_focus_token = portal.ui.terminal_focus_token.wrapping_add(1);
+            paste_text_into_session(portal, session_id, text)
         }
         SessionMessage::Paste(session_id) => {
             if !portal.sessions.contains(session_id) {
@@ -1552,45 +1807,66 @@ pub fn handle_session(portal: &mut Portal, msg: SessionMessage) -> Task<Message>
         },
         SessionMessage::Input(session_id, bytes) => {
             tracing::debug!("Terminal input ({} bytes)", bytes.len());
-            let Some(session) = portal.sessions.get_mut(session_id) else {
-                return Task::none();
-            };
-            session.resume_snapshot_protected_until = None;
-            match &session.backend {
-                SessionBackend::Ssh(ssh_session) => {
-                    let ssh_session = ssh_session.clone();
-                    Task::perform(
-                        async move {
-                            if let Err(e) = ssh_session.send(&bytes).await {
-                                tracing::error!("Failed to send to SSH: {}", e);
-                            }
-                        },
-                        |_| Message::Noop,
-                    )
-                }
-                SessionBackend::Local(local_session) => {
-                    let local_session = local_session.clone();
-                    Task::perform(
-                        async move {
-                            if let Err(e) = local_session.send(&bytes).await {
-                                tracing::error!("Failed to send to local PTY: {}", e);
-                            }
-                        },
-                        |_| Message::Noop,
-                    )
-                }
-                SessionBackend::Proxy(proxy_session) => {
-                    let proxy_session = proxy_session.clone();
-                    Task::perform(
-                        async move {
-                            if let Err(e) = proxy_session.send(&bytes).await {
-                                tracing::error!("Failed to send to Portal Hub: {}", e);
-                            }
-                        },
-                        |_| Message::Noop,
-                    )
-                }
+            // Keep the cursor shown while typing.
+            portal.ui.cursor_blink_visible = true;
+            if let Some(session) = portal.sessions.get_mut(session_id) {
+                macros::record(session, &bytes);
+            }
+            let targets = broadcast::fan_out_targets(&portal.broadcast_group, session_id);
+            if targets.is_empty() {
+                return write_input(portal, session_id, bytes);
+            }
+            let mut tasks: Vec<Task<Message>> = targets
+                .into_iter()
+                .map(|target| write_input(portal, target, bytes.clone()))
+                .collect();
+            tasks.push(write_input(portal, session_id, bytes));
+            Task::batch(tasks)
+        }
+        SessionMessage::ClearScrollback(session_id) => {
+            if let Some(session) = portal.sessions.get_mut(session_id) {
+                session.terminal.clear_scrollback();
+                refresh_search_if_stale(session);
+                session.status_message = Some(("Scrollback cleared".to_string(), Instant::now()));
             }
+            Task::none()
+        }
+        SessionMessage::ResetTerminal(session_id) => {
+            if let Some(session) = portal.sessions.get_mut(session_id) {
+                session.terminal.reset();
+                refresh_search_if_stale(session);
+                session.status_message = Some(("Terminal reset".to_string(), Instant::now()));
+            }
+            Task::none()
+        }
+        SessionMessage::SendReset(session_id) => {
+            // `reset` also restores the remote tty settings (stty sane) that a
+            // local re-init cannot reach.
+            let reset = handle_session(portal, SessionMessage::ResetTerminal(session_id));
+            Task::batch([
+                reset,
+                handle_session(
+                    portal,
+                    SessionMessage::Input(session_id, b"reset\r".to_vec()),
+                ),
+            ])
+        }
+        SessionMessage::ToggleBroadcast(session_id) => broadcast::toggle(portal, session_id),
+        SessionMessage::SplitPane(session_id, axis) => panes::split(portal, session_id, axis),
+        SessionMessage::FocusPane(session_id) => panes::focus(portal, session_id),
+        SessionMessage::FocusNextPane(session_id) => panes::focus_next(portal, session_id),
+        SessionMessage::PaneResized(session_id, event) => panes::resize(portal, session_id, event),
+        SessionMessage::PaneFontSize(session_id, size) => {
+            panes::set_font_size(portal, session_id, size)
+        }
+        SessionMessage::PaneColors(session_id, colors) => {
+            panes::set_colors(portal, session_id, colors)
+        }
+        SessionMessage::MacroRecordToggle(session_id) => {
+            macros::toggle_recording(portal, session_id)
+        }
+        SessionMessage::MacroPlay(session_id, macro_id) => {
+            macros::play(portal, session_id, macro_id)
         }
         SessionMessage::Resize(session_id, cols, rows) => {
             tracing::debug!("Terminal resize: {}x{}", cols, rows);
@@ -1712,12 +1988,16 @@ fn recompute_search(session: &mut ActiveSession, selection: SearchSelection, scr
     let previous_match = search.current_match().cloned();
     let previous_index = search.current;
 
+    search.invalid_pattern = search.regex
+        && !search.query.is_empty()
+        && !terminal_search::is_valid_pattern(&search.query, search.case_sensitive, true);
     search.matches = if search.query.is_empty() {
         Vec::new()
     } else {
         backend.search_matches(
             &search.query,
             search.case_sensitive,
+            search.regex,
             terminal_search::MAX_SEARCH_MATCHES,
         )
     };
@@ -1826,6 +2106,13 @@ fn handle_search(portal: &mut Portal, msg: SearchMessage) -> Task<Message> {
             }
             Task::none()
         }
+        SearchMessage::RegexToggled(session_id) => {
+            if let Some(session) = portal.sessions.get_mut(session_id) {
+                session.search.regex = !session.search.regex;
+                recompute_search(session, SearchSelection::Reset, true);
+            }
+            Task::none()
+        }
     }
 }
 
@@ -1838,7 +2125,7 @@ fn handle_open_link(
 ) -> Task<Message> {
     match link {
         TerminalLink::Url(url) => {
-            open_terminal_url(portal, &url);
+            open_external_url(portal, &url);
             Task::none()
         }
         TerminalLink::FilePath { path, line } => {
@@ -1847,7 +2134,8 @@ fn handle_open_link(
     }
 }
 
-fn open_terminal_url(portal: &mut Portal, url: &str) {
+/// Open an http(s) or mailto link in the system browser.
+pub(super) fn open_external_url(portal: &mut Portal, url: &str) {
     const ALLOWED_SCHEMES: [&str; 3] = ["http://", "https://", "mailto:"];
 
     let lower = url.to_ascii_lowercase();
@@ -1879,14 +2167,6 @@ fn open_terminal_file_link(
         return Task::none();
     };
 
-    if !FileType::from_path(Path::new(&path)).is_viewable() {
-        portal.toast_manager.push(Toast::warning(format!(
-            "{} cannot be opened in the file viewer",
-            truncate_for_toast(&path)
-        )));
-        return Task::none();
-    }
-
     let cwd = session.cwd.clone();
     match &session.backend {
         SessionBackend::Ssh(ssh_session) => {
@@ -2083,10 +2363,16 @@ mod tests {
             last_backlog_warning_at: None,
             terminal_agent_turn_started_at: None,
             last_terminal_notification_at: None,
+            last_bell_at: None,
             resume_snapshot_protected_until: None,
             cwd: None,
             logger: None,
             search: TerminalSearchState::default(),
+            zmodem: None,
+            last_command: None,
+            macro_recording: None,
+            forward_status: HashMap::new(),
+            font_size: None,
         }
     }
 
@@ -2219,6 +2505,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn program_tab_titles_are_trimmed_and_blank_ones_dropped() {
+        assert_eq!(
+            program_tab_title("  vim notes.md\n").as_deref(),
+            Some("vim notes.md")
+        );
+        assert_eq!(program_tab_title("top\u{7}").as_deref(), Some("top"));
+        assert_eq!(program_tab_title(" \t "), None);
+        assert_eq!(program_tab_title(""), None);
+    }
+
+    #[test]
+    fn terminal_bell_floods_are_debounced() {
+        let start = Instant::now();
+        let mut last_bell_at = None;
+
+        assert!(accept_terminal_bell(&mut last_bell_at, start));
+        assert!(!accept_terminal_bell(
+            &mut last_bell_at,
+            start + Duration::from_millis(100)
+        ));
+        assert!(!accept_terminal_bell(
+            &mut last_bell_at,
+            start + TERMINAL_BELL_DEBOUNCE - Duration::from_millis(1)
+        ));
+        assert!(accept_terminal_bell(
+            &mut last_bell_at,
+            start + TERMINAL_BELL_DEBOUNCE
+        ));
+    }
+
     #[test]
     fn output_budget_scales_with_backlog() {
         assert_eq!(
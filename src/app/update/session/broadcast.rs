@@ -0,0 +1,73 @@
+//! Broadcast input: typing in one session of the group is sent to all of them
+
+use std::collections::HashSet;
+
+use iced::Task;
+
+use crate::app::Portal;
+use crate::message::{Message, SessionId};
+use crate::views::toast::Toast;
+
+/// Add the session to the broadcast group, or remove it if already a member.
+pub(super) fn toggle(portal: &mut Portal, session_id: SessionId) -> Task<Message> {
+    if !portal.sessions.contains(session_id) {
+        return Task::none();
+    }
+
+    if portal.broadcast_group.remove(&session_id) {
+        let message = if portal.broadcast_group.is_empty() {
+            "Broadcast input off".to_string()
+        } else {
+            format!(
+                "Removed from broadcast ({} remaining)",
+                portal.broadcast_group.len()
+            )
+        };
+        portal.toast_manager.push(Toast::success(message));
+    } else {
+        portal.broadcast_group.insert(session_id);
+        portal.toast_manager.push(Toast::warning(format!(
+            "Broadcasting input to {} session{}",
+            portal.broadcast_group.len(),
+            if portal.broadcast_group.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        )));
+    }
+    Task::none()
+}
+
+/// Other sessions that should receive input typed into `source`. Empty when
+/// `source` is not part of the broadcast group.
+pub(super) fn fan_out_targets(group: &HashSet<SessionId>, source: SessionId) -> Vec<SessionId> {
+    if !group.contains(&source) {
+        return Vec::new();
+    }
+    group.iter().copied().filter(|id| *id != source).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn fan_out_targets_only_applies_to_members() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+        let outsider = Uuid::new_v4();
+        let group: HashSet<SessionId> = [a, b, c].into_iter().collect();
+
+        let mut targets = fan_out_targets(&group, a);
+        targets.sort();
+        let mut expected = vec![b, c];
+        expected.sort();
+        assert_eq!(targets, expected);
+
+        assert!(fan_out_targets(&group, outsider).is_empty());
+        assert!(fan_out_targets(&[a].into_iter().collect(), a).is_empty());
+    }
+}
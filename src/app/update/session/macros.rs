@@ -0,0 +1,108 @@
+//! Recording and replaying terminal input macros
+
+use std::time::{Duration, Instant};
+
+use iced::Task;
+use uuid::Uuid;
+
+use super::handle_session;
+use crate::app::Portal;
+use crate::app::managers::ActiveSession;
+use crate::config::TerminalMacro;
+use crate::message::{Message, SessionId, SessionMessage};
+use crate::views::toast::Toast;
+
+/// Recording stops accepting input past this many writes, so a forgotten
+/// recording cannot grow without bound.
+const MAX_RECORDED_STEPS: usize = 10_000;
+
+/// Capture user input while the session is recording.
+pub(super) fn record(session: &mut ActiveSession, bytes: &[u8]) {
+    if let Some(steps) = session.macro_recording.as_mut()
+        && steps.len() < MAX_RECORDED_STEPS
+    {
+        steps.push(bytes.to_vec());
+    }
+}
+
+/// Start recording, or stop and save what was recorded as a new macro.
+pub(super) fn toggle_recording(portal: &mut Portal, session_id: SessionId) -> Task<Message> {
+    let Some(session) = portal.sessions.get_mut(session_id) else {
+        return Task::none();
+    };
+
+    let Some(steps) = session.macro_recording.take() else {
+        session.macro_recording = Some(Vec::new());
+        session.status_message = Some(("Recording macro".to_string(), Instant::now()));
+        return Task::none();
+    };
+
+    if steps.is_empty() {
+        portal.toast_manager.push(Toast::warning(
+            "Macro recording stopped - nothing was typed",
+        ));
+        return Task::none();
+    }
+
+    let name = portal.config.macros.next_default_name();
+    let step_count = steps.len();
+    portal
+        .config
+        .macros
+        .add_macro(TerminalMacro::new(name.clone(), steps));
+    if let Err(e) = portal.config.macros.save() {
+        tracing::error!("Failed to save macros config: {}", e);
+        portal
+            .toast_manager
+            .push(Toast::error("Failed to save macro"));
+        return Task::none();
+    }
+    portal.toast_manager.push(Toast::success(format!(
+        "Saved {} ({} inputs) - rename it or bind a key in macros.toml",
+        name, step_count
+    )));
+    Task::none()
+}
+
+/// Replay a macro through the regular input path.
+pub(super) fn play(portal: &mut Portal, session_id: SessionId, macro_id: Uuid) -> Task<Message> {
+    let Some(session) = portal.sessions.get(session_id) else {
+        return Task::none();
+    };
+    if session.macro_recording.is_some() {
+        portal
+            .toast_manager
+            .push(Toast::warning("Stop recording before playing a macro"));
+        return Task::none();
+    }
+    let Some(terminal_macro) = portal.config.macros.find_macro(macro_id) else {
+        portal.toast_manager.push(Toast::warning("Macro not found"));
+        return Task::none();
+    };
+
+    let mut chunks = terminal_macro.replay_chunks();
+    let delay = Duration::from_millis(terminal_macro.delay_ms);
+    tracing::debug!(
+        "Playing macro with {} chunks, {:?} apart",
+        chunks.len(),
+        delay
+    );
+    if delay.is_zero() || chunks.len() <= 1 {
+        return match chunks.pop() {
+            Some(bytes) => handle_session(portal, SessionMessage::Input(session_id, bytes)),
+            None => Task::none(),
+        };
+    }
+
+    Task::run(
+        async_stream::stream! {
+            for (index, bytes) in chunks.into_iter().enumerate() {
+                if index > 0 {
+                    tokio::time::sleep(delay).await;
+                }
+                yield Message::Session(SessionMessage::Input(session_id, bytes));
+            }
+        },
+        |message| message,
+    )
+}
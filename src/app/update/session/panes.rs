@@ -0,0 +1,128 @@
+//! Split panes: several sessions side by side in one terminal tab
+
+use std::time::Instant;
+
+use iced::Task;
+use iced::widget::pane_grid;
+
+use crate::app::managers::{PendingSplit, SessionBackend};
+use crate::app::{Portal, View};
+use crate::config::settings::{TERMINAL_FONT_SIZE_MAX, TERMINAL_FONT_SIZE_MIN};
+use crate::message::{HostMessage, Message, SessionId};
+use crate::theme::TerminalColors;
+use crate::views::toast::Toast;
+
+/// Split the session's pane and open another session for the same host in
+/// the new half. The pane is added once that session has connected.
+pub(super) fn split(
+    portal: &mut Portal,
+    session_id: SessionId,
+    axis: pane_grid::Axis,
+) -> Task<Message> {
+    let Some(session) = portal.sessions.get(session_id) else {
+        return Task::none();
+    };
+    let tab_id = portal
+        .terminal_panes
+        .tab_for_session(session_id)
+        .unwrap_or(session_id);
+    let is_local = matches!(session.backend, SessionBackend::Local(_));
+    let host = session
+        .host_id
+        .and_then(|host_id| portal.config.hosts.find_host(host_id))
+        .cloned();
+
+    let host_id = match (&host, is_local) {
+        (_, true) => None,
+        (Some(host), false) => Some(host.id),
+        (None, false) => {
+            portal.toast_manager.push(Toast::warning(
+                "Only saved hosts and local shells can be split",
+            ));
+            return Task::none();
+        }
+    };
+    portal.terminal_panes.request_split(PendingSplit {
+        tab_id,
+        target: session_id,
+        axis,
+        host_id,
+    });
+
+    match host {
+        Some(host) if !is_local => portal.connect_to_host_new_session(&host),
+        _ => portal.update(Message::Host(HostMessage::LocalTerminal)),
+    }
+}
+
+/// Move keyboard focus to a pane, e.g. after it was clicked.
+pub(super) fn focus(portal: &mut Portal, session_id: SessionId) -> Task<Message> {
+    if matches!(portal.ui.active_view, View::Terminal(active_id) if active_id == session_id) {
+        return Task::none();
+    }
+    if let Some(tab_id) = portal
+        .terminal_panes
+        .focus(session_id, portal.ui.terminal_focus_token)
+    {
+        portal.enter_terminal_view(tab_id, false);
+    }
+    Task::none()
+}
+
+/// Cycle focus to the next pane of the session's tab.
+pub(super) fn focus_next(portal: &mut Portal, session_id: SessionId) -> Task<Message> {
+    let next = portal
+        .terminal_panes
+        .tab_for_session(session_id)
+        .and_then(|tab_id| portal.terminal_panes.next_session(tab_id));
+    match next {
+        Some(next) => focus(portal, next),
+        None => Task::none(),
+    }
+}
+
+pub(super) fn resize(
+    portal: &mut Portal,
+    session_id: SessionId,
+    event: pane_grid::ResizeEvent,
+) -> Task<Message> {
+    if let Some(tab_id) = portal.terminal_panes.tab_for_session(session_id) {
+        portal
+            .terminal_panes
+            .resize(tab_id, event.split, event.ratio);
+    }
+    Task::none()
+}
+
+/// Give one pane its own font size. The pane's widget re-measures its grid
+/// and reports the new size itself.
+pub(super) fn set_font_size(
+    portal: &mut Portal,
+    session_id: SessionId,
+    size: Option<f32>,
+) -> Task<Message> {
+    let Some(prefs) = portal.terminal_panes.view_prefs_mut(session_id) else {
+        return Task::none();
+    };
+    prefs.font_size = size.map(|size| size.clamp(TERMINAL_FONT_SIZE_MIN, TERMINAL_FONT_SIZE_MAX));
+    if let Some(session) = portal.sessions.get_mut(session_id) {
+        let shown = prefs
+            .font_size
+            .or(session.font_size)
+            .unwrap_or(portal.prefs.terminal_font_size);
+        session.status_message = Some((format!("Font size {}px", shown), Instant::now()));
+    }
+    Task::none()
+}
+
+/// Give one pane its own terminal colours
+pub(super) fn set_colors(
+    portal: &mut Portal,
+    session_id: SessionId,
+    colors: Option<TerminalColors>,
+) -> Task<Message> {
+    if let Some(prefs) = portal.terminal_panes.view_prefs_mut(session_id) {
+        prefs.colors = colors;
+    }
+    Task::none()
+}
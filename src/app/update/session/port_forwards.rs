@@ -0,0 +1,230 @@
+//! Port forward status and runtime start/stop for SSH sessions
+
+use std::collections::HashMap;
+
+use iced::Task;
+use uuid::Uuid;
+
+use crate::app::Portal;
+use crate::app::managers::{ForwardStatus, SessionBackend};
+use crate::app::services::connection;
+use crate::config::PortForward;
+use crate::message::{Message, SessionId, SessionMessage};
+use crate::views::dialogs::port_forwards_dialog::PortForwardsDialogState;
+use crate::views::toast::Toast;
+
+/// Record the outcome of the forwards started right after connecting, and
+/// surface any that failed to bind.
+pub(super) fn apply_connect_results(
+    portal: &mut Portal,
+    session_id: SessionId,
+    host_id: Uuid,
+    results: Vec<(Uuid, Result<u16, String>)>,
+) {
+    let forwards = host_forwards(portal, host_id);
+    let status = initial_status(&forwards, results);
+
+    for forward in &forwards {
+        if let Some(ForwardStatus::Failed(error)) = status.get(&forward.id) {
+            portal
+                .toast_manager
+                .push(Toast::error(failure_message(forward, error)));
+        }
+    }
+
+    if let Some(session) = portal.sessions.get_mut(session_id) {
+        session.forward_status = status;
+    }
+}
+
+pub(super) fn open_panel(portal: &mut Portal, session_id: SessionId) -> Task<Message> {
+    let Some(session) = portal.sessions.get(session_id) else {
+        return Task::none();
+    };
+    if !matches!(session.backend, SessionBackend::Ssh(_)) || session.host_id.is_none() {
+        portal.toast_manager.push(Toast::error(
+            "Port forwarding is only available for SSH sessions",
+        ));
+        return Task::none();
+    }
+
+    portal
+        .dialogs
+        .open_port_forwards(PortForwardsDialogState { session_id });
+    Task::none()
+}
+
+pub(super) fn start(portal: &mut Portal, session_id: SessionId, forward_id: Uuid) -> Task<Message> {
+    let Some(session) = portal.sessions.get(session_id) else {
+        return Task::none();
+    };
+    let SessionBackend::Ssh(ssh_session) = &session.backend else {
+        return Task::none();
+    };
+    let Some(forward) = session.host_id.and_then(|host_id| {
+        host_forwards(portal, host_id)
+            .into_iter()
+            .find(|f| f.id == forward_id)
+    }) else {
+        return Task::none();
+    };
+
+    let ssh_session = ssh_session.clone();
+    Task::perform(
+        async move { connection::start_port_forward(&ssh_session, forward).await },
+        move |result| {
+            Message::Session(SessionMessage::PortForwardStarted(
+                session_id, forward_id, result,
+            ))
+        },
+    )
+}
+
+pub(super) fn stop(portal: &mut Portal, session_id: SessionId, forward_id: Uuid) -> Task<Message> {
+    let Some(session) = portal.sessions.get(session_id) else {
+        return Task::none();
+    };
+    let SessionBackend::Ssh(ssh_session) = &session.backend else {
+        return Task::none();
+    };
+
+    let ssh_session = ssh_session.clone();
+    Task::perform(
+        async move {
+            ssh_session
+                .stop_forward(forward_id)
+                .await
+                .map_err(|e| e.to_string())
+        },
+        move |result| {
+            Message::Session(SessionMessage::PortForwardStopped(
+                session_id, forward_id, result,
+            ))
+        },
+    )
+}
+
+pub(super) fn started(
+    portal: &mut Portal,
+    session_id: SessionId,
+    forward_id: Uuid,
+    result: Result<u16, String>,
+) -> Task<Message> {
+    let status = match result {
+        Ok(port) => ForwardStatus::Active(port),
+        Err(error) => {
+            let message = portal
+                .sessions
+                .get(session_id)
+                .and_then(|session| session.host_id)
+                .and_then(|host_id| {
+                    host_forwards(portal, host_id)
+                        .into_iter()
+                        .find(|f| f.id == forward_id)
+                })
+                .map(|forward| failure_message(&forward, &error))
+                .unwrap_or_else(|| format!("Port forward failed: {}", error));
+            portal.toast_manager.push(Toast::error(message));
+            ForwardStatus::Failed(error)
+        }
+    };
+
+    if let Some(session) = portal.sessions.get_mut(session_id) {
+        session.forward_status.insert(forward_id, status);
+    }
+    Task::none()
+}
+
+pub(super) fn stopped(
+    portal: &mut Portal,
+    session_id: SessionId,
+    forward_id: Uuid,
+    result: Result<(), String>,
+) -> Task<Message> {
+    if let Err(error) = result {
+        // The listener is gone either way; only the remote cancel can fail.
+        portal
+            .toast_manager
+            .push(Toast::warning(format!("Stopping port forward: {}", error)));
+    }
+
+    if let Some(session) = portal.sessions.get_mut(session_id) {
+        session
+            .forward_status
+            .insert(forward_id, ForwardStatus::Stopped);
+    }
+    Task::none()
+}
+
+fn host_forwards(portal: &Portal, host_id: Uuid) -> Vec<PortForward> {
+    portal
+        .config
+        .hosts
+        .find_host(host_id)
+        .map(|host| host.port_forwards.clone())
+        .unwrap_or_default()
+}
+
+/// Status for every configured forward: disabled ones start out stopped,
+/// enabled ones take the outcome of their connect-time start.
+fn initial_status(
+    forwards: &[PortForward],
+    results: Vec<(Uuid, Result<u16, String>)>,
+) -> HashMap<Uuid, ForwardStatus> {
+    let mut status: HashMap<Uuid, ForwardStatus> = forwards
+        .iter()
+        .map(|forward| (forward.id, ForwardStatus::Stopped))
+        .collect();
+    for (id, result) in results {
+        let entry = match result {
+            Ok(port) => ForwardStatus::Active(port),
+            Err(error) => ForwardStatus::Failed(error),
+        };
+        status.insert(id, entry);
+    }
+    status
+}
+
+fn failure_message(forward: &PortForward, error: &str) -> String {
+    format!("Port forward {} failed: {}", forward.summary(), error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PortForwardKind;
+
+    fn forward(enabled: bool) -> PortForward {
+        PortForward {
+            id: Uuid::new_v4(),
+            kind: PortForwardKind::Local,
+            bind_host: "localhost".to_string(),
+            bind_port: 8080,
+            target_host: "db".to_string(),
+            target_port: 5432,
+            enabled,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn initial_status_marks_disabled_stopped_and_records_failures() {
+        let ok = forward(true);
+        let failed = forward(true);
+        let disabled = forward(false);
+        let status = initial_status(
+            &[ok.clone(), failed.clone(), disabled.clone()],
+            vec![
+                (ok.id, Ok(8080)),
+                (failed.id, Err("address in use".to_string())),
+            ],
+        );
+
+        assert_eq!(status.get(&ok.id), Some(&ForwardStatus::Active(8080)));
+        assert_eq!(
+            status.get(&failed.id),
+            Some(&ForwardStatus::Failed("address in use".to_string()))
+        );
+        assert_eq!(status.get(&disabled.id), Some(&ForwardStatus::Stopped));
+    }
+}
@@ -0,0 +1,292 @@
+//! ZMODEM (`sz`) receive handling for terminal sessions
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use iced::Task;
+
+use super::{queue_terminal_output, write_to_backend};
+use crate::app::Portal;
+use crate::app::managers::{ZmodemFile, ZmodemTransfer};
+use crate::message::{Message, SessionId, SessionMessage};
+use crate::terminal::zmodem::{CANCEL_SEQUENCE, ZmodemEvent};
+use crate::views::toast::Toast;
+
+/// Output buffered while the save-folder prompt is open. `sz` keeps
+/// re-sending its init header until it hears back, so this stays small.
+const PENDING_LIMIT: usize = 64 * 1024;
+
+/// A transfer was detected: hold the session output and ask where to save.
+pub(super) fn start(portal: &mut Portal, session_id: SessionId, data: Vec<u8>) -> Task<Message> {
+    let Some(session) = portal.sessions.get_mut(session_id) else {
+        return Task::none();
+    };
+    tracing::info!("ZMODEM transfer requested by remote");
+    session.zmodem = Some(ZmodemTransfer::new(data));
+    session.status_message = Some((
+        "ZMODEM transfer requested - choose a folder".to_string(),
+        Instant::now(),
+    ));
+
+    Task::perform(
+        async move {
+            rfd::FileDialog::new()
+                .set_title("Save received files to")
+                .pick_folder()
+        },
+        move |dir| Message::Session(SessionMessage::ZmodemSaveDirChosen(session_id, dir)),
+    )
+}
+
+pub(super) fn save_dir_chosen(
+    portal: &mut Portal,
+    session_id: SessionId,
+    dir: Option<PathBuf>,
+) -> Task<Message> {
+    let Some(dir) = dir else {
+        return cancel(portal, session_id, "ZMODEM transfer cancelled");
+    };
+    let Some(transfer) = portal
+        .sessions
+        .get_mut(session_id)
+        .and_then(|session| session.zmodem.as_mut())
+    else {
+        return Task::none();
+    };
+    transfer.save_dir = Some(dir);
+    let pending = std::mem::take(&mut transfer.pending);
+    receive(portal, session_id, pending)
+}
+
+/// Feed session output into the active transfer.
+pub(super) fn receive(portal: &mut Portal, session_id: SessionId, data: Vec<u8>) -> Task<Message> {
+    let Some(session) = portal.sessions.get_mut(session_id) else {
+        return Task::none();
+    };
+    let Some(transfer) = session.zmodem.as_mut() else {
+        return Task::none();
+    };
+    let Some(save_dir) = transfer.save_dir.clone() else {
+        if transfer.pending.len() + data.len() <= PENDING_LIMIT {
+            transfer.pending.extend(data);
+        }
+        return Task::none();
+    };
+
+    let output = transfer.receiver.feed(&data);
+    let mut reply = output.reply;
+    let mut outcome = None;
+    for event in output.events {
+        let result = match event {
+            ZmodemEvent::FileStart { name, size } => open_file(transfer, &save_dir, &name, size)
+                .map(|path| {
+                    session.status_message =
+                        Some((format!("Receiving {}", file_label(&path)), Instant::now()));
+                }),
+            ZmodemEvent::FileData(bytes) => write_chunk(transfer, &bytes),
+            ZmodemEvent::FileEnd => finish_file(transfer),
+            ZmodemEvent::Finished => {
+                outcome = Some(Ok(format!(
+                    "Received {} file{} into {}",
+                    transfer.completed.len(),
+                    if transfer.completed.len() == 1 {
+                        ""
+                    } else {
+                        "s"
+                    },
+                    save_dir.display()
+                )));
+                Ok(())
+            }
+            ZmodemEvent::Aborted(reason) => {
+                outcome = Some(Err(reason));
+                Ok(())
+            }
+        };
+        if let Err(error) = result {
+            reply.extend(transfer.receiver.abort());
+            outcome = Some(Err(error));
+            break;
+        }
+    }
+
+    let send = write_to_backend(&session.backend, reply);
+    if !transfer.receiver.is_done() {
+        return send;
+    }
+
+    let Some(mut transfer) = session.zmodem.take() else {
+        return send;
+    };
+    discard_partial_file(&mut transfer);
+    let trailing = transfer.receiver.take_trailing();
+    if !trailing.is_empty() {
+        queue_terminal_output(session, trailing, Instant::now());
+    }
+    session.status_message = None;
+    match outcome {
+        Some(Ok(message)) => portal.toast_manager.push(Toast::success(message)),
+        Some(Err(error)) => portal
+            .toast_manager
+            .push(Toast::error(format!("ZMODEM transfer failed: {}", error))),
+        None => {}
+    }
+    send
+}
+
+/// Abort the active transfer and tell the sender to stop.
+pub(super) fn cancel(portal: &mut Portal, session_id: SessionId, reason: &str) -> Task<Message> {
+    let Some(session) = portal.sessions.get_mut(session_id) else {
+        return Task::none();
+    };
+    let Some(mut transfer) = session.zmodem.take() else {
+        return Task::none();
+    };
+    tracing::info!("ZMODEM transfer cancelled");
+    discard_partial_file(&mut transfer);
+    session.status_message = Some((reason.to_string(), Instant::now()));
+    write_to_backend(&session.backend, CANCEL_SEQUENCE.to_vec())
+}
+
+fn open_file(
+    transfer: &mut ZmodemTransfer,
+    dir: &Path,
+    name: &str,
+    size: Option<u64>,
+) -> Result<PathBuf, String> {
+    discard_partial_file(transfer);
+    let file_name = sanitize_file_name(name)
+        .ok_or_else(|| format!("Refusing to save file with unsafe name {:?}", name))?;
+
+    let mut candidate = dir.join(&file_name);
+    let mut counter = 1;
+    let file = loop {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(file) => break file,
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists && counter < 1000 => {
+                candidate = dir.join(numbered_file_name(&file_name, counter));
+                counter += 1;
+            }
+            Err(error) => {
+                return Err(format!(
+                    "Failed to create {}: {}",
+                    candidate.display(),
+                    error
+                ));
+            }
+        }
+    };
+
+    transfer.current = Some(ZmodemFile {
+        path: candidate.clone(),
+        writer: std::io::BufWriter::new(file),
+        received: 0,
+        size,
+    });
+    Ok(candidate)
+}
+
+fn write_chunk(transfer: &mut ZmodemTransfer, bytes: &[u8]) -> Result<(), String> {
+    let Some(file) = transfer.current.as_mut() else {
+        return Ok(());
+    };
+    file.writer
+        .write_all(bytes)
+        .map_err(|error| format!("Failed to write {}: {}", file.path.display(), error))?;
+    file.received += bytes.len() as u64;
+    Ok(())
+}
+
+fn finish_file(transfer: &mut ZmodemTransfer) -> Result<(), String> {
+    let Some(mut file) = transfer.current.take() else {
+        return Ok(());
+    };
+    file.writer
+        .flush()
+        .map_err(|error| format!("Failed to write {}: {}", file.path.display(), error))?;
+    if let Some(size) = file.size
+        && size != file.received
+    {
+        tracing::warn!(
+            "ZMODEM file size mismatch: expected {} bytes, received {}",
+            size,
+            file.received
+        );
+    }
+    transfer.completed.push(file.path);
+    Ok(())
+}
+
+/// Remove a file whose transfer did not complete.
+fn discard_partial_file(transfer: &mut ZmodemTransfer) {
+    if let Some(file) = transfer.current.take() {
+        drop(file.writer);
+        if let Err(error) = std::fs::remove_file(&file.path) {
+            tracing::warn!(
+                "Failed to remove partial ZMODEM file {}: {}",
+                file.path.display(),
+                error
+            );
+        }
+    }
+}
+
+/// Reduce a sender-supplied name to a plain file name in the save folder.
+fn sanitize_file_name(name: &str) -> Option<String> {
+    let base = name.rsplit(['/', '\\']).next()?.trim();
+    let cleaned: String = base.chars().filter(|c| !c.is_control()).collect();
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        return None;
+    }
+    Some(cleaned)
+}
+
+fn numbered_file_name(file_name: &str, counter: u32) -> String {
+    match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => {
+            format!("{} ({}).{}", stem, counter, extension)
+        }
+        _ => format!("{} ({})", file_name, counter),
+    }
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_strips_directories_and_control_characters() {
+        assert_eq!(
+            sanitize_file_name("../../etc/passwd").as_deref(),
+            Some("passwd")
+        );
+        assert_eq!(
+            sanitize_file_name("C:\\temp\\report.txt").as_deref(),
+            Some("report.txt")
+        );
+        assert_eq!(
+            sanitize_file_name("bad\u{1b}[2Jname").as_deref(),
+            Some("bad[2Jname")
+        );
+        assert_eq!(sanitize_file_name(".."), None);
+        assert_eq!(sanitize_file_name("dir/"), None);
+    }
+
+    #[test]
+    fn numbered_names_keep_extension() {
+        assert_eq!(numbered_file_name("log.txt", 2), "log (2).txt");
+        assert_eq!(numbered_file_name(".bashrc", 1), ".bashrc (1)");
+        assert_eq!(numbered_file_name("README", 1), "README (1)");
+    }
+}
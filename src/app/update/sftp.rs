@@ -3,13 +3,18 @@
 use iced::Task;
 use uuid::Uuid;
 
+use crate::app::services::connection;
 use crate::app::{Portal, Tab, View};
 use crate::config::SettingsConfig;
-use crate::fs_utils::{copy_dir_recursive, copy_regular_file, count_items_in_dir};
 use crate::message::{Message, SftpMessage};
-use crate::views::sftp::state::ColumnResizeDrag;
-use crate::views::sftp::{DualPaneSftpState, PaneId, PaneSource};
-use crate::views::toast::Toast;
+use crate::sftp::properties::Account;
+use crate::sftp::sudo;
+use crate::views::sftp::state::{ColumnResizeDrag, SftpDialogState};
+use crate::views::sftp::{
+    ContextMenuAction, DualPaneSftpState, PaneId, PaneSource, PermissionBits, SftpDialogType,
+    path_input_id,
+};
+use crate::views::toast::{Toast, ToastAction};
 
 const DELETE_HOLD_DURATION: std::time::Duration = std::time::Duration::from_millis(1200);
 
@@ -21,7 +26,8 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             let dual_state = DualPaneSftpState::new_with_column_widths(
                 tab_id,
                 portal.prefs.sftp_column_widths.clone(),
-            );
+            )
+            .with_show_hidden(portal.prefs.sftp_show_hidden);
             portal.sftp.insert_tab(tab_id, dual_state);
 
             let tab = Tab::new_sftp(tab_id, "File Browser".to_string(), None);
@@ -53,6 +59,7 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
 
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 let pane = tab_state.pane_mut(pane_id);
+                pane.reset_dir_sizes();
                 pane.source = new_source;
                 pane.current_path = new_path;
                 pane.loading = true;
@@ -102,6 +109,39 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             }
             Task::none()
         }
+        SftpMessage::PaneToggleSelect(tab_id, pane_id, index) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                tab_state.close_actions_menus();
+                tab_state.active_pane = pane_id;
+                tab_state.pane_mut(pane_id).toggle_select(index);
+            }
+            Task::none()
+        }
+        SftpMessage::PaneOpenFile(tab_id, pane_id, index) => {
+            let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) else {
+                return Task::none();
+            };
+            tab_state.close_actions_menus();
+            tab_state.active_pane = pane_id;
+            tab_state.pane_mut(pane_id).select(index);
+            portal.handle_sftp_context_action(tab_id, ContextMenuAction::Open)
+        }
+        SftpMessage::PaneScrolled(tab_id, pane_id, offset, viewport_height) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                let pane = tab_state.pane_mut(pane_id);
+                pane.scroll_offset = offset;
+                pane.viewport_height = viewport_height;
+            }
+            Task::none()
+        }
+        SftpMessage::PaneSelectRange(tab_id, pane_id, index) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                tab_state.close_actions_menus();
+                tab_state.active_pane = pane_id;
+                tab_state.pane_mut(pane_id).select_range(index);
+            }
+            Task::none()
+        }
         SftpMessage::PaneListResult(tab_id, pane_id, requested_source, requested_path, result) => {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 let pane = tab_state.pane_mut(pane_id);
@@ -117,12 +157,102 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
                     return Task::none();
                 }
                 match result {
-                    Ok(entries) => pane.set_entries(entries),
+                    Ok(entries) => {
+                        pane.set_entries(entries);
+                        if let PaneSource::Remote { session_id, .. } = requested_source {
+                            remember_last_sftp_path(portal, session_id, requested_path);
+                        }
+                    }
                     Err(e) => pane.set_error(e),
                 }
             }
             Task::none()
         }
+        SftpMessage::PathEditStart(tab_id, pane_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                tab_state.close_actions_menus();
+                tab_state.active_pane = pane_id;
+                tab_state.pane_mut(pane_id).start_path_edit();
+                return iced::widget::operation::focus(path_input_id(tab_id, pane_id));
+            }
+            Task::none()
+        }
+        SftpMessage::PathInputChanged(tab_id, pane_id, value) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                let pane = tab_state.pane_mut(pane_id);
+                if pane.path_input.is_some() {
+                    pane.path_input = Some(value);
+                    pane.path_input_error = None;
+                }
+            }
+            Task::none()
+        }
+        SftpMessage::PathInputComplete(tab_id, pane_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                tab_state.pane_mut(pane_id).complete_path_input();
+                return iced::widget::operation::move_cursor_to_end(path_input_id(tab_id, pane_id));
+            }
+            Task::none()
+        }
+        SftpMessage::PathEditCancel(tab_id, pane_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                tab_state.pane_mut(pane_id).cancel_path_edit();
+            }
+            Task::none()
+        }
+        SftpMessage::PathSubmit(tab_id, pane_id) => {
+            let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) else {
+                return Task::none();
+            };
+            let pane = tab_state.pane_mut(pane_id);
+            let Some(input) = pane.path_input.as_deref() else {
+                return Task::none();
+            };
+            let typed = input.trim();
+            let absolute = match pane.source {
+                PaneSource::Local => std::path::Path::new(typed).is_absolute(),
+                PaneSource::Remote { .. } => typed.starts_with('/'),
+            };
+            if !absolute {
+                pane.path_input_error = Some("Enter an absolute path".to_string());
+                return Task::none();
+            }
+            let path = std::path::PathBuf::from(typed);
+            if path == pane.current_path {
+                pane.cancel_path_edit();
+                return Task::none();
+            }
+            pane.path_input_error = None;
+            portal.load_typed_pane_path(tab_id, pane_id, path)
+        }
+        SftpMessage::PathSubmitResult(
+            tab_id,
+            pane_id,
+            requested_source,
+            requested_path,
+            result,
+        ) => {
+            let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) else {
+                return Task::none();
+            };
+            let pane = tab_state.pane_mut(pane_id);
+            // The user may have switched sources or stopped editing meanwhile
+            if pane.source != requested_source || pane.path_input.is_none() {
+                return Task::none();
+            }
+            match result {
+                Ok(entries) => {
+                    pane.current_path = requested_path.clone();
+                    pane.set_entries(entries);
+                    pane.cancel_path_edit();
+                    if let PaneSource::Remote { session_id, .. } = requested_source {
+                        remember_last_sftp_path(portal, session_id, requested_path);
+                    }
+                }
+                Err(e) => pane.path_input_error = Some(e),
+            }
+            Task::none()
+        }
         SftpMessage::ConnectHost(tab_id, pane_id, host_id) => {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 tab_state.close_actions_menus();
@@ -140,6 +270,8 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             host_id,
             host_name,
             sftp_session,
+            initial_path,
+            missing_last_path,
         } => {
             tracing::info!("SFTP connected for pane {:?}", pane_id);
             if !portal
@@ -181,16 +313,24 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
                 }
             }
 
-            let home_dir = sftp_session.home_dir().to_path_buf();
             portal.sftp.insert_connection(sftp_session_id, sftp_session);
+            portal.sftp.insert_connection_host(sftp_session_id, host_id);
+
+            if let Some(missing) = missing_last_path {
+                portal.toast_manager.push(Toast::warning(format!(
+                    "{} no longer exists, opened the home directory",
+                    missing.display()
+                )));
+            }
 
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 let pane = tab_state.pane_mut(pane_id);
+                pane.reset_dir_sizes();
                 pane.source = PaneSource::Remote {
                     session_id: sftp_session_id,
                     host_name,
                 };
-                pane.current_path = home_dir;
+                pane.current_path = initial_path;
                 pane.loading = true;
                 pane.clear_entries();
                 return portal.load_dual_pane_directory(tab_id, pane_id);
@@ -256,15 +396,49 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
                         tab_state.pane_mut(pane_id).loading = true;
                         return portal.load_dual_pane_directory(tab_id, pane_id);
                     }
-                    Err(error) => {
-                        if let Some(ref mut dialog) = tab_state.dialog {
-                            dialog.error = Some(error);
-                        }
-                    }
+                    Err(error) => return handle_dialog_error(portal, tab_id, pane_id, error),
                 }
             }
             Task::none()
         }
+        SftpMessage::NewFileOpenAfterToggled(tab_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(dialog) = tab_state.dialog.as_mut()
+                && let SftpDialogType::NewFile { open_after } = &mut dialog.dialog_type
+            {
+                *open_after = !*open_after;
+            }
+            Task::none()
+        }
+        SftpMessage::NewFileResult(tab_id, pane_id, result) => {
+            let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) else {
+                return Task::none();
+            };
+            let path = match result {
+                Ok(path) => path,
+                Err(error) => return handle_dialog_error(portal, tab_id, pane_id, error),
+            };
+            let open_after = matches!(
+                tab_state.dialog.as_ref().map(|dialog| &dialog.dialog_type),
+                Some(SftpDialogType::NewFile { open_after: true })
+            );
+            tab_state.close_dialog();
+            let pane = tab_state.pane_mut(pane_id);
+            pane.loading = true;
+            let source = pane.source.clone();
+            portal.toast_manager.push(Toast::success("File created"));
+
+            let refresh = portal.load_dual_pane_directory(tab_id, pane_id);
+            if !open_after {
+                return refresh;
+            }
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let open = portal.open_pane_file_viewer(&source, file_name, path);
+            Task::batch([refresh, open])
+        }
         SftpMessage::RenameResult(tab_id, pane_id, result) => {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 match result {
@@ -276,11 +450,7 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
                         tab_state.pane_mut(pane_id).loading = true;
                         return portal.load_dual_pane_directory(tab_id, pane_id);
                     }
-                    Err(error) => {
-                        if let Some(ref mut dialog) = tab_state.dialog {
-                            dialog.error = Some(error);
-                        }
-                    }
+                    Err(error) => return handle_dialog_error(portal, tab_id, pane_id, error),
                 }
             }
             Task::none()
@@ -300,11 +470,7 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
                         tab_state.pane_mut(pane_id).loading = true;
                         return portal.load_dual_pane_directory(tab_id, pane_id);
                     }
-                    Err(error) => {
-                        if let Some(ref mut dialog) = tab_state.dialog {
-                            dialog.error = Some(error);
-                        }
-                    }
+                    Err(error) => return handle_dialog_error(portal, tab_id, pane_id, error),
                 }
             }
             Task::none()
@@ -317,6 +483,90 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             }
             Task::none()
         }
+        SftpMessage::PermissionsRecursiveToggled(tab_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(ref mut dialog) = tab_state.dialog
+            {
+                dialog.toggle_recursive_permissions();
+            }
+            Task::none()
+        }
+        SftpMessage::PermissionsSearchableDirsToggled(tab_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(ref mut dialog) = tab_state.dialog
+            {
+                dialog.toggle_searchable_dirs();
+            }
+            Task::none()
+        }
+        SftpMessage::PermissionsProgress {
+            tab_id,
+            walk_id,
+            progress,
+        } => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                tab_state.update_permission_walk(walk_id, progress);
+            }
+            Task::none()
+        }
+        SftpMessage::PermissionsRecursiveResult {
+            tab_id,
+            pane_id,
+            walk_id,
+            result,
+        } => {
+            let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) else {
+                return Task::none();
+            };
+            if !tab_state.finish_permission_walk(walk_id) {
+                return Task::none();
+            }
+            let changing_owner = matches!(
+                tab_state.dialog.as_ref().map(|dialog| &dialog.dialog_type),
+                Some(SftpDialogType::ChangeOwner { .. })
+            );
+            match result {
+                Ok(report) if report.failures.is_empty() => {
+                    let what = if changing_owner {
+                        "Owner"
+                    } else {
+                        "Permissions"
+                    };
+                    portal.toast_manager.push(Toast::success(format!(
+                        "{} updated on {} item(s)",
+                        what, report.progress.changed
+                    )));
+                    tab_state.close_dialog();
+                }
+                // Keep the dialog open so the failed entries can be read
+                Ok(report) => {
+                    tracing::warn!(
+                        "Recursive permission change failed for {} item(s)",
+                        report.progress.failed
+                    );
+                    // A refused chown can be retried as `chown -R` via sudo
+                    if changing_owner {
+                        tab_state.pane_mut(pane_id).loading = true;
+                        let reload = portal.load_dual_pane_directory(tab_id, pane_id);
+                        let prompt =
+                            handle_dialog_error(portal, tab_id, pane_id, report.failure_summary());
+                        return Task::batch([reload, prompt]);
+                    }
+                    if let Some(dialog) = tab_state.dialog.as_mut() {
+                        dialog.error = Some(report.failure_summary());
+                    }
+                }
+                Err(error) => {
+                    tracing::error!("Recursive permission change failed: {}", error);
+                    if let Some(dialog) = tab_state.dialog.as_mut() {
+                        dialog.error = Some(error);
+                    }
+                    return Task::none();
+                }
+            }
+            tab_state.pane_mut(pane_id).loading = true;
+            portal.load_dual_pane_directory(tab_id, pane_id)
+        }
         SftpMessage::PermissionsResult(tab_id, pane_id, result) => {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 match result {
@@ -329,35 +579,146 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
                         tab_state.pane_mut(pane_id).loading = true;
                         return portal.load_dual_pane_directory(tab_id, pane_id);
                     }
+                    Err(error) => return handle_dialog_error(portal, tab_id, pane_id, error),
+                }
+            }
+            Task::none()
+        }
+        SftpMessage::OwnerResult(tab_id, pane_id, result) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                match result {
+                    Ok(()) => {
+                        tracing::info!("Owner updated successfully");
+                        portal.toast_manager.push(Toast::success("Owner updated"));
+                        tab_state.close_dialog();
+                        tab_state.pane_mut(pane_id).loading = true;
+                        return portal.load_dual_pane_directory(tab_id, pane_id);
+                    }
+                    Err(error) => return handle_dialog_error(portal, tab_id, pane_id, error),
+                }
+            }
+            Task::none()
+        }
+        SftpMessage::OwnerInputChanged(tab_id, value) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(ref mut dialog) = tab_state.dialog
+            {
+                dialog.set_ownership_input(false, value);
+            }
+            Task::none()
+        }
+        SftpMessage::GroupInputChanged(tab_id, value) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(ref mut dialog) = tab_state.dialog
+            {
+                dialog.set_ownership_input(true, value);
+            }
+            Task::none()
+        }
+        SftpMessage::PropertiesLoaded(tab_id, loaded_path, result) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(dialog) = tab_state.dialog.as_mut()
+                && let SftpDialogType::Properties {
+                    path, properties, ..
+                } = &mut dialog.dialog_type
+                && *path == loaded_path
+            {
+                match result {
+                    Ok(loaded) => *properties = Some(loaded),
                     Err(error) => {
-                        if let Some(ref mut dialog) = tab_state.dialog {
-                            dialog.error = Some(error);
-                        }
+                        tracing::error!("Failed to read properties: {}", error);
+                        dialog.error = Some(error);
                     }
                 }
             }
             Task::none()
         }
+        SftpMessage::PropertiesEditPermissions(tab_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(dialog) = tab_state.dialog.as_mut()
+                && let SftpDialogType::Properties {
+                    name,
+                    path,
+                    properties: Some(properties),
+                } = &dialog.dialog_type
+                && let Some(mode) = properties.mode
+                && !properties.is_symlink
+            {
+                let edit = SftpDialogState::edit_permissions(
+                    dialog.target_pane,
+                    name.clone(),
+                    path.clone(),
+                    PermissionBits::from_mode(mode),
+                    properties.is_dir,
+                );
+                *dialog = edit;
+            }
+            Task::none()
+        }
+        SftpMessage::PropertiesChangeOwner(tab_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(dialog) = tab_state.dialog.as_mut()
+                && let SftpDialogType::Properties {
+                    name,
+                    path,
+                    properties: Some(properties),
+                } = &dialog.dialog_type
+                && !properties.is_symlink
+            {
+                // Prefill with names where known so the fields read naturally
+                let typed = |account: &Option<Account>| {
+                    account.as_ref().map_or_else(String::new, |account| {
+                        account
+                            .name
+                            .clone()
+                            .unwrap_or_else(|| account.id.to_string())
+                    })
+                };
+                let change = SftpDialogState::change_owner(
+                    dialog.target_pane,
+                    name.clone(),
+                    path.clone(),
+                    properties.is_dir,
+                    typed(&properties.owner),
+                    typed(&properties.group),
+                );
+                *dialog = change;
+            }
+            Task::none()
+        }
+        SftpMessage::SudoPasswordChanged(tab_id, password) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(ref mut dialog) = tab_state.dialog
+            {
+                dialog.sudo_password = Some(password);
+                dialog.error = None;
+            }
+            Task::none()
+        }
         SftpMessage::CopyToTarget(tab_id) => portal.handle_copy_to_target(tab_id),
-        SftpMessage::CopyResult(tab_id, target_pane_id, result) => {
-            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+        SftpMessage::SyncOptionsChanged(tab_id, new_options) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(dialog) = tab_state.dialog.as_mut()
+                && let SftpDialogType::Sync { options, plan, .. } = &mut dialog.dialog_type
+            {
+                *options = new_options;
+                *plan = None;
+                dialog.error = None;
+                return portal.plan_sftp_sync(tab_id);
+            }
+            Task::none()
+        }
+        SftpMessage::SyncPlanned(tab_id, planned_options, result) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+                && let Some(dialog) = tab_state.dialog.as_mut()
+                && let SftpDialogType::Sync { options, plan, .. } = &mut dialog.dialog_type
+                && *options == planned_options
+            {
                 match result {
-                    Ok(count) => {
-                        tracing::info!("Copied {} item(s)", count);
-                        let msg = if count == 1 {
-                            "Copied 1 item".to_string()
-                        } else {
-                            format!("Copied {} items", count)
-                        };
-                        portal.toast_manager.push(Toast::success(msg));
-                        tab_state.pane_mut(target_pane_id).loading = true;
-                        return portal.load_dual_pane_directory(tab_id, target_pane_id);
-                    }
+                    Ok(new_plan) => *plan = Some(new_plan),
                     Err(error) => {
-                        tracing::error!("Copy failed: {}", error);
-                        portal
-                            .toast_manager
-                            .push(Toast::error(format!("Copy failed: {}", error)));
+                        tracing::error!("Sync scan failed: {}", error);
+                        dialog.error = Some(error);
                     }
                 }
             }
@@ -371,6 +732,7 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             transfer_id,
             tab_id,
             target_pane_id,
+            verified,
             result,
         } => {
             let status = match &result {
@@ -385,10 +747,11 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 match result {
                     Ok(count) => {
-                        let msg = if count == 1 {
-                            "Transferred 1 item".to_string()
-                        } else {
-                            format!("Transferred {} items", count)
+                        let msg = match (count, verified) {
+                            (1, false) => "Transferred 1 item".to_string(),
+                            (1, true) => "Transferred and verified 1 item".to_string(),
+                            (count, false) => format!("Transferred {} items", count),
+                            (count, true) => format!("Transferred and verified {} items", count),
                         };
                         portal.toast_manager.push(Toast::success(msg));
                         tab_state.pane_mut(target_pane_id).loading = true;
@@ -409,6 +772,28 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             }
             Task::none()
         }
+        SftpMessage::DirSizeUpdated {
+            tab_id,
+            pane_id,
+            walk_id,
+            path,
+            result,
+        } => {
+            let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) else {
+                return Task::none();
+            };
+            let applied = tab_state
+                .pane_mut(pane_id)
+                .update_dir_size(walk_id, &path, &result);
+            if applied && let Err(error) = result {
+                portal.toast_manager.push(Toast::error(format!(
+                    "Could not calculate size of {}: {}",
+                    path.display(),
+                    error
+                )));
+            }
+            Task::none()
+        }
         SftpMessage::TransferCancel(transfer_id) => {
             if portal.transfers.cancel(transfer_id) {
                 portal
@@ -417,15 +802,109 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             }
             Task::none()
         }
+        SftpMessage::TransferCancelAll(tab_id) => {
+            let cancelled = portal.transfers.cancel_for_tab(tab_id);
+            if cancelled > 0 {
+                portal
+                    .toast_manager
+                    .push_or_refresh(Toast::warning(if cancelled == 1 {
+                        "Cancelling transfer...".to_string()
+                    } else {
+                        format!("Cancelling {} transfers...", cancelled)
+                    }));
+            }
+            Task::none()
+        }
         SftpMessage::TransferClearFinished => {
             portal.transfers.clear_finished();
             Task::none()
         }
+        SftpMessage::WatchUploaded(watch_id, result) => {
+            if let Err(error) = &result {
+                tracing::warn!("Watched file upload failed: {}", error);
+            }
+            // Edited copies are uploaded on request, so report the outcome
+            if let Some(watch) = portal.file_watches.get(watch_id)
+                && watch.is_edit()
+            {
+                let toast = match &result {
+                    Ok(()) => Toast::success(format!(
+                        "Uploaded {} to {}",
+                        watch.label(),
+                        watch.remote_path.display()
+                    )),
+                    Err(error) => {
+                        Toast::error(format!("Failed to upload {}: {}", watch.label(), error))
+                    }
+                };
+                portal.toast_manager.push(toast);
+            }
+            portal.file_watches.record_upload(watch_id, result);
+            Task::none()
+        }
+        SftpMessage::WatchChanged(watch_id) => {
+            portal.prompt_edited_file_upload(watch_id);
+            Task::none()
+        }
+        SftpMessage::WatchUpload(watch_id) => portal.upload_edited_file(watch_id),
+        SftpMessage::OpenWithDownloaded {
+            tab_id,
+            edit_id,
+            session_id,
+            remote_path,
+            command,
+            result,
+        } => match result {
+            Ok(local_path) => portal.start_open_with_edit(
+                tab_id,
+                edit_id,
+                session_id,
+                remote_path,
+                &command,
+                local_path,
+            ),
+            Err(error) => {
+                portal.toast_manager.push(Toast::error(error));
+                Task::none()
+            }
+        },
+        SftpMessage::ContentsLoaded(file_name, result) => match result {
+            Ok(text) => {
+                portal
+                    .toast_manager
+                    .push(Toast::success(format!("Copied {} to clipboard", file_name)));
+                iced::clipboard::write(text)
+            }
+            Err(error) => {
+                portal.toast_manager.push(Toast::error(error));
+                Task::none()
+            }
+        },
+        SftpMessage::WatchStop(watch_id) => {
+            portal
+                .toast_manager
+                .dismiss_action(ToastAction::UploadEditedFile(watch_id));
+            if let Some(watch) = portal.file_watches.remove(watch_id) {
+                portal.toast_manager.push(Toast::success(format!(
+                    "Stopped watching {}",
+                    watch.label()
+                )));
+            }
+            Task::none()
+        }
         SftpMessage::ToggleShowHidden(tab_id, pane_id) => {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 let pane = tab_state.pane_mut(pane_id);
                 pane.toggle_show_hidden();
                 pane.actions_menu_open = false;
+
+                // New panes start with the most recent choice
+                portal.prefs.sftp_show_hidden = pane.show_hidden;
+                let mut settings = SettingsConfig::load().unwrap_or_default();
+                settings.sftp_show_hidden = portal.prefs.sftp_show_hidden;
+                if let Err(e) = settings.save() {
+                    tracing::error!("Failed to save hidden files preference: {}", e);
+                }
             }
             Task::none()
         }
@@ -444,6 +923,43 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             }
             Task::none()
         }
+        SftpMessage::ToggleBookmarksMenu(tab_id, pane_id) => {
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                let open = !tab_state.pane(pane_id).bookmarks_menu_open;
+                tab_state.close_actions_menus();
+                tab_state.pane_mut(pane_id).bookmarks_menu_open = open;
+            }
+            Task::none()
+        }
+        SftpMessage::ToggleBookmark(tab_id, pane_id) => toggle_bookmark(portal, tab_id, pane_id),
+        SftpMessage::BookmarkRename(tab_id, pane_id, bookmark_id) => {
+            let name = portal
+                .config
+                .sftp_bookmarks
+                .bookmarks
+                .iter()
+                .find(|bookmark| bookmark.id == bookmark_id)
+                .map(|bookmark| bookmark.name.clone());
+            if let Some(name) = name
+                && let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
+            {
+                tab_state.show_rename_bookmark_dialog(pane_id, bookmark_id, name);
+            }
+            Task::none()
+        }
+        SftpMessage::BookmarkDelete(tab_id, bookmark_id) => {
+            if let Some(bookmark) = portal.config.sftp_bookmarks.remove(bookmark_id) {
+                portal.save_sftp_bookmarks();
+                portal.toast_manager.push(Toast::success(format!(
+                    "Removed bookmark {}",
+                    bookmark.name
+                )));
+            }
+            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
+                tab_state.close_actions_menus();
+            }
+            Task::none()
+        }
         SftpMessage::FilterChanged(tab_id, pane_id, text) => {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
                 tab_state.close_actions_menus();
@@ -477,7 +993,7 @@ pub fn handle_sftp(portal: &mut Portal, msg: SftpMessage) -> Task<Message> {
             } else {
                 return Task::none();
             };
-            handle_dropped_files(portal, tab_id, paths)
+            portal.handle_dropped_files(tab_id, paths)
         }
         SftpMessage::DeleteHoldStart(tab_id) => {
             if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id)
@@ -590,120 +1106,95 @@ fn navigable_parent(path: &std::path::Path) -> Option<&std::path::Path> {
     Some(parent)
 }
 
-fn handle_dropped_files(
+/// Remember `path` as the host's last SFTP directory after a remote pane
+/// lists it. Hosts with an explicit start directory always open there, so
+/// nothing is remembered for them. The change is written to hosts.toml when
+/// the connection closes rather than on every directory change.
+fn remember_last_sftp_path(portal: &mut Portal, sftp_session_id: Uuid, path: std::path::PathBuf) {
+    let Some(host_id) = portal.sftp.connection_host(sftp_session_id) else {
+        return;
+    };
+    let Some(host) = portal.config.hosts.find_host(host_id) else {
+        return;
+    };
+    if host.sftp_start_path.is_some() || host.last_sftp_path.as_ref() == Some(&path) {
+        return;
+    }
+
+    let mut host = host.clone();
+    host.last_sftp_path = Some(path);
+    if let Err(e) = portal.config.hosts.update_host(host) {
+        tracing::error!("Failed to remember SFTP directory: {}", e);
+        return;
+    }
+    portal.sftp.mark_last_paths_unsaved();
+}
+
+/// Bookmark a pane's current directory, or remove its bookmark.
+fn toggle_bookmark(
     portal: &mut Portal,
     tab_id: crate::message::SessionId,
-    paths: Vec<std::path::PathBuf>,
+    pane_id: PaneId,
 ) -> Task<Message> {
-    if paths.is_empty() {
+    let Some(tab_state) = portal.sftp.get_tab(tab_id) else {
         return Task::none();
+    };
+    let pane = tab_state.pane(pane_id);
+    let Some(host_id) = portal.sftp.bookmark_host(&pane.source) else {
+        return Task::none();
+    };
+    let path = pane.current_path.clone();
+
+    let message = if portal.config.sftp_bookmarks.toggle(host_id, path.clone()) {
+        format!("Bookmarked {}", path.display())
+    } else {
+        format!("Removed bookmark for {}", path.display())
+    };
+    if portal.save_sftp_bookmarks() {
+        portal.toast_manager.push(Toast::success(message));
     }
+    Task::none()
+}
 
-    let Some(tab_state) = portal.sftp.get_tab(tab_id) else {
+/// Show a failed SFTP dialog operation. When a remote operation was refused
+/// for lack of permissions, offer to retry it with sudo (reusing a cached
+/// sudo password for the connection if there is one).
+fn handle_dialog_error(
+    portal: &mut Portal,
+    tab_id: crate::message::SessionId,
+    pane_id: PaneId,
+    error: String,
+) -> Task<Message> {
+    let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) else {
         return Task::none();
     };
-    let target_pane_id = tab_state.active_pane;
-    let target_pane = tab_state.pane(target_pane_id);
-    let target_dir = target_pane.current_path.clone();
-    let target_source = target_pane.source.clone();
-
-    match target_source {
-        PaneSource::Local => {
-            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
-                tab_state.pane_mut(target_pane_id).loading = true;
-            }
-            Task::perform(
-                async move {
-                    tokio::task::spawn_blocking(move || {
-                        let mut count = 0;
-                        for source_path in paths {
-                            let metadata =
-                                std::fs::symlink_metadata(&source_path).map_err(|e| {
-                                    format!("Failed to read {}: {}", source_path.display(), e)
-                                })?;
-                            if metadata.file_type().is_symlink() {
-                                return Err(format!(
-                                    "Cannot copy symbolic link {}",
-                                    source_path.display()
-                                ));
-                            }
-
-                            let name = source_path
-                                .file_name()
-                                .ok_or_else(|| {
-                                    format!("Cannot copy unnamed path {}", source_path.display())
-                                })?
-                                .to_owned();
-                            let target_path = target_dir.join(name);
-
-                            if metadata.is_dir() {
-                                copy_dir_recursive(&source_path, &target_path)?;
-                                count += count_items_in_dir(&source_path)?;
-                            } else {
-                                copy_regular_file(&source_path, &target_path)?;
-                                count += 1;
-                            }
-                        }
-                        Ok(count)
-                    })
-                    .await
-                    .map_err(|e| e.to_string())?
-                },
-                move |result| {
-                    Message::Sftp(SftpMessage::CopyResult(tab_id, target_pane_id, result))
-                },
-            )
-        }
-        PaneSource::Remote { session_id, .. } => {
-            let Some(sftp) = portal.sftp.get_connection(session_id).cloned() else {
-                return Task::none();
-            };
-            if let Some(tab_state) = portal.sftp.get_tab_mut(tab_id) {
-                tab_state.pane_mut(target_pane_id).loading = true;
-            }
-            Task::perform(
-                async move {
-                    let mut count = 0;
-                    for source_path in paths {
-                        let metadata =
-                            tokio::fs::symlink_metadata(&source_path)
-                                .await
-                                .map_err(|e| {
-                                    format!("Failed to read {}: {}", source_path.display(), e)
-                                })?;
-                        if metadata.file_type().is_symlink() {
-                            return Err(format!(
-                                "Cannot upload symbolic link {}",
-                                source_path.display()
-                            ));
-                        }
-                        let name = source_path
-                            .file_name()
-                            .ok_or_else(|| {
-                                format!("Cannot upload unnamed path {}", source_path.display())
-                            })?
-                            .to_owned();
-                        let target_path = target_dir.join(name);
-                        if metadata.is_dir() {
-                            count += sftp
-                                .upload_recursive(&source_path, &target_path)
-                                .await
-                                .map_err(|e| e.to_string())?;
-                        } else {
-                            sftp.upload(&source_path, &target_path)
-                                .await
-                                .map_err(|e| e.to_string())?;
-                            count += 1;
-                        }
-                    }
-                    Ok(count)
-                },
-                move |result| {
-                    Message::Sftp(SftpMessage::CopyResult(tab_id, target_pane_id, result))
-                },
-            )
+    let session_id = tab_state.pane(pane_id).source.session_id();
+    let Some(dialog) = tab_state.dialog.as_mut() else {
+        return Task::none();
+    };
+    let Some(session_id) = session_id else {
+        dialog.error = Some(error);
+        return Task::none();
+    };
+
+    if sudo::is_auth_failure(&error) {
+        connection::shared_sudo_cache().remove(&session_id);
+        dialog.prompt_for_sudo(error);
+        return Task::none();
+    }
+
+    if dialog.sudo_password.is_none() && sudo::is_permission_denied(&error) {
+        if let Some(password) = connection::shared_sudo_cache().get(&session_id) {
+            tracing::debug!("Retrying SFTP operation with cached sudo password");
+            dialog.sudo_password = Some(password);
+            return portal.handle_sftp_dialog_submit(tab_id);
         }
+        dialog.prompt_for_sudo(error);
+        return Task::none();
     }
+
+    dialog.error = Some(error);
+    Task::none()
 }
 
 #[cfg(test)]
@@ -9,8 +9,11 @@ use uuid::Uuid;
 use crate::app::managers::{ExecutionStatus, SnippetExecution};
 use crate::app::{Portal, SnippetEditState};
 use crate::config::{HistoricalHostResult, Host, Snippet};
-use crate::message::{HostExecutionResult, Message, SnippetField, SnippetMessage};
+use crate::message::{
+    HostExecutionResult, Message, SessionId, SessionMessage, SnippetField, SnippetMessage,
+};
 use crate::ssh::SshEvent;
+use crate::views::dialogs::snippet_params_dialog::SnippetParamsDialogState;
 use crate::views::toast::Toast;
 
 /// Handle snippet messages
@@ -92,6 +95,13 @@ pub fn handle_snippet(portal: &mut Portal, msg: SnippetMessage) -> Task<Message>
             Task::none()
         }
 
+        SnippetMessage::OsFilterChanged(os_filter) => {
+            if let Some(edit) = &mut portal.snippets.editing {
+                edit.os_filter = os_filter;
+            }
+            Task::none()
+        }
+
         SnippetMessage::EditCancel => {
             portal.snippets.editing = None;
             Task::none()
@@ -115,6 +125,7 @@ pub fn handle_snippet(portal: &mut Portal, msg: SnippetMessage) -> Task<Message>
                             Some(edit.description.trim().to_string())
                         };
                         snippet.host_ids = host_ids;
+                        snippet.os_filter = edit.os_filter;
                         snippet.updated_at = now;
                     }
                 } else {
@@ -127,6 +138,7 @@ pub fn handle_snippet(portal: &mut Portal, msg: SnippetMessage) -> Task<Message>
                         snippet.description = Some(edit.description.trim().to_string());
                     }
                     snippet.host_ids = host_ids;
+                    snippet.os_filter = edit.os_filter;
                     portal.config.snippets.add_snippet(snippet);
                 }
                 if let Err(e) = portal.config.snippets.save() {
@@ -143,6 +155,12 @@ pub fn handle_snippet(portal: &mut Portal, msg: SnippetMessage) -> Task<Message>
 
         // Execution
         SnippetMessage::Run(snippet_id) => handle_run(portal, snippet_id),
+        SnippetMessage::RunResolved(snippet_id, command) => {
+            run_command(portal, snippet_id, command)
+        }
+        SnippetMessage::Insert(session_id, snippet_id) => {
+            handle_insert(portal, session_id, snippet_id)
+        }
 
         SnippetMessage::HostResult {
             snippet_id,
@@ -279,7 +297,7 @@ pub fn handle_snippet(portal: &mut Portal, msg: SnippetMessage) -> Task<Message>
     }
 }
 
-/// Handle running a snippet on multiple hosts
+/// Run a snippet, first asking for the values of any `{{name}}` placeholders.
 fn handle_run(portal: &mut Portal, snippet_id: Uuid) -> Task<Message> {
     let Some(snippet) = portal.config.snippets.find_snippet(snippet_id) else {
         portal
@@ -288,6 +306,69 @@ fn handle_run(portal: &mut Portal, snippet_id: Uuid) -> Task<Message> {
         return Task::none();
     };
 
+    let placeholders = snippet.placeholders();
+    if placeholders.is_empty() {
+        let command = snippet.command.clone();
+        return run_command(portal, snippet_id, command);
+    }
+    if snippet.host_ids.is_empty() {
+        portal
+            .toast_manager
+            .push(Toast::warning("No hosts assigned to this snippet"));
+        return Task::none();
+    }
+
+    portal
+        .dialogs
+        .open_snippet_params(SnippetParamsDialogState::new(
+            snippet_id,
+            snippet.name.clone(),
+            snippet.command.clone(),
+            placeholders,
+        ));
+    Task::none()
+}
+
+/// Type a snippet at the terminal prompt without running it, first asking for
+/// the values of any placeholders.
+fn handle_insert(portal: &mut Portal, session_id: SessionId, snippet_id: Uuid) -> Task<Message> {
+    let Some(snippet) = portal.config.snippets.find_snippet(snippet_id) else {
+        portal
+            .toast_manager
+            .push(Toast::warning("Snippet not found"));
+        return Task::none();
+    };
+
+    let placeholders = snippet.placeholders();
+    if placeholders.is_empty() {
+        let command = snippet.command.clone();
+        return portal.update(Message::Session(SessionMessage::InsertText(
+            session_id, command,
+        )));
+    }
+
+    portal.dialogs.open_snippet_params(
+        SnippetParamsDialogState::new(
+            snippet_id,
+            snippet.name.clone(),
+            snippet.command.clone(),
+            placeholders,
+        )
+        .insert_into(session_id),
+    );
+    Task::none()
+}
+
+/// Run `command` (the snippet's command with placeholders filled in) on the
+/// snippet's hosts.
+fn run_command(portal: &mut Portal, snippet_id: Uuid, command: String) -> Task<Message> {
+    let Some(snippet) = portal.config.snippets.find_snippet(snippet_id) else {
+        portal
+            .toast_manager
+            .push(Toast::warning("Snippet not found"));
+        return Task::none();
+    };
+
     if snippet.host_ids.is_empty() {
         portal
             .toast_manager
@@ -324,7 +405,7 @@ fn handle_run(portal: &mut Portal, snippet_id: Uuid) -> Task<Message> {
     let execution = SnippetExecution::new(
         snippet_id,
         snippet.name.clone(),
-        snippet.command.clone(),
+        command.clone(),
         hosts_info
             .iter()
             .map(|(id, name, _)| (*id, name.clone()))
@@ -340,7 +421,6 @@ fn handle_run(portal: &mut Portal, snippet_id: Uuid) -> Task<Message> {
     }
 
     // Spawn parallel execution tasks for each host
-    let command = snippet.command.clone();
     let all_hosts = portal.config.hosts.hosts.clone();
     let tasks: Vec<Task<Message>> = hosts_info
         .into_iter()
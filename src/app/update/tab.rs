@@ -4,7 +4,7 @@ use iced::Task;
 
 use crate::app::Portal;
 use crate::app::managers::SessionBackend;
-use crate::message::{Message, TabContextMenuAction, TabMessage};
+use crate::message::{Message, SessionMessage, TabContextMenuAction, TabMessage};
 use crate::views::tabs::{TabType, tab_rename_input_id};
 use crate::views::toast::Toast;
 
@@ -61,6 +61,8 @@ fn submit_rename(portal: &mut Portal, tab_id: uuid::Uuid) -> Task<Message> {
         .expect("tab checked above");
     let previous_title = std::mem::replace(&mut tab.title, requested_title.clone());
     tab.rename_value = None;
+    tab.program_title = None;
+    tab.renamed = true;
 
     if previous_title == requested_title || !persist_to_hub {
         return Task::none();
@@ -182,6 +184,20 @@ pub fn handle_tab(portal: &mut Portal, msg: TabMessage) -> Task<Message> {
                             .push(Toast::warning("No log file available for this session"));
                     }
                 }
+                TabContextMenuAction::ClearScrollback => {
+                    return portal
+                        .update(Message::Session(SessionMessage::ClearScrollback(tab_id)));
+                }
+                TabContextMenuAction::ResetTerminal => {
+                    return portal.update(Message::Session(SessionMessage::ResetTerminal(tab_id)));
+                }
+                TabContextMenuAction::SendReset => {
+                    return portal.update(Message::Session(SessionMessage::SendReset(tab_id)));
+                }
+                TabContextMenuAction::ToggleBroadcast => {
+                    return portal
+                        .update(Message::Session(SessionMessage::ToggleBroadcast(tab_id)));
+                }
                 TabContextMenuAction::OpenLogDirectory => {
                     let log_dir = portal
                         .sessions
@@ -1,15 +1,22 @@
 //! UI state message handlers
 
 mod keyboard;
+pub(crate) mod restore;
 pub(crate) mod settings;
 
+use std::time::Instant;
+
 use iced::Task;
+use iced::widget::pane_grid;
 
 use crate::app::{Portal, SIDEBAR_AUTO_COLLAPSE_THRESHOLD, SidebarState, View};
 use crate::message::{
     CommandAction, HostMessage, Message, ProxySessionsMessage, SessionMessage, SftpMessage,
-    SidebarMenuItem, SnippetMessage, UiMessage,
+    SidebarMenuItem, SnippetMessage, SnippetPaletteEntry, SwitcherTarget, TabMessage, UiMessage,
 };
+use crate::views::dialogs::quit_confirm_dialog::QuitConfirmDialogState;
+use crate::views::host_grid::{HostGridDrag, HostGridDragItem, HostGridDropTarget};
+use crate::views::toast::Toast;
 
 /// Handle UI state messages.
 pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
@@ -32,6 +39,45 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
             }
             Task::none()
         }
+        UiMessage::HostTagToggled(tag) => {
+            let filter = &mut portal.ui.host_tag_filter;
+            if let Some(index) = filter
+                .iter()
+                .position(|selected| selected.eq_ignore_ascii_case(&tag))
+            {
+                filter.remove(index);
+            } else {
+                filter.push(tag);
+            }
+            portal.ui.host_grid_focus_index = None;
+            Task::none()
+        }
+        UiMessage::HostTagFilterCleared => {
+            portal.ui.host_tag_filter.clear();
+            portal.ui.host_grid_focus_index = None;
+            Task::none()
+        }
+        UiMessage::HostGridDragStarted(item) => {
+            portal.ui.host_grid_drag = Some(HostGridDrag { item, target: None });
+            Task::none()
+        }
+        UiMessage::HostGridDragEntered(target) => {
+            if let Some(drag) = &mut portal.ui.host_grid_drag {
+                drag.target = Some(target);
+            }
+            Task::none()
+        }
+        UiMessage::HostGridDragLeft(target) => {
+            // Leaving one target and entering the next arrive in layout
+            // order, so only clear the target this message is about
+            if let Some(drag) = &mut portal.ui.host_grid_drag
+                && drag.target == Some(target)
+            {
+                drag.target = None;
+            }
+            Task::none()
+        }
+        UiMessage::HostGridDropped => handle_host_grid_drop(portal),
         UiMessage::SidebarItemSelect(item) => handle_sidebar_item_select(portal, item),
         UiMessage::SidebarToggleCollapse => {
             portal.ui.sidebar_state = portal.ui.sidebar_state.next();
@@ -41,13 +87,23 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
         }
         UiMessage::SettingsTabSelected(tab) => {
             portal.ui.settings_tab = tab;
+            portal.ui.keybinding_capture = None;
             Task::none()
         }
         UiMessage::CommandPaletteToggle => {
             portal.ui.command_palette_open = !portal.ui.command_palette_open;
             if portal.ui.command_palette_open {
+                portal.ui.quick_switcher_open = false;
+                portal.ui.snippet_palette_open = false;
                 portal.ui.command_palette_query.clear();
-                iced::widget::operation::focus(crate::views::command_palette::command_input_id())
+                Task::batch([
+                    iced::widget::operation::focus(
+                        crate::views::command_palette::command_input_id(),
+                    ),
+                    Task::perform(crate::local::discover_launch_targets(), |targets| {
+                        Message::Ui(UiMessage::LocalLaunchTargetsDiscovered(targets))
+                    }),
+                ])
             } else {
                 Task::none()
             }
@@ -61,22 +117,104 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
             Task::none()
         }
         UiMessage::CommandPaletteRun(action) => run_command_action(portal, action),
+        UiMessage::QuickSwitcherToggle => {
+            portal.ui.quick_switcher_open = !portal.ui.quick_switcher_open;
+            if portal.ui.quick_switcher_open {
+                portal.ui.command_palette_open = false;
+                portal.ui.snippet_palette_open = false;
+                portal.ui.quick_switcher_query.clear();
+                iced::widget::operation::focus(
+                    crate::views::quick_switcher::quick_switcher_input_id(),
+                )
+            } else {
+                Task::none()
+            }
+        }
+        UiMessage::QuickSwitcherClose => {
+            portal.ui.quick_switcher_open = false;
+            Task::none()
+        }
+        UiMessage::QuickSwitcherChanged(query) => {
+            portal.ui.quick_switcher_query = query;
+            Task::none()
+        }
+        UiMessage::QuickSwitcherRun(target) => run_switcher_target(portal, target),
+        UiMessage::SnippetPaletteToggle => {
+            if portal.ui.snippet_palette_open {
+                close_snippet_palette(portal);
+                return Task::none();
+            }
+            if !matches!(portal.ui.active_view, View::Terminal(_)) {
+                portal
+                    .toast_manager
+                    .push(Toast::warning("Open a terminal to insert a snippet"));
+                return Task::none();
+            }
+            portal.ui.snippet_palette_open = true;
+            portal.ui.command_palette_open = false;
+            portal.ui.quick_switcher_open = false;
+            portal.ui.snippet_palette_query.clear();
+            portal.ui.snippet_palette_selected = 0;
+            iced::widget::operation::focus(crate::views::snippet_palette::snippet_palette_input_id())
+        }
+        UiMessage::SnippetPaletteClose => {
+            close_snippet_palette(portal);
+            Task::none()
+        }
+        UiMessage::SnippetPaletteChanged(query) => {
+            portal.ui.snippet_palette_query = query;
+            portal.ui.snippet_palette_selected = 0;
+            Task::none()
+        }
+        UiMessage::SnippetPaletteRun(entry) => run_snippet_palette_entry(portal, entry),
+        UiMessage::LocalLaunchTargetsDiscovered(targets) => {
+            portal.ui.local_launch_targets = targets;
+            Task::none()
+        }
         msg @ (UiMessage::ThemeChange(_)
+        | UiMessage::CustomThemeCreate
+        | UiMessage::CustomThemeRename(_)
+        | UiMessage::CustomThemeColorChanged(..)
+        | UiMessage::CustomThemeDelete
+        | UiMessage::CustomThemeImportRequested
+        | UiMessage::CustomThemeImported(_)
+        | UiMessage::KeybindingRecordStart(_)
+        | UiMessage::KeybindingRecordCancel
+        | UiMessage::KeybindingRecorded(..)
+        | UiMessage::KeybindingReset(_)
+        | UiMessage::KeybindingsResetAll
         | UiMessage::FontChange(_)
         | UiMessage::FontSizeChange(_)
         | UiMessage::TerminalScrollSpeedChange(_)
+        | UiMessage::ScrollbackLinesChange(_)
         | UiMessage::UiScaleChange(_)
         | UiMessage::UiScaleReset
         | UiMessage::SnippetHistoryEnabled(_)
         | UiMessage::SnippetHistoryStoreCommand(_)
         | UiMessage::SnippetHistoryStoreOutput(_)
         | UiMessage::SnippetHistoryRedactOutput(_)
+        | UiMessage::HistoryRedactExport(_)
         | UiMessage::SessionLoggingEnabled(_)
+        | UiMessage::SessionLogInput(_)
         | UiMessage::AllowAgentForwarding(_)
+        | UiMessage::HostReachabilityEnabled(_)
+        | UiMessage::SftpVerifyTransfersEnabled(_)
+        | UiMessage::SftpResumeTransfersEnabled(_)
         | UiMessage::AutoReconnectEnabled(_)
+        | UiMessage::TerminalLigaturesEnabled(_)
+        | UiMessage::TerminalBoldIsBrightEnabled(_)
+        | UiMessage::CursorBlinkEnabled(_)
+        | UiMessage::CursorBlinkIntervalChanged(_)
+        | UiMessage::TerminalBellChanged(_)
+        | UiMessage::TerminalClipboardAccessChanged(_)
+        | UiMessage::ConfirmQuitEnabled(_)
+        | UiMessage::RestoreSessionsEnabled(_)
         | UiMessage::ReconnectMaxAttemptsChanged(_)
         | UiMessage::ReconnectBaseDelayChanged(_)
         | UiMessage::ReconnectMaxDelayChanged(_)
+        | UiMessage::ConnectTimeoutChanged(_)
+        | UiMessage::KeepaliveIntervalChanged(_)
+        | UiMessage::KeepaliveCountMaxChanged(_)
         | UiMessage::CredentialTimeoutChange(_)
         | UiMessage::SecurityAuditLoggingEnabled(_)
         | UiMessage::VncQualityPresetChanged(_)
@@ -87,7 +225,9 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
         | UiMessage::VncPointerIntervalChanged(_)
         | UiMessage::VncRemoteResizeChanged(_)
         | UiMessage::VncClipboardSharingChanged(_)
+        | UiMessage::VncClipboardAutoSendChanged(_)
         | UiMessage::VncViewOnlyChanged(_)
+        | UiMessage::VncKeyboardModeChanged(_)
         | UiMessage::VncShowCursorDotChanged(_)
         | UiMessage::VncShowStatsOverlayChanged(_)
         | UiMessage::PortalHubEnabled(_)
@@ -164,6 +304,9 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
         }
         UiMessage::WindowUnfocused => {
             portal.ui.window_focused = false;
+            // The blink timer stops; leave the cursor shown.
+            portal.ui.cursor_blink_visible = true;
+            portal.ui.keyboard_modifiers = iced::keyboard::Modifiers::default();
             if let View::VncViewer(session_id) = portal.ui.active_view
                 && let Some(vnc) = portal.vnc_sessions.get(&session_id)
             {
@@ -171,10 +314,27 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
             }
             Task::none()
         }
+        UiMessage::ModifiersChanged(modifiers) => {
+            portal.ui.keyboard_modifiers = modifiers;
+            Task::none()
+        }
         UiMessage::WindowFocused => {
             portal.ui.window_focused = true;
             reconcile_active_terminal_size(portal)
         }
+        UiMessage::WindowCloseRequested => {
+            let sessions = portal.sessions.len() + portal.vnc_sessions.len();
+            let transfers = portal.transfers.active_count();
+            if !portal.prefs.confirm_quit || (sessions == 0 && transfers == 0) {
+                portal.save_remembered_sftp_paths();
+                portal.file_watches.clear();
+                return iced::exit();
+            }
+            portal
+                .dialogs
+                .open_quit_confirm(QuitConfirmDialogState::new(sessions, transfers));
+            Task::none()
+        }
         UiMessage::ToastDismiss(id) => {
             portal.toast_manager.dismiss(id);
             Task::none()
@@ -189,16 +349,43 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
                     ));
                     Task::batch([open_vault, refresh])
                 }
+                crate::views::toast::ToastAction::RestoreSessions => restore::accept(portal),
+                crate::views::toast::ToastAction::UploadEditedFile(watch_id) => {
+                    Task::done(Message::Sftp(SftpMessage::WatchUpload(watch_id)))
+                }
             }
         }
         UiMessage::ToastTick => {
             portal.toast_manager.cleanup_expired();
             Task::none()
         }
+        UiMessage::SessionRestoreTick => restore::tick(portal),
         UiMessage::AgentStatusTick => {
             // No-op: drives animated tab agent indicators.
             Task::none()
         }
+        UiMessage::CursorBlinkTick => {
+            portal.ui.cursor_blink_visible = !portal.ui.cursor_blink_visible;
+            Task::none()
+        }
+        UiMessage::BellFlashTick => {
+            let now = Instant::now();
+            for session in portal.sessions.values_mut() {
+                session.terminal.expire_bell_flash(now);
+            }
+            Task::none()
+        }
+        UiMessage::CredentialCacheTick => {
+            crate::app::services::connection::purge_expired_credentials();
+            Task::none()
+        }
+        UiMessage::ForgetCredentials => {
+            crate::app::services::connection::forget_cached_credentials();
+            portal
+                .toast_manager
+                .push(Toast::success("Cached passphrases and passwords forgotten"));
+            Task::none()
+        }
         UiMessage::TabAnimationTick => {
             // No-op: drives the short tab-entry width reveal.
             Task::none()
@@ -212,7 +399,8 @@ pub fn handle_ui(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
     }
 }
 
-/// Omnibox submit: `user@host[:port]` connects directly; otherwise, when the
+/// Omnibox submit: a connection target (`user@host[:port]`, `ssh://` URL,
+/// bracketed IPv6, optional `-i key`) connects directly; otherwise, when the
 /// filter matches exactly one host, connect to it.
 fn handle_search_submitted(portal: &mut Portal) -> Task<Message> {
     let query = portal.ui.search_query.trim().to_string();
@@ -220,36 +408,54 @@ fn handle_search_submitted(portal: &mut Portal) -> Task<Message> {
         return Task::none();
     }
 
-    if let Some((user, rest)) = query.split_once('@') {
-        let (hostname, port) = match rest.rsplit_once(':') {
-            Some((host, port_str)) => match port_str.parse::<u16>() {
-                Ok(port) => (host, port),
-                Err(_) => (rest, 22),
-            },
-            None => (rest, 22),
+    if looks_like_connection_target(&query) {
+        let target = match crate::validation::parse_quick_connect(&query) {
+            Ok(target) => target,
+            Err(error) => {
+                portal.toast_manager.push(Toast::error(format!(
+                    "Cannot connect to \"{}\": {}",
+                    query, error.message
+                )));
+                return Task::none();
+            }
         };
-        if user.is_empty() || hostname.is_empty() {
-            return Task::none();
-        }
 
+        let username = target
+            .username
+            .unwrap_or_else(crate::config::hosts::default_username);
+        let auth = match target.identity {
+            Some(key_path) => crate::config::AuthMethod::PublicKey {
+                key_path: Some(key_path),
+                vault_key_id: None,
+            },
+            None => crate::config::AuthMethod::Agent,
+        };
         let now = chrono::Utc::now();
         let temp_host = crate::config::Host {
             id: uuid::Uuid::new_v4(),
-            name: format!("{}@{}", user, hostname),
-            hostname: hostname.to_string(),
-            port,
-            username: user.to_string(),
+            name: format!("{}@{}", username, target.hostname),
+            hostname: target.hostname,
+            port: target.port.unwrap_or(22),
+            username,
             protocol: crate::config::Protocol::Ssh,
             vnc_port: None,
             vnc_password_id: None,
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
-            auth: crate::config::AuthMethod::Agent,
+            auth,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: crate::config::hosts::HubRouting::Auto,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at: now,
@@ -281,6 +487,11 @@ fn handle_search_submitted(portal: &mut Portal) -> Task<Message> {
     Task::none()
 }
 
+/// Whether omnibox input is meant as an address rather than a host filter
+fn looks_like_connection_target(query: &str) -> bool {
+    query.contains('@') || query.contains("://") || query.starts_with('[') || query.contains(" -i ")
+}
+
 fn should_apply_responsive_sidebar_state(
     sidebar_manually_set: bool,
     active_view: &View,
@@ -305,8 +516,14 @@ pub(super) fn reconcile_active_terminal_size(portal: &Portal) -> Task<Message> {
     let Some(session) = portal.sessions.get(session_id) else {
         return Task::none();
     };
+    // Panes of a split tab are smaller than the window; their widgets report
+    // their own sizes.
+    if portal.terminal_panes.tab_for_session(session_id).is_some() {
+        return Task::none();
+    }
 
-    let (cols, rows) = portal.terminal_initial_size();
+    let font_size = session.font_size.unwrap_or(portal.prefs.terminal_font_size);
+    let (cols, rows) = portal.terminal_size_for_font(font_size);
     if session.terminal.size() == (cols, rows) {
         return Task::none();
     }
@@ -316,6 +533,20 @@ pub(super) fn reconcile_active_terminal_size(portal: &Portal) -> Task<Message> {
     )))
 }
 
+fn split_active_terminal(portal: &mut Portal, axis: pane_grid::Axis) -> Task<Message> {
+    match portal.ui.active_view {
+        View::Terminal(session_id) => portal.update(Message::Session(SessionMessage::SplitPane(
+            session_id, axis,
+        ))),
+        _ => {
+            portal
+                .toast_manager
+                .push(Toast::warning("Open a terminal to split it"));
+            Task::none()
+        }
+    }
+}
+
 fn run_command_action(portal: &mut Portal, action: CommandAction) -> Task<Message> {
     portal.ui.command_palette_open = false;
     portal.ui.command_palette_query.clear();
@@ -330,12 +561,184 @@ fn run_command_action(portal: &mut Portal, action: CommandAction) -> Task<Messag
         CommandAction::QuickConnect => portal.update(Message::Host(HostMessage::QuickConnect)),
         CommandAction::NewHost => portal.update(Message::Host(HostMessage::Add)),
         CommandAction::LocalTerminal => portal.update(Message::Host(HostMessage::LocalTerminal)),
+        CommandAction::LocalTerminalProfile(id) => {
+            portal.update(Message::Host(HostMessage::LocalTerminalProfile(id)))
+        }
+        CommandAction::LocalTerminalTarget(target) => {
+            portal.update(Message::Host(HostMessage::LocalTerminalTarget(target)))
+        }
         CommandAction::ConnectHost(id) => portal.update(Message::Host(HostMessage::Connect(id))),
         CommandAction::RunSnippet(id) => portal.update(Message::Snippet(SnippetMessage::Run(id))),
+        CommandAction::ToggleMacroRecording => match portal.ui.active_view {
+            View::Terminal(session_id) => portal.update(Message::Session(
+                SessionMessage::MacroRecordToggle(session_id),
+            )),
+            _ => {
+                portal
+                    .toast_manager
+                    .push(Toast::warning("Open a terminal to record a macro"));
+                Task::none()
+            }
+        },
+        CommandAction::ToggleBroadcast => match portal.ui.active_view {
+            View::Terminal(session_id) => portal.update(Message::Session(
+                SessionMessage::ToggleBroadcast(session_id),
+            )),
+            _ => {
+                portal
+                    .toast_manager
+                    .push(Toast::warning("Open a terminal to broadcast input"));
+                Task::none()
+            }
+        },
+        CommandAction::SplitRight => split_active_terminal(portal, pane_grid::Axis::Vertical),
+        CommandAction::SplitDown => split_active_terminal(portal, pane_grid::Axis::Horizontal),
+        CommandAction::PlayMacro(id) => match portal.ui.active_view {
+            View::Terminal(session_id) => {
+                portal.update(Message::Session(SessionMessage::MacroPlay(session_id, id)))
+            }
+            _ => {
+                portal
+                    .toast_manager
+                    .push(Toast::warning("Open a terminal to play a macro"));
+                Task::none()
+            }
+        },
         CommandAction::PortalHubSync => portal.update(Message::Ui(UiMessage::PortalHubSyncNow)),
+        CommandAction::ExportSshConfig => {
+            portal.update(Message::Host(HostMessage::ExportSshConfig))
+        }
+        CommandAction::ForgetCredentials => {
+            portal.update(Message::Ui(UiMessage::ForgetCredentials))
+        }
+    }
+}
+
+fn run_switcher_target(portal: &mut Portal, target: SwitcherTarget) -> Task<Message> {
+    portal.ui.quick_switcher_open = false;
+    portal.ui.quick_switcher_query.clear();
+
+    let recent = &mut portal.ui.quick_switcher_recent;
+    recent.retain(|existing| *existing != target);
+    recent.insert(0, target);
+    recent.truncate(crate::views::quick_switcher::MAX_RECENT_TARGETS);
+
+    match target {
+        SwitcherTarget::Session(tab_id) => portal.update(Message::Tab(TabMessage::Select(tab_id))),
+        SwitcherTarget::Host(id) => portal.update(Message::Host(HostMessage::Connect(id))),
+        SwitcherTarget::Snippet(id) => portal.update(Message::Snippet(SnippetMessage::Run(id))),
     }
 }
 
+fn close_snippet_palette(portal: &mut Portal) {
+    portal.ui.snippet_palette_open = false;
+    // Give keyboard focus back to the terminal.
+    portal.ui.terminal_focus_token = portal.ui.terminal_focus_token.wrapping_add(1);
+}
+
+fn run_snippet_palette_entry(portal: &mut Portal, entry: SnippetPaletteEntry) -> Task<Message> {
+    close_snippet_palette(portal);
+    portal.ui.snippet_palette_query.clear();
+
+    let View::Terminal(session_id) = portal.ui.active_view else {
+        return Task::none();
+    };
+    match entry {
+        SnippetPaletteEntry::Snippet(id) => {
+            portal.update(Message::Snippet(SnippetMessage::Insert(session_id, id)))
+        }
+        SnippetPaletteEntry::Command(command) => portal.update(Message::Session(
+            SessionMessage::InsertText(session_id, command),
+        )),
+    }
+}
+
+/// Turn a finished host-grid drag into a host or group move.
+fn handle_host_grid_drop(portal: &mut Portal) -> Task<Message> {
+    let Some(HostGridDrag {
+        item,
+        target: Some(target),
+    }) = portal.ui.host_grid_drag.take()
+    else {
+        return Task::none();
+    };
+    let hosts = &portal.config.hosts;
+
+    let message = match (item, target) {
+        (HostGridDragItem::Host(host_id), HostGridDropTarget::Host(other_id)) => {
+            if host_id == other_id {
+                return Task::none();
+            }
+            let Some(other) = hosts.find_host(other_id) else {
+                return Task::none();
+            };
+            let index = hosts
+                .hosts_in_group(other.group_id)
+                .iter()
+                .position(|host| host.id == other_id)
+                .unwrap_or(usize::MAX);
+            HostMessage::HostMoved {
+                host_id,
+                group_id: other.group_id,
+                index,
+            }
+        }
+        (
+            HostGridDragItem::Host(host_id),
+            HostGridDropTarget::Group(group_id) | HostGridDropTarget::IntoGroup(group_id),
+        ) => HostMessage::HostMoved {
+            host_id,
+            group_id: Some(group_id),
+            index: usize::MAX,
+        },
+        (HostGridDragItem::Host(host_id), HostGridDropTarget::Ungrouped) => {
+            HostMessage::HostMoved {
+                host_id,
+                group_id: None,
+                index: usize::MAX,
+            }
+        }
+        (HostGridDragItem::Group(group_id), HostGridDropTarget::Group(other_id)) => {
+            if group_id == other_id {
+                return Task::none();
+            }
+            let Some(other) = hosts.groups.iter().find(|group| group.id == other_id) else {
+                return Task::none();
+            };
+            // A group with a missing parent is listed at the top level
+            let parent_id = other
+                .parent_id
+                .filter(|parent_id| hosts.groups.iter().any(|group| group.id == *parent_id));
+            let index = hosts
+                .child_groups(parent_id)
+                .iter()
+                .position(|group| group.id == other_id)
+                .unwrap_or(usize::MAX);
+            HostMessage::GroupReordered {
+                group_id,
+                parent_id,
+                index,
+            }
+        }
+        (HostGridDragItem::Group(group_id), HostGridDropTarget::IntoGroup(parent_id)) => {
+            HostMessage::GroupReordered {
+                group_id,
+                parent_id: Some(parent_id),
+                index: usize::MAX,
+            }
+        }
+        (HostGridDragItem::Group(group_id), HostGridDropTarget::Ungrouped) => {
+            HostMessage::GroupReordered {
+                group_id,
+                parent_id: None,
+                index: usize::MAX,
+            }
+        }
+        (HostGridDragItem::Group(_), HostGridDropTarget::Host(_)) => return Task::none(),
+    };
+    Task::done(Message::Host(message))
+}
+
 fn handle_sidebar_item_select(portal: &mut Portal, item: SidebarMenuItem) -> Task<Message> {
     // Auto-close pristine SFTP tab when navigating away (not when staying on SFTP).
     if item != SidebarMenuItem::Sftp
@@ -401,6 +804,15 @@ mod tests {
     use super::*;
     use uuid::Uuid;
 
+    #[test]
+    fn omnibox_treats_addresses_as_connection_targets() {
+        assert!(looks_like_connection_target("root@web1"));
+        assert!(looks_like_connection_target("ssh://web1:2222"));
+        assert!(looks_like_connection_target("[::1]:2222"));
+        assert!(!looks_like_connection_target("web"));
+        assert!(!looks_like_connection_target("prod db"));
+    }
+
     #[test]
     fn responsive_resize_preserves_auto_hidden_terminal_sidebar() {
         assert!(!should_apply_responsive_sidebar_state(
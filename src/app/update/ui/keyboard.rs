@@ -8,10 +8,11 @@ use iced::{Rectangle, Task};
 
 use crate::app::ActiveDialog;
 use crate::app::{FocusSection, Portal, VaultModal, View};
-use crate::keybindings::AppAction;
+use crate::config::settings::VncKeyboardMode;
+use crate::keybindings::{AppAction, Keybinding};
 use crate::message::{
-    DialogMessage, HistoryMessage, HostMessage, Message, SearchMessage, SessionMessage,
-    SftpMessage, SidebarMenuItem, TabMessage, UiMessage, VncMessage,
+    DialogMessage, FileViewerMessage, HistoryMessage, HostMessage, Message, SearchMessage,
+    SessionMessage, SftpMessage, SidebarMenuItem, TabMessage, UiMessage, VncMessage,
 };
 use crate::sftp::FileEntry;
 use crate::ssh::host_key_verification::HostKeyVerificationResponse;
@@ -19,7 +20,9 @@ use crate::views::dialogs::host_dialog::host_dialog_field_id;
 use crate::views::dialogs::portal_hub_dialogs::{
     PortalHubOnboardingField, portal_hub_onboarding_field_from_id,
 };
-use crate::views::sftp::PaneId;
+use crate::views::sftp::state::CursorSelection;
+use crate::views::sftp::{ContextMenuAction, PaneId};
+use crate::views::snippet_palette::{self, SnippetPaletteItem};
 use crate::views::toast::Toast;
 
 pub(super) fn handle_key_released(
@@ -28,12 +31,22 @@ pub(super) fn handle_key_released(
     modifiers: keyboard::Modifiers,
 ) -> Task<Message> {
     if let View::VncViewer(session_id) = portal.ui.active_view {
-        let passthrough = portal
+        let Some((mode, view_only)) = portal
             .vnc_sessions
             .get(&session_id)
-            .map(|v| v.keyboard_passthrough)
-            .unwrap_or(false);
-        let effective_key = if passthrough {
+            .map(|v| (v.keyboard_mode, v.view_only))
+        else {
+            return Task::none();
+        };
+        // View-only sessions send no input, so skip keysym translation
+        if view_only {
+            return Task::none();
+        }
+        // Unicode characters were pressed and released together on key press
+        if mode == VncKeyboardMode::Unicode && matches!(key, Key::Character(_)) {
+            return Task::none();
+        }
+        let effective_key = if mode == VncKeyboardMode::Passthrough {
             if let Key::Character(c) = &key {
                 Key::Character(c.to_lowercase().into())
             } else {
@@ -67,6 +80,22 @@ pub(super) fn handle_keyboard_event(
     modifiers: keyboard::Modifiers,
     shortcut_key: Option<char>,
 ) -> Task<Message> {
+    // Recording a shortcut on the settings page takes the next key press;
+    // modifiers alone wait for the key they are held with
+    if let Some(action) = portal.ui.keybinding_capture
+        && matches!(portal.ui.active_view, View::Settings)
+    {
+        if let Key::Named(keyboard::key::Named::Escape) = key {
+            return Task::done(Message::Ui(UiMessage::KeybindingRecordCancel));
+        }
+        return match Keybinding::from_key_event(&key, &modifiers) {
+            Some(binding) => {
+                Task::done(Message::Ui(UiMessage::KeybindingRecorded(action, binding)))
+            }
+            None => Task::none(),
+        };
+    }
+
     if portal.ui.command_palette_open {
         match &key {
             Key::Named(keyboard::key::Named::Escape) => {
@@ -76,6 +105,10 @@ pub(super) fn handle_keyboard_event(
                 let commands = crate::views::command_palette::available_commands(
                     &portal.config.hosts,
                     &portal.config.snippets,
+                    portal.active_session_host(),
+                    &portal.config.macros,
+                    &portal.config.terminal_profiles,
+                    &portal.ui.local_launch_targets,
                     portal.prefs.portal_hub.sync_configured(),
                 );
                 if let Some(action) = crate::views::command_palette::first_matching_action(
@@ -90,6 +123,65 @@ pub(super) fn handle_keyboard_event(
         }
     }
 
+    if portal.ui.quick_switcher_open {
+        match &key {
+            Key::Named(keyboard::key::Named::Escape) => {
+                return Task::done(Message::Ui(UiMessage::QuickSwitcherClose));
+            }
+            Key::Named(keyboard::key::Named::Enter) => {
+                let items = crate::views::quick_switcher::available_items(
+                    &portal.tabs,
+                    &portal.config.hosts,
+                    &portal.config.snippets,
+                    portal.active_session_host(),
+                );
+                if let Some(target) = crate::views::quick_switcher::first_matching_target(
+                    &items,
+                    &portal.ui.quick_switcher_query,
+                    &portal.ui.quick_switcher_recent,
+                ) {
+                    return Task::done(Message::Ui(UiMessage::QuickSwitcherRun(target)));
+                }
+                return Task::none();
+            }
+            _ => {}
+        }
+    }
+
+    if portal.ui.snippet_palette_open {
+        match &key {
+            Key::Named(keyboard::key::Named::Escape) => {
+                return Task::done(Message::Ui(UiMessage::SnippetPaletteClose));
+            }
+            Key::Named(keyboard::key::Named::ArrowUp) => {
+                portal.ui.snippet_palette_selected =
+                    portal.ui.snippet_palette_selected.saturating_sub(1);
+                return Task::none();
+            }
+            Key::Named(keyboard::key::Named::ArrowDown) => {
+                let count = snippet_palette::filter_items(
+                    &snippet_palette_items(portal),
+                    &portal.ui.snippet_palette_query,
+                )
+                .len();
+                portal.ui.snippet_palette_selected =
+                    (portal.ui.snippet_palette_selected + 1).min(count.saturating_sub(1));
+                return Task::none();
+            }
+            Key::Named(keyboard::key::Named::Enter) => {
+                if let Some(entry) = snippet_palette::selected_entry(
+                    &snippet_palette_items(portal),
+                    &portal.ui.snippet_palette_query,
+                    portal.ui.snippet_palette_selected,
+                ) {
+                    return Task::done(Message::Ui(UiMessage::SnippetPaletteRun(entry)));
+                }
+                return Task::none();
+            }
+            _ => {}
+        }
+    }
+
     // Priority 1: Dialog open - handle dialog-specific keyboard navigation
     if portal.dialogs.is_open() {
         if let Some(task) =
@@ -121,13 +213,34 @@ pub(super) fn handle_keyboard_event(
         return Task::none();
     }
 
+    if let View::DualSftp(tab_id) = portal.ui.active_view
+        && let Some(pane_id) = portal.sftp.get_tab(tab_id).and_then(|state| {
+            [PaneId::Left, PaneId::Right]
+                .into_iter()
+                .find(|&pane_id| state.pane(pane_id).path_input.is_some())
+        })
+    {
+        // The path bar input handles editing and Enter; Tab completes instead
+        // of switching panes.
+        return match key {
+            Key::Named(keyboard::key::Named::Escape) => {
+                Task::done(Message::Sftp(SftpMessage::PathEditCancel(tab_id, pane_id)))
+            }
+            Key::Named(keyboard::key::Named::Tab) => Task::done(Message::Sftp(
+                SftpMessage::PathInputComplete(tab_id, pane_id),
+            )),
+            _ => Task::none(),
+        };
+    }
+
     // Priority 2: VNC viewer — forward all keys to remote (except Ctrl+Shift combos for UI)
     if let View::VncViewer(session_id) = portal.ui.active_view {
-        let passthrough = portal
+        let mode = portal
             .vnc_sessions
             .get(&session_id)
-            .map(|v| v.keyboard_passthrough)
-            .unwrap_or(false);
+            .map(|v| v.keyboard_mode)
+            .unwrap_or_default();
+        let passthrough = mode == VncKeyboardMode::Passthrough;
 
         // Ctrl+Shift+Escape: escape hatch to toggle keyboard passthrough off
         if passthrough
@@ -176,6 +289,7 @@ pub(super) fn handle_keyboard_event(
                 AppAction::NewTab,
                 AppAction::NextSession,
                 AppAction::PreviousSession,
+                AppAction::VncViewOnly,
             ],
         ) {
             return task;
@@ -205,6 +319,16 @@ pub(super) fn handle_keyboard_event(
             }
         }
 
+        // View-only sessions keep local shortcuts but send no keys, so stop
+        // before any keysym translation
+        if portal
+            .vnc_sessions
+            .get(&session_id)
+            .is_none_or(|vnc| vnc.view_only)
+        {
+            return Task::none();
+        }
+
         // Forward modifier key presses (Shift, Ctrl, Alt) to VNC so the remote
         // sees the correct modifier state even in non-passthrough mode.
         if let Key::Named(named) = &key {
@@ -224,11 +348,29 @@ pub(super) fn handle_keyboard_event(
             }
         }
 
+        // Unicode mode sends the typed character itself, so the server needs
+        // no matching layout. The release event may report a different
+        // character (e.g. Shift let go first), so press and release together.
+        if mode == VncKeyboardMode::Unicode
+            && !(modifiers.control() && modifiers.shift())
+            && let Some(keysym) = crate::vnc::keysym::unicode_keysym(&key)
+        {
+            tracing::debug!(
+                "VNC unicode key press: key={:?} keysym=0x{:08X}",
+                key,
+                keysym
+            );
+            return Task::done(Message::Vnc(VncMessage::SendSpecialKeys {
+                session_id,
+                keysyms: vec![keysym],
+            }));
+        }
+
         // Forward remaining keys to VNC (except Ctrl+Shift combos for tab management etc.)
         // When Shift is held, Iced may still report lowercase characters on some
         // platforms. Uppercase single ASCII letters so the correct keysym is sent.
-        // NOTE: The Shift+key mapping assumes US QWERTY layout. Passthrough mode
-        // is recommended for non-US layouts since it delegates layout handling to the server.
+        // NOTE: The Shift+key mapping assumes US QWERTY layout. Non-US layouts
+        // should use Unicode mode, or passthrough to let the server handle it.
         if !(modifiers.control() && modifiers.shift()) {
             let effective_key = if modifiers.shift() {
                 shift_modified_key(&key)
@@ -267,6 +409,36 @@ pub(super) fn handle_keyboard_event(
             )));
         }
 
+        if let Some(task) = handle_configured_actions(
+            portal,
+            &key,
+            &modifiers,
+            &[
+                AppAction::ClearScrollback,
+                AppAction::ResetTerminal,
+                AppAction::SplitRight,
+                AppAction::SplitDown,
+                AppAction::FocusNextPane,
+                AppAction::SnippetPalette,
+                AppAction::ZoomIn,
+                AppAction::ZoomOut,
+                AppAction::ZoomReset,
+            ],
+        ) {
+            return task;
+        }
+
+        if let Some(macro_id) = portal
+            .config
+            .macros
+            .find_by_keybinding(&key, &modifiers)
+            .map(|terminal_macro| terminal_macro.id)
+        {
+            return portal.update(Message::Session(SessionMessage::MacroPlay(
+                session_id, macro_id,
+            )));
+        }
+
         let search_open = portal
             .sessions
             .get(session_id)
@@ -291,6 +463,36 @@ pub(super) fn handle_keyboard_event(
         }
     }
 
+    // File viewer go-to-line: Ctrl+G opens the bar, Escape closes it.
+    // Alt+Z toggles word wrap (the focused editor handles it itself).
+    if let View::FileViewer(viewer_id) = portal.ui.active_view {
+        if modifiers.alt()
+            && !modifiers.command()
+            && !modifiers.shift()
+            && character_shortcut_key(&key).is_some_and(|c| c.eq_ignore_ascii_case(&'z'))
+        {
+            return portal.update(Message::FileViewer(FileViewerMessage::ToggleWordWrap));
+        }
+        if modifiers.command()
+            && !modifiers.shift()
+            && character_shortcut_key(&key).is_some_and(|c| c.eq_ignore_ascii_case(&'g'))
+        {
+            return portal.update(Message::FileViewer(FileViewerMessage::GotoLineOpen(
+                viewer_id,
+            )));
+        }
+        if let Key::Named(keyboard::key::Named::Escape) = key
+            && portal
+                .file_viewers
+                .get(viewer_id)
+                .is_some_and(|viewer| viewer.goto_line.open)
+        {
+            return portal.update(Message::FileViewer(FileViewerMessage::GotoLineClose(
+                viewer_id,
+            )));
+        }
+    }
+
     // Priority 3: Terminal captured - only Ctrl+Escape exits
     if portal.ui.terminal_captured {
         // Ctrl+Escape exits captured mode
@@ -319,7 +521,12 @@ pub(super) fn handle_keyboard_event(
 
     // Priority 3: Global shortcuts (always work unless terminal captured)
     match (&key, modifiers.control(), modifiers.shift()) {
+        // Ctrl+K - Quick switcher
         (Key::Character(c), true, false) if c.as_str() == "k" || c.as_str() == "K" => {
+            return Task::done(Message::Ui(UiMessage::QuickSwitcherToggle));
+        }
+        // Ctrl+Shift+P - Command palette
+        (Key::Character(c), true, true) if c.as_str() == "p" || c.as_str() == "P" => {
             return Task::done(Message::Ui(UiMessage::CommandPaletteToggle));
         }
         // F1 - Focus Sidebar
@@ -339,12 +546,20 @@ pub(super) fn handle_keyboard_event(
         }
         // Escape - close context menus, or exit terminal capture indication
         (Key::Named(keyboard::key::Named::Escape), _, _) => {
+            if let Some((tab_id, pane_id)) = sftp_filter_to_clear(portal) {
+                return portal.update(Message::Sftp(SftpMessage::FilterChanged(
+                    tab_id,
+                    pane_id,
+                    String::new(),
+                )));
+            }
             // Close any open SFTP context menu or dialog
             for tab_state in portal.sftp.tab_values_mut() {
                 tab_state.hide_context_menu();
                 tab_state.close_dialog();
             }
             portal.ui.tab_context_menu.hide();
+            portal.ui.host_context_menu.hide();
             return Task::none();
         }
         // Ctrl+Shift+K - Install SSH key on remote server
@@ -384,6 +599,14 @@ pub(super) fn handle_keyboard_event(
     }
 }
 
+fn snippet_palette_items(portal: &Portal) -> Vec<SnippetPaletteItem> {
+    snippet_palette::available_items(
+        &portal.config.snippets,
+        portal.active_session_host(),
+        &portal.config.snippet_history,
+    )
+}
+
 fn handle_configured_actions(
     portal: &mut Portal,
     key: &Key,
@@ -575,11 +798,14 @@ fn handle_dialog_keyboard(
                 == crate::views::dialogs::host_dialog::AuthMethodChoice::PublicKey;
 
             // Build list of focusable fields based on current state
-            let focusable: Vec<usize> = if has_key_path {
+            let mut focusable: Vec<usize> = if has_key_path {
                 vec![0, 1, 2, 3, 5, 6, 7] // Include key path field
             } else {
                 vec![0, 1, 2, 3, 6, 7] // Skip key path field
             };
+            if state.protocol == crate::views::dialogs::host_dialog::ProtocolChoice::Ssh {
+                focusable.push(8); // SFTP start directory
+            }
 
             match key {
                 Key::Named(keyboard::key::Named::Escape) => {
@@ -948,7 +1174,8 @@ fn handle_history_keyboard(
     key: &Key,
     _modifiers: &keyboard::Modifiers,
 ) -> Task<Message> {
-    let entry_count = portal.config.history.entries.len();
+    let entries = portal.config.history.filtered(&portal.ui.history_filter);
+    let entry_count = entries.len();
     if entry_count == 0 {
         return Task::none();
     }
@@ -979,10 +1206,10 @@ fn handle_history_keyboard(
         }
         Key::Named(keyboard::key::Named::Enter | keyboard::key::Named::Space) => {
             if let Some(idx) = portal.ui.history_focus_index
-                && let Some(entry) = portal.config.history.entries.get(idx)
+                && let Some(entry) = entries.get(idx)
             {
-                let host_id = entry.host_id;
-                return portal.update(Message::History(HistoryMessage::Reconnect(host_id)));
+                let entry_id = entry.id;
+                return portal.update(Message::History(HistoryMessage::Reconnect(entry_id)));
             }
         }
         _ => {}
@@ -995,7 +1222,7 @@ fn handle_sftp_keyboard(
     portal: &mut Portal,
     tab_id: uuid::Uuid,
     key: &Key,
-    _modifiers: &keyboard::Modifiers,
+    modifiers: &keyboard::Modifiers,
 ) -> Task<Message> {
     use iced::widget::scrollable;
 
@@ -1009,9 +1236,8 @@ fn handle_sftp_keyboard(
         PaneId::Right => &mut state.right_pane,
     };
 
-    // Get visible entries (respecting filter and show_hidden)
-    let visible = pane_state.visible_entries();
-    let visible_count = visible.len();
+    // Row count respecting filter and show_hidden
+    let visible_count = pane_state.visible_entry_count();
 
     // Height of each file row for scroll calculation
     const ROW_HEIGHT: f32 = 35.0;
@@ -1024,79 +1250,46 @@ fn handle_sftp_keyboard(
                 PaneId::Right => PaneId::Left,
             };
         }
-        Key::Named(keyboard::key::Named::ArrowUp) if visible_count > 0 => {
-            // Find current position in visible entries
-            let current_visible_pos = pane_state
-                .last_selected_index
-                .and_then(|idx| visible.iter().position(|(i, _)| *i == idx))
-                .unwrap_or(0);
-            let new_visible_pos = current_visible_pos.saturating_sub(1);
-            let new_idx = visible[new_visible_pos].0;
-
-            pane_state.selected_indices.clear();
-            pane_state.selected_indices.insert(new_idx);
-            pane_state.last_selected_index = Some(new_idx);
-
-            // Scroll to keep selection visible
-            let scroll_offset = new_visible_pos as f32 * ROW_HEIGHT;
-            return iced::widget::operation::scroll_to(
-                pane_state.scrollable_id.clone(),
-                scrollable::AbsoluteOffset {
-                    x: 0.0,
-                    y: scroll_offset,
-                },
-            );
-        }
-        Key::Named(keyboard::key::Named::ArrowDown) if visible_count > 0 => {
-            // Find current position in visible entries
-            let current_visible_pos = pane_state
-                .last_selected_index
-                .and_then(|idx| visible.iter().position(|(i, _)| *i == idx))
-                .unwrap_or(0);
-            let new_visible_pos = (current_visible_pos + 1).min(visible_count - 1);
-            let new_idx = visible[new_visible_pos].0;
-
-            pane_state.selected_indices.clear();
-            pane_state.selected_indices.insert(new_idx);
-            pane_state.last_selected_index = Some(new_idx);
-
-            // Scroll to keep selection visible
-            let scroll_offset = new_visible_pos as f32 * ROW_HEIGHT;
-            return iced::widget::operation::scroll_to(
-                pane_state.scrollable_id.clone(),
-                scrollable::AbsoluteOffset {
-                    x: 0.0,
-                    y: scroll_offset,
-                },
-            );
-        }
-        Key::Named(keyboard::key::Named::Home) if visible_count > 0 => {
-            let new_idx = visible[0].0;
-            pane_state.selected_indices.clear();
-            pane_state.selected_indices.insert(new_idx);
-            pane_state.last_selected_index = Some(new_idx);
+        Key::Named(
+            named @ (keyboard::key::Named::ArrowUp
+            | keyboard::key::Named::ArrowDown
+            | keyboard::key::Named::Home
+            | keyboard::key::Named::End),
+        ) if visible_count > 0 => {
+            let current = pane_state.cursor_position();
+            let position = match named {
+                keyboard::key::Named::ArrowUp => current.map_or(0, |pos| pos.saturating_sub(1)),
+                keyboard::key::Named::ArrowDown => {
+                    current.map_or(0, |pos| (pos + 1).min(visible_count - 1))
+                }
+                keyboard::key::Named::Home => 0,
+                _ => visible_count - 1,
+            };
+            let selection = if modifiers.shift() {
+                CursorSelection::Extend
+            } else if modifiers.command() {
+                CursorSelection::Keep
+            } else {
+                CursorSelection::Replace
+            };
+            pane_state.move_cursor(position, selection);
 
-            // Scroll to top
-            return iced::widget::operation::scroll_to(
-                pane_state.scrollable_id.clone(),
-                scrollable::AbsoluteOffset { x: 0.0, y: 0.0 },
-            );
+            // Scroll only as far as needed to keep the cursor row visible
+            if let Some(offset) = pane_state.reveal_offset(position, ROW_HEIGHT) {
+                return iced::widget::operation::scroll_to(
+                    pane_state.scrollable_id.clone(),
+                    scrollable::AbsoluteOffset { x: 0.0, y: offset },
+                );
+            }
         }
-        Key::Named(keyboard::key::Named::End) if visible_count > 0 => {
-            let new_idx = visible[visible_count - 1].0;
-            pane_state.selected_indices.clear();
-            pane_state.selected_indices.insert(new_idx);
-            pane_state.last_selected_index = Some(new_idx);
-
-            // Scroll to bottom
-            let scroll_offset = (visible_count - 1) as f32 * ROW_HEIGHT;
-            return iced::widget::operation::scroll_to(
-                pane_state.scrollable_id.clone(),
-                scrollable::AbsoluteOffset {
-                    x: 0.0,
-                    y: scroll_offset,
-                },
-            );
+        Key::Named(keyboard::key::Named::Space) if modifiers.command() => {
+            pane_state.toggle_cursor_selection();
+        }
+        Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("h") => {
+            return portal.update(Message::Sftp(SftpMessage::ToggleShowHidden(
+                tab_id,
+                active_pane,
+            )));
         }
         Key::Named(keyboard::key::Named::ArrowLeft) => {
             // Need to re-borrow state since we're not inside the match
@@ -1120,16 +1313,27 @@ fn handle_sftp_keyboard(
             return Task::none();
         }
         Key::Named(keyboard::key::Named::Enter) => {
-            // Navigate into directory or activate file
-            if let Some(path) =
-                sftp_enter_navigation_path(pane_state.last_selected_index, &pane_state.entries)
-            {
+            // Navigate into directory or open file under the cursor
+            let cursor = pane_state.cursor_index.or(pane_state.last_selected_index);
+            if let Some(path) = sftp_enter_navigation_path(cursor, &pane_state.entries) {
                 return portal.update(Message::Sftp(SftpMessage::PaneNavigate(
                     tab_id,
                     active_pane,
                     path,
                 )));
             }
+            if let Some(index) = cursor
+                && pane_state
+                    .entries
+                    .get(index)
+                    .is_some_and(|entry| !entry.is_dir && !entry.is_parent())
+            {
+                pane_state.select(index);
+                return portal.update(Message::Sftp(SftpMessage::ContextMenuAction(
+                    tab_id,
+                    ContextMenuAction::Open,
+                )));
+            }
         }
         Key::Named(keyboard::key::Named::Backspace) => {
             // Navigate to parent
@@ -1147,12 +1351,26 @@ fn handle_sftp_keyboard(
     Task::none()
 }
 
+/// Active SFTP pane whose filter Escape should clear: only when no context
+/// menu or dialog in that tab would take the key first.
+fn sftp_filter_to_clear(portal: &Portal) -> Option<(uuid::Uuid, PaneId)> {
+    let View::DualSftp(tab_id) = portal.ui.active_view else {
+        return None;
+    };
+    let tab_state = portal.sftp.get_tab(tab_id)?;
+    if tab_state.context_menu.visible || tab_state.dialog.is_some() {
+        return None;
+    }
+    let pane_id = tab_state.active_pane;
+    (!tab_state.pane(pane_id).filter_text.is_empty()).then_some((tab_id, pane_id))
+}
+
 fn sftp_enter_navigation_path(
     selected_index: Option<usize>,
     entries: &[FileEntry],
 ) -> Option<std::path::PathBuf> {
     let entry = entries.get(selected_index?)?;
-    entry.is_navigable_dir().then(|| entry.path.clone())
+    entry.navigation_path().map(std::path::Path::to_path_buf)
 }
 
 #[cfg(test)]
@@ -1166,6 +1384,7 @@ mod tests {
             path: PathBuf::from(name),
             is_dir,
             is_symlink,
+            symlink_target: None,
             size: 0,
             modified: None,
         }
@@ -1201,7 +1420,21 @@ mod tests {
     }
 
     #[test]
-    fn sftp_enter_navigation_path_rejects_symlinked_directory() {
+    fn sftp_enter_navigation_path_follows_resolved_symlinked_directory() {
+        let mut link = sftp_entry("linked-dir", true, true);
+        link.symlink_target = Some(crate::sftp::SymlinkTarget {
+            target: "../real-dir".to_string(),
+            resolved: Some(PathBuf::from("/srv/real-dir")),
+        });
+
+        assert_eq!(
+            sftp_enter_navigation_path(Some(0), &[link]),
+            Some(PathBuf::from("/srv/real-dir"))
+        );
+    }
+
+    #[test]
+    fn sftp_enter_navigation_path_rejects_unresolved_symlinked_directory() {
         let entries = vec![sftp_entry("linked-dir", true, true)];
 
         assert_eq!(sftp_enter_navigation_path(Some(0), &entries), None);
@@ -0,0 +1,221 @@
+//! Saving the open tabs and reopening them on the next start
+
+use std::path::PathBuf;
+
+use iced::Task;
+use uuid::Uuid;
+
+use crate::app::Portal;
+use crate::app::managers::RestoreStep;
+use crate::app::services::connection;
+use crate::config::{OpenSessionsState, SavedSftpPane, SavedSftpTab};
+use crate::message::{Message, SftpMessage};
+use crate::views::sftp::state::FilePaneState;
+use crate::views::sftp::{DualPaneSftpState, PaneId, PaneSource};
+use crate::views::tabs::{Tab, TabType};
+use crate::views::toast::{Toast, ToastAction};
+
+/// Offer to reopen the tabs saved by the previous run.
+pub(crate) fn offer_on_startup(portal: &mut Portal) {
+    if !portal.prefs.restore_sessions {
+        return;
+    }
+    let mut state = match OpenSessionsState::load() {
+        Ok(state) => state,
+        Err(e) => {
+            tracing::warn!("Failed to load open sessions: {}", e);
+            return;
+        }
+    };
+    state.retain_known_hosts(|host_id| portal.config.hosts.find_host(host_id).is_some());
+    if state.is_empty() {
+        return;
+    }
+
+    let count = state.tab_count();
+    portal.session_restore.set_offer(state);
+    portal.toast_manager.push(
+        Toast::warning(format!(
+            "Reopen {} tab{} from your last session? Click to reconnect.",
+            count,
+            if count == 1 { "" } else { "s" }
+        ))
+        .persistent()
+        .action(ToastAction::RestoreSessions),
+    );
+}
+
+/// Write the open tabs to `open_sessions.toml` when they have changed.
+pub(crate) fn save_open_sessions(portal: &mut Portal) {
+    if !portal.prefs.restore_sessions || portal.session_restore.is_restoring() {
+        return;
+    }
+
+    let state = snapshot(portal);
+    if portal.session_restore.has_offer() {
+        // Keep the saved tabs until the user opens something of their own.
+        if state.is_empty() {
+            return;
+        }
+        portal.session_restore.withdraw_offer();
+        portal
+            .toast_manager
+            .dismiss_action(ToastAction::RestoreSessions);
+    }
+
+    if portal.session_restore.should_save(&state)
+        && let Err(e) = state.save()
+    {
+        tracing::warn!("Failed to save open sessions: {}", e);
+    }
+}
+
+/// Forget the saved tabs, e.g. when the setting is turned off.
+pub(super) fn clear_open_sessions(portal: &mut Portal) {
+    portal.session_restore.withdraw_offer();
+    portal.session_restore.reset_saved();
+    portal
+        .toast_manager
+        .dismiss_action(ToastAction::RestoreSessions);
+    if let Err(e) = OpenSessionsState::clear() {
+        tracing::warn!("Failed to clear open sessions: {}", e);
+    }
+}
+
+pub(super) fn accept(portal: &mut Portal) -> Task<Message> {
+    let count = portal.session_restore.accept_offer();
+    if count == 0 {
+        return Task::none();
+    }
+    tick(portal)
+}
+
+/// Run the next restore step once the previous connect has settled.
+pub(super) fn tick(portal: &mut Portal) -> Task<Message> {
+    if portal.pending_connect.is_some() || portal.dialogs.is_open() {
+        return Task::none();
+    }
+    let sftp = &portal.sftp;
+    let Some(step) = portal
+        .session_restore
+        .next_step(|tab_id, pane_id, host_id| {
+            sftp.pending_connection_matches(tab_id, pane_id, host_id)
+        })
+    else {
+        return Task::none();
+    };
+
+    match step {
+        RestoreStep::Terminal(host_id) => match portal.config.hosts.find_host(host_id).cloned() {
+            Some(host) => portal.connect_to_host_new_session(&host),
+            None => Task::none(),
+        },
+        RestoreStep::SftpTab(saved) => open_sftp_tab(portal, saved),
+        RestoreStep::SftpConnect {
+            tab_id,
+            pane_id,
+            host_id,
+        } => {
+            let host = portal.config.hosts.find_host(host_id).cloned();
+            match host {
+                Some(host) if portal.sftp.contains_tab(tab_id) => {
+                    portal.session_restore.await_sftp(
+                        tab_id,
+                        pane_id,
+                        host_id,
+                        connection::connect_timeout(),
+                    );
+                    portal.connect_sftp_for_pane(tab_id, pane_id, &host)
+                }
+                _ => Task::none(),
+            }
+        }
+        RestoreStep::SftpNavigate {
+            tab_id,
+            pane_id,
+            host_id,
+            path,
+        } => {
+            let connected =
+                portal
+                    .sftp
+                    .get_tab(tab_id)
+                    .is_some_and(|tab| match tab.pane(pane_id).source {
+                        PaneSource::Remote { session_id, .. } => {
+                            portal.sftp.connection_host(session_id) == Some(host_id)
+                        }
+                        PaneSource::Local => false,
+                    });
+            if connected {
+                portal.update(Message::Sftp(SftpMessage::PaneNavigate(
+                    tab_id, pane_id, path,
+                )))
+            } else {
+                Task::none()
+            }
+        }
+    }
+}
+
+fn open_sftp_tab(portal: &mut Portal, saved: SavedSftpTab) -> Task<Message> {
+    let tab_id = Uuid::new_v4();
+    let mut dual_state =
+        DualPaneSftpState::new_with_column_widths(tab_id, portal.prefs.sftp_column_widths.clone())
+            .with_show_hidden(portal.prefs.sftp_show_hidden);
+    for (pane_id, pane) in [(PaneId::Left, &saved.left), (PaneId::Right, &saved.right)] {
+        if pane.host_id.is_none() && !pane.path.as_os_str().is_empty() {
+            dual_state.pane_mut(pane_id).current_path = pane.path.clone();
+        }
+    }
+    portal.sftp.insert_tab(tab_id, dual_state);
+    portal
+        .tabs
+        .push(Tab::new_sftp(tab_id, "File Browser".to_string(), None));
+    portal.enter_sftp_view(tab_id);
+    portal.session_restore.queue_sftp_panes(
+        tab_id,
+        [(PaneId::Left, &saved.left), (PaneId::Right, &saved.right)],
+    );
+
+    let left_task = portal.load_dual_pane_directory(tab_id, PaneId::Left);
+    let right_task = portal.load_dual_pane_directory(tab_id, PaneId::Right);
+    Task::batch([left_task, right_task])
+}
+
+fn snapshot(portal: &Portal) -> OpenSessionsState {
+    let mut state = OpenSessionsState::default();
+    for tab in &portal.tabs {
+        match tab.tab_type {
+            TabType::Terminal => state.terminals.extend(tab.host_id),
+            TabType::Sftp => {
+                if let Some(dual_state) = portal.sftp.get_tab(tab.id) {
+                    state.sftp_tabs.push(SavedSftpTab {
+                        left: saved_pane(portal, dual_state.pane(PaneId::Left)),
+                        right: saved_pane(portal, dual_state.pane(PaneId::Right)),
+                    });
+                }
+            }
+            TabType::NewConnection | TabType::FileViewer | TabType::Vnc => {}
+        }
+    }
+    state
+}
+
+fn saved_pane(portal: &Portal, pane: &FilePaneState) -> SavedSftpPane {
+    match &pane.source {
+        PaneSource::Local => SavedSftpPane {
+            host_id: None,
+            path: pane.current_path.clone(),
+        },
+        PaneSource::Remote { session_id, .. } => match portal.sftp.connection_host(*session_id) {
+            Some(host_id) => SavedSftpPane {
+                host_id: Some(host_id),
+                path: pane.current_path.clone(),
+            },
+            None => SavedSftpPane {
+                host_id: None,
+                path: PathBuf::new(),
+            },
+        },
+    }
+}
@@ -1,24 +1,162 @@
 use iced::Task;
+use uuid::Uuid;
 
 use crate::app::Portal;
 use crate::app::services;
 use crate::config::settings::{
-    SettingsConfig, TERMINAL_SCROLL_SPEED_MAX, TERMINAL_SCROLL_SPEED_MIN,
+    MAX_CONNECT_TIMEOUT_SECS, MAX_CURSOR_BLINK_INTERVAL_MS, MAX_SCROLLBACK_LINES,
+    MIN_CURSOR_BLINK_INTERVAL_MS, MIN_SCROLLBACK_LINES, SettingsConfig, TERMINAL_SCROLL_SPEED_MAX,
+    TERMINAL_SCROLL_SPEED_MIN,
 };
 use crate::hub::sync::{
     ConflictChoice, LocalSyncProfile, PortalHubSyncService, SyncRunActivity, SyncRunOrigin,
     SyncRunResult,
 };
 use crate::hub::vault::HubVaultConfig;
+use crate::keybindings::KeybindingsConfig;
 use crate::message::{Message, UiMessage, VaultMessage};
+use crate::theme::import::read_color_scheme;
+use crate::theme::{CustomTheme, ThemeId, parse_hex_color};
 use crate::views::toast::Toast;
 
+/// The custom theme being edited, when one is selected
+fn selected_custom_theme(portal: &mut Portal) -> Option<&mut CustomTheme> {
+    let ThemeId::Custom(id) = portal.prefs.theme_id else {
+        return None;
+    };
+    portal
+        .prefs
+        .custom_themes
+        .iter_mut()
+        .find(|custom| custom.id == id)
+}
+
 pub(super) fn handle_settings_message(portal: &mut Portal, msg: UiMessage) -> Task<Message> {
     match msg {
         UiMessage::ThemeChange(theme_id) => {
             portal.prefs.theme_id = theme_id;
+            portal.ui.theme_color_inputs.clear();
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::CustomThemeCreate => {
+            // Start from whatever is on screen so small tweaks are easy
+            let custom = CustomTheme {
+                id: Uuid::new_v4(),
+                name: format!("Custom {}", portal.prefs.custom_themes.len() + 1),
+                colors: portal.current_theme(),
+            };
+            portal.prefs.theme_id = ThemeId::Custom(custom.id);
+            portal.prefs.custom_themes.push(custom);
+            portal
+                .terminal_panes
+                .set_custom_themes(&portal.prefs.custom_themes);
+            portal.ui.theme_color_inputs.clear();
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::CustomThemeRename(name) => {
+            if let Some(custom) = selected_custom_theme(portal) {
+                custom.name = name;
+                portal
+                    .terminal_panes
+                    .set_custom_themes(&portal.prefs.custom_themes);
+                save_settings_and_queue_sync(portal);
+            }
+        }
+        UiMessage::CustomThemeColorChanged(field, value) => {
+            let color = parse_hex_color(&value);
+            portal.ui.theme_color_inputs.insert(field, value);
+            if let Some(color) = color
+                && let Some(custom) = selected_custom_theme(portal)
+            {
+                field.set(&mut custom.colors, color);
+                portal
+                    .terminal_panes
+                    .set_custom_themes(&portal.prefs.custom_themes);
+                save_settings_and_queue_sync(portal);
+            }
+        }
+        UiMessage::CustomThemeImportRequested => {
+            return Task::perform(
+                async move {
+                    let path = rfd::FileDialog::new()
+                        .set_title("Import terminal color scheme")
+                        .add_filter("Color schemes", &["itermcolors", "yaml", "yml", "json"])
+                        .pick_file()?;
+                    Some(read_color_scheme(&path))
+                },
+                |result| match result {
+                    Some(result) => Message::Ui(UiMessage::CustomThemeImported(result)),
+                    None => Message::Noop,
+                },
+            );
+        }
+        UiMessage::CustomThemeImported(Ok(scheme)) => {
+            // Interface colors come from the current theme; the scheme only
+            // covers the terminal
+            let mut colors = portal.current_theme();
+            colors.terminal = scheme.terminal;
+            let custom = CustomTheme {
+                id: Uuid::new_v4(),
+                name: scheme.name,
+                colors,
+            };
+            portal
+                .toast_manager
+                .push(Toast::success(format!("Imported \"{}\"", custom.name)));
+            portal.prefs.theme_id = ThemeId::Custom(custom.id);
+            portal.prefs.custom_themes.push(custom);
+            portal.ui.theme_color_inputs.clear();
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::CustomThemeImported(Err(error)) => {
+            portal.toast_manager.push(Toast::error(format!(
+                "Could not import color scheme: {}",
+                error
+            )));
+        }
+        UiMessage::KeybindingRecordStart(action) => {
+            portal.ui.keybinding_capture = Some(action);
+        }
+        UiMessage::KeybindingRecordCancel => {
+            portal.ui.keybinding_capture = None;
+        }
+        UiMessage::KeybindingRecorded(action, binding) => {
+            portal.ui.keybinding_capture = None;
+            portal
+                .prefs
+                .keybindings
+                .set_binding(action, binding.clone());
+            let conflicts = portal.prefs.keybindings.conflicts(action);
+            if !conflicts.is_empty() {
+                let names: Vec<&str> = conflicts.iter().map(|other| other.label()).collect();
+                portal.toast_manager.push(Toast::warning(format!(
+                    "{} is also bound to {}",
+                    binding,
+                    names.join(", ")
+                )));
+            }
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::KeybindingReset(action) => {
+            portal.prefs.keybindings.reset(action);
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::KeybindingsResetAll => {
+            portal.ui.keybinding_capture = None;
+            portal.prefs.keybindings = KeybindingsConfig::default();
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::CustomThemeDelete => {
+            if let ThemeId::Custom(id) = portal.prefs.theme_id {
+                portal.prefs.custom_themes.retain(|custom| custom.id != id);
+                portal
+                    .terminal_panes
+                    .set_custom_themes(&portal.prefs.custom_themes);
+                portal.prefs.theme_id = ThemeId::default();
+                portal.ui.theme_color_inputs.clear();
+                save_settings_and_queue_sync(portal);
+            }
+        }
         UiMessage::FontChange(font) => {
             tracing::info!("Font changed");
             portal.prefs.terminal_font = font;
@@ -35,6 +173,12 @@ pub(super) fn handle_settings_message(portal: &mut Portal, msg: UiMessage) -> Ta
                 speed.clamp(TERMINAL_SCROLL_SPEED_MIN, TERMINAL_SCROLL_SPEED_MAX);
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::ScrollbackLinesChange(lines) => {
+            // Applies to terminals opened from now on; open sessions keep
+            // the history size they were created with.
+            portal.prefs.scrollback_lines = lines.clamp(MIN_SCROLLBACK_LINES, MAX_SCROLLBACK_LINES);
+            save_settings_and_queue_sync(portal);
+        }
         UiMessage::UiScaleChange(scale) => {
             portal.prefs.ui_scale_override = Some(scale.clamp(0.8, 1.5));
             save_settings_and_queue_sync(portal);
@@ -61,18 +205,80 @@ pub(super) fn handle_settings_message(portal: &mut Portal, msg: UiMessage) -> Ta
             portal.config.snippet_history.redact_output = redact_output;
             portal.save_snippet_history();
         }
+        UiMessage::HistoryRedactExport(redact) => {
+            portal.config.history.redact_export = redact;
+            if let Err(e) = portal.config.history.save() {
+                tracing::error!("Failed to save history: {}", e);
+            }
+        }
         UiMessage::SessionLoggingEnabled(enabled) => {
             portal.prefs.session_logging_enabled = enabled;
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::SessionLogInput(enabled) => {
+            portal.prefs.session_log_input = enabled;
+            save_settings_and_queue_sync(portal);
+        }
         UiMessage::AllowAgentForwarding(enabled) => {
             portal.prefs.allow_agent_forwarding = enabled;
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::HostReachabilityEnabled(enabled) => {
+            portal.prefs.host_reachability = enabled;
+            if !enabled {
+                portal.host_reachability.clear();
+            }
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::SftpVerifyTransfersEnabled(enabled) => {
+            portal.prefs.sftp_verify_transfers = enabled;
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::SftpResumeTransfersEnabled(enabled) => {
+            portal.prefs.sftp_resume_transfers = enabled;
+            save_settings_and_queue_sync(portal);
+        }
         UiMessage::AutoReconnectEnabled(enabled) => {
             portal.prefs.auto_reconnect = enabled;
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::TerminalLigaturesEnabled(enabled) => {
+            portal.prefs.terminal_text_style.ligatures = enabled;
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::TerminalBoldIsBrightEnabled(enabled) => {
+            portal.prefs.terminal_text_style.bold_is_bright = enabled;
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::CursorBlinkEnabled(enabled) => {
+            portal.prefs.cursor_blink = enabled;
+            portal.ui.cursor_blink_visible = true;
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::CursorBlinkIntervalChanged(interval_ms) => {
+            portal.prefs.cursor_blink_interval_ms =
+                interval_ms.clamp(MIN_CURSOR_BLINK_INTERVAL_MS, MAX_CURSOR_BLINK_INTERVAL_MS);
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::TerminalBellChanged(bell) => {
+            portal.prefs.terminal_bell = bell;
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::TerminalClipboardAccessChanged(access) => {
+            portal.prefs.terminal_clipboard = access;
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::ConfirmQuitEnabled(enabled) => {
+            portal.prefs.confirm_quit = enabled;
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::RestoreSessionsEnabled(enabled) => {
+            portal.prefs.restore_sessions = enabled;
+            save_settings_and_queue_sync(portal);
+            if !enabled {
+                super::restore::clear_open_sessions(portal);
+            }
+        }
         UiMessage::ReconnectMaxAttemptsChanged(attempts) => {
             portal.prefs.reconnect_max_attempts = attempts.clamp(1, 20);
             save_settings_and_queue_sync(portal);
@@ -90,6 +296,19 @@ pub(super) fn handle_settings_message(portal: &mut Portal, msg: UiMessage) -> Ta
                 delay_ms.clamp(portal.prefs.reconnect_base_delay_ms.max(500), 120_000);
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::ConnectTimeoutChanged(seconds) => {
+            portal.prefs.connect_timeout = seconds.clamp(1, MAX_CONNECT_TIMEOUT_SECS);
+            save_settings_and_queue_sync(portal);
+            services::connection::set_connect_timeout(portal.prefs.connect_timeout);
+        }
+        UiMessage::KeepaliveIntervalChanged(seconds) => {
+            portal.prefs.keepalive_interval = seconds.min(3600);
+            save_settings_and_queue_sync(portal);
+        }
+        UiMessage::KeepaliveCountMaxChanged(count) => {
+            portal.prefs.keepalive_count_max = count.clamp(1, 10);
+            save_settings_and_queue_sync(portal);
+        }
         UiMessage::CredentialTimeoutChange(timeout_seconds) => {
             let clamped = timeout_seconds.min(3600);
             portal.prefs.credential_timeout = clamped;
@@ -135,7 +354,7 @@ pub(super) fn handle_settings_message(portal: &mut Portal, msg: UiMessage) -> Ta
             }
         }
         UiMessage::VncQualityPresetChanged(preset) => {
-            portal.prefs.vnc_settings.quality_preset = preset;
+            super::super::vnc::apply_quality_preset(portal, preset);
             save_settings_and_queue_sync(portal);
         }
         UiMessage::VncScalingModeChanged(mode) => {
@@ -168,10 +387,18 @@ pub(super) fn handle_settings_message(portal: &mut Portal, msg: UiMessage) -> Ta
             portal.prefs.vnc_settings.clipboard_sharing = enabled;
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::VncClipboardAutoSendChanged(enabled) => {
+            portal.prefs.vnc_settings.clipboard_auto_send = enabled;
+            save_settings_and_queue_sync(portal);
+        }
         UiMessage::VncViewOnlyChanged(enabled) => {
             portal.prefs.vnc_settings.view_only = enabled;
             save_settings_and_queue_sync(portal);
         }
+        UiMessage::VncKeyboardModeChanged(mode) => {
+            portal.prefs.vnc_settings.keyboard_mode = mode;
+            save_settings_and_queue_sync(portal);
+        }
         UiMessage::VncShowCursorDotChanged(enabled) => {
             portal.prefs.vnc_settings.show_cursor_dot = enabled;
             save_settings_and_queue_sync(portal);
@@ -910,23 +1137,49 @@ fn reload_synced_config(portal: &mut Portal) {
 
 fn apply_settings_config(portal: &mut Portal, settings: SettingsConfig) {
     portal.prefs.theme_id = settings.theme;
+    portal.prefs.custom_themes = settings.custom_themes.clone();
+    portal
+        .terminal_panes
+        .set_custom_themes(&portal.prefs.custom_themes);
     portal.prefs.ui_scale_override = settings.ui_scale;
     portal.prefs.terminal_font_size = settings.terminal_font_size;
     portal.prefs.terminal_scroll_speed = settings.terminal_scroll_speed;
+    portal.prefs.scrollback_lines = settings.scrollback_lines;
     portal.prefs.terminal_font = settings.terminal_font;
     portal.prefs.terminal_metric_adjustments = settings.terminal_metric_adjustments;
+    portal.prefs.terminal_text_style = settings.terminal_text_style();
+    portal.prefs.cursor_blink = settings.cursor_blink;
+    portal.prefs.cursor_blink_interval_ms = settings.cursor_blink_interval_ms;
+    portal.prefs.terminal_bell = settings.terminal_bell;
+    portal.prefs.terminal_clipboard = settings.terminal_clipboard;
     portal.prefs.sftp_column_widths = settings.sftp_column_widths;
+    portal.prefs.sftp_show_hidden = settings.sftp_show_hidden;
+    portal.prefs.sftp_verify_transfers = settings.sftp_verify_transfers;
+    portal.prefs.sftp_resume_transfers = settings.sftp_resume_transfers;
+    portal.prefs.viewer_word_wrap = settings.viewer_word_wrap;
     portal.prefs.vnc_settings = settings.vnc;
     portal.prefs.portal_hub = settings.portal_hub;
     portal.prefs.auto_reconnect = settings.auto_reconnect;
+    portal.prefs.confirm_quit = settings.confirm_quit;
+    portal.prefs.restore_sessions = settings.restore_sessions;
     portal.prefs.reconnect_max_attempts = settings.reconnect_max_attempts;
     portal.prefs.reconnect_base_delay_ms = settings.reconnect_base_delay_ms;
     portal.prefs.reconnect_max_delay_ms = settings.reconnect_max_delay_ms;
+    portal.prefs.connect_timeout = settings.connect_timeout;
+    services::connection::set_connect_timeout(settings.connect_timeout);
+    portal.prefs.keepalive_interval = settings.keepalive_interval;
+    portal.prefs.keepalive_count_max = settings.keepalive_count_max;
     portal.prefs.allow_agent_forwarding = settings.allow_agent_forwarding;
+    portal.prefs.host_reachability = settings.host_reachability;
+    if !settings.host_reachability {
+        portal.host_reachability.clear();
+    }
     portal.prefs.credential_timeout = settings.credential_timeout;
     portal.prefs.session_logging_enabled = settings.session_logging_enabled;
     portal.prefs.session_log_dir = settings.session_log_dir;
     portal.prefs.session_log_format = settings.session_log_format;
+    portal.prefs.session_log_input = settings.session_log_input;
+    portal.prefs.session_log_max_size_mb = settings.session_log_max_size_mb;
     portal.prefs.security_audit_enabled = settings.security_audit_enabled;
     portal.prefs.security_audit_dir = settings.security_audit_dir;
     portal.prefs.keybindings = settings.keybindings;
@@ -936,21 +1189,40 @@ fn current_settings_config(portal: &Portal) -> SettingsConfig {
     let mut settings = SettingsConfig::default();
     settings.terminal_font_size = portal.prefs.terminal_font_size;
     settings.terminal_scroll_speed = portal.prefs.terminal_scroll_speed;
+    settings.scrollback_lines = portal.prefs.scrollback_lines;
     settings.terminal_font = portal.prefs.terminal_font;
     settings.terminal_metric_adjustments = portal.prefs.terminal_metric_adjustments;
+    settings.set_terminal_text_style(portal.prefs.terminal_text_style);
+    settings.cursor_blink = portal.prefs.cursor_blink;
+    settings.cursor_blink_interval_ms = portal.prefs.cursor_blink_interval_ms;
+    settings.terminal_bell = portal.prefs.terminal_bell;
+    settings.terminal_clipboard = portal.prefs.terminal_clipboard;
     settings.theme = portal.prefs.theme_id;
+    settings.custom_themes = portal.prefs.custom_themes.clone();
     settings.ui_scale = portal.prefs.ui_scale_override;
+    settings.sftp_show_hidden = portal.prefs.sftp_show_hidden;
+    settings.sftp_verify_transfers = portal.prefs.sftp_verify_transfers;
+    settings.sftp_resume_transfers = portal.prefs.sftp_resume_transfers;
+    settings.viewer_word_wrap = portal.prefs.viewer_word_wrap;
     settings.vnc = portal.prefs.vnc_settings.clone();
     settings.portal_hub = portal.prefs.portal_hub.clone();
     settings.auto_reconnect = portal.prefs.auto_reconnect;
+    settings.confirm_quit = portal.prefs.confirm_quit;
+    settings.restore_sessions = portal.prefs.restore_sessions;
     settings.reconnect_max_attempts = portal.prefs.reconnect_max_attempts;
     settings.reconnect_base_delay_ms = portal.prefs.reconnect_base_delay_ms;
     settings.reconnect_max_delay_ms = portal.prefs.reconnect_max_delay_ms;
+    settings.connect_timeout = portal.prefs.connect_timeout;
+    settings.keepalive_interval = portal.prefs.keepalive_interval;
+    settings.keepalive_count_max = portal.prefs.keepalive_count_max;
     settings.allow_agent_forwarding = portal.prefs.allow_agent_forwarding;
+    settings.host_reachability = portal.prefs.host_reachability;
     settings.credential_timeout = portal.prefs.credential_timeout;
     settings.session_logging_enabled = portal.prefs.session_logging_enabled;
     settings.session_log_dir = portal.prefs.session_log_dir.clone();
     settings.session_log_format = portal.prefs.session_log_format;
+    settings.session_log_input = portal.prefs.session_log_input;
+    settings.session_log_max_size_mb = portal.prefs.session_log_max_size_mb;
     settings.security_audit_enabled = portal.prefs.security_audit_enabled;
     settings.security_audit_dir = portal.prefs.security_audit_dir.clone();
     settings.keybindings = portal.prefs.keybindings.clone();
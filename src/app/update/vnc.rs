@@ -4,7 +4,7 @@ use iced::Task;
 
 use crate::app::Portal;
 use crate::app::managers::session_manager::VncActiveSession;
-use crate::config::settings::VncScalingMode;
+use crate::config::settings::{VncKeyboardMode, VncQualityPreset, VncScalingMode};
 use crate::fs_utils;
 use crate::message::{Message, VncMessage};
 use crate::views::tabs::{Tab, promote_connection_tab};
@@ -90,10 +90,11 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
                     first_frame_received: false,
                     status_text: "connecting".to_string(),
                     fullscreen: false,
-                    keyboard_passthrough: false,
+                    keyboard_mode: portal.prefs.vnc_settings.keyboard_mode,
                     view_only: portal.prefs.vnc_settings.view_only,
                     show_cursor_dot: portal.prefs.vnc_settings.show_cursor_dot,
                     show_stats_overlay: portal.prefs.vnc_settings.show_stats_overlay,
+                    shared_clipboard: None,
                     history_entry_id,
                 },
             );
@@ -184,6 +185,12 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
         }
         VncMessage::ConnectFailed { session_id, error } => {
             tracing::error!("VNC connection failed: {}", error);
+            super::history::record_failed_connection(
+                portal,
+                session_id,
+                crate::config::SessionType::Vnc,
+                &error,
+            );
             if !portal.finish_pending_connect_for(session_id) {
                 tracing::warn!(
                     "Ignoring stale VNC connection failure for session {}: {}",
@@ -204,8 +211,11 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
                 .push(Toast::error(format!("VNC: {}", err)));
             Task::none()
         }
-        VncMessage::ClipboardReceived(_session_id, text) => {
+        VncMessage::ClipboardReceived(session_id, text) => {
             if portal.prefs.vnc_settings.clipboard_sharing {
+                if let Some(vnc) = portal.vnc_sessions.get_mut(&session_id) {
+                    vnc.shared_clipboard = Some(text.clone());
+                }
                 // Write to system clipboard via iced
                 return iced::clipboard::write(text);
             }
@@ -213,8 +223,9 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
         }
         VncMessage::ClipboardSend(session_id, text) => {
             if portal.prefs.vnc_settings.clipboard_sharing
-                && let Some(vnc) = portal.vnc_sessions.get(&session_id)
+                && let Some(vnc) = portal.vnc_sessions.get_mut(&session_id)
             {
+                vnc.shared_clipboard = Some(text.clone());
                 let session = vnc.session.clone();
                 return Task::perform(async move { session.send_clipboard(text).await }, |_| {
                     Message::Noop
@@ -222,6 +233,28 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
             }
             Task::none()
         }
+        VncMessage::ClipboardPollTick => {
+            if let crate::app::View::VncViewer(session_id) = portal.ui.active_view
+                && portal.vnc_sessions.contains_key(&session_id)
+            {
+                return iced::clipboard::read().map(move |contents| {
+                    Message::Vnc(VncMessage::LocalClipboardRead(session_id, contents))
+                });
+            }
+            Task::none()
+        }
+        VncMessage::LocalClipboardRead(session_id, contents) => {
+            let changed = contents.filter(|text| !text.is_empty()).filter(|text| {
+                portal
+                    .vnc_sessions
+                    .get(&session_id)
+                    .is_some_and(|vnc| vnc.shared_clipboard.as_ref() != Some(text))
+            });
+            match changed {
+                Some(text) => handle_vnc(portal, VncMessage::ClipboardSend(session_id, text)),
+                None => Task::none(),
+            }
+        }
         VncMessage::SendSpecialKeys {
             session_id,
             keysyms,
@@ -325,7 +358,25 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
                 && let Some(vnc) = portal.vnc_sessions.get_mut(&session_id)
             {
                 vnc.session.release_all_keys();
-                vnc.keyboard_passthrough = !vnc.keyboard_passthrough;
+                // Releasing returns to the configured mode, or Standard when
+                // that is passthrough itself
+                vnc.keyboard_mode =
+                    match (vnc.keyboard_mode, portal.prefs.vnc_settings.keyboard_mode) {
+                        (VncKeyboardMode::Passthrough, VncKeyboardMode::Passthrough) => {
+                            VncKeyboardMode::Standard
+                        }
+                        (VncKeyboardMode::Passthrough, configured) => configured,
+                        _ => VncKeyboardMode::Passthrough,
+                    };
+            }
+            Task::none()
+        }
+        VncMessage::KeyboardModeSelected(mode) => {
+            if let crate::app::View::VncViewer(session_id) = portal.ui.active_view
+                && let Some(vnc) = portal.vnc_sessions.get_mut(&session_id)
+            {
+                vnc.session.release_all_keys();
+                vnc.keyboard_mode = mode;
             }
             Task::none()
         }
@@ -351,7 +402,9 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
             {
                 vnc.session.release_all_keys();
                 vnc.view_only = !vnc.view_only;
-                vnc.keyboard_passthrough = false;
+                if vnc.keyboard_mode == VncKeyboardMode::Passthrough {
+                    vnc.keyboard_mode = VncKeyboardMode::Standard;
+                }
             }
             Task::none()
         }
@@ -371,22 +424,9 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
             }
             Task::none()
         }
-        VncMessage::CycleQualityPreset => {
-            use crate::config::settings::VncQualityPreset;
-            portal.prefs.vnc_settings.quality_preset =
-                match portal.prefs.vnc_settings.quality_preset {
-                    VncQualityPreset::Auto => VncQualityPreset::Speed,
-                    VncQualityPreset::Speed => VncQualityPreset::Balanced,
-                    VncQualityPreset::Balanced => VncQualityPreset::Quality,
-                    VncQualityPreset::Quality => VncQualityPreset::Lossless,
-                    VncQualityPreset::Lossless => VncQualityPreset::Auto,
-                };
-            portal.save_settings();
-            super::ui::settings::queue_portal_hub_local_sync(portal);
-            portal.toast_manager.push(Toast::success(format!(
-                "VNC quality applies to new sessions: {}",
-                portal.prefs.vnc_settings.quality_preset.label()
-            )));
+        VncMessage::QualityPresetSelected(preset) => {
+            apply_quality_preset(portal, preset);
+            super::ui::settings::save_settings_and_queue_sync(portal);
             Task::none()
         }
         VncMessage::Bell(session_id) => {
@@ -398,6 +438,15 @@ pub fn handle_vnc(portal: &mut Portal, msg: VncMessage) -> Task<Message> {
     }
 }
 
+/// Switch the quality preset and re-negotiate every open VNC session so the
+/// change shows immediately.
+pub(super) fn apply_quality_preset(portal: &mut Portal, preset: VncQualityPreset) {
+    portal.prefs.vnc_settings.quality_preset = preset;
+    for vnc in portal.vnc_sessions.values() {
+        vnc.session.apply_quality(&portal.prefs.vnc_settings);
+    }
+}
+
 /// Refresh the toolbar status text and FPS estimate from the session stats.
 fn refresh_session_status(vnc: &mut VncActiveSession, max_fps: f32) {
     let stats = vnc.session.stats_snapshot();
@@ -3,9 +3,11 @@ use crate::config::hosts::HostGroup;
 use crate::config::settings::PortalHubSettings;
 use crate::config::{DetectedOs, Host, HostsConfig, Protocol};
 use crate::views::host_grid::{GroupCard, HostCard};
+use std::collections::HashSet;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 const HOST_GRID_CACHE_LOG_ITEM_THRESHOLD: usize = 200;
 const HOST_GRID_CACHE_LOG_DURATION_THRESHOLD: Duration = Duration::from_millis(8);
@@ -18,21 +20,35 @@ fn host_card(host: &Host, hub_settings: &PortalHubSettings) -> HostCard {
         protocol: host.protocol.clone(),
         last_connected: host.last_connected,
         group_id: host.group_id,
+        tags: host.tags.clone(),
         via_hub: should_use_portal_hub(hub_settings, host),
     }
 }
 
-fn group_card(group: &HostGroup) -> GroupCard {
+fn group_card(group: &HostGroup, depth: usize) -> GroupCard {
     GroupCard {
         id: group.id,
         name: group.name.clone(),
         collapsed: group.collapsed,
+        depth,
     }
 }
 
-/// Create group cards from hosts config
+/// Create group cards from hosts config, parents before their subgroups
 pub(super) fn group_cards(hosts_config: &HostsConfig) -> Vec<GroupCard> {
-    hosts_config.groups.iter().map(group_card).collect()
+    hosts_config
+        .group_tree()
+        .into_iter()
+        .map(|(group, depth)| group_card(group, depth))
+        .collect()
+}
+
+/// Hosts in display order; each group's hosts end up sorted once the grid
+/// buckets them by group
+fn ordered_hosts(hosts_config: &HostsConfig) -> Vec<&Host> {
+    let mut hosts: Vec<&Host> = hosts_config.hosts.iter().collect();
+    hosts.sort_by_key(|host| host.order);
+    hosts
 }
 
 /// Create host cards from hosts config
@@ -40,35 +56,42 @@ pub(super) fn host_cards(
     hosts_config: &HostsConfig,
     hub_settings: &PortalHubSettings,
 ) -> Vec<HostCard> {
-    hosts_config
-        .hosts
-        .iter()
+    ordered_hosts(hosts_config)
+        .into_iter()
         .map(|host| host_card(host, hub_settings))
         .collect()
 }
 
-/// Create host cards after applying the search filter, avoiding clones for filtered-out hosts.
+/// Create host cards after applying the search and tag filters, avoiding clones for
+/// filtered-out hosts. A host must carry every selected tag.
 pub(super) fn filtered_host_cards(
     query: &str,
+    tags: &[String],
     hosts_config: &HostsConfig,
     hub_settings: &PortalHubSettings,
 ) -> Vec<HostCard> {
     let query = normalize_query(query);
-    if query.is_empty() {
+    if query.is_empty() && tags.is_empty() {
         return host_cards(hosts_config, hub_settings);
     }
 
-    hosts_config
-        .hosts
-        .iter()
+    ordered_hosts(hosts_config)
+        .into_iter()
         .filter(|host| {
-            host.name.to_lowercase().contains(&query)
+            query.is_empty()
+                || host.name.to_lowercase().contains(&query)
                 || host.hostname.to_lowercase().contains(&query)
         })
+        .filter(|host| has_tags(host, tags))
         .map(|host| host_card(host, hub_settings))
         .collect()
 }
 
+fn has_tags(host: &Host, tags: &[String]) -> bool {
+    tags.iter()
+        .all(|tag| host.tags.iter().any(|own| own.eq_ignore_ascii_case(tag)))
+}
+
 /// Create group cards after applying the search filter, avoiding clones for filtered-out groups.
 pub(super) fn filtered_group_cards(query: &str, hosts_config: &HostsConfig) -> Vec<GroupCard> {
     let query = normalize_query(query);
@@ -77,22 +100,61 @@ pub(super) fn filtered_group_cards(query: &str, hosts_config: &HostsConfig) -> V
     }
 
     hosts_config
-        .groups
-        .iter()
-        .filter(|group| group.name.to_lowercase().contains(&query))
-        .map(group_card)
+        .group_tree()
+        .into_iter()
+        .filter(|(group, _)| group.name.to_lowercase().contains(&query))
+        .map(|(group, depth)| group_card(group, depth))
         .collect()
 }
 
+/// Group cards for the groups holding `hosts`, with their parent groups so
+/// the nesting stays visible. Used while filtering by tag, where empty
+/// groups would only be noise.
+fn groups_holding(hosts: &[HostCard], hosts_config: &HostsConfig) -> Vec<GroupCard> {
+    let host_groups: HashSet<Uuid> = hosts.iter().filter_map(|host| host.group_id).collect();
+    hosts_config
+        .group_tree()
+        .into_iter()
+        .filter(|(group, _)| {
+            host_groups
+                .iter()
+                .any(|host_group| hosts_config.is_group_within(*host_group, group.id))
+        })
+        .map(|(group, depth)| group_card(group, depth))
+        .collect()
+}
+
+/// Every tag used by a host, plus the selected ones, sorted and without
+/// case-insensitive repeats
+pub(super) fn available_tags(hosts_config: &HostsConfig, selected: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let all = hosts_config
+        .hosts
+        .iter()
+        .flat_map(|host| host.tags.iter())
+        .chain(selected);
+    for tag in all {
+        let tag = tag.trim();
+        if !tag.is_empty() && !tags.iter().any(|known| known.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags.sort_by_key(|tag| tag.to_lowercase());
+    tags
+}
+
 #[derive(Debug, Clone, Default)]
 pub(crate) struct HostGridCards {
     pub groups: Vec<GroupCard>,
     pub hosts: Vec<HostCard>,
+    /// Tags offered as filter chips
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct HostGridCacheKey {
     query: String,
+    tags: Vec<String>,
     signature: u64,
 }
 
@@ -106,19 +168,28 @@ impl HostGridCache {
     pub fn cards(
         &mut self,
         query: &str,
+        tags: &[String],
         hosts_config: &HostsConfig,
         hub_settings: &PortalHubSettings,
     ) -> &HostGridCards {
         let key = HostGridCacheKey {
             query: normalize_query(query),
+            tags: tags.to_vec(),
             signature: hosts_signature(hosts_config, hub_settings),
         };
 
         if self.key.as_ref() != Some(&key) {
             let started = Instant::now();
+            let hosts = filtered_host_cards(&key.query, tags, hosts_config, hub_settings);
+            let groups = if tags.is_empty() {
+                filtered_group_cards(&key.query, hosts_config)
+            } else {
+                groups_holding(&hosts, hosts_config)
+            };
             self.cards = HostGridCards {
-                groups: filtered_group_cards(&key.query, hosts_config),
-                hosts: filtered_host_cards(&key.query, hosts_config, hub_settings),
+                groups,
+                hosts,
+                tags: available_tags(hosts_config, tags),
             };
             let elapsed = started.elapsed();
             let item_count = hosts_config.hosts.len() + hosts_config.groups.len();
@@ -161,6 +232,8 @@ fn hosts_signature(hosts_config: &HostsConfig, hub_settings: &PortalHubSettings)
         protocol_key(&host.protocol).hash(&mut hasher);
         host.hub_routing.hash(&mut hasher);
         host.group_id.hash(&mut hasher);
+        host.order.hash(&mut hasher);
+        host.tags.hash(&mut hasher);
         hash_datetime(host.updated_at, &mut hasher);
         if let Some(last_connected) = host.last_connected {
             hash_datetime(last_connected, &mut hasher);
@@ -175,6 +248,7 @@ fn hosts_signature(hosts_config: &HostsConfig, hub_settings: &PortalHubSettings)
         group.name.hash(&mut hasher);
         group.parent_id.hash(&mut hasher);
         group.collapsed.hash(&mut hasher);
+        group.order.hash(&mut hasher);
     }
 
     hasher.finish()
@@ -205,7 +279,6 @@ mod tests {
     use crate::config::hosts::HubRouting;
     use crate::config::{AuthMethod, DetectedOs, Protocol};
     use chrono::Utc;
-    use uuid::Uuid;
 
     fn host(name: &str, hostname: &str) -> Host {
         let now = Utc::now();
@@ -221,11 +294,19 @@ mod tests {
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
             auth: AuthMethod::Agent,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Auto,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at: now,
@@ -241,6 +322,7 @@ mod tests {
             name: name.to_string(),
             parent_id: None,
             collapsed: false,
+            order: 0,
             created_at: Utc::now(),
         }
     }
@@ -250,8 +332,9 @@ mod tests {
         let config = HostsConfig {
             hosts: vec![host("Production", "prod.example.com")],
             groups: Vec::new(),
+            ..Default::default()
         };
-        let filtered = filtered_host_cards(" prod ", &config, &PortalHubSettings::default());
+        let filtered = filtered_host_cards(" prod ", &[], &config, &PortalHubSettings::default());
 
         assert_eq!(filtered.len(), 1);
     }
@@ -261,6 +344,7 @@ mod tests {
         let config = HostsConfig {
             hosts: Vec::new(),
             groups: vec![group("Databases")],
+            ..Default::default()
         };
         let filtered = filtered_group_cards(" data ", &config);
 
@@ -272,13 +356,14 @@ mod tests {
         let config = HostsConfig {
             hosts: vec![host("Production", "prod.example.com")],
             groups: Vec::new(),
+            ..Default::default()
         };
         let mut cache = HostGridCache::default();
 
-        cache.cards(" prod ", &config, &PortalHubSettings::default());
+        cache.cards(" prod ", &[], &config, &PortalHubSettings::default());
         let first_key = cache.key.clone();
 
-        cache.cards("prod", &config, &PortalHubSettings::default());
+        cache.cards("prod", &[], &config, &PortalHubSettings::default());
 
         assert_eq!(cache.key, first_key);
         assert_eq!(cache.cards.hosts.len(), 1);
@@ -289,18 +374,84 @@ mod tests {
         let mut config = HostsConfig {
             hosts: vec![host("Production", "prod.example.com")],
             groups: Vec::new(),
+            ..Default::default()
         };
         let mut cache = HostGridCache::default();
 
-        cache.cards("prod", &config, &PortalHubSettings::default());
+        cache.cards("prod", &[], &config, &PortalHubSettings::default());
         let first_key = cache.key.clone();
 
         config
             .hosts
             .push(host("Production Backup", "prod-b.example.com"));
-        cache.cards("prod", &config, &PortalHubSettings::default());
+        cache.cards("prod", &[], &config, &PortalHubSettings::default());
 
         assert_ne!(cache.key, first_key);
         assert_eq!(cache.cards.hosts.len(), 2);
     }
+
+    fn tagged(name: &str, tags: &[&str]) -> Host {
+        let mut host = host(name, &format!("{name}.example.com"));
+        host.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        host
+    }
+
+    #[test]
+    fn tag_filter_requires_every_selected_tag() {
+        let config = HostsConfig {
+            hosts: vec![
+                tagged("web1", &["prod", "Web"]),
+                tagged("web2", &["staging", "web"]),
+                tagged("db1", &["prod", "db"]),
+            ],
+            groups: Vec::new(),
+            ..Default::default()
+        };
+        let selected = ["prod".to_string(), "web".to_string()];
+
+        let filtered = filtered_host_cards("", &selected, &config, &PortalHubSettings::default());
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "web1");
+
+        let searched =
+            filtered_host_cards("db", &selected[..1], &config, &PortalHubSettings::default());
+        assert_eq!(searched.len(), 1);
+        assert_eq!(searched[0].name, "db1");
+
+        assert_eq!(
+            available_tags(&config, &[]),
+            ["db", "prod", "staging", "Web"]
+        );
+    }
+
+    #[test]
+    fn tag_filter_keeps_only_groups_holding_matches_and_their_parents() {
+        let parent = group("Prod");
+        let mut child = group("Web");
+        let empty = group("Db");
+        child.parent_id = Some(parent.id);
+        let mut web = tagged("web1", &["nginx"]);
+        web.group_id = Some(child.id);
+        let config = HostsConfig {
+            hosts: vec![web],
+            groups: vec![parent, child, empty],
+            ..Default::default()
+        };
+        let mut cache = HostGridCache::default();
+
+        let cards = cache.cards(
+            "",
+            &["nginx".to_string()],
+            &config,
+            &PortalHubSettings::default(),
+        );
+
+        let names: Vec<&str> = cards
+            .groups
+            .iter()
+            .map(|group| group.name.as_str())
+            .collect();
+        assert_eq!(names, ["Prod", "Web"]);
+        assert_eq!(cards.groups[1].depth, 1);
+    }
 }
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::VersionedConfig;
 use crate::error::ConfigError;
 
 /// Type of session for history entry
@@ -36,6 +37,9 @@ pub struct HistoryEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub disconnected_at: Option<chrono::DateTime<chrono::Utc>>,
     pub session_type: SessionType,
+    /// Why the connection failed; `None` for connections that succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 impl HistoryEntry {
@@ -56,9 +60,30 @@ impl HistoryEntry {
             connected_at: chrono::Utc::now(),
             disconnected_at: None,
             session_type,
+            error: None,
         }
     }
 
+    /// Create an entry for a connection attempt that failed
+    pub fn failed(
+        host_id: Uuid,
+        host_name: String,
+        hostname: String,
+        username: String,
+        session_type: SessionType,
+        error: String,
+    ) -> Self {
+        let mut entry = Self::new(host_id, host_name, hostname, username, session_type);
+        entry.disconnected_at = Some(entry.connected_at);
+        entry.error = Some(error);
+        entry
+    }
+
+    /// Whether the connection was established
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+
     /// Create a new history entry for a local terminal session
     pub fn new_local() -> Self {
         let hostname = std::env::var("HOSTNAME")
@@ -78,6 +103,7 @@ impl HistoryEntry {
             connected_at: chrono::Utc::now(),
             disconnected_at: None,
             session_type: SessionType::Local,
+            error: None,
         }
     }
 
@@ -107,6 +133,113 @@ impl HistoryEntry {
     }
 }
 
+/// Date range facet of the history view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistoryDateRange {
+    #[default]
+    AnyTime,
+    Today,
+    Last7Days,
+    Last30Days,
+}
+
+impl HistoryDateRange {
+    pub const ALL: [HistoryDateRange; 4] = [
+        HistoryDateRange::AnyTime,
+        HistoryDateRange::Today,
+        HistoryDateRange::Last7Days,
+        HistoryDateRange::Last30Days,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryDateRange::AnyTime => "Any time",
+            HistoryDateRange::Today => "Today",
+            HistoryDateRange::Last7Days => "7 days",
+            HistoryDateRange::Last30Days => "30 days",
+        }
+    }
+
+    /// Whether a connection made at `connected_at` falls in the range
+    fn contains(
+        &self,
+        connected_at: chrono::DateTime<chrono::Utc>,
+        now: chrono::DateTime<chrono::Local>,
+    ) -> bool {
+        let days = match self {
+            HistoryDateRange::AnyTime => return true,
+            HistoryDateRange::Today => 0,
+            HistoryDateRange::Last7Days => 6,
+            HistoryDateRange::Last30Days => 29,
+        };
+        // Ranges start at local midnight, so "Today" means the calendar day
+        let first_day = now.date_naive() - chrono::Duration::days(days);
+        connected_at.with_timezone(&chrono::Local).date_naive() >= first_day
+    }
+}
+
+/// Order of the history list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistorySort {
+    /// Most recent connection first
+    #[default]
+    Newest,
+    Oldest,
+}
+
+impl HistorySort {
+    pub const ALL: [HistorySort; 2] = [HistorySort::Newest, HistorySort::Oldest];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistorySort::Newest => "Newest",
+            HistorySort::Oldest => "Oldest",
+        }
+    }
+}
+
+/// Search text and facets narrowing the history list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    /// Matched against host name, hostname and username, ignoring case
+    pub query: String,
+    pub host_id: Option<Uuid>,
+    pub session_type: Option<SessionType>,
+    pub date_range: HistoryDateRange,
+    pub sort: HistorySort,
+}
+
+impl HistoryFilter {
+    /// Whether anything narrows the list (the sort order does not)
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty()
+            || self.host_id.is_some()
+            || self.session_type.is_some()
+            || self.date_range != HistoryDateRange::AnyTime
+    }
+
+    fn matches(&self, entry: &HistoryEntry, now: chrono::DateTime<chrono::Local>) -> bool {
+        if self.host_id.is_some_and(|host_id| host_id != entry.host_id) {
+            return false;
+        }
+        if self
+            .session_type
+            .as_ref()
+            .is_some_and(|session_type| *session_type != entry.session_type)
+        {
+            return false;
+        }
+        if !self.date_range.contains(entry.connected_at, now) {
+            return false;
+        }
+        let query = self.query.trim().to_lowercase();
+        query.is_empty()
+            || [&entry.host_name, &entry.hostname, &entry.username]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
 fn default_max_entries() -> usize {
     100
 }
@@ -114,21 +247,63 @@ fn default_max_entries() -> usize {
 /// Root configuration for history.toml
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
     #[serde(default)]
     pub entries: Vec<HistoryEntry>,
     #[serde(default = "default_max_entries")]
     pub max_entries: usize,
+    /// Leave addresses, usernames and error messages out of exports
+    #[serde(default)]
+    pub redact_export: bool,
 }
 
 impl Default for HistoryConfig {
     fn default() -> Self {
         Self {
+            version: Self::SCHEMA_VERSION,
             entries: Vec::new(),
             max_entries: default_max_entries(),
+            redact_export: false,
         }
     }
 }
 
+impl VersionedConfig for HistoryConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+}
+
+/// File format of a history export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Format matching the extension of `path`, CSV unless it is `.json`
+    pub fn for_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        }
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 impl HistoryConfig {
     /// Add a new history entry, trimming old entries if over limit
     pub fn add_entry(&mut self, entry: HistoryEntry) {
@@ -159,6 +334,112 @@ impl HistoryConfig {
         }
     }
 
+    /// Entries passing `filter`, in its sort order
+    pub fn filtered(&self, filter: &HistoryFilter) -> Vec<&HistoryEntry> {
+        let now = chrono::Local::now();
+        let mut entries: Vec<&HistoryEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| filter.matches(entry, now))
+            .collect();
+        match filter.sort {
+            HistorySort::Newest => {
+                entries.sort_by(|a, b| b.connected_at.cmp(&a.connected_at));
+            }
+            HistorySort::Oldest => {
+                entries.sort_by(|a, b| a.connected_at.cmp(&b.connected_at));
+            }
+        }
+        entries
+    }
+
+    /// Hosts that appear in the history, most recently connected first
+    pub fn hosts(&self) -> Vec<(Uuid, String)> {
+        let mut hosts: Vec<(Uuid, String, chrono::DateTime<chrono::Utc>)> = Vec::new();
+        for entry in &self.entries {
+            match hosts.iter_mut().find(|(id, _, _)| *id == entry.host_id) {
+                Some(host) if host.2 < entry.connected_at => host.2 = entry.connected_at,
+                Some(_) => {}
+                None => hosts.push((entry.host_id, entry.host_name.clone(), entry.connected_at)),
+            }
+        }
+        hosts.sort_by(|a, b| b.2.cmp(&a.2));
+        hosts.into_iter().map(|(id, name, _)| (id, name)).collect()
+    }
+
+    /// All entries, newest first, as CSV or pretty-printed JSON. With
+    /// `redact_export` set, hostnames, usernames and errors are left out.
+    pub fn export(&self, format: ExportFormat) -> String {
+        let mut columns = vec!["host_name"];
+        if !self.redact_export {
+            columns.extend(["hostname", "username"]);
+        }
+        columns.extend(["session_type", "started_at", "duration_seconds", "status"]);
+        if !self.redact_export {
+            columns.push("error");
+        }
+
+        let rows: Vec<Vec<String>> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut row = vec![entry.host_name.clone()];
+                if !self.redact_export {
+                    row.extend([entry.hostname.clone(), entry.username.clone()]);
+                }
+                row.extend([
+                    entry.session_type.display_name().to_string(),
+                    entry.connected_at.to_rfc3339(),
+                    entry.duration().num_seconds().to_string(),
+                    if entry.succeeded() { "ok" } else { "failed" }.to_string(),
+                ]);
+                if !self.redact_export {
+                    row.push(entry.error.clone().unwrap_or_default());
+                }
+                row
+            })
+            .collect();
+
+        match format {
+            ExportFormat::Csv => {
+                let mut output = columns.join(",");
+                output.push('\n');
+                for row in rows {
+                    let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+                    output.push_str(&fields.join(","));
+                    output.push('\n');
+                }
+                output
+            }
+            ExportFormat::Json => {
+                let records: Vec<serde_json::Value> = rows
+                    .into_iter()
+                    .map(|row| {
+                        let record: serde_json::Map<String, serde_json::Value> = columns
+                            .iter()
+                            .zip(row)
+                            .map(|(column, value)| {
+                                let value = match *column {
+                                    "duration_seconds" => value
+                                        .parse::<i64>()
+                                        .map(serde_json::Value::from)
+                                        .unwrap_or(serde_json::Value::String(value)),
+                                    "error" if value.is_empty() => serde_json::Value::Null,
+                                    _ => serde_json::Value::String(value),
+                                };
+                                (column.to_string(), value)
+                            })
+                            .collect();
+                        serde_json::Value::Object(record)
+                    })
+                    .collect();
+                let mut output = serde_json::to_string_pretty(&records).unwrap_or_default();
+                output.push('\n');
+                output
+            }
+        }
+    }
+
     /// Clear all history
     pub fn clear(&mut self) {
         self.entries.clear();
@@ -176,8 +457,11 @@ impl HistoryConfig {
 
         tracing::debug!("Loading history from: {:?}", path);
 
-        let mut config: Self = super::load_toml_or_recover(&path, "history")?;
+        let (mut config, migrated): (Self, bool) = super::load_versioned_toml(&path, "history")?;
         config.trim_to_max_entries();
+        if migrated && let Err(e) = config.save() {
+            tracing::warn!("Failed to save migrated history: {}", e);
+        }
         Ok(config)
     }
 
@@ -1114,6 +1398,145 @@ mod tests {
         assert!(deserialized.find_entry(id).is_some());
     }
 
+    // === HistoryFilter tests ===
+
+    fn filter_fixture() -> (HistoryConfig, Uuid) {
+        let web = Uuid::new_v4();
+        let mut config = HistoryConfig::default();
+        let mut old = HistoryEntry::new(
+            web,
+            "Web".to_string(),
+            "web.example.com".to_string(),
+            "deploy".to_string(),
+            SessionType::Ssh,
+        );
+        old.connected_at -= chrono::Duration::days(10);
+        config.add_entry(old);
+        let mut sftp = HistoryEntry::new(
+            web,
+            "Web".to_string(),
+            "web.example.com".to_string(),
+            "deploy".to_string(),
+            SessionType::Sftp,
+        );
+        sftp.connected_at -= chrono::Duration::minutes(1);
+        config.add_entry(sftp);
+        config.add_entry(HistoryEntry::failed(
+            Uuid::new_v4(),
+            "Database".to_string(),
+            "db.internal".to_string(),
+            "postgres".to_string(),
+            SessionType::Ssh,
+            "Connection refused".to_string(),
+        ));
+        (config, web)
+    }
+
+    #[test]
+    fn history_filter_combines_search_and_facets() {
+        let (config, web) = filter_fixture();
+
+        let by_search = HistoryFilter {
+            query: "  DB.INT ".to_string(),
+            ..Default::default()
+        };
+        let names: Vec<_> = config
+            .filtered(&by_search)
+            .iter()
+            .map(|entry| entry.host_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Database"]);
+
+        let web_ssh = HistoryFilter {
+            host_id: Some(web),
+            session_type: Some(SessionType::Ssh),
+            ..Default::default()
+        };
+        assert_eq!(config.filtered(&web_ssh).len(), 1);
+
+        let recent_web = HistoryFilter {
+            host_id: Some(web),
+            date_range: HistoryDateRange::Last7Days,
+            ..Default::default()
+        };
+        let recent = config.filtered(&recent_web);
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].session_type, SessionType::Sftp);
+        assert!(recent_web.is_active());
+        assert!(!HistoryFilter::default().is_active());
+    }
+
+    #[test]
+    fn history_filter_sorts_and_lists_hosts_by_last_connection() {
+        let (config, web) = filter_fixture();
+
+        let oldest = HistoryFilter {
+            sort: HistorySort::Oldest,
+            ..Default::default()
+        };
+        let filtered = config.filtered(&oldest);
+        assert_eq!(filtered[0].session_type, SessionType::Ssh);
+        assert_eq!(filtered[0].host_id, web);
+        assert!(!filtered[2].succeeded());
+
+        let hosts: Vec<_> = config.hosts().into_iter().map(|(_, name)| name).collect();
+        assert_eq!(hosts, vec!["Database", "Web"]);
+    }
+
+    // === Export tests ===
+
+    #[test]
+    fn history_export_quotes_csv_fields() {
+        let (config, _) = filter_fixture();
+
+        let csv = config.export(ExportFormat::Csv);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "host_name,hostname,username,session_type,started_at,duration_seconds,status,error"
+            )
+        );
+        let failed = lines.next().unwrap();
+        assert!(failed.starts_with("Database,db.internal,postgres,SSH,"));
+        assert!(failed.ends_with(",0,failed,Connection refused"));
+
+        let mut quoted = config.clone();
+        quoted.entries[0].host_name = "Web, \"prod\"".to_string();
+        assert!(
+            quoted
+                .export(ExportFormat::Csv)
+                .contains("\n\"Web, \"\"prod\"\"\",")
+        );
+    }
+
+    #[test]
+    fn history_export_json_omits_redacted_fields() {
+        let (mut config, _) = filter_fixture();
+        config.redact_export = true;
+
+        let json: serde_json::Value =
+            serde_json::from_str(&config.export(ExportFormat::Json)).unwrap();
+        let records = json.as_array().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["host_name"], "Database");
+        assert_eq!(records[0]["status"], "failed");
+        assert_eq!(records[0]["duration_seconds"], 0);
+        assert!(records[0].get("hostname").is_none());
+        assert!(records[0].get("username").is_none());
+        assert!(records[0].get("error").is_none());
+
+        assert!(!config.export(ExportFormat::Csv).contains("db.internal"));
+        assert_eq!(
+            ExportFormat::for_path(std::path::Path::new("audit.JSON")),
+            ExportFormat::Json
+        );
+        assert_eq!(
+            ExportFormat::for_path(std::path::Path::new("audit")),
+            ExportFormat::Csv
+        );
+    }
+
     // === default_max_entries test ===
 
     #[test]
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use super::VersionedConfig;
 use crate::error::ConfigError;
 
 /// Connection protocol
@@ -85,6 +86,21 @@ pub struct PortForward {
     pub description: Option<String>,
 }
 
+impl PortForward {
+    /// One-line description such as `Local localhost:8080 -> db:5432`
+    pub fn summary(&self) -> String {
+        match self.kind {
+            PortForwardKind::Dynamic => {
+                format!("Dynamic {}:{} (SOCKS)", self.bind_host, self.bind_port)
+            }
+            _ => format!(
+                "{} {}:{} -> {}:{}",
+                self.kind, self.bind_host, self.bind_port, self.target_host, self.target_port
+            ),
+        }
+    }
+}
+
 /// Detected operating system from SSH connection
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -415,9 +431,28 @@ pub struct Host {
     pub allow_cleartext_vnc: bool,
     #[serde(default)]
     pub auth: AuthMethod,
+    /// Further methods tried in order when `auth` is rejected (e.g. a second
+    /// key, then password). Empty keeps the built-in fallback chain only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auth_fallbacks: Vec<AuthMethod>,
+    /// SHA256 fingerprint of the agent key to offer first with agent auth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_identity: Option<String>,
     /// Enable SSH agent forwarding for this host
     #[serde(default)]
     pub agent_forwarding: bool,
+    /// Seconds between keepalive probes (0 disables); overrides the global
+    /// `keepalive_interval` setting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive_interval: Option<u64>,
+    /// Unanswered probes before the connection is dropped; overrides the
+    /// global `keepalive_count_max` setting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive_count_max: Option<u32>,
+    /// Log terminal sessions to disk (`Some(false)` never logs); overrides
+    /// the global `session_logging_enabled` setting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_logging: Option<bool>,
     /// SSH port forwards (-L and -R)
     #[serde(default)]
     pub port_forwards: Vec<PortForward>,
@@ -434,8 +469,19 @@ pub struct Host {
     /// Jump (bastion) host to tunnel through when connecting (ProxyJump).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub jump_host_id: Option<Uuid>,
+    /// Directory the SFTP browser opens at (`~` is the remote home); the
+    /// home directory when unset or missing on the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sftp_start_path: Option<String>,
+    /// Remote directory the SFTP browser last showed for this host; reopened
+    /// on the next connection when no `sftp_start_path` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sftp_path: Option<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group_id: Option<Uuid>,
+    /// Position among the hosts of the same group, lowest first
+    #[serde(default)]
+    pub order: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
     #[serde(default)]
@@ -467,6 +513,27 @@ impl Host {
             )
     }
 
+    /// The configured auth method followed by its fallbacks, in attempt order
+    pub fn auth_sequence(&self) -> impl Iterator<Item = &AuthMethod> {
+        std::iter::once(&self.auth).chain(&self.auth_fallbacks)
+    }
+
+    /// Whether connecting needs a password collected up front (the primary
+    /// method is password auth).
+    pub fn needs_password(&self) -> bool {
+        matches!(self.auth, AuthMethod::Password)
+    }
+
+    /// Whether password auth is a fallback, asked for only once the methods
+    /// ahead of it have been rejected.
+    pub fn has_password_fallback(&self) -> bool {
+        !self.needs_password()
+            && self
+                .auth_fallbacks
+                .iter()
+                .any(|method| matches!(method, AuthMethod::Password))
+    }
+
     /// Get the effective SSH username (host override or current user)
     pub fn effective_username(&self) -> String {
         let trimmed = self.username.trim();
@@ -476,6 +543,19 @@ impl Host {
 
         default_username()
     }
+
+    /// Where this host connects: hostname, port and user, ignoring case
+    fn endpoint_key(&self) -> (String, u16, String) {
+        let port = match self.protocol {
+            Protocol::Ssh => self.port,
+            Protocol::Vnc => self.effective_vnc_port(),
+        };
+        (
+            self.hostname.trim().to_ascii_lowercase(),
+            port,
+            self.effective_username().to_ascii_lowercase(),
+        )
+    }
 }
 
 pub(crate) fn default_username() -> String {
@@ -503,18 +583,42 @@ pub struct HostGroup {
     pub parent_id: Option<Uuid>,
     #[serde(default)]
     pub collapsed: bool,
+    /// Position among the groups with the same parent, lowest first
+    #[serde(default)]
+    pub order: u32,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Root configuration for hosts.toml
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HostsConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
     #[serde(default)]
     pub hosts: Vec<Host>,
     #[serde(default)]
     pub groups: Vec<HostGroup>,
 }
 
+impl Default for HostsConfig {
+    fn default() -> Self {
+        Self {
+            version: Self::SCHEMA_VERSION,
+            hosts: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+}
+
+impl VersionedConfig for HostsConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+}
+
 impl HostsConfig {
     /// Find host by ID
     pub fn find_host(&self, id: Uuid) -> Option<&Host> {
@@ -531,11 +635,244 @@ impl HostsConfig {
         self.groups.iter_mut().find(|g| g.id == id)
     }
 
-    /// Add a new host
-    pub fn add_host(&mut self, host: Host) {
+    /// Hosts in `group_id` (`None` for ungrouped), in display order
+    pub fn hosts_in_group(&self, group_id: Option<Uuid>) -> Vec<&Host> {
+        let mut hosts: Vec<&Host> = self
+            .hosts
+            .iter()
+            .filter(|host| host.group_id == group_id)
+            .collect();
+        hosts.sort_by_key(|host| host.order);
+        hosts
+    }
+
+    /// Groups directly inside `parent_id` (`None` for the top level), in
+    /// display order. Groups whose parent no longer exists count as top level.
+    pub fn child_groups(&self, parent_id: Option<Uuid>) -> Vec<&HostGroup> {
+        let mut groups: Vec<&HostGroup> = self
+            .groups
+            .iter()
+            .filter(|group| self.effective_parent(group) == parent_id)
+            .collect();
+        groups.sort_by_key(|group| group.order);
+        groups
+    }
+
+    /// Every group paired with its nesting depth, parents before their
+    /// subgroups
+    pub fn group_tree(&self) -> Vec<(&HostGroup, usize)> {
+        let mut tree = Vec::with_capacity(self.groups.len());
+        let mut pending: Vec<(&HostGroup, usize)> = self
+            .child_groups(None)
+            .into_iter()
+            .rev()
+            .map(|group| (group, 0))
+            .collect();
+        while let Some((group, depth)) = pending.pop() {
+            tree.push((group, depth));
+            pending.extend(
+                self.child_groups(Some(group.id))
+                    .into_iter()
+                    .rev()
+                    .map(|child| (child, depth + 1)),
+            );
+        }
+        // Groups caught in a hand-edited parent cycle are never reached from
+        // the top level; list them flat rather than hiding them
+        for group in &self.groups {
+            if !tree.iter().any(|(listed, _)| listed.id == group.id) {
+                tree.push((group, 0));
+            }
+        }
+        tree
+    }
+
+    /// True when `group_id` is `ancestor_id` or nested somewhere below it
+    pub fn is_group_within(&self, group_id: Uuid, ancestor_id: Uuid) -> bool {
+        let mut current = Some(group_id);
+        // Bounded by the group count so a hand-edited cycle cannot loop forever
+        for _ in 0..=self.groups.len() {
+            match current {
+                Some(id) if id == ancestor_id => return true,
+                Some(id) => {
+                    current = self
+                        .groups
+                        .iter()
+                        .find(|group| group.id == id)
+                        .and_then(|group| self.effective_parent(group));
+                }
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Move a host into `group_id` at `index` among that group's hosts
+    /// (clamped to the end), renumbering the group's order.
+    pub fn move_host(
+        &mut self,
+        host_id: Uuid,
+        group_id: Option<Uuid>,
+        index: usize,
+    ) -> Result<(), ConfigError> {
+        if self.find_host(host_id).is_none() {
+            return Err(ConfigError::HostNotFound(host_id));
+        }
+        if let Some(group_id) = group_id
+            && !self.groups.iter().any(|group| group.id == group_id)
+        {
+            return Err(ConfigError::GroupNotFound(group_id));
+        }
+
+        let mut siblings: Vec<Uuid> = self
+            .hosts_in_group(group_id)
+            .into_iter()
+            .map(|host| host.id)
+            .filter(|id| *id != host_id)
+            .collect();
+        siblings.insert(index.min(siblings.len()), host_id);
+
+        let mut previous_group = group_id;
+        if let Some(host) = self.find_host_mut(host_id) {
+            previous_group = std::mem::replace(&mut host.group_id, group_id);
+        }
+        self.renumber_hosts(&siblings);
+        if previous_group != group_id {
+            let remaining: Vec<Uuid> = self
+                .hosts_in_group(previous_group)
+                .into_iter()
+                .map(|host| host.id)
+                .collect();
+            self.renumber_hosts(&remaining);
+        }
+        Ok(())
+    }
+
+    /// Move a group inside `parent_id` at `index` among its sibling groups
+    /// (clamped to the end), renumbering their order. A group cannot be
+    /// moved into itself or one of its own subgroups.
+    pub fn move_group(
+        &mut self,
+        group_id: Uuid,
+        parent_id: Option<Uuid>,
+        index: usize,
+    ) -> Result<(), ConfigError> {
+        if !self.groups.iter().any(|group| group.id == group_id) {
+            return Err(ConfigError::GroupNotFound(group_id));
+        }
+        if let Some(parent_id) = parent_id {
+            if !self.groups.iter().any(|group| group.id == parent_id) {
+                return Err(ConfigError::GroupNotFound(parent_id));
+            }
+            if self.is_group_within(parent_id, group_id) {
+                return Err(ConfigError::GroupCycle(group_id));
+            }
+        }
+
+        let mut siblings: Vec<Uuid> = self
+            .child_groups(parent_id)
+            .into_iter()
+            .map(|group| group.id)
+            .filter(|id| *id != group_id)
+            .collect();
+        siblings.insert(index.min(siblings.len()), group_id);
+
+        let previous_parent = self
+            .groups
+            .iter()
+            .find(|group| group.id == group_id)
+            .and_then(|group| self.effective_parent(group));
+        if let Some(group) = self.find_group_mut(group_id) {
+            group.parent_id = parent_id;
+        }
+        self.renumber_groups(&siblings);
+        if previous_parent != parent_id {
+            let remaining: Vec<Uuid> = self
+                .child_groups(previous_parent)
+                .into_iter()
+                .map(|group| group.id)
+                .collect();
+            self.renumber_groups(&remaining);
+        }
+        Ok(())
+    }
+
+    fn renumber_hosts(&mut self, ids: &[Uuid]) {
+        for (order, id) in ids.iter().enumerate() {
+            if let Some(host) = self.find_host_mut(*id) {
+                host.order = order as u32;
+            }
+        }
+    }
+
+    fn renumber_groups(&mut self, ids: &[Uuid]) {
+        for (order, id) in ids.iter().enumerate() {
+            if let Some(group) = self.find_group_mut(*id) {
+                group.order = order as u32;
+            }
+        }
+    }
+
+    fn effective_parent(&self, group: &HostGroup) -> Option<Uuid> {
+        group
+            .parent_id
+            .filter(|parent_id| self.groups.iter().any(|other| other.id == *parent_id))
+    }
+
+    /// Another host connecting to the same hostname, port and user as
+    /// `host`, if any
+    pub fn find_duplicate(&self, host: &Host) -> Option<&Host> {
+        let key = host.endpoint_key();
+        self.hosts
+            .iter()
+            .find(|existing| existing.id != host.id && existing.endpoint_key() == key)
+    }
+
+    /// Add a copy of a host named "<name> (copy)" right after it, returning
+    /// the copy's id
+    pub fn duplicate_host(&mut self, id: Uuid) -> Result<Uuid, ConfigError> {
+        let original = self.find_host(id).ok_or(ConfigError::HostNotFound(id))?;
+        let now = chrono::Utc::now();
+        let copy = Host {
+            id: Uuid::new_v4(),
+            name: format!("{} (copy)", original.name),
+            // The vault entry stays with the original; sharing it would let
+            // a password change on one host change the other
+            vnc_password_id: None,
+            created_at: now,
+            updated_at: now,
+            last_connected: None,
+            last_sftp_path: None,
+            ..original.clone()
+        };
+        let copy_id = copy.id;
+        let group_id = copy.group_id;
+        let index = self
+            .hosts_in_group(group_id)
+            .iter()
+            .position(|host| host.id == id)
+            .map_or(usize::MAX, |index| index + 1);
+
+        self.add_host(copy);
+        self.move_host(copy_id, group_id, index)?;
+        Ok(copy_id)
+    }
+
+    /// Add a new host after the hosts already in its group
+    pub fn add_host(&mut self, mut host: Host) {
+        host.order = self.next_host_order(host.group_id);
         self.hosts.push(host);
     }
 
+    fn next_host_order(&self, group_id: Option<Uuid>) -> u32 {
+        self.hosts
+            .iter()
+            .filter(|host| host.group_id == group_id)
+            .map(|host| host.order.saturating_add(1))
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Update an existing host
     pub fn update_host(&mut self, host: Host) -> Result<(), ConfigError> {
         let existing = self
@@ -570,7 +907,11 @@ impl HostsConfig {
 
         tracing::debug!("Loading hosts from: {:?}", path);
 
-        super::load_toml_or_recover(&path, "hosts")
+        let (config, migrated): (Self, bool) = super::load_versioned_toml(&path, "hosts")?;
+        if migrated && let Err(e) = config.save() {
+            tracing::warn!("Failed to save migrated hosts: {}", e);
+        }
+        Ok(config)
     }
 
     /// Save to file
@@ -589,29 +930,51 @@ impl HostsConfig {
         super::write_atomic(&path, &content).map_err(|e| ConfigError::WriteFile { path, source: e })
     }
 
-    /// Import hosts from the user's SSH config file.
+    /// Import hosts from the user's `~/.ssh/config`; a missing file imports
+    /// nothing.
     /// Returns the number of new hosts imported.
     pub fn import_from_ssh_config(&mut self) -> Result<usize, ConfigError> {
-        let ssh_hosts = super::ssh_config::load_hosts_from_ssh_config()?;
+        let path = super::ssh_config::default_ssh_config_path()?;
+        match self.import_ssh_config(&path) {
+            Err(ConfigError::ReadFile { source, .. })
+                if source.kind() == std::io::ErrorKind::NotFound =>
+            {
+                Ok(0)
+            }
+            result => result,
+        }
+    }
+
+    /// Import hosts from an OpenSSH config file at `path`. `Host *` and other
+    /// wildcard blocks fill in defaults for matching aliases; hosts whose
+    /// name or endpoint already exists are skipped.
+    /// Returns the number of new hosts imported.
+    pub fn import_ssh_config(&mut self, path: &std::path::Path) -> Result<usize, ConfigError> {
+        let ssh_hosts = super::ssh_config::load_hosts_from_ssh_config_file(path)?;
         Ok(self.merge_imported_hosts(ssh_hosts))
     }
 
-    /// Merge imported hosts, skipping duplicates of existing hosts while
-    /// remapping jump-host links from skipped duplicates onto the existing
-    /// host entries so ProxyJump chains stay intact.
-    fn merge_imported_hosts(&mut self, ssh_hosts: Vec<Host>) -> usize {
-        fn endpoint_key(host: &Host) -> (String, u16, String) {
-            (
-                host.hostname.to_ascii_lowercase(),
-                host.port,
-                host.effective_username().to_ascii_lowercase(),
-            )
-        }
+    /// Render every SSH host as an OpenSSH config fragment. Importing the
+    /// result recreates the hosts' name, address, user, port, key path and
+    /// jump host.
+    pub fn export_ssh_config(&self) -> String {
+        super::ssh_config::hosts_to_ssh_config(&self.hosts)
+    }
 
+    /// Merge imported hosts, skipping duplicates of existing hosts (same
+    /// name, or same endpoint) while remapping jump-host links from skipped
+    /// duplicates onto the existing host entries so ProxyJump chains stay
+    /// intact.
+    fn merge_imported_hosts(&mut self, ssh_hosts: Vec<Host>) -> usize {
         let existing_by_key: std::collections::HashMap<(String, u16, String), Uuid> = self
             .hosts
             .iter()
-            .map(|host| (endpoint_key(host), host.id))
+            .map(|host| (host.endpoint_key(), host.id))
+            .collect();
+        let existing_by_name: std::collections::HashMap<String, Uuid> = self
+            .hosts
+            .iter()
+            .map(|host| (host.name.clone(), host.id))
             .collect();
 
         // Imported host IDs that collide with existing hosts map to the
@@ -620,7 +983,8 @@ impl HostsConfig {
             .iter()
             .filter_map(|host| {
                 existing_by_key
-                    .get(&endpoint_key(host))
+                    .get(&host.endpoint_key())
+                    .or_else(|| existing_by_name.get(&host.name))
                     .map(|existing_id| (host.id, *existing_id))
             })
             .collect();
@@ -628,6 +992,8 @@ impl HostsConfig {
         let mut imported = 0usize;
         let mut seen: std::collections::HashSet<(String, u16, String)> =
             existing_by_key.keys().cloned().collect();
+        let mut seen_names: std::collections::HashSet<String> =
+            existing_by_name.keys().cloned().collect();
 
         for mut host in ssh_hosts {
             if let Some(jump_id) = host.jump_host_id
@@ -636,12 +1002,13 @@ impl HostsConfig {
                 host.jump_host_id = Some(*existing_id);
             }
 
-            let key = endpoint_key(&host);
-            if seen.contains(&key) {
+            let key = host.endpoint_key();
+            if seen.contains(&key) || seen_names.contains(&host.name) {
                 continue;
             }
             seen.insert(key);
-            self.hosts.push(host);
+            seen_names.insert(host.name.clone());
+            self.add_host(host);
             imported += 1;
         }
 
@@ -667,11 +1034,19 @@ mod tests {
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
             auth: AuthMethod::Agent,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Auto,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at: now,
@@ -689,6 +1064,7 @@ mod tests {
         let mut config = HostsConfig {
             hosts: vec![kept.clone(), deleted],
             groups: Vec::new(),
+            ..Default::default()
         };
 
         let removed = config.remove_host(deleted_id).unwrap();
@@ -708,6 +1084,131 @@ mod tests {
         assert!(matches!(error, ConfigError::HostNotFound(id) if id == missing_id));
     }
 
+    fn test_group(name: &str, parent_id: Option<Uuid>) -> HostGroup {
+        HostGroup {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            parent_id,
+            collapsed: false,
+            order: 0,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn names<'a>(hosts: impl IntoIterator<Item = &'a Host>) -> Vec<&'a str> {
+        hosts.into_iter().map(|host| host.name.as_str()).collect()
+    }
+
+    #[test]
+    fn move_host_takes_the_slot_and_renumbers_both_groups() {
+        let group = test_group("Web", None);
+        let group_id = group.id;
+        let mut config = HostsConfig {
+            hosts: ["a", "b", "c"].map(test_host).to_vec(),
+            groups: vec![group],
+            ..Default::default()
+        };
+        let (a, c) = (config.hosts[0].id, config.hosts[2].id);
+
+        // Dragging "a" onto "c" puts it after "c"
+        config.move_host(a, None, 2).unwrap();
+        assert_eq!(names(config.hosts_in_group(None)), ["b", "c", "a"]);
+
+        config.move_host(c, Some(group_id), usize::MAX).unwrap();
+        assert_eq!(names(config.hosts_in_group(None)), ["b", "a"]);
+        assert_eq!(names(config.hosts_in_group(Some(group_id))), ["c"]);
+        assert_eq!(config.find_host(a).unwrap().order, 1);
+
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            config.move_host(a, Some(missing), 0),
+            Err(ConfigError::GroupNotFound(id)) if id == missing
+        ));
+    }
+
+    #[test]
+    fn move_group_nests_and_refuses_cycles() {
+        let outer = test_group("Outer", None);
+        let inner = test_group("Inner", None);
+        let other = test_group("Other", None);
+        let (outer_id, inner_id, other_id) = (outer.id, inner.id, other.id);
+        let mut config = HostsConfig {
+            hosts: Vec::new(),
+            groups: vec![outer, inner, other],
+            ..Default::default()
+        };
+
+        config.move_group(inner_id, Some(outer_id), 0).unwrap();
+        config.move_group(other_id, None, 0).unwrap();
+        let tree: Vec<(&str, usize)> = config
+            .group_tree()
+            .into_iter()
+            .map(|(group, depth)| (group.name.as_str(), depth))
+            .collect();
+        assert_eq!(tree, [("Other", 0), ("Outer", 0), ("Inner", 1)]);
+
+        assert!(config.is_group_within(inner_id, outer_id));
+        assert!(matches!(
+            config.move_group(outer_id, Some(inner_id), 0),
+            Err(ConfigError::GroupCycle(id)) if id == outer_id
+        ));
+        assert!(config.move_group(outer_id, Some(outer_id), 0).is_err());
+    }
+
+    #[test]
+    fn groups_with_a_missing_parent_are_top_level() {
+        let orphan = test_group("Orphan", Some(Uuid::new_v4()));
+        let config = HostsConfig {
+            hosts: Vec::new(),
+            groups: vec![orphan],
+            ..Default::default()
+        };
+
+        assert_eq!(config.child_groups(None).len(), 1);
+        assert_eq!(config.group_tree()[0].1, 0);
+    }
+
+    #[test]
+    fn find_duplicate_matches_endpoint_ignoring_case() {
+        let existing = test_host("Web");
+        let existing_id = existing.id;
+        let config = HostsConfig {
+            hosts: vec![existing],
+            groups: Vec::new(),
+            ..Default::default()
+        };
+
+        let mut candidate = test_host("Other name");
+        candidate.hostname = "WEB.example.test".to_string();
+        assert_eq!(
+            config.find_duplicate(&candidate).map(|host| host.id),
+            Some(existing_id)
+        );
+
+        candidate.port = 2222;
+        assert!(config.find_duplicate(&candidate).is_none());
+        assert!(config.find_duplicate(&config.hosts[0]).is_none());
+    }
+
+    #[test]
+    fn duplicate_host_inserts_a_copy_after_the_original() {
+        let mut config = HostsConfig {
+            hosts: ["a", "b"].map(test_host).to_vec(),
+            groups: Vec::new(),
+            ..Default::default()
+        };
+        let original_id = config.hosts[0].id;
+        config.hosts[0].vnc_password_id = Some(Uuid::new_v4());
+
+        let copy_id = config.duplicate_host(original_id).unwrap();
+
+        let copy = config.find_host(copy_id).unwrap();
+        assert_ne!(copy_id, original_id);
+        assert_eq!(copy.hostname, "a.example.test");
+        assert!(copy.vnc_password_id.is_none());
+        assert_eq!(names(config.hosts_in_group(None)), ["a", "a (copy)", "b"]);
+    }
+
     // === DetectedOs::from_uname tests ===
 
     #[test]
@@ -823,6 +1324,40 @@ type = "agent"
         assert_eq!(host.jump_host_id, None);
     }
 
+    #[test]
+    fn auth_fallbacks_round_trip_in_order() {
+        let mut host = test_host("Fallbacks");
+        assert!(!toml::to_string(&host).unwrap().contains("auth_fallbacks"));
+        assert!(!host.needs_password());
+
+        host.auth_fallbacks = vec![
+            AuthMethod::PublicKey {
+                key_path: Some(PathBuf::from("~/.ssh/id_work")),
+                vault_key_id: None,
+            },
+            AuthMethod::Password,
+        ];
+        let parsed: Host = toml::from_str(&toml::to_string(&host).unwrap()).unwrap();
+
+        assert_eq!(parsed.auth_fallbacks, host.auth_fallbacks);
+        assert_eq!(parsed.auth_sequence().count(), 3);
+        assert!(!parsed.needs_password());
+        assert!(parsed.has_password_fallback());
+    }
+
+    #[test]
+    fn keepalive_overrides_round_trip() {
+        let mut host = test_host("Keepalive");
+        assert!(!toml::to_string(&host).unwrap().contains("keepalive"));
+
+        host.keepalive_interval = Some(15);
+        host.keepalive_count_max = Some(4);
+        let parsed: Host = toml::from_str(&toml::to_string(&host).unwrap()).unwrap();
+
+        assert_eq!(parsed.keepalive_interval, Some(15));
+        assert_eq!(parsed.keepalive_count_max, Some(4));
+    }
+
     #[test]
     fn host_without_jump_host_serializes_without_field() {
         let host = test_host("NoJump");
@@ -844,6 +1379,31 @@ type = "agent"
         assert_eq!(parsed.jump_host_id, Some(jump_id));
     }
 
+    #[test]
+    fn host_sftp_start_path_round_trips() {
+        let mut host = test_host("Logs");
+        assert!(!toml::to_string(&host).unwrap().contains("sftp_start_path"));
+
+        host.sftp_start_path = Some("/var/log".to_string());
+        let serialized = toml::to_string(&host).unwrap();
+        let parsed: Host = toml::from_str(&serialized).unwrap();
+        assert_eq!(parsed.sftp_start_path.as_deref(), Some("/var/log"));
+    }
+
+    #[test]
+    fn host_last_sftp_path_round_trips() {
+        let mut host = test_host("Logs");
+        assert!(!toml::to_string(&host).unwrap().contains("last_sftp_path"));
+
+        host.last_sftp_path = Some(PathBuf::from("/srv/app/releases"));
+        let serialized = toml::to_string(&host).unwrap();
+        let parsed: Host = toml::from_str(&serialized).unwrap();
+        assert_eq!(
+            parsed.last_sftp_path,
+            Some(PathBuf::from("/srv/app/releases"))
+        );
+    }
+
     #[test]
     fn legacy_host_without_vnc_security_fields_deserializes_cleanly() {
         // Old hosts.toml files predate vnc_via_ssh_host_id and
@@ -912,6 +1472,7 @@ type = "agent"
         let mut config = HostsConfig {
             hosts: vec![existing_bastion],
             groups: Vec::new(),
+            ..Default::default()
         };
 
         // Imported set: a duplicate bastion plus a target jumping through it.
@@ -929,6 +1490,103 @@ type = "agent"
         assert_eq!(target.jump_host_id, Some(existing_id));
     }
 
+    #[test]
+    fn merge_imported_hosts_skips_existing_names() {
+        let mut config = HostsConfig {
+            hosts: vec![test_host("web")],
+            groups: Vec::new(),
+            ..Default::default()
+        };
+
+        // Same name, different endpoint: still a duplicate.
+        let mut renamed_endpoint = test_host("web");
+        renamed_endpoint.hostname = "web2.example.test".to_string();
+        let imported = config.merge_imported_hosts(vec![renamed_endpoint, test_host("db")]);
+
+        assert_eq!(imported, 1);
+        assert_eq!(config.hosts.len(), 2);
+        assert!(config.hosts.iter().any(|h| h.name == "db"));
+    }
+
+    #[test]
+    fn import_ssh_config_applies_wildcard_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config");
+        std::fs::write(
+            &path,
+            "Host web\n  HostName web.example.test\n\nHost *\n  User deploy\n  Port 2222\n",
+        )
+        .unwrap();
+
+        let mut config = HostsConfig::default();
+        let imported = config.import_ssh_config(&path).unwrap();
+
+        assert_eq!(imported, 1);
+        let web = &config.hosts[0];
+        assert_eq!(web.hostname, "web.example.test");
+        assert_eq!(web.username, "deploy");
+        assert_eq!(web.port, 2222);
+    }
+
+    #[test]
+    fn export_ssh_config_round_trips_through_import() {
+        let mut bastion = test_host("bastion");
+        bastion.username = "jump".to_string();
+        bastion.port = 2222;
+        bastion.auth = AuthMethod::Agent;
+        let mut app = test_host("app");
+        app.username = "deploy".to_string();
+        app.port = 2200;
+        app.auth = AuthMethod::PublicKey {
+            key_path: Some(PathBuf::from("/keys/app key")),
+            vault_key_id: None,
+        };
+        app.jump_host_id = Some(bastion.id);
+        let exported = HostsConfig {
+            hosts: vec![bastion, app],
+            groups: Vec::new(),
+            ..Default::default()
+        }
+        .export_ssh_config();
+        assert!(exported.contains("    IdentitiesOnly no\n"));
+        assert!(exported.contains("    ProxyJump bastion\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config");
+        std::fs::write(&path, &exported).unwrap();
+        let mut config = HostsConfig::default();
+        assert_eq!(config.import_ssh_config(&path).unwrap(), 2);
+
+        let bastion = config.hosts.iter().find(|h| h.name == "bastion").unwrap();
+        assert_eq!(bastion.hostname, "bastion.example.test");
+        assert_eq!(bastion.username, "jump");
+        assert_eq!(bastion.port, 2222);
+        assert!(matches!(bastion.auth, AuthMethod::Agent));
+        let app = config.hosts.iter().find(|h| h.name == "app").unwrap();
+        assert_eq!(app.hostname, "app.example.test");
+        assert_eq!(app.username, "deploy");
+        assert_eq!(app.port, 2200);
+        assert_eq!(
+            app.auth,
+            AuthMethod::PublicKey {
+                key_path: Some(PathBuf::from("/keys/app key")),
+                vault_key_id: None,
+            }
+        );
+        assert_eq!(app.jump_host_id, Some(bastion.id));
+    }
+
+    #[test]
+    fn import_ssh_config_reports_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = HostsConfig::default();
+        assert!(
+            config
+                .import_ssh_config(&dir.path().join("missing"))
+                .is_err()
+        );
+    }
+
     #[test]
     fn host_hub_routing_migrates_legacy_bool() {
         let enabled: Host =
@@ -940,6 +1598,30 @@ type = "agent"
         assert_eq!(disabled.hub_routing, HubRouting::Auto);
     }
 
+    #[test]
+    fn unversioned_hosts_file_migrates_to_current_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hosts.toml");
+        std::fs::write(
+            &path,
+            format!(
+                "[[hosts]]\nportal_hub_enabled = true\n{}",
+                HOST_TOML_TAIL.replace("[auth]", "[hosts.auth]")
+            ),
+        )
+        .unwrap();
+
+        let (config, migrated): (HostsConfig, bool) =
+            crate::config::load_versioned_toml(&path, "hosts").unwrap();
+
+        assert!(migrated);
+        assert_eq!(config.version, HostsConfig::SCHEMA_VERSION);
+        assert_eq!(config.hosts[0].hub_routing, HubRouting::Hub);
+        let saved = toml::to_string_pretty(&config).unwrap();
+        assert!(saved.starts_with("version = 1\n"));
+        assert!(!saved.contains("portal_hub_enabled"));
+    }
+
     #[test]
     fn host_hub_routing_roundtrips_enum_values() {
         for routing in HubRouting::ALL {
@@ -1498,4 +2180,23 @@ target_port = 80
         assert_eq!(PortForwardKind::Remote.to_string(), "Remote");
         assert_eq!(PortForwardKind::Dynamic.to_string(), "Dynamic");
     }
+
+    #[test]
+    fn port_forward_summary() {
+        let mut forward = PortForward {
+            id: Uuid::new_v4(),
+            kind: PortForwardKind::Local,
+            bind_host: "localhost".to_string(),
+            bind_port: 8080,
+            target_host: "db".to_string(),
+            target_port: 5432,
+            enabled: true,
+            description: None,
+        };
+        assert_eq!(forward.summary(), "Local localhost:8080 -> db:5432");
+
+        forward.kind = PortForwardKind::Dynamic;
+        forward.bind_port = 1080;
+        assert_eq!(forward.summary(), "Dynamic localhost:1080 (SOCKS)");
+    }
 }
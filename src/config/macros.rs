@@ -0,0 +1,271 @@
+//! Terminal input macros configuration
+//!
+//! A macro is a recorded sequence of terminal input that can be replayed into
+//! the active session, optionally with a pause between steps for programs
+//! that drop input arriving too quickly.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+use super::VersionedConfig;
+use crate::error::ConfigError;
+use crate::keybindings::Keybinding;
+
+/// Bytes sent to the terminal in one step.
+///
+/// Stored as a string when the bytes are valid UTF-8 (the common case, which
+/// keeps `macros.toml` editable) and as a byte array otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroInput(pub Vec<u8>);
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredInput {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Serialize for MacroInput {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match std::str::from_utf8(&self.0) {
+            Ok(text) => StoredInput::Text(text.to_string()),
+            Err(_) => StoredInput::Bytes(self.0.clone()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MacroInput {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match StoredInput::deserialize(deserializer)? {
+            StoredInput::Text(text) => MacroInput(text.into_bytes()),
+            StoredInput::Bytes(bytes) => MacroInput(bytes),
+        })
+    }
+}
+
+/// A recorded terminal input sequence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalMacro {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Display name
+    pub name: String,
+    /// Input in the order it was typed
+    #[serde(default)]
+    pub steps: Vec<MacroInput>,
+    /// Pause between steps on replay; 0 sends everything at once
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// Shortcut that replays the macro in the active terminal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keybinding: Option<Keybinding>,
+    /// Creation timestamp
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Last update timestamp
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TerminalMacro {
+    /// Create a new macro from recorded input
+    pub fn new(name: String, steps: Vec<Vec<u8>>) -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            steps: steps.into_iter().map(MacroInput).collect(),
+            delay_ms: 0,
+            keybinding: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Chunks to write on replay: one per step when a delay is set,
+    /// otherwise a single write with everything.
+    pub fn replay_chunks(&self) -> Vec<Vec<u8>> {
+        if self.delay_ms == 0 {
+            let all: Vec<u8> = self
+                .steps
+                .iter()
+                .flat_map(|step| step.0.iter().copied())
+                .collect();
+            if all.is_empty() {
+                Vec::new()
+            } else {
+                vec![all]
+            }
+        } else {
+            self.steps
+                .iter()
+                .filter(|step| !step.0.is_empty())
+                .map(|step| step.0.clone())
+                .collect()
+        }
+    }
+}
+
+/// Root configuration for macros.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacrosConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
+    /// All macros
+    #[serde(default)]
+    pub macros: Vec<TerminalMacro>,
+}
+
+impl Default for MacrosConfig {
+    fn default() -> Self {
+        Self {
+            version: Self::SCHEMA_VERSION,
+            macros: Vec::new(),
+        }
+    }
+}
+
+impl VersionedConfig for MacrosConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+}
+
+impl MacrosConfig {
+    /// Find a macro by ID
+    pub fn find_macro(&self, id: Uuid) -> Option<&TerminalMacro> {
+        self.macros.iter().find(|m| m.id == id)
+    }
+
+    /// Add a new macro
+    pub fn add_macro(&mut self, terminal_macro: TerminalMacro) {
+        self.macros.push(terminal_macro);
+    }
+
+    /// Macro bound to a key press, if any
+    pub fn find_by_keybinding(
+        &self,
+        key: &iced::keyboard::Key,
+        modifiers: &iced::keyboard::Modifiers,
+    ) -> Option<&TerminalMacro> {
+        self.macros.iter().find(|m| {
+            m.keybinding
+                .as_ref()
+                .is_some_and(|binding| binding.matches(key, modifiers))
+        })
+    }
+
+    /// Keys bound to macros; the terminal must not forward these to the PTY
+    pub fn keybindings(&self) -> Vec<Keybinding> {
+        self.macros
+            .iter()
+            .filter_map(|m| m.keybinding.clone())
+            .collect()
+    }
+
+    /// First "Macro N" name not already taken
+    pub fn next_default_name(&self) -> String {
+        (1..)
+            .map(|n| format!("Macro {}", n))
+            .find(|name| !self.macros.iter().any(|m| &m.name == name))
+            .expect("unbounded range yields a free name")
+    }
+
+    /// Load from file, creating default if not exists
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = super::paths::macros_file().ok_or_else(|| ConfigError::ReadFile {
+            path: std::path::PathBuf::from("macros.toml"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine macros file path",
+            ),
+        })?;
+
+        let (config, migrated): (Self, bool) = super::load_versioned_toml(&path, "macros")?;
+        if migrated && let Err(e) = config.save() {
+            tracing::warn!("Failed to save migrated macros: {}", e);
+        }
+        Ok(config)
+    }
+
+    /// Save to file
+    pub fn save(&self) -> Result<(), ConfigError> {
+        super::paths::ensure_config_dir().map_err(ConfigError::CreateDir)?;
+
+        let path = super::paths::macros_file().ok_or_else(|| ConfigError::WriteFile {
+            path: std::path::PathBuf::from("macros.toml"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine macros file path",
+            ),
+        })?;
+
+        let content = toml::to_string_pretty(self).map_err(ConfigError::Serialize)?;
+        super::write_atomic(&path, &content).map_err(|e| ConfigError::WriteFile { path, source: e })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_round_trip_as_text_or_bytes() {
+        let mut config = MacrosConfig::default();
+        let mut terminal_macro =
+            TerminalMacro::new("menu".to_string(), vec![b"3\r".to_vec(), vec![0xff, 0x1b]]);
+        terminal_macro.keybinding = Some(Keybinding::parse("Ctrl+Shift+1").unwrap());
+        config.add_macro(terminal_macro);
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let raw: toml::Value = toml::from_str(&toml).unwrap();
+        let steps = raw["macros"][0]["steps"].as_array().unwrap();
+        assert_eq!(steps[0].as_str(), Some("3\r"));
+        assert!(steps[1].is_array());
+
+        let parsed: MacrosConfig = toml::from_str(&toml).unwrap();
+        let parsed = &parsed.macros[0];
+        assert_eq!(parsed.steps[0].0, b"3\r");
+        assert_eq!(parsed.steps[1].0, vec![0xff, 0x1b]);
+        assert_eq!(
+            parsed
+                .keybinding
+                .as_ref()
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("Ctrl+Shift+1")
+        );
+    }
+
+    #[test]
+    fn replay_chunks_merge_steps_without_delay() {
+        let mut terminal_macro = TerminalMacro::new(
+            "repl".to_string(),
+            vec![b"a".to_vec(), Vec::new(), b"b\r".to_vec()],
+        );
+        assert_eq!(terminal_macro.replay_chunks(), vec![b"ab\r".to_vec()]);
+
+        terminal_macro.delay_ms = 50;
+        assert_eq!(
+            terminal_macro.replay_chunks(),
+            vec![b"a".to_vec(), b"b\r".to_vec()]
+        );
+    }
+
+    #[test]
+    fn default_names_skip_taken_ones() {
+        let mut config = MacrosConfig::default();
+        assert_eq!(config.next_default_name(), "Macro 1");
+        config.add_macro(TerminalMacro::new("Macro 1".to_string(), Vec::new()));
+        config.add_macro(TerminalMacro::new("Macro 3".to_string(), Vec::new()));
+        assert_eq!(config.next_default_name(), "Macro 2");
+    }
+}
@@ -1,13 +1,18 @@
 pub mod history;
 pub mod hosts;
+pub mod macros;
+pub mod open_sessions;
 pub mod paths;
 pub mod settings;
+pub mod sftp_bookmarks;
 pub mod snippet_history;
 pub mod snippets;
 pub mod ssh_config;
+pub mod terminal_profiles;
 
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use uuid::Uuid;
 
 use crate::error::ConfigError;
@@ -15,13 +20,24 @@ use crate::fs_utils;
 
 const CONFIG_FILE_MAX_BYTES: u64 = 8 * 1024 * 1024;
 
-pub use history::{HistoryConfig, HistoryEntry, SessionType};
+/// Temp and backup files from `write_atomic` older than this are swept at
+/// startup; a write in progress never lives this long
+pub const ATOMIC_LEFTOVER_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+pub use history::{
+    ExportFormat, HistoryConfig, HistoryDateRange, HistoryEntry, HistoryFilter, HistorySort,
+    SessionType,
+};
 pub use hosts::{
     AuthMethod, DetectedOs, Host, HostsConfig, PortForward, PortForwardKind, Protocol,
 };
+pub use macros::{MacrosConfig, TerminalMacro};
+pub use open_sessions::{OpenSessionsState, SavedSftpPane, SavedSftpTab};
 pub use settings::SettingsConfig;
+pub use sftp_bookmarks::{SftpBookmark, SftpBookmarksConfig};
 pub use snippet_history::{HistoricalHostResult, SnippetExecutionEntry, SnippetHistoryConfig};
 pub use snippets::{Snippet, SnippetsConfig};
+pub use terminal_profiles::{TerminalProfile, TerminalProfilesConfig};
 
 pub(crate) fn load_toml_or_recover<T>(path: &Path, label: &str) -> Result<T, ConfigError>
 where
@@ -33,7 +49,12 @@ where
         label,
     ) {
         Ok(content) => content,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(recover_interrupted_write(path, label, |content| {
+                toml::from_str(content).ok()
+            })
+            .unwrap_or_default());
+        }
         Err(source) if source.kind() == std::io::ErrorKind::FileTooLarge => {
             return Err(ConfigError::ReadFile {
                 path: path.to_path_buf(),
@@ -96,7 +117,7 @@ pub(crate) fn recover_corrupt_config(
         ));
     }
 
-    let backup_path = corrupt_backup_path(path);
+    let backup_path = set_aside_path(path, "corrupt");
     std::fs::rename(path, &backup_path)?;
     fs_utils::sync_parent_dir(path);
     tracing::warn!(
@@ -109,7 +130,7 @@ pub(crate) fn recover_corrupt_config(
     Ok(backup_path)
 }
 
-fn corrupt_backup_path(path: &Path) -> std::path::PathBuf {
+fn set_aside_path(path: &Path, tag: &str) -> std::path::PathBuf {
     let parent = path.parent().unwrap_or_else(|| Path::new("."));
     let file_name = path
         .file_name()
@@ -117,13 +138,225 @@ fn corrupt_backup_path(path: &Path) -> std::path::PathBuf {
         .unwrap_or("config");
     let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
     parent.join(format!(
-        "{}.corrupt-{}-{}",
+        "{}.{}-{}-{}",
         file_name,
+        tag,
         timestamp,
         Uuid::new_v4()
     ))
 }
 
+/// A config file whose layout carries a schema `version`.
+///
+/// Files written before versioning existed have no `version` and read as 0;
+/// `Default` must produce [`Self::SCHEMA_VERSION`] so fresh configs are not
+/// taken for old ones.
+pub(crate) trait VersionedConfig {
+    /// Schema version this build reads and writes
+    const SCHEMA_VERSION: u32;
+
+    /// The config's `version` field
+    fn schema_version_mut(&mut self) -> &mut u32;
+
+    /// Upgrade a config read with schema `from` to schema `from + 1`; steps
+    /// that only added fields with serde defaults have nothing to do
+    fn migrate_from(&mut self, _from: u32) {}
+}
+
+/// Load a versioned config, upgrading an older layout to the current schema.
+/// Returns the config and whether it was upgraded and should be saved back.
+///
+/// A file from a newer Portal is copied aside before being used, since the
+/// next save writes only the fields this build knows about.
+pub(crate) fn load_versioned_toml<T>(path: &Path, label: &str) -> Result<(T, bool), ConfigError>
+where
+    T: VersionedConfig + serde::de::DeserializeOwned + Default,
+{
+    let mut config: T = load_toml_or_recover(path, label)?;
+    let version = *config.schema_version_mut();
+
+    if version > T::SCHEMA_VERSION {
+        match preserve_future_config(path, version) {
+            Ok(copy) => tracing::warn!(
+                "{} config at {} has schema version {}, newer than {}; kept a copy at {}",
+                label,
+                path.display(),
+                version,
+                T::SCHEMA_VERSION,
+                copy.display()
+            ),
+            Err(error) => tracing::warn!(
+                "{} config at {} has schema version {}, newer than {}, and could not be copied: {}",
+                label,
+                path.display(),
+                version,
+                T::SCHEMA_VERSION,
+                error
+            ),
+        }
+        *config.schema_version_mut() = T::SCHEMA_VERSION;
+        return Ok((config, false));
+    }
+
+    if version == T::SCHEMA_VERSION {
+        return Ok((config, false));
+    }
+
+    for from in version..T::SCHEMA_VERSION {
+        config.migrate_from(from);
+    }
+    *config.schema_version_mut() = T::SCHEMA_VERSION;
+    tracing::info!(
+        "Migrated {} config at {} from schema version {} to {}",
+        label,
+        path.display(),
+        version,
+        T::SCHEMA_VERSION
+    );
+    Ok((config, true))
+}
+
+fn preserve_future_config(path: &Path, version: u32) -> std::io::Result<PathBuf> {
+    let copy = set_aside_path(path, &format!("v{}", version));
+    std::fs::copy(path, &copy)?;
+    fs_utils::sync_parent_dir(path);
+    Ok(copy)
+}
+
+/// Restore a missing `path` from the temp or backup files an interrupted
+/// `write_atomic` left next to it, taking the newest one `parse` accepts.
+///
+/// A write killed between moving the old file aside and moving the new one
+/// in leaves no config at all, only its `.tmp-` (new) and `.bak-` (old)
+/// copies.
+pub(crate) fn recover_interrupted_write<T>(
+    path: &Path,
+    label: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    let parent = path.parent()?;
+    let file_name = path.file_name()?.to_str()?;
+    let mut candidates: Vec<(SystemTime, PathBuf)> = std::fs::read_dir(parent)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            if atomic_leftover_target(name.to_str()?)? != file_name {
+                return None;
+            }
+            let metadata = std::fs::symlink_metadata(entry.path()).ok()?;
+            metadata.is_file().then(|| {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                (modified, entry.path())
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.0.cmp(&a.0));
+
+    for (_, candidate) in candidates {
+        let Ok(content) = fs_utils::read_regular_file_to_string_limited_io(
+            &candidate,
+            CONFIG_FILE_MAX_BYTES,
+            label,
+        ) else {
+            continue;
+        };
+        let Some(config) = parse(&content) else {
+            continue;
+        };
+        match std::fs::rename(&candidate, path) {
+            Ok(()) => {
+                fs_utils::sync_parent_dir(path);
+                tracing::warn!(
+                    "Restored {} config {} from {} left by an interrupted write",
+                    label,
+                    path.display(),
+                    candidate.display()
+                );
+            }
+            Err(error) => tracing::warn!(
+                "Using {} config from {}, but could not move it back to {}: {}",
+                label,
+                candidate.display(),
+                path.display(),
+                error
+            ),
+        }
+        return Some(config);
+    }
+    None
+}
+
+/// The file a `write_atomic` temp or backup file belongs to, for names of
+/// the form `.<file>.tmp-<uuid>` and `.<file>.bak-<uuid>`
+fn atomic_leftover_target(name: &str) -> Option<&str> {
+    let (target, suffix) = name.strip_prefix('.')?.rsplit_once('.')?;
+    let id = suffix
+        .strip_prefix("tmp-")
+        .or_else(|| suffix.strip_prefix("bak-"))?;
+    Uuid::parse_str(id).ok()?;
+    (!target.is_empty()).then_some(target)
+}
+
+/// Remove temp and backup files that interrupted `write_atomic` calls left
+/// in `dir` once they are older than `max_age`. Leftovers of a file that is
+/// missing are kept, since they are what it gets restored from. Returns how
+/// many files were removed.
+pub fn sweep_atomic_write_leftovers(dir: &Path, max_age: Duration) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(target) = name.to_str().and_then(atomic_leftover_target) else {
+            continue;
+        };
+        if std::fs::symlink_metadata(dir.join(target)).is_err() {
+            continue;
+        }
+        let Ok(metadata) = std::fs::symlink_metadata(entry.path()) else {
+            continue;
+        };
+        let is_stale = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= max_age);
+        if !metadata.is_file() || !is_stale {
+            continue;
+        }
+        match std::fs::remove_file(entry.path()) {
+            Ok(()) => removed += 1,
+            Err(error) => tracing::warn!(
+                "Failed to remove leftover {}: {}",
+                entry.path().display(),
+                error
+            ),
+        }
+    }
+    removed
+}
+
+/// Sweep stale `write_atomic` leftovers from Portal's config directory and
+/// the SSH directory, where known_hosts is written
+pub fn sweep_config_dirs() {
+    for dir in [paths::config_dir(), paths::ssh_dir()]
+        .into_iter()
+        .flatten()
+    {
+        let removed = sweep_atomic_write_leftovers(&dir, ATOMIC_LEFTOVER_MAX_AGE);
+        if removed > 0 {
+            tracing::info!(
+                "Removed {} leftover temp/backup file(s) from {}",
+                removed,
+                dir.display()
+            );
+        }
+    }
+}
+
 pub fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
     write_atomic_with(path, content.as_bytes(), write_and_sync)
 }
@@ -278,7 +511,8 @@ fn open_atomic_temp_file(path: &Path) -> std::io::Result<std::fs::File> {
 #[cfg(test)]
 mod tests {
     use super::{
-        CONFIG_FILE_MAX_BYTES, load_toml_or_recover, recover_corrupt_config, write_atomic,
+        ATOMIC_LEFTOVER_MAX_AGE, CONFIG_FILE_MAX_BYTES, VersionedConfig, load_toml_or_recover,
+        load_versioned_toml, recover_corrupt_config, sweep_atomic_write_leftovers, write_atomic,
         write_atomic_with, write_atomic_with_permissions,
     };
     use crate::error::ConfigError;
@@ -292,6 +526,41 @@ mod tests {
         value: u32,
     }
 
+    /// Schema 2 renamed `value` to `amount`
+    #[derive(Debug, Deserialize)]
+    struct VersionedTestConfig {
+        #[serde(default)]
+        version: u32,
+        #[serde(default)]
+        value: u32,
+        #[serde(default)]
+        amount: u32,
+    }
+
+    impl Default for VersionedTestConfig {
+        fn default() -> Self {
+            Self {
+                version: Self::SCHEMA_VERSION,
+                value: 0,
+                amount: 0,
+            }
+        }
+    }
+
+    impl VersionedConfig for VersionedTestConfig {
+        const SCHEMA_VERSION: u32 = 2;
+
+        fn schema_version_mut(&mut self) -> &mut u32 {
+            &mut self.version
+        }
+
+        fn migrate_from(&mut self, from: u32) {
+            if from == 1 {
+                self.amount = std::mem::take(&mut self.value);
+            }
+        }
+    }
+
     #[test]
     fn load_toml_or_recover_returns_default_for_missing_file() {
         let dir = tempdir().expect("temp dir");
@@ -444,6 +713,132 @@ mod tests {
         );
     }
 
+    /// Write `content` to `path` with a modification time `age` in the past
+    fn write_aged(path: &std::path::Path, content: &str, age: std::time::Duration) {
+        fs::write(path, content).expect("write file");
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .expect("open file")
+            .set_modified(std::time::SystemTime::now() - age)
+            .expect("set mtime");
+    }
+
+    #[test]
+    fn load_toml_or_recover_restores_the_newest_valid_leftover() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("settings.toml");
+        let minutes = |count| std::time::Duration::from_secs(count * 60);
+        // Killed between moving the old file aside and moving the new one in,
+        // while a later write only got as far as a partial temp file
+        let backup = dir
+            .path()
+            .join(format!(".settings.toml.bak-{}", uuid::Uuid::new_v4()));
+        let temp = dir
+            .path()
+            .join(format!(".settings.toml.tmp-{}", uuid::Uuid::new_v4()));
+        let partial = dir
+            .path()
+            .join(format!(".settings.toml.tmp-{}", uuid::Uuid::new_v4()));
+        write_aged(&backup, "value = 1\n", minutes(10));
+        write_aged(&temp, "value = 2\n", minutes(5));
+        write_aged(&partial, "value = ", minutes(1));
+
+        let config: TestConfig =
+            load_toml_or_recover(&path, "settings").expect("leftovers should recover");
+
+        assert_eq!(config.value, 2);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "value = 2\n");
+        assert!(!temp.exists());
+        assert!(backup.exists() && partial.exists());
+    }
+
+    #[test]
+    fn load_versioned_toml_runs_every_step_from_an_unversioned_file() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("settings.toml");
+        fs::write(&path, "value = 7\n").expect("write config");
+
+        let (config, migrated): (VersionedTestConfig, bool) =
+            load_versioned_toml(&path, "settings").expect("v0 config should load");
+
+        assert!(migrated);
+        assert_eq!(config.version, 2);
+        assert_eq!((config.value, config.amount), (0, 7));
+    }
+
+    #[test]
+    fn load_versioned_toml_leaves_missing_and_current_files_alone() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("settings.toml");
+
+        let (_, migrated): (VersionedTestConfig, bool) =
+            load_versioned_toml(&path, "settings").expect("missing config should default");
+        assert!(!migrated);
+
+        fs::write(&path, "version = 2\nvalue = 7\n").expect("write config");
+        let (config, migrated): (VersionedTestConfig, bool) =
+            load_versioned_toml(&path, "settings").expect("current config should load");
+        assert!(!migrated);
+        assert_eq!((config.value, config.amount), (7, 0));
+    }
+
+    #[test]
+    fn load_versioned_toml_keeps_a_copy_of_newer_files() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("settings.toml");
+        let content = "version = 5\namount = 3\nfuture_field = true\n";
+        fs::write(&path, content).expect("write config");
+
+        let (config, migrated): (VersionedTestConfig, bool) =
+            load_versioned_toml(&path, "settings").expect("newer config should load");
+
+        assert!(!migrated);
+        assert_eq!(config.version, 2);
+        assert_eq!(config.amount, 3);
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+        let copies: Vec<_> = fs::read_dir(dir.path())
+            .expect("read dir")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.starts_with("settings.toml.v5-"))
+            })
+            .collect();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(fs::read_to_string(copies[0].path()).unwrap(), content);
+    }
+
+    #[test]
+    fn sweep_removes_only_stale_leftovers_of_existing_files() {
+        let dir = tempdir().expect("temp dir");
+        let hours = |count| std::time::Duration::from_secs(count * 60 * 60);
+        fs::write(dir.path().join("hosts.toml"), "").expect("write hosts");
+        let stale = dir
+            .path()
+            .join(format!(".hosts.toml.bak-{}", uuid::Uuid::new_v4()));
+        let fresh = dir
+            .path()
+            .join(format!(".hosts.toml.tmp-{}", uuid::Uuid::new_v4()));
+        let orphan_of_missing = dir
+            .path()
+            .join(format!(".settings.toml.bak-{}", uuid::Uuid::new_v4()));
+        let unrelated = dir.path().join(".hosts.toml.bak-notes");
+        write_aged(&stale, "", hours(3));
+        write_aged(&fresh, "", std::time::Duration::ZERO);
+        write_aged(&orphan_of_missing, "", hours(3));
+        write_aged(&unrelated, "", hours(3));
+
+        assert_eq!(
+            sweep_atomic_write_leftovers(dir.path(), ATOMIC_LEFTOVER_MAX_AGE),
+            1
+        );
+        assert!(!stale.exists());
+        assert!(fresh.exists() && orphan_of_missing.exists() && unrelated.exists());
+    }
+
     #[test]
     fn recover_corrupt_config_rejects_directory() {
         let dir = tempdir().expect("temp dir");
@@ -0,0 +1,136 @@
+//! Open tabs saved for reopening after a restart
+//!
+//! Written whenever the set of open tabs changes (when session restore is
+//! enabled). Kept apart from `history.toml` so it can be cleared on its own.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::ConfigError;
+
+/// One pane of a saved SFTP tab
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSftpPane {
+    /// Host of a remote pane; `None` for the local file system
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_id: Option<Uuid>,
+    pub path: PathBuf,
+}
+
+/// A saved dual-pane SFTP tab
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSftpTab {
+    pub left: SavedSftpPane,
+    pub right: SavedSftpPane,
+}
+
+/// Root configuration for open_sessions.toml
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenSessionsState {
+    /// Hosts with an open terminal tab, in tab order
+    #[serde(default)]
+    pub terminals: Vec<Uuid>,
+    #[serde(default)]
+    pub sftp_tabs: Vec<SavedSftpTab>,
+}
+
+impl OpenSessionsState {
+    pub fn is_empty(&self) -> bool {
+        self.terminals.is_empty() && self.sftp_tabs.is_empty()
+    }
+
+    /// Number of tabs a restore would reopen
+    pub fn tab_count(&self) -> usize {
+        self.terminals.len() + self.sftp_tabs.len()
+    }
+
+    /// Drop entries whose hosts no longer exist
+    pub fn retain_known_hosts(&mut self, is_known: impl Fn(Uuid) -> bool) {
+        self.terminals.retain(|host_id| is_known(*host_id));
+        for tab in &mut self.sftp_tabs {
+            for pane in [&mut tab.left, &mut tab.right] {
+                if pane.host_id.is_some_and(|host_id| !is_known(host_id)) {
+                    pane.host_id = None;
+                    pane.path = PathBuf::new();
+                }
+            }
+        }
+    }
+
+    /// Load from file, returning an empty state if it does not exist
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = super::paths::open_sessions_file().ok_or_else(|| ConfigError::ReadFile {
+            path: PathBuf::from("open_sessions.toml"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine open sessions file path",
+            ),
+        })?;
+
+        super::load_toml_or_recover(&path, "open sessions")
+    }
+
+    /// Save to file
+    pub fn save(&self) -> Result<(), ConfigError> {
+        super::paths::ensure_config_dir().map_err(ConfigError::CreateDir)?;
+
+        let path = super::paths::open_sessions_file().ok_or_else(|| ConfigError::WriteFile {
+            path: PathBuf::from("open_sessions.toml"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine open sessions file path",
+            ),
+        })?;
+
+        let content = toml::to_string_pretty(self).map_err(ConfigError::Serialize)?;
+        super::write_atomic(&path, &content).map_err(|e| ConfigError::WriteFile { path, source: e })
+    }
+
+    /// Delete the saved state
+    pub fn clear() -> Result<(), ConfigError> {
+        let Some(path) = super::paths::open_sessions_file() else {
+            return Ok(());
+        };
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(source) => Err(ConfigError::WriteFile { path, source }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_forgets_deleted_hosts() {
+        let kept = Uuid::new_v4();
+        let deleted = Uuid::new_v4();
+        let mut state = OpenSessionsState {
+            terminals: vec![kept, deleted],
+            sftp_tabs: vec![SavedSftpTab {
+                left: SavedSftpPane {
+                    host_id: None,
+                    path: PathBuf::from("/home/me"),
+                },
+                right: SavedSftpPane {
+                    host_id: Some(deleted),
+                    path: PathBuf::from("/var/log"),
+                },
+            }],
+        };
+
+        let toml = toml::to_string_pretty(&state).unwrap();
+        let parsed: OpenSessionsState = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed, state);
+        assert_eq!(parsed.tab_count(), 3);
+
+        state.retain_known_hosts(|host_id| host_id == kept);
+        assert_eq!(state.terminals, vec![kept]);
+        assert_eq!(state.sftp_tabs[0].left.path, PathBuf::from("/home/me"));
+        assert_eq!(state.sftp_tabs[0].right.host_id, None);
+    }
+}
@@ -45,11 +45,31 @@ pub fn snippets_file() -> Option<PathBuf> {
     config_dir().map(|dir| dir.join("snippets.toml"))
 }
 
+/// Get the path to the terminal macros file
+pub fn macros_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("macros.toml"))
+}
+
+/// Get the path to the local terminal profiles file
+pub fn terminal_profiles_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("terminal_profiles.toml"))
+}
+
 /// Get the path to the history config file
 pub fn history_file() -> Option<PathBuf> {
     config_dir().map(|dir| dir.join("history.toml"))
 }
 
+/// Get the path to the saved open tabs file
+pub fn open_sessions_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("open_sessions.toml"))
+}
+
+/// Get the path to the SFTP bookmarks file
+pub fn sftp_bookmarks_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("sftp_bookmarks.toml"))
+}
+
 /// Get the path to the settings config file
 pub fn settings_file() -> Option<PathBuf> {
     config_dir().map(|dir| dir.join("settings.toml"))
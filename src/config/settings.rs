@@ -2,16 +2,31 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use url::{Host, Url};
 
+use super::VersionedConfig;
 use crate::error::ConfigError;
 use crate::fonts::TerminalFont;
 use crate::keybindings::KeybindingsConfig;
-use crate::theme::ThemeId;
+use crate::theme::{CustomTheme, ThemeId};
 use crate::views::sftp::ColumnWidths;
 
 pub const TERMINAL_SCROLL_SPEED_BASE: f32 = 2.0;
 pub const TERMINAL_SCROLL_SPEED_MIN: f32 = TERMINAL_SCROLL_SPEED_BASE * 0.25;
 pub const TERMINAL_SCROLL_SPEED_MAX: f32 = TERMINAL_SCROLL_SPEED_BASE * 3.0;
 
+pub const TERMINAL_FONT_SIZE_MIN: f32 = 6.0;
+pub const TERMINAL_FONT_SIZE_MAX: f32 = 48.0;
+
+pub const MIN_SCROLLBACK_LINES: usize = 1_000;
+pub const MAX_SCROLLBACK_LINES: usize = 100_000;
+/// Scrollback sizes above this get a memory warning in settings.
+pub const LARGE_SCROLLBACK_LINES: usize = 50_000;
+
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const MAX_CONNECT_TIMEOUT_SECS: u64 = 120;
+
+pub const MIN_CURSOR_BLINK_INTERVAL_MS: u64 = 200;
+pub const MAX_CURSOR_BLINK_INTERVAL_MS: u64 = 1_500;
+
 /// Ghostty-style terminal metric adjustment.
 ///
 /// Values are deltas: `2` means add 2 px, while `10%` means grow by 10%.
@@ -115,6 +130,24 @@ impl<'de> Deserialize<'de> for MetricAdjustment {
     }
 }
 
+/// How terminal text is shaped and colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalTextStyle {
+    /// Shape plain text so fonts can join sequences like `=>` into ligatures
+    pub ligatures: bool,
+    /// Draw bold text in the bright variant of the eight basic ANSI colors
+    pub bold_is_bright: bool,
+}
+
+impl Default for TerminalTextStyle {
+    fn default() -> Self {
+        Self {
+            ligatures: false,
+            bold_is_bright: default_terminal_bold_is_bright(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub struct TerminalMetricAdjustments {
@@ -177,6 +210,14 @@ pub enum VncQualityPreset {
 }
 
 impl VncQualityPreset {
+    pub const ALL: &[VncQualityPreset] = &[
+        VncQualityPreset::Auto,
+        VncQualityPreset::Speed,
+        VncQualityPreset::Balanced,
+        VncQualityPreset::Quality,
+        VncQualityPreset::Lossless,
+    ];
+
     pub const fn label(self) -> &'static str {
         match self {
             VncQualityPreset::Auto => "Auto",
@@ -188,6 +229,73 @@ impl VncQualityPreset {
     }
 }
 
+impl std::fmt::Display for VncQualityPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// How key presses in the VNC viewer become keysyms
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VncKeyboardMode {
+    /// Characters are sent as US-layout keysyms, with Shift applied locally
+    #[default]
+    Standard,
+    /// Every key goes to the server as its unshifted keysym, local shortcuts
+    /// included; the server applies its own layout
+    Passthrough,
+    /// Characters are sent as the Unicode keysym of what was typed, which
+    /// suits non-US layouts
+    Unicode,
+}
+
+impl VncKeyboardMode {
+    pub const ALL: &[VncKeyboardMode] = &[
+        VncKeyboardMode::Standard,
+        VncKeyboardMode::Passthrough,
+        VncKeyboardMode::Unicode,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            VncKeyboardMode::Standard => "US Keys",
+            VncKeyboardMode::Passthrough => "Grab KB",
+            VncKeyboardMode::Unicode => "Unicode",
+        }
+    }
+}
+
+impl std::fmt::Display for VncKeyboardMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// What happens when a terminal program rings the bell (`BEL`)
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminalBell {
+    /// Flash the terminal and mark its tab
+    #[default]
+    Visual,
+    /// Show a toast, or a desktop notification while the terminal is not in view
+    Notification,
+    Off,
+}
+
+/// Clipboard access granted to terminal programs through OSC 52
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TerminalClipboardAccess {
+    Off,
+    /// Programs may set the clipboard
+    #[default]
+    Copy,
+    /// Programs may also read the clipboard
+    CopyPaste,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
@@ -504,6 +612,10 @@ pub struct VncSettings {
     #[serde(default = "default_vnc_clipboard_sharing")]
     pub clipboard_sharing: bool,
 
+    /// Send local clipboard changes to the server without a paste shortcut
+    #[serde(default = "default_vnc_clipboard_auto_send")]
+    pub clipboard_auto_send: bool,
+
     /// Scaling mode for VNC display
     #[serde(default)]
     pub scaling_mode: VncScalingMode,
@@ -512,6 +624,10 @@ pub struct VncSettings {
     #[serde(default)]
     pub view_only: bool,
 
+    /// Keyboard mode new VNC sessions start in
+    #[serde(default)]
+    pub keyboard_mode: VncKeyboardMode,
+
     /// Show a local cursor position dot over the remote framebuffer
     #[serde(default = "default_vnc_show_cursor_dot")]
     pub show_cursor_dot: bool,
@@ -520,7 +636,7 @@ pub struct VncSettings {
     #[serde(default)]
     pub show_stats_overlay: bool,
 
-    /// Quality/performance preset used for new VNC connections
+    /// Quality/performance preset; switching it re-negotiates open sessions
     #[serde(default)]
     pub quality_preset: VncQualityPreset,
 }
@@ -534,8 +650,10 @@ impl Default for VncSettings {
             pointer_interval_ms: default_vnc_pointer_interval_ms(),
             remote_resize: default_vnc_remote_resize(),
             clipboard_sharing: default_vnc_clipboard_sharing(),
+            clipboard_auto_send: default_vnc_clipboard_auto_send(),
             scaling_mode: VncScalingMode::default(),
             view_only: false,
+            keyboard_mode: VncKeyboardMode::default(),
             show_cursor_dot: default_vnc_show_cursor_dot(),
             show_stats_overlay: false,
             quality_preset: VncQualityPreset::default(),
@@ -619,6 +737,17 @@ impl VncSettings {
         }
     }
 
+    /// JPEG quality level (0-9) requested from Tight servers; `None` leaves
+    /// it to the server, or keeps Tight lossless
+    pub fn effective_jpeg_quality(&self) -> Option<u8> {
+        match self.quality_preset {
+            VncQualityPreset::Speed => Some(3),
+            VncQualityPreset::Balanced => Some(6),
+            VncQualityPreset::Quality => Some(9),
+            VncQualityPreset::Auto | VncQualityPreset::Lossless => None,
+        }
+    }
+
     pub fn effective_refresh_fps(&self) -> u32 {
         match self.quality_preset {
             VncQualityPreset::Speed => self.refresh_fps.max(20),
@@ -649,6 +778,10 @@ fn default_vnc_clipboard_sharing() -> bool {
     true
 }
 
+fn default_vnc_clipboard_auto_send() -> bool {
+    true
+}
+
 fn default_vnc_show_cursor_dot() -> bool {
     true
 }
@@ -656,6 +789,10 @@ fn default_vnc_show_cursor_dot() -> bool {
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SettingsConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
+
     /// Terminal font size
     #[serde(default = "default_terminal_font_size")]
     pub terminal_font_size: f32,
@@ -672,10 +809,38 @@ pub struct SettingsConfig {
     #[serde(default, flatten)]
     pub terminal_metric_adjustments: TerminalMetricAdjustments,
 
+    /// Render programming ligatures in the terminal
+    #[serde(default)]
+    pub terminal_ligatures: bool,
+
+    /// Draw bold terminal text in bright colors
+    #[serde(default = "default_terminal_bold_is_bright")]
+    pub terminal_bold_is_bright: bool,
+
+    /// Blink the terminal cursor unless a program selects a steady style
+    #[serde(default = "default_cursor_blink")]
+    pub cursor_blink: bool,
+
+    /// Time the blinking cursor stays shown or hidden, in milliseconds
+    #[serde(default = "default_cursor_blink_interval_ms")]
+    pub cursor_blink_interval_ms: u64,
+
+    /// Response to the terminal bell
+    #[serde(default)]
+    pub terminal_bell: TerminalBell,
+
+    /// Clipboard access for terminal programs (OSC 52)
+    #[serde(default)]
+    pub terminal_clipboard: TerminalClipboardAccess,
+
     /// Selected theme
     #[serde(default)]
     pub theme: ThemeId,
 
+    /// User-defined color schemes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_themes: Vec<CustomTheme>,
+
     /// UI scale override (None = use system default)
     /// Range: 0.8 to 1.5 (80% to 150%)
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -685,6 +850,22 @@ pub struct SettingsConfig {
     #[serde(default)]
     pub sftp_column_widths: ColumnWidths,
 
+    /// Show dotfiles in SFTP panes
+    #[serde(default)]
+    pub sftp_show_hidden: bool,
+
+    /// Compare SHA-256 checksums of copied files with their source
+    #[serde(default)]
+    pub sftp_verify_transfers: bool,
+
+    /// Keep partial files of failed transfers and continue from them
+    #[serde(default = "default_sftp_resume_transfers")]
+    pub sftp_resume_transfers: bool,
+
+    /// Soft-wrap long lines in the text file viewer
+    #[serde(default = "default_viewer_word_wrap")]
+    pub viewer_word_wrap: bool,
+
     /// VNC settings
     #[serde(default)]
     pub vnc: VncSettings,
@@ -701,6 +882,14 @@ pub struct SettingsConfig {
     #[serde(default = "default_auto_reconnect")]
     pub auto_reconnect: bool,
 
+    /// Ask before quitting while sessions or transfers are running
+    #[serde(default = "default_confirm_quit")]
+    pub confirm_quit: bool,
+
+    /// Save open tabs and offer to reopen them on the next start
+    #[serde(default)]
+    pub restore_sessions: bool,
+
     /// Maximum number of reconnect attempts
     #[serde(default = "default_reconnect_max_attempts")]
     pub reconnect_max_attempts: u32,
@@ -713,10 +902,30 @@ pub struct SettingsConfig {
     #[serde(default = "default_reconnect_max_delay_ms")]
     pub reconnect_max_delay_ms: u64,
 
+    /// Terminal scrollback history size in lines, applied to new sessions
+    #[serde(default = "default_scrollback_lines")]
+    pub scrollback_lines: usize,
+
+    /// Seconds to wait for the TCP connect and SSH handshake before giving up
+    #[serde(default = "default_connect_timeout")]
+    pub connect_timeout: u64,
+
+    /// Seconds between SSH keepalive probes (0 = disabled)
+    #[serde(default = "default_keepalive_interval")]
+    pub keepalive_interval: u64,
+
+    /// Unanswered keepalive probes before an SSH session is dropped
+    #[serde(default = "default_keepalive_count_max")]
+    pub keepalive_count_max: u32,
+
     /// Allow SSH agent forwarding (global safety switch)
     #[serde(default = "default_allow_agent_forwarding")]
     pub allow_agent_forwarding: bool,
 
+    /// Probe the hosts shown in the host grid to mark them up or down
+    #[serde(default = "default_host_reachability")]
+    pub host_reachability: bool,
+
     /// Credential cache timeout in seconds (0 = disabled)
     ///
     /// Controls in-memory caching of sensitive SSH credentials (e.g. key passphrases).
@@ -747,6 +956,15 @@ pub struct SettingsConfig {
     #[serde(default)]
     pub session_log_format: SessionLogFormat,
 
+    /// Also record typed input in session logs; input at password prompts
+    /// is left out
+    #[serde(default)]
+    pub session_log_input: bool,
+
+    /// Size in MB at which a session log rolls over to a new file (0 = never)
+    #[serde(default = "default_session_log_max_size_mb")]
+    pub session_log_max_size_mb: u64,
+
     /// Enable security audit logging to file
     #[serde(default = "default_security_audit_enabled")]
     pub security_audit_enabled: bool,
@@ -767,10 +985,26 @@ fn default_terminal_scroll_speed() -> f32 {
     TERMINAL_SCROLL_SPEED_BASE
 }
 
+fn default_terminal_bold_is_bright() -> bool {
+    true
+}
+
+fn default_cursor_blink() -> bool {
+    true
+}
+
+fn default_cursor_blink_interval_ms() -> u64 {
+    600
+}
+
 fn default_auto_reconnect() -> bool {
     true
 }
 
+fn default_confirm_quit() -> bool {
+    true
+}
+
 fn default_reconnect_max_attempts() -> u32 {
     5
 }
@@ -783,10 +1017,38 @@ fn default_reconnect_max_delay_ms() -> u64 {
     30_000
 }
 
+fn default_scrollback_lines() -> usize {
+    10_000
+}
+
+fn default_connect_timeout() -> u64 {
+    DEFAULT_CONNECT_TIMEOUT_SECS
+}
+
+fn default_keepalive_interval() -> u64 {
+    60
+}
+
+fn default_keepalive_count_max() -> u32 {
+    3
+}
+
 fn default_allow_agent_forwarding() -> bool {
     true
 }
 
+fn default_host_reachability() -> bool {
+    true
+}
+
+fn default_sftp_resume_transfers() -> bool {
+    true
+}
+
+fn default_viewer_word_wrap() -> bool {
+    true
+}
+
 fn default_credential_timeout() -> u64 {
     300 // 5 minutes
 }
@@ -795,6 +1057,10 @@ fn default_session_logging_enabled() -> bool {
     false
 }
 
+fn default_session_log_max_size_mb() -> u64 {
+    10
+}
+
 fn default_session_log_dir() -> Option<PathBuf> {
     crate::config::paths::config_dir().map(|dir| dir.join("logs").join("sessions"))
 }
@@ -810,38 +1076,93 @@ fn default_security_audit_dir() -> Option<PathBuf> {
 impl Default for SettingsConfig {
     fn default() -> Self {
         Self {
+            version: Self::SCHEMA_VERSION,
             terminal_font_size: default_terminal_font_size(),
             terminal_scroll_speed: default_terminal_scroll_speed(),
             terminal_font: TerminalFont::default(),
             terminal_metric_adjustments: TerminalMetricAdjustments::default(),
+            terminal_ligatures: false,
+            terminal_bold_is_bright: default_terminal_bold_is_bright(),
+            cursor_blink: default_cursor_blink(),
+            cursor_blink_interval_ms: default_cursor_blink_interval_ms(),
+            terminal_bell: TerminalBell::default(),
+            terminal_clipboard: TerminalClipboardAccess::default(),
             theme: ThemeId::default(),
+            custom_themes: Vec::new(),
             ui_scale: None,
             sftp_column_widths: ColumnWidths::default(),
+            sftp_show_hidden: false,
+            sftp_verify_transfers: false,
+            sftp_resume_transfers: default_sftp_resume_transfers(),
+            viewer_word_wrap: default_viewer_word_wrap(),
             vnc: VncSettings::default(),
             keybindings: KeybindingsConfig::default(),
             portal_hub: PortalHubSettings::default(),
             auto_reconnect: default_auto_reconnect(),
+            confirm_quit: default_confirm_quit(),
+            restore_sessions: false,
             reconnect_max_attempts: default_reconnect_max_attempts(),
             reconnect_base_delay_ms: default_reconnect_base_delay_ms(),
             reconnect_max_delay_ms: default_reconnect_max_delay_ms(),
+            scrollback_lines: default_scrollback_lines(),
+            connect_timeout: default_connect_timeout(),
+            keepalive_interval: default_keepalive_interval(),
+            keepalive_count_max: default_keepalive_count_max(),
             allow_agent_forwarding: default_allow_agent_forwarding(),
+            host_reachability: default_host_reachability(),
             credential_timeout: default_credential_timeout(),
             dark_mode: None,
             session_logging_enabled: default_session_logging_enabled(),
             session_log_dir: default_session_log_dir(),
             session_log_format: SessionLogFormat::default(),
+            session_log_input: false,
+            session_log_max_size_mb: default_session_log_max_size_mb(),
             security_audit_enabled: default_security_audit_enabled(),
             security_audit_dir: default_security_audit_dir(),
         }
     }
 }
 
+impl VersionedConfig for SettingsConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+
+    fn migrate_from(&mut self, from: u32) {
+        if from == 0 {
+            // The dark_mode bool became the theme field
+            if let Some(dark_mode) = self.dark_mode.take() {
+                self.theme = if dark_mode {
+                    ThemeId::PortalDefault
+                } else {
+                    ThemeId::CatppuccinLatte
+                };
+            }
+        }
+    }
+}
+
 impl SettingsConfig {
+    pub fn terminal_text_style(&self) -> TerminalTextStyle {
+        TerminalTextStyle {
+            ligatures: self.terminal_ligatures,
+            bold_is_bright: self.terminal_bold_is_bright,
+        }
+    }
+
+    pub fn set_terminal_text_style(&mut self, style: TerminalTextStyle) {
+        self.terminal_ligatures = style.ligatures;
+        self.terminal_bold_is_bright = style.bold_is_bright;
+    }
+
     fn normalize_loaded_values(&mut self) -> bool {
         let mut changed = false;
 
         let terminal_font_size = if self.terminal_font_size.is_finite() {
-            self.terminal_font_size.clamp(6.0, 48.0)
+            self.terminal_font_size
+                .clamp(TERMINAL_FONT_SIZE_MIN, TERMINAL_FONT_SIZE_MAX)
         } else {
             default_terminal_font_size()
         };
@@ -865,6 +1186,14 @@ impl SettingsConfig {
             changed = true;
         }
 
+        let cursor_blink_interval_ms = self
+            .cursor_blink_interval_ms
+            .clamp(MIN_CURSOR_BLINK_INTERVAL_MS, MAX_CURSOR_BLINK_INTERVAL_MS);
+        if self.cursor_blink_interval_ms != cursor_blink_interval_ms {
+            self.cursor_blink_interval_ms = cursor_blink_interval_ms;
+            changed = true;
+        }
+
         let ui_scale = self.ui_scale.and_then(|scale| {
             if scale.is_finite() {
                 Some(scale.clamp(0.8, 1.5))
@@ -897,6 +1226,32 @@ impl SettingsConfig {
             changed = true;
         }
 
+        let scrollback_lines = self
+            .scrollback_lines
+            .clamp(MIN_SCROLLBACK_LINES, MAX_SCROLLBACK_LINES);
+        if self.scrollback_lines != scrollback_lines {
+            self.scrollback_lines = scrollback_lines;
+            changed = true;
+        }
+
+        let connect_timeout = self.connect_timeout.clamp(1, MAX_CONNECT_TIMEOUT_SECS);
+        if self.connect_timeout != connect_timeout {
+            self.connect_timeout = connect_timeout;
+            changed = true;
+        }
+
+        let keepalive_interval = self.keepalive_interval.min(3600);
+        if self.keepalive_interval != keepalive_interval {
+            self.keepalive_interval = keepalive_interval;
+            changed = true;
+        }
+
+        let keepalive_count_max = self.keepalive_count_max.clamp(1, 10);
+        if self.keepalive_count_max != keepalive_count_max {
+            self.keepalive_count_max = keepalive_count_max;
+            changed = true;
+        }
+
         let credential_timeout = self.credential_timeout.min(3600);
         if self.credential_timeout != credential_timeout {
             self.credential_timeout = credential_timeout;
@@ -933,18 +1288,8 @@ impl SettingsConfig {
             ),
         })?;
 
-        let mut config: Self = super::load_toml_or_recover(&path, "settings")?;
-        let mut needs_save = false;
-
-        // Migration: convert old dark_mode to new theme field
-        if let Some(dark_mode) = config.dark_mode.take() {
-            config.theme = if dark_mode {
-                ThemeId::PortalDefault
-            } else {
-                ThemeId::CatppuccinLatte
-            };
-            needs_save = true;
-        }
+        let (mut config, mut needs_save): (Self, bool) =
+            super::load_versioned_toml(&path, "settings")?;
 
         // Migration: convert old proportion-based column widths to pixel-based
         // Old values were typically 4-20, new values should be 60+ pixels
@@ -1016,6 +1361,18 @@ adjust-icon-height = "10%"
         ));
     }
 
+    #[test]
+    fn sftp_show_hidden_defaults_off_and_round_trips() {
+        let config: SettingsConfig = toml::from_str("terminal_font_size = 13.0").unwrap();
+        assert!(!config.sftp_show_hidden);
+
+        let mut config = SettingsConfig::default();
+        config.sftp_show_hidden = true;
+        let parsed: SettingsConfig =
+            toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert!(parsed.sftp_show_hidden);
+    }
+
     #[test]
     fn portal_hub_settings_roundtrip() {
         let config: SettingsConfig = toml::from_str(
@@ -1146,6 +1503,10 @@ ui_scale = 9.0
 reconnect_max_attempts = 0
 reconnect_base_delay_ms = 1
 reconnect_max_delay_ms = 10
+scrollback_lines = 10
+connect_timeout = 0
+keepalive_interval = 999999
+keepalive_count_max = 0
 credential_timeout = 999999
 
 [vnc]
@@ -1163,6 +1524,10 @@ pointer_interval_ms = 999999
         assert_eq!(config.reconnect_max_attempts, 1);
         assert_eq!(config.reconnect_base_delay_ms, 500);
         assert_eq!(config.reconnect_max_delay_ms, 500);
+        assert_eq!(config.scrollback_lines, MIN_SCROLLBACK_LINES);
+        assert_eq!(config.connect_timeout, 1);
+        assert_eq!(config.keepalive_interval, 3600);
+        assert_eq!(config.keepalive_count_max, 1);
         assert_eq!(config.credential_timeout, 3600);
         assert_eq!(config.vnc.color_depth, 32);
         assert_eq!(config.vnc.refresh_fps, 1);
@@ -1224,4 +1589,37 @@ ui_scale = nan
         let result: Result<Wrapper, _> = toml::from_str(r#"value = "inf%""#);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn unversioned_settings_file_migrates_to_current_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.toml");
+        std::fs::write(&path, "dark_mode = false\nterminal_font_size = 15.0\n").unwrap();
+
+        let (config, migrated): (SettingsConfig, bool) =
+            crate::config::load_versioned_toml(&path, "settings").unwrap();
+
+        assert!(migrated);
+        assert_eq!(config.version, SettingsConfig::SCHEMA_VERSION);
+        assert_eq!(config.theme, ThemeId::CatppuccinLatte);
+        assert_eq!(config.dark_mode, None);
+        assert_eq!(config.terminal_font_size, 15.0);
+    }
+
+    #[test]
+    fn current_settings_file_is_not_migrated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.toml");
+        std::fs::write(
+            &path,
+            toml::to_string_pretty(&SettingsConfig::default()).unwrap(),
+        )
+        .unwrap();
+
+        let (config, migrated): (SettingsConfig, bool) =
+            crate::config::load_versioned_toml(&path, "settings").unwrap();
+
+        assert!(!migrated);
+        assert_eq!(config.version, SettingsConfig::SCHEMA_VERSION);
+    }
 }
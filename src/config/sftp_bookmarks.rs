@@ -0,0 +1,205 @@
+//! Bookmarked SFTP directories
+//!
+//! Each bookmark belongs to one host, or to the local file system when
+//! `host_id` is `None`, and only shows up in panes browsing that source.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::VersionedConfig;
+use crate::error::ConfigError;
+
+/// A directory the user can jump back to from the pane header
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SftpBookmark {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Host the path lives on; `None` for the local file system
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_id: Option<Uuid>,
+    /// Display name, defaulting to the directory name
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl SftpBookmark {
+    pub fn new(host_id: Option<Uuid>, path: PathBuf) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Self {
+            id: Uuid::new_v4(),
+            host_id,
+            name,
+            path,
+        }
+    }
+}
+
+/// Root configuration for sftp_bookmarks.toml
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SftpBookmarksConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub bookmarks: Vec<SftpBookmark>,
+}
+
+impl Default for SftpBookmarksConfig {
+    fn default() -> Self {
+        Self {
+            version: Self::SCHEMA_VERSION,
+            bookmarks: Vec::new(),
+        }
+    }
+}
+
+impl VersionedConfig for SftpBookmarksConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+}
+
+impl SftpBookmarksConfig {
+    /// Bookmarks for one source, in the order they were added
+    pub fn for_host(&self, host_id: Option<Uuid>) -> impl Iterator<Item = &SftpBookmark> {
+        self.bookmarks
+            .iter()
+            .filter(move |bookmark| bookmark.host_id == host_id)
+    }
+
+    /// Bookmark for `path` on a source, if there is one
+    pub fn find_path(&self, host_id: Option<Uuid>, path: &Path) -> Option<&SftpBookmark> {
+        self.for_host(host_id)
+            .find(|bookmark| bookmark.path == path)
+    }
+
+    /// Bookmark `path`, or remove its bookmark when it already has one.
+    /// Returns true when a bookmark was added.
+    pub fn toggle(&mut self, host_id: Option<Uuid>, path: PathBuf) -> bool {
+        if let Some(id) = self.find_path(host_id, &path).map(|bookmark| bookmark.id) {
+            self.remove(id);
+            false
+        } else {
+            self.bookmarks.push(SftpBookmark::new(host_id, path));
+            true
+        }
+    }
+
+    /// Rename a bookmark; returns false if it does not exist
+    pub fn rename(&mut self, id: Uuid, name: String) -> bool {
+        match self.bookmarks.iter_mut().find(|bookmark| bookmark.id == id) {
+            Some(bookmark) => {
+                bookmark.name = name;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a bookmark by ID
+    pub fn remove(&mut self, id: Uuid) -> Option<SftpBookmark> {
+        let index = self
+            .bookmarks
+            .iter()
+            .position(|bookmark| bookmark.id == id)?;
+        Some(self.bookmarks.remove(index))
+    }
+
+    /// Drop bookmarks whose hosts no longer exist
+    pub fn retain_known_hosts(&mut self, is_known: impl Fn(Uuid) -> bool) {
+        self.bookmarks
+            .retain(|bookmark| bookmark.host_id.is_none_or(&is_known));
+    }
+
+    /// Load from file, returning an empty config if it does not exist
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = super::paths::sftp_bookmarks_file().ok_or_else(|| ConfigError::ReadFile {
+            path: PathBuf::from("sftp_bookmarks.toml"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine SFTP bookmarks file path",
+            ),
+        })?;
+
+        let (config, migrated): (Self, bool) = super::load_versioned_toml(&path, "SFTP bookmarks")?;
+        if migrated && let Err(e) = config.save() {
+            tracing::warn!("Failed to save migrated SFTP bookmarks: {}", e);
+        }
+        Ok(config)
+    }
+
+    /// Save to file
+    pub fn save(&self) -> Result<(), ConfigError> {
+        super::paths::ensure_config_dir().map_err(ConfigError::CreateDir)?;
+
+        let path = super::paths::sftp_bookmarks_file().ok_or_else(|| ConfigError::WriteFile {
+            path: PathBuf::from("sftp_bookmarks.toml"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine SFTP bookmarks file path",
+            ),
+        })?;
+
+        let content = toml::to_string_pretty(self).map_err(ConfigError::Serialize)?;
+        super::write_atomic(&path, &content).map_err(|e| ConfigError::WriteFile { path, source: e })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_is_scoped_to_source_and_round_trips() {
+        let host = Uuid::new_v4();
+        let mut config = SftpBookmarksConfig::default();
+
+        assert!(config.toggle(None, PathBuf::from("/srv/www")));
+        assert!(config.toggle(Some(host), PathBuf::from("/srv/www")));
+        assert_eq!(config.for_host(None).count(), 1);
+        assert_eq!(config.for_host(Some(host)).count(), 1);
+        assert_eq!(config.bookmarks[0].name, "www");
+
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let parsed: SftpBookmarksConfig = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed, config);
+
+        assert!(!config.toggle(None, PathBuf::from("/srv/www")));
+        assert!(config.find_path(None, Path::new("/srv/www")).is_none());
+        assert!(
+            config
+                .find_path(Some(host), Path::new("/srv/www"))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn rename_remove_and_forget_deleted_hosts() {
+        let kept = Uuid::new_v4();
+        let deleted = Uuid::new_v4();
+        let mut config = SftpBookmarksConfig::default();
+        config.toggle(Some(kept), PathBuf::from("/var/log"));
+        config.toggle(Some(deleted), PathBuf::from("/etc"));
+        config.toggle(None, PathBuf::from("/"));
+
+        let id = config.bookmarks[0].id;
+        assert!(config.rename(id, "Logs".to_string()));
+        assert_eq!(config.bookmarks[0].name, "Logs");
+        assert!(!config.rename(Uuid::new_v4(), "Missing".to_string()));
+
+        config.retain_known_hosts(|host_id| host_id == kept);
+        assert_eq!(config.bookmarks.len(), 2);
+        assert_eq!(config.bookmarks[1].name, "/");
+
+        assert!(config.remove(id).is_some());
+        assert!(config.remove(id).is_none());
+        assert_eq!(config.bookmarks.len(), 1);
+    }
+}
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
 use uuid::Uuid;
 
+use super::VersionedConfig;
 use crate::error::ConfigError;
 
 /// Result of executing a command on a single host (for history)
@@ -207,6 +208,9 @@ fn default_redact_output() -> bool {
 /// Root configuration for snippet execution history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnippetHistoryConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
     #[serde(default)]
     pub entries: Vec<SnippetExecutionEntry>,
     #[serde(default = "default_max_entries")]
@@ -224,6 +228,7 @@ pub struct SnippetHistoryConfig {
 impl Default for SnippetHistoryConfig {
     fn default() -> Self {
         Self {
+            version: Self::SCHEMA_VERSION,
             entries: Vec::new(),
             max_entries: default_max_entries(),
             enabled: default_history_enabled(),
@@ -234,6 +239,14 @@ impl Default for SnippetHistoryConfig {
     }
 }
 
+impl VersionedConfig for SnippetHistoryConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+}
+
 impl SnippetHistoryConfig {
     /// Build a history entry based on current persistence settings.
     pub fn build_entry(
@@ -327,6 +340,26 @@ impl SnippetHistoryConfig {
             .collect()
     }
 
+    /// Distinct commands from the most recent executions, newest first.
+    /// Commands that were not stored or had secrets redacted are skipped.
+    pub fn recent_commands(&self, limit: usize) -> Vec<&str> {
+        let mut commands: Vec<&str> = Vec::new();
+        for entry in &self.entries {
+            if commands.len() >= limit {
+                break;
+            }
+            let command = entry.command.as_str();
+            if command.trim().is_empty()
+                || command.contains(REDACTED_PLACEHOLDER)
+                || commands.contains(&command)
+            {
+                continue;
+            }
+            commands.push(command);
+        }
+        commands
+    }
+
     /// Load from file, creating default if not exists
     pub fn load() -> Result<Self, ConfigError> {
         let path = super::paths::snippet_history_file().ok_or_else(|| ConfigError::ReadFile {
@@ -339,8 +372,12 @@ impl SnippetHistoryConfig {
 
         tracing::debug!("Loading snippet history from: {:?}", path);
 
-        let mut config: Self = super::load_toml_or_recover(&path, "snippet history")?;
+        let (mut config, migrated): (Self, bool) =
+            super::load_versioned_toml(&path, "snippet history")?;
         config.trim_to_max_entries();
+        if migrated && let Err(e) = config.save() {
+            tracing::warn!("Failed to save migrated snippet history: {}", e);
+        }
         Ok(config)
     }
 
@@ -489,4 +526,30 @@ mod tests {
         assert_eq!(config.entries[0].snippet_name, "snippet0");
         assert_eq!(config.entries[1].snippet_name, "snippet1");
     }
+
+    #[test]
+    fn recent_commands_are_distinct_and_skip_redacted() {
+        let mut config = SnippetHistoryConfig::default();
+        for command in [
+            "uptime",
+            "deploy --token [REDACTED]",
+            "df -h",
+            "uptime",
+            "[REDACTED]",
+            "free -m",
+        ] {
+            config.entries.push(SnippetExecutionEntry::new(
+                Uuid::new_v4(),
+                "snippet".to_string(),
+                command.to_string(),
+                vec![],
+            ));
+        }
+
+        assert_eq!(
+            config.recent_commands(10),
+            vec!["uptime", "df -h", "free -m"]
+        );
+        assert_eq!(config.recent_commands(2), vec!["uptime", "df -h"]);
+    }
 }
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::VersionedConfig;
+use super::hosts::{DetectedOs, Host};
 use crate::error::ConfigError;
 
 /// A single command snippet
@@ -20,9 +22,13 @@ pub struct Snippet {
     /// Optional tags for filtering
     #[serde(default)]
     pub tags: Vec<String>,
-    /// Target host IDs for multi-host execution
+    /// Target host IDs for multi-host execution. A snippet with targets is
+    /// only suggested in sessions for those hosts.
     #[serde(default)]
     pub host_ids: Vec<Uuid>,
+    /// Only suggest the snippet in sessions for hosts running this OS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_filter: Option<DetectedOs>,
     /// Creation timestamp
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Last update timestamp
@@ -40,26 +46,127 @@ impl Snippet {
             description: None,
             tags: Vec::new(),
             host_ids: Vec::new(),
+            os_filter: None,
             created_at: now,
             updated_at: now,
         }
     }
+
+    /// Whether the snippet is relevant in a session for a host. Snippets
+    /// without target hosts or an OS filter apply everywhere; a `Linux` filter
+    /// matches every Linux distribution.
+    pub fn applies_to(&self, host_id: Uuid, detected_os: Option<&DetectedOs>) -> bool {
+        let host_matches = self.host_ids.is_empty() || self.host_ids.contains(&host_id);
+        let os_matches = match (&self.os_filter, detected_os) {
+            (None, _) => true,
+            (Some(DetectedOs::Linux), Some(os)) => os.is_linux(),
+            (Some(filter), Some(os)) => filter == os,
+            (Some(_), None) => false,
+        };
+        host_matches && os_matches
+    }
+
+    /// Names of the `{{name}}` placeholders in the command, in order of first
+    /// appearance and without repeats
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for (_, name) in placeholder_spans(&self.command) {
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+}
+
+/// Replace each `{{name}}` placeholder with its value. Placeholders without a
+/// value are left as they are.
+pub fn fill_placeholders(command: &str, values: &[(String, String)]) -> String {
+    let mut filled = String::with_capacity(command.len());
+    let mut rest_start = 0;
+    for (range, name) in placeholder_spans(command) {
+        let Some((_, value)) = values.iter().find(|(key, _)| key == name) else {
+            continue;
+        };
+        filled.push_str(&command[rest_start..range.start]);
+        filled.push_str(value);
+        rest_start = range.end;
+    }
+    filled.push_str(&command[rest_start..]);
+    filled
+}
+
+/// Byte ranges and trimmed names of the placeholders in `command`. Names may
+/// contain letters, digits, `_` and `-`; anything else is left as literal text.
+fn placeholder_spans(command: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut spans = Vec::new();
+    let mut search_from = 0;
+    while let Some(open) = command[search_from..].find("{{") {
+        let start = search_from + open;
+        let Some(close) = command[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let name = command[start + 2..end - 2].trim();
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        if valid {
+            spans.push((start..end, name));
+            search_from = end;
+        } else {
+            search_from = start + 1;
+        }
+    }
+    spans
 }
 
 /// Root configuration for snippets.toml
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnippetsConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
     /// All snippets
     #[serde(default)]
     pub snippets: Vec<Snippet>,
 }
 
+impl Default for SnippetsConfig {
+    fn default() -> Self {
+        Self {
+            version: Self::SCHEMA_VERSION,
+            snippets: Vec::new(),
+        }
+    }
+}
+
+impl VersionedConfig for SnippetsConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+}
+
 impl SnippetsConfig {
     /// Find a snippet by ID
     pub fn find_snippet(&self, id: Uuid) -> Option<&Snippet> {
         self.snippets.iter().find(|s| s.id == id)
     }
 
+    /// Snippets to suggest while a session for `host` is active; all of them
+    /// when there is no such host (e.g. a local shell or no session)
+    pub fn snippets_for_host<'a>(
+        &'a self,
+        host: Option<&'a Host>,
+    ) -> impl Iterator<Item = &'a Snippet> + 'a {
+        self.snippets.iter().filter(move |snippet| {
+            host.is_none_or(|host| snippet.applies_to(host.id, host.detected_os.as_ref()))
+        })
+    }
+
     /// Find a snippet by ID (mutable)
     pub fn find_snippet_mut(&mut self, id: Uuid) -> Option<&mut Snippet> {
         self.snippets.iter_mut().find(|s| s.id == id)
@@ -90,7 +197,11 @@ impl SnippetsConfig {
             ),
         })?;
 
-        super::load_toml_or_recover(&path, "snippets")
+        let (config, migrated): (Self, bool) = super::load_versioned_toml(&path, "snippets")?;
+        if migrated && let Err(e) = config.save() {
+            tracing::warn!("Failed to save migrated snippets: {}", e);
+        }
+        Ok(config)
     }
 
     /// Save to file
@@ -242,6 +353,48 @@ mod tests {
         assert_eq!(snippet.command, "echo こんにちは");
     }
 
+    // === Placeholder tests ===
+
+    #[test]
+    fn placeholders_are_deduped_in_order() {
+        let snippet = Snippet::new(
+            "restart".to_string(),
+            "systemctl restart {{service}} && journalctl -u {{ service }} -n {{lines}}".to_string(),
+        );
+        assert_eq!(snippet.placeholders(), vec!["service", "lines"]);
+    }
+
+    #[test]
+    fn placeholders_ignore_invalid_names() {
+        let snippet = Snippet::new(
+            "awk".to_string(),
+            "echo {{}} {{a b}} ${{x}} {{ok_1}} {{unterminated".to_string(),
+        );
+        assert_eq!(snippet.placeholders(), vec!["x", "ok_1"]);
+
+        let plain = Snippet::new("plain".to_string(), "uptime".to_string());
+        assert!(plain.placeholders().is_empty());
+    }
+
+    #[test]
+    fn fill_placeholders_substitutes_every_occurrence() {
+        let values = vec![
+            ("service".to_string(), "nginx".to_string()),
+            ("lines".to_string(), "50".to_string()),
+        ];
+        assert_eq!(
+            fill_placeholders(
+                "systemctl restart {{service}} && journalctl -u {{ service }} -n {{lines}}",
+                &values
+            ),
+            "systemctl restart nginx && journalctl -u nginx -n 50"
+        );
+        assert_eq!(
+            fill_placeholders("echo {{other}}", &values),
+            "echo {{other}}"
+        );
+    }
+
     // === Snippet traits tests ===
 
     #[test]
@@ -434,6 +587,36 @@ mod tests {
         assert_eq!(found.name, "second");
     }
 
+    #[test]
+    fn snippets_for_host_filters_by_host_and_os() {
+        let host_id = Uuid::new_v4();
+        let other_host = Uuid::new_v4();
+
+        let global = Snippet::new("uptime".to_string(), "uptime".to_string());
+        let mut scoped = Snippet::new("deploy".to_string(), "deploy".to_string());
+        scoped.host_ids = vec![other_host];
+        let mut linux = Snippet::new("apt".to_string(), "apt update".to_string());
+        linux.os_filter = Some(DetectedOs::Linux);
+        let mut bsd = Snippet::new("pkg".to_string(), "pkg update".to_string());
+        bsd.os_filter = Some(DetectedOs::FreeBSD);
+
+        let config = SnippetsConfig {
+            snippets: vec![global, scoped, linux, bsd],
+            ..Default::default()
+        };
+
+        let names: Vec<&str> = config
+            .snippets
+            .iter()
+            .filter(|snippet| snippet.applies_to(host_id, Some(&DetectedOs::Ubuntu)))
+            .map(|snippet| snippet.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["uptime", "apt"]);
+        assert!(!config.snippets[2].applies_to(host_id, None));
+        assert!(config.snippets[1].applies_to(other_host, None));
+        assert_eq!(config.snippets_for_host(None).count(), 4);
+    }
+
     #[test]
     fn find_snippet_mut_returns_none_for_empty_config() {
         let mut config = SnippetsConfig::default();
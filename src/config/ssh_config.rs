@@ -1,4 +1,3 @@
-use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 
 use chrono::Utc;
@@ -6,7 +5,7 @@ use uuid::Uuid;
 
 use crate::config::hosts::{HubRouting, default_username};
 use crate::config::paths::{expand_tilde, ssh_dir};
-use crate::config::{AuthMethod, Host, Protocol};
+use crate::config::{AuthMethod, Host, PortForward, PortForwardKind, Protocol};
 use crate::error::ConfigError;
 use crate::fs_utils;
 
@@ -24,8 +23,9 @@ struct HostBlock {
     proxy_jump: Option<Option<String>>,
 }
 
-pub fn load_hosts_from_ssh_config() -> Result<Vec<Host>, ConfigError> {
-    let path = ssh_dir()
+/// Path of the user's `~/.ssh/config`
+pub fn default_ssh_config_path() -> Result<PathBuf, ConfigError> {
+    ssh_dir()
         .map(|dir| dir.join("config"))
         .ok_or_else(|| ConfigError::ReadFile {
             path: PathBuf::from("~/.ssh/config"),
@@ -33,22 +33,53 @@ pub fn load_hosts_from_ssh_config() -> Result<Vec<Host>, ConfigError> {
                 std::io::ErrorKind::NotFound,
                 "Could not determine SSH config path",
             ),
-        })?;
-
-    let content = match read_ssh_config_file(&path) {
-        Ok(content) => content,
-        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
-        Err(error) => {
-            return Err(ConfigError::ReadFile {
-                path: path.clone(),
-                source: error,
-            });
-        }
-    };
+        })
+}
+
+/// Parse hosts from an OpenSSH config file at `path`.
+pub fn load_hosts_from_ssh_config_file(path: &Path) -> Result<Vec<Host>, ConfigError> {
+    let content = read_ssh_config_file(path).map_err(|source| ConfigError::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    })?;
 
     Ok(parse_ssh_config(&content))
 }
 
+/// Whether `~/.ssh/config` turns on `HashKnownHosts` for every host.
+pub fn openssh_hash_known_hosts() -> bool {
+    ssh_dir()
+        .map(|dir| dir.join("config"))
+        .and_then(|path| read_ssh_config_file(&path).ok())
+        .is_some_and(|content| hash_known_hosts_enabled(&content))
+}
+
+/// First `HashKnownHosts` value that applies to all hosts (global or
+/// `Host *` sections, Match blocks ignored), like OpenSSH's first-wins rule.
+pub fn hash_known_hosts_enabled(content: &str) -> bool {
+    let mut applies_to_all = true;
+    for raw_line in content.lines() {
+        let line = strip_comments(raw_line);
+        let tokens = split_tokens(line.trim());
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let (key, args) = directive_parts(&tokens);
+        match key.as_str() {
+            "host" => applies_to_all = args.iter().any(|pattern| pattern == "*"),
+            "match" => applies_to_all = false,
+            "hashknownhosts" if applies_to_all => {
+                return args
+                    .first()
+                    .is_some_and(|value| value.eq_ignore_ascii_case("yes"));
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
 fn read_ssh_config_file(path: &Path) -> std::io::Result<String> {
     fs_utils::read_regular_file_follow_symlink_to_string_limited(
         path,
@@ -273,11 +304,19 @@ fn resolve_host(alias: &str, blocks: &[HostBlock]) -> (Host, Option<String>) {
         vnc_via_ssh_host_id: None,
         allow_cleartext_vnc: false,
         auth,
+        auth_fallbacks: Vec::new(),
         agent_forwarding: false,
         port_forwards: Vec::new(),
         hub_routing: HubRouting::Auto,
         jump_host_id: None,
+        keepalive_interval: None,
+        keepalive_count_max: None,
+        session_logging: None,
+        agent_identity: None,
+        sftp_start_path: None,
+        last_sftp_path: None,
         group_id: None,
+        order: 0,
         notes: None,
         tags: Vec::new(),
         created_at: now,
@@ -418,11 +457,19 @@ fn find_or_synthesize_jump_host(hosts: &mut Vec<Host>, spec: &str) -> Option<Uui
         vnc_via_ssh_host_id: None,
         allow_cleartext_vnc: false,
         auth: AuthMethod::Agent,
+        auth_fallbacks: Vec::new(),
         agent_forwarding: false,
         port_forwards: Vec::new(),
         hub_routing: HubRouting::Auto,
         jump_host_id: None,
+        keepalive_interval: None,
+        keepalive_count_max: None,
+        session_logging: None,
+        agent_identity: None,
+        sftp_start_path: None,
+        last_sftp_path: None,
         group_id: None,
+        order: 0,
         notes: None,
         tags: Vec::new(),
         created_at: now,
@@ -577,11 +624,241 @@ fn split_tokens(line: &str) -> Vec<String> {
     tokens
 }
 
+/// The `ssh` invocation equivalent to connecting to `host`.
+///
+/// `jump_chain` is the host's resolved jump chain, outermost hop first.
+pub fn host_to_ssh_command(host: &Host, jump_chain: &[Host]) -> String {
+    let mut args = vec!["ssh".to_string()];
+    if host.port != 22 {
+        args.extend(["-p".to_string(), host.port.to_string()]);
+    }
+    if let AuthMethod::PublicKey {
+        key_path: Some(path),
+        ..
+    } = &host.auth
+    {
+        args.extend([
+            "-i".to_string(),
+            shell_arg(&path.to_string_lossy()),
+            "-o".to_string(),
+            "IdentitiesOnly=yes".to_string(),
+        ]);
+    }
+    if let Some(methods) = preferred_authentications(&host.auth) {
+        args.extend([
+            "-o".to_string(),
+            format!("PreferredAuthentications={}", methods),
+        ]);
+    }
+    if host.agent_forwarding {
+        args.push("-A".to_string());
+    }
+    if !jump_chain.is_empty() {
+        args.extend(["-J".to_string(), shell_arg(&proxy_jump_spec(jump_chain))]);
+    }
+    for forward in host.port_forwards.iter().filter(|forward| forward.enabled) {
+        let (flag, spec) = match forward.kind {
+            PortForwardKind::Local => ("-L", forward_spec(forward, ":")),
+            PortForwardKind::Remote => ("-R", forward_spec(forward, ":")),
+            PortForwardKind::Dynamic => ("-D", bind_spec(forward)),
+        };
+        args.extend([flag.to_string(), shell_arg(&spec)]);
+    }
+    args.push(shell_arg(&user_at_host(host)));
+    args.join(" ")
+}
+
+/// A `~/.ssh/config` stanza equivalent to `host`; jump hosts are written
+/// inline as a `ProxyJump` spec.
+pub fn host_to_ssh_config_block(host: &Host, jump_chain: &[Host]) -> String {
+    let proxy_jump = (!jump_chain.is_empty()).then(|| proxy_jump_spec(jump_chain));
+    config_block(host, proxy_jump)
+}
+
+/// A `~/.ssh/config` fragment with one stanza per SSH host. Jump hosts are
+/// referenced by alias, so OpenSSH follows each hop's own stanza and
+/// importing the fragment relinks the chain.
+pub fn hosts_to_ssh_config(hosts: &[Host]) -> String {
+    let mut config = String::from("# Generated by Portal\n");
+    for host in hosts.iter().filter(|host| host.protocol == Protocol::Ssh) {
+        let proxy_jump = host
+            .jump_host_id
+            .and_then(|jump_id| {
+                hosts
+                    .iter()
+                    .find(|hop| hop.id == jump_id && hop.protocol == Protocol::Ssh)
+            })
+            .map(|hop| config_alias(&hop.name));
+        config.push('\n');
+        config.push_str(&config_block(host, proxy_jump));
+    }
+    config
+}
+
+fn config_block(host: &Host, proxy_jump: Option<String>) -> String {
+    let mut lines = vec![format!("Host {}", config_alias(&host.name))];
+    let directive = |key: &str, value: String| format!("    {} {}", key, config_value(&value));
+
+    lines.push(directive("HostName", host.hostname.clone()));
+    if !host.username.trim().is_empty() {
+        lines.push(directive("User", host.username.trim().to_string()));
+    }
+    if host.port != 22 {
+        lines.push(directive("Port", host.port.to_string()));
+    }
+    match &host.auth {
+        AuthMethod::PublicKey {
+            key_path: Some(path),
+            ..
+        } => {
+            lines.push(directive(
+                "IdentityFile",
+                path.to_string_lossy().into_owned(),
+            ));
+            lines.push(directive("IdentitiesOnly", "yes".to_string()));
+        }
+        AuthMethod::PublicKey {
+            vault_key_id: Some(_),
+            ..
+        } => lines.push("    # Key is stored in the Portal vault".to_string()),
+        AuthMethod::Agent => lines.push(directive("IdentitiesOnly", "no".to_string())),
+        _ => {}
+    }
+    if let Some(methods) = preferred_authentications(&host.auth) {
+        lines.push(directive("PreferredAuthentications", methods.to_string()));
+    }
+    if host.agent_forwarding {
+        lines.push(directive("ForwardAgent", "yes".to_string()));
+    }
+    if let Some(proxy_jump) = proxy_jump {
+        lines.push(directive("ProxyJump", proxy_jump));
+    }
+    // Forward directives take two arguments, so they are not quoted as one value.
+    for forward in host.port_forwards.iter().filter(|forward| forward.enabled) {
+        lines.push(match forward.kind {
+            PortForwardKind::Local => format!("    LocalForward {}", forward_spec(forward, " ")),
+            PortForwardKind::Remote => format!("    RemoteForward {}", forward_spec(forward, " ")),
+            PortForwardKind::Dynamic => format!("    DynamicForward {}", bind_spec(forward)),
+        });
+    }
+
+    let mut block = lines.join("\n");
+    block.push('\n');
+    block
+}
+
+fn preferred_authentications(auth: &AuthMethod) -> Option<&'static str> {
+    match auth {
+        AuthMethod::Password => Some("password"),
+        AuthMethod::KeyboardInteractive => Some("keyboard-interactive"),
+        AuthMethod::PublicKey { .. } | AuthMethod::Agent => None,
+    }
+}
+
+fn user_at_host(host: &Host) -> String {
+    let username = host.username.trim();
+    if username.is_empty() {
+        host.hostname.clone()
+    } else {
+        format!("{}@{}", username, host.hostname)
+    }
+}
+
+/// `[user@]host[:port]` hops joined by commas, as `-J` and `ProxyJump` take them.
+fn proxy_jump_spec(jump_chain: &[Host]) -> String {
+    jump_chain
+        .iter()
+        .map(|hop| {
+            let username = hop.username.trim();
+            let mut spec = if username.is_empty() {
+                String::new()
+            } else {
+                format!("{}@", username)
+            };
+            if hop.hostname.contains(':') {
+                spec.push_str(&format!("[{}]", hop.hostname));
+            } else {
+                spec.push_str(&hop.hostname);
+            }
+            if hop.port != 22 {
+                spec.push_str(&format!(":{}", hop.port));
+            }
+            spec
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn bind_spec(forward: &PortForward) -> String {
+    format!("{}:{}", forward.bind_host, forward.bind_port)
+}
+
+/// Bind and target address; `separator` is `:` for `-L`/`-R` and a space
+/// for config directives.
+fn forward_spec(forward: &PortForward, separator: &str) -> String {
+    format!(
+        "{}{}{}:{}",
+        bind_spec(forward),
+        separator,
+        forward.target_host,
+        forward.target_port
+    )
+}
+
+fn config_alias(name: &str) -> String {
+    let alias: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '-' } else { c })
+        .collect();
+    if alias.is_empty() {
+        "portal-host".to_string()
+    } else {
+        alias
+    }
+}
+
+fn config_value(value: &str) -> String {
+    if value.chars().any(char::is_whitespace) {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Leave plain arguments bare so the command stays readable; quote the rest.
+fn shell_arg(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,:/@=+%[]".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::ErrorKind;
 
+    #[test]
+    fn hash_known_hosts_reads_global_and_host_star_values() {
+        assert!(hash_known_hosts_enabled("HashKnownHosts yes\n"));
+        assert!(hash_known_hosts_enabled(
+            "Host web\n  User deploy\nHost *\n  HashKnownHosts=yes\n"
+        ));
+        // Host-specific values and later duplicates don't apply globally.
+        assert!(!hash_known_hosts_enabled(
+            "Host web\n  HashKnownHosts yes\n"
+        ));
+        assert!(!hash_known_hosts_enabled(
+            "HashKnownHosts no\nHost *\n  HashKnownHosts yes\n"
+        ));
+    }
+
     #[test]
     fn parse_basic_host() {
         let content = r#"
@@ -1112,4 +1389,78 @@ mod tests {
         assert_eq!(error.kind(), ErrorKind::InvalidInput);
         assert!(error.to_string().contains("not a regular file"));
     }
+
+    fn export_fixture() -> (Host, Vec<Host>) {
+        let content = r#"
+            Host bastion
+              HostName bastion.example.com
+              User jump
+              Port 2222
+            Host app
+              HostName 10.0.0.9
+              User deploy
+              Port 2200
+              IdentityFile "/keys/app key"
+              ProxyJump bastion
+        "#;
+        let hosts = parse_ssh_config(content);
+        let bastion = hosts.iter().find(|h| h.name == "bastion").unwrap().clone();
+        let mut app = hosts.iter().find(|h| h.name == "app").unwrap().clone();
+        app.agent_forwarding = true;
+        app.port_forwards = vec![
+            PortForward {
+                id: Uuid::new_v4(),
+                kind: PortForwardKind::Local,
+                bind_host: "localhost".to_string(),
+                bind_port: 5433,
+                target_host: "db".to_string(),
+                target_port: 5432,
+                enabled: true,
+                description: None,
+            },
+            PortForward {
+                id: Uuid::new_v4(),
+                kind: PortForwardKind::Dynamic,
+                bind_host: "localhost".to_string(),
+                bind_port: 1080,
+                target_host: String::new(),
+                target_port: 0,
+                enabled: false,
+                description: None,
+            },
+        ];
+        (app, vec![bastion])
+    }
+
+    #[test]
+    fn export_ssh_command_includes_jump_key_and_forwards() {
+        let (app, chain) = export_fixture();
+        assert_eq!(
+            host_to_ssh_command(&app, &chain),
+            "ssh -p 2200 -i '/keys/app key' -o IdentitiesOnly=yes -A \
+             -J jump@bastion.example.com:2222 -L localhost:5433:db:5432 deploy@10.0.0.9"
+        );
+    }
+
+    #[test]
+    fn export_ssh_config_block_parses_back() {
+        let (app, chain) = export_fixture();
+        let block = host_to_ssh_config_block(&app, &chain);
+        assert!(block.contains("    IdentityFile \"/keys/app key\"\n"));
+        assert!(block.contains("    ProxyJump jump@bastion.example.com:2222\n"));
+        assert!(block.contains("    LocalForward localhost:5433 db:5432\n"));
+        assert!(!block.contains("DynamicForward"));
+
+        let parsed = parse_ssh_config(&block);
+        let app_again = parsed.iter().find(|h| h.name == "app").unwrap();
+        assert_eq!(app_again.hostname, "10.0.0.9");
+        assert_eq!(app_again.username, "deploy");
+        assert_eq!(app_again.port, 2200);
+        let jump = parsed
+            .iter()
+            .find(|h| Some(h.id) == app_again.jump_host_id)
+            .unwrap();
+        assert_eq!(jump.hostname, "bastion.example.com");
+        assert_eq!(jump.port, 2222);
+    }
 }
@@ -0,0 +1,191 @@
+//! Local terminal profiles configuration
+//!
+//! Profiles describe how a local terminal is spawned: which shell to run,
+//! its startup arguments, the working directory, and environment overrides.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::VersionedConfig;
+use crate::error::ConfigError;
+
+/// A named way to start a local terminal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TerminalProfile {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Display name
+    pub name: String,
+    /// Shell program (`bash`, `/usr/bin/fish`, `pwsh`, ...); the user's
+    /// login shell when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    /// Arguments passed to the shell
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Starting directory (`~` is expanded); the home directory when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// Environment variables set on top of Portal's terminal environment
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+impl TerminalProfile {
+    /// Create a profile that runs the given shell
+    pub fn new(name: String, shell: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            shell,
+            args: Vec::new(),
+            working_dir: None,
+            env: BTreeMap::new(),
+        }
+    }
+}
+
+/// Root configuration for terminal_profiles.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalProfilesConfig {
+    /// Schema version the file was written with (0 before versioning)
+    #[serde(default)]
+    pub version: u32,
+    /// Profile used by "Local Terminal"; the login shell when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<Uuid>,
+    /// All profiles
+    #[serde(default)]
+    pub profiles: Vec<TerminalProfile>,
+}
+
+impl Default for TerminalProfilesConfig {
+    fn default() -> Self {
+        Self {
+            version: Self::SCHEMA_VERSION,
+            default_profile: None,
+            profiles: Vec::new(),
+        }
+    }
+}
+
+impl VersionedConfig for TerminalProfilesConfig {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn schema_version_mut(&mut self) -> &mut u32 {
+        &mut self.version
+    }
+}
+
+impl TerminalProfilesConfig {
+    /// Find a profile by ID
+    pub fn find_profile(&self, id: Uuid) -> Option<&TerminalProfile> {
+        self.profiles.iter().find(|p| p.id == id)
+    }
+
+    /// The configured default profile, if it still exists
+    pub fn default_profile(&self) -> Option<&TerminalProfile> {
+        self.default_profile.and_then(|id| self.find_profile(id))
+    }
+
+    /// Load from file, creating default if not exists
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = super::paths::terminal_profiles_file().ok_or_else(|| ConfigError::ReadFile {
+            path: std::path::PathBuf::from("terminal_profiles.toml"),
+            source: std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine terminal profiles file path",
+            ),
+        })?;
+
+        let (config, migrated): (Self, bool) =
+            super::load_versioned_toml(&path, "terminal profiles")?;
+        if migrated && let Err(e) = config.save() {
+            tracing::warn!("Failed to save migrated terminal profiles: {}", e);
+        }
+        Ok(config)
+    }
+
+    /// Save to file
+    pub fn save(&self) -> Result<(), ConfigError> {
+        super::paths::ensure_config_dir().map_err(ConfigError::CreateDir)?;
+
+        let path =
+            super::paths::terminal_profiles_file().ok_or_else(|| ConfigError::WriteFile {
+                path: std::path::PathBuf::from("terminal_profiles.toml"),
+                source: std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Could not determine terminal profiles file path",
+                ),
+            })?;
+
+        let content = toml::to_string_pretty(self).map_err(ConfigError::Serialize)?;
+        super::write_atomic(&path, &content).map_err(|e| ConfigError::WriteFile { path, source: e })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profile_with_args_and_env() {
+        let config: TerminalProfilesConfig = toml::from_str(
+            r#"
+            default_profile = "6f1c2f4e-93c4-4a55-9d51-8a3f0c1f6b10"
+
+            [[profiles]]
+            id = "6f1c2f4e-93c4-4a55-9d51-8a3f0c1f6b10"
+            name = "Fish"
+            shell = "/usr/bin/fish"
+            args = ["--login"]
+            working_dir = "~/src"
+
+            [profiles.env]
+            EDITOR = "nvim"
+            "#,
+        )
+        .unwrap();
+
+        let profile = config.default_profile().expect("default profile");
+        assert_eq!(profile.name, "Fish");
+        assert_eq!(profile.shell.as_deref(), Some("/usr/bin/fish"));
+        assert_eq!(profile.args, vec!["--login".to_string()]);
+        assert_eq!(profile.working_dir.as_deref(), Some("~/src"));
+        assert_eq!(profile.env.get("EDITOR").map(String::as_str), Some("nvim"));
+    }
+
+    #[test]
+    fn missing_default_profile_falls_back_to_none() {
+        let mut config = TerminalProfilesConfig::default();
+        config.profiles.push(TerminalProfile::new(
+            "Bash".to_string(),
+            Some("bash".into()),
+        ));
+        assert!(config.default_profile().is_none());
+
+        config.default_profile = Some(Uuid::new_v4());
+        assert!(config.default_profile().is_none());
+
+        config.default_profile = Some(config.profiles[0].id);
+        assert_eq!(config.default_profile().unwrap().name, "Bash");
+    }
+
+    #[test]
+    fn serialization_roundtrip_skips_empty_fields() {
+        let mut config = TerminalProfilesConfig::default();
+        config
+            .profiles
+            .push(TerminalProfile::new("Login shell".to_string(), None));
+
+        let content = toml::to_string_pretty(&config).unwrap();
+        assert!(!content.contains("shell ="));
+        assert!(!content.contains("args"));
+        assert!(!content.contains("env"));
+
+        let parsed: TerminalProfilesConfig = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.profiles, config.profiles);
+    }
+}
@@ -25,6 +25,12 @@ pub enum ConfigError {
     #[error("Host not found: {0}")]
     HostNotFound(uuid::Uuid),
 
+    #[error("Group not found: {0}")]
+    GroupNotFound(uuid::Uuid),
+
+    #[error("Group {0} cannot be moved into itself or one of its subgroups")]
+    GroupCycle(uuid::Uuid),
+
     #[error("Snippet not found: {0}")]
     SnippetNotFound(uuid::Uuid),
 
@@ -42,6 +48,19 @@ pub enum SshError {
         reason: String,
     },
 
+    #[error("Could not resolve host {host}: {reason}")]
+    DnsResolution { host: String, reason: String },
+
+    #[error("Connection refused by {host}:{port}")]
+    ConnectionRefused { host: String, port: u16 },
+
+    #[error("SSH handshake with {host}:{port} failed: {reason}")]
+    HandshakeFailed {
+        host: String,
+        port: u16,
+        reason: String,
+    },
+
     #[error("Authentication failed: {0}")]
     AuthenticationFailed(String),
 
@@ -94,6 +113,9 @@ pub enum SftpError {
     #[error("File operation failed: {0}")]
     FileOperation(String),
 
+    #[error("Incorrect sudo password")]
+    SudoAuthFailed,
+
     #[error("Transfer failed: {0}")]
     Transfer(String),
 
@@ -171,6 +193,31 @@ mod tests {
         assert!(msg.contains("network unreachable"));
     }
 
+    #[test]
+    fn ssh_error_connect_stage_display() {
+        let dns = SshError::DnsResolution {
+            host: "nowhere.invalid".to_string(),
+            reason: "no such host".to_string(),
+        };
+        assert!(dns.to_string().contains("resolve host nowhere.invalid"));
+
+        let refused = SshError::ConnectionRefused {
+            host: "example.com".to_string(),
+            port: 2222,
+        };
+        assert_eq!(
+            refused.to_string(),
+            "Connection refused by example.com:2222"
+        );
+
+        let handshake = SshError::HandshakeFailed {
+            host: "example.com".to_string(),
+            port: 22,
+            reason: "no common cipher".to_string(),
+        };
+        assert!(handshake.to_string().contains("no common cipher"));
+    }
+
     #[test]
     fn ssh_error_auth_failed_display() {
         let err = SshError::AuthenticationFailed("invalid key".to_string());
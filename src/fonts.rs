@@ -1,8 +1,10 @@
 //! Font system for Portal SSH client
 
 use iced::Font;
+use iced::font::{Style, Weight};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::OnceLock;
 
 /// Available terminal fonts
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -45,12 +47,53 @@ impl TerminalFont {
 
     /// Resolve the font used for terminal cells.
     ///
-    /// Portal currently bundles only the regular Nerd Font faces. Returning the
-    /// regular face for styled cells keeps Private Use Area glyph coverage
-    /// stable instead of letting the renderer fall back to an unrelated system
-    /// bold/italic face that may not contain prompt symbols.
-    pub fn variant(self, _bold: bool, _italic: bool) -> Font {
-        self.to_iced_font()
+    /// Portal bundles only the regular Nerd Font faces. Bold and italic cells
+    /// use the matching weight/style when that face of the same Nerd Font
+    /// family is installed on the system; otherwise they keep the regular
+    /// face, so Private Use Area glyph coverage stays stable instead of falling
+    /// back to an unrelated face that may not contain prompt symbols.
+    pub fn variant(self, bold: bool, italic: bool) -> Font {
+        let font = self.to_iced_font();
+        let installed = self.styled_faces();
+        let available = match (bold, italic) {
+            (false, false) => return font,
+            (true, false) => installed.bold,
+            (false, true) => installed.italic,
+            (true, true) => installed.bold_italic,
+        };
+        if !available {
+            return font;
+        }
+
+        Font {
+            weight: if bold { Weight::Bold } else { Weight::Normal },
+            style: if italic { Style::Italic } else { Style::Normal },
+            ..font
+        }
+    }
+
+    /// Family name the Nerd Font faces are registered under.
+    const fn family_name(self) -> &'static str {
+        match self {
+            TerminalFont::JetBrainsMono => "JetBrainsMono Nerd Font",
+            TerminalFont::Hack => "Hack Nerd Font",
+        }
+    }
+
+    /// Styled faces of this family known to the font system, looked up once.
+    fn styled_faces(self) -> StyledFaces {
+        static FACES: OnceLock<Vec<(TerminalFont, StyledFaces)>> = OnceLock::new();
+        FACES
+            .get_or_init(|| {
+                TerminalFont::all()
+                    .iter()
+                    .map(|font| (*font, installed_styled_faces(font.family_name())))
+                    .collect()
+            })
+            .iter()
+            .find(|(font, _)| *font == self)
+            .map(|(_, faces)| *faces)
+            .unwrap_or_default()
     }
 
     /// Legacy fallback line height ratio.
@@ -68,6 +111,37 @@ impl TerminalFont {
     }
 }
 
+/// Which styled faces of a terminal font family are installed
+#[derive(Debug, Clone, Copy, Default)]
+struct StyledFaces {
+    bold: bool,
+    italic: bool,
+    bold_italic: bool,
+}
+
+fn installed_styled_faces(family: &str) -> StyledFaces {
+    use iced::advanced::graphics::text::cosmic_text::fontdb;
+    use iced::advanced::graphics::text::font_system;
+
+    let Ok(mut font_system) = font_system().write() else {
+        return StyledFaces::default();
+    };
+    let db = font_system.raw().db();
+    let has_face = |weight: fontdb::Weight, style: fontdb::Style| {
+        db.faces().any(|face| {
+            face.weight == weight
+                && face.style == style
+                && face.families.iter().any(|(name, _)| name == family)
+        })
+    };
+
+    StyledFaces {
+        bold: has_face(fontdb::Weight::BOLD, fontdb::Style::Normal),
+        italic: has_face(fontdb::Weight::NORMAL, fontdb::Style::Italic),
+        bold_italic: has_face(fontdb::Weight::BOLD, fontdb::Style::Italic),
+    }
+}
+
 impl fmt::Display for TerminalFont {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.display_name())
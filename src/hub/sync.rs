@@ -798,6 +798,7 @@ fn canonical_hosts_payload(hosts: &HostsConfig) -> Result<Value, String> {
             if let Some(host) = host.as_object_mut() {
                 host.remove("last_connected");
                 host.remove("detected_os");
+                host.remove("last_sftp_path");
                 if let Some(created_at) = host.get("created_at").cloned() {
                     host.insert("updated_at".to_string(), created_at);
                 }
@@ -893,6 +894,7 @@ fn preserve_runtime_host_metadata(remote: &mut HostsConfig, local: &HostsConfig)
         if let Some(local_host) = local.find_host(host.id) {
             host.last_connected = local_host.last_connected;
             host.detected_os = local_host.detected_os.clone();
+            host.last_sftp_path = local_host.last_sftp_path.clone();
             host.updated_at = local_host.updated_at;
         }
     }
@@ -991,11 +993,19 @@ mod tests {
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
             auth: AuthMethod::Agent,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Hub,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: Some(std::path::PathBuf::from("/srv/app")),
             group_id: None,
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at,
@@ -1007,12 +1017,14 @@ mod tests {
         let payload = canonical_hosts_payload(&HostsConfig {
             hosts: vec![host],
             groups: Vec::new(),
+            ..Default::default()
         })
         .unwrap();
         let host = payload["hosts"][0].as_object().unwrap();
 
         assert!(!host.contains_key("last_connected"));
         assert!(!host.contains_key("detected_os"));
+        assert!(!host.contains_key("last_sftp_path"));
         assert_eq!(host.get("updated_at"), host.get("created_at"));
     }
 
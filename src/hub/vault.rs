@@ -94,7 +94,12 @@ impl HubVaultConfig {
         ) {
             Ok(content) => content,
             Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
-                return Ok(Self::default());
+                return Ok(
+                    config::recover_interrupted_write(&path, "Hub vault", |content| {
+                        serde_json::from_str(content).ok()
+                    })
+                    .unwrap_or_default(),
+                );
             }
             Err(error) if error.kind() == std::io::ErrorKind::FileTooLarge => {
                 return Err(format!("failed to read {}: {}", path.display(), error));
@@ -31,6 +31,9 @@ pub mod ui {
     pub const INFO: &[u8] = include_bytes!("../assets/icons/ui/info.svg");
     pub const ZAP: &[u8] = include_bytes!("../assets/icons/ui/zap.svg");
     pub const KEY: &[u8] = include_bytes!("../assets/icons/ui/key.svg");
+    pub const EYE: &[u8] = include_bytes!("../assets/icons/ui/eye.svg");
+    pub const EYE_OFF: &[u8] = include_bytes!("../assets/icons/ui/eye-off.svg");
+    pub const STAR: &[u8] = include_bytes!("../assets/icons/ui/star.svg");
 }
 
 /// File type icons for SFTP browser
@@ -45,10 +48,13 @@ pub mod files {
     pub const ARCHIVE: &[u8] = include_bytes!("../assets/icons/files/archive.svg");
     pub const FILE_JSON: &[u8] = include_bytes!("../assets/icons/files/file-json.svg");
     pub const FILE_COG: &[u8] = include_bytes!("../assets/icons/files/file-cog.svg");
+    pub const FILE_SYMLINK: &[u8] = include_bytes!("../assets/icons/files/file-symlink.svg");
 }
 
 /// Operating system and distribution logos
 pub mod os {
+    use crate::config::DetectedOs;
+
     // Linux distributions
     pub const LINUX: &[u8] = include_bytes!("../assets/icons/os/linux.svg");
     pub const UBUNTU: &[u8] = include_bytes!("../assets/icons/os/ubuntu.svg");
@@ -75,6 +81,40 @@ pub mod os {
     pub const OPENBSD: &[u8] = include_bytes!("../assets/icons/os/openbsd.svg");
     pub const NETBSD: &[u8] = include_bytes!("../assets/icons/os/netbsd.svg");
     pub const UNKNOWN: &[u8] = include_bytes!("../assets/icons/os/unknown.svg");
+
+    /// Logo for a detected OS; hosts not yet detected, or running something
+    /// unrecognized, get the generic server icon
+    pub fn for_detected(os: Option<&DetectedOs>) -> &'static [u8] {
+        match os {
+            // BSD family
+            Some(DetectedOs::FreeBSD) => FREEBSD,
+            Some(DetectedOs::OpenBSD) => OPENBSD,
+            Some(DetectedOs::NetBSD) => NETBSD,
+            // macOS and Windows
+            Some(DetectedOs::MacOS) => APPLE,
+            Some(DetectedOs::Windows) => WINDOWS,
+            // Linux distributions
+            Some(DetectedOs::Ubuntu) => UBUNTU,
+            Some(DetectedOs::Debian) => DEBIAN,
+            Some(DetectedOs::Fedora) => FEDORA,
+            Some(DetectedOs::Arch) => ARCH,
+            Some(DetectedOs::CentOS) => CENTOS,
+            Some(DetectedOs::RedHat) => REDHAT,
+            Some(DetectedOs::OpenSUSE) => OPENSUSE,
+            Some(DetectedOs::NixOS) => NIXOS,
+            Some(DetectedOs::Manjaro) => MANJARO,
+            Some(DetectedOs::Mint) => MINT,
+            Some(DetectedOs::PopOS) => POPOS,
+            Some(DetectedOs::Gentoo) => GENTOO,
+            Some(DetectedOs::Alpine) => ALPINE,
+            Some(DetectedOs::Kali) => KALI,
+            Some(DetectedOs::Rocky) => ROCKY,
+            Some(DetectedOs::Alma) => ALMA,
+            // Generic Linux fallback
+            Some(DetectedOs::Linux) => LINUX,
+            Some(DetectedOs::Unknown(_)) | None => UNKNOWN,
+        }
+    }
 }
 
 /// Create an SVG icon widget with specified size and color
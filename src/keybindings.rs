@@ -15,6 +15,67 @@ pub enum AppAction {
     Paste,
     ToggleFullscreen,
     TerminalSearch,
+    ClearScrollback,
+    ResetTerminal,
+    SplitRight,
+    SplitDown,
+    FocusNextPane,
+    SnippetPalette,
+    VncViewOnly,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+}
+
+impl AppAction {
+    /// Every action, in the order the settings page lists them
+    pub const ALL: &[AppAction] = &[
+        AppAction::NewWindow,
+        AppAction::NewConnection,
+        AppAction::CloseSession,
+        AppAction::NewTab,
+        AppAction::NextSession,
+        AppAction::PreviousSession,
+        AppAction::Copy,
+        AppAction::Paste,
+        AppAction::ToggleFullscreen,
+        AppAction::TerminalSearch,
+        AppAction::ClearScrollback,
+        AppAction::ResetTerminal,
+        AppAction::SplitRight,
+        AppAction::SplitDown,
+        AppAction::FocusNextPane,
+        AppAction::SnippetPalette,
+        AppAction::VncViewOnly,
+        AppAction::ZoomIn,
+        AppAction::ZoomOut,
+        AppAction::ZoomReset,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AppAction::NewWindow => "New window",
+            AppAction::NewConnection => "New connection",
+            AppAction::CloseSession => "Close session",
+            AppAction::NewTab => "New tab",
+            AppAction::NextSession => "Next session",
+            AppAction::PreviousSession => "Previous session",
+            AppAction::Copy => "Copy",
+            AppAction::Paste => "Paste",
+            AppAction::ToggleFullscreen => "Toggle fullscreen",
+            AppAction::TerminalSearch => "Search terminal",
+            AppAction::ClearScrollback => "Clear scrollback",
+            AppAction::ResetTerminal => "Reset terminal",
+            AppAction::SplitRight => "Split right",
+            AppAction::SplitDown => "Split down",
+            AppAction::FocusNextPane => "Focus next pane",
+            AppAction::SnippetPalette => "Snippet palette",
+            AppAction::VncViewOnly => "VNC view-only",
+            AppAction::ZoomIn => "Zoom in",
+            AppAction::ZoomOut => "Zoom out",
+            AppAction::ZoomReset => "Reset zoom",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -142,6 +203,57 @@ impl Keybinding {
     pub fn matches(&self, key: &Key, modifiers: &Modifiers) -> bool {
         self.0.matches(key, modifiers)
     }
+
+    /// Binding for a pressed key, or `None` for keys a binding cannot name
+    /// (a modifier on its own, arrows, non-ASCII characters, ...)
+    pub fn from_key_event(key: &Key, modifiers: &Modifiers) -> Option<Self> {
+        let key = match key {
+            Key::Character(c) => {
+                let mut chars = c.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) if ch.is_ascii_graphic() => {
+                        KeybindingKey::Character(ch.to_ascii_lowercase())
+                    }
+                    _ => return None,
+                }
+            }
+            Key::Named(keyboard::key::Named::Tab) => KeybindingKey::Tab,
+            Key::Named(keyboard::key::Named::Insert) => KeybindingKey::Insert,
+            Key::Named(named) => KeybindingKey::F(function_key_number(named)?),
+            _ => return None,
+        };
+        Some(Keybinding(KeyCombo {
+            key,
+            modifiers: ModifierState {
+                ctrl: modifiers.control(),
+                shift: modifiers.shift(),
+                alt: modifiers.alt(),
+                super_key: modifiers.logo(),
+            },
+        }))
+    }
+}
+
+fn function_key_number(named: &keyboard::key::Named) -> Option<u8> {
+    use keyboard::key::Named;
+
+    [
+        Named::F1,
+        Named::F2,
+        Named::F3,
+        Named::F4,
+        Named::F5,
+        Named::F6,
+        Named::F7,
+        Named::F8,
+        Named::F9,
+        Named::F10,
+        Named::F11,
+        Named::F12,
+    ]
+    .iter()
+    .position(|candidate| candidate == named)
+    .map(|index| index as u8 + 1)
 }
 
 impl fmt::Display for Keybinding {
@@ -267,6 +379,26 @@ pub struct KeybindingsConfig {
     pub toggle_fullscreen: Vec<Keybinding>,
     #[serde(default = "default_terminal_search")]
     pub terminal_search: Vec<Keybinding>,
+    #[serde(default = "default_clear_scrollback")]
+    pub clear_scrollback: Vec<Keybinding>,
+    #[serde(default = "default_reset_terminal")]
+    pub reset_terminal: Vec<Keybinding>,
+    #[serde(default = "default_split_right")]
+    pub split_right: Vec<Keybinding>,
+    #[serde(default = "default_split_down")]
+    pub split_down: Vec<Keybinding>,
+    #[serde(default = "default_focus_next_pane")]
+    pub focus_next_pane: Vec<Keybinding>,
+    #[serde(default = "default_snippet_palette")]
+    pub snippet_palette: Vec<Keybinding>,
+    #[serde(default = "default_vnc_view_only")]
+    pub vnc_view_only: Vec<Keybinding>,
+    #[serde(default = "default_zoom_in")]
+    pub zoom_in: Vec<Keybinding>,
+    #[serde(default = "default_zoom_out")]
+    pub zoom_out: Vec<Keybinding>,
+    #[serde(default = "default_zoom_reset")]
+    pub zoom_reset: Vec<Keybinding>,
 }
 
 impl Default for KeybindingsConfig {
@@ -282,13 +414,29 @@ impl Default for KeybindingsConfig {
             terminal_paste: default_terminal_paste(),
             toggle_fullscreen: default_toggle_fullscreen(),
             terminal_search: default_terminal_search(),
+            clear_scrollback: default_clear_scrollback(),
+            reset_terminal: default_reset_terminal(),
+            split_right: default_split_right(),
+            split_down: default_split_down(),
+            focus_next_pane: default_focus_next_pane(),
+            snippet_palette: default_snippet_palette(),
+            vnc_view_only: default_vnc_view_only(),
+            zoom_in: default_zoom_in(),
+            zoom_out: default_zoom_out(),
+            zoom_reset: default_zoom_reset(),
         }
     }
 }
 
 impl KeybindingsConfig {
     pub fn matches_action(&self, action: AppAction, key: &Key, modifiers: &Modifiers) -> bool {
-        let bindings = match action {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.matches(key, modifiers))
+    }
+
+    pub fn bindings(&self, action: AppAction) -> &[Keybinding] {
+        match action {
             AppAction::NewWindow => &self.new_window,
             AppAction::NewConnection => &self.new_connection,
             AppAction::CloseSession => &self.close_session,
@@ -299,11 +447,72 @@ impl KeybindingsConfig {
             AppAction::Paste => &self.terminal_paste,
             AppAction::ToggleFullscreen => &self.toggle_fullscreen,
             AppAction::TerminalSearch => &self.terminal_search,
-        };
+            AppAction::ClearScrollback => &self.clear_scrollback,
+            AppAction::ResetTerminal => &self.reset_terminal,
+            AppAction::SplitRight => &self.split_right,
+            AppAction::SplitDown => &self.split_down,
+            AppAction::FocusNextPane => &self.focus_next_pane,
+            AppAction::SnippetPalette => &self.snippet_palette,
+            AppAction::VncViewOnly => &self.vnc_view_only,
+            AppAction::ZoomIn => &self.zoom_in,
+            AppAction::ZoomOut => &self.zoom_out,
+            AppAction::ZoomReset => &self.zoom_reset,
+        }
+    }
+
+    fn bindings_mut(&mut self, action: AppAction) -> &mut Vec<Keybinding> {
+        match action {
+            AppAction::NewWindow => &mut self.new_window,
+            AppAction::NewConnection => &mut self.new_connection,
+            AppAction::CloseSession => &mut self.close_session,
+            AppAction::NewTab => &mut self.new_tab,
+            AppAction::NextSession => &mut self.next_session,
+            AppAction::PreviousSession => &mut self.previous_session,
+            AppAction::Copy => &mut self.terminal_copy,
+            AppAction::Paste => &mut self.terminal_paste,
+            AppAction::ToggleFullscreen => &mut self.toggle_fullscreen,
+            AppAction::TerminalSearch => &mut self.terminal_search,
+            AppAction::ClearScrollback => &mut self.clear_scrollback,
+            AppAction::ResetTerminal => &mut self.reset_terminal,
+            AppAction::SplitRight => &mut self.split_right,
+            AppAction::SplitDown => &mut self.split_down,
+            AppAction::FocusNextPane => &mut self.focus_next_pane,
+            AppAction::SnippetPalette => &mut self.snippet_palette,
+            AppAction::VncViewOnly => &mut self.vnc_view_only,
+            AppAction::ZoomIn => &mut self.zoom_in,
+            AppAction::ZoomOut => &mut self.zoom_out,
+            AppAction::ZoomReset => &mut self.zoom_reset,
+        }
+    }
+
+    /// Replace the bindings of `action` with a single shortcut
+    pub fn set_binding(&mut self, action: AppAction, binding: Keybinding) {
+        *self.bindings_mut(action) = vec![binding];
+    }
 
-        bindings
+    /// Restore the default bindings of `action`
+    pub fn reset(&mut self, action: AppAction) {
+        *self.bindings_mut(action) = Self::default().bindings(action).to_vec();
+    }
+
+    pub fn is_default(&self, action: AppAction) -> bool {
+        self.bindings(action) == Self::default().bindings(action)
+    }
+
+    /// Other actions sharing a shortcut with `action`
+    pub fn conflicts(&self, action: AppAction) -> Vec<AppAction> {
+        let bindings = self.bindings(action);
+        AppAction::ALL
             .iter()
-            .any(|binding| binding.matches(key, modifiers))
+            .copied()
+            .filter(|&other| {
+                other != action
+                    && self
+                        .bindings(other)
+                        .iter()
+                        .any(|binding| bindings.contains(binding))
+            })
+            .collect()
     }
 }
 
@@ -347,6 +556,46 @@ fn default_terminal_search() -> Vec<Keybinding> {
     vec![Keybinding::parse("Ctrl+Shift+F").expect("valid default")]
 }
 
+fn default_clear_scrollback() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+Shift+L").expect("valid default")]
+}
+
+fn default_reset_terminal() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+Shift+R").expect("valid default")]
+}
+
+fn default_split_right() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+Shift+D").expect("valid default")]
+}
+
+fn default_split_down() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+Shift+E").expect("valid default")]
+}
+
+fn default_focus_next_pane() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+Shift+J").expect("valid default")]
+}
+
+fn default_snippet_palette() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+P").expect("valid default")]
+}
+
+fn default_vnc_view_only() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+Shift+I").expect("valid default")]
+}
+
+fn default_zoom_in() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+=").expect("valid default")]
+}
+
+fn default_zoom_out() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+-").expect("valid default")]
+}
+
+fn default_zoom_reset() -> Vec<Keybinding> {
+    vec![Keybinding::parse("Ctrl+0").expect("valid default")]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -435,6 +684,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn key_events_record_as_parseable_bindings() {
+        let binding = Keybinding::from_key_event(
+            &Key::Character("K".into()),
+            &(Modifiers::CTRL | Modifiers::SHIFT),
+        )
+        .unwrap();
+        assert_eq!(binding, Keybinding::parse("Ctrl+Shift+K").unwrap());
+
+        let binding =
+            Keybinding::from_key_event(&Key::Named(keyboard::key::Named::F5), &Modifiers::ALT)
+                .unwrap();
+        assert_eq!(binding.to_string(), "Alt+F5");
+
+        assert!(
+            Keybinding::from_key_event(&Key::Named(keyboard::key::Named::Shift), &Modifiers::SHIFT)
+                .is_none()
+        );
+        assert!(
+            Keybinding::from_key_event(&Key::Character("é".into()), &Modifiers::CTRL).is_none()
+        );
+    }
+
+    #[test]
+    fn shared_shortcuts_are_reported_as_conflicts() {
+        let mut config = KeybindingsConfig::default();
+        assert!(
+            AppAction::ALL
+                .iter()
+                .all(|&action| config.conflicts(action).is_empty())
+        );
+
+        config.set_binding(
+            AppAction::SplitDown,
+            Keybinding::parse("Ctrl+Shift+T").unwrap(),
+        );
+        assert_eq!(
+            config.conflicts(AppAction::SplitDown),
+            vec![AppAction::NewTab]
+        );
+        assert_eq!(
+            config.conflicts(AppAction::NewTab),
+            vec![AppAction::SplitDown]
+        );
+        assert!(!config.is_default(AppAction::SplitDown));
+
+        config.reset(AppAction::SplitDown);
+        assert!(config.is_default(AppAction::SplitDown));
+        assert!(config.conflicts(AppAction::NewTab).is_empty());
+    }
+
     #[test]
     fn parse_rejects_non_ascii_character_keys() {
         assert_eq!(
@@ -0,0 +1,214 @@
+//! Discovery of one-click local shell targets
+//!
+//! Enumerates WSL distributions (Windows only) and running Docker containers
+//! and turns each into a [`LocalSpawnSpec`] that opens a shell inside it.
+
+use std::time::Duration;
+
+use super::LocalSpawnSpec;
+
+/// Maximum time a discovery command may take before it is abandoned.
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shell started inside a container: bash when available, otherwise sh.
+const CONTAINER_SHELL: &str =
+    "if command -v bash >/dev/null 2>&1; then exec bash; else exec sh; fi";
+
+/// A discovered shell that can be opened as a local terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchTarget {
+    /// Tab title and palette label
+    pub name: String,
+    /// Short description shown under the name
+    pub detail: String,
+    /// How to start the shell
+    pub spec: LocalSpawnSpec,
+}
+
+/// Enumerate launchable WSL distributions and Docker containers.
+///
+/// Missing tools and failing commands are not errors; they just contribute
+/// no targets.
+pub async fn discover_launch_targets() -> Vec<LaunchTarget> {
+    let mut targets = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    if let Some(output) = run_discovery_command("wsl.exe", &["--list", "--quiet"]).await {
+        targets.extend(
+            parse_wsl_list(&decode_wsl_output(&output))
+                .into_iter()
+                .map(wsl_target),
+        );
+    }
+
+    if let Some(output) = run_discovery_command(
+        "docker",
+        &["ps", "--format", "{{.ID}}\t{{.Names}}\t{{.Image}}"],
+    )
+    .await
+    {
+        targets.extend(
+            parse_docker_ps(&String::from_utf8_lossy(&output))
+                .into_iter()
+                .map(container_target),
+        );
+    }
+
+    targets
+}
+
+async fn run_discovery_command(program: &str, args: &[&str]) -> Option<Vec<u8>> {
+    let mut command = tokio::process::Command::new(program);
+    command
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    match tokio::time::timeout(DISCOVERY_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) if output.status.success() => Some(output.stdout),
+        Ok(Ok(output)) => {
+            tracing::debug!("{} exited with {}", program, output.status);
+            None
+        }
+        Ok(Err(e)) => {
+            tracing::debug!("Could not run {}: {}", program, e);
+            None
+        }
+        Err(_) => {
+            tracing::debug!("{} timed out during shell discovery", program);
+            None
+        }
+    }
+}
+
+/// Decode `wsl.exe` output, which is UTF-16LE unless `WSL_UTF8` is set.
+#[cfg(any(target_os = "windows", test))]
+fn decode_wsl_output(bytes: &[u8]) -> String {
+    if bytes.len().is_multiple_of(2) && bytes.iter().skip(1).step_by(2).any(|byte| *byte == 0) {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+/// Parse the distribution names printed by `wsl --list --quiet`.
+#[cfg(any(target_os = "windows", test))]
+fn parse_wsl_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '\u{feff}' || c == '\0'))
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A running container as reported by `docker ps`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ContainerInfo {
+    id: String,
+    name: String,
+    image: String,
+}
+
+/// Parse `docker ps --format '{{.ID}}\t{{.Names}}\t{{.Image}}'` output.
+fn parse_docker_ps(output: &str) -> Vec<ContainerInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim_end().split('\t');
+            let id = fields.next()?.trim();
+            let name = fields.next()?.trim();
+            let image = fields.next().unwrap_or_default().trim();
+            if id.is_empty() || name.is_empty() {
+                return None;
+            }
+            Some(ContainerInfo {
+                id: id.to_string(),
+                name: name.to_string(),
+                image: image.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn wsl_target(distro: String) -> LaunchTarget {
+    LaunchTarget {
+        name: format!("WSL: {}", distro),
+        detail: "Windows Subsystem for Linux".to_string(),
+        spec: LocalSpawnSpec {
+            shell: Some("wsl.exe".to_string()),
+            args: vec![
+                "--distribution".to_string(),
+                distro,
+                "--cd".to_string(),
+                "~".to_string(),
+            ],
+            ..Default::default()
+        },
+    }
+}
+
+fn container_target(container: ContainerInfo) -> LaunchTarget {
+    LaunchTarget {
+        name: format!("Container: {}", container.name),
+        detail: format!("docker exec into {}", container.image),
+        spec: LocalSpawnSpec {
+            shell: Some("docker".to_string()),
+            args: vec![
+                "exec".to_string(),
+                "-it".to_string(),
+                container.id,
+                "sh".to_string(),
+                "-c".to_string(),
+                CONTAINER_SHELL.to_string(),
+            ],
+            ..Default::default()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_utf16_wsl_output() {
+        let text = "\u{feff}Ubuntu\r\ndocker-desktop\r\n";
+        let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let decoded = decode_wsl_output(&bytes);
+        assert_eq!(parse_wsl_list(&decoded), vec!["Ubuntu", "docker-desktop"]);
+    }
+
+    #[test]
+    fn decodes_utf8_wsl_output() {
+        let decoded = decode_wsl_output(b"Debian\n\nUbuntu-22.04\n");
+        assert_eq!(parse_wsl_list(&decoded), vec!["Debian", "Ubuntu-22.04"]);
+    }
+
+    #[test]
+    fn parses_docker_ps_rows() {
+        let containers =
+            parse_docker_ps("3f2a1b\tweb\tnginx:latest\n\nbad-row\n9c8d\tdb\tpostgres:16\n");
+        assert_eq!(containers.len(), 2);
+        assert_eq!(containers[0].name, "web");
+        assert_eq!(containers[1].image, "postgres:16");
+    }
+
+    #[test]
+    fn container_target_execs_interactive_shell() {
+        let target = container_target(ContainerInfo {
+            id: "3f2a1b".to_string(),
+            name: "web".to_string(),
+            image: "nginx".to_string(),
+        });
+        assert_eq!(target.name, "Container: web");
+        assert_eq!(target.spec.shell.as_deref(), Some("docker"));
+        assert_eq!(&target.spec.args[..3], ["exec", "-it", "3f2a1b"]);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Provides PTY-based local terminal sessions that run the user's shell.
 
+mod discovery;
 mod session;
 
-pub use session::{LocalEvent, LocalSession};
+pub use discovery::{LaunchTarget, discover_launch_targets};
+pub use session::{LocalEvent, LocalSession, LocalSpawnSpec};
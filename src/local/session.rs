@@ -4,8 +4,11 @@
 
 use portable_pty::{CommandBuilder, PtySize, native_pty_system};
 use std::io::{Read, Write};
+use std::path::PathBuf;
 use tokio::sync::mpsc;
 
+use crate::config::TerminalProfile;
+use crate::config::paths::expand_tilde;
 use crate::error::LocalError;
 
 /// Events emitted by local PTY sessions
@@ -17,6 +20,45 @@ pub enum LocalEvent {
     Disconnected,
 }
 
+/// How to start a local shell
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalSpawnSpec {
+    /// Shell program; the user's configured shell when unset
+    pub shell: Option<String>,
+    /// Arguments passed to the shell
+    pub args: Vec<String>,
+    /// Working directory; the home directory when unset
+    pub cwd: Option<PathBuf>,
+    /// Extra environment variables, applied after Portal's terminal variables
+    pub env: Vec<(String, String)>,
+}
+
+impl LocalSpawnSpec {
+    /// Build a spawn spec from a saved terminal profile
+    pub fn from_profile(profile: &TerminalProfile) -> Self {
+        Self {
+            shell: profile
+                .shell
+                .as_deref()
+                .map(str::trim)
+                .filter(|shell| !shell.is_empty())
+                .map(str::to_string),
+            args: profile.args.clone(),
+            cwd: profile
+                .working_dir
+                .as_deref()
+                .map(str::trim)
+                .filter(|dir| !dir.is_empty())
+                .map(expand_tilde),
+            env: profile
+                .env
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        }
+    }
+}
+
 /// Commands that can be sent to the PTY task
 enum PtyCommand {
     Data(Vec<u8>),
@@ -40,9 +82,11 @@ impl std::fmt::Debug for LocalSession {
 impl LocalSession {
     /// Spawn a new local terminal session
     ///
-    /// Uses the user's configured shell and starts it as an interactive terminal shell.
+    /// Starts the shell described by `spec` (the user's configured shell by
+    /// default) as an interactive terminal shell.
     /// Returns a session handle and spawns a background task for PTY I/O.
     pub fn spawn(
+        spec: &LocalSpawnSpec,
         cols: u16,
         rows: u16,
         event_tx: mpsc::Sender<LocalEvent>,
@@ -51,9 +95,18 @@ impl LocalSession {
         // environment falls back to the password database when SHELL is absent,
         // which is common for GUI-launched applications.
         #[cfg(target_os = "windows")]
-        let shell = std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string());
+        let default_shell = || std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string());
         #[cfg(not(target_os = "windows"))]
-        let shell = CommandBuilder::new_default_prog().get_shell();
+        let default_shell = || CommandBuilder::new_default_prog().get_shell();
+        let shell = spec.shell.clone().unwrap_or_else(default_shell);
+        if let Some(cwd) = &spec.cwd
+            && !cwd.is_dir()
+        {
+            return Err(LocalError::SpawnFailed(format!(
+                "Working directory {} does not exist",
+                cwd.display()
+            )));
+        }
         tracing::info!("Spawning local terminal");
 
         // Create PTY system
@@ -72,6 +125,10 @@ impl LocalSession {
         // Build command for a normal interactive terminal shell. The PTY makes
         // shells interactive, so bash/zsh/fish read their usual rc files.
         let mut cmd = CommandBuilder::new(&shell);
+        cmd.args(&spec.args);
+        if let Some(cwd) = &spec.cwd {
+            cmd.cwd(cwd);
+        }
         // Set TERM for proper terminal emulation
         cmd.env("TERM", "xterm-256color");
         cmd.env("COLORTERM", "truecolor");
@@ -84,6 +141,9 @@ impl LocalSession {
         // OSC 133 command boundary markers, which Portal uses for completion
         // notifications when the shell supports them.
         cmd.env("VTE_VERSION", "7600");
+        for (key, value) in &spec.env {
+            cmd.env(key, value);
+        }
 
         // Spawn shell process
         let child = pair
@@ -227,3 +287,22 @@ impl Drop for LocalSession {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_spec_from_profile_ignores_blank_fields() {
+        let mut profile = TerminalProfile::new("Blank".to_string(), Some("  ".to_string()));
+        profile.working_dir = Some(String::new());
+        profile.args = vec!["-l".to_string()];
+        profile.env.insert("EDITOR".to_string(), "vim".to_string());
+
+        let spec = LocalSpawnSpec::from_profile(&profile);
+        assert_eq!(spec.shell, None);
+        assert_eq!(spec.cwd, None);
+        assert_eq!(spec.args, vec!["-l".to_string()]);
+        assert_eq!(spec.env, vec![("EDITOR".to_string(), "vim".to_string())]);
+    }
+}
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use chrono::{TimeZone, Utc};
 
-use crate::sftp::FileEntry;
+use crate::sftp::{FileEntry, SymlinkTarget};
 
 /// List local directory contents
 pub async fn list_local_dir(path: &Path) -> Result<Vec<FileEntry>, String> {
@@ -28,6 +28,7 @@ fn list_local_dir_sync(path: &Path) -> Result<Vec<FileEntry>, String> {
             path: parent.to_path_buf(),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: None,
         });
@@ -53,6 +54,9 @@ fn list_local_dir_sync(path: &Path) -> Result<Vec<FileEntry>, String> {
             .map(|meta| meta.is_dir())
             .unwrap_or_else(|| metadata.is_dir());
         let is_symlink = metadata.file_type().is_symlink();
+        let symlink_target = is_symlink
+            .then(|| local_symlink_target(&entry_path))
+            .flatten();
         let size = target_metadata
             .as_ref()
             .map(|meta| meta.len())
@@ -73,6 +77,7 @@ fn list_local_dir_sync(path: &Path) -> Result<Vec<FileEntry>, String> {
             path: entry_path,
             is_dir,
             is_symlink,
+            symlink_target,
             size,
             modified,
         });
@@ -81,6 +86,16 @@ fn list_local_dir_sync(path: &Path) -> Result<Vec<FileEntry>, String> {
     Ok(result)
 }
 
+/// Read where a local symlink points; broken links keep their text but
+/// have no resolved path.
+fn local_symlink_target(path: &Path) -> Option<SymlinkTarget> {
+    let target = std::fs::read_link(path).ok()?;
+    Some(SymlinkTarget {
+        target: target.to_string_lossy().into_owned(),
+        resolved: std::fs::canonicalize(path).ok(),
+    })
+}
+
 fn ensure_local_dir_root(path: &Path) -> Result<(), String> {
     let metadata = std::fs::symlink_metadata(path)
         .map_err(|e| format!("Failed to read directory metadata: {}", e))?;
@@ -169,6 +184,11 @@ mod tests {
         assert!(link_entry.is_dir);
         assert!(link_entry.is_symlink);
         assert!(!link_entry.is_navigable_dir());
+        assert!(!link_entry.is_broken_symlink());
+        assert_eq!(
+            link_entry.navigation_path(),
+            Some(std::fs::canonicalize(&target).unwrap().as_path())
+        );
     }
 
     #[cfg(unix)]
@@ -187,6 +207,9 @@ mod tests {
         assert!(!link_entry.is_dir);
         assert!(link_entry.is_symlink);
         assert!(!link_entry.is_navigable_dir());
+        assert!(link_entry.is_broken_symlink());
+        assert_eq!(link_entry.navigation_path(), None);
+        assert!(link_entry.kind_label().ends_with("missing"));
     }
 
     #[cfg(unix)]
@@ -17,6 +17,7 @@ fn main() -> iced::Result {
         .title("Portal")
         .theme(Portal::theme)
         .subscription(Portal::subscription)
+        .exit_on_close_request(false)
         .window_size(Size::new(1200.0, 800.0))
         .default_font(fonts::INTER)
         .font(fonts::INTER_BYTES)
@@ -2,22 +2,26 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
-use iced::widget::text_editor;
+use iced::widget::{pane_grid, text_editor};
 use secrecy::SecretString;
 use uuid::Uuid;
 
 use crate::app::managers::TransferProgress;
-use crate::config::DetectedOs;
-use crate::local::LocalSession;
+use crate::config::{DetectedOs, HistoryDateRange, HistorySort, SessionType};
+use crate::keybindings::{AppAction, Keybinding};
+use crate::local::{LaunchTarget, LocalSession};
 use crate::proxy::{ListedProxySession, ProxySession, ProxyStatus};
-use crate::sftp::{FileEntry, SharedSftpSession};
+use crate::sftp::chmod::{ChmodProgress, ChmodReport};
+use crate::sftp::{DirSize, FileEntry, FileProperties, SharedSftpSession, SyncOptions, SyncPlan};
 use crate::ssh::SshSession;
 use crate::ssh::host_key_verification::HostKeyVerificationRequest;
-use crate::terminal::backend::TerminalEvent;
+use crate::terminal::backend::{ClipboardResponder, TerminalEvent};
 use crate::terminal::links::TerminalLink;
 use crate::terminal_paste::TerminalPastePayload;
-use crate::theme::ThemeId;
-use crate::views::file_viewer::ViewerContent;
+use crate::theme::{ThemeColorField, ThemeId};
+use crate::views::dialogs::host_dialog::ConnectionTestStatus;
+use crate::views::file_viewer::{PdfPageText, ViewerContent};
+use crate::views::host_grid::{HostGridDragItem, HostGridDropTarget};
 use crate::views::sftp::{ContextMenuAction, PaneId, PaneSource, PermissionBit, SftpColumn};
 use crate::vnc::VncSession;
 
@@ -47,6 +51,7 @@ pub enum SettingsTab {
     PortalHub,
     SecurityLogs,
     Snippets,
+    Keybindings,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -60,9 +65,38 @@ pub enum CommandAction {
     QuickConnect,
     NewHost,
     LocalTerminal,
+    LocalTerminalProfile(Uuid),
+    LocalTerminalTarget(LaunchTarget),
     ConnectHost(Uuid),
     RunSnippet(Uuid),
+    ToggleMacroRecording,
+    ToggleBroadcast,
+    SplitRight,
+    SplitDown,
+    PlayMacro(Uuid),
     PortalHubSync,
+    ExportSshConfig,
+    ForgetCredentials,
+}
+
+/// Destination picked in the quick switcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitcherTarget {
+    /// Switch to an open tab
+    Session(Uuid),
+    /// Connect to a saved host
+    Host(Uuid),
+    /// Run a snippet
+    Snippet(Uuid),
+}
+
+/// Pick in the snippet palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnippetPaletteEntry {
+    /// Insert a saved snippet, prompting for its placeholders
+    Snippet(Uuid),
+    /// Insert a recently run command as is
+    Command(String),
 }
 
 #[derive(Debug, Clone)]
@@ -75,11 +109,14 @@ pub enum HostDialogField {
     KeySource,
     KeyPath,
     VaultKeyId,
+    AgentIdentity,
     VncPasswordId,
     VncViaSshHostId,
     AgentForwarding,
+    SessionLogging,
     HubRouting,
     JumpHostId,
+    SftpStartPath,
     Tags,
     Notes,
     Protocol,
@@ -169,6 +206,8 @@ pub enum SearchMessage {
     PreviousMatch(SessionId),
     /// Toggle case-sensitive matching
     CaseSensitiveToggled(SessionId),
+    /// Toggle regex matching
+    RegexToggled(SessionId),
 }
 
 /// Terminal session-related messages
@@ -181,11 +220,14 @@ pub enum SessionMessage {
         ssh_session: Arc<SshSession>,
         host_id: Uuid,
         detected_os: Option<DetectedOs>,
+        /// Bound port (or error) of each enabled port forward, keyed by forward id
+        forward_results: Vec<(Uuid, Result<u16, String>)>,
     },
     /// Local terminal session established
     LocalConnected {
         session_id: SessionId,
         local_session: Arc<LocalSession>,
+        title: String,
     },
     /// Portal Hub terminal session established
     ProxyConnected {
@@ -219,6 +261,8 @@ pub enum SessionMessage {
     },
     /// Terminal input from user
     Input(SessionId, Vec<u8>),
+    /// Type text at the prompt without running it (pasted, so no Enter)
+    InsertText(SessionId, String),
     /// User requested a terminal paste from the local clipboard
     Paste(SessionId),
     /// Local clipboard payload read for a terminal paste
@@ -227,12 +271,38 @@ pub enum SessionMessage {
     PasteTextFallbackLoaded(SessionId, Option<String>, String),
     /// Uploaded clipboard image path ready to paste into terminal
     PasteImageUploaded(SessionId, Result<String, String>),
+    /// Folder chosen for an incoming ZMODEM transfer (`None` = cancelled)
+    ZmodemSaveDirChosen(SessionId, Option<PathBuf>),
+    /// Drop the terminal's scrollback history
+    ClearScrollback(SessionId),
+    /// Re-initialize the terminal emulator state
+    ResetTerminal(SessionId),
+    /// Reset the terminal and run `reset` in the remote shell
+    SendReset(SessionId),
+    /// Add the session to the broadcast input group, or remove it
+    ToggleBroadcast(SessionId),
+    /// Split the session's pane and open another session for the same host in it
+    SplitPane(SessionId, pane_grid::Axis),
+    /// Give keyboard focus to a pane of a split tab
+    FocusPane(SessionId),
+    /// Move focus to the pane after this one in its split tab
+    FocusNextPane(SessionId),
+    /// A split divider of the session's tab was dragged
+    PaneResized(SessionId, pane_grid::ResizeEvent),
+    /// Font size of one pane of a split tab (`None` follows the session)
+    PaneFontSize(SessionId, Option<f32>),
+    /// Terminal colours of one pane of a split tab (`None` follows the theme)
+    PaneColors(SessionId, Option<crate::theme::TerminalColors>),
+    /// Start recording input into a macro, or stop and save the recording
+    MacroRecordToggle(SessionId),
+    /// Replay a saved macro into the session
+    MacroPlay(SessionId, Uuid),
     /// Terminal resize event
     Resize(SessionId, u16, u16),
     /// Terminal backend event (title/bell/clipboard/exit)
     TerminalEvent(SessionId, TerminalEvent),
-    /// Clipboard content read for terminal
-    ClipboardLoaded(SessionId, Option<String>),
+    /// Clipboard content read for an OSC 52 query
+    ClipboardLoaded(SessionId, ClipboardResponder, Option<String>),
     /// Timer tick for session duration updates
     DurationTick,
     /// User pressed Ctrl+Shift+K to install SSH key
@@ -250,6 +320,16 @@ pub enum SessionMessage {
     },
     /// Terminal scrollback search (find-in-buffer)
     Search(SearchMessage),
+    /// Open the port forwards panel for an SSH session
+    OpenPortForwards(SessionId),
+    /// Start one of the host's configured forwards on the live session
+    StartPortForward(SessionId, Uuid),
+    /// Stop a running forward on the live session
+    StopPortForward(SessionId, Uuid),
+    /// Result of starting a forward from the panel (bound port on success)
+    PortForwardStarted(SessionId, Uuid, Result<u16, String>),
+    /// Result of stopping a forward from the panel
+    PortForwardStopped(SessionId, Uuid, Result<(), String>),
 }
 
 /// Resolved target of a Ctrl+clicked terminal file path
@@ -280,6 +360,14 @@ pub enum SftpMessage {
     PaneRefresh(SessionId, PaneId),
     /// Select file by index
     PaneSelect(SessionId, PaneId, usize),
+    /// Add or remove a file from the selection (Ctrl/Cmd+click)
+    PaneToggleSelect(SessionId, PaneId, usize),
+    /// Select the range from the last clicked file (Shift+click)
+    PaneSelectRange(SessionId, PaneId, usize),
+    /// Open a file in the in-app viewer (double-click)
+    PaneOpenFile(SessionId, PaneId, usize),
+    /// File list scrolled (offset, viewport height)
+    PaneScrolled(SessionId, PaneId, f32, f32),
     /// Directory listing result
     PaneListResult(
         SessionId,
@@ -288,6 +376,24 @@ pub enum SftpMessage {
         PathBuf,
         Result<Vec<FileEntry>, String>,
     ),
+    /// Turn the path bar into an editable input
+    PathEditStart(SessionId, PaneId),
+    /// Typed path changed
+    PathInputChanged(SessionId, PaneId, String),
+    /// Tab-complete the typed path
+    PathInputComplete(SessionId, PaneId),
+    /// Navigate to the typed path (Enter)
+    PathSubmit(SessionId, PaneId),
+    /// Stop editing the path bar without navigating
+    PathEditCancel(SessionId, PaneId),
+    /// Listing of a typed path; failures keep the pane where it was
+    PathSubmitResult(
+        SessionId,
+        PaneId,
+        PaneSource,
+        PathBuf,
+        Result<Vec<FileEntry>, String>,
+    ),
     /// Connect pane to remote host
     ConnectHost(SessionId, PaneId, Uuid),
     /// SFTP connection succeeded for pane
@@ -298,6 +404,11 @@ pub enum SftpMessage {
         host_id: Uuid,
         host_name: String,
         sftp_session: SharedSftpSession,
+        /// Directory to show first (the last visited directory, the host's
+        /// SFTP start path, or home)
+        initial_path: PathBuf,
+        /// Last visited directory that no longer exists on the server
+        missing_last_path: Option<PathBuf>,
     },
     /// Show context menu at position
     ShowContextMenu(SessionId, PaneId, f32, f32, Option<usize>),
@@ -313,6 +424,10 @@ pub enum SftpMessage {
     DialogSubmit(SessionId),
     /// Result of folder creation
     NewFolderResult(SessionId, PaneId, Result<(), String>),
+    /// Toggle opening the new file in the viewer once it is created
+    NewFileOpenAfterToggled(SessionId),
+    /// New file creation result, with the created path
+    NewFileResult(SessionId, PaneId, Result<PathBuf, String>),
     /// Result of rename operation
     RenameResult(SessionId, PaneId, Result<(), String>),
     /// Result of delete operation (count deleted)
@@ -321,10 +436,63 @@ pub enum SftpMessage {
     PermissionToggle(SessionId, PermissionBit, bool),
     /// Result of chmod operation
     PermissionsResult(SessionId, PaneId, Result<(), String>),
+    /// Toggle applying permissions or ownership to a folder's whole tree
+    PermissionsRecursiveToggled(SessionId),
+    /// Toggle keeping folders searchable in a recursive permission change
+    PermissionsSearchableDirsToggled(SessionId),
+    /// Owner typed into the Change Owner dialog
+    OwnerInputChanged(SessionId, String),
+    /// Group typed into the Change Owner dialog
+    GroupInputChanged(SessionId, String),
+    /// Switch from the Properties dialog to changing the entry's owner
+    PropertiesChangeOwner(SessionId),
+    /// Result of a chown
+    OwnerResult(SessionId, PaneId, Result<(), String>),
+    /// Running count of a recursive permission or ownership change
+    PermissionsProgress {
+        tab_id: SessionId,
+        walk_id: Uuid,
+        progress: ChmodProgress,
+    },
+    /// A recursive permission or ownership change finished
+    PermissionsRecursiveResult {
+        tab_id: SessionId,
+        pane_id: PaneId,
+        walk_id: Uuid,
+        result: Result<ChmodReport, String>,
+    },
+    /// Metadata read for the open Properties dialog (path it was read for)
+    PropertiesLoaded(SessionId, PathBuf, Result<FileProperties, String>),
+    /// Switch from the Properties dialog to editing the entry's permissions
+    PropertiesEditPermissions(SessionId),
+    /// Sudo password typed into an SFTP dialog's privileged-retry field
+    SudoPasswordChanged(SessionId, SecretString),
     /// Start copying selected files to target pane
     CopyToTarget(SessionId),
-    /// Result of copy operation (count copied, target pane)
-    CopyResult(SessionId, PaneId, Result<usize, String>),
+    /// Sync preview options changed; rescans both directories
+    SyncOptionsChanged(SessionId, SyncOptions),
+    /// Dry-run result for the open sync dialog (options it was planned with)
+    SyncPlanned(SessionId, SyncOptions, Result<SyncPlan, String>),
+    /// A watched local file was uploaded after a change
+    WatchUploaded(Uuid, Result<(), String>),
+    /// Stop watching a local file
+    WatchStop(Uuid),
+    /// A copy opened with an external program changed on disk
+    WatchChanged(Uuid),
+    /// Upload a changed copy opened with an external program back
+    WatchUpload(Uuid),
+    /// A remote file was downloaded for Open With (command to run it with)
+    OpenWithDownloaded {
+        tab_id: SessionId,
+        edit_id: Uuid,
+        /// SFTP connection the file came from
+        session_id: SessionId,
+        remote_path: PathBuf,
+        command: String,
+        result: Result<PathBuf, String>,
+    },
+    /// File contents read for the clipboard (file name, text to copy)
+    ContentsLoaded(String, Result<String, String>),
     /// Transfer progress update for a long-running SFTP copy/upload/download.
     TransferProgress(TransferProgress),
     /// Long-running SFTP transfer finished.
@@ -332,16 +500,36 @@ pub enum SftpMessage {
         transfer_id: Uuid,
         tab_id: SessionId,
         target_pane_id: PaneId,
+        /// Copied files were checked against their source checksums
+        verified: bool,
         result: Result<usize, String>,
     },
     /// Request cancellation of a long-running SFTP transfer.
     TransferCancel(Uuid),
+    /// Request cancellation of every running transfer in an SFTP tab.
+    TransferCancelAll(SessionId),
     /// Clear finished transfer rows.
     TransferClearFinished,
+    /// Running total or final result of a folder size calculation
+    DirSizeUpdated {
+        tab_id: SessionId,
+        pane_id: PaneId,
+        walk_id: Uuid,
+        path: PathBuf,
+        result: Result<DirSize, String>,
+    },
     /// Toggle hidden files visibility
     ToggleShowHidden(SessionId, PaneId),
     /// Toggle actions menu visibility
     ToggleActionsMenu(SessionId, PaneId),
+    /// Bookmark the pane's current directory, or remove its bookmark
+    ToggleBookmark(SessionId, PaneId),
+    /// Toggle bookmarks menu visibility
+    ToggleBookmarksMenu(SessionId, PaneId),
+    /// Open the rename dialog for a bookmark
+    BookmarkRename(SessionId, PaneId, Uuid),
+    /// Remove a bookmark
+    BookmarkDelete(SessionId, Uuid),
     /// Filter text changed
     FilterChanged(SessionId, PaneId, String),
     /// Sort a file list column
@@ -381,6 +569,16 @@ pub enum DialogMessage {
     HostDeleteConfirm,
     /// Host dialog field changed
     FieldChanged(HostDialogField, String),
+    /// Host dialog: list the keys of the running SSH agent
+    AgentIdentitiesLoad,
+    /// Host dialog: agent keys listed
+    AgentIdentitiesLoaded(Result<Vec<crate::ssh::auth::AgentIdentity>, String>),
+    /// Host dialog: try logging in with the current settings
+    ConnectionTest,
+    /// Host dialog: password or key passphrase for the connection test
+    ConnectionTestSecretChanged(SecretString),
+    /// Host dialog: the connection test with this id finished
+    ConnectionTestFinished(Uuid, ConnectionTestStatus),
     /// Port forward editor field changed
     PortForwardFieldChanged(PortForwardField, String),
     /// Toggle port forwards section
@@ -413,6 +611,8 @@ pub enum DialogMessage {
     PasswordSubmit,
     /// Password dialog: user cancelled
     PasswordCancel,
+    /// Password dialog: the methods ahead of a password fallback were rejected
+    PasswordFallbackRequired(PasswordFallbackRequest),
     /// Passphrase dialog: required for encrypted key
     PassphraseRequired(PassphraseRequest),
     /// Passphrase dialog: passphrase text changed
@@ -429,6 +629,10 @@ pub enum DialogMessage {
     QuickConnectSubmit,
     /// Import hosts from SSH config
     ImportFromSshConfig,
+    /// Pick an OpenSSH config file to import hosts from
+    ImportSshConfigFile,
+    /// Import hosts from the picked OpenSSH config file
+    SshConfigFileChosen(std::path::PathBuf),
     /// Keyboard-interactive authentication prompts received from the server
     AuthPrompt(AuthPromptRequestWrapper),
     /// Auth prompt dialog: response input changed (prompt index, value)
@@ -443,6 +647,18 @@ pub enum DialogMessage {
     VncCleartextConnectAnyway,
     /// Unencrypted VNC warning: user cancelled the connection
     VncCleartextCancel,
+    /// Quit confirmation: "don't ask again" toggled
+    QuitConfirmDontAskToggled(bool),
+    /// Quit confirmation: user chose to quit
+    QuitConfirmQuit,
+    /// Quit confirmation: user kept the app open
+    QuitConfirmCancel,
+    /// Snippet parameters: value changed (placeholder index, value)
+    SnippetParamChanged(usize, String),
+    /// Snippet parameters: run with the entered values
+    SnippetParamsSubmit,
+    /// Snippet parameters: user cancelled the run
+    SnippetParamsCancel,
 }
 
 /// Context for passphrase-based SFTP connections
@@ -469,6 +685,15 @@ pub struct PassphraseRequest {
     pub error: Option<String>,
 }
 
+/// Request to prompt for a host's fallback password after the earlier
+/// auth methods were rejected
+#[derive(Debug, Clone)]
+pub struct PasswordFallbackRequest {
+    pub host_id: Uuid,
+    pub session_id: Option<SessionId>,
+    pub sftp_context: Option<PassphraseSftpContext>,
+}
+
 /// Tab management messages
 #[derive(Debug, Clone)]
 pub enum TabMessage {
@@ -509,6 +734,10 @@ pub enum TabContextMenuAction {
     Rename,
     OpenLogFile,
     OpenLogDirectory,
+    ClearScrollback,
+    ResetTerminal,
+    SendReset,
+    ToggleBroadcast,
 }
 
 /// Host management messages
@@ -535,13 +764,60 @@ pub enum HostMessage {
     DetailsOpen(Uuid),
     /// Close the host details sheet
     DetailsClose,
+    /// Copy the equivalent `ssh` command for a host to the clipboard
+    CopySshCommand(Uuid),
+    /// Copy an equivalent `~/.ssh/config` block for a host to the clipboard
+    CopySshConfig(Uuid),
+    /// Write every SSH host to an OpenSSH config file picked by the user
+    ExportSshConfig,
+    /// Result of writing the exported SSH config
+    SshConfigExported(Result<PathBuf, String>),
     /// Quick connect using search query
     QuickConnect,
-    /// Open local terminal (stubbed)
+    /// Open a local terminal with the default profile
     LocalTerminal,
+    /// Open a local terminal with a specific profile
+    LocalTerminalProfile(Uuid),
+    /// Open a shell in a discovered WSL distribution or container
+    LocalTerminalTarget(LaunchTarget),
     /// Cleartext exposure check for a VNC target finished (`warn` is true
     /// when the target resolved to a non-private address)
     VncCleartextCheckDone { host_id: Uuid, warn: bool },
+    /// Probe visible hosts whose reachability is unknown or stale
+    ReachabilityTick,
+    /// A reachability probe for a host finished
+    ReachabilityProbed { host_id: Uuid, reachable: bool },
+    /// Show the host card context menu at position
+    ShowContextMenu(Uuid, f32, f32),
+    /// Hide the host card context menu
+    HideContextMenu,
+    /// Execute a host card context menu action
+    ContextMenuAction(Uuid, HostContextMenuAction),
+    /// Add a copy of a host with a new id, named "<name> (copy)"
+    Duplicate(Uuid),
+    /// Move a host into a group (`None` for ungrouped) at `index` among its
+    /// hosts
+    HostMoved {
+        host_id: Uuid,
+        group_id: Option<Uuid>,
+        index: usize,
+    },
+    /// Move a group inside `parent_id` (`None` for the top level) at `index`
+    /// among its sibling groups
+    GroupReordered {
+        group_id: Uuid,
+        parent_id: Option<Uuid>,
+        index: usize,
+    },
+}
+
+/// Context menu actions for host cards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostContextMenuAction {
+    Connect,
+    Edit,
+    Duplicate,
+    Details,
 }
 
 /// History management messages
@@ -551,6 +827,22 @@ pub enum HistoryMessage {
     Clear,
     /// Reconnect to a history entry
     Reconnect(Uuid),
+    /// Search text changed
+    SearchChanged(String),
+    /// Show only connections to this host (`None` shows all hosts)
+    HostFilterChanged(Option<Uuid>),
+    /// Show only this kind of session (`None` shows all kinds)
+    SessionTypeFilterChanged(Option<SessionType>),
+    /// Show only connections in this date range
+    DateRangeChanged(HistoryDateRange),
+    /// Change the list order
+    SortChanged(HistorySort),
+    /// Reset the search and every facet
+    ClearFilters,
+    /// Pick a file and export the history to it as CSV or JSON
+    Export,
+    /// History export finished, with the file written
+    Exported(Result<PathBuf, String>),
 }
 
 /// Result of executing a snippet command on a single host
@@ -588,10 +880,16 @@ pub enum SnippetMessage {
     // Host association (during edit)
     /// Toggle host selection in edit form
     ToggleHost(Uuid, bool),
+    /// OS filter picked in edit form (`None` = any OS)
+    OsFilterChanged(Option<crate::config::DetectedOs>),
 
     // Execution
     /// Run snippet on associated hosts
     Run(Uuid),
+    /// Run snippet with its placeholders already filled in
+    RunResolved(Uuid, String),
+    /// Insert snippet into a terminal session, prompting for placeholders
+    Insert(SessionId, Uuid),
     /// Single host execution result received
     HostResult {
         snippet_id: Uuid,
@@ -627,16 +925,57 @@ pub enum FileViewerMessage {
     Save(SessionId),
     /// Save operation completed
     SaveResult(SessionId, Result<(), String>),
+    /// Sudo password typed after a refused remote save
+    SudoPasswordChanged(SessionId, SecretString),
+    /// Hide the sudo password field without saving
+    SudoCancel(SessionId),
     /// PDF page navigation
     PdfPageChange(SessionId, usize),
     /// Render a PDF page on demand
     PdfRenderPage(SessionId, usize),
     /// PDF page rendered
     PdfPageRendered(SessionId, usize, Result<Vec<u8>, String>),
+    /// Show or hide the PDF search bar
+    PdfSearchToggle(SessionId),
+    /// PDF search query edited
+    PdfSearchChanged(SessionId, String),
+    /// Jump to the next PDF search match
+    PdfSearchNext(SessionId),
+    /// Jump to the previous PDF search match
+    PdfSearchPrevious(SessionId),
+    /// Text layer of every PDF page read
+    PdfTextLoaded(SessionId, Result<Vec<PdfPageText>, String>),
     /// Toggle markdown preview mode
     MarkdownTogglePreview(SessionId),
+    /// Link clicked in the markdown preview
+    OpenLink(String),
     /// Image zoom level changed
     ImageZoom(SessionId, f32),
+    /// Zoom the image to fit the view and keep it fitted on resize
+    ImageFitToWindow(SessionId),
+    /// Ctrl+wheel zoom: new zoom level and the scroll offset that keeps the
+    /// point under the cursor in place
+    ImageZoomAt(SessionId, f32, iced::widget::scrollable::AbsoluteOffset),
+    /// Image area scrolled
+    ImageScrolled(SessionId, iced::widget::scrollable::AbsoluteOffset),
+    /// Turn the image a quarter turn counter-clockwise
+    ImageRotateLeft(SessionId),
+    /// Turn the image a quarter turn clockwise
+    ImageRotateRight(SessionId),
+    /// Show (or refocus) the go-to-line bar (Ctrl+G)
+    GotoLineOpen(SessionId),
+    /// Go-to-line input edited
+    GotoLineChanged(SessionId, String),
+    /// Jump to the line typed in the go-to-line bar
+    GotoLineSubmit(SessionId),
+    /// Hide the go-to-line bar
+    GotoLineClose(SessionId),
+    /// Turn soft word-wrap of text files on or off (Alt+Z)
+    ToggleWordWrap,
+    /// Switch between the hex dump and the file's own view
+    ToggleHex(SessionId),
+    /// Show the hex dump page containing this byte offset
+    HexOffsetChange(SessionId, usize),
 }
 
 /// VNC session messages
@@ -673,7 +1012,12 @@ pub enum VncMessage {
     ClipboardReceived(SessionId, String),
     /// Send clipboard text to VNC server
     ClipboardSend(SessionId, String),
-    /// Send a special key combination (e.g., Ctrl+Alt+Del)
+    /// Timer tick — check the local clipboard for text to send
+    ClipboardPollTick,
+    /// Local clipboard contents read for the auto-send check
+    LocalClipboardRead(SessionId, Option<String>),
+    /// Press keysyms in order, then release them in reverse: a special key
+    /// combination (e.g., Ctrl+Alt+Del) or a character typed in Unicode mode
     SendSpecialKeys {
         session_id: SessionId,
         keysyms: Vec<u32>,
@@ -695,6 +1039,8 @@ pub enum VncMessage {
     },
     /// Toggle keyboard passthrough mode
     ToggleKeyboardPassthrough,
+    /// Keyboard mode picked in the toolbar for the active session
+    KeyboardModeSelected(crate::config::settings::VncKeyboardMode),
     /// Request a full framebuffer refresh
     ManualRefresh(SessionId),
     /// Toggle display-only mode for the active VNC session
@@ -703,8 +1049,8 @@ pub enum VncMessage {
     ToggleCursorDot,
     /// Toggle detailed VNC stats overlay
     ToggleStatsOverlay,
-    /// Cycle the quality preset for new VNC sessions
-    CycleQualityPreset,
+    /// Quality preset picked in the toolbar; applies to open sessions too
+    QualityPresetSelected(crate::config::settings::VncQualityPreset),
     /// Remote server bell
     Bell(SessionId),
 }
@@ -729,14 +1075,54 @@ pub enum UiMessage {
     CommandPaletteChanged(String),
     /// Execute a command palette action
     CommandPaletteRun(CommandAction),
+    /// Open/close the quick switcher
+    QuickSwitcherToggle,
+    QuickSwitcherClose,
+    /// Quick switcher search changed
+    QuickSwitcherChanged(String),
+    /// Jump to a quick switcher result
+    QuickSwitcherRun(SwitcherTarget),
+    /// Open/close the snippet palette
+    SnippetPaletteToggle,
+    SnippetPaletteClose,
+    /// Snippet palette search changed
+    SnippetPaletteChanged(String),
+    /// Insert a snippet palette pick into the active terminal
+    SnippetPaletteRun(SnippetPaletteEntry),
+    /// Local shell targets (WSL distributions, containers) were discovered
+    LocalLaunchTargetsDiscovered(Vec<LaunchTarget>),
     /// Theme changed
     ThemeChange(ThemeId),
+    /// Create a custom theme from the current one and select it
+    CustomThemeCreate,
+    /// Rename the selected custom theme
+    CustomThemeRename(String),
+    /// Hex value typed for a color of the selected custom theme
+    CustomThemeColorChanged(ThemeColorField, String),
+    /// Delete the selected custom theme
+    CustomThemeDelete,
+    /// Pick a terminal color scheme file to import as a custom theme
+    CustomThemeImportRequested,
+    /// Color scheme file read and parsed
+    CustomThemeImported(Result<crate::theme::import::ImportedScheme, String>),
+    /// Capture the next shortcut pressed as the binding for an action
+    KeybindingRecordStart(AppAction),
+    /// Stop capturing a shortcut
+    KeybindingRecordCancel,
+    /// Shortcut captured for an action
+    KeybindingRecorded(AppAction, Keybinding),
+    /// Restore the default shortcut of an action
+    KeybindingReset(AppAction),
+    /// Restore every default shortcut
+    KeybindingsResetAll,
     /// Terminal font changed
     FontChange(crate::fonts::TerminalFont),
     /// Terminal font size changed
     FontSizeChange(f32),
     /// Terminal mouse wheel / trackpad scroll speed changed
     TerminalScrollSpeedChange(f32),
+    /// Terminal scrollback history size changed (lines, new sessions only)
+    ScrollbackLinesChange(usize),
     /// UI scale changed (0.8 to 1.5)
     UiScaleChange(f32),
     /// Reset UI scale to system default
@@ -749,18 +1135,50 @@ pub enum UiMessage {
     SnippetHistoryStoreOutput(bool),
     /// Redact sensitive values in snippet history
     SnippetHistoryRedactOutput(bool),
+    /// Leave addresses, usernames and errors out of history exports
+    HistoryRedactExport(bool),
     /// Session logging enabled/disabled
     SessionLoggingEnabled(bool),
+    /// Recording typed input in session logs enabled/disabled
+    SessionLogInput(bool),
     /// Global SSH agent forwarding enabled/disabled
     AllowAgentForwarding(bool),
+    /// Host grid reachability probes enabled/disabled
+    HostReachabilityEnabled(bool),
+    /// Checksum verification of SFTP transfers enabled/disabled
+    SftpVerifyTransfersEnabled(bool),
+    /// Resuming interrupted SFTP transfers enabled/disabled
+    SftpResumeTransfersEnabled(bool),
     /// SSH auto-reconnect enabled/disabled
     AutoReconnectEnabled(bool),
+    /// Terminal font ligatures enabled/disabled
+    TerminalLigaturesEnabled(bool),
+    /// Bold terminal text in bright colors enabled/disabled
+    TerminalBoldIsBrightEnabled(bool),
+    /// Terminal cursor blink enabled/disabled
+    CursorBlinkEnabled(bool),
+    /// Terminal cursor blink interval changed (milliseconds)
+    CursorBlinkIntervalChanged(u64),
+    /// Terminal bell response changed
+    TerminalBellChanged(crate::config::settings::TerminalBell),
+    /// Terminal OSC 52 clipboard access changed
+    TerminalClipboardAccessChanged(crate::config::settings::TerminalClipboardAccess),
+    /// Ask before quitting with running sessions enabled/disabled
+    ConfirmQuitEnabled(bool),
+    /// Save and offer to restore open tabs enabled/disabled
+    RestoreSessionsEnabled(bool),
     /// SSH auto-reconnect max attempts changed
     ReconnectMaxAttemptsChanged(u32),
     /// SSH auto-reconnect base delay changed
     ReconnectBaseDelayChanged(u64),
     /// SSH auto-reconnect max delay changed
     ReconnectMaxDelayChanged(u64),
+    /// SSH connect timeout changed (seconds)
+    ConnectTimeoutChanged(u64),
+    /// SSH keepalive interval changed (seconds, 0 = disabled)
+    KeepaliveIntervalChanged(u64),
+    /// SSH keepalive missed-probe limit changed
+    KeepaliveCountMaxChanged(u32),
     /// Credential cache timeout changed (seconds, 0 = disabled)
     CredentialTimeoutChange(u64),
     /// Security audit logging enabled/disabled (writes security events to an audit log file)
@@ -781,8 +1199,12 @@ pub enum UiMessage {
     VncRemoteResizeChanged(bool),
     /// VNC clipboard sharing changed
     VncClipboardSharingChanged(bool),
+    /// VNC clipboard auto-send changed
+    VncClipboardAutoSendChanged(bool),
     /// VNC view-only default changed
     VncViewOnlyChanged(bool),
+    /// VNC default keyboard mode changed
+    VncKeyboardModeChanged(crate::config::settings::VncKeyboardMode),
     /// VNC cursor dot default changed
     VncShowCursorDotChanged(bool),
     /// VNC stats overlay default changed
@@ -813,6 +1235,18 @@ pub enum UiMessage {
     PortalHubDefaultsPromptDismiss,
     /// Host card hover changed (reveals per-card actions)
     HostCardHovered(Option<Uuid>),
+    /// Select or deselect a tag chip filtering the host grid
+    HostTagToggled(String),
+    /// Deselect every tag chip
+    HostTagFilterCleared,
+    /// A host card or group header started being dragged
+    HostGridDragStarted(HostGridDragItem),
+    /// The cursor entered a drop target while dragging
+    HostGridDragEntered(HostGridDropTarget),
+    /// The cursor left a drop target while dragging
+    HostGridDragLeft(HostGridDropTarget),
+    /// The dragged host or group was released
+    HostGridDropped,
     /// Submit the hosts omnibox (user@host quick connect)
     SearchSubmitted,
     /// Portal Hub host/IP changed
@@ -899,20 +1333,34 @@ pub enum UiMessage {
     WindowUnfocused,
     /// Window gained focus
     WindowFocused,
+    /// User asked to close the window
+    WindowCloseRequested,
     /// Dismiss toast notification
     ToastDismiss(Uuid),
     /// Run a toast action and dismiss the toast.
     ToastAction(Uuid, crate::views::toast::ToastAction),
     /// Toast timer tick
     ToastTick,
+    /// Timer tick that runs the next step of a session restore
+    SessionRestoreTick,
     /// Timer tick for animated tab agent indicators.
     AgentStatusTick,
     /// Drives the short width-reveal animation for newly opened tabs.
     TabAnimationTick,
+    /// Flips the terminal cursor blink phase.
+    CursorBlinkTick,
+    /// Ends finished visual bell flashes
+    BellFlashTick,
+    /// Drops cached credentials past their timeout
+    CredentialCacheTick,
+    /// Drop all cached key passphrases and sudo passwords now
+    ForgetCredentials,
     /// Keyboard event
     KeyboardEvent(iced::keyboard::Key, iced::keyboard::Modifiers, Option<char>),
     /// Key released event (used for VNC)
     KeyReleased(iced::keyboard::Key, iced::keyboard::Modifiers),
+    /// Held modifier keys changed (used for SFTP multi-select clicks)
+    ModifiersChanged(iced::keyboard::Modifiers),
 }
 
 #[derive(Debug, Clone)]
@@ -242,6 +242,23 @@ pub fn log_hub_private_key_sent(host: &str, port: u16, username: &str) {
     ));
 }
 
+/// Log a file operation run through `sudo` on a remote host.
+pub fn log_sudo_operation(host: &str, port: u16, operation: &str, success: bool) {
+    warn!(
+        target: "security",
+        event = "sudo_operation",
+        host = %host,
+        port = port,
+        operation = %operation,
+        success = success,
+        "Remote file operation run with sudo"
+    );
+    write_audit_entry(&format!(
+        "SUDO_OPERATION host={}:{} operation={} success={}",
+        host, port, operation, success
+    ));
+}
+
 /// Log when a cached passphrase is used.
 pub fn log_passphrase_cache_hit(key_path: &str) {
     info!(
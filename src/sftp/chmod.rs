@@ -0,0 +1,319 @@
+//! Recursive permission and ownership changes for the SFTP browser
+//!
+//! Like the size walk, this is shared by local and remote panes: callers
+//! supply the listing and change functions. Entries that cannot be changed
+//! are collected instead of stopping the walk.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use super::sudo::is_permission_denied;
+use super::types::FileEntry;
+
+/// Mode for a directory when folders should stay searchable: every class
+/// that can read also gets execute, like `chmod -R a+X`
+pub fn directory_mode(mode: u32) -> u32 {
+    mode | ((mode & 0o444) >> 2)
+}
+
+/// Mode applied to each entry of a recursive permission change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecursiveMode {
+    pub mode: u32,
+    /// Give folders execute wherever they are readable
+    pub searchable_dirs: bool,
+}
+
+impl RecursiveMode {
+    pub fn for_entry(&self, is_dir: bool) -> u32 {
+        if is_dir && self.searchable_dirs {
+            directory_mode(self.mode)
+        } else {
+            self.mode
+        }
+    }
+}
+
+/// Owner and group ids for a chown; `None` keeps the current value
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Ownership {
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+}
+
+/// Error for a refused chown, spelling out that it needs privileges
+pub fn owner_change_error(path: &str, error: &str) -> String {
+    if is_permission_denied(error) {
+        format!(
+            "Operation not permitted: only root can change the owner of {}",
+            path
+        )
+    } else {
+        format!("Failed to change owner of {}: {}", path, error)
+    }
+}
+
+/// Change applied to every entry of a folder tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeChange {
+    Mode(RecursiveMode),
+    Owner(Ownership),
+}
+
+/// Running count of a recursive permission or ownership change
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChmodProgress {
+    pub changed: u64,
+    pub failed: u64,
+}
+
+/// Outcome of a finished recursive permission or ownership change
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChmodReport {
+    pub progress: ChmodProgress,
+    /// Entries that could not be listed or changed, with the error
+    pub failures: Vec<(PathBuf, String)>,
+}
+
+/// Failed entries listed by `ChmodReport::failure_summary`
+const SUMMARY_FAILURE_LIMIT: usize = 5;
+
+impl ChmodReport {
+    /// Count of entries that could not be changed, listing the first few
+    pub fn failure_summary(&self) -> String {
+        let total = self.progress.changed + self.progress.failed;
+        let mut lines = vec![format!(
+            "{} of {} item(s) could not be changed:",
+            self.progress.failed, total
+        )];
+        for (path, error) in self.failures.iter().take(SUMMARY_FAILURE_LIMIT) {
+            lines.push(format!("{}: {}", path.display(), error));
+        }
+        if self.failures.len() > SUMMARY_FAILURE_LIMIT {
+            lines.push(format!(
+                "... and {} more",
+                self.failures.len() - SUMMARY_FAILURE_LIMIT
+            ));
+        }
+        lines.join("\n")
+    }
+
+    fn fail(&mut self, path: PathBuf, error: String) {
+        self.progress.failed += 1;
+        self.failures.push((path, error));
+    }
+}
+
+/// Call `apply(path, is_dir)` for `root` and everything below it.
+///
+/// Symlinks are skipped, never followed. Each directory is changed after
+/// its contents, so taking away read or execute does not lock the walk out
+/// of the rest of the tree. `is_cancelled` is checked before every entry.
+pub async fn walk_tree<L, LFut, A, AFut, F, C>(
+    root: &Path,
+    mut list_dir: L,
+    mut apply: A,
+    mut on_progress: F,
+    is_cancelled: C,
+) -> Result<ChmodReport, String>
+where
+    L: FnMut(PathBuf) -> LFut,
+    LFut: Future<Output = Result<Vec<FileEntry>, String>>,
+    A: FnMut(PathBuf, bool) -> AFut,
+    AFut: Future<Output = Result<(), String>>,
+    F: FnMut(ChmodProgress),
+    C: Fn() -> bool,
+{
+    let mut report = ChmodReport::default();
+    // (directory, whether its contents are done)
+    let mut pending = vec![(root.to_path_buf(), false)];
+    let cancelled = |report: &ChmodReport| {
+        format!(
+            "Cancelled after changing {} item(s)",
+            report.progress.changed
+        )
+    };
+
+    while let Some((dir, contents_done)) = pending.pop() {
+        if is_cancelled() {
+            return Err(cancelled(&report));
+        }
+        if contents_done {
+            match apply(dir.clone(), true).await {
+                Ok(()) => report.progress.changed += 1,
+                Err(error) => report.fail(dir, error),
+            }
+            on_progress(report.progress);
+            continue;
+        }
+
+        let entries = match list_dir(dir.clone()).await {
+            Ok(entries) => entries,
+            Err(error) if dir != root => {
+                report.fail(dir, error);
+                on_progress(report.progress);
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+        pending.push((dir, true));
+        for entry in entries {
+            if entry.is_parent() || entry.is_symlink {
+                continue;
+            }
+            if entry.is_dir {
+                pending.push((entry.path, false));
+                continue;
+            }
+            if is_cancelled() {
+                return Err(cancelled(&report));
+            }
+            match apply(entry.path.clone(), false).await {
+                Ok(()) => report.progress.changed += 1,
+                Err(error) => report.fail(entry.path, error),
+            }
+        }
+        on_progress(report.progress);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    fn entry(path: &str, is_dir: bool, is_symlink: bool) -> FileEntry {
+        let path = PathBuf::from(path);
+        FileEntry {
+            name: path.file_name().map_or_else(
+                || "..".to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            path,
+            is_dir,
+            is_symlink,
+            symlink_target: None,
+            size: 0,
+            modified: None,
+        }
+    }
+
+    fn tree() -> HashMap<PathBuf, Vec<FileEntry>> {
+        HashMap::from([
+            (
+                PathBuf::from("/srv"),
+                vec![
+                    entry("/..", true, false),
+                    entry("/srv/a.txt", false, false),
+                    entry("/srv/site", true, false),
+                    entry("/srv/link", false, true),
+                    entry("/srv/locked", true, false),
+                ],
+            ),
+            (
+                PathBuf::from("/srv/site"),
+                vec![entry("/srv/site/index.html", false, false)],
+            ),
+        ])
+    }
+
+    #[test]
+    fn directory_mode_adds_execute_where_readable() {
+        assert_eq!(directory_mode(0o644), 0o755);
+        assert_eq!(directory_mode(0o600), 0o700);
+        assert_eq!(directory_mode(0o640), 0o750);
+    }
+
+    #[tokio::test]
+    async fn walk_changes_contents_before_folders_and_collects_failures() {
+        let tree = tree();
+        let applied = RefCell::new(Vec::new());
+        let mode = RecursiveMode {
+            mode: 0o644,
+            searchable_dirs: true,
+        };
+        let report = walk_tree(
+            Path::new("/srv"),
+            |dir| {
+                let listing = tree
+                    .get(&dir)
+                    .cloned()
+                    .ok_or_else(|| "Permission denied".to_string());
+                async move { listing }
+            },
+            |path, is_dir| {
+                applied
+                    .borrow_mut()
+                    .push((path.clone(), mode.for_entry(is_dir)));
+                let result = if path.ends_with("a.txt") {
+                    Err("Operation not permitted".to_string())
+                } else {
+                    Ok(())
+                };
+                async move { result }
+            },
+            |_| {},
+            || false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            report.progress,
+            ChmodProgress {
+                changed: 3,
+                failed: 2
+            }
+        );
+        let failed: Vec<_> = report.failures.iter().map(|(path, _)| path).collect();
+        assert_eq!(
+            failed,
+            vec![Path::new("/srv/a.txt"), Path::new("/srv/locked")]
+        );
+        assert!(
+            report
+                .failure_summary()
+                .starts_with("2 of 5 item(s) could not be changed:\n/srv/a.txt: Operation")
+        );
+        let applied = applied.into_inner();
+        assert!(!applied.iter().any(|(path, _)| path.ends_with("link")));
+        assert_eq!(applied.last(), Some(&(PathBuf::from("/srv"), 0o755)));
+        assert!(applied.contains(&(PathBuf::from("/srv/site/index.html"), 0o644)));
+        let site = applied
+            .iter()
+            .position(|(path, _)| path == Path::new("/srv/site"));
+        let index = applied
+            .iter()
+            .position(|(path, _)| path.ends_with("index.html"));
+        assert!(index < site);
+    }
+
+    #[test]
+    fn refused_owner_change_says_it_is_not_permitted() {
+        assert_eq!(
+            owner_change_error("/srv/site", "Permission denied"),
+            "Operation not permitted: only root can change the owner of /srv/site"
+        );
+        assert!(owner_change_error("/srv/site", "No such file").starts_with("Failed to change"));
+    }
+
+    #[tokio::test]
+    async fn walk_stops_when_cancelled() {
+        let tree = tree();
+        let result = walk_tree(
+            Path::new("/srv"),
+            |dir| {
+                let listing = tree.get(&dir).cloned().unwrap_or_default();
+                async move { Ok(listing) }
+            },
+            |_, _| async { Ok(()) },
+            |_| {},
+            || true,
+        )
+        .await;
+        assert!(result.unwrap_err().contains("Cancelled"));
+    }
+}
@@ -8,7 +8,6 @@ use std::time::Duration;
 
 use russh::client::{self, Config};
 use russh_sftp::client::SftpSession as RusshSftpSession;
-use tokio::net::TcpStream;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio::time::timeout;
@@ -19,8 +18,9 @@ use crate::config::Host;
 use crate::error::SftpError;
 use crate::security_log;
 use crate::ssh::SshEvent;
-use crate::ssh::auth::ResolvedAuth;
+use crate::ssh::auth::resolve_auth_sequence;
 use crate::ssh::auth_flow::{self, AuthContext};
+use crate::ssh::client::connect_tcp;
 use crate::ssh::handler::ClientHandler;
 use crate::ssh::known_hosts::KnownHostsManager;
 use crate::ssh::tunnel::{self, TunnelParams};
@@ -125,6 +125,12 @@ impl SftpClient {
     }
 
     /// Internal helper to establish the SFTP session after TCP connection
+    ///
+    /// An SFTP channel is opened on the pooled connection for this host when
+    /// one is alive (e.g. an open terminal), which skips the handshake and
+    /// authentication. If that connection refuses another channel (servers
+    /// cap sessions per connection with `MaxSessions`), a dedicated connection
+    /// is opened instead and the pooled one is left in place.
     async fn establish_sftp_session(
         &self,
         host: &Host,
@@ -138,161 +144,149 @@ impl SftpClient {
         let via = tunnel::chain_via_key(jump_chain);
         let key = SshConnectionKey::with_via(&host.hostname, host.port, &host.username, &via);
 
-        for attempt in 0..2 {
-            let mut connection = pool.get(&key).await;
-
-            if connection.is_none() {
-                let (stream, tunnel_parent) = if jump_chain.is_empty() {
-                    let addr = format!("{}:{}", host.hostname, host.port);
-                    let stream = timeout(connection_timeout, TcpStream::connect(&addr))
-                        .await
-                        .map_err(|_| {
-                            SftpError::ConnectionFailed(format!("Connection timed out to {}", addr))
-                        })?
-                        .map_err(|e| {
-                            SftpError::ConnectionFailed(format!(
-                                "Failed to connect to {}:{}: {}",
-                                host.hostname, host.port, e
-                            ))
-                        })?;
-                    (tunnel::TunnelStream::Tcp(stream), None)
-                } else {
-                    let params = TunnelParams {
-                        config: self.config.clone(),
-                        known_hosts: self.known_hosts.clone(),
-                        event_tx: event_tx.clone(),
-                        connect_timeout: connection_timeout,
-                    };
-                    let tunneled = tunnel::open_tunneled_stream(
-                        &params,
-                        jump_chain,
-                        &host.hostname,
-                        host.port,
-                    )
-                    .await
-                    .map_err(|e| SftpError::ConnectionFailed(e.to_string()))?;
-                    (tunneled.stream, Some(tunneled.last_hop))
-                };
-
-                // SFTP doesn't need remote forwards - create empty registry
-                let remote_forwards = Arc::new(Mutex::new(HashMap::new()));
-                let agent_forwarding_enabled = Arc::new(AtomicBool::new(false));
-                let handler = ClientHandler::new(
-                    host.hostname.clone(),
-                    host.port,
-                    self.known_hosts.clone(),
-                    event_tx.clone(),
-                    agent_forwarding_enabled.clone(),
-                    remote_forwards.clone(),
-                );
-
-                let mut handle = client::connect_stream(self.config.clone(), stream, handler)
-                    .await
-                    .map_err(|e| {
-                        SftpError::ConnectionFailed(format!(
-                            "SSH handshake failed for {}:{}: {}",
-                            host.hostname, host.port, e
-                        ))
-                    })?;
-
-                // Authenticate (with keyboard-interactive and fallback chain)
-                let auth = ResolvedAuth::resolve(&host.auth, password.clone(), passphrase.clone())
-                    .await
-                    .map_err(|e| match e {
-                        crate::error::SshError::KeyFilePassphraseRequired(path) => {
-                            SftpError::KeyFilePassphraseRequired(path)
-                        }
-                        crate::error::SshError::KeyFilePassphraseInvalid(path) => {
-                            SftpError::KeyFilePassphraseInvalid(path)
+        if let Some(connection) = pool.get(&key).await {
+            if connection.is_closed().await {
+                pool.invalidate_if_matches(&key, &connection).await;
+            } else {
+                match open_sftp_channel(&connection).await {
+                    Ok(sftp) => return self.finish_session(host, connection, sftp).await,
+                    Err(e) => {
+                        if connection.is_closed().await {
+                            pool.invalidate_if_matches(&key, &connection).await;
                         }
-                        _ => SftpError::ConnectionFailed(format!("Authentication failed: {}", e)),
-                    })?;
-                auth_flow::authenticate(
-                    &mut handle,
-                    AuthContext {
-                        hostname: &host.hostname,
-                        port: host.port,
-                        username: &host.username,
-                        event_tx: &event_tx,
-                    },
-                    auth,
-                )
-                .await
-                .map_err(|e| {
-                    SftpError::ConnectionFailed(format!("Authentication failed: {}", e))
-                })?;
-
-                connection = Some(SshConnection::new_via(
-                    handle,
-                    remote_forwards,
-                    agent_forwarding_enabled,
-                    Arc::from(host.hostname.clone()),
-                    host.port,
-                    tunnel_parent,
-                ));
-
-                if let Some(conn) = connection.as_ref() {
-                    pool.put(key.clone(), conn.clone()).await;
+                        tracing::info!(
+                            "Existing SSH connection to {}:{} could not open SFTP ({}); \
+                             opening a new connection",
+                            host.hostname,
+                            host.port,
+                            e
+                        );
+                    }
                 }
             }
+        }
 
-            let connection = match connection {
-                Some(conn) => conn,
-                None => {
-                    return Err(SftpError::ConnectionFailed(
-                        "Connection pool lookup failed".to_string(),
-                    ));
-                }
-            };
-
-            // Open channel and request SFTP subsystem
-            let channel = {
-                let handle = connection.handle();
-                let handle_guard = handle.lock().await;
-                handle_guard.channel_open_session().await
-            };
-
-            let channel = match channel {
-                Ok(channel) => channel,
-                Err(e) => {
-                    pool.invalidate_if_matches(&key, &connection).await;
-                    if attempt == 0 {
-                        continue;
-                    }
-                    return Err(SftpError::ConnectionFailed(format!(
-                        "Failed to open channel: {}",
-                        e
-                    )));
-                }
-            };
+        let connection = self
+            .open_connection(
+                host,
+                jump_chain,
+                &event_tx,
+                connection_timeout,
+                password,
+                passphrase,
+            )
+            .await?;
+        // Don't displace a live pooled connection that only refused a channel.
+        if pool.get(&key).await.is_none() {
+            pool.put(key, connection.clone()).await;
+        }
 
-            channel
-                .request_subsystem(false, "sftp")
-                .await
-                .map_err(|e| {
-                    SftpError::ConnectionFailed(format!("Failed to request SFTP subsystem: {}", e))
-                })?;
+        let sftp = open_sftp_channel(&connection).await?;
+        self.finish_session(host, connection, sftp).await
+    }
 
-            // Create SFTP session
-            let sftp = RusshSftpSession::new(channel.into_stream())
+    /// Open and authenticate a new SSH connection for SFTP, tunneling through
+    /// `jump_chain` when it is non-empty.
+    async fn open_connection(
+        &self,
+        host: &Host,
+        jump_chain: &[Host],
+        event_tx: &mpsc::Sender<SshEvent>,
+        connection_timeout: Duration,
+        password: Option<SecretString>,
+        passphrase: Option<SecretString>,
+    ) -> Result<Arc<SshConnection>, SftpError> {
+        let (stream, tunnel_parent) = if jump_chain.is_empty() {
+            let stream = connect_tcp(&host.hostname, host.port, connection_timeout)
                 .await
-                .map_err(|e| {
-                    SftpError::ConnectionFailed(format!("Failed to initialize SFTP session: {}", e))
-                })?;
+                .map_err(|e| SftpError::ConnectionFailed(e.to_string()))?;
+            (tunnel::TunnelStream::Tcp(stream), None)
+        } else {
+            let params = TunnelParams {
+                config: self.config.clone(),
+                known_hosts: self.known_hosts.clone(),
+                event_tx: event_tx.clone(),
+                connect_timeout: connection_timeout,
+            };
+            let tunneled =
+                tunnel::open_tunneled_stream(&params, jump_chain, &host.hostname, host.port)
+                    .await
+                    .map_err(|e| SftpError::ConnectionFailed(e.to_string()))?;
+            (tunneled.stream, Some(tunneled.last_hop))
+        };
 
-            // Get the remote home directory
-            let home_dir = self.get_home_dir(&sftp).await?;
+        // SFTP doesn't need remote forwards - create empty registry
+        let remote_forwards = Arc::new(Mutex::new(HashMap::new()));
+        let agent_forwarding_enabled = Arc::new(AtomicBool::new(false));
+        let handler = ClientHandler::new(
+            host.hostname.clone(),
+            host.port,
+            self.known_hosts.clone(),
+            event_tx.clone(),
+            agent_forwarding_enabled.clone(),
+            remote_forwards.clone(),
+        );
+
+        let mut handle = client::connect_stream(self.config.clone(), stream, handler)
+            .await
+            .map_err(|e| {
+                SftpError::ConnectionFailed(format!(
+                    "SSH handshake failed for {}:{}: {}",
+                    host.hostname, host.port, e
+                ))
+            })?;
+
+        // Authenticate (with keyboard-interactive and fallback chain)
+        let candidates = resolve_auth_sequence(host, password, passphrase)
+            .await
+            .map_err(|e| match e {
+                crate::error::SshError::KeyFilePassphraseRequired(path) => {
+                    SftpError::KeyFilePassphraseRequired(path)
+                }
+                crate::error::SshError::KeyFilePassphraseInvalid(path) => {
+                    SftpError::KeyFilePassphraseInvalid(path)
+                }
+                _ => SftpError::ConnectionFailed(format!("Authentication failed: {}", e)),
+            })?;
+        let auth_method = auth_flow::authenticate(
+            &mut handle,
+            AuthContext {
+                hostname: &host.hostname,
+                port: host.port,
+                username: &host.username,
+                agent_identity: host.agent_identity.as_deref(),
+                event_tx,
+            },
+            candidates,
+        )
+        .await
+        .map_err(|e| SftpError::ConnectionFailed(format!("Authentication failed: {}", e)))?;
+
+        Ok(SshConnection::new_via(
+            handle,
+            remote_forwards,
+            agent_forwarding_enabled,
+            Arc::from(host.hostname.clone()),
+            host.port,
+            Some(auth_method),
+            tunnel_parent,
+        ))
+    }
 
-            // Log successful SFTP connection
-            security_log::log_sftp_connect(&host.hostname, host.port, &host.username);
+    /// Wrap an initialized SFTP channel into a session for `host`
+    async fn finish_session(
+        &self,
+        host: &Host,
+        connection: Arc<SshConnection>,
+        sftp: RusshSftpSession,
+    ) -> Result<SharedSftpSession, SftpError> {
+        // Get the remote home directory
+        let home_dir = self.get_home_dir(&sftp).await?;
 
-            let session = Arc::new(SftpSession::new(connection, sftp, home_dir));
-            return Ok(session);
-        }
+        // Log successful SFTP connection
+        security_log::log_sftp_connect(&host.hostname, host.port, &host.username);
 
-        Err(SftpError::ConnectionFailed(
-            "Failed to establish SFTP session".to_string(),
-        ))
+        Ok(Arc::new(SftpSession::new(connection, sftp, home_dir)))
     }
 
     /// Get the remote user's home directory
@@ -308,6 +302,31 @@ impl SftpClient {
     }
 }
 
+/// Open a session channel on `connection` and start the SFTP subsystem on it
+pub(super) async fn open_sftp_channel(
+    connection: &SshConnection,
+) -> Result<RusshSftpSession, SftpError> {
+    let channel = {
+        let handle = connection.handle();
+        let handle_guard = handle.lock().await;
+        handle_guard.channel_open_session().await
+    }
+    .map_err(|e| SftpError::ConnectionFailed(format!("Failed to open channel: {}", e)))?;
+
+    channel
+        .request_subsystem(false, "sftp")
+        .await
+        .map_err(|e| {
+            SftpError::ConnectionFailed(format!("Failed to request SFTP subsystem: {}", e))
+        })?;
+
+    RusshSftpSession::new(channel.into_stream())
+        .await
+        .map_err(|e| {
+            SftpError::ConnectionFailed(format!("Failed to initialize SFTP session: {}", e))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
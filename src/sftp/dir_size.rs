@@ -0,0 +1,178 @@
+//! Recursive directory size calculation for the SFTP browser
+//!
+//! The walk is shared by local and remote panes: callers supply the listing
+//! function, and the running total is reported after every directory so the
+//! browser can show progress on large trees.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use super::types::{FileEntry, format_size};
+
+/// Running or finished total of a directory size walk
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DirSize {
+    pub bytes: u64,
+    pub files: u64,
+    /// False while the walk is still running
+    pub complete: bool,
+}
+
+impl DirSize {
+    /// Add the files of one directory listing and queue its subdirectories.
+    /// Symlinks are neither counted nor followed.
+    fn add_listing(&mut self, entries: &[FileEntry], pending: &mut Vec<PathBuf>) {
+        for entry in entries {
+            if entry.is_parent() || entry.is_symlink {
+                continue;
+            }
+            if entry.is_dir {
+                pending.push(entry.path.clone());
+            } else {
+                self.bytes = self.bytes.saturating_add(entry.size);
+                self.files = self.files.saturating_add(1);
+            }
+        }
+    }
+
+    /// Text for the size column: the total once finished, otherwise the
+    /// running total and file count.
+    pub fn label(&self) -> String {
+        if self.complete {
+            format_size(self.bytes)
+        } else {
+            format!("{} ({} files)…", format_size(self.bytes), self.files)
+        }
+    }
+}
+
+/// Sum the sizes of all files under `root`.
+///
+/// Subdirectories that cannot be listed are skipped; only a failure to list
+/// `root` itself is an error. `is_cancelled` is checked before each listing.
+pub async fn walk_dir_size<L, Fut, F, C>(
+    root: &Path,
+    mut list_dir: L,
+    mut on_progress: F,
+    is_cancelled: C,
+) -> Result<DirSize, String>
+where
+    L: FnMut(PathBuf) -> Fut,
+    Fut: Future<Output = Result<Vec<FileEntry>, String>>,
+    F: FnMut(DirSize),
+    C: Fn() -> bool,
+{
+    let mut total = DirSize::default();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        if is_cancelled() {
+            return Err("Size calculation cancelled".to_string());
+        }
+        let entries = match list_dir(dir.clone()).await {
+            Ok(entries) => entries,
+            Err(error) if dir != root => {
+                tracing::debug!("Skipping {} in size calculation: {}", dir.display(), error);
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+        total.add_listing(&entries, &mut pending);
+        on_progress(total);
+    }
+
+    total.complete = true;
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entry(path: &str, is_dir: bool, is_symlink: bool, size: u64) -> FileEntry {
+        let path = PathBuf::from(path);
+        FileEntry {
+            name: path.file_name().map_or_else(
+                || "..".to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            path,
+            is_dir,
+            is_symlink,
+            symlink_target: None,
+            size,
+            modified: None,
+        }
+    }
+
+    fn tree() -> HashMap<PathBuf, Vec<FileEntry>> {
+        HashMap::from([
+            (
+                PathBuf::from("/srv"),
+                vec![
+                    entry("/..", true, false, 0),
+                    entry("/srv/a.txt", false, false, 100),
+                    entry("/srv/logs", true, false, 4096),
+                    entry("/srv/link", true, true, 0),
+                    entry("/srv/locked", true, false, 4096),
+                ],
+            ),
+            (
+                PathBuf::from("/srv/logs"),
+                vec![
+                    entry("/srv/logs/b.log", false, false, 20),
+                    entry("/srv/logs/c.log", false, false, 3),
+                ],
+            ),
+        ])
+    }
+
+    #[tokio::test]
+    async fn walk_sums_files_and_skips_symlinks_and_unreadable_dirs() {
+        let tree = tree();
+        let mut updates = Vec::new();
+        let total = walk_dir_size(
+            Path::new("/srv"),
+            |dir| {
+                let listing = tree
+                    .get(&dir)
+                    .cloned()
+                    .ok_or_else(|| "permission denied".to_string());
+                async move { listing }
+            },
+            |size| updates.push(size),
+            || false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(total.bytes, 123);
+        assert_eq!(total.files, 3);
+        assert!(total.complete);
+        assert_eq!(total.label(), format_size(123));
+        assert!(updates.iter().all(|size| !size.complete));
+        assert_eq!(updates.first().map(|size| size.files), Some(1));
+    }
+
+    #[tokio::test]
+    async fn walk_stops_when_cancelled_or_root_is_unreadable() {
+        let cancelled = walk_dir_size(
+            Path::new("/srv"),
+            |_| async { Ok(Vec::new()) },
+            |_| {},
+            || true,
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        let unreadable = walk_dir_size(
+            Path::new("/root"),
+            |_| async { Err("permission denied".to_string()) },
+            |_| {},
+            || false,
+        )
+        .await;
+        assert_eq!(unreadable, Err("permission denied".to_string()));
+    }
+}
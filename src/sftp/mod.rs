@@ -2,10 +2,23 @@
 //!
 //! Provides SFTP file browsing and transfer capabilities.
 
+pub mod chmod;
 pub mod client;
+pub mod dir_size;
+pub mod properties;
+pub mod resume;
 pub mod session;
+pub mod sudo;
+pub mod sync;
 pub mod types;
 
 pub use client::SftpClient;
+pub use dir_size::DirSize;
+pub use properties::FileProperties;
+pub use resume::TransferResume;
 pub use session::SharedSftpSession;
-pub use types::{FileEntry, FileIcon, SortOrder, format_size, is_safe_sftp_entry_name};
+pub use sudo::SudoCommand;
+pub use sync::{SyncOptions, SyncPlan};
+pub use types::{
+    FileEntry, FileIcon, SortOrder, SymlinkTarget, format_size, is_safe_sftp_entry_name,
+};
@@ -0,0 +1,270 @@
+//! Details shown by the SFTP Properties dialog
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::chmod::Ownership;
+use super::types::SymlinkTarget;
+
+/// Largest `/etc/passwd` or `/etc/group` read to turn ids into names
+pub const ACCOUNT_DATABASE_LIMIT: u64 = 1024 * 1024;
+
+/// A file owner or group: the numeric id and, when it could be looked up,
+/// its name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub id: u32,
+    pub name: Option<String>,
+}
+
+impl Account {
+    /// "name (id)", or just the id when the name is unknown
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} ({})", name, self.id),
+            None => self.id.to_string(),
+        }
+    }
+}
+
+/// Metadata of a single local or remote entry, read without following
+/// symlinks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProperties {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    pub size: u64,
+    /// Full mode including the file type bits, when reported
+    pub mode: Option<u32>,
+    pub modified: Option<DateTime<Utc>>,
+    pub accessed: Option<DateTime<Utc>>,
+    /// Creation time; SFTP does not report it for remote files
+    pub created: Option<DateTime<Utc>>,
+    pub owner: Option<Account>,
+    pub group: Option<Account>,
+    pub symlink_target: Option<SymlinkTarget>,
+}
+
+/// `ls -l` style permission string such as "drwxr-xr-x", including the
+/// setuid, setgid and sticky bits
+pub fn permission_string(mode: u32) -> String {
+    let kind = match mode & 0o170000 {
+        0o040000 => 'd',
+        0o120000 => 'l',
+        0o020000 => 'c',
+        0o060000 => 'b',
+        0o010000 => 'p',
+        0o140000 => 's',
+        _ => '-',
+    };
+    let mut result = String::with_capacity(10);
+    result.push(kind);
+    for (shift, special, special_char) in [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')] {
+        let bits = (mode >> shift) & 0o7;
+        result.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        result.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        result.push(match (bits & 0o1 != 0, mode & special != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    result
+}
+
+/// Name for `id` in a passwd or group file (`name:x:id:...` lines)
+pub fn account_name(database: &str, id: u32) -> Option<String> {
+    database.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let line_id = fields.nth(1)?.parse::<u32>().ok()?;
+        (line_id == id && !name.is_empty() && !name.starts_with('#')).then(|| name.to_string())
+    })
+}
+
+/// Id for `name` in a passwd or group file
+pub fn account_id(database: &str, name: &str) -> Option<u32> {
+    database.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        (fields.next()? == name)
+            .then(|| fields.nth(1)?.parse::<u32>().ok())
+            .flatten()
+    })
+}
+
+/// Turn typed owner and group (a name, a numeric id, or blank to keep the
+/// current one) into ids, looking names up in the passwd and group files
+pub fn resolve_ownership(
+    owner: &str,
+    group: &str,
+    passwd: Option<&str>,
+    groups: Option<&str>,
+) -> Result<Ownership, String> {
+    let resolve = |value: &str, database: Option<&str>, kind: &str| {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        if let Ok(id) = value.parse::<u32>() {
+            return Ok(Some(id));
+        }
+        database
+            .and_then(|database| account_id(database, value))
+            .map(Some)
+            .ok_or_else(|| format!("Unknown {} \"{}\"", kind, value))
+    };
+    Ok(Ownership {
+        uid: resolve(owner, passwd, "user")?,
+        gid: resolve(group, groups, "group")?,
+    })
+}
+
+/// Whether typed owner or group names need the account databases
+pub fn needs_account_lookup(owner: &str, group: &str) -> bool {
+    [owner, group].iter().any(|value| {
+        let value = value.trim();
+        !value.is_empty() && value.parse::<u32>().is_err()
+    })
+}
+
+/// Whether Portal runs as root, which local ownership changes require
+pub fn is_local_root() -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail
+        unsafe { libc::geteuid() == 0 }
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Look up `owner` and `group` ids in the given passwd and group file
+/// contents; missing databases leave the ids unnamed
+pub fn resolve_accounts(
+    owner: Option<u32>,
+    group: Option<u32>,
+    passwd: Option<&str>,
+    groups: Option<&str>,
+) -> (Option<Account>, Option<Account>) {
+    let lookup = |id: Option<u32>, database: Option<&str>| {
+        id.map(|id| Account {
+            id,
+            name: database.and_then(|database| account_name(database, id)),
+        })
+    };
+    (lookup(owner, passwd), lookup(group, groups))
+}
+
+fn unix_time(seconds: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(seconds, 0).single()
+}
+
+/// Read the properties of a local path
+pub fn local_properties(path: &Path) -> Result<FileProperties, String> {
+    let metadata = std::fs::symlink_metadata(path)
+        .map_err(|e| format!("Failed to read metadata for {}: {}", path.display(), e))?;
+    let is_symlink = metadata.file_type().is_symlink();
+    let symlink_target = if is_symlink {
+        std::fs::read_link(path).ok().map(|target| SymlinkTarget {
+            target: target.to_string_lossy().into_owned(),
+            resolved: std::fs::canonicalize(path).ok(),
+        })
+    } else {
+        None
+    };
+    let system_time = |time: std::io::Result<std::time::SystemTime>| {
+        let duration = time.ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+        unix_time(i64::try_from(duration.as_secs()).ok()?)
+    };
+
+    #[cfg(unix)]
+    let (mode, owner, group) = {
+        use std::os::unix::fs::MetadataExt;
+
+        let passwd = std::fs::read_to_string("/etc/passwd").ok();
+        let groups = std::fs::read_to_string("/etc/group").ok();
+        let (owner, group) = resolve_accounts(
+            Some(metadata.uid()),
+            Some(metadata.gid()),
+            passwd.as_deref(),
+            groups.as_deref(),
+        );
+        (Some(metadata.mode()), owner, group)
+    };
+    #[cfg(not(unix))]
+    let (mode, owner, group) = (None, None, None);
+
+    Ok(FileProperties {
+        path: path.to_path_buf(),
+        is_dir: metadata.is_dir(),
+        is_symlink,
+        size: metadata.len(),
+        mode,
+        modified: system_time(metadata.modified()),
+        accessed: system_time(metadata.accessed()),
+        created: system_time(metadata.created()),
+        owner,
+        group,
+        symlink_target,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permission_string_shows_type_and_special_bits() {
+        assert_eq!(permission_string(0o040755), "drwxr-xr-x");
+        assert_eq!(permission_string(0o100644), "-rw-r--r--");
+        assert_eq!(permission_string(0o120777), "lrwxrwxrwx");
+        assert_eq!(permission_string(0o104755), "-rwsr-xr-x");
+        assert_eq!(permission_string(0o041777), "drwxrwxrwt");
+        assert_eq!(permission_string(0o102640), "-rw-r-S---");
+    }
+
+    #[test]
+    fn accounts_are_named_from_passwd_and_group_files() {
+        let passwd =
+            "# comment\nroot:x:0:0:root:/root:/bin/bash\nalice:x:1000:1000::/home/alice:/bin/sh\n";
+        let groups = "root:x:0:\nstaff:x:50:alice\n";
+        let (owner, group) = resolve_accounts(Some(1000), Some(50), Some(passwd), Some(groups));
+        assert_eq!(owner.unwrap().label(), "alice (1000)");
+        assert_eq!(group.unwrap().label(), "staff (50)");
+
+        let (owner, group) = resolve_accounts(Some(4242), None, Some(passwd), None);
+        assert_eq!(owner.unwrap().label(), "4242");
+        assert!(group.is_none());
+    }
+
+    #[test]
+    fn typed_ownership_accepts_names_ids_and_blanks() {
+        let passwd = "root:x:0:0:root:/root:/bin/bash\nwww-data:x:33:33::/var/www:/bin/sh\n";
+        let groups = "root:x:0:\nwww-data:x:33:\n";
+        assert_eq!(
+            resolve_ownership("www-data", " 0 ", Some(passwd), Some(groups)),
+            Ok(Ownership {
+                uid: Some(33),
+                gid: Some(0),
+            })
+        );
+        assert_eq!(
+            resolve_ownership("", "www-data", None, Some(groups)),
+            Ok(Ownership {
+                uid: None,
+                gid: Some(33),
+            })
+        );
+        assert_eq!(
+            resolve_ownership("nobody", "", Some(passwd), None),
+            Err("Unknown user \"nobody\"".to_string())
+        );
+        assert!(needs_account_lookup("www-data", ""));
+        assert!(!needs_account_lookup("33", " "));
+    }
+}
@@ -0,0 +1,222 @@
+//! Resuming interrupted single-file transfers
+//!
+//! A resumable transfer stages into a fixed sibling name instead of a random
+//! one and keeps that file when the transfer fails, so the next attempt can
+//! seek both sides to its length and append the rest. The size and
+//! modification time of the source are recorded next to the partial file,
+//! and a source that changed since then is copied from the start.
+
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+/// Suffix of the staging file kept between attempts of a resumable transfer
+const RESUME_MARKER: &str = ".portal-resume";
+
+/// Suffix added to the staging file's name for the source record
+const SOURCE_RECORD_SUFFIX: &str = ".source";
+
+/// Bytes at the end of a partial file compared before appending to it
+pub const RESUME_VERIFY_WINDOW: u64 = 1024 * 1024;
+
+/// How a single-file transfer treats an earlier, interrupted attempt
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TransferResume {
+    /// Stage into a fresh file that is removed if the transfer fails
+    #[default]
+    Off,
+    /// Keep the staging file on failure and continue from its end next time
+    Append,
+    /// Like `Append`, but start over unless the last `RESUME_VERIFY_WINDOW`
+    /// bytes of the partial file match the source
+    AppendVerified,
+}
+
+impl TransferResume {
+    pub fn is_enabled(self) -> bool {
+        self != Self::Off
+    }
+}
+
+/// Staging path kept between attempts to transfer to `path`
+pub(crate) fn resume_staging_path(path: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Cannot create staging path for {}", path.display()))?
+        .to_string_lossy();
+    Ok(path.with_file_name(format!(".{}{}", file_name, RESUME_MARKER)))
+}
+
+/// Size and modification time of the file a partial transfer was copied from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ResumeSource {
+    pub len: u64,
+    /// Seconds since the epoch
+    pub mtime: Option<u64>,
+}
+
+impl ResumeSource {
+    /// One line, `<len> <mtime>`, with `-` for an unknown time
+    pub(crate) fn encode(&self) -> String {
+        match self.mtime {
+            Some(mtime) => format!("{} {}\n", self.len, mtime),
+            None => format!("{} -\n", self.len),
+        }
+    }
+
+    pub(crate) fn decode(record: &str) -> Option<Self> {
+        let mut fields = record.split_whitespace();
+        let len = fields.next()?.parse().ok()?;
+        let mtime = match fields.next()? {
+            "-" => None,
+            mtime => Some(mtime.parse().ok()?),
+        };
+        fields.next().is_none().then_some(Self { len, mtime })
+    }
+}
+
+/// Where the source of the partial file at `partial` is recorded
+pub(crate) fn resume_source_path(partial: &Path) -> PathBuf {
+    let mut path = partial.as_os_str().to_owned();
+    path.push(SOURCE_RECORD_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Where to continue a transfer from `source` given the length of the
+/// partial file left by an earlier attempt and the source recorded with it.
+/// The partial file is only appended to when the recorded size and
+/// modification time match the source; otherwise it is started over.
+pub(crate) fn resume_offset(
+    partial_len: Option<u64>,
+    recorded: Option<ResumeSource>,
+    source: ResumeSource,
+) -> u64 {
+    match (partial_len, recorded) {
+        (Some(len), Some(recorded))
+            if recorded == source && source.mtime.is_some() && len <= source.len =>
+        {
+            len
+        }
+        _ => 0,
+    }
+}
+
+/// SHA-256 of the `RESUME_VERIFY_WINDOW` bytes before `end`
+pub(crate) async fn tail_sha256<R>(reader: &mut R, end: u64) -> std::io::Result<[u8; 32]>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    use sha2::{Digest, Sha256};
+
+    let start = end.saturating_sub(RESUME_VERIFY_WINDOW);
+    reader.seek(SeekFrom::Start(start)).await?;
+    let mut remaining = end - start;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    while remaining > 0 {
+        let want = buffer
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        let read = reader.read(&mut buffer[..want]).await?;
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        hasher.update(&buffer[..read]);
+        remaining -= read as u64;
+    }
+    Ok(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn resume_path_is_stable_hidden_sibling() {
+        let path = resume_staging_path(Path::new("/srv/backup.tar")).unwrap();
+        assert_eq!(path, PathBuf::from("/srv/.backup.tar.portal-resume"));
+        assert_eq!(resume_staging_path(Path::new("/srv/backup.tar")), Ok(path));
+        assert!(resume_staging_path(Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn resume_offset_restarts_when_partial_is_longer_than_source() {
+        let source = ResumeSource {
+            len: 100,
+            mtime: Some(1_700_000_000),
+        };
+        assert_eq!(resume_offset(None, Some(source), source), 0);
+        assert_eq!(resume_offset(Some(40), Some(source), source), 40);
+        assert_eq!(resume_offset(Some(100), Some(source), source), 100);
+        assert_eq!(resume_offset(Some(101), Some(source), source), 0);
+    }
+
+    #[test]
+    fn resume_offset_restarts_when_source_changed() {
+        let source = ResumeSource {
+            len: 100,
+            mtime: Some(1_700_000_000),
+        };
+        let touched = ResumeSource {
+            mtime: Some(1_700_000_060),
+            ..source
+        };
+        let grown = ResumeSource { len: 120, ..source };
+        let unknown_time = ResumeSource {
+            mtime: None,
+            ..source
+        };
+
+        assert_eq!(resume_offset(Some(40), None, source), 0);
+        assert_eq!(resume_offset(Some(40), Some(touched), source), 0);
+        assert_eq!(resume_offset(Some(40), Some(source), grown), 0);
+        assert_eq!(resume_offset(Some(40), Some(unknown_time), unknown_time), 0);
+    }
+
+    #[test]
+    fn resume_source_record_round_trips() {
+        let source = ResumeSource {
+            len: 4096,
+            mtime: Some(1_700_000_000),
+        };
+        assert_eq!(ResumeSource::decode(&source.encode()), Some(source));
+        let unknown_time = ResumeSource {
+            len: 1,
+            mtime: None,
+        };
+        assert_eq!(
+            ResumeSource::decode(&unknown_time.encode()),
+            Some(unknown_time)
+        );
+        assert_eq!(ResumeSource::decode("12"), None);
+        assert_eq!(ResumeSource::decode("12 x"), None);
+        assert_eq!(ResumeSource::decode("12 34 56"), None);
+
+        assert_eq!(
+            resume_source_path(Path::new("/srv/.backup.tar.portal-resume")),
+            PathBuf::from("/srv/.backup.tar.portal-resume.source")
+        );
+    }
+
+    #[tokio::test]
+    async fn tail_sha256_hashes_only_the_window_before_end() {
+        let window = RESUME_VERIFY_WINDOW as usize;
+        let mut source = vec![1u8; window + 10];
+        source.extend_from_slice(b"tail");
+        let mut partial = vec![2u8; 10];
+        partial.extend_from_slice(&source[10..]);
+
+        let end = source.len() as u64;
+        let source_digest = tail_sha256(&mut Cursor::new(&source), end).await.unwrap();
+        let partial_digest = tail_sha256(&mut Cursor::new(&partial), end).await.unwrap();
+        assert_eq!(source_digest, partial_digest);
+
+        partial[window + 12] ^= 0xff;
+        let corrupt_digest = tail_sha256(&mut Cursor::new(&partial), end).await.unwrap();
+        assert_ne!(source_digest, corrupt_digest);
+
+        assert!(tail_sha256(&mut Cursor::new(b"short"), 10).await.is_err());
+    }
+}
@@ -1,28 +1,53 @@
 //! SFTP session for file operations
 
 use std::io::ErrorKind;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use russh::ChannelMsg;
 use russh_sftp::client::SftpSession as RusshSftpSession;
 use russh_sftp::protocol::OpenFlags;
 use tokio::fs::OpenOptions;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tokio::time::timeout;
 
+use secrecy::{ExposeSecret, SecretString};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::error::SftpError;
 use crate::fs_utils::{ensure_dir_no_follow, open_directory_for_sync, open_read_regular_file};
+use crate::security_log;
 use crate::ssh::SshConnection;
 use crate::ssh::SshSession;
 
-use super::types::{FileEntry, is_safe_sftp_entry_name};
+use super::chmod::{
+    ChmodProgress, ChmodReport, Ownership, TreeChange, owner_change_error, walk_tree,
+};
+use super::dir_size::{DirSize, walk_dir_size};
+use super::properties::{
+    ACCOUNT_DATABASE_LIMIT, FileProperties, needs_account_lookup, resolve_accounts,
+    resolve_ownership,
+};
+use super::resume::{
+    ResumeSource, TransferResume, resume_offset, resume_source_path, resume_staging_path,
+    tail_sha256,
+};
+use super::sudo::{self, SUDO_STDERR_LIMIT, SudoCommand};
+use super::types::{FileEntry, SymlinkTarget, is_safe_sftp_entry_name};
 
 const TRANSFER_BUFFER_SIZE: usize = 64 * 1024;
+/// Upper bound for a single sudo command (recursive deletes can take a while).
+const SUDO_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+/// `stat` prints a single link count
+const LINK_COUNT_OUTPUT_LIMIT: usize = 64;
+/// Symlinks in one listing whose targets are requested at the same time
+const SYMLINK_RESOLVE_CONCURRENCY: usize = 32;
+/// `sha256sum` prints one line per file; anything longer is not its output
+const SHA256SUM_OUTPUT_LIMIT: usize = 4096;
 
 /// SFTP session wrapper for file operations
 pub struct SftpSession {
@@ -50,11 +75,63 @@ impl SftpSession {
         }
     }
 
+    /// Replace the SFTP channel with a fresh one on the same SSH
+    /// connection, e.g. after the server closed the subsystem mid-transfer.
+    /// Fails if the SSH connection itself is gone.
+    pub async fn reopen_channel(&self) -> Result<(), SftpError> {
+        if self._connection.is_closed().await {
+            return Err(SftpError::ConnectionFailed(
+                "SSH connection closed".to_string(),
+            ));
+        }
+        let channel = super::client::open_sftp_channel(&self._connection).await?;
+        *self.sftp.lock().await = channel;
+        Ok(())
+    }
+
     /// Get the remote home directory
     pub fn home_dir(&self) -> &Path {
         &self.home_dir
     }
 
+    /// Directory the browser should open at: `start_path` when it exists on
+    /// the server, otherwise the home directory.
+    pub async fn resolve_start_dir(&self, start_path: Option<&str>) -> PathBuf {
+        let Some(candidate) = start_path.and_then(|path| expand_start_path(&self.home_dir, path))
+        else {
+            return self.home_dir.clone();
+        };
+
+        let sftp = self.sftp.lock().await;
+        let path_str = candidate.to_string_lossy().to_string();
+        match sftp.metadata(path_str.clone()).await {
+            Ok(metadata) if metadata.is_dir() => candidate,
+            Ok(_) => {
+                tracing::warn!(
+                    "SFTP start path {} is not a directory, opening home instead",
+                    path_str
+                );
+                self.home_dir.clone()
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "SFTP start path {} is unavailable ({}), opening home instead",
+                    path_str,
+                    e
+                );
+                self.home_dir.clone()
+            }
+        }
+    }
+
+    /// Whether `path` exists on the server and is a directory.
+    pub async fn is_dir(&self, path: &Path) -> bool {
+        let sftp = self.sftp.lock().await;
+        sftp.metadata(path.to_string_lossy().to_string())
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+    }
+
     /// Open a new SFTP channel on an existing authenticated SSH terminal session.
     pub async fn from_ssh_session(
         ssh_session: &SshSession,
@@ -106,6 +183,7 @@ impl SftpSession {
                 path: parent.to_path_buf(),
                 is_dir: true,
                 is_symlink: false,
+                symlink_target: None,
                 size: 0,
                 modified: None,
             });
@@ -120,7 +198,6 @@ impl SftpSession {
             let metadata = entry.metadata();
             let entry_path = path.join(&name);
 
-            let is_dir = metadata.is_dir();
             let is_symlink = metadata.is_symlink();
             let size = metadata.size.unwrap_or(0);
 
@@ -130,16 +207,60 @@ impl SftpSession {
             result.push(FileEntry {
                 name,
                 path: entry_path,
-                is_dir,
+                is_dir: metadata.is_dir(),
                 is_symlink,
+                symlink_target: None,
                 size,
                 modified,
             });
         }
 
+        // Resolve the directory's symlinks together so their requests share
+        // round trips instead of queueing one link after another
+        let links: Vec<(usize, PathBuf)> = result
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.is_symlink)
+            .map(|(index, entry)| (index, entry.path.clone()))
+            .collect();
+        let targets: Vec<_> = futures::stream::iter(links)
+            .map(|(index, link)| {
+                let sftp = &*sftp;
+                async move { (index, remote_symlink_target(sftp, &link).await) }
+            })
+            .buffered(SYMLINK_RESOLVE_CONCURRENCY)
+            .collect()
+            .await;
+        for (index, (target, target_is_dir)) in targets {
+            result[index].is_dir = target_is_dir;
+            result[index].symlink_target = target;
+        }
+
         Ok(result)
     }
 
+    /// Recursively total the files under `path` without following symlinks,
+    /// reporting the running total after each directory.
+    pub async fn dir_size<F, C>(
+        &self,
+        path: &Path,
+        on_progress: F,
+        is_cancelled: C,
+    ) -> Result<DirSize, SftpError>
+    where
+        F: FnMut(DirSize),
+        C: Fn() -> bool,
+    {
+        walk_dir_size(
+            path,
+            |dir| async move { self.list_dir(&dir).await.map_err(|e| e.to_string()) },
+            on_progress,
+            is_cancelled,
+        )
+        .await
+        .map_err(SftpError::FileOperation)
+    }
+
     /// Get file size for a remote path.
     pub async fn file_size(&self, path: &Path) -> Result<u64, SftpError> {
         let sftp = self.sftp.lock().await;
@@ -157,6 +278,206 @@ impl SftpSession {
         Ok(metadata.size.unwrap_or(0))
     }
 
+    /// Read the metadata of a remote path without following symlinks.
+    ///
+    /// Owner and group ids are named from the host's `/etc/passwd` and
+    /// `/etc/group` when those can be read.
+    pub async fn properties(&self, path: &Path) -> Result<FileProperties, SftpError> {
+        let path_str = path.to_string_lossy().to_string();
+        let (metadata, symlink_target) = {
+            let sftp = self.sftp.lock().await;
+            let metadata = sftp.symlink_metadata(path_str.clone()).await.map_err(|e| {
+                SftpError::FileOperation(format!("Failed to get metadata for {}: {}", path_str, e))
+            })?;
+            let symlink_target = if metadata.is_symlink() {
+                remote_symlink_target(&sftp, path).await.0
+            } else {
+                None
+            };
+            (metadata, symlink_target)
+        };
+
+        let passwd = match metadata.user {
+            None if metadata.uid.is_some() => self.read_account_database("/etc/passwd").await,
+            _ => None,
+        };
+        let groups = match metadata.group {
+            None if metadata.gid.is_some() => self.read_account_database("/etc/group").await,
+            _ => None,
+        };
+        let (mut owner, mut group) = resolve_accounts(
+            metadata.uid,
+            metadata.gid,
+            passwd.as_deref(),
+            groups.as_deref(),
+        );
+        // Some servers already report the names
+        if let Some(owner) = owner.as_mut() {
+            owner.name = owner.name.take().or_else(|| metadata.user.clone());
+        }
+        if let Some(group) = group.as_mut() {
+            group.name = group.name.take().or_else(|| metadata.group.clone());
+        }
+
+        Ok(FileProperties {
+            path: path.to_path_buf(),
+            is_dir: metadata.is_dir(),
+            is_symlink: metadata.is_symlink(),
+            size: metadata.size.unwrap_or(0),
+            mode: metadata.permissions,
+            modified: metadata.mtime.and_then(unix_timestamp_to_utc),
+            accessed: metadata.atime.and_then(unix_timestamp_to_utc),
+            created: None,
+            owner,
+            group,
+            symlink_target,
+        })
+    }
+
+    /// SHA-256 of a remote file's contents.
+    pub async fn sha256(&self, path: &Path) -> Result<[u8; 32], SftpError> {
+        use sha2::{Digest, Sha256};
+
+        let sftp = self.sftp.lock().await;
+        let path_str = path.to_string_lossy().to_string();
+        let mut remote = sftp.open(path_str.clone()).await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to open remote file {}: {}", path_str, e))
+        })?;
+
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; TRANSFER_BUFFER_SIZE];
+        loop {
+            let read = remote.read(&mut buffer).await.map_err(|e| {
+                SftpError::FileOperation(format!("Failed to read remote file {}: {}", path_str, e))
+            })?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// SHA-256 of a remote file, computed by `sha256sum` on the server when
+    /// it is available and by streaming the file over SFTP otherwise.
+    pub async fn checksum(&self, path: &Path) -> Result<[u8; 32], SftpError> {
+        match self.exec_sha256sum(path).await {
+            Some(digest) => Ok(digest),
+            None => self.sha256(path).await,
+        }
+    }
+
+    /// Run `sha256sum` over an exec channel. `None` when the server refuses
+    /// shell access, has no `sha256sum`, or prints something unexpected.
+    async fn exec_sha256sum(&self, path: &Path) -> Option<[u8; 32]> {
+        let command_line = format!(
+            "sha256sum -- {}",
+            sudo::shell_quote(&path.to_string_lossy())
+        );
+        let (exit_status, stdout) = self
+            .exec_capture(&command_line, SHA256SUM_OUTPUT_LIMIT)
+            .await?;
+
+        if exit_status != Some(0) {
+            tracing::debug!(
+                "sha256sum unavailable for {} (exit status {:?}), streaming instead",
+                path.display(),
+                exit_status
+            );
+            return None;
+        }
+        parse_sha256sum_output(&stdout)
+    }
+
+    /// Number of hard links to a remote file, read with `stat` since SFTP
+    /// attributes do not carry it. `None` when the server refuses shell
+    /// access or has no usable `stat`.
+    async fn exec_link_count(&self, path: &str) -> Option<u64> {
+        let quoted = sudo::shell_quote(path);
+        // GNU stat first, then the BSD spelling
+        let command_line = format!("stat -c %h -- {quoted} 2>/dev/null || stat -f %l -- {quoted}");
+        let (exit_status, stdout) = self
+            .exec_capture(&command_line, LINK_COUNT_OUTPUT_LIMIT)
+            .await?;
+        if exit_status != Some(0) {
+            return None;
+        }
+        std::str::from_utf8(&stdout).ok()?.trim().parse().ok()
+    }
+
+    /// Run a command over an exec channel and collect its exit status and
+    /// up to `limit` bytes of stdout. `None` when no channel can be opened
+    /// or the server refuses the command.
+    async fn exec_capture(
+        &self,
+        command_line: &str,
+        limit: usize,
+    ) -> Option<(Option<u32>, Vec<u8>)> {
+        let mut channel = {
+            let handle = self._connection.handle();
+            let handle_guard = handle.lock().await;
+            handle_guard.channel_open_session().await
+        }
+        .ok()?;
+        channel.exec(true, command_line).await.ok()?;
+
+        let mut stdout = Vec::new();
+        let mut exit_status = None;
+        loop {
+            match channel.wait().await {
+                Some(ChannelMsg::Data { data }) => {
+                    if stdout.len() < limit {
+                        stdout.extend_from_slice(&data);
+                    }
+                }
+                Some(ChannelMsg::ExitStatus {
+                    exit_status: status,
+                }) => {
+                    exit_status = Some(status);
+                }
+                Some(ChannelMsg::Close) | None => break,
+                Some(_) => {}
+            }
+        }
+        Some((exit_status, stdout))
+    }
+
+    /// Read a whole remote file into memory, refusing files over `limit` bytes.
+    pub async fn read_limited(&self, path: &Path, limit: u64) -> Result<Vec<u8>, SftpError> {
+        let sftp = self.sftp.lock().await;
+        let path_str = path.to_string_lossy().to_string();
+        let metadata = sftp.symlink_metadata(path_str.clone()).await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to get metadata for {}: {}", path_str, e))
+        })?;
+        ensure_remote_file_source(&path_str, metadata.is_dir(), metadata.is_symlink(), "read")
+            .map_err(SftpError::FileOperation)?;
+        let too_large =
+            || SftpError::FileOperation(format!("{} is larger than {} bytes", path_str, limit));
+        if metadata.size.unwrap_or(0) > limit {
+            return Err(too_large());
+        }
+
+        let mut remote = sftp.open(path_str.clone()).await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to open remote file {}: {}", path_str, e))
+        })?;
+        let mut contents = Vec::new();
+        let mut buffer = vec![0u8; TRANSFER_BUFFER_SIZE];
+        loop {
+            let read = remote.read(&mut buffer).await.map_err(|e| {
+                SftpError::FileOperation(format!("Failed to read remote file {}: {}", path_str, e))
+            })?;
+            if read == 0 {
+                break;
+            }
+            // The file may have grown since it was stat'ed.
+            if contents.len() as u64 + read as u64 > limit {
+                return Err(too_large());
+            }
+            contents.extend_from_slice(&buffer[..read]);
+        }
+        Ok(contents)
+    }
+
     /// Create a directory
     pub async fn create_dir(&self, path: &Path) -> Result<(), SftpError> {
         let sftp = self.sftp.lock().await;
@@ -178,6 +499,25 @@ impl SftpSession {
         })
     }
 
+    /// Create an empty file. Existing files are never truncated.
+    pub async fn create_file(&self, path: &Path) -> Result<(), SftpError> {
+        let sftp = self.sftp.lock().await;
+        let path_str = path.to_string_lossy().to_string();
+
+        let mut file = sftp
+            .open_with_flags(
+                path_str.clone(),
+                OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::EXCLUDE,
+            )
+            .await
+            .map_err(|e| {
+                SftpError::FileOperation(format!("Failed to create file {}: {}", path_str, e))
+            })?;
+        file.shutdown().await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to close file {}: {}", path_str, e))
+        })
+    }
+
     /// Create a directory and any missing parents without following symlinks.
     pub async fn ensure_dir_all(&self, path: &Path) -> Result<(), SftpError> {
         let mut current = PathBuf::new();
@@ -291,6 +631,88 @@ impl SftpSession {
             })
     }
 
+    /// Change the owner and/or group of a file or directory (chown).
+    ///
+    /// SFTP always sets both ids, so a missing one keeps its current value.
+    pub async fn set_owner(&self, path: &Path, ownership: Ownership) -> Result<(), SftpError> {
+        let sftp = self.sftp.lock().await;
+        let path_str = path.to_string_lossy().to_string();
+
+        let metadata = sftp.symlink_metadata(path_str.clone()).await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to get metadata for {}: {}", path_str, e))
+        })?;
+        reject_remote_permissions_target(&path_str, metadata.is_symlink())
+            .map_err(SftpError::FileOperation)?;
+
+        let attrs = russh_sftp::protocol::FileAttributes {
+            uid: ownership.uid.or(metadata.uid),
+            gid: ownership.gid.or(metadata.gid),
+            ..Default::default()
+        };
+        sftp.set_metadata(path_str.clone(), attrs)
+            .await
+            .map_err(|e| SftpError::FileOperation(owner_change_error(&path_str, &e.to_string())))
+    }
+
+    /// Turn typed owner and group names or ids into ids using the host's
+    /// `/etc/passwd` and `/etc/group`.
+    pub async fn resolve_ownership(
+        &self,
+        owner: &str,
+        group: &str,
+    ) -> Result<Ownership, SftpError> {
+        let (passwd, groups) = if needs_account_lookup(owner, group) {
+            (
+                self.read_account_database("/etc/passwd").await,
+                self.read_account_database("/etc/group").await,
+            )
+        } else {
+            (None, None)
+        };
+        resolve_ownership(owner, group, passwd.as_deref(), groups.as_deref())
+            .map_err(SftpError::FileOperation)
+    }
+
+    /// Apply `change` to `path` and everything below it without following
+    /// symlinks, reporting the running count after each directory.
+    pub async fn apply_recursive<F, C>(
+        &self,
+        path: &Path,
+        change: TreeChange,
+        on_progress: F,
+        is_cancelled: C,
+    ) -> Result<ChmodReport, SftpError>
+    where
+        F: FnMut(ChmodProgress),
+        C: Fn() -> bool,
+    {
+        walk_tree(
+            path,
+            |dir| async move { self.list_dir(&dir).await.map_err(|e| e.to_string()) },
+            |entry, is_dir| async move {
+                match change {
+                    TreeChange::Mode(mode) => {
+                        self.set_permissions(&entry, mode.for_entry(is_dir)).await
+                    }
+                    TreeChange::Owner(ownership) => self.set_owner(&entry, ownership).await,
+                }
+                .map_err(|e| e.to_string())
+            },
+            on_progress,
+            is_cancelled,
+        )
+        .await
+        .map_err(SftpError::FileOperation)
+    }
+
+    /// Read a small account database such as `/etc/passwd`, if readable.
+    async fn read_account_database(&self, path: &str) -> Option<String> {
+        self.read_limited(Path::new(path), ACCOUNT_DATABASE_LIMIT)
+            .await
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+    }
+
     /// Remove a file
     pub async fn remove_file(&self, path: &Path) -> Result<(), SftpError> {
         let sftp = self.sftp.lock().await;
@@ -311,6 +733,176 @@ impl SftpSession {
         })
     }
 
+    /// Run a file operation through `sudo` on a new exec channel.
+    ///
+    /// The password is written to sudo's stdin followed by EOF, so commands
+    /// that run without a prompt (NOPASSWD, cached sudo timestamp) never see
+    /// more input than that single line.
+    pub async fn run_sudo(
+        &self,
+        command: &SudoCommand,
+        password: &SecretString,
+    ) -> Result<(), SftpError> {
+        if let SudoCommand::Rename { to, .. } = command {
+            let sftp = self.sftp.lock().await;
+            let to_str = to.to_string_lossy().to_string();
+            // Best effort: `mv -n` still refuses to replace files if the
+            // destination can't be checked as the login user.
+            if let Ok(exists) = sftp.try_exists(to_str.clone()).await {
+                reject_existing_remote_rename_destination(&to_str, exists)
+                    .map_err(SftpError::FileOperation)?;
+            }
+        }
+
+        let command_line = command.command_line();
+        let result = timeout(SUDO_COMMAND_TIMEOUT, async {
+            let mut channel = {
+                let handle = self._connection.handle();
+                let handle_guard = handle.lock().await;
+                handle_guard.channel_open_session().await
+            }
+            .map_err(|e| SftpError::FileOperation(format!("Failed to open sudo channel: {e}")))?;
+
+            channel
+                .exec(true, command_line.as_str())
+                .await
+                .map_err(|e| SftpError::FileOperation(format!("Failed to run sudo: {e}")))?;
+
+            channel
+                .data(password.expose_secret().as_bytes())
+                .await
+                .map_err(|e| {
+                    SftpError::FileOperation(format!("Failed to send sudo password: {e}"))
+                })?;
+            channel.data(&b"\n"[..]).await.map_err(|e| {
+                SftpError::FileOperation(format!("Failed to send sudo password: {e}"))
+            })?;
+            let _ = channel.eof().await;
+
+            let mut stderr = String::new();
+            let mut exit_status = None;
+            loop {
+                match channel.wait().await {
+                    Some(ChannelMsg::ExtendedData { data, .. }) => {
+                        if stderr.len() < SUDO_STDERR_LIMIT {
+                            stderr.push_str(&String::from_utf8_lossy(&data));
+                        }
+                    }
+                    Some(ChannelMsg::ExitStatus {
+                        exit_status: status,
+                    }) => {
+                        exit_status = Some(status);
+                    }
+                    // OpenSSH sends the exit status after EOF, so read until
+                    // the channel closes.
+                    Some(ChannelMsg::Close) | None => break,
+                    Some(_) => {}
+                }
+            }
+
+            match exit_status {
+                Some(0) => Ok(()),
+                status => Err(sudo::failure_error(command, &stderr, status)),
+            }
+        })
+        .await
+        .unwrap_or_else(|_| {
+            Err(SftpError::FileOperation(format!(
+                "Timed out trying to {} with sudo",
+                command.describe()
+            )))
+        });
+
+        security_log::log_sudo_operation(
+            self._connection.host(),
+            self._connection.port(),
+            &command.describe(),
+            result.is_ok(),
+        );
+        result
+    }
+
+    /// Write `contents` to a remote file through `sudo`.
+    ///
+    /// The data is staged as a private file in the login user's home
+    /// directory over SFTP, copied into place with `sudo cp`, and the staged
+    /// copy is removed afterwards regardless of the outcome.
+    pub async fn write_with_sudo(
+        &self,
+        contents: &[u8],
+        remote_path: &Path,
+        password: &SecretString,
+    ) -> Result<u64, SftpError> {
+        let staging_path = self.sudo_staging_path();
+        let bytes = self
+            .upload_bytes(contents, &staging_path)
+            .await
+            .map_err(|e| {
+                SftpError::FileOperation(format!("Failed to stage file for sudo: {}", e))
+            })?;
+        self.copy_staged_with_sudo(&staging_path, remote_path, password)
+            .await
+            .map(|()| bytes)
+    }
+
+    /// Upload a local file to a remote path through `sudo`, staged like
+    /// [`Self::write_with_sudo`] but streamed from disk.
+    pub async fn upload_with_sudo(
+        &self,
+        local_path: &Path,
+        remote_path: &Path,
+        password: &SecretString,
+    ) -> Result<u64, SftpError> {
+        let staging_path = self.sudo_staging_path();
+        let bytes = self.upload(local_path, &staging_path).await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to stage file for sudo: {}", e))
+        })?;
+        if let Err(error) = self.set_permissions(&staging_path, 0o600).await {
+            tracing::warn!(
+                "Failed to make sudo staging file {} private: {}",
+                staging_path.display(),
+                error
+            );
+        }
+        self.copy_staged_with_sudo(&staging_path, remote_path, password)
+            .await
+            .map(|()| bytes)
+    }
+
+    fn sudo_staging_path(&self) -> PathBuf {
+        self.home_dir
+            .join(format!(".portal-sudo-{}.tmp", Uuid::new_v4()))
+    }
+
+    /// Copy a staged file into place with `sudo cp`, then remove the staged
+    /// copy whatever the outcome
+    async fn copy_staged_with_sudo(
+        &self,
+        staging_path: &Path,
+        remote_path: &Path,
+        password: &SecretString,
+    ) -> Result<(), SftpError> {
+        let result = self
+            .run_sudo(
+                &SudoCommand::CopyFile {
+                    from: staging_path.to_path_buf(),
+                    to: remote_path.to_path_buf(),
+                },
+                password,
+            )
+            .await;
+
+        if let Err(error) = self.remove_file(staging_path).await {
+            tracing::warn!(
+                "Failed to remove sudo staging file {}: {}",
+                staging_path.display(),
+                error
+            );
+        }
+
+        result
+    }
+
     /// Remove a file or directory recursively
     pub async fn remove_recursive(&self, path: &Path) -> Result<(), SftpError> {
         // First check if it's a directory or symlink (do not follow symlinks)
@@ -345,15 +937,26 @@ impl SftpSession {
 
     /// Download a file from remote to local
     pub async fn download(&self, remote_path: &Path, local_path: &Path) -> Result<u64, SftpError> {
-        self.download_with_progress(remote_path, local_path, |_| {}, || false)
-            .await
+        self.download_with_progress(
+            remote_path,
+            local_path,
+            TransferResume::Off,
+            |_| {},
+            || false,
+        )
+        .await
     }
 
     /// Download a file and report cumulative bytes written.
+    ///
+    /// With `resume` enabled, a partial file left by an interrupted attempt
+    /// is continued instead of started over, and the partial file is kept if
+    /// this attempt fails too (but not if it is cancelled).
     pub async fn download_with_progress<F, C>(
         &self,
         remote_path: &Path,
         local_path: &Path,
+        resume: TransferResume,
         mut on_progress: F,
         is_cancelled: C,
     ) -> Result<u64, SftpError>
@@ -364,7 +967,11 @@ impl SftpSession {
         ensure_local_download_parent(local_path).await?;
         ensure_local_file_download_target(local_path).await?;
 
-        let partial_path = local_staging_path(local_path, STAGING_PARTIAL_MARKER)?;
+        let partial_path = if resume.is_enabled() {
+            resume_staging_path(local_path).map_err(SftpError::LocalIo)?
+        } else {
+            local_staging_path(local_path, STAGING_PARTIAL_MARKER)?
+        };
         let sftp = self.sftp.lock().await;
         let remote_str = remote_path.to_string_lossy().to_string();
 
@@ -389,28 +996,65 @@ impl SftpSession {
             SftpError::Transfer(format!("Failed to open remote file {}: {}", remote_str, e))
         })?;
 
-        let mut local = {
-            let mut options = OpenOptions::new();
-            options.create_new(true).write(true);
-            #[cfg(unix)]
-            {
-                options.mode(0o600);
+        let source = ResumeSource {
+            len: metadata.size.unwrap_or(0),
+            mtime: metadata.mtime.map(u64::from),
+        };
+        let mut offset = if resume.is_enabled() {
+            resume_offset(
+                local_partial_len(&partial_path).await,
+                read_local_resume_source(&partial_path).await,
+                source,
+            )
+        } else {
+            0
+        };
+        let mut local = if offset > 0 {
+            open_local_resume_file(&partial_path).await?
+        } else {
+            if resume.is_enabled() {
+                cleanup_local_staging(&partial_path).await;
+                write_local_resume_source(&partial_path, source).await;
             }
-            options.open(&partial_path).await.map_err(|e| {
-                SftpError::LocalIo(format!(
-                    "Failed to open local staging file {}: {}",
+            create_local_staging_file(&partial_path).await?
+        };
+
+        if offset > 0 && resume == TransferResume::AppendVerified {
+            let local_tail = tail_sha256(&mut local, offset).await;
+            let remote_tail = tail_sha256(&mut remote, offset).await;
+            if !matches!((local_tail, remote_tail), (Ok(a), Ok(b)) if a == b) {
+                tracing::warn!(
+                    "Partial download {} does not match {}, starting over",
                     partial_path.display(),
-                    e
-                ))
-            })?
+                    remote_str
+                );
+                offset = 0;
+            }
+        }
+        let positioned = match local.set_len(offset).await {
+            Ok(()) => seek_for_resume(&mut local, &mut remote, offset).await,
+            Err(e) => Err(e),
         };
+        if let Err(e) = positioned {
+            drop(local);
+            discard_local_partial(&partial_path, resume).await;
+            return Err(SftpError::Transfer(format!(
+                "Failed to resume download of {}: {}",
+                remote_str, e
+            )));
+        }
+        if offset > 0 {
+            tracing::info!("Resuming download of {} at byte {}", remote_str, offset);
+            on_progress(offset);
+        }
 
-        let mut bytes = 0u64;
+        let mut bytes = offset;
         let mut buffer = vec![0u8; TRANSFER_BUFFER_SIZE];
         loop {
             if is_cancelled() {
                 drop(local);
                 cleanup_local_staging(&partial_path).await;
+                cleanup_local_staging(&resume_source_path(&partial_path)).await;
                 return Err(SftpError::Transfer("Transfer cancelled".to_string()));
             }
 
@@ -418,8 +1062,9 @@ impl SftpSession {
                 Ok(0) => break,
                 Ok(read) => read,
                 Err(e) => {
+                    let _ = local.flush().await;
                     drop(local);
-                    cleanup_local_staging(&partial_path).await;
+                    discard_local_partial(&partial_path, resume).await;
                     return Err(SftpError::Transfer(format!(
                         "Failed to download {} to {}: {}",
                         remote_str,
@@ -431,7 +1076,7 @@ impl SftpSession {
 
             if let Err(e) = local.write_all(&buffer[..read]).await {
                 drop(local);
-                cleanup_local_staging(&partial_path).await;
+                discard_local_partial(&partial_path, resume).await;
                 return Err(SftpError::LocalIo(format!(
                     "Failed to write local staging file {}: {}",
                     partial_path.display(),
@@ -444,7 +1089,7 @@ impl SftpSession {
 
         if let Err(e) = local.flush().await {
             drop(local);
-            cleanup_local_staging(&partial_path).await;
+            discard_local_partial(&partial_path, resume).await;
             return Err(SftpError::LocalIo(format!(
                 "Failed to flush local staging file {}: {}",
                 partial_path.display(),
@@ -454,7 +1099,7 @@ impl SftpSession {
 
         if let Err(e) = local.sync_all().await {
             drop(local);
-            cleanup_local_staging(&partial_path).await;
+            discard_local_partial(&partial_path, resume).await;
             return Err(SftpError::LocalIo(format!(
                 "Failed to sync local staging file {}: {}",
                 partial_path.display(),
@@ -464,7 +1109,11 @@ impl SftpSession {
 
         drop(local);
 
-        if let Err(e) = tokio::fs::rename(&partial_path, local_path).await {
+        let promoted = tokio::fs::rename(&partial_path, local_path).await;
+        if resume.is_enabled() {
+            cleanup_local_staging(&resume_source_path(&partial_path)).await;
+        }
+        if let Err(e) = promoted {
             cleanup_local_staging(&partial_path).await;
             return Err(SftpError::LocalIo(format!(
                 "Failed to promote local staging file {} to {}: {}",
@@ -481,8 +1130,14 @@ impl SftpSession {
 
     /// Upload a file from local to remote
     pub async fn upload(&self, local_path: &Path, remote_path: &Path) -> Result<u64, SftpError> {
-        self.upload_with_progress(local_path, remote_path, |_| {}, || false)
-            .await
+        self.upload_with_progress(
+            local_path,
+            remote_path,
+            TransferResume::Off,
+            |_| {},
+            || false,
+        )
+        .await
     }
 
     /// Upload bytes to a new remote file. Existing destinations are not overwritten.
@@ -511,7 +1166,7 @@ impl SftpSession {
                 drop(remote);
                 cleanup_remote_staging(&sftp, &remote_str).await;
                 return Err(SftpError::Transfer(format!(
-                    "Failed to upload clipboard image to {}: {}",
+                    "Failed to write remote file {}: {}",
                     remote_str, e
                 )));
             }
@@ -551,21 +1206,163 @@ impl SftpSession {
         drop(sftp);
 
         if let Err(error) = self.set_permissions(remote_path, 0o600).await {
-            tracing::warn!(
-                "Failed to set clipboard image permissions on {}: {}",
-                remote_str,
-                error
-            );
+            tracing::warn!("Failed to make {} private: {}", remote_str, error);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Replace the contents of an existing remote file.
+    ///
+    /// The bytes go to a staging file next to the target, which takes over
+    /// the original owner, group and permissions and is then renamed into
+    /// place, so a failed write leaves the original untouched. Symlinks are
+    /// followed and the file they point to is replaced.
+    ///
+    /// A rename would give the file a new inode, splitting it from its other
+    /// hard links, and handing the staging file to another owner needs root.
+    /// For files with several links, or when the owner cannot be carried
+    /// over, the file is instead truncated and rewritten in place: its
+    /// identity is kept, but a failed write can leave it incomplete.
+    pub async fn write_file_atomic(
+        &self,
+        remote_path: &Path,
+        contents: &[u8],
+    ) -> Result<u64, SftpError> {
+        let sftp = self.sftp.lock().await;
+        let requested = remote_path.to_string_lossy().to_string();
+
+        let target = sftp.canonicalize(requested.clone()).await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to resolve {}: {}", requested, e))
+        })?;
+        let metadata = sftp.metadata(target.clone()).await.map_err(|e| {
+            SftpError::FileOperation(format!("Failed to get metadata for {}: {}", target, e))
+        })?;
+        if metadata.is_dir() {
+            return Err(SftpError::FileOperation(format!(
+                "{} is a directory",
+                target
+            )));
+        }
+
+        if self
+            .exec_link_count(&target)
+            .await
+            .is_some_and(|links| links > 1)
+        {
+            return write_remote_in_place(&sftp, &target, contents).await;
+        }
+
+        let target_path = PathBuf::from(&target);
+        let partial_str = remote_staging_path(&target_path, STAGING_PARTIAL_MARKER)?
+            .to_string_lossy()
+            .to_string();
+        let backup_str = remote_staging_path(&target_path, STAGING_BACKUP_MARKER)?
+            .to_string_lossy()
+            .to_string();
+
+        let mut remote = sftp
+            .open_with_flags(
+                partial_str.clone(),
+                OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::EXCLUDE,
+            )
+            .await
+            .map_err(|e| {
+                SftpError::Transfer(format!(
+                    "Failed to create {} in the folder of {}: {}",
+                    partial_str, target, e
+                ))
+            })?;
+
+        let mut bytes = 0u64;
+        for chunk in contents.chunks(TRANSFER_BUFFER_SIZE) {
+            if let Err(e) = remote.write_all(chunk).await {
+                let _ = remote.shutdown().await;
+                drop(remote);
+                cleanup_remote_staging(&sftp, &partial_str).await;
+                return Err(SftpError::Transfer(format!(
+                    "Failed to write {}: {}",
+                    target, e
+                )));
+            }
+            bytes = bytes.saturating_add(chunk.len() as u64);
+        }
+
+        if let Err(e) = remote.flush().await {
+            let _ = remote.shutdown().await;
+            drop(remote);
+            cleanup_remote_staging(&sftp, &partial_str).await;
+            return Err(SftpError::Transfer(format!(
+                "Failed to write {}: {}",
+                target, e
+            )));
+        }
+
+        if let Err(e) = remote.sync_all().await {
+            let _ = remote.shutdown().await;
+            drop(remote);
+            cleanup_remote_staging(&sftp, &partial_str).await;
+            return Err(SftpError::Transfer(format!(
+                "Failed to write {}: {}",
+                target, e
+            )));
+        }
+
+        if let Err(e) = remote.shutdown().await {
+            drop(remote);
+            cleanup_remote_staging(&sftp, &partial_str).await;
+            return Err(SftpError::Transfer(format!(
+                "Failed to close {}: {}",
+                partial_str, e
+            )));
+        }
+        drop(remote);
+
+        // Ownership first: chown clears set-id bits the mode then restores
+        if let (Some(uid), Some(gid)) = (metadata.uid, metadata.gid) {
+            let attrs = russh_sftp::protocol::FileAttributes {
+                uid: Some(uid),
+                gid: Some(gid),
+                ..Default::default()
+            };
+            if let Err(e) = sftp.set_metadata(partial_str.clone(), attrs).await {
+                tracing::debug!(
+                    "Cannot give the staged copy of {} its owner ({}), writing in place",
+                    target,
+                    e
+                );
+                cleanup_remote_staging(&sftp, &partial_str).await;
+                return write_remote_in_place(&sftp, &target, contents).await;
+            }
+        }
+
+        if let Some(mode) = metadata.permissions {
+            let attrs = russh_sftp::protocol::FileAttributes {
+                permissions: Some(mode & 0o7777),
+                ..Default::default()
+            };
+            if let Err(e) = sftp.set_metadata(partial_str.clone(), attrs).await {
+                tracing::warn!("Failed to copy permissions of {}: {}", target, e);
+            }
+        }
+
+        if let Err(e) = promote_remote_staging(&sftp, &partial_str, &target, &backup_str).await {
+            cleanup_remote_staging(&sftp, &partial_str).await;
+            return Err(e);
         }
 
         Ok(bytes)
     }
 
     /// Upload a file and report cumulative bytes written.
+    ///
+    /// `resume` works as for [`Self::download_with_progress`], with the
+    /// partial file kept next to the remote destination.
     pub async fn upload_with_progress<F, C>(
         &self,
         local_path: &Path,
         remote_path: &Path,
+        resume: TransferResume,
         mut on_progress: F,
         is_cancelled: C,
     ) -> Result<u64, SftpError>
@@ -574,34 +1371,110 @@ impl SftpSession {
         C: Fn() -> bool,
     {
         let mut local = open_local_upload_file_source(local_path).await?;
+        let local_metadata = local.metadata().await.ok();
+        let source = ResumeSource {
+            len: local_metadata.as_ref().map_or(0, |metadata| metadata.len()),
+            mtime: local_metadata
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|elapsed| elapsed.as_secs()),
+        };
 
         let sftp = self.sftp.lock().await;
         let remote_str = remote_path.to_string_lossy().to_string();
-        let partial_path = remote_staging_path(remote_path, STAGING_PARTIAL_MARKER)?;
+        let partial_path = if resume.is_enabled() {
+            resume_staging_path(remote_path).map_err(SftpError::Transfer)?
+        } else {
+            remote_staging_path(remote_path, STAGING_PARTIAL_MARKER)?
+        };
         let backup_path = remote_staging_path(remote_path, STAGING_BACKUP_MARKER)?;
         let partial_str = partial_path.to_string_lossy().to_string();
         let backup_str = backup_path.to_string_lossy().to_string();
 
-        let mut remote = sftp
-            .open_with_flags(
-                partial_str.clone(),
-                OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::EXCLUDE,
+        let mut offset = if resume.is_enabled() {
+            resume_offset(
+                remote_partial_len(&sftp, &partial_str).await,
+                read_remote_resume_source(&sftp, &partial_str).await,
+                source,
             )
-            .await
-            .map_err(|e| {
-                SftpError::Transfer(format!(
-                    "Failed to open remote staging file {}: {}",
-                    partial_str, e
-                ))
-            })?;
+        } else {
+            0
+        };
+        let mut remote = None;
+        if offset > 0 {
+            match sftp
+                .open_with_flags(partial_str.clone(), OpenFlags::READ | OpenFlags::WRITE)
+                .await
+            {
+                Ok(mut file) => {
+                    if resume == TransferResume::AppendVerified {
+                        let local_tail = tail_sha256(&mut local, offset).await;
+                        let remote_tail = tail_sha256(&mut file, offset).await;
+                        if !matches!((local_tail, remote_tail), (Ok(a), Ok(b)) if a == b) {
+                            tracing::warn!(
+                                "Partial upload {} does not match {}, starting over",
+                                partial_str,
+                                local_path.display()
+                            );
+                            offset = 0;
+                        }
+                    }
+                    if offset > 0 {
+                        remote = Some(file);
+                    } else {
+                        let _ = file.shutdown().await;
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Cannot reopen partial upload {}: {}", partial_str, e);
+                    offset = 0;
+                }
+            }
+        }
+        let mut remote = match remote {
+            Some(file) => file,
+            None => {
+                if resume.is_enabled() {
+                    cleanup_remote_staging(&sftp, &partial_str).await;
+                    write_remote_resume_source(&sftp, &partial_str, source).await;
+                }
+                sftp.open_with_flags(
+                    partial_str.clone(),
+                    OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::EXCLUDE,
+                )
+                .await
+                .map_err(|e| {
+                    SftpError::Transfer(format!(
+                        "Failed to open remote staging file {}: {}",
+                        partial_str, e
+                    ))
+                })?
+            }
+        };
 
-        let mut bytes = 0u64;
+        if let Err(e) = seek_for_resume(&mut local, &mut remote, offset).await {
+            let _ = remote.shutdown().await;
+            drop(remote);
+            discard_remote_partial(&sftp, &partial_str, resume).await;
+            return Err(SftpError::Transfer(format!(
+                "Failed to resume upload of {}: {}",
+                local_path.display(),
+                e
+            )));
+        }
+        if offset > 0 {
+            tracing::info!("Resuming upload to {} at byte {}", remote_str, offset);
+            on_progress(offset);
+        }
+
+        let mut bytes = offset;
         let mut buffer = vec![0u8; TRANSFER_BUFFER_SIZE];
         loop {
             if is_cancelled() {
                 let _ = remote.shutdown().await;
                 drop(remote);
                 cleanup_remote_staging(&sftp, &partial_str).await;
+                forget_remote_resume_source(&sftp, &partial_str, resume).await;
                 return Err(SftpError::Transfer("Transfer cancelled".to_string()));
             }
 
@@ -611,7 +1484,7 @@ impl SftpSession {
                 Err(e) => {
                     let _ = remote.shutdown().await;
                     drop(remote);
-                    cleanup_remote_staging(&sftp, &partial_str).await;
+                    discard_remote_partial(&sftp, &partial_str, resume).await;
                     return Err(SftpError::LocalIo(format!(
                         "Failed to read local file {}: {}",
                         local_path.display(),
@@ -623,7 +1496,7 @@ impl SftpSession {
             if let Err(e) = remote.write_all(&buffer[..read]).await {
                 let _ = remote.shutdown().await;
                 drop(remote);
-                cleanup_remote_staging(&sftp, &partial_str).await;
+                discard_remote_partial(&sftp, &partial_str, resume).await;
                 return Err(SftpError::Transfer(format!(
                     "Failed to upload {} to {}: {}",
                     local_path.display(),
@@ -638,7 +1511,7 @@ impl SftpSession {
         if let Err(e) = remote.flush().await {
             let _ = remote.shutdown().await;
             drop(remote);
-            cleanup_remote_staging(&sftp, &partial_str).await;
+            discard_remote_partial(&sftp, &partial_str, resume).await;
             return Err(SftpError::Transfer(format!(
                 "Failed to flush remote staging file {}: {}",
                 partial_str, e
@@ -648,7 +1521,7 @@ impl SftpSession {
         if let Err(e) = remote.sync_all().await {
             let _ = remote.shutdown().await;
             drop(remote);
-            cleanup_remote_staging(&sftp, &partial_str).await;
+            discard_remote_partial(&sftp, &partial_str, resume).await;
             return Err(SftpError::Transfer(format!(
                 "Failed to sync remote staging file {}: {}",
                 partial_str, e
@@ -657,7 +1530,7 @@ impl SftpSession {
 
         if let Err(e) = remote.shutdown().await {
             drop(remote);
-            cleanup_remote_staging(&sftp, &partial_str).await;
+            discard_remote_partial(&sftp, &partial_str, resume).await;
             return Err(SftpError::Transfer(format!(
                 "Failed to close remote staging file {}: {}",
                 partial_str, e
@@ -666,8 +1539,9 @@ impl SftpSession {
 
         drop(remote);
 
-        if let Err(e) = promote_remote_staging(&sftp, &partial_str, &remote_str, &backup_str).await
-        {
+        let promoted = promote_remote_staging(&sftp, &partial_str, &remote_str, &backup_str).await;
+        forget_remote_resume_source(&sftp, &partial_str, resume).await;
+        if let Err(e) = promoted {
             cleanup_remote_staging(&sftp, &partial_str).await;
             return Err(e);
         }
@@ -763,6 +1637,61 @@ fn parent_entry_path(path: &Path) -> Option<&Path> {
     Some(parent)
 }
 
+/// Read where a remote symlink points and whether its target is a directory.
+/// Broken links keep their text but have no resolved path.
+///
+/// The link text and the target's attributes are requested together. The
+/// resolved path is worked out from the link text rather than asked of the
+/// server, so it is not canonical when the target passes through further
+/// links.
+async fn remote_symlink_target(
+    sftp: &RusshSftpSession,
+    path: &Path,
+) -> (Option<SymlinkTarget>, bool) {
+    let path_str = path.to_string_lossy().to_string();
+    let (target, metadata) =
+        futures::join!(sftp.read_link(path_str.clone()), sftp.metadata(path_str));
+    let Ok(target) = target else {
+        return (None, false);
+    };
+    let Ok(metadata) = metadata else {
+        return (
+            Some(SymlinkTarget {
+                target,
+                resolved: None,
+            }),
+            false,
+        );
+    };
+    let resolved = resolve_link_target(path, &target);
+    (
+        Some(SymlinkTarget {
+            target,
+            resolved: Some(resolved),
+        }),
+        metadata.is_dir(),
+    )
+}
+
+/// Join a link's text onto the directory holding the link, folding `.` and
+/// `..` components
+fn resolve_link_target(link: &Path, target: &str) -> PathBuf {
+    let joined = link.parent().unwrap_or(link).join(target);
+    let mut resolved = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if resolved.parent().is_some() {
+                    resolved.pop();
+                }
+            }
+            other => resolved.push(other),
+        }
+    }
+    resolved
+}
+
 fn should_skip_recursive_download_entry(entry: &FileEntry) -> bool {
     entry.name == ".." || entry.is_symlink
 }
@@ -1050,6 +1979,153 @@ fn staging_file_name(final_name: &str, marker: &str, id: Uuid) -> String {
     format!(".{}{}{}", final_name, marker, id)
 }
 
+/// Create a new, private local staging file
+async fn create_local_staging_file(path: &Path) -> Result<tokio::fs::File, SftpError> {
+    let mut options = OpenOptions::new();
+    options.create_new(true).write(true);
+    #[cfg(unix)]
+    {
+        options.mode(0o600);
+    }
+    options.open(path).await.map_err(|e| {
+        SftpError::LocalIo(format!(
+            "Failed to open local staging file {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Length of a regular local file left by an interrupted download
+async fn local_partial_len(path: &Path) -> Option<u64> {
+    match tokio::fs::symlink_metadata(path).await {
+        Ok(metadata) if metadata.is_file() => Some(metadata.len()),
+        _ => None,
+    }
+}
+
+/// Open an existing local partial download for reading and appending,
+/// refusing to follow a symlink planted in its place
+async fn open_local_resume_file(path: &Path) -> Result<tokio::fs::File, SftpError> {
+    let mut options = OpenOptions::new();
+    options.read(true).write(true);
+    #[cfg(unix)]
+    {
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    options.open(path).await.map_err(|e| {
+        SftpError::LocalIo(format!(
+            "Failed to open partial download {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Source recorded with a local partial download
+async fn read_local_resume_source(partial: &Path) -> Option<ResumeSource> {
+    let record = tokio::fs::read_to_string(resume_source_path(partial))
+        .await
+        .ok()?;
+    ResumeSource::decode(&record)
+}
+
+/// Record the source of a local partial download that starts now. Without
+/// a record the next attempt starts over, so failing here is not fatal.
+async fn write_local_resume_source(partial: &Path, source: ResumeSource) {
+    let path = resume_source_path(partial);
+    cleanup_local_staging(&path).await;
+    let written = match create_local_staging_file(&path).await {
+        Ok(mut file) => file
+            .write_all(source.encode().as_bytes())
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = written {
+        tracing::debug!("Failed to record resume source {}: {}", path.display(), e);
+    }
+}
+
+/// Remove a failed download's staging file unless it is kept for resuming
+async fn discard_local_partial(path: &Path, resume: TransferResume) {
+    if !resume.is_enabled() {
+        cleanup_local_staging(path).await;
+    }
+}
+
+/// Length of a regular remote file left by an interrupted upload
+async fn remote_partial_len(sftp: &RusshSftpSession, path: &str) -> Option<u64> {
+    match sftp.symlink_metadata(path.to_string()).await {
+        Ok(metadata) if !metadata.is_dir() && !metadata.is_symlink() => metadata.size,
+        _ => None,
+    }
+}
+
+/// Source recorded with a remote partial upload
+async fn read_remote_resume_source(sftp: &RusshSftpSession, partial: &str) -> Option<ResumeSource> {
+    let path = resume_source_path(Path::new(partial));
+    let record = sftp.read(path.to_string_lossy().to_string()).await.ok()?;
+    ResumeSource::decode(std::str::from_utf8(&record).ok()?)
+}
+
+/// Record the source of a remote partial upload that starts now. Without a
+/// record the next attempt starts over, so failing here is not fatal.
+async fn write_remote_resume_source(sftp: &RusshSftpSession, partial: &str, source: ResumeSource) {
+    let path = resume_source_path(Path::new(partial))
+        .to_string_lossy()
+        .to_string();
+    cleanup_remote_staging(sftp, &path).await;
+    let written = async {
+        let mut file = sftp
+            .open_with_flags(
+                path.clone(),
+                OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::EXCLUDE,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        file.write_all(source.encode().as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+        file.shutdown().await.map_err(|e| e.to_string())
+    }
+    .await;
+    if let Err(e) = written {
+        tracing::debug!("Failed to record resume source {}: {}", path, e);
+    }
+}
+
+/// Remove the source record of a resumable upload that finished or was
+/// cancelled
+async fn forget_remote_resume_source(
+    sftp: &RusshSftpSession,
+    partial: &str,
+    resume: TransferResume,
+) {
+    if resume.is_enabled() {
+        let path = resume_source_path(Path::new(partial));
+        cleanup_remote_staging(sftp, &path.to_string_lossy()).await;
+    }
+}
+
+/// Remove a failed upload's staging file unless it is kept for resuming
+async fn discard_remote_partial(sftp: &RusshSftpSession, path: &str, resume: TransferResume) {
+    if !resume.is_enabled() {
+        cleanup_remote_staging(sftp, path).await;
+    }
+}
+
+/// Position both sides of a transfer at `offset`
+async fn seek_for_resume<S, D>(source: &mut S, target: &mut D, offset: u64) -> std::io::Result<()>
+where
+    S: tokio::io::AsyncSeek + Unpin,
+    D: tokio::io::AsyncSeek + Unpin,
+{
+    source.seek(std::io::SeekFrom::Start(offset)).await?;
+    target.seek(std::io::SeekFrom::Start(offset)).await?;
+    Ok(())
+}
+
 async fn cleanup_local_staging(path: &Path) {
     match tokio::fs::remove_file(path).await {
         Ok(()) => {}
@@ -1091,6 +2167,47 @@ async fn sync_local_parent_dir(path: &Path) {
     }
 }
 
+/// Truncate and rewrite a remote file through its existing inode. Failures
+/// after the truncate are reported as leaving the file possibly incomplete.
+async fn write_remote_in_place(
+    sftp: &RusshSftpSession,
+    target: &str,
+    contents: &[u8],
+) -> Result<u64, SftpError> {
+    let mut remote = sftp
+        .open_with_flags(target.to_string(), OpenFlags::WRITE | OpenFlags::TRUNCATE)
+        .await
+        .map_err(|e| SftpError::Transfer(format!("Failed to open {}: {}", target, e)))?;
+    let incomplete = |e: String| {
+        SftpError::Transfer(format!(
+            "Failed to rewrite {} in place, it may be incomplete: {}",
+            target, e
+        ))
+    };
+
+    let mut bytes = 0u64;
+    for chunk in contents.chunks(TRANSFER_BUFFER_SIZE) {
+        if let Err(e) = remote.write_all(chunk).await {
+            let _ = remote.shutdown().await;
+            return Err(incomplete(e.to_string()));
+        }
+        bytes = bytes.saturating_add(chunk.len() as u64);
+    }
+    if let Err(e) = remote.flush().await {
+        let _ = remote.shutdown().await;
+        return Err(incomplete(e.to_string()));
+    }
+    if let Err(e) = remote.sync_all().await {
+        let _ = remote.shutdown().await;
+        return Err(incomplete(e.to_string()));
+    }
+    remote
+        .shutdown()
+        .await
+        .map_err(|e| incomplete(e.to_string()))?;
+    Ok(bytes)
+}
+
 async fn cleanup_remote_staging(sftp: &RusshSftpSession, path: &str) {
     if let Err(e) = sftp.remove_file(path.to_string()).await {
         tracing::debug!("Failed to remove remote staging file {}: {}", path, e);
@@ -1181,6 +2298,41 @@ impl Drop for SftpSession {
 /// Thread-safe wrapper for SFTP session
 pub type SharedSftpSession = Arc<SftpSession>;
 
+/// Turn a configured start path into an absolute remote path. `~` refers
+/// to the remote home directory and relative paths are resolved against it.
+fn expand_start_path(home_dir: &Path, start_path: &str) -> Option<PathBuf> {
+    let start_path = start_path.trim();
+    if start_path.is_empty() {
+        return None;
+    }
+    if start_path == "~" {
+        return Some(home_dir.to_path_buf());
+    }
+    if let Some(rest) = start_path.strip_prefix("~/") {
+        return Some(home_dir.join(rest));
+    }
+    if start_path.starts_with('/') {
+        Some(PathBuf::from(start_path))
+    } else {
+        Some(home_dir.join(start_path))
+    }
+}
+
+/// Digest from the first line of `sha256sum` output. Names with unusual
+/// characters make `sha256sum` prefix the line with a backslash.
+fn parse_sha256sum_output(output: &[u8]) -> Option<[u8; 32]> {
+    let output = std::str::from_utf8(output).ok()?;
+    let hex = output.strip_prefix('\\').unwrap_or(output).get(..64)?;
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut digest = [0u8; 32];
+    for (byte, pair) in digest.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(digest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1191,6 +2343,27 @@ mod tests {
     // in tests/ssh_integration/. The unit tests here cover type definitions and
     // any pure helper functions.
 
+    #[test]
+    fn resolve_link_target_folds_relative_components() {
+        let link = Path::new("/srv/app/current");
+        assert_eq!(
+            resolve_link_target(link, "releases/./42"),
+            PathBuf::from("/srv/app/releases/42")
+        );
+        assert_eq!(
+            resolve_link_target(link, "../../var/www"),
+            PathBuf::from("/var/www")
+        );
+        assert_eq!(
+            resolve_link_target(link, "/opt/app"),
+            PathBuf::from("/opt/app")
+        );
+        assert_eq!(
+            resolve_link_target(Path::new("/up"), "../../.."),
+            PathBuf::from("/")
+        );
+    }
+
     // === Type alias tests ===
 
     #[test]
@@ -1206,6 +2379,25 @@ mod tests {
     }
 
     // === Path handling tests ===
+
+    #[test]
+    fn start_path_expands_relative_to_home() {
+        let home = Path::new("/home/deploy");
+        assert_eq!(
+            expand_start_path(home, "/var/log"),
+            Some(PathBuf::from("/var/log"))
+        );
+        assert_eq!(
+            expand_start_path(home, "~/releases"),
+            Some(PathBuf::from("/home/deploy/releases"))
+        );
+        assert_eq!(
+            expand_start_path(home, "apps"),
+            Some(PathBuf::from("/home/deploy/apps"))
+        );
+        assert_eq!(expand_start_path(home, "~"), Some(home.to_path_buf()));
+        assert_eq!(expand_start_path(home, "  "), None);
+    }
     // These test the path conversion patterns used throughout the module
 
     #[test]
@@ -1270,6 +2462,7 @@ mod tests {
             path: parent.to_path_buf(),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: None,
         };
@@ -1293,6 +2486,7 @@ mod tests {
             path: entry_path.clone(),
             is_dir: false,
             is_symlink: false,
+            symlink_target: None,
             size: 1024,
             modified: Some(Utc::now()),
         };
@@ -1315,6 +2509,7 @@ mod tests {
             path: base_path.join(&name),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 4096,
             modified: None,
         };
@@ -1331,6 +2526,7 @@ mod tests {
             path: PathBuf::from("/usr/bin/link"),
             is_dir: false,
             is_symlink: true,
+            symlink_target: None,
             size: 0,
             modified: None,
         };
@@ -1476,6 +2672,7 @@ mod tests {
             path: PathBuf::from("/remote"),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: None,
         };
@@ -1484,6 +2681,7 @@ mod tests {
             path: PathBuf::from("/remote/linked"),
             is_dir: false,
             is_symlink: true,
+            symlink_target: None,
             size: 0,
             modified: None,
         };
@@ -1492,6 +2690,7 @@ mod tests {
             path: PathBuf::from("/remote/file.txt"),
             is_dir: false,
             is_symlink: false,
+            symlink_target: None,
             size: 1,
             modified: None,
         };
@@ -1946,6 +3145,27 @@ mod tests {
         assert!(error.to_string().contains("symbolic link"));
     }
 
+    // === sha256sum output parsing ===
+
+    #[test]
+    fn parse_sha256sum_output_reads_digest_and_escaped_lines() {
+        // sha256 of "abc"
+        let hex = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        let expected: [u8; 32] = {
+            use sha2::{Digest, Sha256};
+            Sha256::digest(b"abc").into()
+        };
+
+        let plain = format!("{hex}  /srv/data/file.txt\n");
+        assert_eq!(parse_sha256sum_output(plain.as_bytes()), Some(expected));
+
+        let escaped = format!("\\{hex}  /srv/data/line\\nbreak\n");
+        assert_eq!(parse_sha256sum_output(escaped.as_bytes()), Some(expected));
+
+        assert_eq!(parse_sha256sum_output(b"sha256sum: not found\n"), None);
+        assert_eq!(parse_sha256sum_output(b""), None);
+    }
+
     // === Error message formatting patterns ===
 
     #[test]
@@ -0,0 +1,250 @@
+//! Privilege-elevated file operations
+//!
+//! When a direct SFTP call is refused with "permission denied", the same
+//! operation can be retried through `sudo` on an exec channel of the SSH
+//! connection that backs the SFTP session. The sudo password is written to
+//! sudo's stdin (`sudo -S`) and never appears on the remote command line.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::SftpError;
+
+/// Maximum amount of stderr kept from a failed sudo command.
+pub(crate) const SUDO_STDERR_LIMIT: usize = 16 * 1024;
+
+/// A file operation that can be run through `sudo`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SudoCommand {
+    /// Create a directory
+    CreateDir(PathBuf),
+    /// Create an empty file, failing if the path already exists
+    CreateFile(PathBuf),
+    /// Rename (move) a path without replacing an existing destination
+    Rename { from: PathBuf, to: PathBuf },
+    /// Remove a file, or a directory tree when `recursive` is set
+    Remove { path: PathBuf, recursive: bool },
+    /// Change permission bits (chmod)
+    SetPermissions { path: PathBuf, mode: u32 },
+    /// Change ownership (chown), of a whole directory tree when `recursive`
+    /// is set; `owner` is `user`, `user:group`, or `:group`
+    SetOwner {
+        path: PathBuf,
+        owner: String,
+        recursive: bool,
+    },
+    /// Copy a staged file over the destination (keeps the ownership and mode
+    /// of an existing destination)
+    CopyFile { from: PathBuf, to: PathBuf },
+}
+
+impl SudoCommand {
+    /// Shell command line executed on the remote host.
+    pub fn command_line(&self) -> String {
+        let program = match self {
+            SudoCommand::CreateDir(path) => format!("mkdir -- {}", quote_path(path)),
+            // noclobber makes the redirection refuse existing files
+            SudoCommand::CreateFile(path) => {
+                format!("sh -c 'set -C && : > \"$1\"' sh {}", quote_path(path))
+            }
+            SudoCommand::Rename { from, to } => {
+                format!("mv -n -- {} {}", quote_path(from), quote_path(to))
+            }
+            SudoCommand::Remove {
+                path,
+                recursive: false,
+            } => format!("rm -f -- {}", quote_path(path)),
+            SudoCommand::Remove {
+                path,
+                recursive: true,
+            } => format!("rm -rf -- {}", quote_path(path)),
+            SudoCommand::SetPermissions { path, mode } => {
+                format!("chmod -- {:o} {}", mode & 0o7777, quote_path(path))
+            }
+            // -h changes symlinks themselves; -R does not follow them
+            SudoCommand::SetOwner {
+                path,
+                owner,
+                recursive,
+            } => format!(
+                "chown -h{} -- {} {}",
+                if *recursive { " -R" } else { "" },
+                shell_quote(owner),
+                quote_path(path)
+            ),
+            SudoCommand::CopyFile { from, to } => {
+                format!("cp -- {} {}", quote_path(from), quote_path(to))
+            }
+        };
+        format!("LC_ALL=C sudo -S -p '' -- {}", program)
+    }
+
+    /// Short human-readable description, used in errors and the audit log.
+    pub fn describe(&self) -> String {
+        match self {
+            SudoCommand::CreateDir(path) => format!("create directory {}", path.display()),
+            SudoCommand::CreateFile(path) => format!("create file {}", path.display()),
+            SudoCommand::Rename { from, to } => {
+                format!("rename {} to {}", from.display(), to.display())
+            }
+            SudoCommand::Remove { path, .. } => format!("remove {}", path.display()),
+            SudoCommand::SetPermissions { path, mode } => {
+                format!(
+                    "set permissions {:03o} on {}",
+                    mode & 0o7777,
+                    path.display()
+                )
+            }
+            SudoCommand::SetOwner { path, owner, .. } => {
+                format!("set owner {} on {}", owner, path.display())
+            }
+            SudoCommand::CopyFile { to, .. } => format!("write {}", to.display()),
+        }
+    }
+}
+
+/// Quote a string for a POSIX shell using single quotes.
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn quote_path(path: &Path) -> String {
+    shell_quote(&path.to_string_lossy())
+}
+
+/// Whether an SFTP error message reports a permission problem that running
+/// the operation through sudo could get around.
+pub fn is_permission_denied(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("permission denied") || message.contains("operation not permitted")
+}
+
+/// Whether an error message came from a rejected sudo password.
+pub fn is_auth_failure(message: &str) -> bool {
+    message.contains(&SftpError::SudoAuthFailed.to_string())
+}
+
+/// Map a failed sudo invocation to an error.
+pub(crate) fn failure_error(
+    command: &SudoCommand,
+    stderr: &str,
+    exit_status: Option<u32>,
+) -> SftpError {
+    let lower = stderr.to_ascii_lowercase();
+    if lower.contains("incorrect password")
+        || lower.contains("sorry, try again")
+        || lower.contains("no password was provided")
+    {
+        return SftpError::SudoAuthFailed;
+    }
+    if lower.contains("not in the sudoers") || lower.contains("not allowed to run sudo") {
+        return SftpError::FileOperation(
+            "Your account is not allowed to use sudo on this host".to_string(),
+        );
+    }
+
+    let detail = stderr.trim();
+    let detail = if detail.is_empty() {
+        match exit_status {
+            Some(status) => format!("exit status {}", status),
+            None => "command ended without an exit status".to_string(),
+        }
+    } else {
+        detail.to_string()
+    };
+    SftpError::FileOperation(format!(
+        "Failed to {} with sudo: {}",
+        command.describe(),
+        detail
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("$(rm -rf /)"), "'$(rm -rf /)'");
+    }
+
+    #[test]
+    fn command_line_runs_through_sudo_with_quoted_paths() {
+        let command = SudoCommand::Remove {
+            path: PathBuf::from("/etc/my app/conf"),
+            recursive: true,
+        };
+        assert_eq!(
+            command.command_line(),
+            "LC_ALL=C sudo -S -p '' -- rm -rf -- '/etc/my app/conf'"
+        );
+    }
+
+    #[test]
+    fn create_file_refuses_to_truncate_existing_files() {
+        let command = SudoCommand::CreateFile(PathBuf::from("/etc/it's new"));
+        assert_eq!(
+            command.command_line(),
+            r#"LC_ALL=C sudo -S -p '' -- sh -c 'set -C && : > "$1"' sh '/etc/it'\''s new'"#
+        );
+    }
+
+    #[test]
+    fn command_line_formats_mode_as_octal() {
+        let command = SudoCommand::SetPermissions {
+            path: PathBuf::from("/srv/site"),
+            mode: 0o100755,
+        };
+        assert!(command.command_line().ends_with("chmod -- 755 '/srv/site'"));
+    }
+
+    #[test]
+    fn set_owner_can_recurse_without_following_symlinks() {
+        let command = SudoCommand::SetOwner {
+            path: PathBuf::from("/srv/site"),
+            owner: "www-data:www-data".to_string(),
+            recursive: true,
+        };
+        assert!(
+            command
+                .command_line()
+                .ends_with("chown -h -R -- 'www-data:www-data' '/srv/site'")
+        );
+    }
+
+    #[test]
+    fn permission_denied_detection_is_case_insensitive() {
+        assert!(is_permission_denied(
+            "File operation failed: Failed to create directory /etc/x: Permission denied"
+        ));
+        assert!(is_permission_denied("Operation not permitted"));
+        assert!(!is_permission_denied("No such file"));
+    }
+
+    #[test]
+    fn failure_error_detects_wrong_password() {
+        let command = SudoCommand::CreateDir(PathBuf::from("/opt/x"));
+        let error = failure_error(
+            &command,
+            "Sorry, try again.\nsudo: no password was provided\n",
+            Some(1),
+        );
+        assert!(matches!(error, SftpError::SudoAuthFailed));
+        assert!(is_auth_failure(&error.to_string()));
+    }
+
+    #[test]
+    fn failure_error_reports_command_stderr() {
+        let command = SudoCommand::CreateDir(PathBuf::from("/opt/x"));
+        let error = failure_error(
+            &command,
+            "mkdir: cannot create directory: File exists\n",
+            Some(1),
+        );
+        let message = error.to_string();
+        assert!(message.contains("create directory /opt/x"));
+        assert!(message.contains("File exists"));
+        assert!(!is_auth_failure(&message));
+    }
+}
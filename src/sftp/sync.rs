@@ -0,0 +1,505 @@
+//! One-way directory synchronisation
+//!
+//! Compares a source and a target directory tree and works out which files
+//! have to be copied (and, optionally, which target entries have to be
+//! removed) so the target mirrors the source. Only planning happens here;
+//! the transfers themselves run through the regular SFTP copy path.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::session::SftpSession;
+use super::types::FileEntry;
+
+/// Modification times closer than this are treated as equal. Covers
+/// filesystems with coarse timestamps (FAT, some SMB mounts).
+const MTIME_TOLERANCE_SECS: i64 = 2;
+
+/// Upper bound on entries walked per side, so syncing `/` by accident does
+/// not scan a whole server.
+pub const MAX_SYNC_ENTRIES: usize = 50_000;
+
+/// How a sync compares and cleans up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SyncOptions {
+    /// Remove target entries that do not exist in the source
+    pub delete_extraneous: bool,
+    /// Compare file contents instead of modification times
+    pub compare_checksum: bool,
+}
+
+/// Why a file is going to be copied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncReason {
+    /// Not present in the target
+    Missing,
+    /// Sizes differ
+    SizeChanged,
+    /// Source modified after the target copy
+    Newer,
+    /// Same size but different checksum
+    ContentChanged,
+}
+
+impl SyncReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            SyncReason::Missing => "new",
+            SyncReason::SizeChanged => "size differs",
+            SyncReason::Newer => "newer",
+            SyncReason::ContentChanged => "content differs",
+        }
+    }
+}
+
+/// One entry of a sync plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncItem {
+    /// Path relative to the synced directories
+    pub relative_path: PathBuf,
+    /// Absolute path on the side the item is read from (copies) or
+    /// removed from (deletions)
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+/// Result of comparing two directory trees
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SyncPlan {
+    /// Entries to copy from source to target. Missing directories are
+    /// copied as a whole and their contents are not listed separately.
+    pub copies: Vec<(SyncItem, SyncReason)>,
+    /// Target entries to remove (only with `delete_extraneous`)
+    pub deletions: Vec<SyncItem>,
+    /// Same-size file pairs whose contents still need comparing (only with
+    /// `compare_checksum`): `(source, target)`
+    pub to_verify: Vec<(SyncItem, SyncItem)>,
+    /// Paths that are a file on one side and a directory on the other
+    pub conflicts: Vec<PathBuf>,
+    /// Source symlinks, which the copy path refuses to follow
+    pub skipped_symlinks: usize,
+}
+
+impl SyncPlan {
+    /// Nothing would change
+    pub fn is_empty(&self) -> bool {
+        self.copies.is_empty() && self.deletions.is_empty() && self.to_verify.is_empty()
+    }
+
+    /// Bytes that would be copied (directory copies count as zero)
+    pub fn copy_bytes(&self) -> u64 {
+        self.copies
+            .iter()
+            .filter(|(item, _)| !item.is_dir)
+            .map(|(item, _)| item.size)
+            .sum()
+    }
+
+    /// Apply checksum results: pairs whose contents differ become copies,
+    /// the rest are dropped.
+    pub fn resolve_verified(&mut self, changed: &HashSet<PathBuf>) {
+        for (source, _) in std::mem::take(&mut self.to_verify) {
+            if changed.contains(&source.relative_path) {
+                self.copies.push((source, SyncReason::ContentChanged));
+            }
+        }
+        self.copies
+            .sort_by(|(a, _), (b, _)| a.relative_path.cmp(&b.relative_path));
+    }
+}
+
+/// Compare two flattened directory trees and plan a one-way sync from
+/// `source_root` to `target_root`.
+///
+/// Both trees hold absolute paths below their root, as produced by
+/// [`collect_local_tree`] and [`collect_remote_tree`]. Copies do not keep
+/// modification times, so a file only counts as changed when the source is
+/// newer than the target, not merely different.
+pub fn diff_trees(
+    source_root: &Path,
+    source: &[FileEntry],
+    target_root: &Path,
+    target: &[FileEntry],
+    options: SyncOptions,
+) -> SyncPlan {
+    let source_index = index_tree(source_root, source);
+    let target_index = index_tree(target_root, target);
+    let mut plan = SyncPlan::default();
+
+    let mut source_paths: Vec<&PathBuf> = source_index.keys().collect();
+    source_paths.sort();
+
+    let mut copied_dirs: Vec<&Path> = Vec::new();
+    let mut conflict_dirs: Vec<&Path> = Vec::new();
+    for relative in source_paths {
+        if is_below_any(relative, &copied_dirs) || is_below_any(relative, &conflict_dirs) {
+            continue;
+        }
+        let entry = source_index[relative];
+        if entry.is_symlink {
+            plan.skipped_symlinks += 1;
+            continue;
+        }
+        let item = sync_item(relative, entry);
+
+        let Some(existing) = target_index.get(relative) else {
+            if entry.is_dir {
+                copied_dirs.push(relative);
+            }
+            plan.copies.push((item, SyncReason::Missing));
+            continue;
+        };
+
+        if entry.is_dir != existing.is_dir || existing.is_symlink {
+            plan.conflicts.push(relative.clone());
+            if entry.is_dir {
+                conflict_dirs.push(relative);
+            }
+            continue;
+        }
+        if entry.is_dir {
+            continue;
+        }
+
+        if entry.size != existing.size {
+            plan.copies.push((item, SyncReason::SizeChanged));
+        } else if options.compare_checksum {
+            plan.to_verify.push((item, sync_item(relative, existing)));
+        } else if is_newer(entry, existing) {
+            plan.copies.push((item, SyncReason::Newer));
+        }
+    }
+
+    if options.delete_extraneous {
+        let mut target_paths: Vec<&PathBuf> = target_index.keys().collect();
+        target_paths.sort();
+
+        let mut deleted_dirs: Vec<&Path> = Vec::new();
+        for relative in target_paths {
+            if source_index.contains_key(relative) || is_below_any(relative, &deleted_dirs) {
+                continue;
+            }
+            let entry = target_index[relative];
+            if entry.is_dir && !entry.is_symlink {
+                deleted_dirs.push(relative);
+            }
+            plan.deletions.push(sync_item(relative, entry));
+        }
+    }
+
+    plan
+}
+
+fn index_tree<'a>(root: &Path, entries: &'a [FileEntry]) -> HashMap<PathBuf, &'a FileEntry> {
+    entries
+        .iter()
+        .filter(|entry| !entry.is_parent())
+        .filter_map(|entry| {
+            let relative = entry.path.strip_prefix(root).ok()?;
+            (!relative.as_os_str().is_empty()).then(|| (relative.to_path_buf(), entry))
+        })
+        .collect()
+}
+
+fn sync_item(relative: &Path, entry: &FileEntry) -> SyncItem {
+    SyncItem {
+        relative_path: relative.to_path_buf(),
+        path: entry.path.clone(),
+        is_dir: entry.is_dir,
+        size: entry.size,
+    }
+}
+
+fn is_below_any(path: &Path, dirs: &[&Path]) -> bool {
+    dirs.iter().any(|dir| path != *dir && path.starts_with(dir))
+}
+
+fn is_newer(source: &FileEntry, target: &FileEntry) -> bool {
+    match (source.modified, target.modified) {
+        (Some(source), Some(target)) => {
+            source.timestamp() - target.timestamp() > MTIME_TOLERANCE_SECS
+        }
+        // Without timestamps there is nothing to go on but the size.
+        _ => false,
+    }
+}
+
+/// Recursively list a local directory. Symlinks are listed but not followed.
+pub async fn collect_local_tree(root: &Path) -> Result<Vec<FileEntry>, String> {
+    let mut entries = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in crate::local_fs::list_local_dir(&dir).await? {
+            if entry.is_parent() {
+                continue;
+            }
+            if entry.is_navigable_dir() {
+                pending.push(entry.path.clone());
+            }
+            entries.push(entry);
+            if entries.len() > MAX_SYNC_ENTRIES {
+                return Err(too_many_entries(root));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Recursively list a remote directory. Symlinks are listed but not followed.
+pub async fn collect_remote_tree(
+    sftp: &SftpSession,
+    root: &Path,
+) -> Result<Vec<FileEntry>, String> {
+    let mut entries = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in sftp.list_dir(&dir).await.map_err(|e| e.to_string())? {
+            if entry.is_parent() {
+                continue;
+            }
+            if entry.is_navigable_dir() {
+                pending.push(entry.path.clone());
+            }
+            entries.push(entry);
+            if entries.len() > MAX_SYNC_ENTRIES {
+                return Err(too_many_entries(root));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+fn too_many_entries(root: &Path) -> String {
+    format!(
+        "{} holds more than {} entries; pick a smaller directory to sync",
+        root.display(),
+        MAX_SYNC_ENTRIES
+    )
+}
+
+/// SHA-256 of a local file
+pub async fn local_sha256(path: &Path) -> Result<[u8; 32], String> {
+    use sha2::{Digest, Sha256};
+    use tokio::io::AsyncReadExt;
+
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn file(root: &str, relative: &str, size: u64, mtime: i64) -> FileEntry {
+        let path = Path::new(root).join(relative);
+        FileEntry {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            path,
+            is_dir: false,
+            is_symlink: false,
+            symlink_target: None,
+            size,
+            modified: Utc.timestamp_opt(mtime, 0).single(),
+        }
+    }
+
+    fn dir(root: &str, relative: &str) -> FileEntry {
+        FileEntry {
+            is_dir: true,
+            size: 0,
+            ..file(root, relative, 0, 0)
+        }
+    }
+
+    fn copied(plan: &SyncPlan) -> Vec<(&str, SyncReason)> {
+        plan.copies
+            .iter()
+            .map(|(item, reason)| (item.relative_path.to_str().unwrap(), *reason))
+            .collect()
+    }
+
+    #[test]
+    fn copies_missing_changed_and_newer_files() {
+        let source = vec![
+            file("/src", "same.txt", 10, 1_000),
+            file("/src", "grown.txt", 20, 1_000),
+            file("/src", "touched.txt", 10, 5_000),
+            file("/src", "new.txt", 3, 1_000),
+        ];
+        let target = vec![
+            file("/dst", "same.txt", 10, 1_001),
+            file("/dst", "grown.txt", 10, 9_000),
+            file("/dst", "touched.txt", 10, 1_000),
+        ];
+
+        let plan = diff_trees(
+            Path::new("/src"),
+            &source,
+            Path::new("/dst"),
+            &target,
+            SyncOptions::default(),
+        );
+
+        assert_eq!(
+            copied(&plan),
+            vec![
+                ("grown.txt", SyncReason::SizeChanged),
+                ("new.txt", SyncReason::Missing),
+                ("touched.txt", SyncReason::Newer),
+            ]
+        );
+        assert!(plan.deletions.is_empty());
+        assert_eq!(plan.copy_bytes(), 33);
+    }
+
+    #[test]
+    fn older_source_is_not_copied_back() {
+        let source = vec![file("/src", "a.txt", 10, 1_000)];
+        let target = vec![file("/dst", "a.txt", 10, 2_000)];
+
+        let plan = diff_trees(
+            Path::new("/src"),
+            &source,
+            Path::new("/dst"),
+            &target,
+            SyncOptions::default(),
+        );
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn missing_directory_is_copied_whole() {
+        let source = vec![
+            dir("/src", "assets"),
+            file("/src", "assets/logo.png", 100, 1_000),
+            dir("/src", "assets/icons"),
+            file("/src", "assets/icons/a.svg", 5, 1_000),
+        ];
+
+        let plan = diff_trees(
+            Path::new("/src"),
+            &source,
+            Path::new("/dst"),
+            &[],
+            SyncOptions::default(),
+        );
+
+        assert_eq!(copied(&plan), vec![("assets", SyncReason::Missing)]);
+        assert!(plan.copies[0].0.is_dir);
+    }
+
+    #[test]
+    fn deletes_only_topmost_extraneous_entries() {
+        let source = vec![dir("/src", "keep"), file("/src", "keep/a.txt", 1, 1_000)];
+        let target = vec![
+            dir("/dst", "keep"),
+            file("/dst", "keep/a.txt", 1, 1_000),
+            file("/dst", "keep/stale.txt", 1, 1_000),
+            dir("/dst", "old"),
+            file("/dst", "old/b.txt", 1, 1_000),
+        ];
+
+        let options = SyncOptions {
+            delete_extraneous: true,
+            ..Default::default()
+        };
+        let plan = diff_trees(
+            Path::new("/src"),
+            &source,
+            Path::new("/dst"),
+            &target,
+            options,
+        );
+
+        let deleted: Vec<_> = plan
+            .deletions
+            .iter()
+            .map(|item| item.relative_path.to_str().unwrap())
+            .collect();
+        assert_eq!(deleted, vec!["keep/stale.txt", "old"]);
+        assert_eq!(plan.deletions[1].path, PathBuf::from("/dst/old"));
+
+        let plan = diff_trees(
+            Path::new("/src"),
+            &source,
+            Path::new("/dst"),
+            &target,
+            SyncOptions::default(),
+        );
+        assert!(plan.deletions.is_empty());
+    }
+
+    #[test]
+    fn checksum_mode_defers_same_size_files() {
+        let source = vec![
+            file("/src", "a.txt", 10, 5_000),
+            file("/src", "b.txt", 10, 1_000),
+        ];
+        let target = vec![
+            file("/dst", "a.txt", 10, 1_000),
+            file("/dst", "b.txt", 10, 1_000),
+        ];
+
+        let options = SyncOptions {
+            compare_checksum: true,
+            ..Default::default()
+        };
+        let mut plan = diff_trees(
+            Path::new("/src"),
+            &source,
+            Path::new("/dst"),
+            &target,
+            options,
+        );
+        assert!(plan.copies.is_empty());
+        assert_eq!(plan.to_verify.len(), 2);
+
+        plan.resolve_verified(&HashSet::from([PathBuf::from("b.txt")]));
+        assert!(plan.to_verify.is_empty());
+        assert_eq!(copied(&plan), vec![("b.txt", SyncReason::ContentChanged)]);
+    }
+
+    #[test]
+    fn type_conflicts_and_symlinks_are_skipped() {
+        let mut link = file("/src", "link", 0, 1_000);
+        link.is_symlink = true;
+        let source = vec![
+            dir("/src", "data"),
+            file("/src", "data/x.txt", 1, 1_000),
+            file("/src", "config", 1, 1_000),
+            link,
+        ];
+        let target = vec![file("/dst", "data", 1, 1_000), dir("/dst", "config")];
+
+        let plan = diff_trees(
+            Path::new("/src"),
+            &source,
+            Path::new("/dst"),
+            &target,
+            SyncOptions::default(),
+        );
+
+        assert!(plan.copies.is_empty());
+        assert_eq!(
+            plan.conflicts,
+            vec![PathBuf::from("config"), PathBuf::from("data")]
+        );
+        assert_eq!(plan.skipped_symlinks, 1);
+    }
+}
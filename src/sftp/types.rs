@@ -28,10 +28,21 @@ pub struct FileEntry {
     pub path: PathBuf,
     pub is_dir: bool,
     pub is_symlink: bool,
+    /// Where a symlink points, when it could be read
+    pub symlink_target: Option<SymlinkTarget>,
     pub size: u64,
     pub modified: Option<DateTime<Utc>>,
 }
 
+/// Target of a symbolic link as read from the file system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymlinkTarget {
+    /// Link text exactly as stored, which may be relative to the link
+    pub target: String,
+    /// Fully resolved target path, or None when the link is broken
+    pub resolved: Option<PathBuf>,
+}
+
 /// Return true when `name` is a single safe SFTP directory-entry name.
 pub fn is_safe_sftp_entry_name(name: &str) -> bool {
     !name.is_empty()
@@ -54,6 +65,39 @@ impl FileEntry {
         self.is_dir && !self.is_symlink
     }
 
+    /// Directory to open when this entry is activated in the browser.
+    ///
+    /// Symlinked directories open at their resolved target so listings never
+    /// read through a link.
+    pub fn navigation_path(&self) -> Option<&Path> {
+        if self.is_navigable_dir() {
+            return Some(&self.path);
+        }
+        if !self.is_symlink || !self.is_dir {
+            return None;
+        }
+        self.symlink_target
+            .as_ref()
+            .and_then(|target| target.resolved.as_deref())
+    }
+
+    /// Check if this is a symlink whose target does not exist.
+    pub fn is_broken_symlink(&self) -> bool {
+        self.is_symlink
+            && self
+                .symlink_target
+                .as_ref()
+                .is_some_and(|target| target.resolved.is_none())
+    }
+
+    /// Text for the Kind column, showing "→ target" for readable symlinks
+    pub fn kind_label(&self) -> String {
+        match &self.symlink_target {
+            Some(target) if self.is_symlink => format!("→ {}", target.target),
+            _ => self.kind_description().to_string(),
+        }
+    }
+
     /// Get file extension if any
     pub fn extension(&self) -> Option<&str> {
         if self.is_dir {
@@ -362,6 +406,7 @@ mod tests {
             path: PathBuf::from(format!("/test/{}", name)),
             is_dir: false,
             is_symlink: false,
+            symlink_target: None,
             size: 1024,
             modified: None,
         }
@@ -373,6 +418,7 @@ mod tests {
             path: PathBuf::from(format!("/test/{}", name)),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: None,
         }
@@ -384,6 +430,7 @@ mod tests {
             path: PathBuf::from(format!("/test/{}", name)),
             is_dir: false,
             is_symlink: true,
+            symlink_target: None,
             size: 0,
             modified: None,
         }
@@ -395,6 +442,7 @@ mod tests {
             path: PathBuf::from(format!("/test/{}", name)),
             is_dir: true,
             is_symlink: true,
+            symlink_target: None,
             size: 0,
             modified: None,
         }
@@ -434,6 +482,7 @@ mod tests {
             path: PathBuf::from("/"),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: None,
         };
@@ -461,6 +510,7 @@ mod tests {
                 path: PathBuf::from("/"),
                 is_dir: true,
                 is_symlink: false,
+                symlink_target: None,
                 size: 0,
                 modified: None,
             }
@@ -474,6 +524,41 @@ mod tests {
         assert!(!make_symlink_dir("linked-folder").is_navigable_dir());
     }
 
+    #[test]
+    fn navigation_path_follows_resolved_symlinked_directories_only() {
+        let mut link = make_symlink_dir("linked-folder");
+        assert_eq!(link.navigation_path(), None);
+
+        link.symlink_target = Some(SymlinkTarget {
+            target: "../real".to_string(),
+            resolved: Some(PathBuf::from("/real")),
+        });
+        assert_eq!(link.navigation_path(), Some(Path::new("/real")));
+
+        let mut file_link = make_symlink("link");
+        file_link.symlink_target = link.symlink_target.clone();
+        assert_eq!(file_link.navigation_path(), None);
+        assert_eq!(
+            make_dir("subdir").navigation_path(),
+            Some(Path::new("/test/subdir"))
+        );
+    }
+
+    #[test]
+    fn kind_label_shows_symlink_target_and_broken_state() {
+        let mut link = make_symlink("link");
+        assert_eq!(link.kind_label(), "Symbolic Link");
+        assert!(!link.is_broken_symlink());
+
+        link.symlink_target = Some(SymlinkTarget {
+            target: "missing.txt".to_string(),
+            resolved: None,
+        });
+        assert_eq!(link.kind_label(), "→ missing.txt");
+        assert!(link.is_broken_symlink());
+        assert_eq!(make_file("main.rs").kind_label(), "Rust Source");
+    }
+
     // === FileEntry::extension tests ===
 
     #[test]
@@ -516,6 +601,7 @@ mod tests {
             path: PathBuf::from("/"),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: None,
         };
@@ -633,6 +719,7 @@ mod tests {
             path: PathBuf::from("/"),
             is_dir: true,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: None,
         };
@@ -690,6 +777,7 @@ mod tests {
             path: PathBuf::from("/test.txt"),
             is_dir: false,
             is_symlink: false,
+            symlink_target: None,
             size: 100,
             modified: Some(Utc.with_ymd_and_hms(2024, 6, 15, 14, 30, 0).unwrap()),
         };
@@ -713,6 +801,7 @@ mod tests {
                 path: PathBuf::from("/"),
                 is_dir: true,
                 is_symlink: false,
+                symlink_target: None,
                 size: 0,
                 modified: None,
             },
@@ -766,6 +855,7 @@ mod tests {
                 path: PathBuf::from("/"),
                 is_dir: true,
                 is_symlink: false,
+                symlink_target: None,
                 size: 0,
                 modified: None,
             },
@@ -794,6 +884,7 @@ mod tests {
                 path: PathBuf::from("/"),
                 is_dir: true,
                 is_symlink: false,
+                symlink_target: None,
                 size: 0,
                 modified: None,
             },
@@ -905,6 +996,7 @@ mod tests {
             path: PathBuf::from("/test/test.txt"),
             is_dir: false,
             is_symlink: false,
+            symlink_target: None,
             size: 1024,
             modified: Some(Utc::now()),
         };
@@ -1,10 +1,11 @@
 use std::path::Path;
 use std::sync::Arc;
 
+use russh::keys::agent::client::AgentClient;
 use russh::keys::{HashAlg, PrivateKeyWithHashAlg};
 use secrecy::{ExposeSecret, SecretString};
 
-use crate::config::{AuthMethod, paths};
+use crate::config::{AuthMethod, Host, paths};
 use crate::error::SshError;
 
 /// Resolved authentication for an SSH connection
@@ -81,6 +82,76 @@ impl ResolvedAuth {
     }
 }
 
+/// A resolved method paired with a display label for it
+#[derive(Debug)]
+pub struct AuthCandidate {
+    pub auth: ResolvedAuth,
+    /// e.g. "agent" or "publickey (id_ed25519)"
+    pub label: String,
+}
+
+impl AuthCandidate {
+    pub async fn resolve(
+        method: &AuthMethod,
+        password: Option<SecretString>,
+        passphrase: Option<SecretString>,
+    ) -> Result<Self, SshError> {
+        Ok(Self {
+            auth: ResolvedAuth::resolve(method, password, passphrase).await?,
+            label: method_label(method),
+        })
+    }
+}
+
+/// Resolve a host's auth method followed by its configured fallbacks.
+///
+/// The primary method must resolve, so passphrase prompts and missing-key
+/// errors surface as before. Fallbacks that cannot be resolved (a password
+/// fallback without a collected password, an unreadable key) are skipped.
+pub async fn resolve_auth_sequence(
+    host: &Host,
+    password: Option<SecretString>,
+    passphrase: Option<SecretString>,
+) -> Result<Vec<AuthCandidate>, SshError> {
+    let mut candidates =
+        vec![AuthCandidate::resolve(&host.auth, password.clone(), passphrase.clone()).await?];
+    for method in &host.auth_fallbacks {
+        match AuthCandidate::resolve(method, password.clone(), passphrase.clone()).await {
+            Ok(candidate) => candidates.push(candidate),
+            Err(e) => tracing::debug!(
+                "Skipping fallback auth {} for {}: {}",
+                method_label(method),
+                host.hostname,
+                e
+            ),
+        }
+    }
+    Ok(candidates)
+}
+
+/// Short display name for a configured method, naming the key file when set
+pub fn method_label(method: &AuthMethod) -> String {
+    match method {
+        AuthMethod::Password => "password".to_string(),
+        AuthMethod::PublicKey {
+            vault_key_id: Some(_),
+            ..
+        } => "publickey (vault key)".to_string(),
+        AuthMethod::PublicKey {
+            key_path: Some(path),
+            ..
+        } => format!(
+            "publickey ({})",
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string())
+        ),
+        AuthMethod::PublicKey { .. } => "publickey".to_string(),
+        AuthMethod::Agent => "agent".to_string(),
+        AuthMethod::KeyboardInteractive => "keyboard-interactive".to_string(),
+    }
+}
+
 /// Find the first available default SSH key
 fn find_default_key() -> Option<std::path::PathBuf> {
     paths::default_identity_files()
@@ -206,6 +277,87 @@ fn public_key_auth(key: russh::keys::PrivateKey) -> ResolvedAuth {
     ResolvedAuth::PublicKey(PrivateKeyWithHashAlg::new(Arc::new(key), hash_alg))
 }
 
+/// A key offered by the running SSH agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentIdentity {
+    /// SHA256 fingerprint, e.g. "SHA256:..."
+    pub fingerprint: String,
+    pub algorithm: String,
+    pub comment: String,
+}
+
+impl std::fmt::Display for AgentIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = if self.comment.is_empty() {
+            &self.algorithm
+        } else {
+            &self.comment
+        };
+        let short = self
+            .fingerprint
+            .rsplit_once(':')
+            .map(|(_, value)| value)
+            .unwrap_or(&self.fingerprint);
+        write!(
+            f,
+            "{} ({})",
+            name,
+            short.chars().take(12).collect::<String>()
+        )
+    }
+}
+
+/// Connect to the agent listening on `SSH_AUTH_SOCK`
+pub async fn connect_agent() -> Result<AgentClient<tokio::net::UnixStream>, SshError> {
+    let agent_path = std::env::var("SSH_AUTH_SOCK").map_err(|_| {
+        SshError::Agent("SSH_AUTH_SOCK not set - is ssh-agent running?".to_string())
+    })?;
+
+    let stream = tokio::net::UnixStream::connect(&agent_path)
+        .await
+        .map_err(|e| {
+            SshError::Agent(format!(
+                "Failed to connect to SSH agent at {}: {}",
+                agent_path, e
+            ))
+        })?;
+
+    Ok(AgentClient::connect(stream))
+}
+
+/// List the keys the running SSH agent offers
+pub async fn list_agent_identities() -> Result<Vec<AgentIdentity>, SshError> {
+    let mut agent = connect_agent().await?;
+    let identities = agent
+        .request_identities()
+        .await
+        .map_err(|e| SshError::Agent(format!("Failed to get identities: {}", e)))?;
+
+    Ok(identities
+        .iter()
+        .map(|identity| {
+            let key = identity.public_key();
+            AgentIdentity {
+                fingerprint: key.fingerprint(HashAlg::Sha256).to_string(),
+                algorithm: key.algorithm().as_str().to_string(),
+                comment: identity.comment().to_string(),
+            }
+        })
+        .collect())
+}
+
+/// Move the identity whose fingerprint is `preferred` to the front, keeping
+/// the agent's order otherwise
+pub fn prefer_identity<T>(
+    identities: &mut [T],
+    preferred: Option<&str>,
+    fingerprint: impl Fn(&T) -> String,
+) {
+    if let Some(preferred) = preferred {
+        identities.sort_by_key(|identity| fingerprint(identity) != preferred);
+    }
+}
+
 fn is_public_key_line(line: &str) -> bool {
     let algorithm = line.split_whitespace().next().unwrap_or_default();
     algorithm.starts_with("ssh-")
@@ -221,6 +373,33 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn preferred_agent_identity_is_offered_first() {
+        let mut identities = vec!["SHA256:aaa", "SHA256:bbb", "SHA256:ccc"];
+
+        prefer_identity(&mut identities, Some("SHA256:ccc"), |id| id.to_string());
+        assert_eq!(identities, vec!["SHA256:ccc", "SHA256:aaa", "SHA256:bbb"]);
+
+        prefer_identity(&mut identities, Some("SHA256:missing"), |id| id.to_string());
+        assert_eq!(identities, vec!["SHA256:ccc", "SHA256:aaa", "SHA256:bbb"]);
+    }
+
+    #[test]
+    fn agent_identity_label_uses_comment_and_short_fingerprint() {
+        let identity = AgentIdentity {
+            fingerprint: "SHA256:abcdefghijklmnopqrstuvwxyz".to_string(),
+            algorithm: "ssh-ed25519".to_string(),
+            comment: "work laptop".to_string(),
+        };
+        assert_eq!(identity.to_string(), "work laptop (abcdefghijkl)");
+
+        let unnamed = AgentIdentity {
+            comment: String::new(),
+            ..identity
+        };
+        assert_eq!(unnamed.to_string(), "ssh-ed25519 (abcdefghijkl)");
+    }
+
     /// Test password authentication with password provided
     #[tokio::test]
     async fn resolve_password_auth_with_password() {
@@ -267,6 +446,17 @@ mod tests {
         assert!(matches!(result.unwrap(), ResolvedAuth::KeyboardInteractive));
     }
 
+    #[test]
+    fn method_label_names_the_key_file() {
+        let key = AuthMethod::PublicKey {
+            key_path: Some(std::path::PathBuf::from("~/.ssh/id_work")),
+            vault_key_id: None,
+        };
+        assert_eq!(method_label(&key), "publickey (id_work)");
+        assert_eq!(method_label(&AuthMethod::Agent), "agent");
+        assert_eq!(method_label(&AuthMethod::Password), "password");
+    }
+
     #[test]
     fn detects_public_key_files_by_algorithm_prefix() {
         assert!(is_public_key_line("ssh-ed25519 AAAA comment"));
@@ -1,12 +1,13 @@
 //! SSH authentication with keyboard-interactive support and a fallback chain.
 //!
-//! The configured method is tried first; when the server rejects it (or the
-//! method is unavailable), the remaining applicable methods are tried in
-//! order: publickey/agent (if configured) -> keyboard-interactive -> password
-//! (only when a password was already collected). The server-advertised
+//! The configured method is tried first, then the host's configured
+//! fallbacks in order (each public key separately). When all of those are
+//! rejected or unavailable, keyboard-interactive and finally password (only
+//! when a password was already collected) are tried. The server-advertised
 //! `remaining_methods` list from auth failures is honored when deciding the
 //! next method.
 
+use std::collections::VecDeque;
 use std::time::Duration;
 
 use russh::client::{AuthResult, Handle, Handler, KeyboardInteractiveAuthResponse};
@@ -19,13 +20,17 @@ use crate::error::SshError;
 use crate::security_log;
 
 use super::SshEvent;
-use super::auth::ResolvedAuth;
+use super::auth::{AuthCandidate, ResolvedAuth, connect_agent, prefer_identity};
 use super::auth_prompt::{AuthPrompt, AuthPromptRequest, AuthPromptResponse};
 
 /// How long to wait for the user to answer a keyboard-interactive prompt
 /// round (consistent with the host key verification dialog).
 const AUTH_PROMPT_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// Upper bound on keyboard-interactive rounds, so a server that keeps sending
+/// info requests cannot hold the connection attempt open forever.
+const MAX_KBD_INTERACTIVE_ROUNDS: usize = 16;
+
 /// Authentication methods Portal can attempt, in fallback order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AuthKind {
@@ -63,17 +68,33 @@ impl AuthKind {
 /// available). Keyboard-interactive is always appended as a fallback because
 /// its prompts come from the server at auth time.
 pub fn auth_fallback_chain(primary: AuthKind, has_password: bool) -> Vec<AuthKind> {
-    let mut chain = Vec::with_capacity(3);
-    if primary != AuthKind::Password || has_password {
-        chain.push(primary);
+    auth_plan(&[primary], has_password)
+}
+
+/// Build the ordered list of methods to attempt from a configured sequence
+/// (the primary method followed by the host's fallbacks).
+///
+/// Every public key keeps its own entry so several keys can be tried in
+/// turn; other methods are attempted once. Keyboard-interactive and password
+/// are appended as in [`auth_fallback_chain`].
+pub fn auth_plan(configured: &[AuthKind], has_password: bool) -> Vec<AuthKind> {
+    let mut plan = Vec::with_capacity(configured.len() + 2);
+    for &kind in configured {
+        if kind == AuthKind::Password && !has_password {
+            continue;
+        }
+        if kind != AuthKind::PublicKey && plan.contains(&kind) {
+            continue;
+        }
+        plan.push(kind);
     }
-    if !chain.contains(&AuthKind::KeyboardInteractive) {
-        chain.push(AuthKind::KeyboardInteractive);
+    if !plan.contains(&AuthKind::KeyboardInteractive) {
+        plan.push(AuthKind::KeyboardInteractive);
     }
-    if has_password && !chain.contains(&AuthKind::Password) {
-        chain.push(AuthKind::Password);
+    if has_password && !plan.contains(&AuthKind::Password) {
+        plan.push(AuthKind::Password);
     }
-    chain
+    plan
 }
 
 /// Decide the next method to attempt.
@@ -85,15 +106,27 @@ pub fn next_auth_method(
     attempted: &[AuthKind],
     remaining: Option<&MethodSet>,
 ) -> Option<AuthKind> {
-    chain.iter().copied().find(|kind| {
-        if attempted.contains(kind) {
-            return false;
-        }
-        match remaining {
-            Some(methods) => methods.iter().any(|m| *m == kind.wire_kind()),
-            None => true,
-        }
-    })
+    chain
+        .iter()
+        .copied()
+        .find(|kind| !attempted.contains(kind) && server_allows(*kind, remaining))
+}
+
+/// Like [`next_auth_method`], but tracks attempts by position in `plan` so
+/// repeated entries (several public keys) are each tried once.
+pub fn next_auth_step(
+    plan: &[AuthKind],
+    attempted: &[usize],
+    remaining: Option<&MethodSet>,
+) -> Option<usize> {
+    (0..plan.len()).find(|step| !attempted.contains(step) && server_allows(plan[*step], remaining))
+}
+
+fn server_allows(kind: AuthKind, remaining: Option<&MethodSet>) -> bool {
+    match remaining {
+        Some(methods) => methods.iter().any(|m| *m == kind.wire_kind()),
+        None => true,
+    }
 }
 
 /// Context shared by all auth attempts for one connection.
@@ -101,6 +134,8 @@ pub struct AuthContext<'a> {
     pub hostname: &'a str,
     pub port: u16,
     pub username: &'a str,
+    /// Fingerprint of the agent key to offer first
+    pub agent_identity: Option<&'a str>,
     pub event_tx: &'a mpsc::Sender<SshEvent>,
 }
 
@@ -113,29 +148,54 @@ enum AttemptOutcome {
     Unavailable(String),
 }
 
-/// Authenticate using the resolved primary method with automatic fallback.
+/// Authenticate with the resolved methods in order, then the automatic
+/// fallbacks. Returns the label of the method the server accepted.
 ///
-/// `password` is the pre-collected password (if any) used both for the
-/// primary password attempt and as a fallback after other methods fail.
+/// `candidates` holds the primary method first. A collected password is
+/// used both for password entries and as the final fallback.
 pub async fn authenticate<H: Handler>(
     handle: &mut Handle<H>,
     ctx: AuthContext<'_>,
-    primary: ResolvedAuth,
-) -> Result<(), SshError> {
-    let (primary_kind, mut key, password) = match primary {
-        ResolvedAuth::Password(password) => (AuthKind::Password, None, Some(password)),
-        ResolvedAuth::PublicKey(key) => (AuthKind::PublicKey, Some(key), None),
-        ResolvedAuth::Agent => (AuthKind::Agent, None, None),
-        ResolvedAuth::KeyboardInteractive => (AuthKind::KeyboardInteractive, None, None),
-    };
+    candidates: Vec<AuthCandidate>,
+) -> Result<String, SshError> {
+    let mut configured = Vec::with_capacity(candidates.len());
+    let mut labels: Vec<(AuthKind, String)> = Vec::new();
+    let mut keys = VecDeque::new();
+    let mut password: Option<SecretString> = None;
+    for candidate in candidates {
+        let kind = match candidate.auth {
+            ResolvedAuth::Password(secret) => {
+                password.get_or_insert(secret);
+                AuthKind::Password
+            }
+            ResolvedAuth::PublicKey(key) => {
+                keys.push_back((key, candidate.label));
+                configured.push(AuthKind::PublicKey);
+                continue;
+            }
+            ResolvedAuth::Agent => AuthKind::Agent,
+            ResolvedAuth::KeyboardInteractive => AuthKind::KeyboardInteractive,
+        };
+        configured.push(kind);
+        labels.push((kind, candidate.label));
+    }
 
-    let chain = auth_fallback_chain(primary_kind, password.is_some());
-    let mut attempted: Vec<AuthKind> = Vec::new();
+    let plan = auth_plan(&configured, password.is_some());
+    let mut attempted: Vec<usize> = Vec::new();
     let mut remaining: Option<MethodSet> = None;
     let mut last_reason = String::from("Authentication rejected by server");
-
-    while let Some(kind) = next_auth_method(&chain, &attempted, remaining.as_ref()) {
-        attempted.push(kind);
+    // Why the agent could not be used, kept so a later rejection of the
+    // fallbacks does not hide it
+    let mut agent_error: Option<String> = None;
+
+    while let Some(step) = next_auth_step(&plan, &attempted, remaining.as_ref()) {
+        attempted.push(step);
+        let kind = plan[step];
+        let mut label = labels
+            .iter()
+            .find(|(labelled, _)| *labelled == kind)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_else(|| kind.method_name().to_string());
         security_log::log_auth_attempt(ctx.hostname, ctx.port, ctx.username, kind.method_name());
 
         let outcome = match kind {
@@ -155,9 +215,10 @@ pub async fn authenticate<H: Handler>(
                 }
             }
             AuthKind::PublicKey => {
-                let Some(key) = key.take() else {
+                let Some((key, key_label)) = keys.pop_front() else {
                     continue;
                 };
+                label = key_label;
                 match handle.authenticate_publickey(ctx.username, key).await {
                     Ok(AuthResult::Success) => AttemptOutcome::Success,
                     Ok(AuthResult::Failure {
@@ -166,13 +227,15 @@ pub async fn authenticate<H: Handler>(
                     Err(e) => return Err(SshError::AuthenticationFailed(e.to_string())),
                 }
             }
-            AuthKind::Agent => match authenticate_with_agent(handle, ctx.username).await {
-                Ok(AuthResult::Success) => AttemptOutcome::Success,
-                Ok(AuthResult::Failure {
-                    remaining_methods, ..
-                }) => AttemptOutcome::Rejected(Some(remaining_methods)),
-                Err(e) => AttemptOutcome::Unavailable(e.to_string()),
-            },
+            AuthKind::Agent => {
+                match authenticate_with_agent(handle, ctx.username, ctx.agent_identity).await {
+                    Ok(AuthResult::Success) => AttemptOutcome::Success,
+                    Ok(AuthResult::Failure {
+                        remaining_methods, ..
+                    }) => AttemptOutcome::Rejected(Some(remaining_methods)),
+                    Err(e) => AttemptOutcome::Unavailable(e.to_string()),
+                }
+            }
             AuthKind::KeyboardInteractive => {
                 match authenticate_keyboard_interactive(handle, &ctx).await? {
                     KbdInteractiveOutcome::Success => AttemptOutcome::Success,
@@ -191,7 +254,7 @@ pub async fn authenticate<H: Handler>(
                     ctx.username,
                     kind.method_name(),
                 );
-                return Ok(());
+                return Ok(label);
             }
             AttemptOutcome::Rejected(methods) => {
                 last_reason = format!(
@@ -211,6 +274,9 @@ pub async fn authenticate<H: Handler>(
             }
             AttemptOutcome::Unavailable(reason) => {
                 last_reason = reason.clone();
+                if kind == AuthKind::Agent {
+                    agent_error = Some(reason.clone());
+                }
                 security_log::log_auth_failure(
                     ctx.hostname,
                     ctx.port,
@@ -229,7 +295,21 @@ pub async fn authenticate<H: Handler>(
         }
     }
 
-    Err(SshError::AuthenticationFailed(last_reason))
+    Err(SshError::AuthenticationFailed(auth_failure_reason(
+        last_reason,
+        agent_error,
+    )))
+}
+
+/// Final auth error, naming the agent problem when the agent could not be
+/// used and a later method was rejected
+fn auth_failure_reason(last_reason: String, agent_error: Option<String>) -> String {
+    match agent_error {
+        Some(agent_error) if agent_error != last_reason => {
+            format!("{}; {}", last_reason, agent_error)
+        }
+        _ => last_reason,
+    }
 }
 
 enum KbdInteractiveOutcome {
@@ -241,7 +321,8 @@ enum KbdInteractiveOutcome {
 ///
 /// The server may send multiple rounds of prompts; each round with prompts
 /// opens a dialog and waits (bounded) for the user's responses. Rounds with
-/// no prompts are answered automatically with an empty response list.
+/// no prompts are answered automatically with an empty response list. The
+/// exchange gives up after `MAX_KBD_INTERACTIVE_ROUNDS` rounds.
 async fn authenticate_keyboard_interactive<H: Handler>(
     handle: &mut Handle<H>,
     ctx: &AuthContext<'_>,
@@ -250,6 +331,7 @@ async fn authenticate_keyboard_interactive<H: Handler>(
         .authenticate_keyboard_interactive_start(ctx.username, None)
         .await
         .map_err(|e| SshError::AuthenticationFailed(e.to_string()))?;
+    let mut rounds = 0;
 
     loop {
         match response {
@@ -266,6 +348,12 @@ async fn authenticate_keyboard_interactive<H: Handler>(
                 instructions,
                 prompts,
             } => {
+                rounds += 1;
+                if rounds > MAX_KBD_INTERACTIVE_ROUNDS {
+                    return Err(SshError::AuthenticationFailed(
+                        "Server sent too many keyboard-interactive prompt rounds".to_string(),
+                    ));
+                }
                 let responses: Vec<String> = if prompts.is_empty() {
                     Vec::new()
                 } else {
@@ -336,22 +424,16 @@ async fn request_prompt_responses(
     }
 }
 
-/// Try every identity offered by the local SSH agent.
+/// Try every identity offered by the local SSH agent, the `preferred`
+/// fingerprint first.
 pub async fn authenticate_with_agent<H: Handler>(
     handle: &mut Handle<H>,
     username: &str,
+    preferred: Option<&str>,
 ) -> Result<AuthResult, SshError> {
-    let agent_path = std::env::var("SSH_AUTH_SOCK").map_err(|_| {
-        SshError::Agent("SSH_AUTH_SOCK not set - is ssh-agent running?".to_string())
-    })?;
-
-    let stream = tokio::net::UnixStream::connect(&agent_path)
-        .await
-        .map_err(|e| SshError::Agent(format!("Failed to connect to SSH agent: {}", e)))?;
+    let mut agent = connect_agent().await?;
 
-    let mut agent = russh::keys::agent::client::AgentClient::connect(stream);
-
-    let identities = agent
+    let mut identities = agent
         .request_identities()
         .await
         .map_err(|e| SshError::Agent(format!("Failed to get identities: {}", e)))?;
@@ -362,6 +444,27 @@ pub async fn authenticate_with_agent<H: Handler>(
         ));
     }
 
+    if let Some(preferred) = preferred
+        && !identities.iter().any(|identity| {
+            identity
+                .public_key()
+                .fingerprint(HashAlg::Sha256)
+                .to_string()
+                == preferred
+        })
+    {
+        tracing::warn!(
+            "Preferred agent key {} is not loaded in the agent",
+            preferred
+        );
+    }
+    prefer_identity(&mut identities, preferred, |identity| {
+        identity
+            .public_key()
+            .fingerprint(HashAlg::Sha256)
+            .to_string()
+    });
+
     let mut last_failure: Option<AuthResult> = None;
 
     // Try each identity with SHA-512 for RSA keys
@@ -459,6 +562,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn plan_keeps_configured_order_and_every_key() {
+        let configured = [
+            AuthKind::Agent,
+            AuthKind::PublicKey,
+            AuthKind::PublicKey,
+            AuthKind::Password,
+        ];
+        assert_eq!(
+            auth_plan(&configured, true),
+            vec![
+                AuthKind::Agent,
+                AuthKind::PublicKey,
+                AuthKind::PublicKey,
+                AuthKind::Password,
+                AuthKind::KeyboardInteractive
+            ]
+        );
+        assert_eq!(
+            auth_plan(&configured, false),
+            vec![
+                AuthKind::Agent,
+                AuthKind::PublicKey,
+                AuthKind::PublicKey,
+                AuthKind::KeyboardInteractive
+            ]
+        );
+    }
+
+    #[test]
+    fn next_step_tries_each_key_once() {
+        let plan = auth_plan(&[AuthKind::PublicKey, AuthKind::PublicKey], false);
+        assert_eq!(next_auth_step(&plan, &[], None), Some(0));
+        assert_eq!(next_auth_step(&plan, &[0], None), Some(1));
+        assert_eq!(next_auth_step(&plan, &[0, 1], None), Some(2));
+        assert_eq!(next_auth_step(&plan, &[0, 1, 2], None), None);
+
+        // Server stops offering publickey after the first key is rejected.
+        let remaining = methods(&[MethodKind::KeyboardInteractive]);
+        assert_eq!(next_auth_step(&plan, &[0], Some(&remaining)), Some(2));
+    }
+
     #[test]
     fn next_method_without_server_hint_takes_chain_order() {
         let chain = auth_fallback_chain(AuthKind::Agent, false);
@@ -510,6 +655,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn failure_reason_keeps_agent_error_behind_later_rejections() {
+        assert_eq!(
+            auth_failure_reason(
+                "Authentication rejected by server (keyboard-interactive auth)".to_string(),
+                Some("SSH agent error: SSH_AUTH_SOCK not set".to_string()),
+            ),
+            "Authentication rejected by server (keyboard-interactive auth); \
+             SSH agent error: SSH_AUTH_SOCK not set"
+        );
+        assert_eq!(
+            auth_failure_reason("agent down".to_string(), Some("agent down".to_string())),
+            "agent down"
+        );
+        assert_eq!(
+            auth_failure_reason("rejected".to_string(), None),
+            "rejected"
+        );
+    }
+
     #[test]
     fn agent_and_publickey_share_wire_method() {
         let chain = auth_fallback_chain(AuthKind::Agent, false);
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
+use std::io;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Duration;
 
 use russh::Pty;
 use russh::client::{self, Config};
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, lookup_host};
 use tokio::sync::{Mutex, mpsc};
 use tokio::time::{sleep, timeout};
 
@@ -18,7 +19,7 @@ use crate::config::DetectedOs;
 use secrecy::SecretString;
 
 use super::SshEvent;
-use super::auth::ResolvedAuth;
+use super::auth::resolve_auth_sequence;
 use super::auth_flow::{self, AuthContext};
 use super::connection_pool::{SshConnection, SshConnectionKey};
 use super::handler::ClientHandler;
@@ -35,6 +36,9 @@ const NEW_CONNECTION_TRANSPORT_RETRY_DELAY: Duration = Duration::from_millis(250
 /// (host key verification dialogs and keyboard-interactive prompts); each
 /// individual dialog wait is itself bounded at 60 seconds.
 const INTERACTIVE_AUTH_GRACE: Duration = Duration::from_secs(120);
+/// Extra budget on top of the connection timeout for the SSH handshake, which
+/// can wait on the (60 second bounded) host key verification dialog.
+const HOST_KEY_PROMPT_GRACE: Duration = Duration::from_secs(60);
 
 fn default_pty_modes() -> &'static [(Pty, u32)] {
     &[
@@ -108,6 +112,75 @@ fn is_transient_transport_error(reason: &str) -> bool {
     .any(|needle| lower.contains(needle))
 }
 
+/// Resolve `hostname` and open a TCP connection within `connect_timeout`,
+/// reporting DNS failures, refusals and timeouts as distinct errors.
+pub(crate) async fn connect_tcp(
+    hostname: &str,
+    port: u16,
+    connect_timeout: Duration,
+) -> Result<TcpStream, SshError> {
+    let attempt = async {
+        let addrs = lookup_host((hostname, port))
+            .await
+            .map_err(|e| SshError::DnsResolution {
+                host: hostname.to_string(),
+                reason: e.to_string(),
+            })?;
+
+        let mut last_error = None;
+        for addr in addrs {
+            match TcpStream::connect(addr).await {
+                Ok(stream) => return Ok(stream),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(match last_error {
+            Some(error) => tcp_connect_error(hostname, port, error),
+            None => SshError::DnsResolution {
+                host: hostname.to_string(),
+                reason: "no addresses found".to_string(),
+            },
+        })
+    };
+
+    timeout(connect_timeout, attempt)
+        .await
+        .unwrap_or_else(|_| Err(SshError::Timeout(format!("{}:{}", hostname, port))))
+}
+
+fn tcp_connect_error(hostname: &str, port: u16, error: io::Error) -> SshError {
+    match error.kind() {
+        io::ErrorKind::ConnectionRefused => SshError::ConnectionRefused {
+            host: hostname.to_string(),
+            port,
+        },
+        _ => SshError::ConnectionFailed {
+            host: hostname.to_string(),
+            port,
+            reason: error.to_string(),
+        },
+    }
+}
+
+/// Whether a failed TCP connect is worth retrying on a fresh socket
+fn is_transient_connect_error(error: &SshError) -> bool {
+    match error {
+        SshError::ConnectionRefused { .. } => true,
+        SshError::ConnectionFailed { reason, .. } => is_transient_transport_error(reason),
+        _ => false,
+    }
+}
+
+/// Keepalive probing for an SSH connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keepalive {
+    /// Seconds between probes; 0 disables keepalive
+    pub interval_secs: u64,
+    /// Unanswered probes before the connection is considered dead
+    pub count_max: u32,
+}
+
 /// SSH client for establishing connections
 pub struct SshClient {
     config: Arc<Config>,
@@ -139,18 +212,34 @@ impl SshClient {
     pub fn with_known_hosts(
         keepalive_interval: u64,
         known_hosts: Arc<Mutex<KnownHostsManager>>,
+    ) -> Self {
+        Self::with_keepalive(
+            Keepalive {
+                interval_secs: keepalive_interval,
+                count_max: 3,
+            },
+            known_hosts,
+        )
+    }
+
+    /// Create a client whose connections probe the server every
+    /// `keepalive.interval_secs` and drop after `keepalive.count_max`
+    /// unanswered probes.
+    pub fn with_keepalive(
+        keepalive: Keepalive,
+        known_hosts: Arc<Mutex<KnownHostsManager>>,
     ) -> Self {
         // Treat 0 as "no keepalive" to avoid immediate timeout
-        let keepalive = if keepalive_interval == 0 {
+        let interval = if keepalive.interval_secs == 0 {
             None
         } else {
-            Some(Duration::from_secs(keepalive_interval))
+            Some(Duration::from_secs(keepalive.interval_secs))
         };
 
         let config = Config {
             inactivity_timeout: Some(Duration::from_secs(3600)),
-            keepalive_interval: keepalive,
-            keepalive_max: 3,
+            keepalive_interval: interval,
+            keepalive_max: keepalive.count_max.max(1) as usize,
             ..Default::default()
         };
 
@@ -204,6 +293,47 @@ impl SshClient {
         }
     }
 
+    /// Check that `host` accepts a login without opening a session: a fresh
+    /// connection goes through the handshake, host key verification and
+    /// authentication, then is torn down straight away. Returns the auth
+    /// method that succeeded.
+    ///
+    /// The connection pool is bypassed so an already authenticated
+    /// connection cannot stand in for the settings being tested.
+    pub async fn test_connection(
+        &self,
+        host: &Host,
+        jump_chain: &[Host],
+        event_tx: mpsc::Sender<SshEvent>,
+        connection_timeout: Duration,
+        password: Option<SecretString>,
+        passphrase: Option<SecretString>,
+    ) -> Result<String, SshError> {
+        let addr = format!("{}:{}", host.hostname, host.port);
+        let connection = match timeout(
+            connection_timeout + INTERACTIVE_AUTH_GRACE,
+            self.open_connection(
+                host,
+                jump_chain,
+                &event_tx,
+                connection_timeout,
+                password,
+                passphrase,
+            ),
+        )
+        .await
+        {
+            Ok(result) => result?,
+            Err(_) => return Err(SshError::Timeout(addr)),
+        };
+
+        let auth_method = connection.auth_method().unwrap_or("unknown").to_string();
+        if let Err(e) = connection.disconnect().await {
+            tracing::debug!("Disconnect after connection test failed: {}", e);
+        }
+        Ok(auth_method)
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn establish_session(
         &self,
@@ -395,24 +525,18 @@ impl SshClient {
             // jump chain. Jump-hop failures are not retried here — they carry
             // their own hop-specific error message.
             let (stream, tunnel_parent) = if jump_chain.is_empty() {
-                match timeout(connection_timeout, TcpStream::connect(&addr)).await {
-                    Ok(Ok(stream)) => (tunnel::TunnelStream::Tcp(stream), None),
-                    Ok(Err(error)) => {
-                        let reason = error.to_string();
+                match connect_tcp(&host.hostname, host.port, connection_timeout).await {
+                    Ok(stream) => (tunnel::TunnelStream::Tcp(stream), None),
+                    Err(error) => {
                         if transport_attempt + 1 < NEW_CONNECTION_TRANSPORT_ATTEMPTS
-                            && is_transient_transport_error(&reason)
+                            && is_transient_connect_error(&error)
                         {
-                            last_transport_error = Some(reason);
+                            last_transport_error = Some(error.to_string());
                             sleep(NEW_CONNECTION_TRANSPORT_RETRY_DELAY).await;
                             continue;
                         }
-                        return Err(SshError::ConnectionFailed {
-                            host: host.hostname.clone(),
-                            port: host.port,
-                            reason,
-                        });
+                        return Err(error);
                     }
-                    Err(_) => return Err(SshError::Timeout(addr.clone())),
                 }
             } else {
                 let params = TunnelParams {
@@ -439,39 +563,44 @@ impl SshClient {
                 remote_forwards.clone(),
             );
 
-            let mut handle =
-                match client::connect_stream(self.config.clone(), stream, handler).await {
-                    Ok(handle) => handle,
-                    Err(error) => {
-                        let reason = error.to_string();
-                        if transport_attempt + 1 < NEW_CONNECTION_TRANSPORT_ATTEMPTS
-                            && is_transient_transport_error(&reason)
-                        {
-                            last_transport_error = Some(reason);
-                            sleep(NEW_CONNECTION_TRANSPORT_RETRY_DELAY).await;
-                            continue;
-                        }
-                        return Err(SshError::ConnectionFailed {
-                            host: host.hostname.clone(),
-                            port: host.port,
-                            reason,
-                        });
+            let handshake = timeout(
+                connection_timeout + HOST_KEY_PROMPT_GRACE,
+                client::connect_stream(self.config.clone(), stream, handler),
+            );
+            let mut handle = match handshake.await {
+                Ok(Ok(handle)) => handle,
+                Ok(Err(error)) => {
+                    let reason = error.to_string();
+                    if transport_attempt + 1 < NEW_CONNECTION_TRANSPORT_ATTEMPTS
+                        && is_transient_transport_error(&reason)
+                    {
+                        last_transport_error = Some(reason);
+                        sleep(NEW_CONNECTION_TRANSPORT_RETRY_DELAY).await;
+                        continue;
                     }
-                };
+                    return Err(SshError::HandshakeFailed {
+                        host: host.hostname.clone(),
+                        port: host.port,
+                        reason,
+                    });
+                }
+                Err(_) => return Err(SshError::Timeout(addr.clone())),
+            };
 
-            // Authenticate with the configured method plus automatic
-            // fallback (publickey/agent -> keyboard-interactive -> password).
-            let auth =
-                ResolvedAuth::resolve(&host.auth, password.clone(), passphrase.clone()).await?;
-            auth_flow::authenticate(
+            // Authenticate with the configured method and its fallbacks,
+            // then the automatic ones (keyboard-interactive -> password).
+            let candidates =
+                resolve_auth_sequence(host, password.clone(), passphrase.clone()).await?;
+            let auth_method = auth_flow::authenticate(
                 &mut handle,
                 AuthContext {
                     hostname: &host.hostname,
                     port: host.port,
                     username: &host.username,
+                    agent_identity: host.agent_identity.as_deref(),
                     event_tx,
                 },
-                auth,
+                candidates,
             )
             .await?;
 
@@ -481,6 +610,7 @@ impl SshClient {
                 agent_forwarding_enabled_flag,
                 Arc::from(host.hostname.clone()),
                 host.port,
+                Some(auth_method),
                 tunnel_parent,
             ));
         }
@@ -515,6 +645,40 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn refused_tcp_connects_are_reported_as_refused() {
+        let error = tcp_connect_error(
+            "example.com",
+            2222,
+            io::Error::from(io::ErrorKind::ConnectionRefused),
+        );
+        assert!(matches!(
+            error,
+            SshError::ConnectionRefused { ref host, port: 2222 } if host == "example.com"
+        ));
+        assert!(is_transient_connect_error(&error));
+
+        let unreachable = tcp_connect_error(
+            "example.com",
+            22,
+            io::Error::from(io::ErrorKind::HostUnreachable),
+        );
+        assert!(matches!(unreachable, SshError::ConnectionFailed { .. }));
+        assert!(!is_transient_connect_error(&SshError::Timeout(
+            "example.com:22".to_string()
+        )));
+    }
+
+    #[tokio::test]
+    async fn connect_tcp_reports_closed_local_port_as_refused() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let result = connect_tcp("127.0.0.1", port, Duration::from_secs(5)).await;
+        assert!(matches!(result, Err(SshError::ConnectionRefused { .. })));
+    }
+
     #[test]
     fn new_creates_client_with_custom_settings() {
         let client = SshClient::new(30, 120);
@@ -654,4 +818,21 @@ mod tests {
             Some(Duration::from_secs(45))
         );
     }
+
+    #[test]
+    fn with_keepalive_sets_interval_and_max() {
+        let known_hosts = Arc::new(Mutex::new(KnownHostsManager::new()));
+        let client = SshClient::with_keepalive(
+            Keepalive {
+                interval_secs: 15,
+                count_max: 5,
+            },
+            known_hosts,
+        );
+        assert_eq!(
+            client.config.keepalive_interval,
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(client.config.keepalive_max, 5);
+    }
 }
@@ -62,6 +62,8 @@ pub struct SshConnection {
     agent_forwarding_enabled: Arc<AtomicBool>,
     host: Arc<str>,
     port: u16,
+    /// Label of the auth method the server accepted (e.g. "agent").
+    auth_method: Option<String>,
     /// The jump-host connection this connection is tunneled through, if any.
     /// Held (not read) to keep the tunnel transport alive for as long as
     /// this connection lives (the pool only holds weak references).
@@ -93,6 +95,7 @@ impl SshConnection {
             host,
             port,
             None,
+            None,
         )
     }
 
@@ -105,6 +108,7 @@ impl SshConnection {
         agent_forwarding_enabled: Arc<AtomicBool>,
         host: Arc<str>,
         port: u16,
+        auth_method: Option<String>,
         tunnel_parent: Option<Arc<SshConnection>>,
     ) -> Arc<Self> {
         Arc::new(Self {
@@ -113,6 +117,7 @@ impl SshConnection {
             agent_forwarding_enabled,
             host,
             port,
+            auth_method,
             tunnel_parent,
         })
     }
@@ -137,10 +142,19 @@ impl SshConnection {
         self.port
     }
 
+    pub fn auth_method(&self) -> Option<&str> {
+        self.auth_method.as_deref()
+    }
+
     pub fn enable_agent_forwarding(&self) {
         self.agent_forwarding_enabled.store(true, Ordering::SeqCst);
     }
 
+    /// Whether the underlying SSH transport has shut down
+    pub async fn is_closed(&self) -> bool {
+        self.handle.lock().await.is_closed()
+    }
+
     pub async fn disconnect(&self) -> Result<(), SshError> {
         let handle_guard = self.handle.lock().await;
         handle_guard
@@ -1,7 +1,11 @@
-use data_encoding::BASE64_MIME;
+use data_encoding::{BASE64, BASE64_MIME};
 use hmac::{Hmac, Mac};
+use rand::RngCore;
 use sha1::Sha1;
 
+/// Salt length OpenSSH uses for hashed hostnames (the SHA-1 block output)
+const HASHED_HOST_SALT_LEN: usize = 20;
+
 pub(crate) fn host_matches(host_port: &str, host: &str, host_field: &str) -> bool {
     let mut matched = false;
 
@@ -67,6 +71,20 @@ pub(crate) fn match_hashed_host(host_port: &str, pattern: &str) -> bool {
     hmac.verify_slice(&hash).is_ok()
 }
 
+/// Hash `host_port` into OpenSSH's `|1|salt|hash` form with a fresh salt
+pub(crate) fn hash_host(host_port: &str) -> String {
+    let mut salt = [0u8; HASHED_HOST_SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+
+    let mut hmac = Hmac::<Sha1>::new_from_slice(&salt).expect("HMAC accepts any key length");
+    hmac.update(host_port.as_bytes());
+    format!(
+        "|1|{}|{}",
+        BASE64.encode(&salt),
+        BASE64.encode(&hmac.finalize().into_bytes())
+    )
+}
+
 pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
     let (mut p_idx, mut t_idx) = (0usize, 0usize);
     let mut star_idx = None;
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
-use russh::keys::{HashAlg, PublicKey};
+use russh::keys::{self, HashAlg, PublicKey};
 
+use crate::config::ssh_config::openssh_hash_known_hosts;
 use crate::config::{paths, write_atomic};
 use crate::error::SshError;
 use crate::fs_utils;
@@ -35,12 +36,37 @@ pub enum HostKeyStatus {
     Revoked { fingerprint: String },
 }
 
+/// Marker prefix on a known_hosts line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownHostMarker {
+    /// `@revoked`: the key must never be accepted
+    Revoked,
+    /// `@cert-authority`: the key signs host certificates
+    CertAuthority,
+}
+
+/// One parsed known_hosts line
+#[derive(Debug, Clone)]
+pub struct KnownHostEntry {
+    /// Host patterns as written (hashed entries stay hashed)
+    pub host_pattern: String,
+    pub key_type: String,
+    pub fingerprint: String,
+    /// File the entry was read from
+    pub path: PathBuf,
+    /// 1-based line number within `path`
+    pub line_number: usize,
+    pub marker: Option<KnownHostMarker>,
+}
+
 /// Manager for known_hosts file operations
 pub struct KnownHostsManager {
     /// Primary known_hosts file (Portal config dir)
     primary_path: Option<PathBuf>,
     /// Optional OpenSSH known_hosts file (~/.ssh/known_hosts)
     ssh_path: Option<PathBuf>,
+    /// Write learned hostnames in OpenSSH's hashed `|1|salt|hash` form
+    hash_new_entries: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -50,9 +76,13 @@ enum KnownHostsPathKind {
 }
 
 impl KnownHostsManager {
-    /// Create a new manager and load known hosts from file
+    /// Create a new manager and load known hosts from file. New entries are
+    /// hashed when `~/.ssh/config` sets `HashKnownHosts yes`.
     pub fn new() -> Self {
-        Self::with_paths(paths::known_hosts_file(), paths::ssh_known_hosts_file())
+        let mut manager =
+            Self::with_paths(paths::known_hosts_file(), paths::ssh_known_hosts_file());
+        manager.hash_new_entries = openssh_hash_known_hosts();
+        manager
     }
 
     /// Create a new manager with explicit paths (useful for tests)
@@ -60,9 +90,19 @@ impl KnownHostsManager {
         Self {
             primary_path,
             ssh_path,
+            hash_new_entries: false,
         }
     }
 
+    /// Whether newly learned hostnames are written hashed
+    pub fn hash_new_entries(&self) -> bool {
+        self.hash_new_entries
+    }
+
+    pub fn set_hash_new_entries(&mut self, hash: bool) {
+        self.hash_new_entries = hash;
+    }
+
     #[cfg(test)]
     fn known_hosts_paths(&self) -> Vec<PathBuf> {
         self.known_hosts_path_entries()
@@ -199,6 +239,29 @@ impl KnownHostsManager {
 
     /// Add a host key to known_hosts
     pub fn add_host_key(&mut self, host: &str, port: u16, key: &PublicKey) -> Result<(), SshError> {
+        self.append_host_key(host, port, key, self.hash_new_entries)
+    }
+
+    /// Update a host key (after user confirms key change). The replacement
+    /// stays hashed when the entry it replaces was hashed.
+    pub fn update_host_key(
+        &mut self,
+        host: &str,
+        port: u16,
+        key: &PublicKey,
+    ) -> Result<(), SshError> {
+        let hashed = self.hash_new_entries || self.scan_known_hosts_for_host(host, port).hashed;
+        self.remove_host_key_entries_all(host, port)?;
+        self.append_host_key(host, port, key, hashed)
+    }
+
+    fn append_host_key(
+        &self,
+        host: &str,
+        port: u16,
+        key: &PublicKey,
+        hashed: bool,
+    ) -> Result<(), SshError> {
         let (path, kind) = self.primary_write_path().ok_or_else(|| {
             SshError::HostKeyVerification("No known_hosts path configured".to_string())
         })?;
@@ -208,22 +271,11 @@ impl KnownHostsManager {
         if !content.is_empty() && !content.ends_with('\n') {
             content.push('\n');
         }
-        content.push_str(&known_hosts_line(host, port, key)?);
+        content.push_str(&known_hosts_line(host, port, key, hashed)?);
 
         write_known_hosts_content(&path, kind, &content)
     }
 
-    /// Update a host key (after user confirms key change)
-    pub fn update_host_key(
-        &mut self,
-        host: &str,
-        port: u16,
-        key: &PublicKey,
-    ) -> Result<(), SshError> {
-        self.remove_host_key_entries_all(host, port)?;
-        self.add_host_key(host, port, key)
-    }
-
     fn remove_host_key_entries_all(&self, host: &str, port: u16) -> Result<(), SshError> {
         for (path, kind) in self.known_hosts_path_entries() {
             self.remove_host_key_entries(host, port, &path, kind)?;
@@ -248,22 +300,71 @@ impl KnownHostsManager {
         }
 
         let line_numbers: HashSet<usize> = scan.line_numbers.into_iter().collect();
-        let filtered: Vec<&str> = content
-            .lines()
-            .enumerate()
-            .filter(|(idx, _)| !line_numbers.contains(&(idx + 1)))
-            .map(|(_, line)| line)
-            .collect();
-
-        if filtered.len() == content.lines().count() {
-            return Ok(());
+        match without_lines(&content, &line_numbers) {
+            Some(new_content) => write_known_hosts_content(path, kind, &new_content),
+            None => Ok(()),
         }
+    }
 
-        let mut new_content = filtered.join("\n");
-        if content.ends_with('\n') {
-            new_content.push('\n');
+    /// Every parsed entry across the known_hosts files, in file order
+    pub fn list_entries(&self) -> Vec<KnownHostEntry> {
+        let mut entries = Vec::new();
+        for (path, kind) in self.known_hosts_path_entries() {
+            let content = match read_known_hosts_content(&path, kind) {
+                Ok(Some(content)) => content,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::debug!("Failed to read known_hosts {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            for (index, line) in content.lines().enumerate() {
+                let Some(parsed) = scan::parse_line(line) else {
+                    continue;
+                };
+                let marker = match parsed.marker {
+                    None => None,
+                    Some("revoked") => Some(KnownHostMarker::Revoked),
+                    Some("cert-authority") => Some(KnownHostMarker::CertAuthority),
+                    Some(_) => continue,
+                };
+                let Ok(key) = keys::parse_public_key_base64(parsed.key_data) else {
+                    continue;
+                };
+                entries.push(KnownHostEntry {
+                    host_pattern: parsed.hosts_field.to_string(),
+                    key_type: parsed.key_type.to_string(),
+                    fingerprint: Self::get_fingerprint(&key),
+                    path: path.clone(),
+                    line_number: index + 1,
+                    marker,
+                });
+            }
         }
+        entries
+    }
 
+    /// Remove line `line_number` (1-based, as in [`KnownHostEntry`]) from one
+    /// of the known_hosts files this manager reads.
+    pub fn remove_entry(&mut self, path: &Path, line_number: usize) -> Result<(), SshError> {
+        let kind = self
+            .known_hosts_path_entries()
+            .into_iter()
+            .find(|(known, _)| known == path)
+            .map(|(_, kind)| kind)
+            .ok_or_else(|| {
+                SshError::HostKeyVerification(format!(
+                    "{} is not a known_hosts file",
+                    path.display()
+                ))
+            })?;
+
+        let content = read_known_hosts_content(path, kind)?.unwrap_or_default();
+        let line_numbers = HashSet::from([line_number]);
+        let new_content = without_lines(&content, &line_numbers).ok_or_else(|| {
+            SshError::HostKeyVerification(format!("{} has no line {}", path.display(), line_number))
+        })?;
         write_known_hosts_content(path, kind, &new_content)
     }
 
@@ -305,6 +406,26 @@ impl KnownHostsManager {
     }
 }
 
+/// `content` without the given 1-based lines, or `None` if none of them exist
+fn without_lines(content: &str, line_numbers: &HashSet<usize>) -> Option<String> {
+    let filtered: Vec<&str> = content
+        .lines()
+        .enumerate()
+        .filter(|(idx, _)| !line_numbers.contains(&(idx + 1)))
+        .map(|(_, line)| line)
+        .collect();
+
+    if filtered.len() == content.lines().count() {
+        return None;
+    }
+
+    let mut new_content = filtered.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    Some(new_content)
+}
+
 fn read_known_hosts_content(
     path: &Path,
     kind: KnownHostsPathKind,
@@ -371,12 +492,22 @@ fn write_known_hosts_content(
     })
 }
 
-fn known_hosts_line(host: &str, port: u16, key: &PublicKey) -> Result<String, SshError> {
-    let host_field = if port == 22 {
+fn known_hosts_line(
+    host: &str,
+    port: u16,
+    key: &PublicKey,
+    hashed: bool,
+) -> Result<String, SshError> {
+    let host_port = if port == 22 {
         host.to_string()
     } else {
         format!("[{}]:{}", host, port)
     };
+    let host_field = if hashed {
+        matchers::hash_host(&host_port)
+    } else {
+        host_port
+    };
     let key = key.to_openssh().map_err(|error| {
         SshError::HostKeyVerification(format!("Failed to format known_hosts key: {}", error))
     })?;
@@ -392,8 +523,8 @@ impl Default for KnownHostsManager {
 #[cfg(test)]
 mod tests {
     use super::{
-        HostKeyStatus, KnownHostsManager, KnownHostsPathKind, OPENSSH_KNOWN_HOSTS_MAX_BYTES,
-        glob_match, matchers, read_known_hosts_content,
+        HostKeyStatus, KnownHostMarker, KnownHostsManager, KnownHostsPathKind,
+        OPENSSH_KNOWN_HOSTS_MAX_BYTES, glob_match, matchers, read_known_hosts_content,
     };
     use russh::keys;
     use std::fs;
@@ -402,6 +533,7 @@ mod tests {
 
     const KEY1: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIJdD7y3aLq454yWBdwLWbieU1ebz9/cu7/QEXn9OIeZJ";
     const KEY2: &str = "AAAAC3NzaC1lZDI1NTE5AAAAILIG2T/B0l0gaqj3puu510tu9N1OkQ4znY3LYuEm5zCF";
+    const CA_KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIGVdtOQMBPodXjpN/4gKhCu5ruCEalnIMcJzb2RsXlKq";
 
     // === Existing tests ===
 
@@ -1280,6 +1412,103 @@ mod tests {
         assert!(matches!(status, HostKeyStatus::Changed { .. }));
     }
 
+    #[test]
+    fn hashed_entries_are_written_and_matched() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("known_hosts");
+
+        let mut manager = KnownHostsManager::with_paths(Some(path.clone()), None);
+        manager.set_hash_new_entries(true);
+        let key = keys::parse_public_key_base64(KEY1).expect("parse key");
+        manager
+            .add_host_key("example.com", 2222, &key)
+            .expect("add key");
+
+        let content = fs::read_to_string(&path).expect("read known_hosts");
+        assert!(content.starts_with("|1|"));
+        assert!(!content.contains("example.com"));
+        assert!(matches!(
+            manager.check_host_key("example.com", 2222, &key),
+            HostKeyStatus::Known
+        ));
+        assert!(matches!(
+            manager.check_host_key("example.com", 22, &key),
+            HostKeyStatus::Unknown { .. }
+        ));
+    }
+
+    #[test]
+    fn update_host_key_keeps_hashed_format() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("known_hosts");
+        let hashed = matchers::hash_host("example.com");
+        fs::write(&path, format!("{hashed} ssh-ed25519 {KEY1}\n")).expect("write initial");
+
+        // Hashing new entries is off; the replaced entry decides.
+        let mut manager = KnownHostsManager::with_paths(Some(path.clone()), None);
+        let new_key = keys::parse_public_key_base64(KEY2).expect("parse new key");
+        manager
+            .update_host_key("example.com", 22, &new_key)
+            .expect("update key");
+
+        let content = fs::read_to_string(&path).expect("read known_hosts");
+        assert_eq!(content.lines().count(), 1);
+        assert!(content.starts_with("|1|"));
+        assert!(matches!(
+            manager.check_host_key("example.com", 22, &new_key),
+            HostKeyStatus::Known
+        ));
+    }
+
+    #[test]
+    fn list_entries_reports_lines_and_markers() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("known_hosts");
+        fs::write(
+            &path,
+            format!(
+                "# comment\nexample.com ssh-ed25519 {KEY1}\n@revoked * ssh-ed25519 {KEY2}\n@cert-authority *.example.com ssh-ed25519 {CA_KEY}\n"
+            ),
+        )
+        .expect("write known_hosts");
+
+        let manager = KnownHostsManager::with_paths(Some(path.clone()), None);
+        let entries = manager.list_entries();
+        let key1 = keys::parse_public_key_base64(KEY1).expect("parse key");
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].host_pattern, "example.com");
+        assert_eq!(entries[0].key_type, "ssh-ed25519");
+        assert_eq!(
+            entries[0].fingerprint,
+            KnownHostsManager::get_fingerprint(&key1)
+        );
+        assert_eq!(entries[0].path, path);
+        assert_eq!(entries[0].line_number, 2);
+        assert_eq!(entries[0].marker, None);
+        assert_eq!(entries[1].marker, Some(KnownHostMarker::Revoked));
+        assert_eq!(entries[2].marker, Some(KnownHostMarker::CertAuthority));
+    }
+
+    #[test]
+    fn remove_entry_drops_only_that_line() {
+        let dir = tempdir().expect("temp dir");
+        let path = dir.path().join("known_hosts");
+        fs::write(
+            &path,
+            format!("one.com ssh-ed25519 {KEY1}\ntwo.com ssh-ed25519 {KEY2}\n"),
+        )
+        .expect("write known_hosts");
+
+        let mut manager = KnownHostsManager::with_paths(Some(path.clone()), None);
+        manager.remove_entry(&path, 1).expect("remove entry");
+
+        let content = fs::read_to_string(&path).expect("read known_hosts");
+        assert_eq!(content, format!("two.com ssh-ed25519 {KEY2}\n"));
+        assert!(manager.remove_entry(&path, 5).is_err());
+        assert!(manager.remove_entry(&dir.path().join("other"), 1).is_err());
+    }
+
     // === Default trait test ===
 
     #[test]
@@ -10,6 +10,42 @@ pub(crate) struct HostKeyScan {
     pub(crate) keys: Vec<PublicKey>,
     pub(crate) revoked_keys: Vec<PublicKey>,
     pub(crate) line_numbers: Vec<usize>,
+    /// Whether any matching plain entry stores its hostname hashed
+    pub(crate) hashed: bool,
+}
+
+/// The fields of one known_hosts entry line
+pub(crate) struct KnownHostsLine<'a> {
+    pub(crate) marker: Option<&'a str>,
+    pub(crate) hosts_field: &'a str,
+    pub(crate) key_type: &'a str,
+    pub(crate) key_data: &'a str,
+}
+
+/// Split an entry line into its fields; `None` for blanks, comments, and
+/// lines missing a field.
+pub(crate) fn parse_line(line: &str) -> Option<KnownHostsLine<'_>> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    let (marker, rest) = if let Some(stripped) = trimmed.strip_prefix('@') {
+        let mut marker_parts = stripped.splitn(2, char::is_whitespace);
+        let marker = marker_parts.next()?;
+        let rest = marker_parts.next()?;
+        (Some(marker), rest.trim_start())
+    } else {
+        (None, trimmed)
+    };
+
+    let mut parts = rest.split_whitespace();
+    Some(KnownHostsLine {
+        marker,
+        hosts_field: parts.next()?,
+        key_type: parts.next()?,
+        key_data: parts.next()?,
+    })
 }
 
 pub(crate) fn scan_known_hosts_content(
@@ -27,32 +63,13 @@ pub(crate) fn scan_known_hosts_content(
     let mut scan = HostKeyScan::default();
 
     for (index, line) in content.lines().enumerate() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-
-        let (marker, rest) = if let Some(stripped) = trimmed.strip_prefix('@') {
-            let mut marker_parts = stripped.splitn(2, char::is_whitespace);
-            let Some(marker) = marker_parts.next() else {
-                continue;
-            };
-            let Some(rest) = marker_parts.next() else {
-                continue;
-            };
-            (Some(marker), rest.trim_start())
-        } else {
-            (None, trimmed)
-        };
-
-        let mut parts = rest.split_whitespace();
-        let Some(hosts_field) = parts.next() else {
-            continue;
-        };
-        let Some(_key_type) = parts.next() else {
-            continue;
-        };
-        let Some(key_data) = parts.next() else {
+        let Some(KnownHostsLine {
+            marker,
+            hosts_field,
+            key_data,
+            ..
+        }) = parse_line(line)
+        else {
             continue;
         };
 
@@ -86,6 +103,7 @@ pub(crate) fn scan_known_hosts_content(
             None => {
                 scan.keys.push(key);
                 scan.line_numbers.push(index + 1);
+                scan.hashed |= hosts_field.starts_with("|1|");
             }
         }
     }
@@ -17,7 +17,7 @@ pub mod reconnect;
 pub mod session;
 pub mod tunnel;
 
-pub use client::SshClient;
+pub use client::{Keepalive, SshClient};
 pub use connection_pool::{SshConnection, SshConnectionKey, SshConnectionPool};
 pub use key_install::install_ssh_key;
 pub use passphrase_cache::PassphraseCache;
@@ -4,17 +4,20 @@
 //! users repeatedly within a configurable timeout period.
 
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use secrecy::{ExposeSecret, SecretString};
+use uuid::Uuid;
 
 /// Default cache timeout in seconds (5 minutes).
 const DEFAULT_TIMEOUT_SECONDS: u64 = 300;
 
 static SHARED_CACHE: OnceLock<Arc<PassphraseCache>> = OnceLock::new();
+static SUDO_CACHE: OnceLock<Arc<PassphraseCache<Uuid>>> = OnceLock::new();
 
 /// Get the process-wide shared passphrase cache instance.
 pub fn shared_cache() -> Arc<PassphraseCache> {
@@ -23,6 +26,13 @@ pub fn shared_cache() -> Arc<PassphraseCache> {
         .clone()
 }
 
+/// Get the process-wide sudo password cache, keyed by SFTP session ID.
+pub fn shared_sudo_cache() -> Arc<PassphraseCache<Uuid>> {
+    SUDO_CACHE
+        .get_or_init(|| Arc::new(PassphraseCache::new(DEFAULT_TIMEOUT_SECONDS)))
+        .clone()
+}
+
 /// Cached passphrase entry with expiration
 struct CacheEntry {
     passphrase: SecretString,
@@ -32,13 +42,14 @@ struct CacheEntry {
 /// Thread-safe passphrase cache with automatic expiration.
 ///
 /// Passphrases are stored in memory and automatically cleared
-/// after the configured timeout period.
-pub struct PassphraseCache {
-    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+/// after the configured timeout period. Entries are keyed by key file path
+/// by default; other secrets (e.g. sudo passwords) can use their own key type.
+pub struct PassphraseCache<K = PathBuf> {
+    entries: Mutex<HashMap<K, CacheEntry>>,
     timeout_seconds: AtomicU64,
 }
 
-impl PassphraseCache {
+impl<K: Eq + Hash> PassphraseCache<K> {
     /// Create a new passphrase cache with the given timeout.
     ///
     /// A timeout of 0 disables caching entirely.
@@ -53,7 +64,7 @@ impl PassphraseCache {
     ///
     /// The passphrase will expire after the configured timeout.
     /// If timeout is 0, the passphrase is not stored.
-    pub fn store(&self, key_path: PathBuf, passphrase: SecretString) {
+    pub fn store(&self, key_path: K, passphrase: SecretString) {
         let timeout_seconds = self.timeout_seconds.load(Ordering::Relaxed);
         if timeout_seconds == 0 {
             return;
@@ -73,7 +84,7 @@ impl PassphraseCache {
     ///
     /// Returns None if the passphrase is not cached or has expired.
     /// Expired entries are automatically removed.
-    pub fn get(&self, key_path: &PathBuf) -> Option<SecretString> {
+    pub fn get(&self, key_path: &K) -> Option<SecretString> {
         let mut entries = self.entries.lock().ok()?;
 
         if let Some(entry) = entries.get(key_path) {
@@ -99,7 +110,7 @@ impl PassphraseCache {
     }
 
     /// Clear the cached passphrase for a specific key.
-    pub fn remove(&self, key_path: &PathBuf) {
+    pub fn remove(&self, key_path: &K) {
         if let Ok(mut entries) = self.entries.lock() {
             entries.remove(key_path);
         }
@@ -113,6 +124,14 @@ impl PassphraseCache {
         }
     }
 
+    /// Whether nothing is cached, counting entries not yet cleaned up.
+    pub fn is_empty(&self) -> bool {
+        self.entries
+            .lock()
+            .map(|entries| entries.is_empty())
+            .unwrap_or(true)
+    }
+
     /// Update the cache timeout.
     ///
     /// This only affects new entries; existing entries keep their
@@ -179,6 +198,22 @@ mod tests {
         assert!(cache.get(&key2).is_none());
     }
 
+    #[test]
+    fn test_cleanup_expired_empties_cache() {
+        let cache = PassphraseCache::new(60);
+        let key = PathBuf::from("/home/user/.ssh/id_ed25519");
+        cache.store(key.clone(), SecretString::new("pass".to_string().into()));
+
+        cache.cleanup_expired();
+        assert!(!cache.is_empty());
+
+        if let Ok(mut entries) = cache.entries.lock() {
+            entries.get_mut(&key).unwrap().expires_at = Instant::now();
+        }
+        cache.cleanup_expired();
+        assert!(cache.is_empty());
+    }
+
     #[test]
     fn test_remove_specific_key() {
         let cache = PassphraseCache::new(60);
@@ -194,6 +229,18 @@ mod tests {
         assert!(cache.get(&key2).is_some());
     }
 
+    #[test]
+    fn test_sudo_cache_keyed_by_session() {
+        let cache = PassphraseCache::<Uuid>::new(60);
+        let session_a = Uuid::new_v4();
+        let session_b = Uuid::new_v4();
+
+        cache.store(session_a, SecretString::new("sudo-pass".to_string().into()));
+
+        assert_eq!(cache.get(&session_a).unwrap().expose_secret(), "sudo-pass");
+        assert!(cache.get(&session_b).is_none());
+    }
+
     #[test]
     fn test_default_timeout() {
         let cache = PassphraseCache::default();
@@ -235,6 +235,11 @@ impl SshSession {
         self._connection.clone()
     }
 
+    /// Label of the auth method that opened this session's connection
+    pub fn auth_method(&self) -> Option<&str> {
+        self._connection.auth_method()
+    }
+
     /// Notify the remote shell of a window size change
     pub async fn window_change(&self, cols: u16, rows: u16) -> Result<(), SshError> {
         self.command_tx
@@ -376,7 +381,9 @@ impl SshSession {
         }
     }
 
-    pub async fn create_local_forward(&self, forward: PortForward) -> Result<(), SshError> {
+    /// Listen locally and tunnel each connection to the target (`-L`).
+    /// Returns the port actually bound.
+    pub async fn create_local_forward(&self, forward: PortForward) -> Result<u16, SshError> {
         if forward.kind != PortForwardKind::Local {
             return Err(SshError::Channel("Forward kind is not local".to_string()));
         }
@@ -472,10 +479,12 @@ impl SshSession {
             actual_port
         );
 
-        Ok(())
+        Ok(actual_port)
     }
 
-    pub async fn create_remote_forward(&self, mut forward: PortForward) -> Result<(), SshError> {
+    /// Ask the server to listen and tunnel connections back (`-R`).
+    /// Returns the port the server assigned.
+    pub async fn create_remote_forward(&self, mut forward: PortForward) -> Result<u16, SshError> {
         if forward.kind != PortForwardKind::Remote {
             return Err(SshError::Channel("Forward kind is not remote".to_string()));
         }
@@ -519,10 +528,12 @@ impl SshSession {
             actual_port
         );
 
-        Ok(())
+        Ok(actual_port)
     }
 
-    pub async fn create_dynamic_forward(&self, forward: PortForward) -> Result<(), SshError> {
+    /// Run a local SOCKS5 proxy that opens a direct-tcpip channel per
+    /// CONNECT request (`-D`). Returns the port actually bound.
+    pub async fn create_dynamic_forward(&self, forward: PortForward) -> Result<u16, SshError> {
         if forward.kind != PortForwardKind::Dynamic {
             return Err(SshError::Channel("Forward kind is not dynamic".to_string()));
         }
@@ -632,7 +643,7 @@ impl SshSession {
             actual_port
         );
 
-        Ok(())
+        Ok(actual_port)
     }
 
     pub async fn stop_forward(&self, forward_id: Uuid) -> Result<(), SshError> {
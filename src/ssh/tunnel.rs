@@ -19,7 +19,6 @@ use russh::{Channel, ChannelStream};
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::net::TcpStream;
 use tokio::sync::{Mutex, mpsc};
-use tokio::time::timeout;
 use uuid::Uuid;
 
 use crate::config::{Host, Protocol};
@@ -27,8 +26,9 @@ use crate::error::SshError;
 use crate::security_log;
 
 use super::SshEvent;
-use super::auth::ResolvedAuth;
+use super::auth::{AuthCandidate, ResolvedAuth};
 use super::auth_flow::{self, AuthContext};
+use super::client::connect_tcp;
 use super::connection_pool::{SshConnection, SshConnectionKey};
 use super::handler::ClientHandler;
 use super::known_hosts::KnownHostsManager;
@@ -247,12 +247,7 @@ async fn connect_chain(
 
         // Reuse a live pooled connection when possible.
         if let Some(conn) = pool.get(&key).await {
-            let closed = {
-                let handle = conn.handle();
-                let guard = handle.lock().await;
-                guard.is_closed()
-            };
-            if closed {
+            if conn.is_closed().await {
                 pool.invalidate_if_matches(&key, &conn).await;
             } else {
                 prev = Some(conn);
@@ -264,10 +259,8 @@ async fn connect_chain(
         // a direct-tcpip channel through the previous hop otherwise.
         let stream = match &prev {
             None => {
-                let addr = format!("{}:{}", hop.hostname, hop.port);
-                let stream = timeout(params.connect_timeout, TcpStream::connect(&addr))
+                let stream = connect_tcp(&hop.hostname, hop.port, params.connect_timeout)
                     .await
-                    .map_err(|_| hop_error(hop, format!("connection to {} timed out", addr)))?
                     .map_err(|e| hop_error(hop, e.to_string()))?;
                 TunnelStream::Tcp(stream)
             }
@@ -309,12 +302,13 @@ async fn connect_chain(
             other => hop_error(hop, other.to_string()),
         })?;
 
-        auth_flow::authenticate(
+        let auth_method = auth_flow::authenticate(
             &mut handle,
             AuthContext {
                 hostname: &hop.hostname,
                 port: hop.port,
                 username: &username,
+                agent_identity: hop.agent_identity.as_deref(),
                 event_tx: &params.event_tx,
             },
             resolved,
@@ -328,6 +322,7 @@ async fn connect_chain(
             Arc::new(AtomicBool::new(false)),
             Arc::from(hop.hostname.clone()),
             hop.port,
+            Some(auth_method),
             prev.take(),
         );
         security_log::log_ssh_connect(&hop.hostname, hop.port, &username);
@@ -342,27 +337,41 @@ async fn connect_chain(
     })
 }
 
-async fn resolve_hop_auth(hop: &Host) -> Result<ResolvedAuth, SshError> {
+async fn resolve_hop_auth(hop: &Host) -> Result<Vec<AuthCandidate>, SshError> {
     use crate::config::AuthMethod;
 
-    if matches!(hop.auth, AuthMethod::Password) {
-        // No pre-collected password for jump hosts: let the server prompt
-        // via keyboard-interactive instead.
-        return Ok(ResolvedAuth::KeyboardInteractive);
-    }
+    let mut candidates = Vec::with_capacity(1 + hop.auth_fallbacks.len());
+    for (index, method) in hop.auth_sequence().enumerate() {
+        let resolved = if matches!(method, AuthMethod::Password) {
+            // No pre-collected password for jump hosts: let the server
+            // prompt via keyboard-interactive instead.
+            Ok(AuthCandidate {
+                auth: ResolvedAuth::KeyboardInteractive,
+                label: "keyboard-interactive".to_string(),
+            })
+        } else {
+            let passphrase = match method {
+                AuthMethod::PublicKey {
+                    key_path: Some(path),
+                    ..
+                } => {
+                    let expanded = crate::config::paths::expand_tilde(&path.to_string_lossy());
+                    passphrase_cache::shared_cache().get(&expanded)
+                }
+                _ => None,
+            };
+            AuthCandidate::resolve(method, None, passphrase).await
+        };
 
-    let passphrase = match &hop.auth {
-        AuthMethod::PublicKey {
-            key_path: Some(path),
-            ..
-        } => {
-            let expanded = crate::config::paths::expand_tilde(&path.to_string_lossy());
-            passphrase_cache::shared_cache().get(&expanded)
+        match resolved {
+            Ok(candidate) => candidates.push(candidate),
+            // The primary method must resolve; fallbacks are best-effort.
+            Err(e) if index == 0 => return Err(e),
+            Err(e) => tracing::debug!("Skipping fallback auth for hop {}: {}", hop.name, e),
         }
-        _ => None,
-    };
+    }
 
-    ResolvedAuth::resolve(&hop.auth, None, passphrase).await
+    Ok(candidates)
 }
 
 async fn open_direct_tcpip(
@@ -402,11 +411,19 @@ mod tests {
             vnc_via_ssh_host_id: None,
             allow_cleartext_vnc: false,
             auth: AuthMethod::Agent,
+            auth_fallbacks: Vec::new(),
             agent_forwarding: false,
             port_forwards: Vec::new(),
             hub_routing: HubRouting::Auto,
             jump_host_id: jump,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging: None,
+            agent_identity: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes: None,
             tags: Vec::new(),
             created_at: now,
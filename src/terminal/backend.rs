@@ -10,8 +10,10 @@ use alacritty_terminal::event::{Event, EventListener, WindowSize};
 use alacritty_terminal::grid::Dimensions;
 use alacritty_terminal::term::Config as TermConfig;
 use alacritty_terminal::term::cell::Flags as CellFlags;
-use alacritty_terminal::term::{Term, TermMode};
-use alacritty_terminal::vte::ansi::{CursorShape, NamedColor, Processor, Rgb};
+use alacritty_terminal::term::{Osc52, Term, TermMode};
+use alacritty_terminal::vte::ansi::{
+    ClearMode, CursorShape, CursorStyle, Handler, NamedColor, Processor, Rgb,
+};
 use iced::Color;
 use iced::advanced::text::Shaping;
 use parking_lot::Mutex;
@@ -24,6 +26,25 @@ const OSC_NOTIFICATION_BUFFER_LIMIT: usize = 16 * 1024;
 const COMMAND_FINISH_NOTIFICATION_THRESHOLD: Duration = Duration::from_secs(5);
 const BRACKETED_PASTE_START: &[u8] = b"\x1b[200~";
 const BRACKETED_PASTE_END: &[u8] = b"\x1b[201~";
+/// Largest clipboard text exchanged over OSC 52, in bytes. Bigger copies from
+/// the remote side are dropped, and bigger clipboards are not sent to it.
+pub const OSC52_MAX_BYTES: usize = 1024 * 1024;
+
+/// Formats clipboard text as the OSC 52 reply a program asked for
+#[derive(Clone)]
+pub struct ClipboardResponder(Arc<dyn Fn(&str) -> String + Sync + Send + 'static>);
+
+impl ClipboardResponder {
+    pub fn reply(&self, text: &str) -> Vec<u8> {
+        (self.0)(text).into_bytes()
+    }
+}
+
+impl std::fmt::Debug for ClipboardResponder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClipboardResponder")
+    }
+}
 
 /// Events emitted by the terminal backend
 #[derive(Debug, Clone)]
@@ -32,13 +53,18 @@ pub enum TerminalEvent {
     Title(String),
     /// Bell rang
     Bell,
-    /// Clipboard request (copy)
+    /// OSC 52 request to set the clipboard
     ClipboardStore(String),
-    /// Clipboard request (paste)
-    ClipboardLoad,
+    /// OSC 52 query for the clipboard contents
+    ClipboardLoad(ClipboardResponder),
     /// Desktop notification requested by the terminal stream.
     Notification { title: String, body: String },
-    /// Shell integration reported that a command finished.
+    /// Shell integration reported the result of a command (every command).
+    CommandStatus {
+        exit_status: Option<i32>,
+        duration: Duration,
+    },
+    /// Shell integration reported that a long-running command finished.
     CommandFinished {
         exit_status: Option<i32>,
         duration: Duration,
@@ -107,8 +133,17 @@ impl EventListener for EventProxy {
             Event::Bell => TerminalEvent::Bell,
             Event::Exit => TerminalEvent::Exit,
             Event::Title(title) => TerminalEvent::Title(title),
-            Event::ClipboardStore(_, data) => TerminalEvent::ClipboardStore(data),
-            Event::ClipboardLoad(_, _) => TerminalEvent::ClipboardLoad,
+            Event::ResetTitle => TerminalEvent::Title(String::new()),
+            Event::ClipboardStore(_, data) => {
+                if data.len() > OSC52_MAX_BYTES {
+                    tracing::warn!("Ignoring {} byte OSC 52 clipboard copy", data.len());
+                    return;
+                }
+                TerminalEvent::ClipboardStore(data)
+            }
+            Event::ClipboardLoad(_, format) => {
+                TerminalEvent::ClipboardLoad(ClipboardResponder(format))
+            }
             Event::ColorRequest(index, format) => {
                 let colors = *self.colors.lock();
                 TerminalEvent::PtyWrite(format(osc_color_for_index(index, &colors)).into_bytes())
@@ -217,6 +252,12 @@ impl TerminalSize {
             history_size: DEFAULT_HISTORY_SIZE,
         }
     }
+
+    /// Use `history_size` lines of scrollback instead of the default.
+    pub fn with_history_size(mut self, history_size: usize) -> Self {
+        self.history_size = history_size;
+        self
+    }
 }
 
 impl Dimensions for TerminalSize {
@@ -277,6 +318,26 @@ pub struct CursorInfo {
     pub line: usize,
     pub shape: CursorShape,
     pub visible: bool,
+    /// Whether the cursor style blinks; programs pick steady or blinking
+    /// styles with DECSCUSR.
+    pub blinking: bool,
+}
+
+/// Terminal config with `history_size` lines of scrollback.
+///
+/// The default cursor style blinks, so the cursor blinks unless a program
+/// asks for a steady style (or blinking is turned off in settings). OSC 52
+/// copy and paste both reach the app, which applies the clipboard setting.
+fn term_config(history_size: usize) -> TermConfig {
+    TermConfig {
+        scrolling_history: history_size,
+        default_cursor_style: CursorStyle {
+            shape: CursorShape::Block,
+            blinking: true,
+        },
+        osc52: Osc52::CopyPaste,
+        ..TermConfig::default()
+    }
 }
 
 /// Terminal backend wrapping alacritty_terminal
@@ -323,9 +384,7 @@ impl OscNotificationParser {
                     });
                 }
 
-                if let Some(event) = self.parse_osc_command_marker(&osc) {
-                    events.push(event);
-                }
+                events.extend(self.parse_osc_command_marker(&osc));
 
                 if let Some(event) = parse_osc_cwd(&osc) {
                     events.push(event);
@@ -355,33 +414,41 @@ impl OscNotificationParser {
         events
     }
 
-    fn parse_osc_command_marker(&mut self, bytes: &[u8]) -> Option<TerminalEvent> {
+    fn parse_osc_command_marker(&mut self, bytes: &[u8]) -> Vec<TerminalEvent> {
         let content = String::from_utf8_lossy(bytes);
         let mut parts = content.split(';');
 
         if parts.next() != Some("133") {
-            return None;
+            return Vec::new();
         }
 
         match parts.next() {
             Some("C") => {
                 self.command_started_at = Some(Instant::now());
-                None
+                Vec::new()
             }
             Some("D") => {
-                let started_at = self.command_started_at.take()?;
+                // A D marker without a preceding C is the prompt closing an
+                // empty command line; there is no result to report.
+                let Some(started_at) = self.command_started_at.take() else {
+                    return Vec::new();
+                };
                 let duration = started_at.elapsed();
-                if duration < COMMAND_FINISH_NOTIFICATION_THRESHOLD {
-                    return None;
-                }
-
                 let exit_status = parts.next().and_then(|status| status.parse::<i32>().ok());
-                Some(TerminalEvent::CommandFinished {
+
+                let mut events = vec![TerminalEvent::CommandStatus {
                     exit_status,
                     duration,
-                })
+                }];
+                if duration >= COMMAND_FINISH_NOTIFICATION_THRESHOLD {
+                    events.push(TerminalEvent::CommandFinished {
+                        exit_status,
+                        duration,
+                    });
+                }
+                events
             }
-            _ => None,
+            _ => Vec::new(),
         }
     }
 }
@@ -497,10 +564,7 @@ impl TerminalBackend {
         let render_epoch = Arc::new(AtomicU64::new(1));
 
         // Create terminal config with scrollback history
-        let config = TermConfig {
-            scrolling_history: size.history_size,
-            ..TermConfig::default()
-        };
+        let config = term_config(size.history_size);
 
         // Create the terminal
         let term = Term::new(config, &size, event_proxy);
@@ -576,10 +640,7 @@ impl TerminalBackend {
         );
         event_proxy.set_muted(true);
 
-        let config = TermConfig {
-            scrolling_history: self.size.history_size,
-            ..TermConfig::default()
-        };
+        let config = term_config(self.size.history_size);
         let mut snapshot = Term::new(config, &self.size, event_proxy.clone());
         let mut snapshot_processor: Processor = Processor::new();
         snapshot_processor.advance(&mut snapshot, bytes);
@@ -590,20 +651,44 @@ impl TerminalBackend {
         self.render_epoch.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Drop all scrollback lines, keeping the visible screen. Works from the
+    /// alternate screen too, where it clears the primary screen's history.
+    pub fn clear_history(&self) {
+        self.term.lock().clear_screen(ClearMode::Saved);
+        self.render_epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Re-create the terminal state from scratch, discarding the screen,
+    /// scrollback, and any modes a program left behind.
+    pub fn reset(&self) {
+        let event_proxy = EventProxy::new(
+            self.event_sender.clone(),
+            self.colors.clone(),
+            self.window_size.clone(),
+        );
+        let config = term_config(self.size.history_size);
+
+        *self.term.lock() = Term::new(config, &self.size, event_proxy);
+        *self.processor.lock() = Processor::new();
+        *self.notification_parser.lock() = OscNotificationParser::default();
+        self.render_epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Current render epoch value (see [`Self::render_epoch`]).
     pub fn current_epoch(&self) -> u64 {
         self.render_epoch.load(Ordering::Relaxed)
     }
 
-    /// Find literal search matches in the whole buffer (scrollback + viewport).
+    /// Find search matches in the whole buffer (scrollback + viewport).
     pub fn search_matches(
         &self,
         query: &str,
         case_sensitive: bool,
+        regex: bool,
         max_matches: usize,
     ) -> Vec<super::search::Match> {
         let term = self.term.lock();
-        super::search::find_matches(&term, query, case_sensitive, max_matches)
+        super::search::find_matches(&term, query, case_sensitive, regex, max_matches)
     }
 
     /// Bottommost visible grid line of the current viewport.
@@ -813,6 +898,39 @@ mod tests {
         assert_eq!(cwd, Some(std::path::PathBuf::from("/root/Code/my project")));
     }
 
+    #[test]
+    fn process_input_emits_osc52_clipboard_events() {
+        let (backend, mut event_rx) = TerminalBackend::new(TerminalSize::new(10, 3));
+
+        backend.process_input(b"\x1b]52;c;cG9ydGFs\x07");
+        backend.process_input(b"\x1b]52;c;?\x07");
+
+        let mut stored = None;
+        let mut reply = None;
+        while let Ok(event) = event_rx.try_recv() {
+            match event {
+                TerminalEvent::ClipboardStore(text) => stored = Some(text),
+                TerminalEvent::ClipboardLoad(responder) => reply = Some(responder.reply("hi")),
+                _ => {}
+            }
+        }
+        assert_eq!(stored.as_deref(), Some("portal"));
+        assert_eq!(reply, Some(b"\x1b]52;c;aGk=\x07".to_vec()));
+    }
+
+    #[test]
+    fn process_input_drops_oversized_osc52_copies() {
+        let (backend, mut event_rx) = TerminalBackend::new(TerminalSize::new(10, 3));
+
+        // Each "QUFB" decodes to three bytes.
+        let payload = "QUFB".repeat(OSC52_MAX_BYTES / 3 + 1);
+        backend.process_input(format!("\x1b]52;c;{payload}\x07").as_bytes());
+
+        while let Ok(event) = event_rx.try_recv() {
+            assert!(!matches!(event, TerminalEvent::ClipboardStore(_)));
+        }
+    }
+
     #[test]
     fn process_input_ignores_malformed_osc7() {
         let (backend, mut event_rx) = TerminalBackend::new(TerminalSize::new(10, 3));
@@ -848,6 +966,23 @@ mod tests {
         assert!(duration >= COMMAND_FINISH_NOTIFICATION_THRESHOLD);
     }
 
+    #[test]
+    fn process_input_reports_status_for_short_osc133_command() {
+        let (backend, mut event_rx) = TerminalBackend::new(TerminalSize::new(10, 3));
+
+        backend.process_input(b"\x1b]133;C\x07");
+        backend.process_input(b"\x1b]133;D;127\x07");
+
+        let status = loop {
+            match event_rx.try_recv() {
+                Ok(TerminalEvent::CommandStatus { exit_status, .. }) => break Some(exit_status),
+                Ok(_) => continue,
+                Err(_) => break None,
+            }
+        };
+        assert_eq!(status, Some(Some(127)));
+    }
+
     #[test]
     fn process_input_suppresses_short_osc133_command() {
         let (backend, mut event_rx) = TerminalBackend::new(TerminalSize::new(10, 3));
@@ -991,6 +1126,42 @@ mod tests {
         assert_eq!(backend.current_epoch(), epoch);
     }
 
+    #[test]
+    fn history_size_caps_scrollback() {
+        let (backend, _event_rx) =
+            TerminalBackend::new(TerminalSize::new(10, 3).with_history_size(5));
+        for i in 0..20 {
+            backend.process_input(format!("line {i}\r\n").as_bytes());
+        }
+        assert_eq!(backend.term.lock().grid().history_size(), 5);
+    }
+
+    #[test]
+    fn clear_history_keeps_screen_and_reset_clears_everything() {
+        let (backend, _event_rx) = TerminalBackend::new(TerminalSize::new(10, 3));
+        for i in 0..10 {
+            backend.process_input(format!("line {i}\r\n").as_bytes());
+        }
+        assert!(backend.term.lock().grid().history_size() > 0);
+
+        backend.clear_history();
+        {
+            let term = backend.term.lock();
+            assert_eq!(term.grid().history_size(), 0);
+            assert_eq!(term.grid()[Line(0)][Column(0)].c, 'l');
+        }
+
+        backend.process_input(b"\x1b[?1049h\x1b[?25l");
+
+        let epoch = backend.current_epoch();
+        backend.reset();
+        assert_ne!(backend.current_epoch(), epoch);
+        let term = backend.term.lock();
+        assert!(!term.mode().contains(TermMode::ALT_SCREEN));
+        assert!(term.mode().contains(TermMode::SHOW_CURSOR));
+        assert_eq!(term.grid()[Line(0)][Column(0)].c, ' ');
+    }
+
     #[test]
     fn process_input_answers_text_area_pixel_query() {
         let (mut backend, mut event_rx) = TerminalBackend::new(TerminalSize::new(10, 3));
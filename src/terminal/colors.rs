@@ -54,6 +54,19 @@ pub fn cell_fg_to_iced(color: AnsiColor, flags: CellFlags, colors: &TerminalColo
     color
 }
 
+/// Map the eight basic ANSI colors of a bold cell to their bright variants,
+/// as terminals with "bold is bright" do. Other colors are left unchanged.
+pub fn bold_as_bright(color: AnsiColor, flags: CellFlags) -> AnsiColor {
+    if !flags.contains(CellFlags::BOLD) {
+        return color;
+    }
+    match color {
+        AnsiColor::Named(named) if (named as usize) < 8 => AnsiColor::Named(named.to_bright()),
+        AnsiColor::Indexed(idx) if idx < 8 => AnsiColor::Indexed(idx + 8),
+        color => color,
+    }
+}
+
 /// Convert a named color to iced Color using themed colors
 fn named_to_iced_themed(named: NamedColor, colors: &TerminalColors) -> Color {
     match named {
@@ -170,6 +183,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bold_as_bright_maps_only_basic_ansi_colors() {
+        let colors = Theme::portal_default().terminal;
+        let bright = |color| {
+            cell_fg_to_iced(
+                bold_as_bright(color, CellFlags::BOLD),
+                CellFlags::BOLD,
+                &colors,
+            )
+        };
+
+        assert_eq!(bright(AnsiColor::Named(NamedColor::Red)), colors.ansi[9]);
+        assert_eq!(bright(AnsiColor::Indexed(2)), colors.ansi[10]);
+        assert_eq!(bright(AnsiColor::Indexed(12)), colors.ansi[12]);
+        assert_eq!(
+            bright(AnsiColor::Named(NamedColor::Foreground)),
+            colors.foreground
+        );
+        assert_eq!(
+            bold_as_bright(AnsiColor::Indexed(1), CellFlags::empty()),
+            AnsiColor::Indexed(1)
+        );
+    }
+
     #[test]
     fn truecolor_is_not_brightened_by_bold_flag() {
         let colors = Theme::portal_default().terminal;
@@ -1,8 +1,15 @@
 //! Terminal session logging utilities
+//!
+//! Output is appended to one file per session, rolling over to numbered
+//! files (`host_date.1.log`, `host_date.2.log`, ...) once a size limit is
+//! reached. Typed input can be recorded as `[input]` lines; input typed
+//! after a password or passphrase prompt is replaced with `[hidden]`.
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use chrono::Local;
+use parking_lot::Mutex;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 
@@ -12,23 +19,43 @@ use crate::fs_utils::{ensure_private_dir_no_follow, open_append_regular_file};
 
 const LOG_QUEUE_CAPACITY: usize = 256;
 
+/// Characters of the latest output line kept to recognise prompts
+const PROMPT_TAIL_LEN: usize = 160;
+
+/// Longest input line recorded; the rest of the line is dropped
+const MAX_INPUT_LINE_LEN: usize = 4096;
+
+/// Prompt words after which typed input is kept out of the log
+const SECRET_PROMPT_WORDS: [&str; 6] = [
+    "password",
+    "passphrase",
+    "passcode",
+    "pin",
+    "verification code",
+    "one-time",
+];
+
 enum LogCommand {
     Write(Vec<u8>),
+    Input(Vec<u8>),
     Shutdown,
 }
 
-/// Async session logger for terminal output.
+/// Async session logger for terminal output and, optionally, typed input.
 pub struct SessionLogger {
-    path: PathBuf,
+    path: Arc<Mutex<PathBuf>>,
     sender: mpsc::Sender<LogCommand>,
     join_handle: tokio::task::JoinHandle<()>,
 }
 
 impl SessionLogger {
+    /// Start logging to a new file under `log_dir`. With `max_bytes` set,
+    /// the log moves on to a numbered file whenever it grows past that size.
     pub fn start(
         host_name: &str,
         log_dir: PathBuf,
         format: SessionLogFormat,
+        max_bytes: Option<u64>,
     ) -> std::io::Result<Self> {
         let log_dir = normalize_log_dir(log_dir);
         prepare_session_log_dir(&log_dir)?;
@@ -36,42 +63,50 @@ impl SessionLogger {
         let filename = session_log_filename(host_name);
         let path = log_dir.join(filename);
         let file = open_session_log_file(&path)?;
+        let current_path = Arc::new(Mutex::new(path.clone()));
 
         let (sender, mut receiver) = mpsc::channel(LOG_QUEUE_CAPACITY);
-        let path_for_task = path.clone();
+        let mut writer = LogWriter {
+            file: tokio::fs::File::from_std(file),
+            base_path: path,
+            current_path: current_path.clone(),
+            format,
+            max_bytes: max_bytes.filter(|max| *max > 0),
+            written: 0,
+            rotations: 0,
+            at_line_start: true,
+            input: InputRecorder::default(),
+        };
 
         let join_handle = tokio::spawn(async move {
-            let mut file = tokio::fs::File::from_std(file);
-            let mut at_line_start = true;
             while let Some(command) = receiver.recv().await {
-                match command {
-                    LogCommand::Write(data) => {
-                        if let Err(error) =
-                            write_log_data(&mut file, &data, format, &mut at_line_start).await
-                        {
-                            tracing::error!(
-                                "Failed writing session log {}: {}",
-                                path_for_task.display(),
-                                error
-                            );
-                        }
-                    }
+                let result = match command {
+                    LogCommand::Write(data) => writer.write_output(&data).await,
+                    LogCommand::Input(data) => writer.write_input(&data).await,
                     LogCommand::Shutdown => break,
+                };
+                if let Err(error) = result {
+                    tracing::error!(
+                        "Failed writing session log {}: {}",
+                        writer.current_path.lock().display(),
+                        error
+                    );
                 }
             }
 
-            let _ = file.flush().await;
+            writer.finish().await;
         });
 
         Ok(Self {
-            path,
+            path: current_path,
             sender,
             join_handle,
         })
     }
 
-    pub fn path(&self) -> &Path {
-        &self.path
+    /// File currently being written, which changes when the log rotates
+    pub fn path(&self) -> PathBuf {
+        self.path.lock().clone()
     }
 
     pub fn write(&self, data: &[u8]) {
@@ -84,12 +119,192 @@ impl SessionLogger {
         }
     }
 
+    /// Record bytes typed into the session
+    pub fn write_input(&self, data: &[u8]) {
+        if self
+            .sender
+            .try_send(LogCommand::Input(data.to_vec()))
+            .is_err()
+        {
+            tracing::warn!("Session log queue full; dropping input chunk");
+        }
+    }
+
     pub async fn shutdown(self) {
         let _ = self.sender.send(LogCommand::Shutdown).await;
         let _ = self.join_handle.await;
     }
 }
 
+/// State of the logging task
+struct LogWriter {
+    file: tokio::fs::File,
+    /// First file of the session; rotated files are numbered after it
+    base_path: PathBuf,
+    current_path: Arc<Mutex<PathBuf>>,
+    format: SessionLogFormat,
+    max_bytes: Option<u64>,
+    written: u64,
+    rotations: u32,
+    at_line_start: bool,
+    input: InputRecorder,
+}
+
+impl LogWriter {
+    async fn write_output(&mut self, data: &[u8]) -> std::io::Result<()> {
+        self.input.observe_output(data);
+        self.append(data).await
+    }
+
+    async fn write_input(&mut self, data: &[u8]) -> std::io::Result<()> {
+        for line in self.input.record(data) {
+            self.write_input_line(&line).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_input_line(&mut self, line: &str) -> std::io::Result<()> {
+        let separator = if self.at_line_start { "" } else { "\n" };
+        let record = format!("{}[input] {}\n", separator, line);
+        self.append(record.as_bytes()).await
+    }
+
+    async fn append(&mut self, data: &[u8]) -> std::io::Result<()> {
+        let written =
+            write_log_data(&mut self.file, data, self.format, &mut self.at_line_start).await?;
+        self.written += written as u64;
+        if self.max_bytes.is_some_and(|max| self.written >= max) {
+            self.rotate().await;
+        }
+        Ok(())
+    }
+
+    async fn rotate(&mut self) {
+        let _ = self.file.flush().await;
+        self.rotations += 1;
+        self.written = 0;
+        let path = rotated_log_path(&self.base_path, self.rotations);
+        match open_session_log_file(&path) {
+            Ok(file) => {
+                self.file = tokio::fs::File::from_std(file);
+                self.at_line_start = true;
+                *self.current_path.lock() = path;
+            }
+            Err(error) => {
+                // Keep appending to the current file rather than losing output
+                tracing::error!(
+                    "Failed to rotate session log to {}: {}",
+                    path.display(),
+                    error
+                );
+            }
+        }
+    }
+
+    async fn finish(mut self) {
+        if let Some(line) = self.input.take_partial_line()
+            && let Err(error) = self.write_input_line(&line).await
+        {
+            tracing::error!("Failed writing session log input: {}", error);
+        }
+        let _ = self.file.flush().await;
+    }
+}
+
+/// Turns keystrokes into readable input lines, hiding those typed at
+/// password prompts. The remote side does not tell us when it turns echo
+/// off, so prompts are recognised from the last line of output.
+#[derive(Debug, Default)]
+struct InputRecorder {
+    /// Latest output line with escape sequences removed
+    prompt_tail: String,
+    in_escape: bool,
+    line: String,
+    hidden: bool,
+}
+
+impl InputRecorder {
+    fn observe_output(&mut self, data: &[u8]) {
+        for ch in String::from_utf8_lossy(data).chars() {
+            if self.in_escape {
+                // CSI sequences end with a letter, OSC titles with BEL
+                self.in_escape = !(ch.is_ascii_alphabetic() || ch == '~' || ch == '\u{7}');
+                continue;
+            }
+            match ch {
+                '\u{1b}' => self.in_escape = true,
+                '\r' | '\n' => self.prompt_tail.clear(),
+                ch if ch.is_control() => {}
+                ch => {
+                    self.prompt_tail.push(ch);
+                    while self.prompt_tail.len() > PROMPT_TAIL_LEN {
+                        self.prompt_tail.remove(0);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Feed typed bytes, returning every line finished by Enter
+    fn record(&mut self, data: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for ch in String::from_utf8_lossy(data).chars() {
+            match ch {
+                '\r' | '\n' => {
+                    let line = std::mem::take(&mut self.line);
+                    lines.push(if self.hidden {
+                        "[hidden]".to_string()
+                    } else {
+                        line
+                    });
+                    self.hidden = false;
+                    // The prompt has been answered
+                    self.prompt_tail.clear();
+                }
+                _ if self.hidden || is_secret_prompt(&self.prompt_tail) => {
+                    self.hidden = true;
+                    self.line.clear();
+                }
+                '\u{7f}' | '\u{8}' => {
+                    self.line.pop();
+                }
+                _ if self.line.len() >= MAX_INPUT_LINE_LEN => {}
+                ch if ch.is_ascii_control() => {
+                    self.line.push('^');
+                    self.line.push(char::from((ch as u8) ^ 0x40));
+                }
+                ch => self.line.push(ch),
+            }
+        }
+        lines
+    }
+
+    /// Input typed since the last Enter, if any
+    fn take_partial_line(&mut self) -> Option<String> {
+        if self.hidden {
+            self.hidden = false;
+            self.line.clear();
+            return Some("[hidden]".to_string());
+        }
+        Some(std::mem::take(&mut self.line)).filter(|line| !line.is_empty())
+    }
+}
+
+/// Whether the output line looks like it is asking for a secret
+fn is_secret_prompt(line: &str) -> bool {
+    let line = line.trim_end().to_lowercase();
+    line.ends_with(':') && SECRET_PROMPT_WORDS.iter().any(|word| line.contains(word))
+}
+
+/// `host_date.log` becomes `host_date.N.log` for the Nth rotation
+fn rotated_log_path(base_path: &Path, rotation: u32) -> PathBuf {
+    let stem = base_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "session".to_string());
+    base_path.with_file_name(format!("{}.{}.log", stem, rotation))
+}
+
 fn open_session_log_file(path: &Path) -> std::io::Result<std::fs::File> {
     open_append_regular_file(path)
 }
@@ -135,10 +350,14 @@ async fn write_log_data(
     data: &[u8],
     format: SessionLogFormat,
     at_line_start: &mut bool,
-) -> std::io::Result<()> {
+) -> std::io::Result<usize> {
     match format {
         SessionLogFormat::Plain => {
             file.write_all(data).await?;
+            if let Some(last) = data.last() {
+                *at_line_start = *last == b'\n';
+            }
+            Ok(data.len())
         }
         SessionLogFormat::Timestamped => {
             let mut output = Vec::with_capacity(data.len() + 16);
@@ -166,10 +385,9 @@ async fn write_log_data(
             }
 
             file.write_all(&output).await?;
+            Ok(output.len())
         }
     }
-
-    Ok(())
 }
 
 fn append_timestamp(output: &mut Vec<u8>) {
@@ -181,7 +399,10 @@ fn append_timestamp(output: &mut Vec<u8>) {
 
 #[cfg(test)]
 mod tests {
-    use super::{SessionLogger, open_session_log_file, prepare_session_log_dir};
+    use super::{
+        InputRecorder, SessionLogger, open_session_log_file, prepare_session_log_dir,
+        rotated_log_path,
+    };
     use crate::config::settings::SessionLogFormat;
 
     #[tokio::test]
@@ -191,9 +412,10 @@ mod tests {
             "example-host",
             temp.path().to_path_buf(),
             SessionLogFormat::Plain,
+            None,
         )
         .expect("session logger should start");
-        let path = logger.path().to_path_buf();
+        let path = logger.path();
 
         logger.write(b"hello\n");
         logger.shutdown().await;
@@ -201,6 +423,73 @@ mod tests {
         assert_eq!(std::fs::read_to_string(path).unwrap(), "hello\n");
     }
 
+    #[tokio::test]
+    async fn session_logger_rotates_to_numbered_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let logger = SessionLogger::start(
+            "example-host",
+            temp.path().to_path_buf(),
+            SessionLogFormat::Plain,
+            Some(8),
+        )
+        .expect("session logger should start");
+        let first = logger.path();
+
+        logger.write(b"0123456789\n");
+        logger.write(b"second\n");
+        logger.shutdown().await;
+
+        let rotated = rotated_log_path(&first, 1);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "0123456789\n");
+        assert_eq!(std::fs::read_to_string(&rotated).unwrap(), "second\n");
+        assert!(
+            rotated
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .ends_with(".1.log")
+        );
+    }
+
+    #[tokio::test]
+    async fn session_logger_records_input_on_its_own_line() {
+        let temp = tempfile::tempdir().unwrap();
+        let logger = SessionLogger::start(
+            "example-host",
+            temp.path().to_path_buf(),
+            SessionLogFormat::Plain,
+            None,
+        )
+        .expect("session logger should start");
+        let path = logger.path();
+
+        logger.write(b"$ ");
+        logger.write_input(b"lx\x7fs");
+        logger.write_input(b"\r");
+        logger.write(b"file.txt\n");
+        logger.shutdown().await;
+
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "$ \n[input] ls\nfile.txt\n"
+        );
+    }
+
+    #[test]
+    fn input_typed_at_password_prompts_is_hidden() {
+        let mut recorder = InputRecorder::default();
+        recorder.observe_output(b"\x1b[1m[sudo] password for alice: \x1b[0m");
+        assert_eq!(recorder.record(b"hunter2\r"), vec!["[hidden]"]);
+        assert!(!recorder.line.contains("hunter"));
+
+        recorder.observe_output(b"\r\nalice@host:~$ ");
+        assert_eq!(recorder.record(b"echo hi\x03\r"), vec!["echo hi^C"]);
+
+        recorder.observe_output(b"Enter passphrase for key '/home/alice/.ssh/id':");
+        assert!(recorder.record(b"secret").is_empty());
+        assert_eq!(recorder.take_partial_line().as_deref(), Some("[hidden]"));
+    }
+
     #[test]
     fn prepare_session_log_dir_creates_missing_dir() {
         let temp = tempfile::tempdir().unwrap();
@@ -278,7 +567,7 @@ mod tests {
         std::fs::create_dir(&target).unwrap();
         std::os::unix::fs::symlink(&target, &link).unwrap();
 
-        assert!(SessionLogger::start("example-host", link, SessionLogFormat::Plain).is_err());
+        assert!(SessionLogger::start("example-host", link, SessionLogFormat::Plain, None).is_err());
 
         assert!(std::fs::read_dir(target).unwrap().next().is_none());
     }
@@ -12,5 +12,6 @@ pub mod metrics;
 mod nerd_font_attributes;
 pub mod search;
 pub mod widget;
+pub mod zmodem;
 
 pub use backend::TerminalBackend;
@@ -1,7 +1,7 @@
 //! Terminal scrollback search (find-in-buffer)
 //!
-//! Wraps alacritty_terminal's regex search to provide literal (escaped)
-//! whole-buffer search with per-session state for the search bar UI.
+//! Wraps alacritty_terminal's regex search to provide literal (escaped) or
+//! regex whole-buffer search with per-session state for the search bar UI.
 
 use alacritty_terminal::grid::Dimensions;
 use alacritty_terminal::index::{Column, Line, Point};
@@ -19,10 +19,14 @@ pub const MAX_SEARCH_MATCHES: usize = 1000;
 pub struct TerminalSearchState {
     /// Whether the search bar is visible for this session.
     pub open: bool,
-    /// Current query text (searched literally, not as a regex).
+    /// Current query text (searched literally unless `regex` is set).
     pub query: String,
     /// Case-sensitive matching. Off by default.
     pub case_sensitive: bool,
+    /// Treat the query as a regular expression. Off by default.
+    pub regex: bool,
+    /// The query is not a valid regex (only possible in regex mode).
+    pub invalid_pattern: bool,
     /// All matches in buffer order (topmost first), capped at
     /// [`MAX_SEARCH_MATCHES`]. Points are in grid coordinates (negative lines
     /// are scrollback).
@@ -78,6 +82,9 @@ impl TerminalSearchState {
         if self.query.is_empty() {
             return None;
         }
+        if self.invalid_pattern {
+            return Some("Invalid regex".to_string());
+        }
         if self.matches.is_empty() {
             return Some("No matches".to_string());
         }
@@ -91,27 +98,39 @@ impl TerminalSearchState {
     }
 }
 
-/// Build the regex pattern for a literal query with explicit case flags.
-pub fn search_pattern(query: &str, case_sensitive: bool) -> String {
+/// Build the regex pattern for a query with explicit case flags. Literal
+/// queries are escaped; regex queries are used as written.
+pub fn search_pattern(query: &str, case_sensitive: bool, regex: bool) -> String {
     // alacritty's RegexSearch applies smart-case on its own; set the flag
     // explicitly so the toggle always wins.
     let flag = if case_sensitive { "(?-i)" } else { "(?i)" };
-    format!("{flag}{}", regex::escape(query))
+    if regex {
+        // Group the query so a top-level alternation stays under the flag.
+        format!("{flag}(?:{query})")
+    } else {
+        format!("{flag}{}", regex::escape(query))
+    }
+}
+
+/// Whether `query` compiles as a search pattern.
+pub fn is_valid_pattern(query: &str, case_sensitive: bool, regex: bool) -> bool {
+    RegexSearch::new(&search_pattern(query, case_sensitive, regex)).is_ok()
 }
 
-/// Find all literal matches of `query` in the whole buffer (scrollback +
-/// viewport), in buffer order, capped at `max_matches`.
+/// Find all matches of `query` in the whole buffer (scrollback + viewport),
+/// in buffer order, capped at `max_matches`.
 pub fn find_matches<T>(
     term: &Term<T>,
     query: &str,
     case_sensitive: bool,
+    regex: bool,
     max_matches: usize,
 ) -> Vec<Match> {
     if query.is_empty() || max_matches == 0 {
         return Vec::new();
     }
 
-    let pattern = search_pattern(query, case_sensitive);
+    let pattern = search_pattern(query, case_sensitive, regex);
     let mut regex = match RegexSearch::new(&pattern) {
         Ok(regex) => regex,
         Err(error) => {
@@ -131,8 +150,8 @@ pub fn find_matches<T>(
             break;
         };
 
-        // Guard against a non-advancing search (defensive; cannot happen for
-        // non-empty literal queries).
+        // Guard against a non-advancing search (regexes that can match the
+        // empty string).
         if matches
             .last()
             .is_some_and(|last| regex_match.start() <= last.start())
@@ -184,8 +203,31 @@ mod tests {
 
     #[test]
     fn search_pattern_escapes_literals_and_sets_case_flag() {
-        assert_eq!(search_pattern("1.5*(a)", false), r"(?i)1\.5\*\(a\)");
-        assert_eq!(search_pattern("Foo", true), "(?-i)Foo");
+        assert_eq!(search_pattern("1.5*(a)", false, false), r"(?i)1\.5\*\(a\)");
+        assert_eq!(search_pattern("Foo", true, false), "(?-i)Foo");
+        assert_eq!(search_pattern("a|b", false, true), "(?i)(?:a|b)");
+    }
+
+    #[test]
+    fn find_matches_supports_regex_mode() {
+        let (backend, _events) = TerminalBackend::new(TerminalSize::new(20, 5));
+        backend.process_input(b"error 42 and ERROR 7");
+
+        let term = backend.term();
+        let term = term.lock();
+
+        let matches = find_matches(&term, r"error \d+", false, true, MAX_SEARCH_MATCHES);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(match_coords(&matches[0]), ((0, 0), (0, 7)));
+        assert_eq!(match_coords(&matches[1]), ((0, 13), (0, 19)));
+
+        let sensitive = find_matches(&term, r"error \d+", true, true, MAX_SEARCH_MATCHES);
+        assert_eq!(sensitive.len(), 1);
+
+        assert!(is_valid_pattern("err(or)?", false, true));
+        assert!(!is_valid_pattern("err(", false, true));
+        assert!(is_valid_pattern("err(", false, false));
+        assert!(find_matches(&term, "err(", false, true, MAX_SEARCH_MATCHES).is_empty());
     }
 
     #[test]
@@ -196,18 +238,18 @@ mod tests {
         let term = backend.term();
         let term = term.lock();
 
-        let matches = find_matches(&term, "foo", false, MAX_SEARCH_MATCHES);
+        let matches = find_matches(&term, "foo", false, false, MAX_SEARCH_MATCHES);
         assert_eq!(matches.len(), 4);
         assert_eq!(match_coords(&matches[0]), ((0, 0), (0, 2)));
         assert_eq!(match_coords(&matches[1]), ((0, 8), (0, 10)));
         assert_eq!(match_coords(&matches[2]), ((1, 0), (1, 2)));
         assert_eq!(match_coords(&matches[3]), ((1, 3), (1, 5)));
 
-        let sensitive = find_matches(&term, "foo", true, MAX_SEARCH_MATCHES);
+        let sensitive = find_matches(&term, "foo", true, false, MAX_SEARCH_MATCHES);
         assert_eq!(sensitive.len(), 3);
         assert_eq!(match_coords(&sensitive[0]), ((0, 0), (0, 2)));
 
-        let upper = find_matches(&term, "Foo", true, MAX_SEARCH_MATCHES);
+        let upper = find_matches(&term, "Foo", true, false, MAX_SEARCH_MATCHES);
         assert_eq!(upper.len(), 1);
         assert_eq!(match_coords(&upper[0]), ((0, 8), (0, 10)));
     }
@@ -220,7 +262,7 @@ mod tests {
         let term = backend.term();
         let term = term.lock();
 
-        let matches = find_matches(&term, "1.5", false, MAX_SEARCH_MATCHES);
+        let matches = find_matches(&term, "1.5", false, false, MAX_SEARCH_MATCHES);
         assert_eq!(matches.len(), 1);
         assert_eq!(match_coords(&matches[0]), ((0, 8), (0, 10)));
     }
@@ -235,7 +277,7 @@ mod tests {
         let term = backend.term();
         let term = term.lock();
 
-        let matches = find_matches(&term, "match", false, MAX_SEARCH_MATCHES);
+        let matches = find_matches(&term, "match", false, false, MAX_SEARCH_MATCHES);
         assert_eq!(matches.len(), 10);
         // Ten lines printed with a trailing newline on a 3-line screen leaves
         // 8 lines in history; the earliest match starts at the top of it.
@@ -253,7 +295,7 @@ mod tests {
         let term = backend.term();
         let term = term.lock();
 
-        assert_eq!(find_matches(&term, "cap", false, 4).len(), 4);
+        assert_eq!(find_matches(&term, "cap", false, false, 4).len(), 4);
     }
 
     #[test]
@@ -264,8 +306,8 @@ mod tests {
         let term = backend.term();
         let term = term.lock();
 
-        assert!(find_matches(&term, "", false, MAX_SEARCH_MATCHES).is_empty());
-        assert!(find_matches(&term, "nothing", false, MAX_SEARCH_MATCHES).is_empty());
+        assert!(find_matches(&term, "", false, false, MAX_SEARCH_MATCHES).is_empty());
+        assert!(find_matches(&term, "nothing", false, false, MAX_SEARCH_MATCHES).is_empty());
     }
 
     #[test]
@@ -277,7 +319,7 @@ mod tests {
         let term = backend.term();
         {
             let term = term.lock();
-            state.matches = find_matches(&term, "one", false, MAX_SEARCH_MATCHES);
+            state.matches = find_matches(&term, "one", false, false, MAX_SEARCH_MATCHES);
         }
         assert_eq!(state.matches.len(), 3);
 
@@ -309,7 +351,7 @@ mod tests {
 
         let term = backend.term();
         let term = term.lock();
-        let matches = find_matches(&term, "hit", false, MAX_SEARCH_MATCHES);
+        let matches = find_matches(&term, "hit", false, false, MAX_SEARCH_MATCHES);
 
         // Viewport at the bottom (bottom grid line = screen_lines - 1 = 2):
         // pick the last match at or above it.
@@ -330,6 +372,10 @@ mod tests {
         state.query = "x".to_string();
         assert_eq!(state.counter_label(), Some("No matches".to_string()));
 
+        state.invalid_pattern = true;
+        assert_eq!(state.counter_label(), Some("Invalid regex".to_string()));
+        state.invalid_pattern = false;
+
         state.matches = vec![
             Point::new(Line(0), Column(0))..=Point::new(Line(0), Column(0)),
             Point::new(Line(1), Column(0))..=Point::new(Line(1), Column(0)),
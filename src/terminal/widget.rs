@@ -26,7 +26,7 @@ use parking_lot::Mutex;
 
 use super::backend::{CursorInfo, EventProxy, RenderCell, paste_bytes_for_mode};
 use super::block_elements::{TerminalGraphicCell, render_terminal_graphic};
-use super::colors::{DEFAULT_BG, DEFAULT_FG, ansi_to_iced_themed, cell_fg_to_iced};
+use super::colors::{DEFAULT_BG, DEFAULT_FG, ansi_to_iced_themed, bold_as_bright, cell_fg_to_iced};
 use super::glyph_constraints::GlyphSize;
 use super::links::{self, LinkRegex, ScreenSpan, TerminalLink};
 use super::metrics::{TERMINAL_PADDING_LEFT, TerminalMetrics};
@@ -34,10 +34,10 @@ use super::nerd_font_attributes;
 use super::search::Match;
 use crate::config::settings::{
     TERMINAL_SCROLL_SPEED_BASE, TERMINAL_SCROLL_SPEED_MAX, TERMINAL_SCROLL_SPEED_MIN,
-    TerminalMetricAdjustments,
+    TerminalMetricAdjustments, TerminalTextStyle,
 };
 use crate::fonts::{JETBRAINS_MONO_NERD, TerminalFont};
-use crate::keybindings::{AppAction, KeybindingsConfig};
+use crate::keybindings::{AppAction, Keybinding, KeybindingsConfig};
 use crate::theme::TerminalColors;
 
 fn is_powerline_separator(c: char) -> bool {
@@ -218,11 +218,18 @@ pub struct TerminalWidget<'a, Message> {
     font: iced::Font,
     terminal_font: TerminalFont,
     terminal_metric_adjustments: TerminalMetricAdjustments,
+    text_style: TerminalTextStyle,
     terminal_colors: Option<TerminalColors>,
     render_epoch: Option<Arc<AtomicU64>>,
     keybindings: KeybindingsConfig,
+    /// Extra key combos handled by the app (macro shortcuts); never sent to the PTY.
+    reserved_keys: Vec<Keybinding>,
     scroll_speed: f32,
     focus_token: u64,
+    /// Blink phase of the cursor: `false` hides a blinking cursor style.
+    cursor_blink_visible: bool,
+    /// Whether the visual bell is flashing the terminal
+    bell_flash: bool,
     /// Whether keyboard input is forwarded to the terminal. Disabled while the
     /// search bar owns the keyboard so keystrokes are not sent to the PTY.
     keyboard_input: bool,
@@ -254,11 +261,15 @@ impl<'a, Message> TerminalWidget<'a, Message> {
             font: JETBRAINS_MONO_NERD,
             terminal_font: TerminalFont::default(),
             terminal_metric_adjustments: TerminalMetricAdjustments::default(),
+            text_style: TerminalTextStyle::default(),
             terminal_colors: None,
             render_epoch: None,
             keybindings: KeybindingsConfig::default(),
+            reserved_keys: Vec::new(),
             scroll_speed: TERMINAL_SCROLL_SPEED_BASE,
             focus_token: 0,
+            cursor_blink_visible: true,
+            bell_flash: false,
             keyboard_input: true,
             search_matches: &[],
             search_current: None,
@@ -293,6 +304,12 @@ impl<'a, Message> TerminalWidget<'a, Message> {
         self
     }
 
+    /// Set ligature and bold color options.
+    pub fn text_style(mut self, style: TerminalTextStyle) -> Self {
+        self.text_style = style;
+        self
+    }
+
     /// Set terminal colors from theme
     pub fn terminal_colors(mut self, colors: TerminalColors) -> Self {
         self.terminal_colors = Some(colors);
@@ -311,12 +328,30 @@ impl<'a, Message> TerminalWidget<'a, Message> {
         self
     }
 
+    /// Set key combos the app handles itself, such as macro shortcuts
+    pub fn reserved_keys(mut self, keys: Vec<Keybinding>) -> Self {
+        self.reserved_keys = keys;
+        self
+    }
+
     /// Request keyboard focus when this token changes.
     pub fn focus_token(mut self, token: u64) -> Self {
         self.focus_token = token;
         self
     }
 
+    /// Set the blink phase; `false` hides the cursor when its style blinks.
+    pub fn cursor_blink_visible(mut self, visible: bool) -> Self {
+        self.cursor_blink_visible = visible;
+        self
+    }
+
+    /// Flash the terminal for the visual bell.
+    pub fn bell_flash(mut self, flash: bool) -> Self {
+        self.bell_flash = flash;
+        self
+    }
+
     /// Enable or disable forwarding keyboard input to the terminal.
     ///
     /// Disable while another widget (e.g. the search bar) owns the keyboard.
@@ -405,11 +440,13 @@ impl<'a, Message> TerminalWidget<'a, Message> {
         // Cursor: convert grid line to screen line by adding display_offset.
         // Skip cursor if outside visible screen (scrolled out of view).
         let cursor_screen_line = cursor.point.line.0 + display_offset as i32;
+        let cursor_blinking = term.cursor_style().blinking;
         cache.cursor = (cursor_screen_line >= 0).then(|| CursorInfo {
             column: cursor.point.column.0,
             line: cursor_screen_line as usize,
             shape: cursor.shape,
             visible: cursor.shape != CursorShape::Hidden,
+            blinking: cursor_blinking,
         });
 
         cache.cells.clear();
@@ -475,7 +512,12 @@ impl<'a, Message> TerminalWidget<'a, Message> {
                 || !cell.flags.is_empty()
             {
                 // Resolve final colors once: theme palette + DIM, then INVERSE swap.
-                let mut fg = cell_fg_to_iced(cell.fg, cell.flags, colors);
+                let fg_color = if self.text_style.bold_is_bright {
+                    bold_as_bright(cell.fg, cell.flags)
+                } else {
+                    cell.fg
+                };
+                let mut fg = cell_fg_to_iced(fg_color, cell.flags, colors);
                 let mut bg = ansi_to_iced_themed(cell.bg, colors);
                 if cell.flags.contains(CellFlags::INVERSE) {
                     std::mem::swap(&mut fg, &mut bg);
@@ -1003,7 +1045,6 @@ fn selection_side(offset: f32, column: usize, cell_width: f32) -> Option<Side> {
 #[derive(Debug)]
 struct TerminalState {
     is_focused: bool,
-    cursor_visible: bool,
     last_size: Option<(u16, u16)>,
     scroll_pixels: f32, // Accumulated scroll pixels for trackpad
     scroll_lines: f32,  // Accumulated fractional line scroll for mouse wheels
@@ -1031,6 +1072,82 @@ struct TerminalState {
     link_regex: Option<LinkRegex>,
 }
 
+/// Consecutive plain ASCII glyphs on one line sharing a color and font
+/// variant. Blank cells inside the run are spaces in `text`.
+#[derive(Debug, Clone, PartialEq)]
+struct TextRun {
+    line: usize,
+    column: usize,
+    text: String,
+    fg: Color,
+    bold: bool,
+    italic: bool,
+}
+
+/// Longest run, in columns, whose glyphs stay within half a pixel of their
+/// cells. The font advances `face_width` per glyph while the grid advances the
+/// rounded `cell_width`, so the mismatch accumulates along a run.
+fn max_run_columns(metrics: TerminalMetrics) -> usize {
+    let drift = (metrics.cell_width - metrics.face_width).abs();
+    if drift <= f32::EPSILON {
+        usize::MAX
+    } else {
+        ((0.5 / drift) as usize).max(1)
+    }
+}
+
+/// Group the glyphs of `cells` into text runs of at most `max_columns`.
+///
+/// Only basic-shaped cells (plain ASCII without combining chars) join runs;
+/// wide chars, combining chars, terminal graphics, and Nerd Font symbols need
+/// per-cell placement and are listed in `single_glyphs`. Blank and hidden
+/// cells draw nothing and neither start nor break a run.
+fn build_text_runs(
+    cells: &[RenderCell],
+    max_columns: usize,
+    runs: &mut Vec<TextRun>,
+    single_glyphs: &mut Vec<usize>,
+) {
+    runs.clear();
+    single_glyphs.clear();
+
+    for (index, cell) in cells.iter().enumerate() {
+        if cell.character == ' ' || cell.flags.contains(CellFlags::HIDDEN) {
+            continue;
+        }
+        if cell.shaping != Shaping::Basic || cell.flags.contains(CellFlags::WIDE_CHAR) {
+            single_glyphs.push(index);
+            continue;
+        }
+
+        let bold = cell.flags.contains(CellFlags::BOLD);
+        let italic = cell.flags.contains(CellFlags::ITALIC);
+        if let Some(run) = runs.last_mut()
+            && run.line == cell.line
+            && run.fg == cell.fg
+            && run.bold == bold
+            && run.italic == italic
+            && cell.column - run.column < max_columns
+        {
+            // Run text is ASCII, so its byte length is its column count.
+            let end = run.column + run.text.len();
+            run.text
+                .extend(std::iter::repeat_n(' ', cell.column.saturating_sub(end)));
+            run.text.push(cell.character);
+            continue;
+        }
+
+        runs.push(TextRun {
+            line: cell.line,
+            column: cell.column,
+            text: cell.character.to_string(),
+            fg: cell.fg,
+            bold,
+            italic,
+        });
+    }
+}
+
 /// Hovered link underline state (screen spans + probe epoch).
 #[derive(Debug)]
 struct HoveredLink {
@@ -1054,9 +1171,17 @@ struct RenderCache {
     search_version: u64,
     /// Reusable flat rows*cols scratch buffer for constraint-width lookups.
     row_chars: Vec<char>,
+    /// Plain glyphs batched into one `fill_text` per run.
+    text_runs: Vec<TextRun>,
+    /// Indices into `cells` of glyphs that are drawn one at a time.
+    single_glyphs: Vec<usize>,
+    /// Run length cap the runs were built with; 0 when not built yet.
+    run_columns: usize,
     /// Theme colors the cached cells were resolved with; a theme change
     /// invalidates the cache.
     colors: Option<TerminalColors>,
+    /// Whether cells were resolved with bold-is-bright colors.
+    bold_is_bright: bool,
     epoch: u64,
     needs_refresh: bool,
 }
@@ -1070,6 +1195,7 @@ impl std::fmt::Debug for RenderCache {
             .field("search_spans", &self.search_spans)
             .field("active_search_spans", &self.active_search_spans)
             .field("search_version", &self.search_version)
+            .field("text_runs", &self.text_runs.len())
             .field("has_colors", &self.colors.is_some())
             .field("epoch", &self.epoch)
             .field("needs_refresh", &self.needs_refresh)
@@ -1088,7 +1214,6 @@ impl Default for TerminalState {
     fn default() -> Self {
         Self {
             is_focused: true,
-            cursor_visible: true,
             last_size: None,
             scroll_pixels: 0.0,
             scroll_lines: 0.0,
@@ -1144,7 +1269,6 @@ where
     fn state(&self) -> widget::tree::State {
         widget::tree::State::new(TerminalState {
             is_focused: true,
-            cursor_visible: true,
             last_size: None,
             scroll_pixels: 0.0,
             scroll_lines: 0.0,
@@ -1182,7 +1306,7 @@ where
         let bounds = layout.bounds();
         let state = tree.state.downcast_ref::<TerminalState>();
         let is_focused = state.is_focused || state.last_focus_token != self.focus_token;
-        let cursor_visible = state.cursor_visible;
+        let cursor_blink_visible = self.cursor_blink_visible;
 
         // Get terminal colors (from theme or defaults)
         let default_colors = TerminalColors {
@@ -1233,6 +1357,10 @@ where
             {
                 needs_refresh = true;
             }
+            if cache.bold_is_bright != self.text_style.bold_is_bright {
+                cache.bold_is_bright = self.text_style.bold_is_bright;
+                needs_refresh = true;
+            }
             if cache.search_version != self.search_version {
                 cache.search_version = self.search_version;
                 needs_refresh = true;
@@ -1244,6 +1372,20 @@ where
                 cache.needs_refresh = false;
             }
 
+            // Runs depend on the cells and on the font metrics, which can
+            // change without new terminal content.
+            let run_columns = max_run_columns(metrics);
+            if needs_refresh || cache.run_columns != run_columns {
+                let RenderCache {
+                    cells,
+                    text_runs,
+                    single_glyphs,
+                    ..
+                } = &mut *cache;
+                build_text_runs(cells, run_columns, text_runs, single_glyphs);
+                cache.run_columns = run_columns;
+            }
+
             let cached_cursor = cache.cursor.clone();
             drop(cache);
 
@@ -1348,8 +1490,47 @@ where
             }
 
             // Draw glyphs after all backgrounds so non-standard terminal glyphs are
-            // not clipped by the next cell's background.
-            for cell in &render_cache.cells {
+            // not clipped by the next cell's background. Plain text goes out a
+            // run at a time; everything else is placed cell by cell. Only
+            // advanced shaping applies the font's ligatures.
+            let run_shaping = if self.text_style.ligatures {
+                Shaping::Advanced
+            } else {
+                Shaping::Basic
+            };
+            for run in &render_cache.text_runs {
+                let text = iced::advanced::Text {
+                    content: run.text.clone(),
+                    bounds: Size::new(
+                        run.text.len() as f32 * cell_width.max(metrics.face_width),
+                        cell_height,
+                    ),
+                    size: iced::Pixels(self.font_size),
+                    line_height: iced::advanced::text::LineHeight::Absolute(iced::Pixels(
+                        cell_height,
+                    )),
+                    font: self.terminal_font.variant(run.bold, run.italic),
+                    align_x: iced::alignment::Horizontal::Left.into(),
+                    align_y: iced::alignment::Vertical::Top,
+                    shaping: run_shaping,
+                    wrapping: iced::advanced::text::Wrapping::None,
+                };
+                renderer.fill_text(
+                    text,
+                    iced::Point::new(
+                        bounds.x + TERMINAL_PADDING_LEFT + run.column as f32 * cell_width,
+                        bounds.y + run.line as f32 * cell_height,
+                    ),
+                    run.fg,
+                    bounds,
+                );
+            }
+
+            for cell in render_cache
+                .single_glyphs
+                .iter()
+                .filter_map(|&index| render_cache.cells.get(index))
+            {
                 let x = bounds.x + TERMINAL_PADDING_LEFT + cell.column as f32 * cell_width;
                 let y = bounds.y + cell.line as f32 * cell_height;
                 let fg_color = cell.fg;
@@ -1492,10 +1673,12 @@ where
             }
 
             // Draw cursor (only if visible and in valid position)
+            // A blinking cursor style is hidden during the off phase of the
+            // app's blink timer; steady styles ignore it.
             if is_focused
-                && cursor_visible
                 && let Some(cursor_info) = cached_cursor
                 && cursor_info.visible
+                && (cursor_blink_visible || !cursor_info.blinking)
             {
                 let cursor_x =
                     bounds.x + TERMINAL_PADDING_LEFT + cursor_info.column as f32 * cell_width;
@@ -1582,6 +1765,22 @@ where
                     }
                 }
             }
+
+            // Visual bell: wash the whole terminal in the foreground color.
+            if self.bell_flash {
+                renderer.fill_quad(
+                    Quad {
+                        bounds,
+                        border: Border::default(),
+                        shadow: Shadow::default(),
+                        snap: true,
+                    },
+                    Background::Color(Color {
+                        a: 0.15,
+                        ..colors.foreground
+                    }),
+                );
+            }
         });
     }
 
@@ -1707,6 +1906,26 @@ where
                 }
                 state.mouse_button = None;
             }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle))
+                if cursor.is_over(bounds) =>
+            {
+                // X11-style middle-click paste: the primary selection, falling
+                // back to the clipboard where there is no primary selection.
+                state.is_focused = true;
+                let text_content = clipboard
+                    .read(iced::advanced::clipboard::Kind::Primary)
+                    .filter(|text| !text.is_empty())
+                    .or_else(|| clipboard.read(iced::advanced::clipboard::Kind::Standard));
+                if let Some(text_content) = text_content {
+                    let bytes = {
+                        let term = self.term.lock();
+                        paste_bytes_for_mode(&text_content, term.mode())
+                    };
+                    if !bytes.is_empty() {
+                        shell.publish((self.on_input)(bytes));
+                    }
+                }
+            }
             Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
                 if cursor.is_over(bounds) =>
             {
@@ -1898,6 +2117,9 @@ where
                 if state.is_selecting =>
             {
                 state.is_selecting = false;
+                if let Some(text_content) = self.selected_text().filter(|text| !text.is_empty()) {
+                    clipboard.write(iced::advanced::clipboard::Kind::Primary, text_content);
+                }
                 state.last_drag_anchor = None;
                 state.last_drag_update = None;
                 state.last_auto_scroll = None;
@@ -1968,11 +2190,28 @@ where
                 text,
                 ..
             }) if state.is_focused && self.keyboard_input => {
-                // The search binding is handled at the app level; never forward
-                // it to the PTY (Ctrl+Shift+F would otherwise send ^F).
-                if self
-                    .keybindings
-                    .matches_action(AppAction::TerminalSearch, key, modifiers)
+                // The search, clear, reset, split, snippet palette and zoom
+                // bindings and macro shortcuts are handled at the app level;
+                // never forward them to the PTY (Ctrl+Shift+F would otherwise
+                // send ^F).
+                if [
+                    AppAction::TerminalSearch,
+                    AppAction::ClearScrollback,
+                    AppAction::ResetTerminal,
+                    AppAction::SplitRight,
+                    AppAction::SplitDown,
+                    AppAction::FocusNextPane,
+                    AppAction::SnippetPalette,
+                    AppAction::ZoomIn,
+                    AppAction::ZoomOut,
+                    AppAction::ZoomReset,
+                ]
+                .into_iter()
+                .any(|action| self.keybindings.matches_action(action, key, modifiers))
+                    || self
+                        .reserved_keys
+                        .iter()
+                        .any(|binding| binding.matches(key, modifiers))
                 {
                     return;
                 }
@@ -2428,6 +2667,33 @@ mod tests {
         assert!(cache.cursor.is_some());
     }
 
+    #[test]
+    fn refresh_cache_tracks_decscusr_cursor_blinking() {
+        let (backend, _events) = TerminalBackend::new(TerminalSize::new(10, 3));
+        let widget = TerminalWidget::<()>::new(backend.term(), |_| ());
+        let colors = TerminalColors {
+            foreground: DEFAULT_FG,
+            background: DEFAULT_BG,
+            cursor: DEFAULT_FG,
+            ansi: crate::terminal::colors::ANSI_COLORS,
+        };
+        let mut cache = RenderCache::default();
+        let blinking = |cache: &RenderCache| cache.cursor.as_ref().map(|cursor| cursor.blinking);
+
+        // The default style blinks.
+        widget.refresh_render_cache(&mut cache, &colors);
+        assert_eq!(blinking(&cache), Some(true));
+
+        // CSI 2 q selects a steady block; CSI 0 q restores the default.
+        backend.process_input(b"\x1b[2 q");
+        widget.refresh_render_cache(&mut cache, &colors);
+        assert_eq!(blinking(&cache), Some(false));
+
+        backend.process_input(b"\x1b[0 q");
+        widget.refresh_render_cache(&mut cache, &colors);
+        assert_eq!(blinking(&cache), Some(true));
+    }
+
     #[test]
     fn refresh_cache_reuses_buffers_and_tracks_selection_spans() {
         let (backend, _events) = TerminalBackend::new(TerminalSize::new(10, 3));
@@ -2474,7 +2740,7 @@ mod tests {
         let term = backend.term();
         let matches = {
             let term = term.lock();
-            crate::terminal::search::find_matches(&term, "hit", false, 100)
+            crate::terminal::search::find_matches(&term, "hit", false, false, 100)
         };
         assert_eq!(matches.len(), 2);
 
@@ -2509,7 +2775,7 @@ mod tests {
         let term = backend.term();
         let matches = {
             let term = term.lock();
-            crate::terminal::search::find_matches(&term, "hit", false, 100)
+            crate::terminal::search::find_matches(&term, "hit", false, false, 100)
         };
         assert_eq!(matches.len(), 10);
 
@@ -2536,6 +2802,58 @@ mod tests {
         assert_eq!(cache.active_search_spans, vec![(1, 0, 2)]);
     }
 
+    #[test]
+    fn text_runs_batch_plain_glyphs_and_keep_special_cells_separate() {
+        let (backend, _events) = TerminalBackend::new(TerminalSize::new(12, 3));
+        // Plain text with a gap, an inverse cell, a block element, a wide CJK
+        // char, then plain text again; a dim word on the next line.
+        backend.process_input("ab cd\x1b[7mX\x1b[0m█你e".as_bytes());
+        backend.process_input(b"\r\n\x1b[2mdim\x1b[0m ok");
+
+        let widget = TerminalWidget::<()>::new(backend.term(), |_| ());
+        let colors = TerminalColors {
+            foreground: DEFAULT_FG,
+            background: DEFAULT_BG,
+            cursor: DEFAULT_FG,
+            ansi: crate::terminal::colors::ANSI_COLORS,
+        };
+        let mut cache = RenderCache::default();
+        widget.refresh_render_cache(&mut cache, &colors);
+
+        let mut runs = Vec::new();
+        let mut singles = Vec::new();
+        build_text_runs(&cache.cells, usize::MAX, &mut runs, &mut singles);
+
+        let summary: Vec<(usize, usize, &str)> = runs
+            .iter()
+            .map(|run| (run.line, run.column, run.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 0, "ab cd"),
+                (0, 5, "X"),
+                (0, 9, "e"),
+                (1, 0, "dim"),
+                (1, 4, "ok"),
+            ]
+        );
+        // INVERSE and DIM resolve to different colors, so they split runs.
+        assert_eq!(runs[1].fg, colors.background);
+        assert_ne!(runs[3].fg, runs[4].fg);
+
+        let single_chars: Vec<char> = singles
+            .iter()
+            .map(|&index| cache.cells[index].character)
+            .collect();
+        assert_eq!(single_chars, vec!['█', '你']);
+
+        // A drift cap of two columns splits the first word pair.
+        build_text_runs(&cache.cells, 2, &mut runs, &mut singles);
+        assert_eq!(runs[0].text, "ab");
+        assert_eq!((runs[1].column, runs[1].text.as_str()), (3, "cd"));
+    }
+
     #[test]
     fn arrow_keys_use_normal_cursor_sequences_by_default() {
         assert_eq!(
@@ -0,0 +1,778 @@
+//! ZMODEM receive support
+//!
+//! A sans-IO receiver for files sent with `sz` over the terminal channel.
+//! [`find_start`] spots the sender's `ZRQINIT` header in terminal output; from
+//! then on every byte of the session stream goes to [`ZmodemReceiver::feed`],
+//! which returns file events plus the reply bytes that must be written back to
+//! the session.
+
+/// Frame delimiter / escape character (also CAN)
+const ZDLE: u8 = 0x18;
+const ZPAD: u8 = b'*';
+const ZBIN: u8 = b'A';
+const ZHEX: u8 = b'B';
+const ZBIN32: u8 = b'C';
+
+// Data subpacket frame ends
+const ZCRCE: u8 = b'h';
+const ZCRCG: u8 = b'i';
+const ZCRCQ: u8 = b'j';
+const ZCRCW: u8 = b'k';
+const ZRUB0: u8 = b'l';
+const ZRUB1: u8 = b'm';
+
+// Frame types
+const ZRQINIT: u8 = 0;
+const ZRINIT: u8 = 1;
+const ZSINIT: u8 = 2;
+const ZACK: u8 = 3;
+const ZFILE: u8 = 4;
+const ZSKIP: u8 = 5;
+const ZABORT: u8 = 7;
+const ZFIN: u8 = 8;
+const ZRPOS: u8 = 9;
+const ZDATA: u8 = 10;
+const ZEOF: u8 = 11;
+const ZFERR: u8 = 12;
+const ZCAN: u8 = 16;
+const ZCOMMAND: u8 = 18;
+
+// ZRINIT capability flags (ZF0)
+const CANFDX: u8 = 0x01;
+const CANOVIO: u8 = 0x02;
+const CANFC32: u8 = 0x20;
+
+/// Hex `ZRQINIT` header prefix sent by `sz` when a transfer starts
+const START_SEQUENCE: &[u8] = b"**\x18B00";
+
+/// Largest data subpacket accepted (ZMODEM-8k plus slack)
+const MAX_SUBPACKET: usize = 16 * 1024;
+
+/// Bytes that cancel a transfer on the remote side: CANs followed by
+/// backspaces to erase them from a shell prompt.
+pub const CANCEL_SEQUENCE: &[u8] = &[
+    ZDLE, ZDLE, ZDLE, ZDLE, ZDLE, ZDLE, ZDLE, ZDLE, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08,
+    0x08, 0x08,
+];
+
+/// Find where a ZMODEM transfer starts in a chunk of terminal output.
+pub fn find_start(data: &[u8]) -> Option<usize> {
+    data.windows(START_SEQUENCE.len())
+        .position(|window| window == START_SEQUENCE)
+}
+
+/// Something the application has to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZmodemEvent {
+    /// The sender offers a file; following `FileData` belongs to it
+    FileStart { name: String, size: Option<u64> },
+    /// Next chunk of the current file
+    FileData(Vec<u8>),
+    /// The current file was received completely
+    FileEnd,
+    /// The sender finished the session
+    Finished,
+    /// The sender cancelled or the stream could not be understood
+    Aborted(String),
+}
+
+/// Result of feeding bytes into the receiver
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ZmodemOutput {
+    /// Bytes to write back to the session
+    pub reply: Vec<u8>,
+    pub events: Vec<ZmodemEvent>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Subpacket {
+    Sinit,
+    FileInfo,
+    Data,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Waiting for the next header; anything else is skipped
+    Header,
+    /// Reading a data subpacket that follows a header
+    Subpacket { kind: Subpacket, crc32: bool },
+    /// Session ended (finished or aborted)
+    Done,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Header {
+    frame_type: u8,
+    data: [u8; 4],
+    crc32: bool,
+}
+
+impl Header {
+    fn position(&self) -> u32 {
+        u32::from_le_bytes(self.data)
+    }
+}
+
+enum Parse<T> {
+    /// Parsed value and number of bytes consumed
+    Done(T, usize),
+    /// More input needed
+    Incomplete,
+    /// Malformed input; skip this many bytes
+    Invalid(usize),
+}
+
+/// ZMODEM receiver state machine
+#[derive(Debug)]
+pub struct ZmodemReceiver {
+    state: State,
+    buffer: Vec<u8>,
+    file_offset: u32,
+    receiving_file: bool,
+    consecutive_can: usize,
+}
+
+impl Default for ZmodemReceiver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZmodemReceiver {
+    pub fn new() -> Self {
+        Self {
+            state: State::Header,
+            buffer: Vec::new(),
+            file_offset: 0,
+            receiving_file: false,
+            consecutive_can: 0,
+        }
+    }
+
+    /// Whether the session has ended
+    pub fn is_done(&self) -> bool {
+        self.state == State::Done
+    }
+
+    /// Bytes received after the session ended, minus the sender's trailing
+    /// "OO"; these belong to the terminal again.
+    pub fn take_trailing(&mut self) -> Vec<u8> {
+        let mut trailing = std::mem::take(&mut self.buffer);
+        let skip = trailing
+            .iter()
+            .take(2)
+            .take_while(|byte| **byte == b'O')
+            .count();
+        trailing.drain(..skip);
+        trailing
+    }
+
+    /// Cancel the transfer; returns the bytes to send to the remote side.
+    pub fn abort(&mut self) -> Vec<u8> {
+        self.state = State::Done;
+        self.buffer.clear();
+        CANCEL_SEQUENCE.to_vec()
+    }
+
+    /// Process bytes read from the session.
+    pub fn feed(&mut self, input: &[u8]) -> ZmodemOutput {
+        let mut output = ZmodemOutput::default();
+        if self.state == State::Done {
+            self.buffer.extend_from_slice(input);
+            return output;
+        }
+
+        for &byte in input {
+            if byte == ZDLE {
+                self.consecutive_can += 1;
+                if self.consecutive_can >= 5 {
+                    self.state = State::Done;
+                    self.buffer.clear();
+                    output.events.push(ZmodemEvent::Aborted(
+                        "Transfer cancelled by sender".to_string(),
+                    ));
+                    return output;
+                }
+            } else {
+                self.consecutive_can = 0;
+            }
+        }
+        self.buffer.extend_from_slice(input);
+
+        loop {
+            let progressed = match self.state {
+                State::Header => self.process_header(&mut output),
+                State::Subpacket { kind, crc32 } => {
+                    self.process_subpacket(kind, crc32, &mut output)
+                }
+                State::Done => false,
+            };
+            if !progressed {
+                break;
+            }
+        }
+        output
+    }
+
+    fn process_header(&mut self, output: &mut ZmodemOutput) -> bool {
+        let Some(start) = self.buffer.iter().position(|byte| *byte == ZPAD) else {
+            self.buffer.clear();
+            return false;
+        };
+        self.buffer.drain(..start);
+
+        match parse_header(&self.buffer) {
+            Parse::Done(header, consumed) => {
+                self.buffer.drain(..consumed);
+                self.handle_header(header, output);
+                true
+            }
+            Parse::Incomplete => false,
+            Parse::Invalid(skip) => {
+                self.buffer.drain(..skip);
+                true
+            }
+        }
+    }
+
+    fn handle_header(&mut self, header: Header, output: &mut ZmodemOutput) {
+        match header.frame_type {
+            ZRQINIT => output.reply.extend(receiver_init()),
+            ZSINIT => {
+                self.state = State::Subpacket {
+                    kind: Subpacket::Sinit,
+                    crc32: header.crc32,
+                };
+            }
+            ZFILE => {
+                self.state = State::Subpacket {
+                    kind: Subpacket::FileInfo,
+                    crc32: header.crc32,
+                };
+            }
+            ZDATA if self.receiving_file => {
+                if header.position() == self.file_offset {
+                    self.state = State::Subpacket {
+                        kind: Subpacket::Data,
+                        crc32: header.crc32,
+                    };
+                } else {
+                    output
+                        .reply
+                        .extend(hex_header(ZRPOS, self.file_offset.to_le_bytes()));
+                }
+            }
+            ZEOF if self.receiving_file && header.position() == self.file_offset => {
+                self.receiving_file = false;
+                output.events.push(ZmodemEvent::FileEnd);
+                output.reply.extend(receiver_init());
+            }
+            ZFIN => {
+                output.reply.extend(hex_header(ZFIN, [0; 4]));
+                output.events.push(ZmodemEvent::Finished);
+                self.state = State::Done;
+            }
+            ZCOMMAND => {
+                output.reply.extend(self.abort());
+                output.events.push(ZmodemEvent::Aborted(
+                    "Remote commands are not supported".to_string(),
+                ));
+            }
+            ZABORT | ZFERR | ZCAN => {
+                self.state = State::Done;
+                self.buffer.clear();
+                output.events.push(ZmodemEvent::Aborted(
+                    "Transfer aborted by sender".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    fn process_subpacket(
+        &mut self,
+        kind: Subpacket,
+        crc32: bool,
+        output: &mut ZmodemOutput,
+    ) -> bool {
+        let (data, frame_end, consumed) = match parse_subpacket(&self.buffer, crc32) {
+            Parse::Done((data, frame_end), consumed) => (data, frame_end, consumed),
+            Parse::Incomplete => {
+                if self.buffer.len() > MAX_SUBPACKET * 2 + 16 {
+                    self.subpacket_error(kind, output);
+                    return true;
+                }
+                return false;
+            }
+            Parse::Invalid(_) => {
+                self.subpacket_error(kind, output);
+                return true;
+            }
+        };
+        self.buffer.drain(..consumed);
+
+        match kind {
+            Subpacket::Sinit => {
+                self.state = State::Header;
+                output.reply.extend(hex_header(ZACK, [0; 4]));
+            }
+            Subpacket::FileInfo => {
+                self.state = State::Header;
+                match parse_file_info(&data) {
+                    Some((name, size)) => {
+                        self.file_offset = 0;
+                        self.receiving_file = true;
+                        output.events.push(ZmodemEvent::FileStart { name, size });
+                        output.reply.extend(hex_header(ZRPOS, [0; 4]));
+                    }
+                    None => output.reply.extend(hex_header(ZSKIP, [0; 4])),
+                }
+            }
+            Subpacket::Data => {
+                self.file_offset = self.file_offset.wrapping_add(data.len() as u32);
+                if !data.is_empty() {
+                    output.events.push(ZmodemEvent::FileData(data));
+                }
+                if matches!(frame_end, ZCRCQ | ZCRCW) {
+                    output
+                        .reply
+                        .extend(hex_header(ZACK, self.file_offset.to_le_bytes()));
+                }
+                if matches!(frame_end, ZCRCE | ZCRCW) {
+                    self.state = State::Header;
+                }
+            }
+        }
+        true
+    }
+
+    /// A subpacket was damaged: ask the sender to resend from the last good
+    /// position, and discard input until the next header.
+    fn subpacket_error(&mut self, kind: Subpacket, output: &mut ZmodemOutput) {
+        self.state = State::Header;
+        if let Some(start) = self.buffer.iter().skip(1).position(|byte| *byte == ZPAD) {
+            self.buffer.drain(..start + 1);
+        } else {
+            self.buffer.clear();
+        }
+        match kind {
+            Subpacket::Data => output
+                .reply
+                .extend(hex_header(ZRPOS, self.file_offset.to_le_bytes())),
+            Subpacket::Sinit | Subpacket::FileInfo => output.reply.extend(receiver_init()),
+        }
+    }
+}
+
+fn receiver_init() -> Vec<u8> {
+    hex_header(ZRINIT, [0, 0, 0, CANFDX | CANOVIO | CANFC32])
+}
+
+/// Encode a hex header, the format receivers use for every reply.
+fn hex_header(frame_type: u8, data: [u8; 4]) -> Vec<u8> {
+    let mut raw = [0u8; 5];
+    raw[0] = frame_type;
+    raw[1..].copy_from_slice(&data);
+    let crc = crc16(&raw);
+
+    let mut out = vec![ZPAD, ZPAD, ZDLE, ZHEX];
+    for byte in raw.iter().chain(crc.to_be_bytes().iter()) {
+        out.extend_from_slice(format!("{:02x}", byte).as_bytes());
+    }
+    out.extend_from_slice(b"\r\x8a");
+    if frame_type != ZACK && frame_type != ZFIN {
+        out.push(0x11);
+    }
+    out
+}
+
+fn parse_header(buf: &[u8]) -> Parse<Header> {
+    // `*` [`*`] ZDLE format
+    let mut pos = 1;
+    if buf.get(pos) == Some(&ZPAD) {
+        pos += 1;
+    }
+    let Some(&zdle) = buf.get(pos) else {
+        return Parse::Incomplete;
+    };
+    if zdle != ZDLE {
+        return Parse::Invalid(1);
+    }
+    let Some(&format) = buf.get(pos + 1) else {
+        return Parse::Incomplete;
+    };
+    pos += 2;
+
+    match format {
+        ZHEX => {
+            let Some(digits) = buf.get(pos..pos + 14) else {
+                return Parse::Incomplete;
+            };
+            let Some(bytes) = decode_hex(digits) else {
+                return Parse::Invalid(1);
+            };
+            if crc16(&bytes[..5]) != u16::from_be_bytes([bytes[5], bytes[6]]) {
+                return Parse::Invalid(1);
+            }
+            // Hex headers end with CR LF and (usually) XON
+            let trailer = buf[pos + 14..]
+                .iter()
+                .take(3)
+                .take_while(|byte| matches!(byte, 0x0d | 0x8d | 0x0a | 0x8a | 0x11))
+                .count();
+            Parse::Done(header_from(&bytes[..5], false), pos + 14 + trailer)
+        }
+        ZBIN | ZBIN32 => {
+            let crc32 = format == ZBIN32;
+            let len = if crc32 { 9 } else { 7 };
+            let mut bytes = Vec::with_capacity(len);
+            while bytes.len() < len {
+                match unescape(buf, pos) {
+                    Parse::Done(Unescaped::Byte(byte), used) => {
+                        bytes.push(byte);
+                        pos += used;
+                    }
+                    Parse::Done(Unescaped::FrameEnd(_), _) | Parse::Invalid(_) => {
+                        return Parse::Invalid(1);
+                    }
+                    Parse::Incomplete => return Parse::Incomplete,
+                }
+            }
+            let valid = if crc32 {
+                crc32_ieee(&bytes[..5]).to_le_bytes() == bytes[5..9]
+            } else {
+                crc16(&bytes[..5]).to_be_bytes() == bytes[5..7]
+            };
+            if !valid {
+                return Parse::Invalid(1);
+            }
+            Parse::Done(header_from(&bytes[..5], crc32), pos)
+        }
+        _ => Parse::Invalid(1),
+    }
+}
+
+fn header_from(bytes: &[u8], crc32: bool) -> Header {
+    Header {
+        frame_type: bytes[0],
+        data: [bytes[1], bytes[2], bytes[3], bytes[4]],
+        crc32,
+    }
+}
+
+fn decode_hex(digits: &[u8]) -> Option<Vec<u8>> {
+    digits
+        .chunks_exact(2)
+        .map(|pair| {
+            let text = std::str::from_utf8(pair).ok()?;
+            u8::from_str_radix(text, 16).ok()
+        })
+        .collect()
+}
+
+enum Unescaped {
+    Byte(u8),
+    FrameEnd(u8),
+}
+
+/// Decode one (possibly ZDLE-escaped) byte starting at `pos`, skipping
+/// unescaped flow-control characters.
+fn unescape(buf: &[u8], mut pos: usize) -> Parse<Unescaped> {
+    let start = pos;
+    loop {
+        let Some(&byte) = buf.get(pos) else {
+            return Parse::Incomplete;
+        };
+        match byte {
+            0x11 | 0x13 | 0x91 | 0x93 => pos += 1,
+            ZDLE => {
+                let Some(&next) = buf.get(pos + 1) else {
+                    return Parse::Incomplete;
+                };
+                let used = pos + 2 - start;
+                return match next {
+                    ZCRCE | ZCRCG | ZCRCQ | ZCRCW => Parse::Done(Unescaped::FrameEnd(next), used),
+                    ZRUB0 => Parse::Done(Unescaped::Byte(0x7f), used),
+                    ZRUB1 => Parse::Done(Unescaped::Byte(0xff), used),
+                    _ if next & 0x60 == 0x40 => Parse::Done(Unescaped::Byte(next ^ 0x40), used),
+                    _ => Parse::Invalid(used),
+                };
+            }
+            _ => return Parse::Done(Unescaped::Byte(byte), pos + 1 - start),
+        }
+    }
+}
+
+/// Parse a data subpacket: escaped data, ZDLE + frame end, escaped CRC.
+fn parse_subpacket(buf: &[u8], crc32: bool) -> Parse<(Vec<u8>, u8)> {
+    let mut data = Vec::new();
+    let mut pos = 0;
+    let frame_end = loop {
+        match unescape(buf, pos) {
+            Parse::Done(Unescaped::Byte(byte), used) => {
+                if data.len() >= MAX_SUBPACKET {
+                    return Parse::Invalid(pos);
+                }
+                data.push(byte);
+                pos += used;
+            }
+            Parse::Done(Unescaped::FrameEnd(end), used) => {
+                pos += used;
+                break end;
+            }
+            Parse::Incomplete => return Parse::Incomplete,
+            Parse::Invalid(used) => return Parse::Invalid(pos + used),
+        }
+    };
+
+    let crc_len = if crc32 { 4 } else { 2 };
+    let mut crc = Vec::with_capacity(crc_len);
+    while crc.len() < crc_len {
+        match unescape(buf, pos) {
+            Parse::Done(Unescaped::Byte(byte), used) => {
+                crc.push(byte);
+                pos += used;
+            }
+            Parse::Done(Unescaped::FrameEnd(_), used) | Parse::Invalid(used) => {
+                return Parse::Invalid(pos + used);
+            }
+            Parse::Incomplete => return Parse::Incomplete,
+        }
+    }
+
+    let mut checked = data.clone();
+    checked.push(frame_end);
+    let valid = if crc32 {
+        crc32_ieee(&checked).to_le_bytes()[..] == crc[..]
+    } else {
+        crc16(&checked).to_be_bytes()[..] == crc[..]
+    };
+    if !valid {
+        return Parse::Invalid(pos);
+    }
+    Parse::Done((data, frame_end), pos)
+}
+
+/// Parse a `ZFILE` subpacket: `name\0size mtime mode ...\0`.
+fn parse_file_info(data: &[u8]) -> Option<(String, Option<u64>)> {
+    let mut parts = data.splitn(2, |byte| *byte == 0);
+    let name = String::from_utf8_lossy(parts.next()?).into_owned();
+    if name.is_empty() {
+        return None;
+    }
+    let size = parts
+        .next()
+        .and_then(|rest| rest.split(|byte| *byte == 0).next())
+        .and_then(|info| std::str::from_utf8(info).ok())
+        .and_then(|info| info.split_whitespace().next())
+        .and_then(|size| size.parse().ok());
+    Some((name, size))
+}
+
+/// CRC-16/XMODEM
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// CRC-32 (IEEE 802.3)
+fn crc32_ieee(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escape(data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for &byte in data {
+            match byte {
+                ZDLE | 0x10 | 0x90 | 0x11 | 0x91 | 0x13 | 0x93 => {
+                    out.push(ZDLE);
+                    out.push(byte ^ 0x40);
+                }
+                _ => out.push(byte),
+            }
+        }
+        out
+    }
+
+    fn bin32_header(frame_type: u8, data: [u8; 4]) -> Vec<u8> {
+        let mut raw = vec![frame_type];
+        raw.extend_from_slice(&data);
+        let crc = crc32_ieee(&raw);
+        raw.extend_from_slice(&crc.to_le_bytes());
+        let mut out = vec![ZPAD, ZDLE, ZBIN32];
+        out.extend(escape(&raw));
+        out
+    }
+
+    fn subpacket32(data: &[u8], frame_end: u8) -> Vec<u8> {
+        let mut checked = data.to_vec();
+        checked.push(frame_end);
+        let crc = crc32_ieee(&checked);
+        let mut out = escape(data);
+        out.push(ZDLE);
+        out.push(frame_end);
+        out.extend(escape(&crc.to_le_bytes()));
+        out
+    }
+
+    fn header_types(reply: &[u8]) -> Vec<u8> {
+        let mut types = Vec::new();
+        let mut pos = 0;
+        while let Some(start) = find_start_of_hex(&reply[pos..]) {
+            let at = pos + start + 4;
+            types.push(decode_hex(&reply[at..at + 2]).unwrap()[0]);
+            pos = at;
+        }
+        types
+    }
+
+    fn find_start_of_hex(data: &[u8]) -> Option<usize> {
+        data.windows(4)
+            .position(|window| window == [ZPAD, ZPAD, ZDLE, ZHEX])
+    }
+
+    #[test]
+    fn crc_known_values() {
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+        assert_eq!(crc32_ieee(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn detects_sz_start_sequence() {
+        let data = b"$ sz notes.txt\r\nrz\r**\x18B00000000000000\r\x8a\x11";
+        assert_eq!(find_start(data), Some(19));
+        assert_eq!(find_start(b"plain output"), None);
+    }
+
+    #[test]
+    fn hex_header_roundtrips() {
+        let encoded = hex_header(ZRPOS, 1234u32.to_le_bytes());
+        let Parse::Done(header, _) = parse_header(&encoded) else {
+            panic!("header did not parse");
+        };
+        assert_eq!(header.frame_type, ZRPOS);
+        assert_eq!(header.position(), 1234);
+    }
+
+    #[test]
+    fn receives_a_file() {
+        let mut receiver = ZmodemReceiver::new();
+        let output = receiver.feed(b"rz\r**\x18B00000000000000\r\x8a\x11");
+        assert_eq!(header_types(&output.reply), vec![ZRINIT]);
+
+        let contents = b"hello\x18world\x11".to_vec();
+        let mut stream = bin32_header(ZFILE, [0; 4]);
+        stream.extend(subpacket32(b"notes.txt\x0012 0 100644\x00", ZCRCW));
+        let output = receiver.feed(&stream);
+        assert_eq!(
+            output.events,
+            vec![ZmodemEvent::FileStart {
+                name: "notes.txt".to_string(),
+                size: Some(12),
+            }]
+        );
+        assert_eq!(header_types(&output.reply), vec![ZRPOS]);
+
+        let mut stream = bin32_header(ZDATA, [0; 4]);
+        stream.extend(subpacket32(&contents[..6], ZCRCG));
+        stream.extend(subpacket32(&contents[6..], ZCRCE));
+        stream.extend(bin32_header(ZEOF, 12u32.to_le_bytes()));
+        // Deliver in small pieces to exercise buffering across reads.
+        let mut events = Vec::new();
+        let mut reply = Vec::new();
+        for chunk in stream.chunks(3) {
+            let output = receiver.feed(chunk);
+            events.extend(output.events);
+            reply.extend(output.reply);
+        }
+        let received: Vec<u8> = events
+            .iter()
+            .filter_map(|event| match event {
+                ZmodemEvent::FileData(data) => Some(data.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        assert_eq!(received, contents);
+        assert_eq!(events.last(), Some(&ZmodemEvent::FileEnd));
+        assert_eq!(header_types(&reply), vec![ZRINIT]);
+
+        let mut stream = hex_header(ZFIN, [0; 4]);
+        stream.extend_from_slice(b"OO$ ");
+        let output = receiver.feed(&stream);
+        assert_eq!(output.events, vec![ZmodemEvent::Finished]);
+        assert_eq!(header_types(&output.reply), vec![ZFIN]);
+        assert!(receiver.is_done());
+        assert_eq!(receiver.take_trailing(), b"$ ");
+    }
+
+    #[test]
+    fn corrupt_data_requests_resend_from_last_position() {
+        let mut receiver = ZmodemReceiver::new();
+        let mut stream = bin32_header(ZFILE, [0; 4]);
+        stream.extend(subpacket32(b"a.bin\x00", ZCRCW));
+        receiver.feed(&stream);
+
+        let mut stream = bin32_header(ZDATA, [0; 4]);
+        let mut packet = subpacket32(b"abcd", ZCRCG);
+        packet[0] = b'X';
+        stream.extend(packet);
+        let output = receiver.feed(&stream);
+        assert!(output.events.is_empty());
+        let reply = output.reply;
+        assert_eq!(header_types(&reply), vec![ZRPOS]);
+        let Parse::Done(header, _) = parse_header(&reply) else {
+            panic!("reply did not parse");
+        };
+        assert_eq!(header.position(), 0);
+    }
+
+    #[test]
+    fn sender_cancel_aborts() {
+        let mut receiver = ZmodemReceiver::new();
+        receiver.feed(START_SEQUENCE);
+        let output = receiver.feed(&CANCEL_SEQUENCE[..8]);
+        assert!(matches!(
+            output.events.as_slice(),
+            [ZmodemEvent::Aborted(_)]
+        ));
+        assert!(receiver.is_done());
+    }
+
+    #[test]
+    fn remote_commands_are_refused() {
+        let mut receiver = ZmodemReceiver::new();
+        let output = receiver.feed(&bin32_header(ZCOMMAND, [0; 4]));
+        assert!(matches!(
+            output.events.as_slice(),
+            [ZmodemEvent::Aborted(_)]
+        ));
+        assert_eq!(output.reply, CANCEL_SEQUENCE);
+    }
+}
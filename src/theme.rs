@@ -7,10 +7,15 @@
 //! - Catppuccin Macchiato (dark, medium)
 //! - Catppuccin Mocha (dark, rich)
 //! - Noctalia (Ghostty-compatible dark palette)
+//!
+//! Users can add their own palettes as [`CustomTheme`]s, stored in settings.
 
 use iced::Color;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use uuid::Uuid;
+
+pub mod import;
 
 /// Available theme identifiers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -23,10 +28,12 @@ pub enum ThemeId {
     CatppuccinMacchiato,
     CatppuccinMocha,
     Noctalia,
+    /// A user-defined palette, looked up with [`theme_for`]
+    Custom(Uuid),
 }
 
 impl ThemeId {
-    /// Get all available themes
+    /// Get all built-in themes
     pub const fn all() -> &'static [ThemeId] {
         &[
             ThemeId::PortalDefault,
@@ -47,13 +54,9 @@ impl ThemeId {
             ThemeId::CatppuccinMacchiato => "Catppuccin Macchiato",
             ThemeId::CatppuccinMocha => "Catppuccin Mocha",
             ThemeId::Noctalia => "Noctalia",
+            ThemeId::Custom(_) => "Custom",
         }
     }
-
-    /// Whether this is a dark theme (for Iced theme selection)
-    pub const fn is_dark(&self) -> bool {
-        !matches!(self, ThemeId::CatppuccinLatte)
-    }
 }
 
 impl fmt::Display for ThemeId {
@@ -63,39 +66,80 @@ impl fmt::Display for ThemeId {
 }
 
 /// Terminal color palette (16 ANSI colors + fg/bg/cursor)
-#[derive(Clone, Copy)]
+///
+/// Serialized with `#RRGGBB` strings; missing colors keep the Portal Default
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TerminalColors {
+    #[serde(with = "hex_color")]
     pub foreground: Color,
+    #[serde(with = "hex_color")]
     pub background: Color,
+    #[serde(with = "hex_color")]
     pub cursor: Color,
     /// Standard ANSI colors (16 colors: 0-7 normal, 8-15 bright)
+    #[serde(with = "hex_color_array")]
     pub ansi: [Color; 16],
 }
 
+impl Default for TerminalColors {
+    fn default() -> Self {
+        Theme::portal_default().terminal
+    }
+}
+
 /// Complete theme including UI and terminal colors
-#[derive(Clone, Copy)]
+///
+/// Serialized like [`TerminalColors`] for custom themes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Theme {
     // UI colors
+    #[serde(with = "hex_color")]
     pub background: Color,
+    #[serde(with = "hex_color")]
     pub surface: Color,
+    #[serde(with = "hex_color")]
     pub sidebar: Color,
+    #[serde(with = "hex_color")]
     pub tab_bar: Color,
+    #[serde(with = "hex_color")]
     pub accent: Color,
+    #[serde(with = "hex_color")]
     pub text_primary: Color,
+    #[serde(with = "hex_color")]
     pub text_secondary: Color,
     /// Lowest-contrast text that still meets AA at small sizes (metadata,
     /// fingerprints, timestamps). Use `text_muted` for decoration only.
+    #[serde(with = "hex_color")]
     pub text_tertiary: Color,
+    #[serde(with = "hex_color")]
     pub text_muted: Color,
+    #[serde(with = "hex_color")]
     pub border: Color,
+    #[serde(with = "hex_color")]
     pub hover: Color,
+    #[serde(with = "hex_color")]
     pub selected: Color,
+    #[serde(with = "hex_color")]
     pub focus_ring: Color,
     // Terminal colors
     pub terminal: TerminalColors,
 }
 
+impl Default for Theme {
+    fn default() -> Self {
+        Self::portal_default()
+    }
+}
+
 impl Theme {
+    /// Whether the background is dark (for Iced theme selection)
+    pub fn is_dark(&self) -> bool {
+        relative_luminance(self.background) < 0.5
+    }
+
     /// Pick a readable foreground color for text/icons rendered on a solid background.
     pub fn text_on(&self, background: Color) -> Color {
         let dark_text = Color::from_rgb8(0x1e, 0x20, 0x30);
@@ -394,7 +438,228 @@ fn linear_srgb(component: f32) -> f32 {
     }
 }
 
-/// Get theme by ID
+/// A user-defined palette, stored in settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomTheme {
+    pub id: Uuid,
+    pub name: String,
+    pub colors: Theme,
+}
+
+/// One editable color of a theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeColorField {
+    Background,
+    Surface,
+    Sidebar,
+    TabBar,
+    Accent,
+    TextPrimary,
+    TextSecondary,
+    TextTertiary,
+    TextMuted,
+    Border,
+    Hover,
+    Selected,
+    FocusRing,
+    TerminalForeground,
+    TerminalBackground,
+    TerminalCursor,
+    /// ANSI palette entry 0-15
+    Ansi(usize),
+}
+
+/// Names of the 16 ANSI colors, normal then bright
+pub const ANSI_COLOR_NAMES: [&str; 16] = [
+    "Black",
+    "Red",
+    "Green",
+    "Yellow",
+    "Blue",
+    "Magenta",
+    "Cyan",
+    "White",
+    "Bright Black",
+    "Bright Red",
+    "Bright Green",
+    "Bright Yellow",
+    "Bright Blue",
+    "Bright Magenta",
+    "Bright Cyan",
+    "Bright White",
+];
+
+impl ThemeColorField {
+    /// Interface colors, in editor order
+    pub const UI: &[ThemeColorField] = &[
+        ThemeColorField::Background,
+        ThemeColorField::Surface,
+        ThemeColorField::Sidebar,
+        ThemeColorField::TabBar,
+        ThemeColorField::Accent,
+        ThemeColorField::TextPrimary,
+        ThemeColorField::TextSecondary,
+        ThemeColorField::TextTertiary,
+        ThemeColorField::TextMuted,
+        ThemeColorField::Border,
+        ThemeColorField::Hover,
+        ThemeColorField::Selected,
+        ThemeColorField::FocusRing,
+    ];
+
+    /// Terminal colors other than the ANSI palette
+    pub const TERMINAL: &[ThemeColorField] = &[
+        ThemeColorField::TerminalForeground,
+        ThemeColorField::TerminalBackground,
+        ThemeColorField::TerminalCursor,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeColorField::Background => "Background",
+            ThemeColorField::Surface => "Surface",
+            ThemeColorField::Sidebar => "Sidebar",
+            ThemeColorField::TabBar => "Tab bar",
+            ThemeColorField::Accent => "Accent",
+            ThemeColorField::TextPrimary => "Text",
+            ThemeColorField::TextSecondary => "Secondary text",
+            ThemeColorField::TextTertiary => "Tertiary text",
+            ThemeColorField::TextMuted => "Muted text",
+            ThemeColorField::Border => "Border",
+            ThemeColorField::Hover => "Hover",
+            ThemeColorField::Selected => "Selected",
+            ThemeColorField::FocusRing => "Focus ring",
+            ThemeColorField::TerminalForeground => "Foreground",
+            ThemeColorField::TerminalBackground => "Background",
+            ThemeColorField::TerminalCursor => "Cursor",
+            ThemeColorField::Ansi(index) => ANSI_COLOR_NAMES.get(index).copied().unwrap_or("ANSI"),
+        }
+    }
+
+    pub fn get(self, theme: &Theme) -> Color {
+        match self {
+            ThemeColorField::Background => theme.background,
+            ThemeColorField::Surface => theme.surface,
+            ThemeColorField::Sidebar => theme.sidebar,
+            ThemeColorField::TabBar => theme.tab_bar,
+            ThemeColorField::Accent => theme.accent,
+            ThemeColorField::TextPrimary => theme.text_primary,
+            ThemeColorField::TextSecondary => theme.text_secondary,
+            ThemeColorField::TextTertiary => theme.text_tertiary,
+            ThemeColorField::TextMuted => theme.text_muted,
+            ThemeColorField::Border => theme.border,
+            ThemeColorField::Hover => theme.hover,
+            ThemeColorField::Selected => theme.selected,
+            ThemeColorField::FocusRing => theme.focus_ring,
+            ThemeColorField::TerminalForeground => theme.terminal.foreground,
+            ThemeColorField::TerminalBackground => theme.terminal.background,
+            ThemeColorField::TerminalCursor => theme.terminal.cursor,
+            ThemeColorField::Ansi(index) => theme.terminal.ansi[index.min(15)],
+        }
+    }
+
+    pub fn set(self, theme: &mut Theme, color: Color) {
+        let slot = match self {
+            ThemeColorField::Background => &mut theme.background,
+            ThemeColorField::Surface => &mut theme.surface,
+            ThemeColorField::Sidebar => &mut theme.sidebar,
+            ThemeColorField::TabBar => &mut theme.tab_bar,
+            ThemeColorField::Accent => &mut theme.accent,
+            ThemeColorField::TextPrimary => &mut theme.text_primary,
+            ThemeColorField::TextSecondary => &mut theme.text_secondary,
+            ThemeColorField::TextTertiary => &mut theme.text_tertiary,
+            ThemeColorField::TextMuted => &mut theme.text_muted,
+            ThemeColorField::Border => &mut theme.border,
+            ThemeColorField::Hover => &mut theme.hover,
+            ThemeColorField::Selected => &mut theme.selected,
+            ThemeColorField::FocusRing => &mut theme.focus_ring,
+            ThemeColorField::TerminalForeground => &mut theme.terminal.foreground,
+            ThemeColorField::TerminalBackground => &mut theme.terminal.background,
+            ThemeColorField::TerminalCursor => &mut theme.terminal.cursor,
+            ThemeColorField::Ansi(index) => &mut theme.terminal.ansi[index.min(15)],
+        };
+        *slot = color;
+    }
+}
+
+/// `#RRGGBB` form of a color; alpha is dropped
+pub fn color_to_hex(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+/// Parse a `#RRGGBB` (or bare `RRGGBB`) color
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.len() != 6 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |start: usize| u8::from_str_radix(&hex[start..start + 2], 16).ok();
+    Some(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+mod hex_color {
+    use iced::Color;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::color_to_hex(*color))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        super::parse_hex_color(&raw).ok_or_else(|| {
+            de::Error::custom(format!("invalid color \"{}\", expected #RRGGBB", raw))
+        })
+    }
+}
+
+mod hex_color_array {
+    use iced::Color;
+    use serde::{Deserialize, Deserializer, Serializer, de, ser::SerializeSeq};
+
+    pub fn serialize<S: Serializer>(
+        colors: &[Color; 16],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(colors.len()))?;
+        for color in colors {
+            seq.serialize_element(&super::color_to_hex(*color))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[Color; 16], D::Error> {
+        let raw = Vec::<String>::deserialize(deserializer)?;
+        if raw.len() != 16 {
+            return Err(de::Error::invalid_length(raw.len(), &"16 ANSI colors"));
+        }
+        let mut colors = [Color::BLACK; 16];
+        for (slot, value) in colors.iter_mut().zip(&raw) {
+            *slot = super::parse_hex_color(value).ok_or_else(|| {
+                de::Error::custom(format!("invalid color \"{}\", expected #RRGGBB", value))
+            })?;
+        }
+        Ok(colors)
+    }
+}
+
+/// Resolve a theme id, looking custom themes up in `custom`. A custom theme
+/// that no longer exists falls back to Portal Default.
+pub fn theme_for(id: ThemeId, custom: &[CustomTheme]) -> Theme {
+    match id {
+        ThemeId::Custom(theme_id) => custom
+            .iter()
+            .find(|theme| theme.id == theme_id)
+            .map_or_else(Theme::portal_default, |theme| theme.colors),
+        _ => get_theme(id),
+    }
+}
+
+/// Get a built-in theme by ID; use [`theme_for`] to resolve custom themes
 pub fn get_theme(id: ThemeId) -> Theme {
     match id {
         ThemeId::PortalDefault => Theme::portal_default(),
@@ -403,6 +668,7 @@ pub fn get_theme(id: ThemeId) -> Theme {
         ThemeId::CatppuccinMacchiato => Theme::catppuccin_macchiato(),
         ThemeId::CatppuccinMocha => Theme::catppuccin_mocha(),
         ThemeId::Noctalia => Theme::noctalia(),
+        ThemeId::Custom(_) => Theme::portal_default(),
     }
 }
 
@@ -586,4 +852,58 @@ mod tests {
         assert_eq!(Theme::catppuccin_latte().text_on_accent(), Color::WHITE);
         assert_eq!(Theme::portal_default().text_on_accent(), Color::WHITE);
     }
+
+    #[test]
+    fn hex_colors_round_trip() {
+        let color = Color::from_rgb8(0x1e, 0x66, 0xf5);
+        assert_eq!(color_to_hex(color), "#1E66F5");
+        assert_eq!(parse_hex_color("#1e66f5"), Some(color));
+        assert_eq!(parse_hex_color(" 1E66F5 "), Some(color));
+        assert_eq!(parse_hex_color("#1e66f"), None);
+        assert_eq!(parse_hex_color("#1e66fg"), None);
+    }
+
+    #[test]
+    fn custom_themes_round_trip_and_fill_missing_colors() {
+        let mut colors = Theme::catppuccin_mocha();
+        ThemeColorField::Ansi(1).set(&mut colors, Color::from_rgb8(0xff, 0x00, 0x00));
+        let custom = CustomTheme {
+            id: Uuid::new_v4(),
+            name: "Mine".to_string(),
+            colors,
+        };
+        let serialized = toml::to_string(&custom).unwrap();
+        assert!(serialized.contains("\"#FF0000\""));
+        let parsed: CustomTheme = toml::from_str(&serialized).unwrap();
+        assert_eq!(color_to_hex(parsed.colors.terminal.ansi[1]), "#FF0000");
+        assert_eq!(
+            color_to_hex(parsed.colors.accent),
+            color_to_hex(colors.accent)
+        );
+
+        let sparse: CustomTheme = toml::from_str(&format!(
+            "id = \"{}\"\nname = \"Sparse\"\n[colors]\naccent = \"#123456\"\n",
+            custom.id
+        ))
+        .unwrap();
+        assert_eq!(color_to_hex(sparse.colors.accent), "#123456");
+        assert_eq!(sparse.colors.background, Theme::portal_default().background);
+    }
+
+    #[test]
+    fn theme_for_resolves_custom_themes() {
+        let custom = CustomTheme {
+            id: Uuid::new_v4(),
+            name: "Light".to_string(),
+            colors: Theme::catppuccin_latte(),
+        };
+        let resolved = theme_for(ThemeId::Custom(custom.id), std::slice::from_ref(&custom));
+        assert_eq!(resolved, Theme::catppuccin_latte());
+        assert!(!resolved.is_dark());
+        assert_eq!(
+            theme_for(ThemeId::Custom(Uuid::new_v4()), &[custom]),
+            Theme::portal_default()
+        );
+        assert!(Theme::portal_default().is_dark());
+    }
 }
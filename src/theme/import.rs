@@ -0,0 +1,327 @@
+//! Terminal color schemes from other applications
+//!
+//! Reads iTerm2 `.itermcolors` property lists, base16 YAML schemes and
+//! Windows Terminal JSON schemes into [`TerminalColors`]. Only the palette,
+//! foreground, background and cursor are taken; everything else in the file
+//! is ignored.
+
+use std::path::Path;
+
+use iced::Color;
+use regex::Regex;
+use serde_json::Value;
+
+use super::{TerminalColors, parse_hex_color};
+
+/// Largest scheme file read; real schemes are a few kilobytes
+pub const MAX_SCHEME_FILE_LEN: u64 = 1024 * 1024;
+
+/// A color scheme read from a file, ready to become a custom theme
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedScheme {
+    pub name: String,
+    pub terminal: TerminalColors,
+}
+
+/// Windows Terminal keys for ANSI colors 0-15
+const WINDOWS_TERMINAL_KEYS: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightPurple",
+    "brightCyan",
+    "brightWhite",
+];
+
+/// base16 slots for ANSI colors 0-15, as laid out by base16-shell
+const BASE16_ANSI_SLOTS: [&str; 16] = [
+    "base00", "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base05", "base03",
+    "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base07",
+];
+
+/// Read and parse the scheme at `path`, named after the file when the
+/// scheme does not carry a name
+pub fn read_color_scheme(path: &Path) -> Result<ImportedScheme, String> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if metadata.len() > MAX_SCHEME_FILE_LEN {
+        return Err(format!(
+            "{} is too large to be a color scheme",
+            path.display()
+        ));
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file_name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported".to_string());
+    parse_color_scheme(&content, &file_name)
+}
+
+/// Parse scheme text, detecting the format from its content
+pub fn parse_color_scheme(content: &str, fallback_name: &str) -> Result<ImportedScheme, String> {
+    let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+    let (name, terminal) = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        parse_windows_terminal(trimmed)?
+    } else if trimmed.starts_with("<?xml") || trimmed.starts_with("<plist") {
+        (None, parse_itermcolors(trimmed)?)
+    } else if trimmed.contains("base00") {
+        parse_base16(trimmed)?
+    } else {
+        return Err(
+            "Unrecognized color scheme; expected .itermcolors, base16 YAML or Windows Terminal JSON"
+                .to_string(),
+        );
+    };
+
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| fallback_name.to_string());
+    Ok(ImportedScheme { name, terminal })
+}
+
+fn parse_itermcolors(content: &str) -> Result<TerminalColors, String> {
+    let entry = Regex::new(r"(?s)<key>\s*([^<]+?)\s*</key>\s*<dict>(.*?)</dict>")
+        .expect("pattern compiles");
+    let component = Regex::new(
+        r"(?s)<key>\s*(Red|Green|Blue) Component\s*</key>\s*<(?:real|integer)>\s*([^<]+?)\s*</(?:real|integer)>",
+    )
+    .expect("pattern compiles");
+
+    let colors: Vec<(String, Result<Color, String>)> = entry
+        .captures_iter(content)
+        .map(|entry| {
+            let key = entry[1].to_string();
+            let mut rgb = [None; 3];
+            for channel in component.captures_iter(&entry[2]) {
+                let slot = match &channel[1] {
+                    "Red" => 0,
+                    "Green" => 1,
+                    _ => 2,
+                };
+                rgb[slot] = channel[2].parse::<f32>().ok();
+            }
+            let color = match rgb {
+                [Some(r), Some(g), Some(b)]
+                    if [r, g, b].iter().all(|value| (0.0..=1.0).contains(value)) =>
+                {
+                    Ok(Color::from_rgb(r, g, b))
+                }
+                _ => Err(format!("Invalid color for \"{}\"", key)),
+            };
+            (key, color)
+        })
+        .collect();
+    let color = |key: &str| match colors.iter().find(|(name, _)| name == key) {
+        Some((_, color)) => color.clone(),
+        None => Err(format!("Missing \"{}\"", key)),
+    };
+
+    let mut ansi = [Color::BLACK; 16];
+    for (index, slot) in ansi.iter_mut().enumerate() {
+        *slot = color(&format!("Ansi {} Color", index))?;
+    }
+    let foreground = color("Foreground Color")?;
+    Ok(TerminalColors {
+        foreground,
+        background: color("Background Color")?,
+        cursor: color("Cursor Color").unwrap_or(foreground),
+        ansi,
+    })
+}
+
+fn parse_base16(content: &str) -> Result<(Option<String>, TerminalColors), String> {
+    let mut name = None;
+    let mut slots = Vec::new();
+    for line in content.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        // Quoted values may contain '#'; bare ones end at a trailing comment
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value.split(" #").next().unwrap_or_default().trim(),
+        };
+        match key.trim() {
+            "scheme" | "name" => name = Some(value.to_string()),
+            key if key.starts_with("base") => slots.push((key.to_string(), value.to_string())),
+            _ => {}
+        }
+    }
+    let color = |slot: &str| {
+        let (_, value) = slots
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(slot))
+            .ok_or_else(|| format!("Missing \"{}\"", slot))?;
+        parse_hex_color(value).ok_or_else(|| format!("Invalid color for \"{}\": {}", slot, value))
+    };
+
+    let mut ansi = [Color::BLACK; 16];
+    for (slot, base) in ansi.iter_mut().zip(BASE16_ANSI_SLOTS) {
+        *slot = color(base)?;
+    }
+    Ok((
+        name,
+        TerminalColors {
+            foreground: color("base05")?,
+            background: color("base00")?,
+            cursor: color("base05")?,
+            ansi,
+        },
+    ))
+}
+
+fn parse_windows_terminal(content: &str) -> Result<(Option<String>, TerminalColors), String> {
+    let value: Value = serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+    // Accept a single scheme, a list of schemes, or a whole settings.json;
+    // the first scheme is imported
+    let scheme = match &value {
+        Value::Array(schemes) => schemes.first(),
+        Value::Object(object) => match object.get("schemes") {
+            Some(Value::Array(schemes)) => schemes.first(),
+            _ => Some(&value),
+        },
+        _ => None,
+    }
+    .ok_or_else(|| "No color scheme found in the JSON file".to_string())?;
+
+    let color = |key: &str| {
+        let value = scheme
+            .get(key)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("Missing \"{}\"", key))?;
+        parse_hex_color(value).ok_or_else(|| format!("Invalid color for \"{}\": {}", key, value))
+    };
+
+    let mut ansi = [Color::BLACK; 16];
+    for (slot, key) in ansi.iter_mut().zip(WINDOWS_TERMINAL_KEYS) {
+        *slot = color(key)?;
+    }
+    let foreground = color("foreground")?;
+    Ok((
+        scheme
+            .get("name")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        TerminalColors {
+            foreground,
+            background: color("background")?,
+            cursor: color("cursorColor").unwrap_or(foreground),
+            ansi,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::color_to_hex;
+
+    fn plist_color(key: &str, r: f32, g: f32, b: f32) -> String {
+        format!(
+            "<key>{key}</key>\n<dict>\n<key>Blue Component</key>\n<real>{b}</real>\n\
+             <key>Color Space</key>\n<string>sRGB</string>\n\
+             <key>Green Component</key>\n<real>{g}</real>\n\
+             <key>Red Component</key>\n<real>{r}</real>\n</dict>\n"
+        )
+    }
+
+    #[test]
+    fn itermcolors_map_ansi_and_default_the_cursor() {
+        let mut body: String = (0..16)
+            .map(|index| {
+                plist_color(
+                    &format!("Ansi {} Color", index),
+                    index as f32 / 15.0,
+                    0.0,
+                    0.0,
+                )
+            })
+            .collect();
+        body.push_str(&plist_color("Foreground Color", 1.0, 1.0, 1.0));
+        body.push_str(&plist_color("Background Color", 0.0, 0.0, 0.0));
+        let content = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<plist version=\"1.0\">\n<dict>\n{body}</dict>\n</plist>\n"
+        );
+
+        let scheme = parse_color_scheme(&content, "Dracula").unwrap();
+        assert_eq!(scheme.name, "Dracula");
+        assert_eq!(color_to_hex(scheme.terminal.ansi[15]), "#FF0000");
+        assert_eq!(color_to_hex(scheme.terminal.background), "#000000");
+        assert_eq!(scheme.terminal.cursor, scheme.terminal.foreground);
+    }
+
+    #[test]
+    fn base16_yaml_uses_the_shell_layout() {
+        let slots = "0123456789ABCDEF";
+        let mut content = "scheme: \"Gruvbox dark\"\nauthor: \"someone\"\n".to_string();
+        for (index, digit) in slots.chars().enumerate() {
+            content.push_str(&format!(
+                "base0{digit}: \"{:02x}{:02x}{:02x}\" # slot\n",
+                index, index, index
+            ));
+        }
+
+        let scheme = parse_color_scheme(&content, "file").unwrap();
+        assert_eq!(scheme.name, "Gruvbox dark");
+        assert_eq!(color_to_hex(scheme.terminal.background), "#000000");
+        assert_eq!(color_to_hex(scheme.terminal.foreground), "#050505");
+        assert_eq!(color_to_hex(scheme.terminal.ansi[1]), "#080808");
+        assert_eq!(color_to_hex(scheme.terminal.ansi[15]), "#070707");
+    }
+
+    #[test]
+    fn windows_terminal_settings_import_the_first_scheme() {
+        let mut scheme = serde_json::json!({
+            "name": "Solarized Dark",
+            "foreground": "#839496",
+            "background": "#002B36",
+            "cursorColor": "#93A1A1",
+        });
+        for key in WINDOWS_TERMINAL_KEYS {
+            scheme[key] = Value::from("#112233");
+        }
+        let content = serde_json::json!({ "schemes": [scheme] }).to_string();
+
+        let imported = parse_color_scheme(&content, "settings").unwrap();
+        assert_eq!(imported.name, "Solarized Dark");
+        assert_eq!(color_to_hex(imported.terminal.background), "#002B36");
+        assert_eq!(color_to_hex(imported.terminal.cursor), "#93A1A1");
+        assert_eq!(color_to_hex(imported.terminal.ansi[8]), "#112233");
+    }
+
+    #[test]
+    fn malformed_schemes_say_what_is_wrong() {
+        assert_eq!(
+            parse_color_scheme("{\"foreground\": \"#ffffff\"}", "x").unwrap_err(),
+            "Missing \"black\""
+        );
+        assert_eq!(
+            parse_color_scheme("base00: \"zz0000\"", "x").unwrap_err(),
+            "Invalid color for \"base00\": zz0000"
+        );
+        assert!(
+            parse_color_scheme("{ not json", "x")
+                .unwrap_err()
+                .starts_with("Invalid JSON")
+        );
+        assert!(
+            parse_color_scheme("hello", "x")
+                .unwrap_err()
+                .starts_with("Unrecognized")
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! user inputs to ensure they conform to expected formats before use.
 
 use std::net::{IpAddr, Ipv6Addr};
+use std::path::PathBuf;
 
 use regex::Regex;
 use std::sync::LazyLock;
@@ -30,6 +31,14 @@ static DNS_LABEL_REGEX: LazyLock<Regex> =
 static USERNAME_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[a-zA-Z_][a-zA-Z0-9_.-]{0,254}\$?$").unwrap());
 
+/// Kind of address a valid hostname turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostnameKind {
+    Ipv4,
+    Ipv6,
+    Dns,
+}
+
 /// Validate a hostname (DNS name or IP address).
 ///
 /// Accepts:
@@ -40,7 +49,7 @@ static USERNAME_REGEX: LazyLock<Regex> =
 /// # Errors
 ///
 /// Returns `ValidationError` if the hostname is empty, too long, or malformed.
-pub fn validate_hostname(hostname: &str) -> Result<(), ValidationError> {
+pub fn validate_hostname(hostname: &str) -> Result<HostnameKind, ValidationError> {
     let hostname = hostname.trim();
 
     if hostname.is_empty() {
@@ -65,23 +74,26 @@ pub fn validate_hostname(hostname: &str) -> Result<(), ValidationError> {
 
     // Try parsing as IP address first. Bracketed IPv6 literals and scoped
     // link-local IPv6 addresses are common in SSH/VNC connection UIs.
-    if parse_valid_ip_literal(ip_candidate, bracketed.is_some()) {
-        return Ok(());
+    if let Some(kind) = parse_valid_ip_literal(ip_candidate, bracketed.is_some()) {
+        return Ok(kind);
     }
 
     // Validate as DNS hostname (RFC 1123)
-    validate_dns_hostname(hostname)
+    validate_dns_hostname(hostname)?;
+    Ok(HostnameKind::Dns)
 }
 
-fn parse_valid_ip_literal(candidate: &str, bracketed: bool) -> bool {
+fn parse_valid_ip_literal(candidate: &str, bracketed: bool) -> Option<HostnameKind> {
     if let Some((ip, scope)) = candidate.split_once('%') {
-        return !ip.is_empty() && !scope.is_empty() && ip.parse::<Ipv6Addr>().is_ok();
+        return (!ip.is_empty() && !scope.is_empty() && ip.parse::<Ipv6Addr>().is_ok())
+            .then_some(HostnameKind::Ipv6);
     }
 
     match candidate.parse::<IpAddr>() {
-        Ok(IpAddr::V4(_)) => !bracketed,
-        Ok(IpAddr::V6(_)) => true,
-        Err(_) => false,
+        Ok(IpAddr::V4(_)) if !bracketed => Some(HostnameKind::Ipv4),
+        Ok(IpAddr::V4(_)) => None,
+        Ok(IpAddr::V6(_)) => Some(HostnameKind::Ipv6),
+        Err(_) => None,
     }
 }
 
@@ -105,6 +117,19 @@ fn validate_dns_hostname(hostname: &str) -> Result<(), ValidationError> {
         });
     }
 
+    // An all-numeric name is a mistyped IPv4 address (RFC 1123 forbids a
+    // numeric top-level label), e.g. "256.1.1.1" or "10.0.1"
+    if labels.len() > 1
+        && labels
+            .iter()
+            .all(|label| !label.is_empty() && label.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(ValidationError {
+            field: "hostname".to_string(),
+            message: "Invalid IPv4 address: expected four numbers from 0 to 255".to_string(),
+        });
+    }
+
     for label in labels {
         // Each label must be 1-63 characters
         if label.is_empty() || label.len() > 63 {
@@ -144,6 +169,14 @@ pub fn validate_port(port_str: &str) -> Result<u16, ValidationError> {
         });
     }
 
+    // `u16::from_str` also takes a leading '+'
+    if !port_str.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ValidationError {
+            field: "port".to_string(),
+            message: format!("Invalid port number: '{}'", port_str),
+        });
+    }
+
     match port_str.parse::<u16>() {
         Ok(port) if port >= 1 => Ok(port),
         Ok(_) => Err(ValidationError {
@@ -196,6 +229,133 @@ pub fn validate_username(username: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Connection target typed into a quick-connect field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickConnectTarget {
+    pub username: Option<String>,
+    /// Host name or IP address; IPv6 literals are stored without brackets
+    pub hostname: String,
+    pub port: Option<u16>,
+    /// Private key given with `-i <path>`
+    pub identity: Option<PathBuf>,
+}
+
+fn quick_connect_error(message: impl Into<String>) -> ValidationError {
+    ValidationError {
+        field: "address".to_string(),
+        message: message.into(),
+    }
+}
+
+/// Parse quick-connect input into a connection target.
+///
+/// Accepts:
+/// - `host`, `user@host`, `user@host:port`
+/// - `ssh://user@host:port/` URLs
+/// - IPv6 literals, bracketed when followed by a port (`[::1]:2222`)
+/// - a trailing identity hint, `user@host -i ~/.ssh/id_ed25519`
+///
+/// # Errors
+///
+/// Returns `ValidationError` describing the first malformed part.
+pub fn parse_quick_connect(input: &str) -> Result<QuickConnectTarget, ValidationError> {
+    let input = input.trim();
+
+    let (address, identity) = match input.split_once(" -i ") {
+        Some((address, path)) => {
+            let path = path.trim();
+            if path.is_empty() {
+                return Err(quick_connect_error("-i needs the path of a key file"));
+            }
+            (address.trim(), Some(PathBuf::from(path)))
+        }
+        None if input.ends_with(" -i") => {
+            return Err(quick_connect_error("-i needs the path of a key file"));
+        }
+        None => (input, None),
+    };
+
+    if address.is_empty() {
+        return Err(quick_connect_error("Enter a host to connect to"));
+    }
+
+    let address = match address.split_once("://") {
+        Some((scheme, rest)) if scheme.eq_ignore_ascii_case("ssh") => {
+            let rest = rest.strip_suffix('/').unwrap_or(rest);
+            if rest.contains('/') || rest.contains('?') || rest.contains('#') {
+                return Err(quick_connect_error(
+                    "ssh:// URLs cannot include a path or query",
+                ));
+            }
+            rest
+        }
+        Some((scheme, _)) => {
+            return Err(quick_connect_error(format!(
+                "Unsupported scheme '{}://', use ssh://",
+                scheme
+            )));
+        }
+        None => address,
+    };
+
+    let (username, host_port) = match address.split_once('@') {
+        Some(("", _)) => return Err(quick_connect_error("Username is missing before '@'")),
+        Some((user, rest)) => {
+            validate_username(user).map_err(|e| quick_connect_error(e.message))?;
+            (Some(user.to_string()), rest)
+        }
+        None => (None, address),
+    };
+
+    let (hostname, port) = split_host_port(host_port)?;
+    validate_hostname(hostname).map_err(|e| quick_connect_error(e.message))?;
+    let port = port
+        .map(|port| validate_port(port).map_err(|e| quick_connect_error(e.message)))
+        .transpose()?;
+
+    Ok(QuickConnectTarget {
+        username,
+        hostname: hostname.to_string(),
+        port,
+        identity,
+    })
+}
+
+/// Split `host[:port]`, `[v6]:port` or a bare IPv6 address.
+fn split_host_port(value: &str) -> Result<(&str, Option<&str>), ValidationError> {
+    if let Some(stripped) = value.strip_prefix('[') {
+        let Some((host, tail)) = stripped.split_once(']') else {
+            return Err(quick_connect_error("Missing ']' after the IPv6 address"));
+        };
+        if parse_valid_ip_literal(host, true).is_none() {
+            return Err(quick_connect_error(format!(
+                "'{}' in brackets is not an IPv6 address",
+                host
+            )));
+        }
+        return match tail {
+            "" => Ok((host, None)),
+            _ => match tail.strip_prefix(':') {
+                Some(port) => Ok((host, Some(port))),
+                None => Err(quick_connect_error("Expected ':port' after ']'")),
+            },
+        };
+    }
+
+    // A bare IPv6 address has several colons and cannot carry a port
+    match value.matches(':').count() {
+        0 => Ok((value, None)),
+        1 => {
+            let (host, port) = value.split_once(':').unwrap_or((value, ""));
+            Ok((host, Some(port)))
+        }
+        _ if parse_valid_ip_literal(value, false).is_some() => Ok((value, None)),
+        _ => Err(quick_connect_error(
+            "Put IPv6 addresses in brackets to add a port, e.g. [::1]:2222",
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,6 +399,44 @@ mod tests {
         assert!(validate_hostname("example.com.").is_ok());
     }
 
+    #[test]
+    fn hostname_reports_its_kind() {
+        assert_eq!(validate_hostname("10.0.0.1").unwrap(), HostnameKind::Ipv4);
+        assert_eq!(validate_hostname("[::1]").unwrap(), HostnameKind::Ipv6);
+        assert_eq!(
+            validate_hostname("fe80::1%eth0").unwrap(),
+            HostnameKind::Ipv6
+        );
+        assert_eq!(
+            validate_hostname("db1.example.com").unwrap(),
+            HostnameKind::Dns
+        );
+        assert_eq!(
+            validate_hostname("3com.example").unwrap(),
+            HostnameKind::Dns
+        );
+    }
+
+    #[test]
+    fn hostname_invalid_ipv4_lookalikes() {
+        assert!(validate_hostname("256.1.1.1").is_err());
+        assert!(validate_hostname("10.0.1").is_err());
+        assert!(validate_hostname("192.168.1.1.5").is_err());
+        assert!(validate_hostname("1.2.3.").is_err());
+        // A single numeric label is a valid (if unusual) host name
+        assert!(validate_hostname("1234").is_ok());
+    }
+
+    #[test]
+    fn hostname_invalid_length_limits() {
+        let label = "a".repeat(63);
+        assert!(validate_hostname(&format!("{}.example.com", label)).is_ok());
+        assert!(validate_hostname(&format!("{}a.example.com", label)).is_err());
+        let long = vec!["abcdefghi"; 26].join(".");
+        assert!(long.len() > 253);
+        assert!(validate_hostname(&long).is_err());
+    }
+
     #[test]
     fn hostname_invalid_empty() {
         assert!(validate_hostname("").is_err());
@@ -295,6 +493,13 @@ mod tests {
         assert!(validate_port("-1").is_err());
     }
 
+    #[test]
+    fn port_invalid_sign_or_inner_space() {
+        assert!(validate_port("+22").is_err());
+        assert!(validate_port("2 2").is_err());
+        assert_eq!(validate_port("0022").unwrap(), 22);
+    }
+
     #[test]
     fn port_invalid_empty() {
         assert!(validate_port("").is_err());
@@ -342,4 +547,97 @@ mod tests {
         let long_name = "a".repeat(257);
         assert!(validate_username(&long_name).is_err());
     }
+
+    // ---- Quick connect parsing tests ----
+
+    fn target(username: Option<&str>, hostname: &str, port: Option<u16>) -> QuickConnectTarget {
+        QuickConnectTarget {
+            username: username.map(str::to_string),
+            hostname: hostname.to_string(),
+            port,
+            identity: None,
+        }
+    }
+
+    #[test]
+    fn quick_connect_plain_forms() {
+        assert_eq!(
+            parse_quick_connect("example.com").unwrap(),
+            target(None, "example.com", None)
+        );
+        assert_eq!(
+            parse_quick_connect(" root@example.com:2222 ").unwrap(),
+            target(Some("root"), "example.com", Some(2222))
+        );
+    }
+
+    #[test]
+    fn quick_connect_ssh_urls() {
+        assert_eq!(
+            parse_quick_connect("ssh://deploy@10.0.0.5:2200/").unwrap(),
+            target(Some("deploy"), "10.0.0.5", Some(2200))
+        );
+        assert_eq!(
+            parse_quick_connect("SSH://example.com").unwrap(),
+            target(None, "example.com", None)
+        );
+    }
+
+    #[test]
+    fn quick_connect_ipv6_literals() {
+        assert_eq!(
+            parse_quick_connect("[::1]:2222").unwrap(),
+            target(None, "::1", Some(2222))
+        );
+        assert_eq!(
+            parse_quick_connect("ssh://admin@[2001:db8::1]/").unwrap(),
+            target(Some("admin"), "2001:db8::1", None)
+        );
+        assert_eq!(
+            parse_quick_connect("user@fe80::1%eth0").unwrap(),
+            target(Some("user"), "fe80::1%eth0", None)
+        );
+    }
+
+    #[test]
+    fn quick_connect_identity_hint() {
+        let parsed = parse_quick_connect("root@example.com -i ~/.ssh/id_ed25519").unwrap();
+        assert_eq!(parsed.username.as_deref(), Some("root"));
+        assert_eq!(parsed.identity, Some(PathBuf::from("~/.ssh/id_ed25519")));
+    }
+
+    #[test]
+    fn quick_connect_rejects_malformed_input() {
+        let message = |input: &str| parse_quick_connect(input).unwrap_err().message;
+
+        assert_eq!(message("  "), "Enter a host to connect to");
+        assert_eq!(message("@example.com"), "Username is missing before '@'");
+        assert_eq!(
+            message("http://example.com"),
+            "Unsupported scheme 'http://', use ssh://"
+        );
+        assert_eq!(
+            message("ssh://example.com/home"),
+            "ssh:// URLs cannot include a path or query"
+        );
+        assert_eq!(message("[::1:22"), "Missing ']' after the IPv6 address");
+        assert_eq!(message("[::1]22"), "Expected ':port' after ']'");
+        assert_eq!(
+            message("[example.com]:22"),
+            "'example.com' in brackets is not an IPv6 address"
+        );
+        assert_eq!(
+            message("root@example.com:ssh"),
+            "Invalid port number: 'ssh'"
+        );
+        assert_eq!(message("example.com:0"), "Port must be between 1 and 65535");
+        assert_eq!(message("example.com:"), "Port is required");
+        assert!(message("2001:db8::1:abc:xyz").contains("brackets"));
+        assert_eq!(
+            message("root@example.com -i "),
+            "-i needs the path of a key file"
+        );
+        assert!(message("[127.0.0.1]:22").contains("not an IPv6 address"));
+        assert!(parse_quick_connect("bad host").is_err());
+    }
 }
@@ -1,8 +1,11 @@
 use iced::widget::{Column, button, column, container, row, scrollable, text, text_input};
 use iced::{Alignment, Element, Fill, Length};
 
-use crate::config::{HostsConfig, Protocol, SnippetsConfig};
+use crate::config::{
+    Host, HostsConfig, MacrosConfig, Protocol, SnippetsConfig, TerminalProfilesConfig,
+};
 use crate::icons::{self, icon_with_color};
+use crate::local::LaunchTarget;
 use crate::message::{CommandAction, Message, UiMessage};
 use crate::theme::{BORDER_RADIUS, CARD_BORDER_RADIUS, ScaledFonts, Theme};
 
@@ -18,9 +21,15 @@ pub struct CommandItem {
     pub icon: &'static [u8],
 }
 
+/// `snippet_host` is the host of the active session; only snippets that apply
+/// to it are listed.
 pub fn available_commands(
     hosts: &HostsConfig,
     snippets: &SnippetsConfig,
+    snippet_host: Option<&Host>,
+    macros: &MacrosConfig,
+    terminal_profiles: &TerminalProfilesConfig,
+    launch_targets: &[LaunchTarget],
     portal_hub_configured: bool,
 ) -> Vec<CommandItem> {
     let mut commands = vec![
@@ -78,8 +87,68 @@ pub fn available_commands(
             CommandAction::LocalTerminal,
             icons::ui::TERMINAL,
         ),
+        command(
+            "Export SSH Config",
+            "Write all SSH hosts to an OpenSSH config file",
+            CommandAction::ExportSshConfig,
+            icons::files::FILE_COG,
+        ),
+        command(
+            "Forget Credentials",
+            "Clear cached key passphrases and sudo passwords",
+            CommandAction::ForgetCredentials,
+            icons::ui::KEY,
+        ),
+        command(
+            "Toggle Broadcast Input",
+            "Add or remove the active terminal from the broadcast group",
+            CommandAction::ToggleBroadcast,
+            icons::ui::TERMINAL,
+        ),
+        command(
+            "Split Terminal Right",
+            "Open another session for this host beside the active pane",
+            CommandAction::SplitRight,
+            icons::ui::TERMINAL,
+        ),
+        command(
+            "Split Terminal Down",
+            "Open another session for this host below the active pane",
+            CommandAction::SplitDown,
+            icons::ui::TERMINAL,
+        ),
+        command(
+            "Record Macro",
+            "Start or stop recording input in the active terminal",
+            CommandAction::ToggleMacroRecording,
+            icons::ui::TERMINAL,
+        ),
     ];
 
+    commands.extend(terminal_profiles.profiles.iter().map(|profile| {
+        let shell = profile.shell.as_deref().unwrap_or("Login shell");
+        let subtitle = if profile.args.is_empty() {
+            shell.to_string()
+        } else {
+            format!("{} {}", shell, profile.args.join(" "))
+        };
+        command(
+            format!("Local Terminal: {}", profile.name),
+            subtitle,
+            CommandAction::LocalTerminalProfile(profile.id),
+            icons::ui::TERMINAL,
+        )
+    }));
+
+    commands.extend(launch_targets.iter().map(|target| {
+        command(
+            target.name.clone(),
+            target.detail.clone(),
+            CommandAction::LocalTerminalTarget(target.clone()),
+            icons::ui::TERMINAL,
+        )
+    }));
+
     if portal_hub_configured {
         commands.push(command(
             "Sync Portal Hub",
@@ -107,7 +176,7 @@ pub fn available_commands(
         )
     }));
 
-    commands.extend(snippets.snippets.iter().map(|snippet| {
+    commands.extend(snippets.snippets_for_host(snippet_host).map(|snippet| {
         command(
             format!("Run {}", snippet.name),
             snippet.command.clone(),
@@ -116,6 +185,19 @@ pub fn available_commands(
         )
     }));
 
+    commands.extend(macros.macros.iter().map(|terminal_macro| {
+        let subtitle = match &terminal_macro.keybinding {
+            Some(binding) => format!("Replay in the active terminal ({})", binding),
+            None => "Replay in the active terminal".to_string(),
+        };
+        command(
+            format!("Play {}", terminal_macro.name),
+            subtitle,
+            CommandAction::PlayMacro(terminal_macro.id),
+            icons::ui::TERMINAL,
+        )
+    }));
+
     commands
 }
 
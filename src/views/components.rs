@@ -285,17 +285,17 @@ pub fn toggle_group<'a, T, Message, F>(
     fonts: ScaledFonts,
 ) -> Element<'a, Message>
 where
-    T: Copy + PartialEq + 'a,
+    T: Clone + PartialEq + 'a,
     Message: Clone + 'a,
     F: Fn(T) -> Message + Copy + 'a,
 {
     let mut controls = Row::new().spacing(2).align_y(Alignment::Center);
 
-    for &(value, label) in options {
+    for (value, label) in options {
         controls = controls.push(toggle_group_button(
             label,
-            value == current,
-            on_select(value),
+            *value == current,
+            on_select(value.clone()),
             theme,
             fonts,
         ));
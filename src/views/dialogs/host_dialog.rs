@@ -4,13 +4,15 @@ use iced::widget::{
     Row, Space, button, checkbox, column, container, pick_list, row, scrollable, text, text_input,
 };
 use iced::{Alignment, Element, Length};
+use secrecy::{ExposeSecret, SecretString};
 use uuid::Uuid;
 
 use crate::config::hosts::{HubRouting, default_username};
 use crate::config::{AuthMethod, Host, PortForward, PortForwardKind, Protocol};
 use crate::hub::vault::{VaultKey, VaultSecret};
-use crate::message::{DialogMessage, HostDialogField, Message};
-use crate::theme::{BORDER_RADIUS, ScaledFonts, Theme};
+use crate::message::{DialogMessage, HostDialogField, HostMessage, Message};
+use crate::ssh::auth::AgentIdentity;
+use crate::theme::{BORDER_RADIUS, STATUS_SUCCESS, ScaledFonts, Theme};
 use crate::validation::{validate_hostname, validate_port, validate_username};
 use crate::views::components::toggle_group;
 
@@ -30,10 +32,15 @@ pub fn host_dialog_field_id(index: usize) -> iced::widget::Id {
         5 => iced::widget::Id::new("host_dialog_field_5"),
         6 => iced::widget::Id::new("host_dialog_field_6"),
         7 => iced::widget::Id::new("host_dialog_field_7"),
+        8 => iced::widget::Id::new("host_dialog_field_8"),
         _ => iced::widget::Id::new("host_dialog_field_0"),
     }
 }
 
+/// Fields checked by [`HostDialogState::validate`], keyed as in
+/// `validation_errors`
+const VALIDATED_FIELDS: [&str; 5] = ["name", "hostname", "port", "username", "vault_key"];
+
 /// State for the host dialog (add or edit)
 #[derive(Debug, Clone)]
 pub struct HostDialogState {
@@ -48,13 +55,22 @@ pub struct HostDialogState {
     pub key_source: KeySourceChoice,
     pub key_path: String,
     pub vault_key_id: Option<Uuid>,
+    /// Fingerprint of the agent key to offer first
+    pub agent_identity: Option<String>,
+    /// Keys listed from the running agent; `None` until loaded
+    pub agent_identities: Option<Result<Vec<AgentIdentity>, String>>,
+    pub agent_identities_loading: bool,
     pub vnc_password_id: Option<Uuid>,
     /// SSH host to tunnel VNC traffic through
     pub vnc_via_ssh_host_id: Option<Uuid>,
     pub agent_forwarding: bool,
+    /// Per-host session logging override; `None` follows the global setting
+    pub session_logging: Option<bool>,
     pub hub_routing: HubRouting,
     /// Jump (bastion) host to tunnel through
     pub jump_host_id: Option<Uuid>,
+    /// Directory the SFTP browser opens at
+    pub sftp_start_path: String,
     pub tags: String,
     pub notes: String,
     /// Connection protocol
@@ -64,6 +80,13 @@ pub struct HostDialogState {
     pub port_forwards_expanded: bool,
     pub port_forward_editor: Option<PortForwardEditorState>,
     pub delete_requested: bool,
+    /// Existing host (id, name) with the same hostname, port and user,
+    /// shown before a new host duplicates it; saving again adds it anyway
+    pub duplicate_of: Option<(Uuid, String)>,
+    /// Outcome of the last "Test" press; `None` until one is run
+    pub connection_test: Option<ConnectionTestStatus>,
+    /// Password or key passphrase used by the test only, never saved
+    pub test_secret: SecretString,
     /// Validation errors by field name
     pub validation_errors: HashMap<String, String>,
 }
@@ -124,6 +147,37 @@ impl From<&VaultKey> for VaultKeyOption {
     }
 }
 
+/// Entry of the agent key picker; `fingerprint: None` offers every key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentIdentityOption {
+    pub fingerprint: Option<String>,
+    pub label: String,
+}
+
+impl AgentIdentityOption {
+    fn any() -> Self {
+        Self {
+            fingerprint: None,
+            label: "Any agent key".to_string(),
+        }
+    }
+}
+
+impl From<&AgentIdentity> for AgentIdentityOption {
+    fn from(identity: &AgentIdentity) -> Self {
+        Self {
+            fingerprint: Some(identity.fingerprint.clone()),
+            label: identity.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for AgentIdentityOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
 impl std::fmt::Display for VaultKeyOption {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.label)
@@ -151,6 +205,17 @@ impl ProtocolChoice {
     pub const ALL: [ProtocolChoice; 2] = [ProtocolChoice::Ssh, ProtocolChoice::Vnc];
 }
 
+/// Progress of the host dialog's connection test
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionTestStatus {
+    /// The test with this id is connecting
+    Running(Uuid),
+    Succeeded(String),
+    Failed(String),
+    /// The key is encrypted; the test asks for its passphrase
+    PassphraseRequired(String),
+}
+
 /// Simplified auth method for the dropdown
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AuthMethodChoice {
@@ -362,18 +427,26 @@ impl HostDialogState {
             },
             key_path: String::new(),
             vault_key_id: default_vault_key.filter(|_| prefer_vault),
+            agent_identity: None,
+            agent_identities: None,
+            agent_identities_loading: false,
             vnc_password_id: None,
             vnc_via_ssh_host_id: None,
             agent_forwarding: false,
+            session_logging: None,
             hub_routing: HubRouting::Auto,
             jump_host_id: None,
             tags: String::new(),
+            sftp_start_path: String::new(),
             notes: String::new(),
             protocol: ProtocolChoice::Ssh,
             port_forwards: Vec::new(),
             port_forwards_expanded: false,
             port_forward_editor: None,
             delete_requested: false,
+            duplicate_of: None,
+            connection_test: None,
+            test_secret: SecretString::from(String::new()),
             validation_errors: HashMap::new(),
         }
     }
@@ -410,12 +483,17 @@ impl HostDialogState {
                 AuthMethod::PublicKey { vault_key_id, .. } => *vault_key_id,
                 _ => None,
             },
+            agent_identity: host.agent_identity.clone(),
+            agent_identities: None,
+            agent_identities_loading: false,
             vnc_password_id: host.vnc_password_id,
             vnc_via_ssh_host_id: host.vnc_via_ssh_host_id,
             agent_forwarding: host.agent_forwarding,
+            session_logging: host.session_logging,
             hub_routing: host.hub_routing,
             jump_host_id: host.jump_host_id,
             tags: host.tags.join(", "),
+            sftp_start_path: host.sftp_start_path.clone().unwrap_or_default(),
             notes: host.notes.clone().unwrap_or_default(),
             protocol: match host.protocol {
                 Protocol::Ssh => ProtocolChoice::Ssh,
@@ -425,6 +503,9 @@ impl HostDialogState {
             port_forwards_expanded: false,
             port_forward_editor: None,
             delete_requested: false,
+            duplicate_of: None,
+            connection_test: None,
+            test_secret: SecretString::from(String::new()),
             validation_errors: HashMap::new(),
         }
     }
@@ -433,39 +514,45 @@ impl HostDialogState {
     /// Also updates self.validation_errors with results.
     pub fn validate(&mut self) -> bool {
         self.validation_errors.clear();
-
-        // Name is required
-        if self.name.trim().is_empty() {
-            self.validation_errors
-                .insert("name".to_string(), "Name is required".to_string());
-        }
-
-        // Validate hostname
-        if let Err(e) = validate_hostname(&self.hostname) {
-            self.validation_errors
-                .insert("hostname".to_string(), e.message);
-        }
-
-        // Validate port
-        if let Err(e) = validate_port(&self.port) {
-            self.validation_errors.insert("port".to_string(), e.message);
+        for field in VALIDATED_FIELDS {
+            if let Some(error) = self.field_error(field) {
+                self.validation_errors.insert(field.to_string(), error);
+            }
         }
+        self.validation_errors.is_empty()
+    }
 
-        // Validate username (empty is allowed)
-        if let Err(e) = validate_username(&self.username) {
-            self.validation_errors
-                .insert("username".to_string(), e.message);
+    /// Re-check one field after an edit so its inline error appears or
+    /// clears while typing. Untouched fields keep showing nothing.
+    pub fn validate_field(&mut self, field: &str) {
+        match self.field_error(field) {
+            Some(error) => {
+                self.validation_errors.insert(field.to_string(), error);
+            }
+            None => {
+                self.validation_errors.remove(field);
+            }
         }
+    }
 
-        if self.auth_method == AuthMethodChoice::PublicKey
-            && self.key_source == KeySourceChoice::Vault
-            && self.vault_key_id.is_none()
-        {
-            self.validation_errors
-                .insert("vault_key".to_string(), "Select a vault key".to_string());
+    /// Error for one field's current value, if it is invalid
+    fn field_error(&self, field: &str) -> Option<String> {
+        match field {
+            "name" => self
+                .name
+                .trim()
+                .is_empty()
+                .then(|| "Name is required".to_string()),
+            "hostname" => validate_hostname(&self.hostname).err().map(|e| e.message),
+            "port" => validate_port(&self.port).err().map(|e| e.message),
+            // Empty is allowed (defaults to the local user)
+            "username" => validate_username(&self.username).err().map(|e| e.message),
+            "vault_key" => (self.auth_method == AuthMethodChoice::PublicKey
+                && self.key_source == KeySourceChoice::Vault
+                && self.vault_key_id.is_none())
+            .then(|| "Select a vault key".to_string()),
+            _ => None,
         }
-
-        self.validation_errors.is_empty()
     }
 
     /// Convert dialog state to a Host struct.
@@ -539,6 +626,11 @@ impl HostDialogState {
         } else {
             false
         };
+        let session_logging = if protocol == Protocol::Ssh {
+            self.session_logging
+        } else {
+            None
+        };
 
         let hub_routing = if protocol == Protocol::Ssh
             && !matches!(auth, AuthMethod::Password | AuthMethod::KeyboardInteractive)
@@ -554,6 +646,13 @@ impl HostDialogState {
             None
         };
 
+        let sftp_start_path =
+            if protocol == Protocol::Ssh && !self.sftp_start_path.trim().is_empty() {
+                Some(self.sftp_start_path.trim().to_string())
+            } else {
+                None
+            };
+
         let vnc_port = if protocol == Protocol::Vnc && port != 5900 {
             Some(port)
         } else {
@@ -576,6 +675,12 @@ impl HostDialogState {
             Vec::new()
         };
 
+        let agent_identity = if matches!(auth, AuthMethod::Agent) {
+            self.agent_identity.clone()
+        } else {
+            None
+        };
+
         Some(Host {
             id,
             name: self.name.trim().to_string(),
@@ -588,11 +693,19 @@ impl HostDialogState {
             vnc_via_ssh_host_id,
             allow_cleartext_vnc: false,
             auth,
+            auth_fallbacks: Vec::new(),
+            agent_identity,
             agent_forwarding,
             port_forwards,
             hub_routing,
             jump_host_id,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            session_logging,
+            sftp_start_path,
+            last_sftp_path: None,
             group_id: None,
+            order: 0,
             notes,
             tags,
             created_at,
@@ -602,21 +715,118 @@ impl HostDialogState {
         })
     }
 
-    /// Check if the form has no validation errors.
-    /// Does not run validation; use validate() first for accurate results.
+    /// Whether every field currently holds a valid value; Save stays
+    /// disabled until it does
     pub fn is_valid(&self) -> bool {
-        // Quick check - name and hostname must not be empty
-        if self.name.trim().is_empty() || self.hostname.trim().is_empty() {
-            return false;
-        }
-        // If we've run validation, check for errors
-        self.validation_errors.is_empty()
+        VALIDATED_FIELDS
+            .iter()
+            .all(|field| self.field_error(field).is_none())
     }
 
     /// Get validation error for a specific field
     pub fn get_error(&self, field: &str) -> Option<&String> {
         self.validation_errors.get(field)
     }
+
+    /// Whether a connection test is in flight
+    pub fn is_testing(&self) -> bool {
+        matches!(self.connection_test, Some(ConnectionTestStatus::Running(_)))
+    }
+
+    /// Label for the secret the connection test needs, if any: the password
+    /// for password logins, or the key passphrase once the key turned out to
+    /// be encrypted
+    pub fn test_secret_label(&self) -> Option<&'static str> {
+        if self.protocol != ProtocolChoice::Ssh {
+            return None;
+        }
+        match self.auth_method {
+            AuthMethodChoice::Password => Some("Password"),
+            AuthMethodChoice::PublicKey
+                if matches!(
+                    self.connection_test,
+                    Some(ConnectionTestStatus::PassphraseRequired(_))
+                ) || !self.test_secret.expose_secret().is_empty() =>
+            {
+                Some("Key passphrase")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Picker for the agent key offered first, filled from the running agent
+fn agent_identity_section(
+    state: &HostDialogState,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let mut options = vec![AgentIdentityOption::any()];
+    if let Some(Ok(identities)) = &state.agent_identities {
+        options.extend(identities.iter().map(AgentIdentityOption::from));
+    }
+    // Keep the saved key selectable before the agent has been asked
+    if let Some(fingerprint) = &state.agent_identity
+        && !options
+            .iter()
+            .any(|option| option.fingerprint.as_ref() == Some(fingerprint))
+    {
+        options.push(AgentIdentityOption {
+            fingerprint: Some(fingerprint.clone()),
+            label: fingerprint.clone(),
+        });
+    }
+    let selected = options
+        .iter()
+        .find(|option| option.fingerprint == state.agent_identity)
+        .cloned();
+
+    let load_label = if state.agent_identities_loading {
+        "Loading..."
+    } else {
+        "Load keys"
+    };
+    let load_button = button(text(load_label).size(fonts.label))
+        .padding([8, 10])
+        .style(secondary_button_style(theme))
+        .on_press_maybe(
+            (!state.agent_identities_loading)
+                .then_some(Message::Dialog(DialogMessage::AgentIdentitiesLoad)),
+        );
+
+    let status = match &state.agent_identities {
+        Some(Err(error)) => text(error.clone()).size(fonts.small).color(ERROR_COLOR),
+        Some(Ok(identities)) if identities.is_empty() => text("The agent holds no keys.")
+            .size(fonts.small)
+            .color(theme.text_tertiary),
+        _ => text("Offered first; the agent's other keys are tried after it.")
+            .size(fonts.small)
+            .color(theme.text_tertiary),
+    };
+
+    column![
+        text("Agent Key")
+            .size(fonts.label)
+            .color(theme.text_secondary),
+        row![
+            pick_list(options, selected, |choice| {
+                Message::Dialog(DialogMessage::FieldChanged(
+                    HostDialogField::AgentIdentity,
+                    choice.fingerprint.unwrap_or_default(),
+                ))
+            })
+            .width(Length::Fill)
+            .padding(8)
+            .style(dialog_pick_list_style(theme))
+            .menu_style(dialog_pick_list_menu_style(theme)),
+            load_button,
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        status,
+    ]
+    .spacing(4)
+    .into()
 }
 
 /// Build the host dialog view
@@ -647,9 +857,11 @@ pub fn host_dialog_view(
     let selected_vault_key_id = state.vault_key_id;
     let selected_vnc_password_id = state.vnc_password_id;
     let agent_forwarding = state.agent_forwarding;
+    let session_logging = state.session_logging;
     let hub_routing = state.hub_routing;
     let tags_value = state.tags.clone();
     let notes_value = state.notes.clone();
+    let sftp_start_path_value = state.sftp_start_path.clone();
     let auth_method = state.auth_method;
     let protocol = state.protocol;
     let is_vnc = protocol == ProtocolChoice::Vnc;
@@ -847,6 +1059,8 @@ pub fn host_dialog_view(
             .spacing(4)
             .into()
         }
+    } else if auth_method == AuthMethodChoice::Agent {
+        agent_identity_section(state, theme, fonts)
     } else {
         column![].into()
     };
@@ -985,6 +1199,35 @@ pub fn host_dialog_view(
             text("Forwards your local SSH agent to this host. Only enable for trusted systems.")
                 .size(fonts.small)
                 .color(theme.text_tertiary),
+            text("Session logging")
+                .size(fonts.label)
+                .color(theme.text_secondary),
+            toggle_group(
+                session_logging,
+                &[
+                    (None, "Default"),
+                    (Some(true), "Always"),
+                    (Some(false), "Never"),
+                ],
+                |logging| Message::Dialog(DialogMessage::FieldChanged(
+                    HostDialogField::SessionLogging,
+                    match logging {
+                        None => "Default",
+                        Some(true) => "Always",
+                        Some(false) => "Never",
+                    }
+                    .to_string(),
+                )),
+                theme,
+                fonts,
+            ),
+            text(match session_logging {
+                None => "Follows the Session logging setting.",
+                Some(true) => "Terminal output is always saved to a log file.",
+                Some(false) => "Never logged, even when Session logging is on.",
+            })
+            .size(fonts.small)
+            .color(theme.text_tertiary),
         ]
         .spacing(6)
         .into()
@@ -1078,6 +1321,23 @@ pub fn host_dialog_view(
     ]
     .spacing(4);
 
+    let sftp_start_path_input = column![
+        text("SFTP Start Directory")
+            .size(fonts.label)
+            .color(theme.text_secondary),
+        text_input("Home directory", &sftp_start_path_value)
+            .id(host_dialog_field_id(8))
+            .on_input(|s| Message::Dialog(DialogMessage::FieldChanged(
+                HostDialogField::SftpStartPath,
+                s
+            )))
+            .on_submit(Message::Dialog(DialogMessage::Submit))
+            .padding(8)
+            .width(Length::Fill)
+            .style(dialog_input_style(theme))
+    ]
+    .spacing(4);
+
     // Clone port forward data to avoid lifetime issues
     let port_forwards = state.port_forwards.clone();
     let port_forwards_expanded = state.port_forwards_expanded;
@@ -1110,14 +1370,7 @@ pub fn host_dialog_view(
             } else {
                 for forward in &port_forwards {
                     let forward_id = forward.id;
-                    let summary = format!(
-                        "{} {}:{} -> {}:{}",
-                        forward.kind,
-                        forward.bind_host,
-                        forward.bind_port,
-                        forward.target_host,
-                        forward.target_port
-                    );
+                    let summary = forward.summary();
                     let description_text = forward.description.clone();
                     let mut row_content = row![
                         checkbox(forward.enabled)
@@ -1335,6 +1588,14 @@ pub fn host_dialog_view(
     .padding([8, 16])
     .style(secondary_button_style(theme))
     .on_press(Message::Dialog(DialogMessage::ImportFromSshConfig));
+    let import_file_button = button(
+        text("Import File...")
+            .size(fonts.button_small)
+            .color(theme.text_primary),
+    )
+    .padding([8, 16])
+    .style(secondary_button_style(theme))
+    .on_press(Message::Dialog(DialogMessage::ImportSshConfigFile));
 
     let cancel_button = button(
         text("Cancel")
@@ -1345,7 +1606,13 @@ pub fn host_dialog_view(
     .style(secondary_button_style(theme))
     .on_press(Message::Dialog(DialogMessage::Close));
 
-    let save_button = button(text("Save").size(fonts.button_small))
+    // A new host matching an existing endpoint asks once before saving
+    let save_label = if state.duplicate_of.is_some() {
+        "Add Anyway"
+    } else {
+        "Save"
+    };
+    let save_button = button(text(save_label).size(fonts.button_small))
         .padding([8, 16])
         .style(primary_button_style(theme))
         .on_press_maybe(if is_valid {
@@ -1357,7 +1624,23 @@ pub fn host_dialog_view(
     let mut button_row = Row::new()
         .spacing(8)
         .align_y(Alignment::Center)
-        .push(import_button);
+        .push(import_button)
+        .push(import_file_button);
+
+    if let Some((existing_id, existing_name)) = &state.duplicate_of {
+        let warning = text(format!("Same host, port and user as {}", existing_name))
+            .size(fonts.label)
+            .color(theme.text_secondary);
+        let edit_existing = button(
+            text("Edit Existing")
+                .size(fonts.button_small)
+                .color(theme.text_primary),
+        )
+        .padding([8, 16])
+        .style(secondary_button_style(theme))
+        .on_press(Message::Host(HostMessage::Edit(*existing_id)));
+        button_row = button_row.push(warning).push(edit_existing);
+    }
 
     if state.editing_id.is_some() {
         let delete_label = if state.delete_requested {
@@ -1377,10 +1660,33 @@ pub fn host_dialog_view(
         button_row = button_row.push(delete_button);
     }
 
-    let button_row = button_row
-        .push(Space::new().width(Length::Fill))
-        .push(cancel_button)
-        .push(save_button);
+    let mut button_row = button_row.push(Space::new().width(Length::Fill));
+    if !is_vnc {
+        let test_label = if state.is_testing() {
+            "Testing..."
+        } else {
+            "Test"
+        };
+        let test_button = button(
+            text(test_label)
+                .size(fonts.button_small)
+                .color(theme.text_primary),
+        )
+        .padding([8, 16])
+        .style(secondary_button_style(theme))
+        .on_press_maybe(
+            (is_valid && !state.is_testing())
+                .then_some(Message::Dialog(DialogMessage::ConnectionTest)),
+        );
+        button_row = button_row.push(test_button);
+    }
+    let button_row = button_row.push(cancel_button).push(save_button);
+
+    let mut footer_content = column![].spacing(10);
+    if !is_vnc && let Some(test_row) = connection_test_row(state, is_valid, theme, fonts) {
+        footer_content = footer_content.push(test_row);
+    }
+    let footer_content = footer_content.push(button_row);
 
     // Protocol picker
     let protocol_picker = column![
@@ -1424,6 +1730,7 @@ pub fn host_dialog_view(
         auth_picker,
         key_path_section,
         jump_host_section,
+        sftp_start_path_input,
         routing_section,
         security_section,
     ]
@@ -1451,7 +1758,7 @@ pub fn host_dialog_view(
         .padding([18, 24])
         .width(Length::Fill);
 
-    let footer = container(button_row)
+    let footer = container(footer_content)
         .padding([14, 24])
         .width(Length::Fill)
         .style(move |_theme| container::Style {
@@ -1476,6 +1783,66 @@ pub fn host_dialog_view(
     host_dialog_backdrop(form, theme)
 }
 
+/// Result of the last connection test, with an input for the password or
+/// key passphrase the test needs
+fn connection_test_row(
+    state: &HostDialogState,
+    is_valid: bool,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Option<Element<'static, Message>> {
+    let secret_label = state.test_secret_label();
+    let status = match &state.connection_test {
+        None if secret_label.is_none() => return None,
+        None => None,
+        Some(ConnectionTestStatus::Running(_)) => Some(
+            text("Testing connection...")
+                .size(fonts.label)
+                .color(theme.text_secondary),
+        ),
+        Some(ConnectionTestStatus::Succeeded(message)) => Some(
+            text(message.clone())
+                .size(fonts.label)
+                .color(STATUS_SUCCESS),
+        ),
+        Some(ConnectionTestStatus::Failed(message)) => {
+            Some(text(message.clone()).size(fonts.label).color(ERROR_COLOR))
+        }
+        Some(ConnectionTestStatus::PassphraseRequired(message)) => Some(
+            text(message.clone())
+                .size(fonts.label)
+                .color(theme.text_secondary),
+        ),
+    };
+
+    let mut test_row = Row::new().spacing(12).align_y(Alignment::Center);
+    if let Some(label) = secret_label {
+        let secret_input = text_input(
+            &format!("{} for the test (not saved)", label),
+            state.test_secret.expose_secret(),
+        )
+        .size(fonts.body)
+        .padding(8)
+        .width(Length::Fixed(260.0))
+        .secure(true)
+        .style(dialog_input_style(theme))
+        .on_input(|value| {
+            Message::Dialog(DialogMessage::ConnectionTestSecretChanged(
+                SecretString::from(value),
+            ))
+        })
+        .on_submit_maybe(
+            (is_valid && !state.is_testing())
+                .then_some(Message::Dialog(DialogMessage::ConnectionTest)),
+        );
+        test_row = test_row.push(secret_input);
+    }
+    if let Some(status) = status {
+        test_row = test_row.push(status);
+    }
+    Some(test_row.into())
+}
+
 fn section_heading(
     label: &'static str,
     theme: Theme,
@@ -1572,4 +1939,49 @@ mod tests {
             Some(&"Select a vault key".to_string())
         );
     }
+
+    #[test]
+    fn edited_fields_show_errors_and_invalid_values_disable_save() {
+        let mut state = HostDialogState::new_host();
+        state.name = "prod".to_string();
+        state.hostname = "prod.example.test".to_string();
+        assert!(state.is_valid());
+
+        state.port = "70000".to_string();
+        state.validate_field("port");
+        assert_eq!(
+            state.get_error("port"),
+            Some(&"Port must be between 1 and 65535".to_string())
+        );
+        assert!(!state.is_valid());
+
+        // Untouched invalid fields disable Save without showing an error
+        state.port = "2222".to_string();
+        state.validate_field("port");
+        state.hostname = "bad host".to_string();
+        assert_eq!(state.get_error("port"), None);
+        assert_eq!(state.get_error("hostname"), None);
+        assert!(!state.is_valid());
+
+        state.hostname = "[2001:db8::1]".to_string();
+        state.validate_field("hostname");
+        assert!(state.is_valid());
+    }
+
+    #[test]
+    fn connection_test_asks_for_the_secret_it_needs() {
+        let mut state = HostDialogState::new_host();
+        state.auth_method = AuthMethodChoice::Password;
+        assert_eq!(state.test_secret_label(), Some("Password"));
+
+        state.auth_method = AuthMethodChoice::PublicKey;
+        assert_eq!(state.test_secret_label(), None);
+        state.connection_test = Some(ConnectionTestStatus::PassphraseRequired(
+            "id_ed25519 is encrypted".to_string(),
+        ));
+        assert_eq!(state.test_secret_label(), Some("Key passphrase"));
+
+        state.protocol = ProtocolChoice::Vnc;
+        assert_eq!(state.test_secret_label(), None);
+    }
 }
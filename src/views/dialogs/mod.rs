@@ -6,7 +6,10 @@ pub mod host_dialog;
 pub mod host_key_dialog;
 pub mod passphrase_dialog;
 pub mod password_dialog;
+pub mod port_forwards_dialog;
 pub mod portal_hub_dialogs;
 pub mod quick_connect_dialog;
+pub mod quit_confirm_dialog;
 pub mod session_choice_dialog;
+pub mod snippet_params_dialog;
 pub mod vnc_cleartext_dialog;
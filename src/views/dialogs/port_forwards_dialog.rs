@@ -0,0 +1,171 @@
+//! Panel listing a live SSH session's port forwards, with their status and
+//! controls to start or stop each one without reconnecting.
+
+use std::collections::HashMap;
+
+use iced::widget::{Column, Space, button, column, container, row, text};
+use iced::{Alignment, Element, Length};
+use uuid::Uuid;
+
+use crate::app::managers::ForwardStatus;
+use crate::config::PortForward;
+use crate::icons::{self, icon_with_color};
+use crate::message::{DialogMessage, HostMessage, Message, SessionId, SessionMessage};
+use crate::theme::{BORDER_RADIUS, STATUS_FAILURE, STATUS_SUCCESS, ScaledFonts, Theme};
+
+use super::common::{dialog_backdrop, secondary_button_style};
+
+/// State for the port forwards panel
+#[derive(Debug, Clone)]
+pub struct PortForwardsDialogState {
+    pub session_id: SessionId,
+}
+
+/// Build the port forwards panel for one session
+pub fn port_forwards_dialog_view(
+    state: &PortForwardsDialogState,
+    host_id: Option<Uuid>,
+    forwards: &[PortForward],
+    status: &HashMap<Uuid, ForwardStatus>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let title = row![
+        icon_with_color(icons::ui::ZAP, 24, theme.accent),
+        text("Port Forwards")
+            .size(fonts.heading)
+            .color(theme.text_primary),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    let mut rows: Vec<Element<'static, Message>> = Vec::new();
+    if forwards.is_empty() {
+        rows.push(
+            text("No port forwards configured for this host.")
+                .size(fonts.body)
+                .color(theme.text_secondary)
+                .into(),
+        );
+    }
+    for forward in forwards {
+        let forward_status = status
+            .get(&forward.id)
+            .cloned()
+            .unwrap_or(ForwardStatus::Stopped);
+        rows.push(forward_row(
+            state.session_id,
+            forward,
+            forward_status,
+            theme,
+            fonts,
+        ));
+    }
+
+    let mut button_row = row![Space::new().width(Length::Fill)].spacing(8);
+    if let Some(host_id) = host_id {
+        button_row = button_row.push(
+            button(
+                text("Edit Forwards")
+                    .size(fonts.button_small)
+                    .color(theme.text_primary),
+            )
+            .padding([8, 16])
+            .style(secondary_button_style(theme))
+            .on_press(Message::Host(HostMessage::Edit(host_id))),
+        );
+    }
+    button_row = button_row.push(
+        button(
+            text("Close")
+                .size(fonts.button_small)
+                .color(theme.text_primary),
+        )
+        .padding([8, 16])
+        .style(secondary_button_style(theme))
+        .on_press(Message::Dialog(DialogMessage::Close)),
+    );
+
+    let content = column![
+        title,
+        Space::new().height(12),
+        Column::with_children(rows).spacing(8),
+        Space::new().height(24),
+        button_row,
+    ]
+    .spacing(4)
+    .padding(24)
+    .width(Length::Fixed(560.0));
+
+    dialog_backdrop(content, theme)
+}
+
+fn forward_row(
+    session_id: SessionId,
+    forward: &PortForward,
+    status: ForwardStatus,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let forward_id = forward.id;
+    let (status_label, status_color, action_label, action) = match &status {
+        ForwardStatus::Active(port) => (
+            format!("Active on port {}", port),
+            STATUS_SUCCESS,
+            "Stop",
+            SessionMessage::StopPortForward(session_id, forward_id),
+        ),
+        ForwardStatus::Stopped => (
+            "Stopped".to_string(),
+            theme.text_muted,
+            "Start",
+            SessionMessage::StartPortForward(session_id, forward_id),
+        ),
+        ForwardStatus::Failed(error) => (
+            format!("Failed: {}", error),
+            STATUS_FAILURE,
+            "Retry",
+            SessionMessage::StartPortForward(session_id, forward_id),
+        ),
+    };
+
+    let mut details = column![
+        text(forward.summary())
+            .size(fonts.label)
+            .color(theme.text_primary),
+        text(status_label).size(fonts.small).color(status_color),
+    ]
+    .spacing(2)
+    .width(Length::Fill);
+    if let Some(description) = &forward.description {
+        details = details.push(
+            text(description.clone())
+                .size(fonts.small)
+                .color(theme.text_secondary),
+        );
+    }
+
+    let content = row![
+        details,
+        button(text(action_label).size(fonts.label))
+            .padding([4, 10])
+            .style(secondary_button_style(theme))
+            .on_press(Message::Session(action)),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    container(content)
+        .padding([10, 12])
+        .width(Length::Fill)
+        .style(move |_theme| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: BORDER_RADIUS.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,135 @@
+//! Confirmation shown when the window is closed while sessions or
+//! transfers are still running.
+
+use iced::widget::{Space, button, checkbox, column, row, text};
+use iced::{Alignment, Element, Length};
+
+use crate::icons::{self, icon_with_color};
+use crate::message::{DialogMessage, Message};
+use crate::theme::{ScaledFonts, Theme};
+
+use super::common::{dialog_backdrop, primary_button_style, secondary_button_style};
+
+/// State for the quit confirmation dialog
+#[derive(Debug, Clone)]
+pub struct QuitConfirmDialogState {
+    /// Open terminal and VNC sessions
+    pub sessions: usize,
+    /// SFTP transfers still running
+    pub transfers: usize,
+    /// "Don't ask again" checkbox state
+    pub dont_ask_again: bool,
+}
+
+impl QuitConfirmDialogState {
+    pub fn new(sessions: usize, transfers: usize) -> Self {
+        Self {
+            sessions,
+            transfers,
+            dont_ask_again: false,
+        }
+    }
+
+    /// "3 sessions, 1 transfer in progress"
+    pub fn summary(&self) -> String {
+        let plural = |count: usize, noun: &str| {
+            if count == 1 {
+                format!("1 {}", noun)
+            } else {
+                format!("{} {}s", count, noun)
+            }
+        };
+        match (self.sessions, self.transfers) {
+            (sessions, 0) => format!("{} open", plural(sessions, "session")),
+            (0, transfers) => format!("{} in progress", plural(transfers, "transfer")),
+            (sessions, transfers) => format!(
+                "{}, {} in progress",
+                plural(sessions, "session"),
+                plural(transfers, "transfer")
+            ),
+        }
+    }
+}
+
+/// Build the quit confirmation dialog view
+pub fn quit_confirm_dialog_view(
+    state: &QuitConfirmDialogState,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let warning_color = iced::Color::from_rgb8(230, 160, 30);
+    let warning_icon = icon_with_color(icons::ui::ALERT_TRIANGLE, 28, warning_color);
+
+    let title = text("Quit Portal?")
+        .size(fonts.heading)
+        .color(theme.text_primary);
+
+    let summary = text(format!("{} \u{2014} quit anyway?", state.summary()))
+        .size(fonts.body)
+        .color(theme.text_primary);
+
+    let body = text("Quitting disconnects every session and cancels running transfers.")
+        .size(fonts.body)
+        .color(theme.text_secondary);
+
+    let dont_ask_checkbox = checkbox(state.dont_ask_again)
+        .label("Don't ask again")
+        .on_toggle(|value| Message::Dialog(DialogMessage::QuitConfirmDontAskToggled(value)))
+        .spacing(8);
+
+    let cancel_button = button(
+        text("Cancel")
+            .size(fonts.button_small)
+            .color(theme.text_primary),
+    )
+    .padding([8, 16])
+    .style(secondary_button_style(theme))
+    .on_press(Message::Dialog(DialogMessage::QuitConfirmCancel));
+
+    let quit_button = button(text("Quit").size(fonts.button_small))
+        .padding([8, 16])
+        .style(primary_button_style(theme))
+        .on_press(Message::Dialog(DialogMessage::QuitConfirmQuit));
+
+    let button_row = row![Space::new().width(Length::Fill), cancel_button, quit_button].spacing(8);
+
+    let content = column![
+        row![warning_icon, title]
+            .spacing(12)
+            .align_y(Alignment::Center),
+        Space::new().height(12),
+        summary,
+        Space::new().height(8),
+        body,
+        Space::new().height(12),
+        dont_ask_checkbox,
+        Space::new().height(24),
+        button_row,
+    ]
+    .spacing(4)
+    .padding(24)
+    .width(Length::Fixed(420.0));
+
+    dialog_backdrop(content, theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_counts_sessions_and_transfers() {
+        assert_eq!(
+            QuitConfirmDialogState::new(3, 1).summary(),
+            "3 sessions, 1 transfer in progress"
+        );
+        assert_eq!(
+            QuitConfirmDialogState::new(1, 0).summary(),
+            "1 session open"
+        );
+        assert_eq!(
+            QuitConfirmDialogState::new(0, 2).summary(),
+            "2 transfers in progress"
+        );
+    }
+}
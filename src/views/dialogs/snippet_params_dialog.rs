@@ -0,0 +1,208 @@
+//! Prompt for the values of a snippet's `{{name}}` placeholders before it runs
+//! or is inserted into a terminal.
+
+use iced::widget::{Space, button, column, row, text, text_input};
+use iced::{Alignment, Element, Font, Length};
+use uuid::Uuid;
+
+use crate::config::snippets::fill_placeholders;
+use crate::icons::{self, icon_with_color};
+use crate::message::{DialogMessage, Message, SessionId};
+use crate::theme::{ScaledFonts, Theme};
+
+use super::common::{
+    ERROR_COLOR, dialog_backdrop, dialog_input_style, primary_button_style, secondary_button_style,
+};
+
+/// What happens to the command once its placeholders are filled in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnippetParamsTarget {
+    /// Run it on the snippet's hosts
+    Run,
+    /// Type it at the prompt of a terminal session
+    Insert(SessionId),
+}
+
+/// State for the snippet parameter dialog
+#[derive(Debug, Clone)]
+pub struct SnippetParamsDialogState {
+    pub snippet_id: Uuid,
+    pub target: SnippetParamsTarget,
+    pub snippet_name: String,
+    /// Command with placeholders still in place
+    pub command: String,
+    /// Placeholder names, one input each
+    pub names: Vec<String>,
+    pub values: Vec<String>,
+    /// Shown after submitting with empty values
+    pub error: Option<String>,
+}
+
+impl SnippetParamsDialogState {
+    pub fn new(
+        snippet_id: Uuid,
+        snippet_name: String,
+        command: String,
+        names: Vec<String>,
+    ) -> Self {
+        let values = vec![String::new(); names.len()];
+        Self {
+            snippet_id,
+            target: SnippetParamsTarget::Run,
+            snippet_name,
+            command,
+            names,
+            values,
+            error: None,
+        }
+    }
+
+    /// Insert the filled-in command into `session_id` instead of running it
+    pub fn insert_into(mut self, session_id: SessionId) -> Self {
+        self.target = SnippetParamsTarget::Insert(session_id);
+        self
+    }
+
+    pub fn set_value(&mut self, index: usize, value: String) {
+        if let Some(slot) = self.values.get_mut(index) {
+            *slot = value;
+            self.error = None;
+        }
+    }
+
+    /// The command with every placeholder filled in, or an error naming the
+    /// placeholders that are still empty
+    pub fn resolved_command(&self) -> Result<String, String> {
+        let missing: Vec<&str> = self
+            .names
+            .iter()
+            .zip(&self.values)
+            .filter(|(_, value)| value.trim().is_empty())
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Enter a value for {}", missing.join(", ")));
+        }
+
+        let values: Vec<(String, String)> = self
+            .names
+            .iter()
+            .cloned()
+            .zip(self.values.iter().cloned())
+            .collect();
+        Ok(fill_placeholders(&self.command, &values))
+    }
+}
+
+/// Build the snippet parameter dialog view
+pub fn snippet_params_dialog_view(
+    state: &SnippetParamsDialogState,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let snippet_icon = icon_with_color(icons::ui::CODE, 28, theme.accent);
+
+    let title = text(state.snippet_name.clone())
+        .size(fonts.heading)
+        .color(theme.text_primary);
+
+    let command = text(state.command.clone())
+        .size(fonts.mono_tiny)
+        .font(Font::with_name("monospace"))
+        .color(theme.text_secondary);
+
+    let mut content_items: Vec<Element<'static, Message>> = vec![
+        row![snippet_icon, title]
+            .spacing(12)
+            .align_y(Alignment::Center)
+            .into(),
+        Space::new().height(8).into(),
+        command.into(),
+        Space::new().height(8).into(),
+    ];
+
+    for (index, name) in state.names.iter().enumerate() {
+        let label = text(name.clone()).size(fonts.label).color(theme.text_muted);
+
+        let value = state.values.get(index).cloned().unwrap_or_default();
+        let input = text_input("", &value)
+            .size(fonts.body)
+            .padding(10)
+            .width(Length::Fill)
+            .style(dialog_input_style(theme))
+            .on_input(move |value| {
+                Message::Dialog(DialogMessage::SnippetParamChanged(index, value))
+            })
+            .on_submit(Message::Dialog(DialogMessage::SnippetParamsSubmit));
+
+        content_items.push(label.into());
+        content_items.push(Space::new().height(4).into());
+        content_items.push(input.into());
+        content_items.push(Space::new().height(8).into());
+    }
+
+    if let Some(error) = &state.error {
+        content_items.push(
+            text(error.clone())
+                .size(fonts.small)
+                .color(ERROR_COLOR)
+                .into(),
+        );
+    }
+
+    let cancel_button = button(
+        text("Cancel")
+            .size(fonts.button_small)
+            .color(theme.text_primary),
+    )
+    .padding([8, 16])
+    .style(secondary_button_style(theme))
+    .on_press(Message::Dialog(DialogMessage::SnippetParamsCancel));
+
+    let submit_label = match state.target {
+        SnippetParamsTarget::Run => "Run",
+        SnippetParamsTarget::Insert(_) => "Insert",
+    };
+    let run_button = button(text(submit_label).size(fonts.button_small))
+        .padding([8, 16])
+        .style(primary_button_style(theme))
+        .on_press(Message::Dialog(DialogMessage::SnippetParamsSubmit));
+
+    let button_row = row![Space::new().width(Length::Fill), cancel_button, run_button].spacing(8);
+
+    content_items.extend([Space::new().height(16).into(), button_row.into()]);
+
+    let content = column(content_items)
+        .spacing(4)
+        .padding(24)
+        .width(Length::Fixed(420.0));
+
+    dialog_backdrop(content, theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolved_command_requires_every_value() {
+        let mut state = SnippetParamsDialogState::new(
+            Uuid::new_v4(),
+            "Restart".to_string(),
+            "systemctl restart {{service}} --{{mode}}".to_string(),
+            vec!["service".to_string(), "mode".to_string()],
+        );
+
+        state.set_value(0, "nginx".to_string());
+        assert_eq!(
+            state.resolved_command(),
+            Err("Enter a value for mode".to_string())
+        );
+
+        state.set_value(1, "now".to_string());
+        assert_eq!(
+            state.resolved_command(),
+            Ok("systemctl restart nginx --now".to_string())
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Line diff for comparing two text files side by side
+
+/// Largest file that can be compared; bigger files are refused up front
+pub const MAX_DIFF_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Edit distance (in lines) beyond which the differing middle of the files
+/// is shown as one replaced block instead of searching for a minimal diff
+const MAX_EDIT_DISTANCE: usize = 2_000;
+
+/// How a row of the side-by-side view differs between the two files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Same,
+    /// Only in the left file
+    Removed,
+    /// Only in the right file
+    Added,
+    /// Different on each side
+    Changed,
+}
+
+/// A 1-based line number and its text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub number: usize,
+    pub text: String,
+}
+
+/// One row of the side-by-side view; a side is `None` where only the other
+/// file has a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRow {
+    pub kind: DiffKind,
+    pub left: Option<DiffLine>,
+    pub right: Option<DiffLine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Decode a file for comparison, refusing binary content
+pub fn diff_text(name: &str, bytes: Vec<u8>) -> Result<String, String> {
+    if bytes.contains(&0) {
+        return Err(format!("{} is a binary file and cannot be compared", name));
+    }
+    String::from_utf8(bytes)
+        .map_err(|_| format!("{} is not UTF-8 text and cannot be compared", name))
+}
+
+/// Line diff of `left` against `right`, with runs of removed and added lines
+/// paired up as changed rows
+pub fn side_by_side(left: &str, right: &str) -> Vec<DiffRow> {
+    let a: Vec<&str> = left.lines().collect();
+    let b: Vec<&str> = right.lines().collect();
+
+    // Only the differing middle needs the (quadratic worst case) search
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (middle_a, middle_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut edits = vec![Edit::Equal; prefix];
+    edits.extend(edit_script(middle_a, middle_b).unwrap_or_else(|| {
+        let mut replaced = vec![Edit::Delete; middle_a.len()];
+        replaced.extend(vec![Edit::Insert; middle_b.len()]);
+        replaced
+    }));
+    edits.extend(vec![Edit::Equal; suffix]);
+
+    let line = |lines: &[&str], index: usize| DiffLine {
+        number: index + 1,
+        text: lines[index].to_string(),
+    };
+    let mut rows = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut edits = edits.into_iter().peekable();
+    while let Some(edit) = edits.next() {
+        if edit == Edit::Equal {
+            rows.push(DiffRow {
+                kind: DiffKind::Same,
+                left: Some(line(&a, i)),
+                right: Some(line(&b, j)),
+            });
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        let (mut removed, mut added) = (0, 0);
+        let mut count = |edit| match edit {
+            Edit::Delete => removed += 1,
+            _ => added += 1,
+        };
+        count(edit);
+        while let Some(&next) = edits.peek()
+            && next != Edit::Equal
+        {
+            count(next);
+            edits.next();
+        }
+        for n in 0..removed.max(added) {
+            let left = (n < removed).then(|| line(&a, i + n));
+            let right = (n < added).then(|| line(&b, j + n));
+            let kind = match (&left, &right) {
+                (Some(_), Some(_)) => DiffKind::Changed,
+                (Some(_), None) => DiffKind::Removed,
+                _ => DiffKind::Added,
+            };
+            rows.push(DiffRow { kind, left, right });
+        }
+        i += removed;
+        j += added;
+    }
+    rows
+}
+
+/// Shortest edit script turning `a` into `b` (Myers' algorithm), or `None`
+/// when it would take more than `MAX_EDIT_DISTANCE` edits
+fn edit_script(a: &[&str], b: &[&str]) -> Option<Vec<Edit>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max_d = (a.len() + b.len()).min(MAX_EDIT_DISTANCE) as isize;
+    let offset = max_d + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    // Furthest x per diagonal k before each round d, for k in -(d+1)..=d+1
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max_d {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let index = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, n, m));
+            }
+        }
+    }
+    None
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<Edit> {
+    let (mut x, mut y) = (n, m);
+    let mut edits = Vec::new();
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            edits.push(Edit::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            edits.push(if x == prev_x {
+                Edit::Insert
+            } else {
+                Edit::Delete
+            });
+            x = prev_x;
+            y = prev_y;
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(rows: &[DiffRow]) -> Vec<DiffKind> {
+        rows.iter().map(|row| row.kind).collect()
+    }
+
+    #[test]
+    fn identical_files_have_only_same_rows() {
+        let rows = side_by_side("a\nb\nc\n", "a\nb\nc");
+        assert_eq!(kinds(&rows), vec![DiffKind::Same; 3]);
+        assert_eq!(rows[2].right.as_ref().map(|line| line.number), Some(3));
+    }
+
+    #[test]
+    fn edits_are_paired_and_numbered_per_side() {
+        let rows = side_by_side("a\nb\nc\nd\n", "a\nB\nc\nx\nd\ne\n");
+        assert_eq!(
+            kinds(&rows),
+            vec![
+                DiffKind::Same,
+                DiffKind::Changed,
+                DiffKind::Same,
+                DiffKind::Added,
+                DiffKind::Same,
+                DiffKind::Added,
+            ]
+        );
+        assert_eq!(rows[1].left.as_ref().unwrap().text, "b");
+        assert_eq!(rows[1].right.as_ref().unwrap().text, "B");
+        assert!(rows[3].left.is_none());
+        assert_eq!(rows[4].left.as_ref().unwrap().number, 4);
+        assert_eq!(rows[4].right.as_ref().unwrap().number, 5);
+    }
+
+    #[test]
+    fn removed_lines_leave_the_right_side_empty() {
+        let rows = side_by_side("keep\ndrop 1\ndrop 2\n", "keep\n");
+        assert_eq!(
+            kinds(&rows),
+            vec![DiffKind::Same, DiffKind::Removed, DiffKind::Removed]
+        );
+        assert!(rows[2].right.is_none());
+        assert_eq!(kinds(&side_by_side("", "new")), vec![DiffKind::Added]);
+    }
+
+    #[test]
+    fn binary_and_non_utf8_content_is_refused() {
+        assert_eq!(diff_text("a.txt", b"hello".to_vec()).unwrap(), "hello");
+        assert!(diff_text("a.bin", vec![b'a', 0, b'b']).is_err());
+        assert!(diff_text("a.txt", vec![0xff, 0xfe]).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Hex dump formatting for the binary viewer
+
+/// Bytes shown per hex dump row
+pub const HEX_ROW_BYTES: usize = 16;
+
+/// Bytes shown per page; only one page is laid out at a time
+pub const HEX_PAGE_BYTES: usize = HEX_ROW_BYTES * 256;
+
+/// Start of the page containing `offset`
+pub fn page_start(offset: usize) -> usize {
+    offset - offset % HEX_PAGE_BYTES
+}
+
+/// Format the page of `bytes` starting at `offset` as `hexdump -C` style rows:
+/// the offset, 16 bytes in hex split into two groups of eight, and the
+/// printable ASCII characters.
+pub fn hex_dump_page(bytes: &[u8], offset: usize) -> String {
+    let start = page_start(offset).min(bytes.len());
+    let end = (start + HEX_PAGE_BYTES).min(bytes.len());
+
+    let mut out = String::with_capacity((end - start) / HEX_ROW_BYTES * 80 + 80);
+    for (row, chunk) in bytes[start..end].chunks(HEX_ROW_BYTES).enumerate() {
+        if row > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!("{:08x} ", start + row * HEX_ROW_BYTES));
+        for index in 0..HEX_ROW_BYTES {
+            if index % 8 == 0 {
+                out.push(' ');
+            }
+            match chunk.get(index) {
+                Some(byte) => out.push_str(&format!("{:02x} ", byte)),
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str(" |");
+        out.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        }));
+        out.push('|');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_dump_page_matches_hexdump_layout() {
+        let bytes = b"Hello, world!\n\x00\x01ABC";
+        assert_eq!(
+            hex_dump_page(bytes, 0),
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|\n\
+             00000010  41 42 43                                          |ABC|"
+        );
+    }
+
+    #[test]
+    fn hex_dump_page_only_formats_the_requested_page() {
+        let bytes = vec![0u8; HEX_PAGE_BYTES + 32];
+        let second = hex_dump_page(&bytes, HEX_PAGE_BYTES + 20);
+
+        assert_eq!(second.lines().count(), 2);
+        assert!(second.starts_with(&format!("{:08x}", HEX_PAGE_BYTES)));
+        assert_eq!(hex_dump_page(&bytes, 0).lines().count(), 256);
+    }
+}
@@ -0,0 +1,133 @@
+//! Syntax highlighting for the text viewer
+//!
+//! Tokens are classified by iced's syntect-based highlighter; their colors
+//! are then mapped onto the active theme's palette so code matches the UI.
+
+use iced::advanced::text::highlighter::Format;
+use iced::highlighter::{self, Highlight};
+use iced::{Color, Font};
+
+use crate::theme::Theme;
+
+/// Larger buffers are shown as plain text to keep editing responsive
+pub const MAX_HIGHLIGHT_LINES: usize = 20_000;
+
+/// Syntect theme used for classifying tokens; only its brightness matters
+pub fn highlighter_theme(theme: Theme) -> highlighter::Theme {
+    if theme.text_on(theme.background) == Color::WHITE {
+        highlighter::Theme::Base16Ocean
+    } else {
+        highlighter::Theme::InspiredGitHub
+    }
+}
+
+/// Turn a token's highlight into a format using the theme palette colors
+pub fn theme_format(highlight: &Highlight, theme: &iced::Theme) -> Format<Font> {
+    Format {
+        color: highlight
+            .color()
+            .map(|color| palette_color(color, theme.palette())),
+        font: highlight.font(),
+    }
+}
+
+/// The palette color closest to `color`: greys map to the text color (dimmed
+/// when nearer the background, like comments), other colors to the accent
+/// or status color with the nearest hue.
+fn palette_color(color: Color, palette: iced::theme::Palette) -> Color {
+    let (hue, saturation) = hue_saturation(color);
+    if saturation < 0.2 {
+        return if distance(color, palette.background) < distance(color, palette.text) {
+            Color {
+                a: 0.6,
+                ..palette.text
+            }
+        } else {
+            palette.text
+        };
+    }
+
+    [
+        palette.primary,
+        palette.success,
+        palette.warning,
+        palette.danger,
+    ]
+    .into_iter()
+    .min_by(|a, b| {
+        hue_distance(hue, hue_saturation(*a).0).total_cmp(&hue_distance(hue, hue_saturation(*b).0))
+    })
+    .unwrap_or(palette.text)
+}
+
+/// Hue in degrees and HSL saturation
+fn hue_saturation(color: Color) -> (f32, f32) {
+    let max = color.r.max(color.g).max(color.b);
+    let min = color.r.min(color.g).min(color.b);
+    let delta = max - min;
+    if delta <= f32::EPSILON {
+        return (0.0, 0.0);
+    }
+
+    let lightness = (max + min) / 2.0;
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs()).max(f32::EPSILON);
+    let hue = if max == color.r {
+        60.0 * ((color.g - color.b) / delta).rem_euclid(6.0)
+    } else if max == color.g {
+        60.0 * ((color.b - color.r) / delta + 2.0)
+    } else {
+        60.0 * ((color.r - color.g) / delta + 4.0)
+    };
+    (hue, saturation.min(1.0))
+}
+
+fn hue_distance(a: f32, b: f32) -> f32 {
+    let diff = (a - b).abs() % 360.0;
+    diff.min(360.0 - diff)
+}
+
+fn distance(a: Color, b: Color) -> f32 {
+    (a.r - b.r).powi(2) + (a.g - b.g).powi(2) + (a.b - b.b).powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> iced::theme::Palette {
+        iced::theme::Palette {
+            background: Color::from_rgb8(0x1D, 0x20, 0x33),
+            text: Color::from_rgb8(0xE0, 0xE0, 0xE0),
+            primary: Color::from_rgb8(0x00, 0x96, 0xFF),
+            success: Color::from_rgb8(0x40, 0xa0, 0x2b),
+            warning: Color::from_rgb8(0xdf, 0x8e, 0x1d),
+            danger: Color::from_rgb8(0xd2, 0x0f, 0x39),
+        }
+    }
+
+    #[test]
+    fn palette_color_maps_hues_and_greys() {
+        let palette = palette();
+
+        assert_eq!(
+            palette_color(Color::from_rgb8(0x5f, 0x87, 0xd7), palette),
+            palette.primary
+        );
+        assert_eq!(
+            palette_color(Color::from_rgb8(0xa3, 0xbe, 0x8c), palette),
+            palette.success
+        );
+        assert_eq!(
+            palette_color(Color::from_rgb8(0xbf, 0x61, 0x6a), palette),
+            palette.danger
+        );
+        assert_eq!(
+            palette_color(Color::from_rgb8(0xc0, 0xc5, 0xce), palette),
+            palette.text
+        );
+        assert_eq!(
+            palette_color(Color::from_rgb8(0x65, 0x73, 0x7e), palette).a,
+            0.6
+        );
+    }
+}
@@ -1,31 +1,80 @@
 //! File viewer module for in-app file viewing and editing
 //!
 //! Supports text files (with syntax highlighting), images, PDFs, and markdown.
+//! Binary files, or any file on request, are shown as a hex dump. Two text
+//! files can also be compared side by side.
 
+mod diff;
+mod hex;
+mod highlight;
+mod pdf_search;
 mod state;
 mod types;
 
-pub use state::{FileViewerState, ViewerContent};
-pub use types::{FileSource, FileType};
+pub use diff::{DiffKind, DiffLine, DiffRow, MAX_DIFF_BYTES, diff_text, side_by_side};
+pub use pdf_search::{PdfPageText, PdfSearchState, PdfTextRect};
+pub use state::{FileViewerState, GotoLineState, RasterImage, ViewerContent, goto_line_target};
+pub use types::{FileSource, FileType, ImageMetadata};
 
 use iced::widget::{
-    Image, Space, Svg, button, column, container, row, scrollable, text, text_editor,
+    Image, Space, Stack, Svg, button, column, container, markdown, responsive, row, scrollable,
+    text, text_editor, text_input,
 };
-use iced::{Alignment, Color, Element, Fill, Length};
+use iced::{Alignment, Color, Element, Fill, Font, Length, Size, mouse};
+use secrecy::{ExposeSecret, SecretString};
 
 use crate::message::{FileViewerMessage, Message, SessionId};
 use crate::theme::{ScaledFonts, Theme};
+use crate::widgets::mouse_area;
+use state::{
+    IMAGE_PADDING, MAX_IMAGE_ZOOM, MIN_IMAGE_ZOOM, fit_zoom, longest_line_columns,
+    zoom_anchor_offset,
+};
 
 // Error color constant
 const ERROR_COLOR: Color = Color::from_rgb(0.9, 0.3, 0.3);
 
+const MONOSPACE_FONT: Font = Font::with_name("monospace");
+
+/// Padding around the text editor
+const EDITOR_PADDING: f32 = 16.0;
+/// Default text size the editor renders with
+const EDITOR_TEXT_SIZE: f32 = 16.0;
+/// Generous average glyph width of the UI font, as a fraction of its size
+const EDITOR_CHAR_WIDTH: f32 = 0.62;
+
+/// Height of the controls and metadata above an image
+const IMAGE_HEADER_HEIGHT: f32 = 76.0;
+/// Size SVGs are drawn at for 100% zoom
+const SVG_BASE_SIZE: f32 = 600.0;
+/// Zoom factor per Ctrl+wheel notch
+const WHEEL_ZOOM_STEP: f32 = 1.1;
+/// Trackpad scroll distance counted as one wheel notch
+const WHEEL_PIXELS_PER_STEP: f32 = 40.0;
+
+/// Widget id of the text editor (for focus handling)
+pub fn file_viewer_editor_id() -> iced::widget::Id {
+    iced::widget::Id::new("file_viewer_editor")
+}
+
+/// Widget id of the image scroll area (for zooming toward the cursor)
+pub fn image_scroll_id() -> iced::widget::Id {
+    iced::widget::Id::new("file_viewer_image_scroll")
+}
+
+/// Widget id of the go-to-line input (for focus handling)
+pub fn goto_line_input_id() -> iced::widget::Id {
+    iced::widget::Id::new("file_viewer_goto_line")
+}
+
 /// Main file viewer view
 pub fn file_viewer_view(
     state: &FileViewerState,
     theme: Theme,
     fonts: ScaledFonts,
+    word_wrap: bool,
 ) -> Element<'_, Message> {
-    let toolbar = file_viewer_toolbar(state, theme, fonts);
+    let toolbar = file_viewer_toolbar(state, theme, fonts, word_wrap);
 
     let content: Element<'_, Message> = match &state.content {
         ViewerContent::Loading => container(
@@ -53,36 +102,56 @@ pub fn file_viewer_view(
         .align_x(Alignment::Center)
         .align_y(Alignment::Center)
         .into(),
-        ViewerContent::Text { content } => text_editor_view(state.viewer_id, content, theme),
+        ViewerContent::Text { content } => {
+            let language = match &state.file_type {
+                FileType::Text { language } => language.as_deref(),
+                _ => None,
+            };
+            text_editor_view(state.viewer_id, content, language, word_wrap, theme)
+        }
         ViewerContent::Markdown {
             content,
-            raw_text,
             preview_mode,
+            preview,
+            ..
         } => {
             if *preview_mode {
-                markdown_preview_view(raw_text, theme, fonts)
+                markdown_preview_view(preview, theme, fonts)
             } else {
-                text_editor_view(state.viewer_id, content, theme)
+                text_editor_view(state.viewer_id, content, Some("md"), word_wrap, theme)
             }
         }
         ViewerContent::Image {
             data,
             zoom,
+            fit_to_window,
+            scroll,
             width,
             height,
-            is_svg,
+            raster,
+            ..
         } => image_viewer_view(
             ImageViewerParams {
                 data,
                 zoom: *zoom,
+                fit_to_window: *fit_to_window,
+                scroll: *scroll,
                 width: *width,
                 height: *height,
-                is_svg: *is_svg,
+                raster: raster.as_ref(),
                 viewer_id: state.viewer_id,
             },
             theme,
             fonts,
         ),
+        ViewerContent::Diff {
+            left_label,
+            right_label,
+            rows,
+        } => diff_viewer_view(left_label, right_label, rows, theme, fonts),
+        ViewerContent::Hex { bytes, offset } => {
+            hex_viewer_view(bytes, *offset, state.viewer_id, theme, fonts)
+        }
         ViewerContent::Pdf {
             pages,
             rendering_pages,
@@ -93,13 +162,27 @@ pub fn file_viewer_view(
             rendering_pages,
             *current_page,
             *total_pages,
+            &state.pdf_search,
             state.viewer_id,
             theme,
             fonts,
         ),
     };
 
-    let main_content = column![toolbar, content].spacing(0);
+    let mut main_content = column![toolbar].spacing(0);
+    if state.sudo_password.is_some() {
+        main_content = main_content.push(sudo_save_bar(state, theme, fonts));
+    }
+    if state.goto_line.open && state.editor_content().is_some() {
+        main_content = main_content.push(goto_line_bar(state, theme, fonts));
+    }
+    main_content = main_content.push(content);
+    if let Some((line, column)) = state.cursor_line_column() {
+        let line_count = state
+            .editor_content()
+            .map_or(0, |content| content.line_count());
+        main_content = main_content.push(text_status_bar(line, column, line_count, theme, fonts));
+    }
 
     container(main_content)
         .width(Fill)
@@ -116,6 +199,7 @@ fn file_viewer_toolbar(
     state: &FileViewerState,
     theme: Theme,
     fonts: ScaledFonts,
+    word_wrap: bool,
 ) -> Element<'_, Message> {
     let viewer_id = state.viewer_id;
 
@@ -174,15 +258,87 @@ fn file_viewer_toolbar(
     };
 
     // Preview toggle for markdown
-    let preview_toggle: Element<'_, Message> = if matches!(state.file_type, FileType::Markdown) {
-        let is_preview = matches!(
-            &state.content,
-            ViewerContent::Markdown {
-                preview_mode: true,
-                ..
+    let preview_toggle: Element<'_, Message> =
+        if matches!(state.file_type, FileType::Markdown) && !state.hex_mode {
+            let is_preview = matches!(
+                &state.content,
+                ViewerContent::Markdown {
+                    preview_mode: true,
+                    ..
+                }
+            );
+            let label = if is_preview { "Edit" } else { "Preview" };
+
+            button(
+                text(label)
+                    .size(fonts.button_small)
+                    .color(theme.text_primary),
+            )
+            .style(move |_theme, status| {
+                let bg = match status {
+                    button::Status::Hovered => theme.hover,
+                    _ => theme.surface,
+                };
+                button::Style {
+                    background: Some(bg.into()),
+                    text_color: theme.text_primary,
+                    border: iced::Border {
+                        radius: 4.0.into(),
+                        color: theme.border,
+                        width: 1.0,
+                    },
+                    ..Default::default()
+                }
+            })
+            .padding([6, 12])
+            .on_press(Message::FileViewer(
+                FileViewerMessage::MarkdownTogglePreview(viewer_id),
+            ))
+            .into()
+        } else {
+            Space::new().width(0).into()
+        };
+
+    // Word-wrap toggle while text is being edited
+    let wrap_toggle: Element<'_, Message> = if state.editor_content().is_some() {
+        let label = if word_wrap { "No Wrap" } else { "Wrap" };
+
+        button(
+            text(label)
+                .size(fonts.button_small)
+                .color(theme.text_primary),
+        )
+        .style(move |_theme, status| {
+            let bg = match status {
+                button::Status::Hovered => theme.hover,
+                _ => theme.surface,
+            };
+            button::Style {
+                background: Some(bg.into()),
+                text_color: theme.text_primary,
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    color: theme.border,
+                    width: 1.0,
+                },
+                ..Default::default()
             }
-        );
-        let label = if is_preview { "Edit" } else { "Preview" };
+        })
+        .padding([6, 12])
+        .on_press(Message::FileViewer(FileViewerMessage::ToggleWordWrap))
+        .into()
+    } else {
+        Space::new().width(0).into()
+    };
+
+    // Hex toggle for files that also have a typed view
+    let show_hex_toggle = state.file_type != FileType::Binary && !state.is_comparison;
+    let hex_toggle: Element<'_, Message> = if show_hex_toggle {
+        let label = if state.hex_mode {
+            "View Original"
+        } else {
+            "View as Hex"
+        };
 
         button(
             text(label)
@@ -206,9 +362,11 @@ fn file_viewer_toolbar(
             }
         })
         .padding([6, 12])
-        .on_press(Message::FileViewer(
-            FileViewerMessage::MarkdownTogglePreview(viewer_id),
-        ))
+        .on_press_maybe(
+            state
+                .can_toggle_hex()
+                .then_some(Message::FileViewer(FileViewerMessage::ToggleHex(viewer_id))),
+        )
         .into()
     } else {
         Space::new().width(0).into()
@@ -217,6 +375,10 @@ fn file_viewer_toolbar(
     let toolbar_content = row![
         file_name,
         Space::new().width(Length::Fill),
+        wrap_toggle,
+        Space::new().width(8),
+        hex_toggle,
+        Space::new().width(8),
         preview_toggle,
         Space::new().width(8),
         save_btn,
@@ -238,18 +400,130 @@ fn file_viewer_toolbar(
         .into()
 }
 
-/// Text editor view using iced's text_editor widget
-fn text_editor_view(
+/// Side-by-side diff: line numbers and text for each file, with removed
+/// lines tinted red and added lines green from the terminal palette
+fn diff_viewer_view<'a>(
+    left_label: &'a str,
+    right_label: &'a str,
+    rows: &'a [DiffRow],
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let removed = theme.terminal.ansi[1];
+    let added = theme.terminal.ansi[2];
+    let (removed_count, added_count) = rows.iter().fold((0, 0), |(removed, added), row| match row
+        .kind
+    {
+        DiffKind::Same => (removed, added),
+        DiffKind::Removed => (removed + 1, added),
+        DiffKind::Added => (removed, added + 1),
+        DiffKind::Changed => (removed + 1, added + 1),
+    });
+
+    let summary: Element<'a, Message> = if removed_count == 0 && added_count == 0 {
+        text("The files are identical")
+            .size(fonts.label)
+            .color(theme.text_secondary)
+            .into()
+    } else {
+        row![
+            text(format!("\u{2212}{} lines", removed_count))
+                .size(fonts.label)
+                .color(removed),
+            text(format!("+{} lines", added_count))
+                .size(fonts.label)
+                .color(added),
+        ]
+        .spacing(12)
+        .into()
+    };
+
+    let header_label = |label: &'a str| {
+        text(label)
+            .size(fonts.label)
+            .color(theme.text_primary)
+            .font(MONOSPACE_FONT)
+            .width(Fill)
+    };
+    let header = row![header_label(left_label), header_label(right_label)]
+        .spacing(16)
+        .padding([8, 16]);
+
+    let side = move |line: Option<&'a DiffLine>, tint: Option<Color>| {
+        let content: Element<'a, Message> = match line {
+            Some(line) => row![
+                text(line.number.to_string())
+                    .size(fonts.label)
+                    .color(theme.text_muted)
+                    .font(MONOSPACE_FONT)
+                    .width(Length::Fixed(48.0)),
+                text(line.text.as_str())
+                    .size(fonts.label)
+                    .color(theme.text_primary)
+                    .font(MONOSPACE_FONT)
+                    .wrapping(text::Wrapping::None),
+            ]
+            .spacing(8)
+            .into(),
+            None => Space::new().into(),
+        };
+        container(content)
+            .width(Fill)
+            .padding([1, 8])
+            .clip(true)
+            .style(move |_| container::Style {
+                background: tint.map(|color| Color { a: 0.18, ..color }.into()),
+                ..Default::default()
+            })
+    };
+
+    let lines = iced::widget::Column::with_children(rows.iter().map(|diff_row| {
+        let (left_tint, right_tint) = match diff_row.kind {
+            DiffKind::Same => (None, None),
+            DiffKind::Removed => (Some(removed), None),
+            DiffKind::Added => (None, Some(added)),
+            DiffKind::Changed => (Some(removed), Some(added)),
+        };
+        row![
+            side(diff_row.left.as_ref(), left_tint),
+            side(diff_row.right.as_ref(), right_tint),
+        ]
+        .spacing(16)
+        .into()
+    }))
+    .padding([0, 8]);
+
+    column![
+        container(summary).padding([8, 16]),
+        header,
+        scrollable(lines).height(Fill),
+    ]
+    .into()
+}
+
+/// Text editor view using iced's text_editor widget, syntax highlighted
+/// for known languages unless the buffer is very large. Long lines wrap at
+/// the view width, or scroll sideways with word wrap off.
+fn text_editor_view<'a>(
     viewer_id: SessionId,
-    content: &text_editor::Content,
+    content: &'a text_editor::Content,
+    language: Option<&str>,
+    word_wrap: bool,
     theme: Theme,
-) -> Element<'_, Message> {
+) -> Element<'a, Message> {
     let editor = text_editor(content)
+        .id(file_viewer_editor_id())
         .on_action(move |action| {
             Message::FileViewer(FileViewerMessage::TextChanged(viewer_id, action))
         })
+        .key_binding(editor_key_binding)
+        .wrapping(if word_wrap {
+            text::Wrapping::Word
+        } else {
+            text::Wrapping::None
+        })
         .height(Fill)
-        .padding(16)
+        .padding(EDITOR_PADDING)
         .style(move |_theme, _status| text_editor::Style {
             background: theme.background.into(),
             border: iced::Border::default(),
@@ -258,101 +532,604 @@ fn text_editor_view(
             selection: theme.selected,
         });
 
-    container(editor).width(Fill).height(Fill).into()
+    let editor: Element<'a, Message> =
+        match language.filter(|_| content.line_count() <= highlight::MAX_HIGHLIGHT_LINES) {
+            Some(token) => editor
+                .highlight_with::<iced::highlighter::Highlighter>(
+                    iced::highlighter::Settings {
+                        theme: highlight::highlighter_theme(theme),
+                        token: token.to_string(),
+                    },
+                    highlight::theme_format,
+                )
+                .into(),
+            None => editor.into(),
+        };
+
+    if word_wrap {
+        return container(editor).width(Fill).height(Fill).into();
+    }
+
+    // The editor cannot scroll sideways itself, so give it room for the
+    // longest line and scroll that horizontally
+    let columns = longest_line_columns(&content.text());
+    let width = columns as f32 * EDITOR_TEXT_SIZE * EDITOR_CHAR_WIDTH + EDITOR_PADDING * 3.0;
+    scrollable(container(editor).width(Length::Fixed(width)).height(Fill))
+        .direction(scrollable::Direction::Horizontal(
+            scrollable::Scrollbar::new(),
+        ))
+        .width(Fill)
+        .height(Fill)
+        .into()
 }
 
-/// Markdown preview view (simple text display for now)
-fn markdown_preview_view(raw_text: &str, theme: Theme, fonts: ScaledFonts) -> Element<'_, Message> {
-    // Simple text preview for markdown - could be enhanced with proper markdown rendering
-    let preview_text = text(raw_text.to_string())
+/// Editor keys, plus Alt+Z to toggle word wrap while the editor has focus
+fn editor_key_binding(key_press: text_editor::KeyPress) -> Option<text_editor::Binding<Message>> {
+    let focused = matches!(key_press.status, text_editor::Status::Focused { .. });
+    let modifiers = key_press.modifiers;
+    if focused
+        && modifiers.alt()
+        && !modifiers.command()
+        && !modifiers.shift()
+        && key_press.key.to_latin(key_press.physical_key) == Some('z')
+    {
+        return Some(text_editor::Binding::Custom(Message::FileViewer(
+            FileViewerMessage::ToggleWordWrap,
+        )));
+    }
+    text_editor::Binding::from_key_press(key_press)
+}
+
+/// Line number input for jumping through a text file
+fn goto_line_bar<'a>(
+    state: &FileViewerState,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let viewer_id = state.viewer_id;
+    let input = text_input("Line number", &state.goto_line.input)
+        .id(goto_line_input_id())
+        .on_input(move |value| {
+            Message::FileViewer(FileViewerMessage::GotoLineChanged(viewer_id, value))
+        })
+        .on_submit(Message::FileViewer(FileViewerMessage::GotoLineSubmit(
+            viewer_id,
+        )))
         .size(fonts.body)
-        .color(theme.text_primary);
+        .padding([6, 10])
+        .width(Length::Fixed(160.0));
+
+    let hint: Element<'a, Message> = match &state.goto_line.error {
+        Some(error) => text(error.clone())
+            .size(fonts.small)
+            .color(ERROR_COLOR)
+            .into(),
+        None => text("Enter to jump, Esc to close")
+            .size(fonts.small)
+            .color(theme.text_secondary)
+            .into(),
+    };
+
+    row![
+        text("Go to line")
+            .size(fonts.label)
+            .color(theme.text_secondary),
+        Space::new().width(8),
+        input,
+        Space::new().width(8),
+        button(text("Go").size(fonts.button_small))
+            .padding([4, 12])
+            .on_press(Message::FileViewer(FileViewerMessage::GotoLineSubmit(
+                viewer_id
+            ))),
+        Space::new().width(12),
+        hint,
+    ]
+    .align_y(Alignment::Center)
+    .padding([6, 16])
+    .into()
+}
+
+/// Sudo password field offered after the server refused a remote save
+fn sudo_save_bar<'a>(
+    state: &FileViewerState,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let viewer_id = state.viewer_id;
+    let password = state
+        .sudo_password
+        .as_ref()
+        .map(|password| password.expose_secret().to_string())
+        .unwrap_or_default();
+    let input = text_input("Sudo password", &password)
+        .secure(true)
+        .on_input(move |value| {
+            Message::FileViewer(FileViewerMessage::SudoPasswordChanged(
+                viewer_id,
+                SecretString::from(value),
+            ))
+        })
+        .on_submit(Message::FileViewer(FileViewerMessage::Save(viewer_id)))
+        .size(fonts.body)
+        .padding([6, 10])
+        .width(Length::Fixed(220.0));
+
+    let mut save = button(text("Save as root").size(fonts.button_small)).padding([4, 12]);
+    if !state.is_saving {
+        save = save.on_press(Message::FileViewer(FileViewerMessage::Save(viewer_id)));
+    }
+
+    row![
+        text("Permission denied. Retry with sudo:")
+            .size(fonts.label)
+            .color(theme.text_secondary),
+        Space::new().width(8),
+        input,
+        Space::new().width(8),
+        save,
+        Space::new().width(8),
+        button(text("Cancel").size(fonts.button_small))
+            .padding([4, 12])
+            .on_press(Message::FileViewer(FileViewerMessage::SudoCancel(
+                viewer_id
+            ))),
+    ]
+    .align_y(Alignment::Center)
+    .padding([6, 16])
+    .into()
+}
+
+/// Cursor position and length of the text being edited
+fn text_status_bar<'a>(
+    line: usize,
+    column: usize,
+    line_count: usize,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let lines = if line_count == 1 {
+        "1 line".to_string()
+    } else {
+        format!("{} lines", line_count)
+    };
+    let status = row![
+        text(format!("Ln {}, Col {}", line, column))
+            .size(fonts.small)
+            .color(theme.text_secondary),
+        Space::new().width(16),
+        text(lines).size(fonts.small).color(theme.text_muted),
+        Space::new().width(Length::Fill),
+        text("Ctrl+G Go to line")
+            .size(fonts.small)
+            .color(theme.text_muted),
+    ]
+    .align_y(Alignment::Center)
+    .padding([4, 16]);
+
+    container(status)
+        .width(Fill)
+        .style(move |_| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 0.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+}
 
-    let content = scrollable(container(preview_text).padding(16))
+/// Rendered markdown preview; links open in the browser
+fn markdown_preview_view(
+    items: &[markdown::Item],
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'_, Message> {
+    let style = markdown::Style {
+        font: Font::default(),
+        inline_code_highlight: markdown::Highlight {
+            background: theme.surface.into(),
+            border: iced::border::rounded(4),
+        },
+        inline_code_padding: iced::padding::left(2).right(2),
+        inline_code_color: theme.text_primary,
+        inline_code_font: MONOSPACE_FONT,
+        code_block_font: MONOSPACE_FONT,
+        link_color: theme.accent,
+    };
+
+    let preview = markdown::view(items, markdown::Settings::with_text_size(fonts.body, style))
+        .map(|url| Message::FileViewer(FileViewerMessage::OpenLink(url)));
+
+    let content = scrollable(container(preview).padding(16).max_width(900))
         .width(Fill)
         .height(Fill);
 
     container(content).width(Fill).height(Fill).into()
 }
 
-/// Image viewer with zoom controls
+/// Image viewer with zoom, fit and rotate controls and a metadata line
+#[derive(Clone, Copy)]
 struct ImageViewerParams<'a> {
     data: &'a [u8],
     zoom: f32,
+    fit_to_window: bool,
+    scroll: scrollable::AbsoluteOffset,
     width: u32,
     height: u32,
-    is_svg: bool,
+    /// `None` for SVG
+    raster: Option<&'a RasterImage>,
     viewer_id: SessionId,
 }
 
+impl ImageViewerParams<'_> {
+    /// Size of the image at 100%
+    fn natural_size(&self) -> Size {
+        match self.raster {
+            Some(_) => Size::new(self.width as f32, self.height as f32),
+            None => Size::new(SVG_BASE_SIZE, SVG_BASE_SIZE),
+        }
+    }
+}
+
 fn image_viewer_view(
     params: ImageViewerParams<'_>,
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'_, Message> {
+    // Built at layout time so fit-to-window follows the available space
+    responsive(move |size| {
+        let viewport = Size::new(size.width, (size.height - IMAGE_HEADER_HEIGHT).max(0.0));
+        let natural = params.natural_size();
+        let zoom = if params.fit_to_window {
+            fit_zoom(natural, viewport)
+        } else {
+            params.zoom
+        };
+        let header = image_viewer_header(params, zoom, theme, fonts);
+
+        let scaled = Size::new(
+            (natural.width * zoom).max(1.0),
+            (natural.height * zoom).max(1.0),
+        );
+        let image_element: Element<'_, Message> = match params.raster {
+            Some(raster) => Image::new(raster.handle.clone())
+                .width(Length::Fixed(scaled.width))
+                .height(Length::Fixed(scaled.height))
+                .into(),
+            None => Svg::new(iced::widget::svg::Handle::from_memory(params.data.to_vec()))
+                .width(Length::Fixed(scaled.width.max(80.0)))
+                .height(Length::Fixed(scaled.height.max(80.0)))
+                .into(),
+        };
+
+        // Centred while smaller than the view, scrollable once larger
+        let viewer_id = params.viewer_id;
+        let content = scrollable(
+            container(image_element)
+                .padding(IMAGE_PADDING)
+                .width(Length::Fixed(
+                    (scaled.width + 2.0 * IMAGE_PADDING).max(viewport.width),
+                ))
+                .height(Length::Fixed(
+                    (scaled.height + 2.0 * IMAGE_PADDING).max(viewport.height),
+                ))
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center),
+        )
+        .id(image_scroll_id())
+        .direction(scrollable::Direction::Both {
+            vertical: scrollable::Scrollbar::new(),
+            horizontal: scrollable::Scrollbar::new(),
+        })
+        .on_scroll(move |scrolled| {
+            Message::FileViewer(FileViewerMessage::ImageScrolled(
+                viewer_id,
+                scrolled.absolute_offset(),
+            ))
+        })
+        .width(Fill)
+        .height(Fill);
+
+        // Ctrl+wheel zooms toward the cursor
+        let content = mouse_area(content)
+            .width(Fill)
+            .height(Fill)
+            .on_command_scroll(move |delta, cursor| {
+                let steps = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => y,
+                    mouse::ScrollDelta::Pixels { y, .. } => y / WHEEL_PIXELS_PER_STEP,
+                };
+                let to = (zoom * WHEEL_ZOOM_STEP.powf(steps)).clamp(MIN_IMAGE_ZOOM, MAX_IMAGE_ZOOM);
+                let offset = zoom_anchor_offset(natural, viewport, params.scroll, cursor, zoom, to);
+                Message::FileViewer(FileViewerMessage::ImageZoomAt(viewer_id, to, offset))
+            });
+
+        column![header, content].into()
+    })
+    .into()
+}
+
+/// Title, controls and metadata line above the image, `IMAGE_HEADER_HEIGHT`
+/// tall so the space left for the image is known
+fn image_viewer_header<'a>(
+    params: ImageViewerParams<'a>,
+    zoom: f32,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let viewer_id = params.viewer_id;
+
+    let rotate_controls: Element<'a, Message> = if params.raster.is_some() {
+        row![
+            button(text("Rotate Left").size(fonts.button_small))
+                .padding([6, 12])
+                .on_press(Message::FileViewer(FileViewerMessage::ImageRotateLeft(
+                    viewer_id
+                ))),
+            Space::new().width(8),
+            button(text("Rotate Right").size(fonts.button_small))
+                .padding([6, 12])
+                .on_press(Message::FileViewer(FileViewerMessage::ImageRotateRight(
+                    viewer_id
+                ))),
+            Space::new().width(16),
+        ]
+        .align_y(Alignment::Center)
+        .into()
+    } else {
+        Space::new().width(0).into()
+    };
+
+    let fit_controls =
+        row![
+            button(text("Fit to Window").size(fonts.button_small))
+                .padding([6, 12])
+                .on_press_maybe((!params.fit_to_window).then_some(Message::FileViewer(
+                    FileViewerMessage::ImageFitToWindow(viewer_id)
+                ))),
+            Space::new().width(8),
+            button(text("Actual Size").size(fonts.button_small))
+                .padding([6, 12])
+                .on_press_maybe((params.fit_to_window || zoom != 1.0).then_some(
+                    Message::FileViewer(FileViewerMessage::ImageZoom(viewer_id, 1.0))
+                )),
+            Space::new().width(16),
+        ]
+        .align_y(Alignment::Center);
+
     let zoom_controls = row![
         button(text("-").size(fonts.section))
             .padding([4, 12])
             .on_press(Message::FileViewer(FileViewerMessage::ImageZoom(
-                params.viewer_id,
-                params.zoom - 0.1
+                viewer_id,
+                zoom - 0.1
             ))),
         Space::new().width(8),
         button(text("+").size(fonts.section))
             .padding([4, 12])
             .on_press(Message::FileViewer(FileViewerMessage::ImageZoom(
-                params.viewer_id,
-                params.zoom + 0.1
+                viewer_id,
+                zoom + 0.1
             ))),
         Space::new().width(12),
-        text(format!("Zoom: {:.0}%", params.zoom * 100.0))
+        text(format!("Zoom: {:.0}%", zoom * 100.0))
             .size(fonts.small)
             .color(theme.text_secondary),
     ]
     .align_y(Alignment::Center);
 
-    let image_element: Element<'_, Message> = if params.is_svg {
-        let base = 600.0;
-        let size = Length::Fixed((base * params.zoom).max(80.0));
-        Svg::new(iced::widget::svg::Handle::from_memory(params.data.to_vec()))
-            .width(size)
-            .height(size)
-            .into()
-    } else {
-        let scaled_width = (params.width as f32 * params.zoom).max(1.0);
-        let scaled_height = (params.height as f32 * params.zoom).max(1.0);
-        Image::new(iced::widget::image::Handle::from_bytes(
-            params.data.to_vec(),
-        ))
-        .width(Length::Fixed(scaled_width))
-        .height(Length::Fixed(scaled_height))
-        .scale(params.zoom)
-        .into()
+    let details = text(image_details(&params))
+        .size(fonts.small)
+        .color(theme.text_secondary);
+
+    container(column![
+        row![
+            text("Image Viewer")
+                .size(fonts.section)
+                .color(theme.text_primary),
+            Space::new().width(Length::Fill),
+            rotate_controls,
+            fit_controls,
+            zoom_controls,
+        ]
+        .align_y(Alignment::Center)
+        .padding([8, 16]),
+        container(details).padding([0, 16]),
+    ])
+    .height(Length::Fixed(IMAGE_HEADER_HEIGHT))
+    .clip(true)
+    .into()
+}
+
+/// One line of image facts: format, size and, for photos, camera, date
+/// and the EXIF orientation that was corrected
+fn image_details(params: &ImageViewerParams<'_>) -> String {
+    let Some(raster) = params.raster else {
+        return "SVG \u{00B7} vector image".to_string();
     };
+    let metadata = &raster.metadata;
 
-    let content = scrollable(
-        container(image_element)
-            .padding(16)
-            .align_x(Alignment::Center)
-            .align_y(Alignment::Center),
-    )
-    .width(Fill)
-    .height(Fill);
+    let mut details = vec![
+        metadata.format.clone(),
+        format!("{} \u{00D7} {} px", params.width, params.height),
+    ];
+    if let Some(camera) = &metadata.camera {
+        details.push(camera.clone());
+    }
+    if let Some(taken) = &metadata.taken {
+        details.push(format!("Taken {}", taken));
+    }
+    if let Some(orientation) = metadata.orientation_label() {
+        details.push(format!("EXIF orientation: {} (corrected)", orientation));
+    }
+    details.join(" \u{00B7} ")
+}
+
+/// Hex dump of one page of the file, with page navigation
+fn hex_viewer_view(
+    bytes: &[u8],
+    offset: usize,
+    viewer_id: SessionId,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'_, Message> {
+    let total_pages = bytes.len().div_ceil(hex::HEX_PAGE_BYTES).max(1);
+    let current_page = offset / hex::HEX_PAGE_BYTES;
+
+    let page_controls = row![
+        button(text("<").size(fonts.section))
+            .padding([4, 12])
+            .on_press_maybe((current_page > 0).then_some(Message::FileViewer(
+                FileViewerMessage::HexOffsetChange(viewer_id, offset - hex::HEX_PAGE_BYTES)
+            ))),
+        Space::new().width(16),
+        button(text(">").size(fonts.section))
+            .padding([4, 12])
+            .on_press_maybe(
+                (current_page + 1 < total_pages).then_some(Message::FileViewer(
+                    FileViewerMessage::HexOffsetChange(viewer_id, offset + hex::HEX_PAGE_BYTES)
+                ))
+            ),
+        Space::new().width(12),
+        text(format!(
+            "Page {} of {} \u{00B7} {} bytes",
+            current_page + 1,
+            total_pages,
+            bytes.len()
+        ))
+        .size(fonts.small)
+        .color(theme.text_secondary),
+    ]
+    .align_y(Alignment::Center);
+
+    let dump = text(hex::hex_dump_page(bytes, offset))
+        .size(fonts.body)
+        .font(MONOSPACE_FONT)
+        .color(theme.text_primary)
+        .wrapping(text::Wrapping::None);
+
+    let body = scrollable(container(dump).padding(16))
+        .direction(scrollable::Direction::Both {
+            vertical: scrollable::Scrollbar::default(),
+            horizontal: scrollable::Scrollbar::default(),
+        })
+        .width(Fill)
+        .height(Fill);
 
     let layout = column![
         row![
-            text("Image Viewer")
+            text("Hex Viewer")
                 .size(fonts.section)
                 .color(theme.text_primary),
             Space::new().width(Length::Fill),
-            zoom_controls,
+            search_toggle,
+            Space::new().width(16),
+            page_controls,
         ]
         .align_y(Alignment::Center)
         .padding([8, 16]),
-        content,
     ];
+    let layout = if search.open {
+        layout.push(pdf_search_bar(search, viewer_id, theme, fonts))
+    } else {
+        layout
+    };
+
+    container(layout.push(body)).width(Fill).height(Fill).into()
+}
+
+/// Query input, match count and match navigation for a PDF
+fn pdf_search_bar<'a>(
+    search: &PdfSearchState,
+    viewer_id: SessionId,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let input = text_input("Search PDF...", &search.query)
+        .on_input(move |query| {
+            Message::FileViewer(FileViewerMessage::PdfSearchChanged(viewer_id, query))
+        })
+        .on_submit(Message::FileViewer(FileViewerMessage::PdfSearchNext(
+            viewer_id,
+        )))
+        .size(fonts.body)
+        .padding([6, 10])
+        .width(Length::Fixed(280.0));
+
+    let status = if let Some(error) = &search.error {
+        error.clone()
+    } else if search.pages.is_none() {
+        "Reading text...".to_string()
+    } else if search.has_no_text() {
+        "No searchable text".to_string()
+    } else if search.query.trim().is_empty() {
+        String::new()
+    } else if let Some(current) = search.current {
+        format!("{} of {}", current + 1, search.matches.len())
+    } else {
+        "No matches".to_string()
+    };
+
+    let has_matches = !search.matches.is_empty();
+    row![
+        input,
+        Space::new().width(8),
+        button(text("Previous").size(fonts.button_small))
+            .padding([4, 12])
+            .on_press_maybe(has_matches.then_some(Message::FileViewer(
+                FileViewerMessage::PdfSearchPrevious(viewer_id)
+            ))),
+        Space::new().width(4),
+        button(text("Next").size(fonts.button_small))
+            .padding([4, 12])
+            .on_press_maybe(has_matches.then_some(Message::FileViewer(
+                FileViewerMessage::PdfSearchNext(viewer_id)
+            ))),
+        Space::new().width(12),
+        text(status).size(fonts.small).color(theme.text_secondary),
+    ]
+    .align_y(Alignment::Center)
+    .padding([0, 16])
+    .into()
+}
+
+/// A match highlight placed over the page by splitting the page size into
+/// portions around `rect`
+fn pdf_highlight<'a>(rect: PdfTextRect, selected: bool, theme: Theme) -> Element<'a, Message> {
+    const SCALE: f32 = 10_000.0;
+    let portion =
+        |fraction: f32| Length::FillPortion((fraction.clamp(0.0, 1.0) * SCALE).max(1.0) as u16);
+
+    // Other matches use the terminal palette's yellow, like a highlighter pen.
+    let color = if selected {
+        theme.accent
+    } else {
+        theme.terminal.ansi[11]
+    };
+    let marker = container(Space::new())
+        .width(portion(rect.width))
+        .height(Fill)
+        .style(move |_| container::Style {
+            background: Some(Color { a: 0.35, ..color }.into()),
+            ..Default::default()
+        });
 
-    container(layout).width(Fill).height(Fill).into()
+    column![
+        Space::new().height(portion(rect.y)),
+        row![
+            Space::new().width(portion(rect.x)),
+            marker,
+            Space::new().width(portion(1.0 - rect.x - rect.width)),
+        ]
+        .height(portion(rect.height)),
+        Space::new().height(portion(1.0 - rect.y - rect.height)),
+    ]
+    .width(Fill)
+    .height(Fill)
+    .into()
 }
 
 /// PDF viewer with page navigation
@@ -361,10 +1138,17 @@ fn pdf_viewer_view<'a>(
     rendering_pages: &'a [bool],
     current_page: usize,
     total_pages: usize,
+    search: &PdfSearchState,
     viewer_id: SessionId,
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'a, Message> {
+    let search_toggle = button(text("Search").size(fonts.button_small))
+        .padding([4, 12])
+        .on_press(Message::FileViewer(FileViewerMessage::PdfSearchToggle(
+            viewer_id,
+        )));
+
     let page_controls = row![
         button(text("<").size(fonts.section))
             .padding([4, 12])
@@ -394,7 +1178,18 @@ fn pdf_viewer_view<'a>(
             .width(Fill)
             .height(Length::Shrink)
             .expand(true);
-        scrollable(container(page_image).padding(16))
+        // Highlights are laid over the image, which sizes the stack.
+        let highlights = if search.open {
+            search.highlights(current_page)
+        } else {
+            Vec::new()
+        };
+        let page = highlights
+            .into_iter()
+            .fold(Stack::new().push(page_image), |page, (rect, selected)| {
+                page.push(pdf_highlight(rect, selected, theme))
+            });
+        scrollable(container(page).padding(16))
             .width(Fill)
             .height(Fill)
             .into()
@@ -439,12 +1234,18 @@ fn pdf_viewer_view<'a>(
                 .size(fonts.section)
                 .color(theme.text_primary),
             Space::new().width(Length::Fill),
+            search_toggle,
+            Space::new().width(16),
             page_controls,
         ]
         .align_y(Alignment::Center)
         .padding([8, 16]),
-        body,
     ];
+    let layout = if search.open {
+        layout.push(pdf_search_bar(search, viewer_id, theme, fonts))
+    } else {
+        layout
+    };
 
-    container(layout).width(Fill).height(Fill).into()
+    container(layout.push(body)).width(Fill).height(Fill).into()
 }
@@ -0,0 +1,234 @@
+//! Text search in the PDF viewer
+//!
+//! The text layer of every page is read once, when search is first opened.
+//! Matches are found in that copy and carry their highlight rectangles in
+//! page-relative coordinates so they can be drawn over the rendered page at
+//! any size.
+
+/// A rectangle on a page, as fractions of the page size with the origin at
+/// the top left
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfTextRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PdfTextRect {
+    fn same_line(&self, other: &Self) -> bool {
+        let center = other.y + other.height / 2.0;
+        center >= self.y && center <= self.y + self.height
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+        Self {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        }
+    }
+}
+
+/// Text layer of one page: each character with its bounds, if it has any
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdfPageText {
+    pub chars: Vec<(char, Option<PdfTextRect>)>,
+}
+
+impl PdfPageText {
+    fn has_text(&self) -> bool {
+        self.chars.iter().any(|(c, _)| !c.is_whitespace())
+    }
+}
+
+/// One occurrence of the query
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfMatch {
+    pub page: usize,
+    /// Highlight rectangles, one per line the match spans
+    pub rects: Vec<PdfTextRect>,
+}
+
+/// Search bar state of a PDF viewer
+#[derive(Debug, Clone, Default)]
+pub struct PdfSearchState {
+    pub open: bool,
+    pub query: String,
+    /// Text layer per page; `None` until loaded
+    pub pages: Option<Vec<PdfPageText>>,
+    /// Error reading the text layer
+    pub error: Option<String>,
+    pub matches: Vec<PdfMatch>,
+    /// Index into `matches` of the match jumped to
+    pub current: Option<usize>,
+}
+
+impl PdfSearchState {
+    /// Whether the loaded text layer is empty, i.e. the PDF is scanned images
+    pub fn has_no_text(&self) -> bool {
+        self.pages
+            .as_ref()
+            .is_some_and(|pages| !pages.iter().any(PdfPageText::has_text))
+    }
+
+    /// Find the query again and select the first match on or after
+    /// `from_page`. Returns the page of the selected match.
+    pub fn refresh(&mut self, from_page: usize) -> Option<usize> {
+        self.matches = match &self.pages {
+            Some(pages) => find_matches(pages, &self.query),
+            None => Vec::new(),
+        };
+        self.current = self
+            .matches
+            .iter()
+            .position(|found| found.page >= from_page)
+            .or((!self.matches.is_empty()).then_some(0));
+        self.current_page()
+    }
+
+    /// Select the next (or previous) match, wrapping around. Returns its page.
+    pub fn step(&mut self, forward: bool) -> Option<usize> {
+        let count = self.matches.len();
+        if count == 0 {
+            return None;
+        }
+        self.current = Some(match self.current {
+            Some(current) if forward => (current + 1) % count,
+            Some(current) => (current + count - 1) % count,
+            None => 0,
+        });
+        self.current_page()
+    }
+
+    pub fn current_page(&self) -> Option<usize> {
+        self.current
+            .and_then(|index| self.matches.get(index))
+            .map(|found| found.page)
+    }
+
+    /// Highlights on `page`, with whether each one is the selected match
+    pub fn highlights(&self, page: usize) -> Vec<(PdfTextRect, bool)> {
+        self.matches
+            .iter()
+            .enumerate()
+            .filter(|(_, found)| found.page == page)
+            .flat_map(|(index, found)| {
+                let selected = self.current == Some(index);
+                found.rects.iter().map(move |rect| (*rect, selected))
+            })
+            .collect()
+    }
+}
+
+/// Case-insensitive, non-overlapping occurrences of `query` on every page
+pub fn find_matches(pages: &[PdfPageText], query: &str) -> Vec<PdfMatch> {
+    let query: Vec<char> = query.trim().chars().map(fold_case).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    for (page_index, page) in pages.iter().enumerate() {
+        let text: Vec<char> = page.chars.iter().map(|(c, _)| fold_case(*c)).collect();
+        let mut start = 0;
+        while start + query.len() <= text.len() {
+            if text[start..start + query.len()] != query[..] {
+                start += 1;
+                continue;
+            }
+            let mut rects: Vec<PdfTextRect> = Vec::new();
+            for rect in page.chars[start..start + query.len()]
+                .iter()
+                .filter_map(|(_, rect)| *rect)
+            {
+                match rects.last_mut() {
+                    Some(last) if last.same_line(&rect) => *last = last.union(&rect),
+                    _ => rects.push(rect),
+                }
+            }
+            matches.push(PdfMatch {
+                page: page_index,
+                rects,
+            });
+            start += query.len();
+        }
+    }
+    matches
+}
+
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A page with one line of text, each character 0.01 wide
+    fn page(text: &str) -> PdfPageText {
+        PdfPageText {
+            chars: text
+                .chars()
+                .enumerate()
+                .map(|(index, c)| {
+                    (
+                        c,
+                        Some(PdfTextRect {
+                            x: index as f32 * 0.01,
+                            y: 0.1,
+                            width: 0.01,
+                            height: 0.02,
+                        }),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn finds_case_insensitive_matches_with_merged_rects() {
+        let pages = vec![page("Portal SSH"), page("no hits"), page("ssh and SSH")];
+        let matches = find_matches(&pages, "ssh");
+
+        assert_eq!(
+            matches.iter().map(|found| found.page).collect::<Vec<_>>(),
+            vec![0, 2, 2]
+        );
+        assert_eq!(matches[0].rects.len(), 1);
+        let rect = matches[0].rects[0];
+        assert!((rect.x - 0.07).abs() < 1e-6);
+        assert!((rect.width - 0.03).abs() < 1e-6);
+    }
+
+    #[test]
+    fn navigation_starts_at_the_current_page_and_wraps() {
+        let mut search = PdfSearchState {
+            pages: Some(vec![page("alpha"), page("beta"), page("alpha beta")]),
+            query: "beta".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(search.refresh(2), Some(2));
+        assert_eq!(search.step(true), Some(1));
+        assert_eq!(search.step(false), Some(2));
+        assert_eq!(
+            search.highlights(2),
+            vec![(search.matches[1].rects[0], true)]
+        );
+    }
+
+    #[test]
+    fn scanned_pdf_has_no_text() {
+        let mut search = PdfSearchState::default();
+        assert!(!search.has_no_text());
+
+        search.pages = Some(vec![PdfPageText::default(), page("  ")]);
+        assert!(search.has_no_text());
+    }
+}
@@ -1,8 +1,14 @@
 //! File viewer state management
 
-use iced::widget::text_editor;
+use iced::widget::scrollable::AbsoluteOffset;
+use iced::widget::{image as image_widget, markdown, text_editor};
+use iced::{Point, Size};
+use secrecy::SecretString;
 
-use super::types::{FileSource, FileType};
+use super::diff::DiffRow;
+use super::hex::page_start;
+use super::pdf_search::PdfSearchState;
+use super::types::{FileSource, FileType, ImageMetadata};
 use crate::message::SessionId;
 
 /// Content held by the viewer based on file type
@@ -17,14 +23,24 @@ pub enum ViewerContent {
         content: text_editor::Content,
         raw_text: String,
         preview_mode: bool,
+        /// Parsed `raw_text`, refreshed when switching to preview
+        preview: Vec<markdown::Item>,
     },
     /// Image data (bytes and dimensions)
     Image {
         data: Vec<u8>,
         zoom: f32,
+        /// Zoom to fit the view, following window resizes; `zoom` is
+        /// ignored while set
+        fit_to_window: bool,
+        /// Scroll position of the image area
+        scroll: AbsoluteOffset,
         width: u32,
         height: u32,
         is_svg: bool,
+        /// Decoded pixels and metadata; `None` for SVG, which is drawn from
+        /// `data` and has no raster metadata
+        raster: Option<RasterImage>,
     },
     /// PDF pages
     Pdf {
@@ -33,10 +49,143 @@ pub enum ViewerContent {
         current_page: usize,
         total_pages: usize,
     },
+    /// Raw bytes shown as a hex dump, one page at a time
+    Hex {
+        bytes: Vec<u8>,
+        /// Start of the page being shown
+        offset: usize,
+    },
+    /// Side-by-side line diff of two files
+    Diff {
+        left_label: String,
+        right_label: String,
+        rows: Vec<DiffRow>,
+    },
     /// Error loading content
     Error(String),
 }
 
+/// Decoded raster image, ready to draw
+#[derive(Debug, Clone)]
+pub struct RasterImage {
+    /// Pixels turned upright by the EXIF orientation, plus any rotation
+    /// the user applied
+    pub pixels: image::RgbaImage,
+    /// Handle drawing `pixels`
+    pub handle: image_widget::Handle,
+    pub metadata: ImageMetadata,
+}
+
+impl RasterImage {
+    pub fn new(pixels: image::RgbaImage, metadata: ImageMetadata) -> Self {
+        let handle = image_widget::Handle::from_rgba(
+            pixels.width(),
+            pixels.height(),
+            pixels.as_raw().clone(),
+        );
+        Self {
+            pixels,
+            handle,
+            metadata,
+        }
+    }
+}
+
+/// Image zoom limits
+pub const MIN_IMAGE_ZOOM: f32 = 0.1;
+pub const MAX_IMAGE_ZOOM: f32 = 5.0;
+
+/// Space around the image inside its scroll area
+pub const IMAGE_PADDING: f32 = 16.0;
+
+/// Zoom at which an image of `size` fits `viewport` with its padding
+pub fn fit_zoom(size: Size, viewport: Size) -> f32 {
+    if size.width <= 0.0 || size.height <= 0.0 {
+        return 1.0;
+    }
+    let available_width = (viewport.width - 2.0 * IMAGE_PADDING).max(1.0);
+    let available_height = (viewport.height - 2.0 * IMAGE_PADDING).max(1.0);
+    (available_width / size.width)
+        .min(available_height / size.height)
+        .clamp(MIN_IMAGE_ZOOM, MAX_IMAGE_ZOOM)
+}
+
+/// Start of an image of `length` along one axis of a `viewport` long view:
+/// centred when it fits, else after the padding
+pub fn image_origin(length: f32, viewport: f32) -> f32 {
+    ((viewport - length) / 2.0).max(IMAGE_PADDING)
+}
+
+/// Scroll offset that keeps the image point under `cursor` in place when
+/// an image of unscaled `size` is zoomed from `from` to `to`
+pub fn zoom_anchor_offset(
+    size: Size,
+    viewport: Size,
+    scroll: AbsoluteOffset,
+    cursor: Point,
+    from: f32,
+    to: f32,
+) -> AbsoluteOffset {
+    let axis = |length: f32, viewport: f32, scroll: f32, cursor: f32| {
+        let image_point =
+            ((scroll + cursor - image_origin(length * from, viewport)) / from).clamp(0.0, length);
+        let scaled = length * to;
+        let max_scroll = (scaled + 2.0 * IMAGE_PADDING - viewport).max(0.0);
+        (image_origin(scaled, viewport) + image_point * to - cursor).clamp(0.0, max_scroll)
+    };
+    AbsoluteOffset {
+        x: axis(size.width, viewport.width, scroll.x, cursor.x),
+        y: axis(size.height, viewport.height, scroll.y, cursor.y),
+    }
+}
+
+/// Lines kept visible above a line jumped to
+const GOTO_LINE_CONTEXT: usize = 3;
+
+/// "Go to line" bar of a text viewer
+#[derive(Debug, Clone, Default)]
+pub struct GotoLineState {
+    pub open: bool,
+    pub input: String,
+    /// Why the input is not a line number
+    pub error: Option<String>,
+}
+
+/// Line to jump to for go-to-line input: 1-based, with numbers past either
+/// end of the document clamped to the first or last line.
+pub fn goto_line_target(input: &str, line_count: usize) -> Result<usize, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter a line number".to_string());
+    }
+    if !input.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("\"{}\" is not a line number", input));
+    }
+    let last = line_count.max(1);
+    // Digits too long for usize are still just "past the end"
+    Ok(input.parse::<usize>().unwrap_or(last).clamp(1, last))
+}
+
+/// Columns a tab advances when measuring unwrapped lines
+const TAB_COLUMNS: usize = 8;
+
+/// Width in columns of the longest line of `text`, counting a tab as a
+/// full tab stop. Sizes the horizontal scroll area when word wrap is off.
+pub fn longest_line_columns(text: &str) -> usize {
+    text.lines()
+        .map(|line| {
+            line.chars().fold(0, |columns, ch| {
+                if ch == '\t' {
+                    (columns / TAB_COLUMNS + 1) * TAB_COLUMNS
+                } else {
+                    columns + 1
+                }
+            })
+        })
+        .max()
+        .unwrap_or(0)
+}
+
 /// State for a single file viewer instance
 #[derive(Debug, Clone)]
 pub struct FileViewerState {
@@ -54,9 +203,19 @@ pub struct FileViewerState {
     pub is_modified: bool,
     /// Whether a save operation is in progress
     pub is_saving: bool,
+    /// Sudo password field, shown after the server refused a remote save
+    pub sudo_password: Option<SecretString>,
+    /// Whether the file is shown as a hex dump instead of by its type
+    pub hex_mode: bool,
     /// 1-based line to scroll to once text content loads (from a Ctrl+clicked
     /// `path:line` terminal link)
     pub pending_goto_line: Option<usize>,
+    /// Ctrl+G go-to-line bar
+    pub goto_line: GotoLineState,
+    /// Text search over the PDF's text layer
+    pub pdf_search: PdfSearchState,
+    /// Whether this viewer compares two files rather than showing one
+    pub is_comparison: bool,
 }
 
 impl FileViewerState {
@@ -67,15 +226,63 @@ impl FileViewerState {
         file_source: FileSource,
         file_type: FileType,
     ) -> Self {
+        let hex_mode = file_type == FileType::Binary;
         Self {
             viewer_id,
             file_name,
             file_source,
             file_type,
+            hex_mode,
             content: ViewerContent::Loading,
             is_modified: false,
             is_saving: false,
+            sudo_password: None,
             pending_goto_line: None,
+            goto_line: GotoLineState::default(),
+            pdf_search: PdfSearchState::default(),
+            is_comparison: false,
+        }
+    }
+
+    /// Create a viewer for comparing two files. Both files are read into
+    /// memory, so `file_source` only names the left file and is never
+    /// loaded from.
+    pub fn new_comparison(viewer_id: SessionId, title: String, file_source: FileSource) -> Self {
+        Self {
+            hex_mode: false,
+            is_comparison: true,
+            ..Self::new(
+                viewer_id,
+                title,
+                file_source,
+                FileType::Text { language: None },
+            )
+        }
+    }
+
+    /// The type the content is loaded as: binary while in hex mode
+    pub fn content_type(&self) -> FileType {
+        if self.hex_mode {
+            FileType::Binary
+        } else {
+            self.file_type.clone()
+        }
+    }
+
+    /// Whether hex mode can be switched off, i.e. the file has another view
+    pub fn can_toggle_hex(&self) -> bool {
+        self.file_type != FileType::Binary && !self.is_modified && !self.is_comparison
+    }
+
+    /// Show the page of the hex dump containing `offset`
+    pub fn set_hex_offset(&mut self, offset: usize) {
+        if let ViewerContent::Hex {
+            bytes,
+            offset: current,
+        } = &mut self.content
+            && offset < bytes.len()
+        {
+            *current = page_start(offset);
         }
     }
 
@@ -102,6 +309,71 @@ impl FileViewerState {
     pub fn mark_saved(&mut self) {
         self.is_modified = false;
         self.is_saving = false;
+        self.sudo_password = None;
+    }
+
+    /// Ask for the sudo password after a save was refused (or the previous
+    /// password was rejected)
+    pub fn prompt_for_sudo(&mut self) {
+        self.sudo_password = Some(SecretString::from(String::new()));
+    }
+
+    /// Editable text shown right now: text files, and markdown outside preview
+    pub fn editor_content(&self) -> Option<&text_editor::Content> {
+        match &self.content {
+            ViewerContent::Text { content } => Some(content),
+            ViewerContent::Markdown {
+                content,
+                preview_mode: false,
+                ..
+            } => Some(content),
+            _ => None,
+        }
+    }
+
+    fn editor_content_mut(&mut self) -> Option<&mut text_editor::Content> {
+        match &mut self.content {
+            ViewerContent::Text { content } => Some(content),
+            ViewerContent::Markdown {
+                content,
+                preview_mode: false,
+                ..
+            } => Some(content),
+            _ => None,
+        }
+    }
+
+    /// 1-based line and column of the text cursor
+    pub fn cursor_line_column(&self) -> Option<(usize, usize)> {
+        let position = self.editor_content()?.cursor().position;
+        Some((position.line + 1, position.column + 1))
+    }
+
+    /// Put the cursor at the start of 1-based `line` (clamped to the text)
+    /// and scroll it near the top of the view, with a few lines of context
+    /// above it.
+    pub fn go_to_line(&mut self, line: usize) {
+        let Some(content) = self.editor_content_mut() else {
+            return;
+        };
+        let line_count = content.line_count().max(1);
+        let line = line.clamp(1, line_count) - 1;
+        let to_i32 = |lines: usize| lines.min(i32::MAX as usize) as i32;
+
+        // Scrolling is relative, so start from the top of the text
+        content.perform(text_editor::Action::Scroll {
+            lines: -to_i32(line_count),
+        });
+        let scroll = line.saturating_sub(GOTO_LINE_CONTEXT);
+        if scroll > 0 {
+            content.perform(text_editor::Action::Scroll {
+                lines: to_i32(scroll),
+            });
+        }
+        content.move_to(text_editor::Cursor {
+            position: text_editor::Position { line, column: 0 },
+            selection: None,
+        });
     }
 
     /// Toggle markdown preview mode
@@ -110,11 +382,14 @@ impl FileViewerState {
             preview_mode,
             content,
             raw_text,
+            preview,
         } = &mut self.content
         {
             *preview_mode = !*preview_mode;
-            // Update raw_text when leaving preview mode
-            if !*preview_mode {
+            if *preview_mode {
+                *preview = markdown::parse(raw_text).collect();
+            } else {
+                // Update raw_text when leaving preview mode
                 *raw_text = content.text();
             }
         }
@@ -133,6 +408,14 @@ impl FileViewerState {
         }
     }
 
+    /// Page shown by the PDF viewer
+    pub fn pdf_page(&self) -> Option<usize> {
+        match &self.content {
+            ViewerContent::Pdf { current_page, .. } => Some(*current_page),
+            _ => None,
+        }
+    }
+
     /// Mark a PDF page as rendering
     pub fn set_pdf_rendering(&mut self, page: usize, rendering: bool) {
         if let ViewerContent::Pdf {
@@ -153,13 +436,56 @@ impl FileViewerState {
         }
     }
 
-    /// Set image zoom level
+    /// Set image zoom level, leaving fit-to-window mode
     pub fn set_zoom(&mut self, zoom: f32) {
         if let ViewerContent::Image {
-            zoom: current_zoom, ..
+            zoom: current_zoom,
+            fit_to_window,
+            ..
+        } = &mut self.content
+        {
+            *current_zoom = zoom.clamp(MIN_IMAGE_ZOOM, MAX_IMAGE_ZOOM);
+            *fit_to_window = false;
+        }
+    }
+
+    /// Keep the image fitted to the view until the zoom is changed
+    pub fn fit_image_to_window(&mut self) {
+        if let ViewerContent::Image {
+            fit_to_window,
+            scroll,
+            ..
         } = &mut self.content
         {
-            *current_zoom = zoom.clamp(0.1, 5.0);
+            *fit_to_window = true;
+            *scroll = AbsoluteOffset::default();
+        }
+    }
+
+    /// Remember where the image area is scrolled to
+    pub fn set_image_scroll(&mut self, offset: AbsoluteOffset) {
+        if let ViewerContent::Image { scroll, .. } = &mut self.content {
+            *scroll = offset;
+        }
+    }
+
+    /// Turn a raster image a quarter turn and redraw it
+    pub fn rotate_image(&mut self, clockwise: bool) {
+        if let ViewerContent::Image {
+            width,
+            height,
+            raster: Some(raster),
+            ..
+        } = &mut self.content
+        {
+            let pixels = if clockwise {
+                image::imageops::rotate90(&raster.pixels)
+            } else {
+                image::imageops::rotate270(&raster.pixels)
+            };
+            *width = pixels.width();
+            *height = pixels.height();
+            *raster = RasterImage::new(pixels, std::mem::take(&mut raster.metadata));
         }
     }
 }
@@ -167,9 +493,183 @@ impl FileViewerState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::views::file_viewer::hex::HEX_PAGE_BYTES;
     use iced::widget::text_editor;
     use std::path::PathBuf;
 
+    #[test]
+    fn saving_hides_the_sudo_prompt() {
+        let mut state = FileViewerState::new(
+            uuid::Uuid::new_v4(),
+            "hosts".to_string(),
+            FileSource::Remote {
+                temp_path: PathBuf::from("/tmp/hosts"),
+                session_id: uuid::Uuid::new_v4(),
+                remote_path: PathBuf::from("/etc/hosts"),
+            },
+            FileType::Text { language: None },
+        );
+        assert!(state.sudo_password.is_none());
+
+        state.prompt_for_sudo();
+        assert!(state.sudo_password.is_some());
+
+        state.mark_saved();
+        assert!(state.sudo_password.is_none());
+    }
+
+    #[test]
+    fn hex_offset_snaps_to_pages_within_the_file() {
+        let mut state = FileViewerState::new(
+            uuid::Uuid::new_v4(),
+            "blob.bin".to_string(),
+            FileSource::Local {
+                path: PathBuf::from("blob.bin"),
+            },
+            FileType::Binary,
+        );
+        assert!(state.hex_mode);
+        assert!(!state.can_toggle_hex());
+
+        state.content = ViewerContent::Hex {
+            bytes: vec![0; HEX_PAGE_BYTES * 2 + 10],
+            offset: 0,
+        };
+
+        state.set_hex_offset(HEX_PAGE_BYTES * 2 + 5);
+        assert!(matches!(
+            state.content,
+            ViewerContent::Hex { offset, .. } if offset == HEX_PAGE_BYTES * 2
+        ));
+
+        state.set_hex_offset(HEX_PAGE_BYTES * 3);
+        assert!(matches!(
+            state.content,
+            ViewerContent::Hex { offset, .. } if offset == HEX_PAGE_BYTES * 2
+        ));
+    }
+
+    #[test]
+    fn goto_line_target_validates_and_clamps() {
+        assert_eq!(goto_line_target(" 42 ", 100), Ok(42));
+        assert_eq!(goto_line_target("0", 100), Ok(1));
+        assert_eq!(goto_line_target("250", 100), Ok(100));
+        assert_eq!(goto_line_target("99999999999999999999999", 100), Ok(100));
+        assert_eq!(goto_line_target("3", 0), Ok(1));
+        assert!(goto_line_target("", 100).is_err());
+        assert!(goto_line_target("-5", 100).is_err());
+        assert!(goto_line_target("12a", 100).is_err());
+    }
+
+    #[test]
+    fn longest_line_columns_counts_tab_stops() {
+        assert_eq!(longest_line_columns(""), 0);
+        assert_eq!(longest_line_columns("ab\nabcdef\r\nabc"), 6);
+        assert_eq!(longest_line_columns("\tx"), 9);
+        assert_eq!(longest_line_columns("abc\tx"), 9);
+        assert_eq!(longest_line_columns("héllo"), 5);
+    }
+
+    #[test]
+    fn go_to_line_places_the_cursor_on_the_clamped_line() {
+        let mut state = FileViewerState::new(
+            uuid::Uuid::new_v4(),
+            "app.log".to_string(),
+            FileSource::Local {
+                path: PathBuf::from("app.log"),
+            },
+            FileType::Text { language: None },
+        );
+        state.content = ViewerContent::Text {
+            content: text_editor::Content::with_text("one\ntwo\nthree\nfour"),
+        };
+
+        state.go_to_line(3);
+        assert_eq!(state.cursor_line_column(), Some((3, 1)));
+
+        state.go_to_line(40);
+        assert_eq!(state.cursor_line_column(), Some((4, 1)));
+    }
+
+    #[test]
+    fn fit_zoom_fits_the_longer_side_inside_the_padding() {
+        let viewport = Size::new(432.0, 332.0);
+        assert_eq!(fit_zoom(Size::new(800.0, 600.0), viewport), 0.5);
+        assert_eq!(fit_zoom(Size::new(100.0, 300.0), viewport), 1.0);
+        assert_eq!(fit_zoom(Size::new(1.0, 1.0), viewport), MAX_IMAGE_ZOOM);
+        assert_eq!(fit_zoom(Size::new(0.0, 0.0), viewport), 1.0);
+    }
+
+    #[test]
+    fn zoom_anchor_offset_keeps_the_point_under_the_cursor() {
+        let size = Size::new(1000.0, 1000.0);
+        let viewport = Size::new(400.0, 400.0);
+        let cursor = Point::new(100.0, 200.0);
+        let scroll = AbsoluteOffset { x: 300.0, y: 0.0 };
+
+        // Image point under the cursor: (300 + 100 - 16, 0 + 200 - 16)
+        let offset = zoom_anchor_offset(size, viewport, scroll, cursor, 1.0, 2.0);
+        assert_eq!(offset.x, IMAGE_PADDING + 384.0 * 2.0 - cursor.x);
+        assert_eq!(offset.y, IMAGE_PADDING + 184.0 * 2.0 - cursor.y);
+
+        // Zoomed out far enough to fit, the image is centred and unscrolled
+        let offset = zoom_anchor_offset(size, viewport, scroll, cursor, 1.0, 0.2);
+        assert_eq!((offset.x, offset.y), (0.0, 0.0));
+    }
+
+    #[test]
+    fn rotate_image_turns_the_pixels_a_quarter_turn() {
+        let mut state = FileViewerState::new(
+            uuid::Uuid::new_v4(),
+            "photo.png".to_string(),
+            FileSource::Local {
+                path: PathBuf::from("photo.png"),
+            },
+            FileType::Image,
+        );
+        // Two pixels side by side: red then blue
+        let mut pixels = image::RgbaImage::new(2, 1);
+        pixels.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        pixels.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+        state.content = ViewerContent::Image {
+            data: Vec::new(),
+            zoom: 1.0,
+            fit_to_window: false,
+            scroll: AbsoluteOffset::default(),
+            width: 2,
+            height: 1,
+            is_svg: false,
+            raster: Some(RasterImage::new(pixels, ImageMetadata::default())),
+        };
+
+        state.rotate_image(true);
+        let ViewerContent::Image {
+            width,
+            height,
+            raster: Some(raster),
+            ..
+        } = &state.content
+        else {
+            panic!("expected a raster image");
+        };
+        assert_eq!((*width, *height), (1, 2));
+        // Turning right puts the left (red) pixel on top
+        assert_eq!(
+            raster.pixels.get_pixel(0, 0),
+            &image::Rgba([255, 0, 0, 255])
+        );
+
+        state.rotate_image(false);
+        assert!(matches!(
+            state.content,
+            ViewerContent::Image {
+                width: 2,
+                height: 1,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn toggle_preview_updates_mode_and_raw_text() {
         let mut state = FileViewerState::new(
@@ -185,6 +685,7 @@ mod tests {
             content: text_editor::Content::with_text("initial"),
             raw_text: "initial".to_string(),
             preview_mode: false,
+            preview: Vec::new(),
         };
 
         state.toggle_preview();
@@ -192,10 +693,12 @@ mod tests {
             ViewerContent::Markdown {
                 preview_mode,
                 raw_text,
+                preview,
                 ..
             } => {
                 assert!(*preview_mode);
                 assert_eq!(raw_text, "initial");
+                assert_eq!(preview.len(), 1);
             }
             _ => panic!("expected markdown content"),
         }
@@ -15,7 +15,7 @@ pub enum FileType {
     Pdf,
     /// Markdown file (supports edit/preview toggle)
     Markdown,
-    /// Binary file (unsupported for viewing)
+    /// Binary file (shown as a hex dump)
     Binary,
 }
 
@@ -140,11 +140,6 @@ impl FileType {
             .unwrap_or(Self::Binary)
     }
 
-    /// Check if file type is viewable
-    pub fn is_viewable(&self) -> bool {
-        !matches!(self, Self::Binary)
-    }
-
     /// Check if file type is editable
     pub fn is_editable(&self) -> bool {
         matches!(self, Self::Text { .. } | Self::Markdown)
@@ -167,6 +162,46 @@ pub enum FileSource {
     },
 }
 
+impl FileSource {
+    /// Path of the local copy the viewer loads from
+    pub fn local_path(&self) -> &Path {
+        match self {
+            Self::Local { path } => path,
+            Self::Remote { temp_path, .. } => temp_path,
+        }
+    }
+}
+
+/// Metadata read from a raster image file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageMetadata {
+    /// Encoding of the file, e.g. "PNG" or "JPEG"
+    pub format: String,
+    /// EXIF orientation tag (1-8)
+    pub orientation: Option<u32>,
+    /// Camera make and model
+    pub camera: Option<String>,
+    /// When the photo was taken, as "YYYY-MM-DD HH:MM:SS"
+    pub taken: Option<String>,
+}
+
+impl ImageMetadata {
+    /// How the EXIF orientation turned the stored pixels upright, or `None`
+    /// when they were stored upright already
+    pub fn orientation_label(&self) -> Option<&'static str> {
+        match self.orientation? {
+            2 => Some("mirrored"),
+            3 => Some("rotated 180\u{00B0}"),
+            4 => Some("flipped"),
+            5 => Some("mirrored and rotated 90\u{00B0} left"),
+            6 => Some("rotated 90\u{00B0} right"),
+            7 => Some("mirrored and rotated 90\u{00B0} right"),
+            8 => Some("rotated 90\u{00B0} left"),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
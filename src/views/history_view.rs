@@ -1,13 +1,35 @@
 use chrono::Local;
-use iced::widget::{Column, Space, button, column, container, row, scrollable, text};
+use iced::widget::{
+    Column, Space, button, column, container, pick_list, row, scrollable, text, text_input,
+};
 use iced::{Alignment, Element, Fill, Length};
+use uuid::Uuid;
 
 use crate::app::FocusSection;
-use crate::config::{HistoryConfig, HistoryEntry, HostsConfig, SessionType};
+use crate::config::{
+    HistoryConfig, HistoryDateRange, HistoryEntry, HistoryFilter, HistorySort, HostsConfig,
+    SessionType,
+};
 use crate::icons::{self, icon_with_color};
 use crate::message::{HistoryMessage, Message};
-use crate::theme::{BORDER_RADIUS, CARD_BORDER_RADIUS, ScaledFonts, Theme};
-use crate::views::host_grid::os_icon_data;
+use crate::theme::{BORDER_RADIUS, CARD_BORDER_RADIUS, STATUS_FAILURE, ScaledFonts, Theme};
+use crate::views::components::toggle_group;
+use crate::views::dialogs::common::{
+    dialog_input_style, dialog_pick_list_menu_style, dialog_pick_list_style,
+};
+
+/// Entry of the host facet picker
+#[derive(Debug, Clone, PartialEq)]
+struct HostFacetOption {
+    id: Option<Uuid>,
+    label: String,
+}
+
+impl std::fmt::Display for HostFacetOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
 
 /// Format a date as a relative label ("Today", "Yesterday") or "Mon dd" format
 fn format_relative_date(date: chrono::DateTime<chrono::Utc>) -> String {
@@ -32,6 +54,7 @@ fn date_key(date: chrono::DateTime<chrono::Utc>) -> String {
 /// Build the history view showing recent connections
 pub fn history_view(
     history: &HistoryConfig,
+    filter: &HistoryFilter,
     hosts_config: &HostsConfig,
     theme: Theme,
     fonts: ScaledFonts,
@@ -44,43 +67,37 @@ pub fn history_view(
             .size(fonts.heading)
             .color(theme.text_primary),
         Space::new().width(Length::Fill),
-        button(
-            text("Clear History")
-                .size(fonts.label)
-                .color(theme.text_secondary),
-        )
-        .style(move |_theme, status| {
-            let bg = match status {
-                button::Status::Hovered => Some(theme.hover.into()),
-                _ => None,
-            };
-            button::Style {
-                background: bg,
-                text_color: theme.text_secondary,
-                border: iced::Border {
-                    color: theme.border,
-                    width: 1.0,
-                    radius: BORDER_RADIUS.into(),
-                },
-                ..Default::default()
-            }
-        })
-        .padding([6, 12])
-        .on_press(Message::History(HistoryMessage::Clear)),
+        header_button(
+            "Export",
+            (!history.entries.is_empty()).then_some(Message::History(HistoryMessage::Export)),
+            theme,
+            fonts,
+        ),
+        header_button(
+            "Clear History",
+            Some(Message::History(HistoryMessage::Clear)),
+            theme,
+            fonts,
+        ),
     ]
+    .spacing(8)
     .align_y(Alignment::Center)
     .padding(iced::Padding::new(24.0).bottom(16.0));
 
+    let entries = history.filtered(filter);
+
     // History entries grouped by day as a timeline
     let content: Element<'static, Message> = if history.entries.is_empty() {
         empty_state(theme, fonts)
+    } else if entries.is_empty() {
+        no_matches_state(theme, fonts)
     } else {
         // Group entries by date
         #[allow(clippy::type_complexity)]
         let mut day_groups: Vec<(String, String, Vec<(usize, &HistoryEntry)>)> = Vec::new();
         let mut current_key: Option<String> = None;
 
-        for (idx, entry) in history.entries.iter().enumerate() {
+        for (idx, entry) in entries.into_iter().enumerate() {
             let key = date_key(entry.connected_at);
             let label = format_relative_date(entry.connected_at);
 
@@ -115,7 +132,11 @@ pub fn history_view(
         scrollable(main_column).height(Fill).width(Fill).into()
     };
 
-    let main_content = column![header, content];
+    let main_content: Element<'static, Message> = if history.entries.is_empty() {
+        column![header, content].into()
+    } else {
+        column![header, filter_bar(history, filter, theme, fonts), content].into()
+    };
 
     container(main_content)
         .width(Fill)
@@ -127,6 +148,35 @@ pub fn history_view(
         .into()
 }
 
+/// Outlined button in the page header
+fn header_button(
+    label: &'static str,
+    on_press: Option<Message>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    button(text(label).size(fonts.label).color(theme.text_secondary))
+        .style(move |_theme, status| {
+            let bg = match status {
+                button::Status::Hovered => Some(theme.hover.into()),
+                _ => None,
+            };
+            button::Style {
+                background: bg,
+                text_color: theme.text_secondary,
+                border: iced::Border {
+                    color: theme.border,
+                    width: 1.0,
+                    radius: BORDER_RADIUS.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .padding([6, 12])
+        .on_press_maybe(on_press)
+        .into()
+}
+
 /// Build a complete day section with timeline
 #[allow(clippy::too_many_arguments)]
 fn build_day_section(
@@ -226,7 +276,7 @@ fn build_entry_card(
         if let Some(detected_os) = host.detected_os.as_ref() {
             let (r, g, b) = detected_os.icon_color();
             (
-                os_icon_data(&host.detected_os),
+                icons::os::for_detected(host.detected_os.as_ref()),
                 iced::Color::from_rgba8(r, g, b, 0.85),
             )
         } else {
@@ -268,10 +318,10 @@ fn build_entry_card(
             }),
         ]
         .align_y(Alignment::Center),
-        text(format!("{}@{} | {}", username, hostname, duration_str))
+        text(format!("{}@{}", username, hostname))
             .size(fonts.label)
             .color(theme.text_muted),
-        text(time_str).size(fonts.label).color(theme.text_muted),
+        status_line(entry, time_str, duration_str, theme, fonts),
     ]
     .spacing(4);
 
@@ -381,3 +431,166 @@ fn empty_state(theme: Theme, fonts: ScaledFonts) -> Element<'static, Message> {
         .align_y(Alignment::Center)
         .into()
 }
+
+/// Search box and facet controls above the timeline
+fn filter_bar(
+    history: &HistoryConfig,
+    filter: &HistoryFilter,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let search = text_input("Search by host, address or user", &filter.query)
+        .on_input(|query| Message::History(HistoryMessage::SearchChanged(query)))
+        .padding(8)
+        .size(fonts.body)
+        .width(Length::Fill)
+        .style(dialog_input_style(theme));
+
+    let mut host_options = vec![HostFacetOption {
+        id: None,
+        label: "All hosts".to_string(),
+    }];
+    host_options.extend(
+        history
+            .hosts()
+            .into_iter()
+            .map(|(id, label)| HostFacetOption {
+                id: Some(id),
+                label,
+            }),
+    );
+    let selected_host = host_options
+        .iter()
+        .find(|option| option.id == filter.host_id)
+        .cloned();
+    let host_picker = pick_list(host_options, selected_host, |option| {
+        Message::History(HistoryMessage::HostFilterChanged(option.id))
+    })
+    .padding(8)
+    .text_size(fonts.body)
+    .width(Length::Fixed(200.0))
+    .style(dialog_pick_list_style(theme))
+    .menu_style(dialog_pick_list_menu_style(theme));
+
+    let type_options = [
+        (None, "All"),
+        (Some(SessionType::Ssh), "SSH"),
+        (Some(SessionType::Sftp), "SFTP"),
+        (Some(SessionType::Vnc), "VNC"),
+        (Some(SessionType::Local), "Local"),
+    ];
+    let type_facet = toggle_group(
+        filter.session_type.clone(),
+        &type_options,
+        |session_type| Message::History(HistoryMessage::SessionTypeFilterChanged(session_type)),
+        theme,
+        fonts,
+    );
+
+    let date_options: Vec<(HistoryDateRange, &'static str)> = HistoryDateRange::ALL
+        .iter()
+        .map(|range| (*range, range.label()))
+        .collect();
+    let date_facet = toggle_group(
+        filter.date_range,
+        &date_options,
+        |range| Message::History(HistoryMessage::DateRangeChanged(range)),
+        theme,
+        fonts,
+    );
+
+    let sort_options: Vec<(HistorySort, &'static str)> = HistorySort::ALL
+        .iter()
+        .map(|sort| (*sort, sort.label()))
+        .collect();
+    let sort_toggle = toggle_group(
+        filter.sort,
+        &sort_options,
+        |sort| Message::History(HistoryMessage::SortChanged(sort)),
+        theme,
+        fonts,
+    );
+
+    let mut facets = row![type_facet, date_facet, Space::new().width(Length::Fill)]
+        .spacing(12)
+        .align_y(Alignment::Center);
+    if filter.is_active() {
+        facets = facets.push(
+            button(
+                text("Clear filters")
+                    .size(fonts.label)
+                    .color(theme.text_secondary),
+            )
+            .style(move |_theme, status| button::Style {
+                background: matches!(status, button::Status::Hovered).then(|| theme.hover.into()),
+                text_color: theme.text_secondary,
+                border: iced::Border {
+                    radius: BORDER_RADIUS.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .padding([6, 12])
+            .on_press(Message::History(HistoryMessage::ClearFilters)),
+        );
+    }
+    facets = facets.push(sort_toggle);
+
+    column![
+        row![search, host_picker]
+            .spacing(12)
+            .align_y(Alignment::Center),
+        facets,
+    ]
+    .spacing(10)
+    .max_width(720)
+    .padding(iced::Padding::new(24.0).top(0.0).bottom(16.0))
+    .into()
+}
+
+/// Start time with how long the session lasted, or why it failed
+fn status_line(
+    entry: &HistoryEntry,
+    time_str: String,
+    duration_str: String,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let (status, color) = match &entry.error {
+        Some(error) => (format!("Failed: {}", error), STATUS_FAILURE),
+        None if entry.disconnected_at.is_none() => {
+            (format!("Connected for {}", duration_str), theme.text_muted)
+        }
+        None => (format!("Lasted {}", duration_str), theme.text_muted),
+    };
+
+    row![
+        text(time_str).size(fonts.label).color(theme.text_muted),
+        text("·").size(fonts.label).color(theme.text_muted),
+        text(status).size(fonts.label).color(color),
+    ]
+    .spacing(6)
+    .into()
+}
+
+/// Shown when history has entries but none pass the filters
+fn no_matches_state(theme: Theme, fonts: ScaledFonts) -> Element<'static, Message> {
+    let content = column![
+        icon_with_color(icons::ui::HISTORY, 48, theme.text_muted),
+        text("No matching connections")
+            .size(fonts.heading)
+            .color(theme.text_primary),
+        text("Try a different search or clear the filters")
+            .size(fonts.body)
+            .color(theme.text_muted),
+    ]
+    .spacing(8)
+    .align_x(Alignment::Center);
+
+    container(content)
+        .width(Fill)
+        .height(Length::Fixed(300.0))
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center)
+        .into()
+}
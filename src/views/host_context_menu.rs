@@ -0,0 +1,142 @@
+//! Context menu for host cards
+
+use iced::widget::{Column, Space, button, container, text};
+use iced::{Color, Element, Length, Padding};
+use iced::{Fill, Point};
+use uuid::Uuid;
+
+use crate::message::{HostContextMenuAction, HostMessage, Message};
+use crate::theme::{ScaledFonts, Theme};
+use crate::widgets::mouse_area;
+
+const CONTEXT_MENU_WIDTH: f32 = 200.0;
+const ESTIMATED_MENU_HEIGHT: f32 = 170.0;
+
+/// State for the host card context menu
+#[derive(Debug, Clone)]
+pub struct HostContextMenuState {
+    pub visible: bool,
+    pub position: Point,
+    pub target_host: Option<Uuid>,
+}
+
+impl Default for HostContextMenuState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            position: Point::ORIGIN,
+            target_host: None,
+        }
+    }
+}
+
+impl HostContextMenuState {
+    pub fn show(&mut self, host_id: Uuid, x: f32, y: f32) {
+        self.visible = true;
+        self.position = Point::new(x, y);
+        self.target_host = Some(host_id);
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.target_host = None;
+    }
+}
+
+fn context_menu_item<'a>(
+    label: &'static str,
+    action: HostContextMenuAction,
+    host_id: Uuid,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let content = container(text(label).size(fonts.body).color(theme.text_primary))
+        .padding(Padding::new(8.0).left(12.0))
+        .width(Length::Fill);
+
+    button(content)
+        .style(move |_theme, status| {
+            let background = match status {
+                iced::widget::button::Status::Hovered => Some(theme.hover.into()),
+                _ => None,
+            };
+            iced::widget::button::Style {
+                background,
+                text_color: theme.text_primary,
+                ..Default::default()
+            }
+        })
+        .padding(0)
+        .on_press(Message::Host(HostMessage::ContextMenuAction(
+            host_id, action,
+        )))
+        .into()
+}
+
+/// Build the context menu overlay for host cards
+pub fn host_context_menu_overlay(
+    state: &HostContextMenuState,
+    theme: Theme,
+    fonts: ScaledFonts,
+    window_size: iced::Size,
+) -> Element<'_, Message> {
+    if !state.visible {
+        return Space::new().into();
+    }
+
+    let Some(host_id) = state.target_host else {
+        return Space::new().into();
+    };
+
+    let items: Vec<Element<'_, Message>> = [
+        ("Connect", HostContextMenuAction::Connect),
+        ("Edit", HostContextMenuAction::Edit),
+        ("Duplicate", HostContextMenuAction::Duplicate),
+        ("Details", HostContextMenuAction::Details),
+    ]
+    .into_iter()
+    .map(|(label, action)| context_menu_item(label, action, host_id, theme, fonts))
+    .collect();
+
+    let menu = container(Column::with_children(items).spacing(4))
+        .padding(8)
+        .width(Length::Fixed(CONTEXT_MENU_WIDTH))
+        .style(move |_| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 12.0.into(),
+            },
+            shadow: iced::Shadow {
+                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
+                offset: iced::Vector::new(0.0, 4.0),
+                blur_radius: 16.0,
+            },
+            ..Default::default()
+        });
+    let menu = mouse_area(menu).capture_all_events(true);
+
+    let pos = state.position;
+    let mut x = pos.x;
+    let mut y = pos.y;
+
+    if x + CONTEXT_MENU_WIDTH > window_size.width {
+        x = (window_size.width - CONTEXT_MENU_WIDTH).max(0.0);
+    }
+
+    if y + ESTIMATED_MENU_HEIGHT > window_size.height {
+        y = (window_size.height - ESTIMATED_MENU_HEIGHT).max(0.0);
+    }
+
+    let background = mouse_area(
+        container(Space::new().width(Fill).height(Fill))
+            .width(Fill)
+            .height(Fill),
+    )
+    .on_press(Message::Host(HostMessage::HideContextMenu));
+
+    let positioned_menu = container(menu).padding(Padding::new(0.0).top(y).left(x));
+
+    iced::widget::stack![background, positioned_menu].into()
+}
@@ -137,37 +137,43 @@ pub fn host_details_sheet_view(
             ..Default::default()
         })
         .on_press(Message::Host(HostMessage::Connect(host_id))),
-        button(
-            row![
-                icon_with_color(icons::ui::PENCIL, 14, theme.text_primary),
-                text("Edit")
-                    .size(fonts.button_small)
-                    .color(theme.text_primary),
-            ]
-            .spacing(6)
-            .align_y(Alignment::Center),
-        )
-        .padding([9, 14])
-        .style(move |_theme, status| {
-            let bg = match status {
-                button::Status::Hovered => theme.hover,
-                _ => theme.surface,
-            };
-            button::Style {
-                background: Some(bg.into()),
-                text_color: theme.text_primary,
-                border: iced::Border {
-                    color: theme.border,
-                    width: 1.0,
-                    radius: BORDER_RADIUS.into(),
-                },
-                ..Default::default()
-            }
-        })
-        .on_press(Message::Host(HostMessage::Edit(host_id))),
+        secondary_action(
+            icons::ui::PENCIL,
+            "Edit",
+            Message::Host(HostMessage::Edit(host_id)),
+            theme,
+            fonts,
+        ),
     ]
     .spacing(8);
 
+    let actions: Element<'static, Message> = if host.protocol == Protocol::Ssh {
+        column![
+            actions,
+            row![
+                secondary_action(
+                    icons::ui::TERMINAL,
+                    "Copy ssh command",
+                    Message::Host(HostMessage::CopySshCommand(host_id)),
+                    theme,
+                    fonts,
+                ),
+                secondary_action(
+                    icons::ui::CODE,
+                    "Copy config block",
+                    Message::Host(HostMessage::CopySshConfig(host_id)),
+                    theme,
+                    fonts,
+                ),
+            ]
+            .spacing(8),
+        ]
+        .spacing(8)
+        .into()
+    } else {
+        actions.into()
+    };
+
     let sheet = container(scrollable(column![header, actions, meta].spacing(22)))
         .width(Length::Fixed(420.0))
         .height(Fill)
@@ -208,6 +214,44 @@ pub fn host_details_sheet_view(
     .into()
 }
 
+fn secondary_action(
+    icon: &'static [u8],
+    label: &'static str,
+    message: Message,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    button(
+        row![
+            icon_with_color(icon, 14, theme.text_primary),
+            text(label)
+                .size(fonts.button_small)
+                .color(theme.text_primary),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center),
+    )
+    .padding([9, 14])
+    .style(move |_theme, status| {
+        let bg = match status {
+            button::Status::Hovered => theme.hover,
+            _ => theme.surface,
+        };
+        button::Style {
+            background: Some(bg.into()),
+            text_color: theme.text_primary,
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: BORDER_RADIUS.into(),
+            },
+            ..Default::default()
+        }
+    })
+    .on_press(message)
+    .into()
+}
+
 fn detail_row(
     label: &'static str,
     value: impl Into<String>,
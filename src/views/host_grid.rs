@@ -1,6 +1,6 @@
 use iced::widget::{Column, Row, Space, button, column, container, row, text, text_input};
 use iced::{Alignment, Element, Fill, Length, Padding};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Search input ID for auto-focus
@@ -8,15 +8,18 @@ pub fn search_input_id() -> iced::widget::Id {
     iced::widget::Id::new("hosts_search")
 }
 
+use crate::app::managers::Reachability;
 use crate::app::{FocusSection, SidebarState};
 use crate::config::{DetectedOs, Protocol};
 use crate::icons::{self, icon_with_color};
-use crate::message::{HostMessage, Message, UiMessage};
+use crate::message::{DialogMessage, HostMessage, Message, UiMessage};
 use crate::theme::{
     BORDER_RADIUS, CARD_BORDER_RADIUS, CARD_HEIGHT, GRID_PADDING, GRID_SPACING, MIN_CARD_WIDTH,
-    RADIUS_SM, SIDEBAR_WIDTH, SIDEBAR_WIDTH_COLLAPSED, ScaledFonts, Theme,
+    RADIUS_SM, SIDEBAR_WIDTH, SIDEBAR_WIDTH_COLLAPSED, STATUS_FAILURE, STATUS_SUCCESS, ScaledFonts,
+    Theme,
 };
 use crate::views::components::hub_pill;
+use crate::widgets::{draggable, mouse_area};
 
 /// Format a timestamp as a relative time string (e.g. "2h ago", "3d ago")
 fn format_relative_time(dt: &chrono::DateTime<chrono::Utc>) -> String {
@@ -46,6 +49,37 @@ pub struct GroupCard {
     pub id: Uuid,
     pub name: String,
     pub collapsed: bool,
+    /// Nesting level; top-level groups are 0
+    pub depth: usize,
+}
+
+/// Something being dragged in the host grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostGridDragItem {
+    Host(Uuid),
+    Group(Uuid),
+}
+
+/// Where a dragged host or group would land if released
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostGridDropTarget {
+    /// Take this host's place (hosts only)
+    Host(Uuid),
+    /// A group header: hosts move into the group, groups take its place
+    Group(Uuid),
+    /// The "Inside" chip on a group header: the dragged group becomes a
+    /// subgroup
+    IntoGroup(Uuid),
+    /// The ungrouped section: hosts leave their group, groups move to the
+    /// top level
+    Ungrouped,
+}
+
+/// Drag in progress on the host grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostGridDrag {
+    pub item: HostGridDragItem,
+    pub target: Option<HostGridDropTarget>,
 }
 
 /// Host card data for the grid view
@@ -57,6 +91,7 @@ pub struct HostCard {
     pub protocol: Protocol,
     pub last_connected: Option<chrono::DateTime<chrono::Utc>>,
     pub group_id: Option<Uuid>,
+    pub tags: Vec<String>,
     /// Sessions for this host currently route through Portal Hub.
     pub via_hub: bool,
 }
@@ -134,22 +169,7 @@ fn build_action_bar(
         .spacing(6)
         .align_y(Alignment::Center),
     )
-    .style(move |_theme, status| {
-        let bg = match status {
-            button::Status::Hovered => theme.hover,
-            _ => theme.background,
-        };
-        button::Style {
-            background: Some(bg.into()),
-            text_color: theme.text_primary,
-            border: iced::Border {
-                color: theme.border,
-                width: 1.0,
-                radius: 22.0.into(),
-            },
-            ..Default::default()
-        }
-    })
+    .style(pill_button_style(theme))
     .padding([12, 20])
     .on_press(Message::Host(HostMessage::Add));
 
@@ -164,25 +184,25 @@ fn build_action_bar(
         .spacing(6)
         .align_y(Alignment::Center),
     )
-    .style(move |_theme, status| {
-        let bg = match status {
-            button::Status::Hovered => theme.hover,
-            _ => theme.background,
-        };
-        button::Style {
-            background: Some(bg.into()),
-            text_color: theme.text_primary,
-            border: iced::Border {
-                color: theme.border,
-                width: 1.0,
-                radius: 22.0.into(),
-            },
-            ..Default::default()
-        }
-    })
+    .style(pill_button_style(theme))
     .padding([12, 20])
     .on_press(Message::Host(HostMessage::LocalTerminal));
 
+    // Import button - pulls hosts from ~/.ssh/config
+    let import_btn = button(
+        row![
+            icon_with_color(icons::files::FILE_COG, 14, theme.text_primary),
+            text("Import")
+                .size(fonts.button_small)
+                .color(theme.text_primary),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center),
+    )
+    .style(pill_button_style(theme))
+    .padding([12, 20])
+    .on_press(Message::Dialog(DialogMessage::ImportFromSshConfig));
+
     // Build the bar row
     let bar_content = row![
         search_input,
@@ -190,6 +210,8 @@ fn build_action_bar(
         new_host_btn,
         Space::new().width(8),
         terminal_btn,
+        Space::new().width(8),
+        import_btn,
     ]
     .align_y(Alignment::Center);
 
@@ -205,19 +227,97 @@ fn build_action_bar(
         .into()
 }
 
+/// Row of tag chips filtering the grid; a host must carry every selected tag
+fn build_tag_bar(
+    tags: &[String],
+    selected_tags: &[String],
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let mut chips = Row::new().spacing(6).align_y(Alignment::Center);
+    for tag in tags {
+        let selected = selected_tags
+            .iter()
+            .any(|selected| selected.eq_ignore_ascii_case(tag));
+        chips = chips.push(
+            button(text(format!("#{}", tag)).size(fonts.label))
+                .padding([4, 12])
+                .style(move |_theme, status| {
+                    let bg = match (selected, status) {
+                        (true, _) => theme.accent,
+                        (false, button::Status::Hovered) => theme.hover,
+                        (false, _) => theme.background,
+                    };
+                    button::Style {
+                        background: Some(bg.into()),
+                        text_color: if selected {
+                            theme.text_on(bg)
+                        } else {
+                            theme.text_secondary
+                        },
+                        border: iced::Border {
+                            color: if selected { bg } else { theme.border },
+                            width: 1.0,
+                            radius: 12.0.into(),
+                        },
+                        ..Default::default()
+                    }
+                })
+                .on_press(Message::Ui(UiMessage::HostTagToggled(tag.clone()))),
+        );
+    }
+    if !selected_tags.is_empty() {
+        chips = chips.push(
+            button(text("Clear").size(fonts.label).color(theme.text_muted))
+                .padding([4, 8])
+                .style(|_theme, _status| button::Style::default())
+                .on_press(Message::Ui(UiMessage::HostTagFilterCleared)),
+        );
+    }
+
+    container(chips.wrap())
+        .width(Fill)
+        .padding(Padding::from([0, 24]).bottom(8.0))
+        .into()
+}
+
+/// Pill-shaped bordered style shared by the action bar buttons
+fn pill_button_style(theme: Theme) -> impl Fn(&iced::Theme, button::Status) -> button::Style {
+    move |_iced_theme, status| {
+        let bg = match status {
+            button::Status::Hovered => theme.hover,
+            _ => theme.background,
+        };
+        button::Style {
+            background: Some(bg.into()),
+            text_color: theme.text_primary,
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 22.0.into(),
+            },
+            ..Default::default()
+        }
+    }
+}
+
 /// Build the host grid view (main content area)
 #[allow(clippy::too_many_arguments)]
 pub fn host_grid_view(
     search_query: &str,
     groups: &[GroupCard],
     hosts: &[HostCard],
+    tags: &[String],
+    selected_tags: &[String],
     column_count: usize,
     theme: Theme,
     fonts: ScaledFonts,
     focus_section: FocusSection,
     focus_index: Option<usize>,
     hovered_host: Option<Uuid>,
+    drag: Option<HostGridDrag>,
     live_counts: &HashMap<Uuid, usize>,
+    reachability: Option<&HashMap<Uuid, Reachability>>,
     hub_prompt_direct_count: Option<usize>,
 ) -> Element<'static, Message> {
     // Main scrollable content
@@ -232,7 +332,18 @@ pub fn host_grid_view(
     let groups_empty = groups.is_empty();
     let hosts_empty = hosts.is_empty();
 
-    if hosts_empty && groups_empty {
+    if hosts_empty && groups_empty && !selected_tags.is_empty() {
+        content = content.push(
+            container(
+                text("No hosts carry all of the selected tags")
+                    .size(fonts.body)
+                    .color(theme.text_muted),
+            )
+            .width(Fill)
+            .padding(40)
+            .align_x(Alignment::Center),
+        );
+    } else if hosts_empty && groups_empty {
         content = content.push(empty_state(theme, fonts));
     } else {
         let mut hosts_by_group: HashMap<Option<Uuid>, Vec<&HostCard>> = HashMap::new();
@@ -242,14 +353,36 @@ pub fn host_grid_view(
 
         // Track global focus index across all hosts
         let mut global_idx: usize = 0;
+        let dragged_groups = dragged_group_subtree(groups, drag);
+        // Depth of the collapsed group whose subgroups are being skipped
+        let mut collapsed_depth: Option<usize> = None;
 
-        // Render each group with its hosts inline
+        // Render each group with its hosts inline, subgroups indented
+        // below their parent
         for group in groups {
             let group_hosts = hosts_by_group.remove(&Some(group.id)).unwrap_or_default();
+            if collapsed_depth.is_some_and(|depth| group.depth <= depth) {
+                collapsed_depth = None;
+            }
+            if collapsed_depth.is_some() {
+                global_idx += group_hosts.len();
+                continue;
+            }
+            if group.collapsed {
+                collapsed_depth = Some(group.depth);
+            }
+            let indent = Padding::ZERO.left(group.depth as f32 * GROUP_INDENT);
 
             // Group section header
-            let header = build_group_header(group, group_hosts.len(), theme, fonts);
-            content = content.push(header);
+            let header = build_group_header(
+                group,
+                group_hosts.len(),
+                drag,
+                dragged_groups.contains(&group.id),
+                theme,
+                fonts,
+            );
+            content = content.push(container(header).padding(indent));
 
             // Render hosts if not collapsed
             if !group.collapsed {
@@ -262,23 +395,22 @@ pub fn host_grid_view(
                     focus_index,
                     global_idx,
                     hovered_host,
+                    drag,
                     live_counts,
+                    reachability,
                 );
-                content = content.push(section);
+                content = content.push(container(section).padding(indent));
             }
             global_idx += group_hosts.len();
         }
 
-        // Ungrouped hosts
+        // Ungrouped hosts. While dragging, the header stays visible as a
+        // drop target even when every host is in a group.
         let ungrouped = hosts_by_group.remove(&None).unwrap_or_default();
+        if !groups_empty && (!ungrouped.is_empty() || drag.is_some()) {
+            content = content.push(build_ungrouped_header(ungrouped.len(), drag, theme, fonts));
+        }
         if !ungrouped.is_empty() {
-            if !groups_empty {
-                let header_text = format!("Ungrouped  ({} hosts)", ungrouped.len());
-                let header = text(header_text)
-                    .size(fonts.section)
-                    .color(theme.text_muted);
-                content = content.push(header);
-            }
             let section = build_host_cards_grid(
                 &ungrouped,
                 column_count,
@@ -288,7 +420,9 @@ pub fn host_grid_view(
                 focus_index,
                 global_idx,
                 hovered_host,
+                drag,
                 live_counts,
+                reachability,
             );
             content = content.push(section);
         }
@@ -337,8 +471,13 @@ pub fn host_grid_view(
     // Action bar (fixed at top, below tab bar)
     let action_bar = build_action_bar(search_query, theme, fonts);
 
-    // Main layout: action bar at top, scrollable content fills remaining space
-    let main_content = column![action_bar, scrollable_content];
+    // Main layout: action bar and tag chips at top, scrollable content fills
+    // remaining space
+    let mut main_content = Column::new().push(action_bar);
+    if !tags.is_empty() {
+        main_content = main_content.push(build_tag_bar(tags, selected_tags, theme, fonts));
+    }
+    let main_content = main_content.push(scrollable_content);
 
     container(main_content)
         .width(Fill)
@@ -350,10 +489,80 @@ pub fn host_grid_view(
         .into()
 }
 
-/// Build a group section header (clickable to collapse/expand)
+/// Left indent per nesting level of a subgroup
+const GROUP_INDENT: f32 = 24.0;
+
+/// The dragged group and every group nested inside it; none of them can
+/// take a dropped group. `groups` is in tree order.
+fn dragged_group_subtree(groups: &[GroupCard], drag: Option<HostGridDrag>) -> HashSet<Uuid> {
+    let mut subtree = HashSet::new();
+    let Some(HostGridDrag {
+        item: HostGridDragItem::Group(dragged_id),
+        ..
+    }) = drag
+    else {
+        return subtree;
+    };
+    let mut dragged_depth = None;
+    for group in groups {
+        match dragged_depth {
+            Some(depth) if group.depth > depth => {
+                subtree.insert(group.id);
+            }
+            Some(_) => break,
+            None if group.id == dragged_id => {
+                dragged_depth = Some(group.depth);
+                subtree.insert(group.id);
+            }
+            None => {}
+        }
+    }
+    subtree
+}
+
+/// Groups whose hosts are out of sight: collapsed, or nested inside a
+/// collapsed group. `groups` is in tree order.
+pub fn hidden_host_groups(groups: &[GroupCard]) -> HashSet<Uuid> {
+    let mut hidden = HashSet::new();
+    let mut collapsed_depth: Option<usize> = None;
+    for group in groups {
+        if collapsed_depth.is_some_and(|depth| group.depth <= depth) {
+            collapsed_depth = None;
+        }
+        if collapsed_depth.is_some() || group.collapsed {
+            hidden.insert(group.id);
+        }
+        if collapsed_depth.is_none() && group.collapsed {
+            collapsed_depth = Some(group.depth);
+        }
+    }
+    hidden
+}
+
+/// Messages a drop target sends as the dragged item enters and leaves it
+fn drop_messages(target: HostGridDropTarget) -> (Message, Message) {
+    (
+        Message::Ui(UiMessage::HostGridDragEntered(target)),
+        Message::Ui(UiMessage::HostGridDragLeft(target)),
+    )
+}
+
+/// Header background: highlighted while a drag would drop onto it
+fn header_background(theme: Theme, status: button::Status, is_target: bool) -> iced::Color {
+    match status {
+        _ if is_target => theme.selected,
+        button::Status::Hovered => theme.hover,
+        _ => iced::Color::TRANSPARENT,
+    }
+}
+
+/// Build a group section header (clickable to collapse/expand, draggable
+/// to reorder or nest the group)
 fn build_group_header(
     group: &GroupCard,
     host_count: usize,
+    drag: Option<HostGridDrag>,
+    in_dragged_subtree: bool,
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'static, Message> {
@@ -386,42 +595,117 @@ fn build_group_header(
     .spacing(8)
     .align_y(Alignment::Center);
 
+    // Hosts drop into the group; other groups take its place. A group
+    // cannot land on itself or inside its own subtree.
+    let accepts_drop = match drag.map(|drag| drag.item) {
+        Some(HostGridDragItem::Host(_)) => true,
+        Some(HostGridDragItem::Group(_)) => !in_dragged_subtree,
+        None => false,
+    };
+    let target = drag.and_then(|drag| drag.target);
+    let is_target = target == Some(HostGridDropTarget::Group(group_id));
+
     let header_btn = button(
         container(header_content)
             .padding(Padding::from([8, 4]))
             .width(Length::Fill),
     )
-    .style(move |_theme, status| {
-        let bg = match status {
-            button::Status::Hovered => theme.hover,
-            _ => iced::Color::TRANSPARENT,
-        };
-        button::Style {
-            background: Some(bg.into()),
-            text_color: theme.text_primary,
-            border: iced::Border {
-                radius: 6.0.into(),
-                ..Default::default()
-            },
+    .style(move |_theme, status| button::Style {
+        background: Some(header_background(theme, status, is_target).into()),
+        text_color: theme.text_primary,
+        border: iced::Border {
+            radius: 6.0.into(),
             ..Default::default()
-        }
+        },
+        ..Default::default()
     })
     .padding(0)
     .width(Length::Fill)
     .on_press(Message::Ui(UiMessage::FolderToggle(group_id)));
 
-    // Subtle bottom border
-    container(header_btn)
-        .width(Length::Fill)
-        .style(move |_theme| container::Style {
+    let mut header = draggable(header_btn)
+        .on_drag(Message::Ui(UiMessage::HostGridDragStarted(
+            HostGridDragItem::Group(group_id),
+        )))
+        .on_drop(Message::Ui(UiMessage::HostGridDropped));
+    if accepts_drop {
+        let (enter, leave) = drop_messages(HostGridDropTarget::Group(group_id));
+        header = header.drop_target(enter, leave);
+    }
+
+    let mut header_row = Row::new()
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .push(header);
+
+    // While a group is dragged, a chip beside each possible parent nests it
+    if accepts_drop && matches!(drag.map(|drag| drag.item), Some(HostGridDragItem::Group(_))) {
+        let nest_target = HostGridDropTarget::IntoGroup(group_id);
+        let is_nest_target = target == Some(nest_target);
+        let chip = container(
+            text("Move inside")
+                .size(fonts.label)
+                .color(theme.text_secondary),
+        )
+        .padding(Padding::from([6, 12]))
+        .style(move |_| container::Style {
+            background: Some(
+                if is_nest_target {
+                    theme.selected
+                } else {
+                    theme.surface
+                }
+                .into(),
+            ),
             border: iced::Border {
-                color: theme.border,
-                width: 0.0,
-                ..Default::default()
+                color: if is_nest_target {
+                    theme.accent
+                } else {
+                    theme.border
+                },
+                width: 1.0,
+                radius: 6.0.into(),
             },
             ..Default::default()
-        })
-        .into()
+        });
+        let (enter, leave) = drop_messages(nest_target);
+        header_row = header_row.push(draggable(chip).drop_target(enter, leave));
+    }
+
+    header_row.into()
+}
+
+/// Header of the ungrouped section, which takes hosts out of their group
+/// and groups up to the top level
+fn build_ungrouped_header(
+    host_count: usize,
+    drag: Option<HostGridDrag>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let is_target = drag.and_then(|drag| drag.target) == Some(HostGridDropTarget::Ungrouped);
+    let header = container(
+        text(format!("Ungrouped  ({} hosts)", host_count))
+            .size(fonts.section)
+            .color(theme.text_muted),
+    )
+    .padding(Padding::from([8, 4]))
+    .width(Length::Fill)
+    .style(move |_| container::Style {
+        background: is_target.then(|| theme.selected.into()),
+        border: iced::Border {
+            radius: 6.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let mut header = draggable(header);
+    if drag.is_some() {
+        let (enter, leave) = drop_messages(HostGridDropTarget::Ungrouped);
+        header = header.drop_target(enter, leave);
+    }
+    header.into()
 }
 
 /// Build a grid of host cards from a slice of host references
@@ -435,7 +719,9 @@ fn build_host_cards_grid(
     focus_index: Option<usize>,
     global_offset: usize,
     hovered_host: Option<Uuid>,
+    drag: Option<HostGridDrag>,
     live_counts: &HashMap<Uuid, usize>,
+    reachability: Option<&HashMap<Uuid, Reachability>>,
 ) -> Element<'static, Message> {
     let mut rows: Vec<Element<'static, Message>> = Vec::new();
     let mut current_row: Vec<Element<'static, Message>> = Vec::new();
@@ -445,9 +731,35 @@ fn build_host_cards_grid(
         let is_focused = focus_section == FocusSection::Content && focus_index == Some(global_idx);
         let is_hovered = hovered_host == Some(host.id);
         let live_count = live_counts.get(&host.id).copied().unwrap_or(0);
-        current_row.push(host_card(
-            host, theme, fonts, is_focused, is_hovered, live_count,
-        ));
+        let host_reachability =
+            reachability.map(|statuses| statuses.get(&host.id).copied().unwrap_or_default());
+        let target = HostGridDropTarget::Host(host.id);
+        let is_drop_target = drag.is_some_and(|drag| drag.target == Some(target));
+        let mut card = draggable(host_card(
+            host,
+            theme,
+            fonts,
+            is_focused,
+            is_hovered,
+            is_drop_target,
+            live_count,
+            host_reachability,
+        ))
+        .on_drag(Message::Ui(UiMessage::HostGridDragStarted(
+            HostGridDragItem::Host(host.id),
+        )))
+        .on_drop(Message::Ui(UiMessage::HostGridDropped));
+        // Hosts swap places with other hosts; groups never drop onto cards
+        if let Some(HostGridDrag {
+            item: HostGridDragItem::Host(dragged_id),
+            ..
+        }) = drag
+            && dragged_id != host.id
+        {
+            let (enter, leave) = drop_messages(target);
+            card = card.drop_target(enter, leave);
+        }
+        current_row.push(card.into());
 
         if current_row.len() >= column_count {
             rows.push(
@@ -468,41 +780,6 @@ fn build_host_cards_grid(
     Column::with_children(rows).spacing(GRID_SPACING).into()
 }
 
-/// Get the icon data for a detected OS
-pub fn os_icon_data(os: &Option<DetectedOs>) -> &'static [u8] {
-    match os {
-        // BSD family
-        Some(DetectedOs::FreeBSD) => icons::os::FREEBSD,
-        Some(DetectedOs::OpenBSD) => icons::os::OPENBSD,
-        Some(DetectedOs::NetBSD) => icons::os::NETBSD,
-        // macOS and Windows
-        Some(DetectedOs::MacOS) => icons::os::APPLE,
-        Some(DetectedOs::Windows) => icons::os::WINDOWS,
-        // Linux distributions
-        Some(DetectedOs::Ubuntu) => icons::os::UBUNTU,
-        Some(DetectedOs::Debian) => icons::os::DEBIAN,
-        Some(DetectedOs::Fedora) => icons::os::FEDORA,
-        Some(DetectedOs::Arch) => icons::os::ARCH,
-        Some(DetectedOs::CentOS) => icons::os::CENTOS,
-        Some(DetectedOs::RedHat) => icons::os::REDHAT,
-        Some(DetectedOs::OpenSUSE) => icons::os::OPENSUSE,
-        Some(DetectedOs::NixOS) => icons::os::NIXOS,
-        Some(DetectedOs::Manjaro) => icons::os::MANJARO,
-        Some(DetectedOs::Mint) => icons::os::MINT,
-        Some(DetectedOs::PopOS) => icons::os::POPOS,
-        Some(DetectedOs::Gentoo) => icons::os::GENTOO,
-        Some(DetectedOs::Alpine) => icons::os::ALPINE,
-        Some(DetectedOs::Kali) => icons::os::KALI,
-        Some(DetectedOs::Rocky) => icons::os::ROCKY,
-        Some(DetectedOs::Alma) => icons::os::ALMA,
-        // Generic Linux fallback
-        Some(DetectedOs::Linux) => icons::os::LINUX,
-        // Unknown
-        Some(DetectedOs::Unknown(_)) => icons::os::UNKNOWN,
-        None => icons::os::UNKNOWN,
-    }
-}
-
 /// Get the brand color for a detected OS
 fn os_icon_color(os: &Option<DetectedOs>) -> iced::Color {
     match os {
@@ -514,19 +791,25 @@ fn os_icon_color(os: &Option<DetectedOs>) -> iced::Color {
     }
 }
 
+/// Tags listed on a host card before the rest are summarized as "+N"
+const CARD_TAG_LIMIT: usize = 2;
+
 /// Single host card
+#[allow(clippy::too_many_arguments)]
 fn host_card(
     host: &HostCard,
     theme: Theme,
     fonts: ScaledFonts,
     is_focused: bool,
     is_hovered: bool,
+    is_drop_target: bool,
     live_count: usize,
+    reachability: Option<Reachability>,
 ) -> Element<'static, Message> {
     let host_id = host.id;
 
     // Get OS icon and color
-    let os_icon_bytes = os_icon_data(&host.detected_os);
+    let os_icon_bytes = icons::os::for_detected(host.detected_os.as_ref());
     let os_color = os_icon_color(&host.detected_os);
 
     // OS icon with vibrant solid background and white icon
@@ -548,7 +831,11 @@ fn host_card(
 
     // Name row: host name, then a Hub pill on routed hosts. Protocol chips
     // mark exceptions only (VNC) so color is reserved for what differs.
-    let mut name_row = Row::new().spacing(8).align_y(Alignment::Center).push(
+    let mut name_row = Row::new().spacing(8).align_y(Alignment::Center);
+    if let Some(reachability) = reachability {
+        name_row = name_row.push(reachability_dot(reachability, theme));
+    }
+    name_row = name_row.push(
         text(host.name.clone())
             .size(fonts.section)
             .color(theme.text_primary),
@@ -586,6 +873,22 @@ fn host_card(
         );
     }
 
+    // A few tags keep the chip filter discoverable without crowding the card
+    if !host.tags.is_empty() {
+        let mut tags = host
+            .tags
+            .iter()
+            .take(CARD_TAG_LIMIT)
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if host.tags.len() > CARD_TAG_LIMIT {
+            tags.push_str(&format!(" +{}", host.tags.len() - CARD_TAG_LIMIT));
+        }
+        detail_row = detail_row.push(text("·").size(fonts.label).color(theme.text_muted));
+        detail_row = detail_row.push(text(tags).size(fonts.label).color(theme.text_muted));
+    }
+
     let info = column![name_row, detail_row].spacing(4);
 
     let icon_button = |icon: &'static [u8], message: Message| {
@@ -670,7 +973,13 @@ fn host_card(
             (button::Status::Hovered, _) => (theme.hover, 0.25),
             _ => (card_bg, 0.15),
         };
-        let border = if is_focused {
+        let border = if is_drop_target {
+            iced::Border {
+                color: theme.accent,
+                width: 2.0,
+                radius: 12.0.into(),
+            }
+        } else if is_focused {
             iced::Border {
                 color: theme.focus_ring,
                 width: 2.0,
@@ -699,14 +1008,40 @@ fn host_card(
     .height(Length::Fixed(CARD_HEIGHT))
     .on_press(Message::Host(HostMessage::Connect(host_id)));
 
-    iced::widget::mouse_area(
+    let hover_area = iced::widget::mouse_area(
         container(card_button)
             .width(Length::FillPortion(1))
             .height(Length::Fixed(CARD_HEIGHT)),
     )
     .on_enter(Message::Ui(UiMessage::HostCardHovered(Some(host_id))))
-    .on_exit(Message::Ui(UiMessage::HostCardHovered(None)))
-    .into()
+    .on_exit(Message::Ui(UiMessage::HostCardHovered(None)));
+
+    mouse_area(hover_area)
+        .width(Length::FillPortion(1))
+        .on_right_press(move |x, y| Message::Host(HostMessage::ShowContextMenu(host_id, x, y)))
+        .into()
+}
+
+/// Small status dot: green when the host answered the last probe, red when
+/// it did not, muted while unknown.
+fn reachability_dot(reachability: Reachability, theme: Theme) -> Element<'static, Message> {
+    let color = match reachability {
+        Reachability::Reachable => STATUS_SUCCESS,
+        Reachability::Unreachable => STATUS_FAILURE,
+        Reachability::Unknown => theme.text_muted,
+    };
+    container(Space::new())
+        .width(8)
+        .height(8)
+        .style(move |_| container::Style {
+            background: Some(color.into()),
+            border: iced::Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
 }
 
 /// Prompt shown when Portal Hub is signed in but eligible hosts still
@@ -838,12 +1173,54 @@ fn empty_state(theme: Theme, fonts: ScaledFonts) -> Element<'static, Message> {
 
 #[cfg(test)]
 mod tests {
-    use super::calculate_columns;
+    use super::*;
     use crate::app::SidebarState;
 
+    fn group(name: &str, depth: usize, collapsed: bool) -> GroupCard {
+        GroupCard {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            collapsed,
+            depth,
+        }
+    }
+
     #[test]
     fn calculate_columns_handles_non_finite_width() {
         assert_eq!(calculate_columns(f32::NAN, SidebarState::Hidden), 1);
         assert_eq!(calculate_columns(f32::INFINITY, SidebarState::Hidden), 1);
     }
+
+    #[test]
+    fn collapsed_groups_hide_their_subgroups_hosts() {
+        let groups = [
+            group("Prod", 0, true),
+            group("Web", 1, false),
+            group("Staging", 0, false),
+        ];
+
+        let hidden = hidden_host_groups(&groups);
+
+        assert!(hidden.contains(&groups[0].id));
+        assert!(hidden.contains(&groups[1].id));
+        assert!(!hidden.contains(&groups[2].id));
+    }
+
+    #[test]
+    fn dragged_group_subtree_stops_at_the_next_sibling() {
+        let groups = [
+            group("Prod", 0, false),
+            group("Web", 1, false),
+            group("Cache", 2, false),
+            group("Db", 1, false),
+        ];
+        let drag = HostGridDrag {
+            item: HostGridDragItem::Group(groups[1].id),
+            target: None,
+        };
+
+        let subtree = dragged_group_subtree(&groups, Some(drag));
+
+        assert_eq!(subtree, HashSet::from([groups[1].id, groups[2].id]));
+    }
 }
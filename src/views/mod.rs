@@ -3,14 +3,17 @@ pub mod components;
 pub mod dialogs;
 pub mod file_viewer;
 pub mod history_view;
+pub mod host_context_menu;
 pub mod host_details_sheet;
 pub mod host_grid;
 pub mod proxy_sessions;
+pub mod quick_switcher;
 pub mod settings_page;
 pub mod sftp;
 pub mod sidebar;
 pub mod snippet_edit;
 pub mod snippet_grid;
+pub mod snippet_palette;
 pub mod snippet_results;
 pub mod tab_context_menu;
 pub mod tabs;
@@ -0,0 +1,391 @@
+//! Quick switcher overlay: fuzzy jump to a host, open session, or snippet
+
+use iced::widget::{Column, button, column, container, row, scrollable, text, text_input};
+use iced::{Alignment, Element, Fill, Length};
+
+use crate::config::{Host, HostsConfig, Protocol, SnippetsConfig};
+use crate::icons::{self, icon_with_color};
+use crate::message::{Message, SwitcherTarget, UiMessage};
+use crate::theme::{BORDER_RADIUS, CARD_BORDER_RADIUS, ScaledFonts, Theme};
+use crate::views::tabs::{Tab, TabType};
+
+/// Most recently used targets remembered for ranking
+pub const MAX_RECENT_TARGETS: usize = 20;
+
+/// Rows shown in the overlay
+const MAX_VISIBLE_ITEMS: usize = 10;
+
+pub fn quick_switcher_input_id() -> iced::widget::Id {
+    iced::widget::Id::new("quick_switcher_input")
+}
+
+#[derive(Debug, Clone)]
+pub struct SwitcherItem {
+    pub title: String,
+    pub subtitle: String,
+    pub target: SwitcherTarget,
+    pub icon: &'static [u8],
+}
+
+impl SwitcherItem {
+    fn kind_label(&self) -> &'static str {
+        match self.target {
+            SwitcherTarget::Session(_) => "Session",
+            SwitcherTarget::Host(_) => "Host",
+            SwitcherTarget::Snippet(_) => "Snippet",
+        }
+    }
+}
+
+/// Everything the switcher can jump to: open sessions, then hosts, then the
+/// snippets that apply to `snippet_host` (the active session's host)
+pub fn available_items(
+    tabs: &[Tab],
+    hosts: &HostsConfig,
+    snippets: &SnippetsConfig,
+    snippet_host: Option<&Host>,
+) -> Vec<SwitcherItem> {
+    let mut items: Vec<SwitcherItem> = tabs
+        .iter()
+        .filter_map(|tab| {
+            let (kind, icon) = match tab.tab_type {
+                TabType::NewConnection => return None,
+                TabType::Terminal => ("Terminal", icons::ui::TERMINAL),
+                TabType::Sftp => ("SFTP", icons::ui::HARD_DRIVE),
+                TabType::FileViewer => ("File viewer", icons::files::FILE_TEXT),
+                TabType::Vnc => ("VNC", icons::ui::SERVER),
+            };
+            Some(SwitcherItem {
+                title: tab.label().to_string(),
+                subtitle: format!("Switch to open {} tab", kind),
+                target: SwitcherTarget::Session(tab.id),
+                icon,
+            })
+        })
+        .collect();
+
+    items.extend(hosts.hosts.iter().map(|host| {
+        let protocol = match host.protocol {
+            Protocol::Ssh => "SSH",
+            Protocol::Vnc => "VNC",
+        };
+        SwitcherItem {
+            title: host.name.clone(),
+            subtitle: format!(
+                "{} {}@{}",
+                protocol,
+                host.effective_username(),
+                host.hostname
+            ),
+            target: SwitcherTarget::Host(host.id),
+            icon: icons::ui::ZAP,
+        }
+    }));
+
+    items.extend(
+        snippets
+            .snippets_for_host(snippet_host)
+            .map(|snippet| SwitcherItem {
+                title: snippet.name.clone(),
+                subtitle: snippet.command.clone(),
+                target: SwitcherTarget::Snippet(snippet.id),
+                icon: icons::ui::CODE,
+            }),
+    );
+
+    items
+}
+
+/// Score `candidate` as an in-order, case-insensitive subsequence match of
+/// `query`. Consecutive characters and word starts score higher; `None`
+/// means not every query character was found.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut matched = 0;
+    let mut previous_matched = false;
+    let mut previous_char: Option<char> = None;
+    for c in candidate.to_lowercase().chars() {
+        if matched < query.len() && c == query[matched] {
+            score += 1;
+            if previous_matched {
+                score += 3;
+            }
+            if previous_char.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 2;
+            }
+            matched += 1;
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+        previous_char = Some(c);
+    }
+
+    (matched == query.len()).then_some(score)
+}
+
+/// Items matching `query`, recently used ones first (most recent at the top),
+/// the rest ordered by match quality. `recent` is most recent first.
+pub fn filter_items(
+    items: &[SwitcherItem],
+    query: &str,
+    recent: &[SwitcherTarget],
+) -> Vec<SwitcherItem> {
+    let mut scored: Vec<(usize, u32, &SwitcherItem)> = items
+        .iter()
+        .filter_map(|item| {
+            let title = fuzzy_score(query, &item.title);
+            let subtitle = fuzzy_score(query, &item.subtitle).map(|score| score / 2);
+            let score = title.max(subtitle)?;
+            let recency = recent
+                .iter()
+                .position(|target| *target == item.target)
+                .unwrap_or(usize::MAX);
+            Some((recency, score, item))
+        })
+        .collect();
+
+    // Stable sort keeps the sessions/hosts/snippets order between equals.
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+    scored
+        .into_iter()
+        .map(|(_, _, item)| item.clone())
+        .collect()
+}
+
+pub fn first_matching_target(
+    items: &[SwitcherItem],
+    query: &str,
+    recent: &[SwitcherTarget],
+) -> Option<SwitcherTarget> {
+    filter_items(items, query, recent)
+        .first()
+        .map(|item| item.target)
+}
+
+pub fn quick_switcher_view(
+    query: &str,
+    items: &[SwitcherItem],
+    recent: &[SwitcherTarget],
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let filtered = filter_items(items, query, recent);
+
+    let input = text_input("Jump to a host, session, or snippet...", query)
+        .id(quick_switcher_input_id())
+        .on_input(|value| Message::Ui(UiMessage::QuickSwitcherChanged(value)))
+        .padding([12, 14])
+        .size(fonts.body)
+        .style(move |_theme, status| {
+            let border_color = match status {
+                text_input::Status::Focused { .. } => theme.accent,
+                _ => theme.border,
+            };
+            text_input::Style {
+                background: theme.background.into(),
+                border: iced::Border {
+                    color: border_color,
+                    width: 1.0,
+                    radius: BORDER_RADIUS.into(),
+                },
+                icon: theme.text_muted,
+                placeholder: theme.text_muted,
+                value: theme.text_primary,
+                selection: theme.selected,
+            }
+        });
+
+    let list: Element<'static, Message> = if filtered.is_empty() {
+        container(text("No matches").size(fonts.body).color(theme.text_muted))
+            .padding(20)
+            .width(Fill)
+            .align_x(Alignment::Center)
+            .into()
+    } else {
+        let rows: Vec<Element<'static, Message>> = filtered
+            .into_iter()
+            .take(MAX_VISIBLE_ITEMS)
+            .enumerate()
+            .map(|(index, item)| switcher_row(index == 0, item, theme, fonts))
+            .collect();
+        scrollable(Column::with_children(rows).spacing(4))
+            .height(Length::Fixed(420.0))
+            .into()
+    };
+
+    let panel = container(column![input, list].spacing(10))
+        .width(Length::Fixed(560.0))
+        .padding(12)
+        .style(move |_| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: CARD_BORDER_RADIUS.into(),
+            },
+            shadow: iced::Shadow {
+                color: iced::Color {
+                    a: 0.45,
+                    ..iced::Color::BLACK
+                },
+                offset: iced::Vector::new(0.0, 8.0),
+                blur_radius: 28.0,
+            },
+            ..Default::default()
+        });
+
+    button(
+        container(
+            container(panel)
+                .width(Fill)
+                .height(Fill)
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center)
+                .padding([48, 24]),
+        )
+        .width(Fill)
+        .height(Fill)
+        .style(move |_| container::Style {
+            background: Some(iced::Color::from_rgba(0.0, 0.0, 0.0, 0.55).into()),
+            ..Default::default()
+        }),
+    )
+    .padding(0)
+    .width(Fill)
+    .height(Fill)
+    .style(|_, _| button::Style {
+        background: None,
+        ..Default::default()
+    })
+    .on_press(Message::Ui(UiMessage::QuickSwitcherClose))
+    .into()
+}
+
+fn switcher_row(
+    selected: bool,
+    item: SwitcherItem,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let background = if selected {
+        theme.selected
+    } else {
+        theme.surface
+    };
+    let icon_color = if selected {
+        theme.text_primary
+    } else {
+        theme.text_secondary
+    };
+    let hint = if selected { "Enter" } else { item.kind_label() };
+
+    button(
+        row![
+            container(icon_with_color(item.icon, 17, icon_color))
+                .width(32)
+                .height(32)
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center)
+                .style(move |_| container::Style {
+                    background: Some(theme.background.into()),
+                    border: iced::Border {
+                        color: theme.border,
+                        width: 1.0,
+                        radius: 6.0.into(),
+                    },
+                    ..Default::default()
+                }),
+            column![
+                text(item.title.clone())
+                    .size(fonts.body)
+                    .color(theme.text_primary),
+                text(item.subtitle.clone())
+                    .size(fonts.label)
+                    .color(theme.text_muted)
+                    .wrapping(text::Wrapping::None),
+            ]
+            .spacing(2)
+            .width(Fill),
+            text(hint).size(fonts.label).color(theme.text_secondary),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center),
+    )
+    .padding([9, 10])
+    .width(Fill)
+    .style(move |_theme, status| {
+        let bg = match status {
+            button::Status::Hovered => theme.hover,
+            _ => background,
+        };
+        button::Style {
+            background: Some(bg.into()),
+            text_color: theme.text_primary,
+            border: iced::Border {
+                color: if selected {
+                    theme.accent
+                } else {
+                    iced::Color::TRANSPARENT
+                },
+                width: if selected { 1.0 } else { 0.0 },
+                radius: BORDER_RADIUS.into(),
+            },
+            ..Default::default()
+        }
+    })
+    .on_press(Message::Ui(UiMessage::QuickSwitcherRun(item.target)))
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn item(title: &str, target: SwitcherTarget) -> SwitcherItem {
+        SwitcherItem {
+            title: title.to_string(),
+            subtitle: String::new(),
+            target,
+            icon: icons::ui::ZAP,
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_requires_ordered_subsequence() {
+        assert!(fuzzy_score("prd", "prod-db").is_some());
+        assert!(fuzzy_score("PDB", "prod-db").is_some());
+        assert!(fuzzy_score("dbp", "prod-db").is_none());
+        assert!(fuzzy_score("prod", "prod-db").unwrap() > fuzzy_score("prod", "p-r-o-d").unwrap());
+    }
+
+    #[test]
+    fn recent_items_float_to_the_top() {
+        let web = SwitcherTarget::Host(Uuid::new_v4());
+        let db = SwitcherTarget::Host(Uuid::new_v4());
+        let deploy = SwitcherTarget::Snippet(Uuid::new_v4());
+        let items = vec![item("web", web), item("db", db), item("deploy", deploy)];
+
+        let titles = |query: &str, recent: &[SwitcherTarget]| -> Vec<String> {
+            filter_items(&items, query, recent)
+                .into_iter()
+                .map(|item| item.title)
+                .collect()
+        };
+
+        assert_eq!(titles("", &[]), vec!["web", "db", "deploy"]);
+        assert_eq!(titles("", &[deploy, db]), vec!["deploy", "db", "web"]);
+        assert_eq!(titles("d", &[db]), vec!["db", "deploy"]);
+        assert_eq!(first_matching_target(&items, "xyz", &[]), None);
+    }
+}
@@ -1,5 +1,7 @@
 //! Settings page view (full page, not dialog)
 
+use std::collections::HashMap;
+
 use iced::widget::{
     Column, Row, Space, button, column, container, mouse_area, row, scrollable, slider, text,
     text_input,
@@ -7,38 +9,61 @@ use iced::widget::{
 use iced::{Alignment, Element, Fill, Length, Padding};
 
 use crate::config::settings::{
-    PortalHubSettings, TERMINAL_SCROLL_SPEED_BASE, TERMINAL_SCROLL_SPEED_MAX,
-    TERMINAL_SCROLL_SPEED_MIN, VncEncodingPreference, VncQualityPreset, VncScalingMode,
+    LARGE_SCROLLBACK_LINES, MAX_CONNECT_TIMEOUT_SECS, MAX_CURSOR_BLINK_INTERVAL_MS,
+    MAX_SCROLLBACK_LINES, MIN_CURSOR_BLINK_INTERVAL_MS, MIN_SCROLLBACK_LINES, PortalHubSettings,
+    TERMINAL_SCROLL_SPEED_BASE, TERMINAL_SCROLL_SPEED_MAX, TERMINAL_SCROLL_SPEED_MIN, TerminalBell,
+    TerminalTextStyle, VncEncodingPreference, VncKeyboardMode, VncQualityPreset, VncScalingMode,
     VncSettings,
 };
 use crate::fonts::TerminalFont;
 use crate::hub::diagnostics::{DiagnosticStatus, PortalHubDiagnosticsReport};
 use crate::hub::sync::PortalHubSyncService;
 use crate::icons::{self, icon_with_color};
+use crate::keybindings::{AppAction, Keybinding, KeybindingsConfig};
 use crate::message::{Message, SettingsTab, UiMessage};
 use crate::proxy::ProxyStatus;
-use crate::theme::{BORDER_RADIUS, ScaledFonts, Theme, ThemeId, get_theme};
+use crate::theme::{
+    BORDER_RADIUS, CustomTheme, STATUS_PARTIAL, ScaledFonts, Theme, ThemeColorField, ThemeId,
+    color_to_hex, get_theme, parse_hex_color,
+};
 use crate::views::components::{
     BadgeTone, field, form_card, help_tooltip, status_badge, toggle_group,
 };
+use crate::views::dialogs::common::{dialog_input_style, dialog_input_style_with_error};
 
 pub struct SettingsPageContext {
     pub current_theme: ThemeId,
     pub active_tab: SettingsTab,
     pub terminal_font_size: f32,
     pub terminal_scroll_speed: f32,
+    pub scrollback_lines: usize,
     pub terminal_font: TerminalFont,
+    pub terminal_text_style: TerminalTextStyle,
+    pub cursor_blink: bool,
+    pub cursor_blink_interval_ms: u64,
+    pub terminal_bell: TerminalBell,
+    pub terminal_clipboard: TerminalClipboardAccess,
     pub vnc_settings: VncSettings,
     pub auto_reconnect: bool,
+    pub confirm_quit: bool,
+    pub restore_sessions: bool,
     pub reconnect_max_attempts: u32,
     pub reconnect_base_delay_ms: u64,
     pub reconnect_max_delay_ms: u64,
+    pub connect_timeout: u64,
+    pub keepalive_interval: u64,
+    pub keepalive_count_max: u32,
     pub allow_agent_forwarding: bool,
+    pub host_reachability: bool,
+    pub sftp_verify_transfers: bool,
+    pub sftp_resume_transfers: bool,
     pub snippet_history_enabled: bool,
     pub snippet_store_command: bool,
     pub snippet_store_output: bool,
     pub snippet_redact_output: bool,
     pub session_logging_enabled: bool,
+    pub session_log_input: bool,
+    pub history_redact_export: bool,
     pub portal_hub: PortalHubSettings,
     pub portal_hub_status: Option<ProxyStatus>,
     pub portal_hub_status_error: Option<String>,
@@ -65,6 +90,12 @@ pub struct SettingsPageContext {
     pub system_ui_scale: f32,
     /// Whether user has overridden the UI scale
     pub has_ui_scale_override: bool,
+    pub custom_themes: Vec<CustomTheme>,
+    /// Hex text typed into the custom theme editor
+    pub theme_color_inputs: HashMap<ThemeColorField, String>,
+    pub keybindings: KeybindingsConfig,
+    /// Action whose shortcut is being recorded
+    pub keybinding_capture: Option<AppAction>,
 }
 
 /// Build the settings page view
@@ -147,6 +178,11 @@ fn settings_tabs(
             label: "Snippets",
             icon: icons::ui::CODE,
         },
+        SettingsTabItem {
+            tab: SettingsTab::Keybindings,
+            label: "Shortcuts",
+            icon: icons::ui::ZAP,
+        },
     ];
 
     let controls = Row::from_vec(
@@ -251,10 +287,11 @@ fn active_tab_description(tab: SettingsTab) -> &'static str {
     match tab {
         SettingsTab::UiUx => "Theme, scale, and interface presentation.",
         SettingsTab::Terminal => "Terminal font and scroll behavior.",
-        SettingsTab::Connections => "SSH reconnect behavior and VNC defaults.",
+        SettingsTab::Connections => "SSH reconnect behavior, SFTP transfers, and VNC defaults.",
         SettingsTab::PortalHub => "Persistent SSH sessions through Portal Hub.",
         SettingsTab::SecurityLogs => "Credential caching, session logs, and audit logs.",
         SettingsTab::Snippets => "Snippet execution history and stored output.",
+        SettingsTab::Keybindings => "Keyboard shortcuts for app and terminal actions.",
     }
 }
 
@@ -268,8 +305,17 @@ fn active_tab_sections(
             "UI & UX",
             theme,
             fonts,
-            vec![
-                theme_tiles_row(context.current_theme, theme, fonts),
+            [theme_tiles_row(
+                context.current_theme,
+                &context.custom_themes,
+                theme,
+                fonts,
+            )]
+            .into_iter()
+            .chain(selected_custom_theme(context).map(|custom| {
+                custom_theme_editor(custom, &context.theme_color_inputs, theme, fonts)
+            }))
+            .chain([
                 ui_scale_setting(
                     context.ui_scale,
                     context.system_ui_scale,
@@ -277,7 +323,24 @@ fn active_tab_sections(
                     theme,
                     fonts,
                 ),
-            ],
+                toggle_setting(
+                    "Confirm before quitting",
+                    "Ask before closing Portal while sessions or transfers are running",
+                    context.confirm_quit,
+                    |value| Message::Ui(UiMessage::ConfirmQuitEnabled(value)),
+                    theme,
+                    fonts,
+                ),
+                toggle_setting(
+                    "Restore sessions on startup",
+                    "Remember open hosts and SFTP folders and offer to reopen them",
+                    context.restore_sessions,
+                    |value| Message::Ui(UiMessage::RestoreSessionsEnabled(value)),
+                    theme,
+                    fonts,
+                ),
+            ])
+            .collect(),
         )],
         SettingsTab::Terminal => vec![settings_section(
             "Terminal",
@@ -287,6 +350,58 @@ fn active_tab_sections(
                 font_selector_setting(context.terminal_font, theme, fonts),
                 font_size_setting(context.terminal_font_size, theme, fonts),
                 terminal_scroll_speed_setting(context.terminal_scroll_speed, theme, fonts),
+                scrollback_lines_setting(context.scrollback_lines, theme, fonts),
+                toggle_setting(
+                    "Blinking cursor",
+                    "Blink the cursor unless a program asks for a steady one",
+                    context.cursor_blink,
+                    |value| Message::Ui(UiMessage::CursorBlinkEnabled(value)),
+                    theme,
+                    fonts,
+                ),
+                cursor_blink_interval_setting(context.cursor_blink_interval_ms, theme, fonts),
+                toggle_setting(
+                    "Font ligatures",
+                    "Join sequences like => and != into single glyphs when the font has them",
+                    context.terminal_text_style.ligatures,
+                    |value| Message::Ui(UiMessage::TerminalLigaturesEnabled(value)),
+                    theme,
+                    fonts,
+                ),
+                toggle_setting(
+                    "Bold text in bright colors",
+                    "Draw bold text in the bright variant of the eight basic colors",
+                    context.terminal_text_style.bold_is_bright,
+                    |value| Message::Ui(UiMessage::TerminalBoldIsBrightEnabled(value)),
+                    theme,
+                    fonts,
+                ),
+                choice_setting(
+                    "Bell",
+                    "What happens when a program rings the terminal bell",
+                    context.terminal_bell,
+                    &[
+                        (TerminalBell::Visual, "Visual"),
+                        (TerminalBell::Notification, "Notification"),
+                        (TerminalBell::Off, "Off"),
+                    ],
+                    |value| Message::Ui(UiMessage::TerminalBellChanged(value)),
+                    theme,
+                    fonts,
+                ),
+                choice_setting(
+                    "Clipboard access",
+                    "Let programs like tmux and vim set or read the clipboard (OSC 52)",
+                    context.terminal_clipboard,
+                    &[
+                        (TerminalClipboardAccess::Off, "Off"),
+                        (TerminalClipboardAccess::Copy, "Copy"),
+                        (TerminalClipboardAccess::CopyPaste, "Copy and paste"),
+                    ],
+                    |value| Message::Ui(UiMessage::TerminalClipboardAccessChanged(value)),
+                    theme,
+                    fonts,
+                ),
             ],
         )],
         SettingsTab::Connections => vec![
@@ -311,6 +426,14 @@ fn active_tab_sections(
                         theme,
                         fonts,
                     ),
+                    toggle_setting(
+                        "Host status checks",
+                        "Probe the hosts on the host grid to show whether they are up",
+                        context.host_reachability,
+                        |value| Message::Ui(UiMessage::HostReachabilityEnabled(value)),
+                        theme,
+                        fonts,
+                    ),
                     reconnect_attempts_setting(context.reconnect_max_attempts, theme, fonts),
                     reconnect_delay_setting(
                         "Initial reconnect delay",
@@ -336,6 +459,32 @@ fn active_tab_sections(
                         theme,
                         fonts,
                     ),
+                    connect_timeout_setting(context.connect_timeout, theme, fonts),
+                    keepalive_interval_setting(context.keepalive_interval, theme, fonts),
+                    keepalive_count_setting(context.keepalive_count_max, theme, fonts),
+                ],
+            ),
+            settings_section(
+                "SFTP",
+                theme,
+                fonts,
+                vec![
+                    toggle_setting(
+                        "Verify transfers",
+                        "Compare SHA-256 checksums after copying; slower for large files",
+                        context.sftp_verify_transfers,
+                        |value| Message::Ui(UiMessage::SftpVerifyTransfersEnabled(value)),
+                        theme,
+                        fonts,
+                    ),
+                    toggle_setting(
+                        "Resume interrupted transfers",
+                        "Keep partial files when a transfer fails and continue from them; retried automatically with auto reconnect",
+                        context.sftp_resume_transfers,
+                        |value| Message::Ui(UiMessage::SftpResumeTransfersEnabled(value)),
+                        theme,
+                        fonts,
+                    ),
                 ],
             ),
             settings_section(
@@ -352,6 +501,14 @@ fn active_tab_sections(
             fonts,
             vec![
                 credential_timeout_setting(context.credential_timeout, theme, fonts),
+                field(
+                    "Forget credentials",
+                    "Clear cached key passphrases and sudo passwords now",
+                    small_settings_button("Forget now", theme, fonts)
+                        .on_press(Message::Ui(UiMessage::ForgetCredentials)),
+                    theme,
+                    fonts,
+                ),
                 toggle_setting(
                     "Session logging",
                     "Save terminal output to a log file per session",
@@ -360,6 +517,22 @@ fn active_tab_sections(
                     theme,
                     fonts,
                 ),
+                toggle_setting(
+                    "Log typed input",
+                    "Also record commands you type; input at password prompts is left out",
+                    context.session_log_input,
+                    |value| Message::Ui(UiMessage::SessionLogInput(value)),
+                    theme,
+                    fonts,
+                ),
+                toggle_setting(
+                    "Redact history exports",
+                    "Leave addresses, usernames and errors out of exported connection history",
+                    context.history_redact_export,
+                    |value| Message::Ui(UiMessage::HistoryRedactExport(value)),
+                    theme,
+                    fonts,
+                ),
                 toggle_setting(
                     "Security audit logging",
                     "Write security events to an on-disk audit log",
@@ -377,6 +550,29 @@ fn active_tab_sections(
                 ),
             ],
         )],
+        SettingsTab::Keybindings => vec![settings_section(
+            "Keyboard Shortcuts",
+            theme,
+            fonts,
+            std::iter::once(field(
+                "Reset all shortcuts",
+                "Restore the default shortcut of every action",
+                small_settings_button("Reset all", theme, fonts)
+                    .on_press(Message::Ui(UiMessage::KeybindingsResetAll)),
+                theme,
+                fonts,
+            ))
+            .chain(AppAction::ALL.iter().map(|&action| {
+                keybinding_setting(
+                    action,
+                    &context.keybindings,
+                    context.keybinding_capture == Some(action),
+                    theme,
+                    fonts,
+                )
+            }))
+            .collect(),
+        )],
         SettingsTab::Snippets => vec![settings_section(
             "Snippet History",
             theme,
@@ -434,16 +630,52 @@ fn settings_section<'a>(
     section.push(form_card(items, theme)).into()
 }
 
-/// Theme selector with visual tile previews
+/// Theme selector with visual tile previews, custom themes after the
+/// built-in ones
 fn theme_tiles_row(
     current: ThemeId,
+    custom_themes: &[CustomTheme],
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'static, Message> {
-    let tiles: Vec<Element<'static, Message>> = ThemeId::all()
+    let mut tiles: Vec<Element<'static, Message>> = ThemeId::all()
         .iter()
-        .map(|&theme_id| theme_tile(theme_id, theme_id == current, theme, fonts))
+        .map(|&theme_id| {
+            theme_tile(
+                theme_id,
+                built_in_short_name(theme_id).to_string(),
+                get_theme(theme_id),
+                theme_id == current,
+                theme,
+                fonts,
+            )
+        })
         .collect();
+    tiles.extend(custom_themes.iter().map(|custom| {
+        let theme_id = ThemeId::Custom(custom.id);
+        theme_tile(
+            theme_id,
+            custom.name.clone(),
+            custom.colors,
+            theme_id == current,
+            theme,
+            fonts,
+        )
+    }));
+    tiles.push(action_tile(
+        "+",
+        "New theme",
+        UiMessage::CustomThemeCreate,
+        theme,
+        fonts,
+    ));
+    tiles.push(action_tile(
+        "…",
+        "Import",
+        UiMessage::CustomThemeImportRequested,
+        theme,
+        fonts,
+    ));
 
     let tiles_row = Row::from_vec(tiles).spacing(12);
 
@@ -457,28 +689,31 @@ fn theme_tiles_row(
     column![scrollable_tiles,].spacing(0).into()
 }
 
+/// Short theme name for a built-in tile
+fn built_in_short_name(theme_id: ThemeId) -> &'static str {
+    match theme_id {
+        ThemeId::PortalDefault => "Default",
+        ThemeId::CatppuccinLatte => "Latte",
+        ThemeId::CatppuccinFrappe => "Frappé",
+        ThemeId::CatppuccinMacchiato => "Macchiato",
+        ThemeId::CatppuccinMocha => "Mocha",
+        ThemeId::Noctalia => "Noctalia",
+        ThemeId::Custom(_) => "Custom",
+    }
+}
+
 /// Individual theme tile with mini app preview
 fn theme_tile(
     tile_theme_id: ThemeId,
+    short_name: String,
+    preview_theme: Theme,
     is_selected: bool,
     current_theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'static, Message> {
-    let preview_theme = get_theme(tile_theme_id);
-
     // Mini app preview
     let preview = mini_app_preview(preview_theme);
 
-    // Short theme name for the tile
-    let short_name = match tile_theme_id {
-        ThemeId::PortalDefault => "Default",
-        ThemeId::CatppuccinLatte => "Latte",
-        ThemeId::CatppuccinFrappe => "Frappé",
-        ThemeId::CatppuccinMacchiato => "Macchiato",
-        ThemeId::CatppuccinMocha => "Mocha",
-        ThemeId::Noctalia => "Noctalia",
-    };
-
     let name = text(short_name).size(fonts.small).color(if is_selected {
         current_theme.accent
     } else {
@@ -513,6 +748,227 @@ fn theme_tile(
         .into()
 }
 
+/// Tile shaped like a theme tile that adds a custom theme: a copy of the
+/// current one, or an imported terminal color scheme
+fn action_tile(
+    symbol: &'static str,
+    label: &'static str,
+    message: UiMessage,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let symbol = container(text(symbol).size(fonts.heading).color(theme.text_muted))
+        .width(80)
+        .height(48)
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center)
+        .style(move |_| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                radius: 4.0.into(),
+                width: 1.0,
+                color: theme.border,
+            },
+            ..Default::default()
+        });
+
+    let tile_content = column![
+        symbol,
+        Space::new().height(6),
+        text(label).size(fonts.small).color(theme.text_secondary),
+    ]
+    .align_x(Alignment::Center)
+    .spacing(0);
+
+    let tile_container = container(tile_content)
+        .padding(6)
+        .style(move |_| container::Style {
+            border: iced::Border {
+                radius: BORDER_RADIUS.into(),
+                width: 1.0,
+                color: theme.border,
+            },
+            ..Default::default()
+        });
+
+    mouse_area(tile_container)
+        .on_press(Message::Ui(message))
+        .into()
+}
+
+/// The selected theme when it is a custom one
+fn selected_custom_theme(context: &SettingsPageContext) -> Option<&CustomTheme> {
+    let ThemeId::Custom(id) = context.current_theme else {
+        return None;
+    };
+    context.custom_themes.iter().find(|custom| custom.id == id)
+}
+
+/// Name and colors of the selected custom theme. It is the active theme, so
+/// the whole app previews every edit.
+fn custom_theme_editor(
+    custom: &CustomTheme,
+    inputs: &HashMap<ThemeColorField, String>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let name_input = text_input("Theme name", &custom.name)
+        .on_input(|name| Message::Ui(UiMessage::CustomThemeRename(name)))
+        .size(fonts.label)
+        .padding(Padding::from([5, 8]))
+        .width(Length::Fixed(220.0))
+        .style(dialog_input_style(theme));
+
+    let header = row![
+        text("Theme name")
+            .size(fonts.body)
+            .color(theme.text_primary),
+        Space::new().width(Fill),
+        name_input,
+        small_settings_button("Delete", theme, fonts)
+            .on_press(Message::Ui(UiMessage::CustomThemeDelete)),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let ansi_fields: Vec<ThemeColorField> = (0..16).map(ThemeColorField::Ansi).collect();
+    let group = |title: &'static str, fields: &[ThemeColorField]| {
+        let rows: Vec<Element<'static, Message>> = fields
+            .chunks(3)
+            .map(|chunk| {
+                Row::from_vec(
+                    chunk
+                        .iter()
+                        .map(|&field| {
+                            theme_color_input(field, &custom.colors, inputs, theme, fonts)
+                        })
+                        .collect(),
+                )
+                .spacing(16)
+                .into()
+            })
+            .collect();
+        column![
+            text(title).size(fonts.label).color(theme.text_muted),
+            Column::from_vec(rows).spacing(8),
+        ]
+        .spacing(8)
+    };
+
+    column![
+        header,
+        group("Interface", ThemeColorField::UI),
+        group("Terminal", ThemeColorField::TERMINAL),
+        terminal_colors_preview(custom.colors, fonts),
+        group("ANSI colors", &ansi_fields),
+    ]
+    .spacing(16)
+    .padding(Padding::from([12, 0]))
+    .into()
+}
+
+/// Swatch, label and hex input for one color of a custom theme
+fn theme_color_input(
+    field: ThemeColorField,
+    colors: &Theme,
+    inputs: &HashMap<ThemeColorField, String>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let color = field.get(colors);
+    let value = inputs
+        .get(&field)
+        .cloned()
+        .unwrap_or_else(|| color_to_hex(color));
+    let valid = parse_hex_color(&value).is_some();
+
+    let swatch = container(Space::new().width(18).height(18)).style(move |_| container::Style {
+        background: Some(color.into()),
+        border: iced::Border {
+            radius: 4.0.into(),
+            width: 1.0,
+            color: theme.border,
+        },
+        ..Default::default()
+    });
+
+    row![
+        swatch,
+        text(field.label())
+            .size(fonts.label)
+            .color(theme.text_secondary)
+            .width(Length::Fixed(110.0)),
+        text_input("#RRGGBB", &value)
+            .on_input(move |value| Message::Ui(UiMessage::CustomThemeColorChanged(field, value)))
+            .size(fonts.label)
+            .padding(Padding::from([4, 6]))
+            .width(Length::Fixed(84.0))
+            .style(dialog_input_style_with_error(theme, !valid)),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// A prompt and colored listing drawn with the theme's terminal colors
+fn terminal_colors_preview(colors: Theme, fonts: ScaledFonts) -> Element<'static, Message> {
+    let terminal = colors.terminal;
+    let listing: Vec<Element<'static, Message>> =
+        ["docs", "build.sh", "notes.md", "backup.tar", "logs", "ssh"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                text(name)
+                    .font(iced::Font::MONOSPACE)
+                    .size(fonts.label)
+                    .color(terminal.ansi[index + 1])
+                    .into()
+            })
+            .collect();
+    let palette: Vec<Element<'static, Message>> = (0..16)
+        .map(|index| {
+            container(Space::new().width(16).height(10))
+                .style(move |_| container::Style {
+                    background: Some(terminal.ansi[index].into()),
+                    ..Default::default()
+                })
+                .into()
+        })
+        .collect();
+
+    container(
+        column![
+            row![
+                text("user@host:~$ ls ")
+                    .font(iced::Font::MONOSPACE)
+                    .size(fonts.label)
+                    .color(terminal.foreground),
+                container(Space::new().width(7).height(fonts.label)).style(move |_| {
+                    container::Style {
+                        background: Some(terminal.cursor.into()),
+                        ..Default::default()
+                    }
+                }),
+            ],
+            Row::from_vec(listing).spacing(12),
+            Row::from_vec(palette).spacing(2),
+        ]
+        .spacing(6),
+    )
+    .padding(10)
+    .width(Fill)
+    .style(move |_| container::Style {
+        background: Some(terminal.background.into()),
+        border: iced::Border {
+            radius: 6.0.into(),
+            width: 1.0,
+            color: colors.border,
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
 /// Mini app preview showing sidebar, main area, and accent elements
 fn mini_app_preview(preview_theme: Theme) -> Element<'static, Message> {
     // Sidebar strip
@@ -748,7 +1204,101 @@ fn terminal_scroll_speed_setting(
     .into()
 }
 
+fn scrollback_lines_setting(
+    current_lines: usize,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let current_lines = current_lines.clamp(MIN_SCROLLBACK_LINES, MAX_SCROLLBACK_LINES);
+
+    let label = text("Scrollback")
+        .size(fonts.body)
+        .color(theme.text_primary);
+
+    let (description_text, description_color) = if current_lines > LARGE_SCROLLBACK_LINES {
+        (
+            "Large scrollback uses a lot of memory per open terminal",
+            STATUS_PARTIAL,
+        )
+    } else {
+        ("Lines of history kept for new terminals", theme.text_muted)
+    };
+    let description = text(description_text)
+        .size(fonts.label)
+        .color(description_color);
+
+    let slider_widget = slider(
+        MIN_SCROLLBACK_LINES as f32..=MAX_SCROLLBACK_LINES as f32,
+        current_lines as f32,
+        |v| {
+            let rounded = ((v / 1000.0).round() * 1000.0) as usize;
+            Message::Ui(UiMessage::ScrollbackLinesChange(rounded))
+        },
+    )
+    .step(1000.0)
+    .width(140);
+
+    let value_text = text(format!("{}k", current_lines / 1000))
+        .size(fonts.body)
+        .color(theme.text_secondary);
+
+    column![
+        row![
+            label,
+            Space::new().width(Length::Fill),
+            slider_widget,
+            Space::new().width(12),
+            value_text,
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(4),
+        description,
+    ]
+    .spacing(0)
+    .into()
+}
+
 /// UI scale slider setting
+fn cursor_blink_interval_setting(
+    current_ms: u64,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let label = text("Cursor blink interval")
+        .size(fonts.body)
+        .color(theme.text_primary);
+    let description = text("How long the cursor stays shown or hidden")
+        .size(fonts.label)
+        .color(theme.text_muted);
+    let current = current_ms.clamp(MIN_CURSOR_BLINK_INTERVAL_MS, MAX_CURSOR_BLINK_INTERVAL_MS);
+    let slider_widget = slider(
+        MIN_CURSOR_BLINK_INTERVAL_MS as f32..=MAX_CURSOR_BLINK_INTERVAL_MS as f32,
+        current as f32,
+        |value| Message::Ui(UiMessage::CursorBlinkIntervalChanged(value.round() as u64)),
+    )
+    .step(50.0)
+    .width(160);
+    let value_text = text(format_duration_ms(current))
+        .size(fonts.body)
+        .color(theme.text_secondary)
+        .width(Length::Fixed(56.0));
+
+    column![
+        row![
+            label,
+            Space::new().width(Length::Fill),
+            slider_widget,
+            Space::new().width(12),
+            value_text,
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(4),
+        description,
+    ]
+    .spacing(0)
+    .into()
+}
+
 fn ui_scale_setting(
     current_scale: f32,
     system_scale: f32,
@@ -852,6 +1402,80 @@ where
     field(label, description, control, theme, fonts)
 }
 
+/// An action with its shortcut, a record button and a reset button
+fn keybinding_setting(
+    action: AppAction,
+    keybindings: &KeybindingsConfig,
+    recording: bool,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let join = |bindings: &[Keybinding]| {
+        if bindings.is_empty() {
+            "Not set".to_string()
+        } else {
+            bindings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        }
+    };
+    let conflicts = keybindings.conflicts(action);
+    let description = if conflicts.is_empty() {
+        format!(
+            "Default: {}",
+            join(KeybindingsConfig::default().bindings(action))
+        )
+    } else {
+        let names: Vec<&str> = conflicts.iter().map(|other| other.label()).collect();
+        format!("Also bound to {}", names.join(", "))
+    };
+
+    let (current, current_color) = if recording {
+        ("Press a shortcut…".to_string(), theme.accent)
+    } else {
+        (join(keybindings.bindings(action)), theme.text_primary)
+    };
+    let shortcut = container(text(current).size(fonts.label).color(current_color))
+        .padding([5, 10])
+        .width(Length::Fixed(170.0))
+        .align_x(Alignment::Center)
+        .style(move |_| container::Style {
+            background: Some(theme.background.into()),
+            border: iced::Border {
+                radius: 6.0.into(),
+                width: 1.0,
+                color: if recording {
+                    theme.accent
+                } else {
+                    theme.border
+                },
+            },
+            ..Default::default()
+        });
+
+    let record = if recording {
+        small_settings_button("Cancel", theme, fonts)
+            .on_press(Message::Ui(UiMessage::KeybindingRecordCancel))
+    } else {
+        small_settings_button("Record", theme, fonts)
+            .on_press(Message::Ui(UiMessage::KeybindingRecordStart(action)))
+    };
+    let reset = small_settings_button("Reset", theme, fonts).on_press_maybe(
+        (!keybindings.is_default(action))
+            .then_some(Message::Ui(UiMessage::KeybindingReset(action))),
+    );
+
+    let mut control = Row::new().spacing(8).align_y(Alignment::Center);
+    if !conflicts.is_empty() {
+        control = control.push(status_badge("Conflict", BadgeTone::Warning, theme, fonts));
+    }
+    let control = control.push(shortcut).push(record).push(reset);
+
+    field(action.label(), description, control, theme, fonts)
+}
+
 fn read_only_setting(
     label: &'static str,
     description: &'static str,
@@ -1646,6 +2270,124 @@ fn reconnect_attempts_setting(
     .into()
 }
 
+fn connect_timeout_setting(
+    current_secs: u64,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let label = text("Connection timeout")
+        .size(fonts.body)
+        .color(theme.text_primary);
+    let description = text("Seconds to wait for a host to answer before giving up")
+        .size(fonts.label)
+        .color(theme.text_muted);
+    let current = current_secs.clamp(1, MAX_CONNECT_TIMEOUT_SECS);
+    let slider_widget = slider(
+        1.0..=MAX_CONNECT_TIMEOUT_SECS as f32,
+        current as f32,
+        |value| Message::Ui(UiMessage::ConnectTimeoutChanged(value.round() as u64)),
+    )
+    .step(1.0)
+    .width(160);
+    let value_text = text(format!("{}s", current))
+        .size(fonts.body)
+        .color(theme.text_secondary);
+
+    column![
+        row![
+            label,
+            Space::new().width(Length::Fill),
+            slider_widget,
+            Space::new().width(12),
+            value_text,
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(4),
+        description,
+    ]
+    .spacing(0)
+    .into()
+}
+
+fn keepalive_interval_setting(
+    current_secs: u64,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let label = text("Keepalive interval")
+        .size(fonts.body)
+        .color(theme.text_primary);
+    let description = text("Seconds between SSH keepalive probes (0 disables)")
+        .size(fonts.label)
+        .color(theme.text_muted);
+    let current = current_secs.min(300) as f32;
+    let slider_widget = slider(0.0..=300.0, current, |value| {
+        Message::Ui(UiMessage::KeepaliveIntervalChanged(value.round() as u64))
+    })
+    .step(5.0)
+    .width(160);
+    let value_text = if current_secs == 0 {
+        "Off".to_string()
+    } else {
+        format!("{}s", current_secs)
+    };
+    let value_text = text(value_text)
+        .size(fonts.body)
+        .color(theme.text_secondary);
+
+    column![
+        row![
+            label,
+            Space::new().width(Length::Fill),
+            slider_widget,
+            Space::new().width(12),
+            value_text,
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(4),
+        description,
+    ]
+    .spacing(0)
+    .into()
+}
+
+fn keepalive_count_setting(
+    current_count: u32,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let label = text("Missed keepalives")
+        .size(fonts.body)
+        .color(theme.text_primary);
+    let description = text("Unanswered probes before the connection is treated as lost")
+        .size(fonts.label)
+        .color(theme.text_muted);
+    let current = current_count.clamp(1, 10) as f32;
+    let slider_widget = slider(1.0..=10.0, current, |value| {
+        Message::Ui(UiMessage::KeepaliveCountMaxChanged(value.round() as u32))
+    })
+    .step(1.0)
+    .width(160);
+    let value_text = text(current_count.clamp(1, 10).to_string())
+        .size(fonts.body)
+        .color(theme.text_secondary);
+
+    column![
+        row![
+            label,
+            Space::new().width(Length::Fill),
+            slider_widget,
+            Space::new().width(12),
+            value_text,
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(4),
+        description,
+    ]
+    .spacing(0)
+    .into()
+}
+
 #[derive(Debug, Clone, Copy)]
 struct ReconnectDelayBounds {
     min_ms: u64,
@@ -1711,7 +2453,7 @@ fn vnc_settings_items(
     vec![
         choice_setting(
             "Quality preset",
-            "Preset used when new VNC sessions start",
+            "Encodings and JPEG quality requested from the server",
             settings.quality_preset,
             &[
                 (VncQualityPreset::Auto, "Auto"),
@@ -1778,6 +2520,26 @@ fn vnc_settings_items(
             theme,
             fonts,
         ),
+        toggle_setting(
+            "Send clipboard automatically",
+            "Share text copied locally without pressing Ctrl+Shift+V",
+            settings.clipboard_auto_send,
+            |value| Message::Ui(UiMessage::VncClipboardAutoSendChanged(value)),
+            theme,
+            fonts,
+        ),
+        choice_setting(
+            "Keyboard mode",
+            "Unicode sends typed characters, for non-US layouts",
+            settings.keyboard_mode,
+            &[
+                (VncKeyboardMode::Standard, "US layout"),
+                (VncKeyboardMode::Unicode, "Unicode"),
+            ],
+            |value| Message::Ui(UiMessage::VncKeyboardModeChanged(value)),
+            theme,
+            fonts,
+        ),
         toggle_setting(
             "View-only default",
             "Start VNC sessions without sending input",
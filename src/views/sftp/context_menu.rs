@@ -12,13 +12,20 @@ use crate::theme::{ScaledFonts, Theme};
 use crate::widgets::mouse_area;
 
 use super::state::DualPaneSftpState;
-use super::types::ContextMenuAction;
+use super::types::{ContextMenuAction, PaneSource};
 
 /// Red color for destructive actions
 const DESTRUCTIVE_COLOR: Color = Color::from_rgb(0.86, 0.24, 0.24);
 const CONTEXT_MENU_WIDTH: f32 = 240.0;
-/// Estimated max menu height for bounds checking (7 items max * ~28px + padding)
-const ESTIMATED_MENU_HEIGHT: f32 = 220.0;
+
+#[cfg(target_os = "macos")]
+const REVEAL_LABEL: &str = "Reveal in Finder";
+#[cfg(target_os = "windows")]
+const REVEAL_LABEL: &str = "Show in Explorer";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const REVEAL_LABEL: &str = "Show in Files";
+/// Estimated max menu height for bounds checking (8 items max * ~28px + padding)
+const ESTIMATED_MENU_HEIGHT: f32 = 340.0;
 
 /// Build a context menu item button
 fn context_menu_item<'a>(
@@ -94,6 +101,31 @@ fn can_copy_selection(entries: &[&FileEntry]) -> bool {
     !entries.is_empty() && !selection_has_parent(entries) && !selection_has_symlink(entries)
 }
 
+/// Folders whose size can be calculated: real directories only, since
+/// symlinks are never followed
+fn can_calculate_size_selection(entries: &[&FileEntry]) -> bool {
+    !entries.is_empty()
+        && entries
+            .iter()
+            .all(|entry| entry.is_dir && !entry.is_symlink && !entry.is_parent())
+}
+
+/// Symlink whose target can be copied: a single link that could be read
+fn link_target_selection<'a>(entries: &[&'a FileEntry]) -> Option<&'a str> {
+    match entries {
+        [entry] if entry.is_symlink => entry
+            .symlink_target
+            .as_ref()
+            .map(|target| target.target.as_str()),
+        _ => None,
+    }
+}
+
+/// Single entry whose location can be revealed or copied
+fn is_single_entry_selection(entries: &[&FileEntry]) -> bool {
+    entries.len() == 1 && !selection_has_parent(entries)
+}
+
 fn can_edit_permissions_selection(entries: &[&FileEntry]) -> bool {
     entries.len() == 1 && !selection_has_parent(entries) && !selection_has_symlink(entries)
 }
@@ -101,6 +133,7 @@ fn can_edit_permissions_selection(entries: &[&FileEntry]) -> bool {
 /// Build the context menu overlay
 pub fn context_menu_view(
     state: &DualPaneSftpState,
+    watching: bool,
     theme: Theme,
     fonts: ScaledFonts,
     window_size: iced::Size,
@@ -121,7 +154,7 @@ pub fn context_menu_view(
     // Order matches screenshot: Copy to target, Rename, Delete, divider, Refresh, New Folder, Edit Permissions
     let mut items: Vec<Element<'_, Message>> = vec![];
 
-    // Open (only for single file selection)
+    // Open and copy contents (only for single file selection)
     if can_open_selection(&selected_entries) {
         items.push(context_menu_item(
             "Open",
@@ -132,6 +165,33 @@ pub fn context_menu_view(
             theme,
             fonts,
         ));
+        items.push(context_menu_item(
+            "Open With...",
+            ContextMenuAction::OpenWith,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+        items.push(context_menu_item(
+            "Copy Contents",
+            ContextMenuAction::CopyContents,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+        items.push(context_menu_item(
+            "Copy as Base64",
+            ContextMenuAction::CopyBase64,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
     }
 
     // Copy to target directory (for any selection except parent directory)
@@ -145,6 +205,116 @@ pub fn context_menu_view(
             theme,
             fonts,
         ));
+        items.push(context_menu_item(
+            "Duplicate",
+            ContextMenuAction::Duplicate,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+    }
+
+    // Sync the whole directory into the other pane (local <-> remote only)
+    if state.can_sync_panes() {
+        items.push(context_menu_item(
+            "Sync to target directory...",
+            ContextMenuAction::SyncToTarget,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+    }
+
+    // Watch a local file and upload it to the remote pane on change
+    if state
+        .watch_candidate(state.context_menu.target_pane)
+        .is_some()
+    {
+        items.push(context_menu_item(
+            if watching {
+                "Stop watching"
+            } else {
+                "Watch and upload on change"
+            },
+            ContextMenuAction::ToggleWatch,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+    }
+
+    if link_target_selection(&selected_entries).is_some() {
+        items.push(context_menu_item(
+            "Copy Link Target",
+            ContextMenuAction::CopyLinkTarget,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+    }
+
+    // Local-only path actions; greyed out on remote panes
+    if is_single_entry_selection(&selected_entries) {
+        let is_local = matches!(pane.source, PaneSource::Local);
+        items.push(context_menu_item(
+            REVEAL_LABEL,
+            ContextMenuAction::RevealInFileManager,
+            tab_id,
+            is_local,
+            false,
+            theme,
+            fonts,
+        ));
+        items.push(context_menu_item(
+            "Copy Path",
+            ContextMenuAction::CopyPath,
+            tab_id,
+            is_local,
+            false,
+            theme,
+            fonts,
+        ));
+    }
+
+    // Calculate folder sizes (stops instead when all are already running)
+    if can_calculate_size_selection(&selected_entries) {
+        let calculating = selected_entries
+            .iter()
+            .all(|entry| pane.is_calculating_size(&entry.path));
+        items.push(context_menu_item(
+            if calculating {
+                "Stop Calculating Size"
+            } else {
+                "Calculate Size"
+            },
+            ContextMenuAction::CalculateSize,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+    }
+
+    // Compare with the file selected in the other pane
+    if state.comparison_files().is_some() {
+        items.push(context_menu_item(
+            "Compare with other pane",
+            ContextMenuAction::Compare,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
     }
 
     // Rename (only for single non-parent selection)
@@ -192,6 +362,15 @@ pub fn context_menu_view(
         theme,
         fonts,
     ));
+    items.push(context_menu_item(
+        "New File",
+        ContextMenuAction::NewFile,
+        tab_id,
+        true,
+        false,
+        theme,
+        fonts,
+    ));
 
     // Edit Permissions (only for single file/folder selection, not parent)
     if can_edit_permissions_selection(&selected_entries) {
@@ -206,6 +385,19 @@ pub fn context_menu_view(
         ));
     }
 
+    // Properties (single entry, including symlinks)
+    if is_single && !has_parent {
+        items.push(context_menu_item(
+            "Properties",
+            ContextMenuAction::Properties,
+            tab_id,
+            true,
+            false,
+            theme,
+            fonts,
+        ));
+    }
+
     // Menu container with larger radius and theme-aware background
     let menu = container(Column::with_children(items).spacing(4))
         .padding(8)
@@ -258,8 +450,11 @@ pub fn context_menu_view(
 
 #[cfg(test)]
 mod tests {
-    use super::{can_copy_selection, can_edit_permissions_selection, can_open_selection};
-    use crate::sftp::FileEntry;
+    use super::{
+        can_calculate_size_selection, can_copy_selection, can_edit_permissions_selection,
+        can_open_selection, is_single_entry_selection, link_target_selection,
+    };
+    use crate::sftp::{FileEntry, SymlinkTarget};
     use std::path::PathBuf;
 
     fn entry(name: &str, is_dir: bool, is_symlink: bool) -> FileEntry {
@@ -268,6 +463,7 @@ mod tests {
             path: PathBuf::from(name),
             is_dir,
             is_symlink,
+            symlink_target: None,
             size: 0,
             modified: None,
         }
@@ -303,6 +499,20 @@ mod tests {
         assert!(can_edit_permissions_selection(&selection));
     }
 
+    #[test]
+    fn only_real_directories_can_calculate_size() {
+        let dir = entry("dir", true, false);
+        let file = entry("file.txt", false, false);
+        let dir_link = entry("dir-link", true, true);
+        let parent = entry("..", true, false);
+
+        assert!(can_calculate_size_selection(&[&dir]));
+        assert!(!can_calculate_size_selection(&[&dir, &file]));
+        assert!(!can_calculate_size_selection(&[&dir_link]));
+        assert!(!can_calculate_size_selection(&[&parent]));
+        assert!(!can_calculate_size_selection(&[]));
+    }
+
     #[test]
     fn parent_selection_cannot_use_file_actions() {
         let parent = entry("..", true, false);
@@ -321,4 +531,33 @@ mod tests {
 
         assert!(!can_copy_selection(&selection));
     }
+
+    #[test]
+    fn path_actions_need_a_single_real_entry() {
+        let file = entry("file.txt", false, false);
+        let link = entry("link", false, true);
+        let parent = entry("..", true, false);
+
+        assert!(is_single_entry_selection(&[&file]));
+        assert!(is_single_entry_selection(&[&link]));
+        assert!(!is_single_entry_selection(&[&parent]));
+        assert!(!is_single_entry_selection(&[&file, &link]));
+        assert!(!is_single_entry_selection(&[]));
+    }
+
+    #[test]
+    fn only_single_readable_symlink_offers_link_target() {
+        let mut link = entry("link", false, true);
+        link.symlink_target = Some(SymlinkTarget {
+            target: "/etc/hosts".to_string(),
+            resolved: None,
+        });
+        let unreadable = entry("unreadable", false, true);
+        let file = entry("file.txt", false, false);
+
+        assert_eq!(link_target_selection(&[&link]), Some("/etc/hosts"));
+        assert_eq!(link_target_selection(&[&unreadable]), None);
+        assert_eq!(link_target_selection(&[&file]), None);
+        assert_eq!(link_target_selection(&[&link, &file]), None);
+    }
 }
@@ -1,19 +1,24 @@
 //! SFTP dialog rendering
 //!
 //! This module contains the rendering functions for SFTP-related dialogs
-//! (New Folder, Rename, Delete, Permissions).
+//! (New Folder, New File, Rename, Open With, Delete, Permissions, Change
+//! Owner, Sync, Properties).
 
 use iced::widget::{Column, Space, button, column, container, mouse_area, row, text, text_input};
 use iced::{Alignment, Element, Fill, Length, Padding};
+use secrecy::{ExposeSecret, SecretString};
 use std::path::PathBuf;
 
 use crate::icons::{self, icon_with_color};
 use crate::message::{Message, SessionId, SftpMessage};
+use crate::sftp::chmod::{ChmodProgress, directory_mode};
+use crate::sftp::properties::{Account, is_local_root, permission_string};
+use crate::sftp::{FileProperties, SyncOptions, SyncPlan, format_size};
 use crate::theme::{ScaledFonts, Theme};
 use crate::views::components::progress_bar;
 
 use super::state::{DualPaneSftpState, SftpDialogState};
-use super::types::{PermissionBit, PermissionBits, SftpDialogType};
+use super::types::{PaneSource, PermissionBit, PermissionBits, SftpDialogType};
 
 /// Build the SFTP dialog overlay (New Folder, Rename, or Delete)
 pub fn sftp_dialog_view(
@@ -34,15 +39,71 @@ pub fn sftp_dialog_view(
             entries,
             dialog.error.as_deref(),
             dialog.delete_hold_started,
+            sudo_password_section(tab_id, dialog, theme, fonts),
             theme,
             fonts,
         ),
         SftpDialogType::EditPermissions {
-            name, permissions, ..
+            name,
+            permissions,
+            is_dir,
+            recursive,
+            searchable_dirs,
+            ..
         } => build_permissions_dialog(
             tab_id,
             name,
             permissions,
+            (*is_dir).then_some((*recursive, *searchable_dirs)),
+            state.permission_progress(),
+            dialog.error.as_deref(),
+            sudo_password_section(tab_id, dialog, theme, fonts),
+            theme,
+            fonts,
+        ),
+        SftpDialogType::Sync {
+            source_pane,
+            options,
+            plan,
+        } => build_sync_dialog(
+            tab_id,
+            &state.pane(*source_pane).current_path,
+            &state.pane(dialog.target_pane).current_path,
+            *options,
+            plan.as_ref(),
+            dialog.error.as_deref(),
+            theme,
+            fonts,
+        ),
+        SftpDialogType::ChangeOwner {
+            name,
+            is_dir,
+            owner,
+            group,
+            recursive,
+            ..
+        } => build_owner_dialog(
+            tab_id,
+            dialog,
+            name,
+            owner,
+            group,
+            (*is_dir).then_some(*recursive),
+            state.permission_progress(),
+            theme,
+            fonts,
+        ),
+        SftpDialogType::Properties {
+            name, properties, ..
+        } => build_properties_dialog(
+            tab_id,
+            name,
+            properties.as_ref(),
+            // Only root may chown local files; remote servers decide themselves
+            matches!(
+                state.pane(dialog.target_pane).source,
+                PaneSource::Remote { .. }
+            ) || is_local_root(),
             dialog.error.as_deref(),
             theme,
             fonts,
@@ -84,7 +145,7 @@ pub fn sftp_dialog_view(
     backdrop.into()
 }
 
-/// Build input dialog for New Folder or Rename
+/// Build input dialog for New Folder, New File, or Rename
 fn build_input_dialog(
     tab_id: SessionId,
     dialog: &SftpDialogState,
@@ -94,7 +155,11 @@ fn build_input_dialog(
     // Handle unexpected dialog types by returning an error element early
     if matches!(
         &dialog.dialog_type,
-        SftpDialogType::Delete { .. } | SftpDialogType::EditPermissions { .. }
+        SftpDialogType::Delete { .. }
+            | SftpDialogType::EditPermissions { .. }
+            | SftpDialogType::ChangeOwner { .. }
+            | SftpDialogType::Sync { .. }
+            | SftpDialogType::Properties { .. }
     ) {
         // These dialog types should be handled by build_delete_dialog and
         // build_permissions_dialog respectively. If we reach here, it's a bug.
@@ -114,18 +179,33 @@ fn build_input_dialog(
         .into();
     }
 
-    let (title, placeholder, submit_label, subtitle): (
+    let (title, placeholder, mut submit_label, subtitle): (
         &'static str,
         &'static str,
         &'static str,
         Option<String>,
     ) = match &dialog.dialog_type {
         SftpDialogType::NewFolder => ("New Folder", "Folder name", "Create", None),
+        SftpDialogType::NewFile { .. } => ("New File", "File name", "Create", None),
         SftpDialogType::Rename { .. } => ("Rename", "New name", "Rename", None),
-        // Already handled above with early return
-        SftpDialogType::Delete { .. } | SftpDialogType::EditPermissions { .. } => {
-            ("Error", "", "Close", None)
+        SftpDialogType::RenameBookmark { .. } => {
+            ("Rename Bookmark", "Bookmark name", "Rename", None)
         }
+        SftpDialogType::OpenWith { name, .. } => (
+            "Open With",
+            "Command, e.g. code",
+            "Open",
+            Some(format!(
+                "Runs the command with {} as its last argument",
+                name
+            )),
+        ),
+        // Already handled above with early return
+        SftpDialogType::Delete { .. }
+        | SftpDialogType::EditPermissions { .. }
+        | SftpDialogType::ChangeOwner { .. }
+        | SftpDialogType::Sync { .. }
+        | SftpDialogType::Properties { .. } => ("Error", "", "Close", None),
     };
 
     let title_text = text(title).size(fonts.heading).color(theme.text_primary);
@@ -159,6 +239,26 @@ fn build_input_dialog(
         Space::new().into()
     };
 
+    let open_after_option: Element<'_, Message> = match dialog.dialog_type {
+        SftpDialogType::NewFile { open_after } => column![
+            Space::new().height(8),
+            sync_option_row(
+                "Open in editor after creating",
+                open_after,
+                Message::Sftp(SftpMessage::NewFileOpenAfterToggled(tab_id)),
+                theme,
+                fonts,
+            ),
+        ]
+        .into(),
+        _ => Space::new().into(),
+    };
+
+    let sudo_section = sudo_password_section(tab_id, dialog, theme, fonts);
+    if dialog.sudo_password.is_some() {
+        submit_label = "Retry with sudo";
+    }
+
     let cancel_btn = dialog_cancel_button(tab_id, theme, fonts);
 
     let is_valid = dialog.is_valid();
@@ -182,6 +282,8 @@ fn build_input_dialog(
         Space::new().height(12),
         input,
         error_text,
+        open_after_option,
+        sudo_section,
         Space::new().height(16),
         button_row,
     ]
@@ -197,6 +299,7 @@ fn build_delete_dialog<'a>(
     entries: &'a [(String, PathBuf, bool)],
     error: Option<&'a str>,
     hold_started: Option<std::time::Instant>,
+    sudo_section: Element<'a, Message>,
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'a, Message> {
@@ -341,6 +444,7 @@ fn build_delete_dialog<'a>(
         Space::new().height(12),
         permanent_warning,
         error_text,
+        sudo_section,
         Space::new().height(16),
         button_row,
     ]
@@ -350,6 +454,281 @@ fn build_delete_dialog<'a>(
     .into()
 }
 
+/// Maximum number of planned changes listed in the sync preview
+const SYNC_PREVIEW_LIMIT: usize = 8;
+
+/// Build the sync dry-run preview dialog
+#[allow(clippy::too_many_arguments)]
+fn build_sync_dialog<'a>(
+    tab_id: SessionId,
+    source_dir: &'a std::path::Path,
+    target_dir: &'a std::path::Path,
+    options: SyncOptions,
+    plan: Option<&'a SyncPlan>,
+    error: Option<&'a str>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let title_text = text("Sync Directories")
+        .size(fonts.heading)
+        .color(theme.text_primary);
+
+    let direction = text(format!(
+        "{}  \u{2192}  {}",
+        source_dir.display(),
+        target_dir.display()
+    ))
+    .size(fonts.button_small)
+    .color(theme.text_secondary);
+
+    let delete_option = sync_option_row(
+        "Delete files missing from the source",
+        options.delete_extraneous,
+        Message::Sftp(SftpMessage::SyncOptionsChanged(
+            tab_id,
+            SyncOptions {
+                delete_extraneous: !options.delete_extraneous,
+                ..options
+            },
+        )),
+        theme,
+        fonts,
+    );
+    let checksum_option = sync_option_row(
+        "Compare checksums instead of modification times",
+        options.compare_checksum,
+        Message::Sftp(SftpMessage::SyncOptionsChanged(
+            tab_id,
+            SyncOptions {
+                compare_checksum: !options.compare_checksum,
+                ..options
+            },
+        )),
+        theme,
+        fonts,
+    );
+
+    let preview: Element<'a, Message> = match plan {
+        None if error.is_none() => text("Comparing directories...")
+            .size(fonts.body)
+            .color(theme.text_muted)
+            .into(),
+        None => Space::new().into(),
+        Some(plan) => sync_plan_preview(plan, theme, fonts),
+    };
+
+    let preview_container = container(preview)
+        .padding(Padding::from([8, 12]))
+        .width(Fill)
+        .style(move |_| container::Style {
+            background: Some(theme.background.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        });
+
+    let error_text: Element<'_, Message> = if let Some(error) = error {
+        text(error)
+            .size(fonts.label)
+            .color(iced::Color::from_rgb8(220, 80, 80))
+            .into()
+    } else {
+        Space::new().into()
+    };
+
+    let is_valid = plan.is_some_and(|plan| !plan.is_empty());
+    let is_destructive = plan.is_some_and(|plan| !plan.deletions.is_empty());
+    let cancel_btn = dialog_cancel_button(tab_id, theme, fonts);
+    let sync_btn = dialog_submit_button(tab_id, "Sync", is_valid, is_destructive, theme, fonts);
+    let button_row = row![Space::new().width(Fill), cancel_btn, sync_btn].spacing(8);
+
+    column![
+        title_text,
+        direction,
+        Space::new().height(12),
+        delete_option,
+        checksum_option,
+        Space::new().height(12),
+        preview_container,
+        error_text,
+        Space::new().height(16),
+        button_row,
+    ]
+    .spacing(4)
+    .padding(24)
+    .width(Length::Fixed(480.0))
+    .into()
+}
+
+/// Summary and first few entries of a sync plan
+fn sync_plan_preview<'a>(
+    plan: &'a SyncPlan,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    if plan.is_empty() && plan.conflicts.is_empty() {
+        return text("Already in sync - nothing to transfer.")
+            .size(fonts.body)
+            .color(theme.text_secondary)
+            .into();
+    }
+
+    let mut summary = format!(
+        "{} to copy ({})",
+        plan.copies.len(),
+        format_size(plan.copy_bytes())
+    );
+    if !plan.deletions.is_empty() {
+        summary.push_str(&format!(", {} to delete", plan.deletions.len()));
+    }
+    let mut items: Vec<Element<'a, Message>> = vec![
+        text(summary)
+            .size(fonts.body)
+            .color(theme.text_primary)
+            .into(),
+    ];
+
+    let copies = plan.copies.iter().map(|(item, reason)| {
+        (
+            format!("+ {} ({})", item.relative_path.display(), reason.label()),
+            theme.text_secondary,
+        )
+    });
+    let deletions = plan.deletions.iter().map(|item| {
+        (
+            format!("- {}", item.relative_path.display()),
+            iced::Color::from_rgb8(220, 80, 80),
+        )
+    });
+    let total = plan.copies.len() + plan.deletions.len();
+    for (line, color) in copies.chain(deletions).take(SYNC_PREVIEW_LIMIT) {
+        items.push(text(line).size(fonts.button_small).color(color).into());
+    }
+    if total > SYNC_PREVIEW_LIMIT {
+        items.push(
+            text(format!("... and {} more", total - SYNC_PREVIEW_LIMIT))
+                .size(fonts.button_small)
+                .color(theme.text_muted)
+                .into(),
+        );
+    }
+
+    if !plan.conflicts.is_empty() || plan.skipped_symlinks > 0 {
+        items.push(
+            text(format!(
+                "Skipped: {} file/folder conflict(s), {} symbolic link(s)",
+                plan.conflicts.len(),
+                plan.skipped_symlinks
+            ))
+            .size(fonts.label)
+            .color(iced::Color::from_rgb8(220, 160, 60))
+            .into(),
+        );
+    }
+
+    Column::with_children(items).spacing(4).into()
+}
+
+/// Checkbox row used by the sync options
+fn sync_option_row<'a>(
+    label: &'a str,
+    checked: bool,
+    on_press: Message,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let icon_content: Element<'static, Message> = if checked {
+        icon_with_color(icons::ui::CHECK, 12, theme.text_on_accent()).into()
+    } else {
+        Space::new().width(12).height(12).into()
+    };
+    let indicator_bg = if checked {
+        theme.accent
+    } else {
+        theme.background
+    };
+    let indicator = container(icon_content)
+        .width(Length::Fixed(18.0))
+        .height(Length::Fixed(18.0))
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center)
+        .style(move |_| container::Style {
+            background: Some(indicator_bg.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        });
+
+    button(
+        row![
+            indicator,
+            text(label)
+                .size(fonts.button_small)
+                .color(theme.text_primary)
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+    )
+    .padding([4, 0])
+    .style(|_theme, _status| iced::widget::button::Style::default())
+    .on_press(on_press)
+    .into()
+}
+
+/// Build the sudo password field offered after a remote operation was
+/// refused with "permission denied"
+fn sudo_password_section<'a>(
+    tab_id: SessionId,
+    dialog: &SftpDialogState,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let Some(password) = dialog.sudo_password.as_ref() else {
+        return Space::new().into();
+    };
+
+    let hint = text("Retry as root with sudo. Enter your sudo password.")
+        .size(fonts.label)
+        .color(theme.text_muted);
+
+    let mut input = text_input("Sudo password", password.expose_secret())
+        .secure(true)
+        .on_input(move |value| {
+            Message::Sftp(SftpMessage::SudoPasswordChanged(
+                tab_id,
+                SecretString::from(value),
+            ))
+        })
+        .padding([10, 12])
+        .size(fonts.body)
+        .style(move |_theme, _status| text_input::Style {
+            background: theme.background.into(),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            icon: theme.text_muted,
+            placeholder: theme.text_muted,
+            value: theme.text_primary,
+            selection: theme.accent,
+        });
+    // Deletes still require the press-and-hold confirmation.
+    if !matches!(dialog.dialog_type, SftpDialogType::Delete { .. }) {
+        input = input.on_submit(Message::Sftp(SftpMessage::DialogSubmit(tab_id)));
+    }
+
+    column![Space::new().height(8), hint, input]
+        .spacing(6)
+        .into()
+}
+
 fn hold_delete_button<'a>(
     tab_id: SessionId,
     hold_started: Option<std::time::Instant>,
@@ -400,12 +779,17 @@ fn hold_delete_button<'a>(
         .into()
 }
 
-/// Build the permissions dialog
+/// Build the permissions dialog. `recursive` is `Some((recursive,
+/// searchable_dirs))` for folders, which can apply the mode to their tree.
+#[allow(clippy::too_many_arguments)]
 fn build_permissions_dialog<'a>(
     tab_id: SessionId,
     name: &'a str,
     permissions: &'a PermissionBits,
+    recursive: Option<(bool, bool)>,
+    progress: Option<ChmodProgress>,
     error: Option<&'a str>,
+    sudo_section: Element<'a, Message>,
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'a, Message> {
@@ -421,8 +805,15 @@ fn build_permissions_dialog<'a>(
     .spacing(8)
     .align_y(Alignment::Center);
 
-    // Current mode display
-    let mode_text = text(format!("Mode: {}", permissions.as_octal_string()))
+    // Current mode display; folders get their own mode when kept searchable
+    let mut mode_label = format!("Mode: {}", permissions.as_octal_string());
+    if let Some((true, true)) = recursive {
+        let dir_mode = directory_mode(permissions.to_mode());
+        if dir_mode != permissions.to_mode() {
+            mode_label.push_str(&format!(" (folders: {:03o})", dir_mode));
+        }
+    }
+    let mode_text = text(mode_label)
         .size(fonts.button_small)
         .color(theme.text_muted);
 
@@ -512,8 +903,50 @@ fn build_permissions_dialog<'a>(
         Space::new().into()
     };
 
+    let recursive_options: Element<'a, Message> = match recursive {
+        Some((recursive, searchable_dirs)) => {
+            let mut options = column![
+                Space::new().height(8),
+                sync_option_row(
+                    "Apply to all enclosed files and folders",
+                    recursive,
+                    Message::Sftp(SftpMessage::PermissionsRecursiveToggled(tab_id)),
+                    theme,
+                    fonts,
+                ),
+            ];
+            if recursive {
+                options = options.push(sync_option_row(
+                    "Keep folders searchable (add execute where readable)",
+                    searchable_dirs,
+                    Message::Sftp(SftpMessage::PermissionsSearchableDirsToggled(tab_id)),
+                    theme,
+                    fonts,
+                ));
+            }
+            options.into()
+        }
+        None => Space::new().into(),
+    };
+
+    let progress_text: Element<'a, Message> = match progress {
+        Some(progress) => {
+            let mut label = format!("Changed {} item(s)", progress.changed);
+            if progress.failed > 0 {
+                label.push_str(&format!(", {} failed", progress.failed));
+            }
+            label.push_str("...");
+            text(label)
+                .size(fonts.label)
+                .color(theme.text_secondary)
+                .into()
+        }
+        None => Space::new().into(),
+    };
+
+    // Cancel also stops a running recursive change
     let cancel_btn = dialog_cancel_button(tab_id, theme, fonts);
-    let apply_btn = dialog_submit_button(tab_id, "Apply", true, false, theme, fonts);
+    let apply_btn = dialog_submit_button(tab_id, "Apply", progress.is_none(), false, theme, fonts);
 
     let button_row = row![Space::new().width(Fill), cancel_btn, apply_btn].spacing(8);
 
@@ -524,13 +957,342 @@ fn build_permissions_dialog<'a>(
         mode_text,
         Space::new().height(12),
         permission_grid,
+        recursive_options,
+        progress_text,
+        error_text,
+        sudo_section,
+        Space::new().height(16),
+        button_row,
+    ]
+    .spacing(4)
+    .padding(24)
+    .width(Length::Fixed(380.0))
+    .into()
+}
+
+/// Build the Change Owner dialog. `recursive` is `Some` for folders, which
+/// can apply the change to their tree.
+#[allow(clippy::too_many_arguments)]
+fn build_owner_dialog<'a>(
+    tab_id: SessionId,
+    dialog: &'a SftpDialogState,
+    name: &'a str,
+    owner: &'a str,
+    group: &'a str,
+    recursive: Option<bool>,
+    progress: Option<ChmodProgress>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let title_text = text("Change Owner")
+        .size(fonts.heading)
+        .color(theme.text_primary);
+
+    let icon_data = if recursive.is_some() {
+        icons::files::FOLDER
+    } else {
+        icons::files::FILE
+    };
+    let file_info = row![
+        icon_with_color(icon_data, 16, theme.text_muted),
+        text(name).size(fonts.body).color(theme.text_secondary)
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let field = |label: &'a str, value: &'a str, on_input: fn(SessionId, String) -> SftpMessage| {
+        row![
+            text(label)
+                .size(fonts.button_small)
+                .color(theme.text_muted)
+                .width(Length::Fixed(64.0)),
+            text_input("Name or ID (blank keeps current)", value)
+                .on_input(move |value| Message::Sftp(on_input(tab_id, value)))
+                .on_submit(Message::Sftp(SftpMessage::DialogSubmit(tab_id)))
+                .padding([8, 12])
+                .size(fonts.body)
+                .style(move |_theme, _status| text_input::Style {
+                    background: theme.background.into(),
+                    border: iced::Border {
+                        color: theme.border,
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    icon: theme.text_muted,
+                    placeholder: theme.text_muted,
+                    value: theme.text_primary,
+                    selection: theme.accent,
+                }),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+    };
+
+    let recursive_option: Element<'a, Message> = match recursive {
+        Some(recursive) => column![
+            Space::new().height(8),
+            sync_option_row(
+                "Apply to all enclosed files and folders",
+                recursive,
+                Message::Sftp(SftpMessage::PermissionsRecursiveToggled(tab_id)),
+                theme,
+                fonts,
+            ),
+        ]
+        .into(),
+        None => Space::new().into(),
+    };
+
+    let progress_text: Element<'a, Message> = match progress {
+        Some(progress) => {
+            let mut label = format!("Changed {} item(s)", progress.changed);
+            if progress.failed > 0 {
+                label.push_str(&format!(", {} failed", progress.failed));
+            }
+            label.push_str("...");
+            text(label)
+                .size(fonts.label)
+                .color(theme.text_secondary)
+                .into()
+        }
+        None => Space::new().into(),
+    };
+
+    let error_text: Element<'_, Message> = if let Some(ref error) = dialog.error {
+        text(error)
+            .size(fonts.label)
+            .color(iced::Color::from_rgb8(220, 80, 80))
+            .into()
+    } else {
+        Space::new().into()
+    };
+
+    let submit_label = if dialog.sudo_password.is_some() {
+        "Retry with sudo"
+    } else {
+        "Apply"
+    };
+    let cancel_btn = dialog_cancel_button(tab_id, theme, fonts);
+    let apply_btn = dialog_submit_button(
+        tab_id,
+        submit_label,
+        dialog.is_valid() && progress.is_none(),
+        false,
+        theme,
+        fonts,
+    );
+    let button_row = row![Space::new().width(Fill), cancel_btn, apply_btn].spacing(8);
+
+    column![
+        title_text,
+        Space::new().height(12),
+        file_info,
+        Space::new().height(12),
+        field("Owner", owner, SftpMessage::OwnerInputChanged),
+        field("Group", group, SftpMessage::GroupInputChanged),
+        recursive_option,
+        progress_text,
+        error_text,
+        sudo_password_section(tab_id, dialog, theme, fonts),
+        Space::new().height(16),
+        button_row,
+    ]
+    .spacing(4)
+    .padding(24)
+    .width(Length::Fixed(380.0))
+    .into()
+}
+
+/// Build the read-only Properties dialog. `can_change_owner` offers chown,
+/// which local panes only allow when running as root.
+fn build_properties_dialog<'a>(
+    tab_id: SessionId,
+    name: &'a str,
+    properties: Option<&'a FileProperties>,
+    can_change_owner: bool,
+    error: Option<&'a str>,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let title_text = text("Properties")
+        .size(fonts.heading)
+        .color(theme.text_primary);
+
+    let icon_data = match properties {
+        Some(properties) if properties.is_dir => icons::files::FOLDER,
+        _ => icons::files::FILE,
+    };
+    let file_info = row![
+        icon_with_color(icon_data, 16, theme.text_muted),
+        text(name).size(fonts.body).color(theme.text_secondary)
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let details: Element<'a, Message> = match properties {
+        None if error.is_none() => text("Reading properties...")
+            .size(fonts.body)
+            .color(theme.text_muted)
+            .into(),
+        None => Space::new().into(),
+        Some(properties) => {
+            let time = |time: Option<chrono::DateTime<chrono::Utc>>| {
+                time.map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| "—".to_string())
+            };
+            let account = |account: Option<&Account>| {
+                account
+                    .map(|account| account.label())
+                    .unwrap_or_else(|| "—".to_string())
+            };
+            let kind = if properties.is_symlink {
+                "Symbolic Link"
+            } else if properties.is_dir {
+                "Folder"
+            } else {
+                "File"
+            };
+
+            let mut rows = vec![
+                ("Path", properties.path.display().to_string()),
+                ("Kind", kind.to_string()),
+                (
+                    "Size",
+                    format!(
+                        "{} ({} bytes)",
+                        format_size(properties.size),
+                        properties.size
+                    ),
+                ),
+                ("Modified", time(properties.modified)),
+                ("Accessed", time(properties.accessed)),
+                ("Created", time(properties.created)),
+                ("Owner", account(properties.owner.as_ref())),
+                ("Group", account(properties.group.as_ref())),
+                (
+                    "Permissions",
+                    properties
+                        .mode
+                        .map(|mode| format!("{} ({:03o})", permission_string(mode), mode & 0o7777))
+                        .unwrap_or_else(|| "—".to_string()),
+                ),
+            ];
+            if let Some(target) = &properties.symlink_target {
+                let mut value = target.target.clone();
+                match &target.resolved {
+                    Some(resolved) if resolved.as_os_str() != target.target.as_str() => {
+                        value.push_str(&format!(" ({})", resolved.display()));
+                    }
+                    Some(_) => {}
+                    None => value.push_str(" (broken)"),
+                }
+                rows.push(("Link target", value));
+            }
+
+            let rows: Vec<Element<'a, Message>> = rows
+                .into_iter()
+                .map(|(label, value)| {
+                    row![
+                        text(label)
+                            .size(fonts.button_small)
+                            .color(theme.text_muted)
+                            .width(Length::Fixed(96.0)),
+                        text(value)
+                            .size(fonts.button_small)
+                            .color(theme.text_primary)
+                            .width(Fill),
+                    ]
+                    .spacing(8)
+                    .into()
+                })
+                .collect();
+            Column::with_children(rows).spacing(6).into()
+        }
+    };
+
+    let details_container =
+        container(details)
+            .padding(12)
+            .width(Fill)
+            .style(move |_| container::Style {
+                background: Some(theme.background.into()),
+                border: iced::Border {
+                    color: theme.border,
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            });
+
+    let error_text: Element<'_, Message> = if let Some(error) = error {
+        text(error)
+            .size(fonts.label)
+            .color(iced::Color::from_rgb8(220, 80, 80))
+            .into()
+    } else {
+        Space::new().into()
+    };
+
+    let secondary_button = |label: &'a str, message: SftpMessage| -> Element<'a, Message> {
+        button(
+            text(label)
+                .size(fonts.button_small)
+                .color(theme.text_primary),
+        )
+        .padding([8, 16])
+        .style(move |_theme, status| {
+            let bg = match status {
+                iced::widget::button::Status::Hovered => theme.hover,
+                _ => theme.surface,
+            };
+            iced::widget::button::Style {
+                background: Some(bg.into()),
+                text_color: theme.text_primary,
+                border: iced::Border {
+                    color: theme.border,
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .on_press(Message::Sftp(message))
+        .into()
+    };
+
+    // Symlinks are never chmod-ed or chown-ed, so only offer editing for
+    // real entries
+    let mut actions = row![].spacing(8);
+    if let Some(properties) = properties.filter(|properties| !properties.is_symlink) {
+        if properties.mode.is_some() {
+            actions = actions.push(secondary_button(
+                "Edit Permissions",
+                SftpMessage::PropertiesEditPermissions(tab_id),
+            ));
+        }
+        if can_change_owner {
+            actions = actions.push(secondary_button(
+                "Change Owner",
+                SftpMessage::PropertiesChangeOwner(tab_id),
+            ));
+        }
+    }
+    let close_btn = dialog_submit_button(tab_id, "Close", true, false, theme, fonts);
+    let button_row = row![actions, Space::new().width(Fill), close_btn].spacing(8);
+
+    column![
+        title_text,
+        Space::new().height(12),
+        file_info,
+        Space::new().height(12),
+        details_container,
         error_text,
         Space::new().height(16),
         button_row,
     ]
     .spacing(4)
     .padding(24)
-    .width(Length::Fixed(350.0))
+    .width(Length::Fixed(460.0))
     .into()
 }
 
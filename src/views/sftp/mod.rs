@@ -16,6 +16,7 @@ pub mod state;
 pub mod types;
 
 // Re-export types for external use
+pub use pane::path_input_id;
 pub use state::DualPaneSftpState;
 pub use types::{
     ColumnWidths, ContextMenuAction, PaneId, PaneSource, PermissionBit, PermissionBits, SftpColumn,
@@ -23,10 +24,11 @@ pub use types::{
 };
 
 use iced::widget::{Space, button, column, container, progress_bar, row, stack, text};
-use iced::{Element, Fill, Length};
+use iced::{Color, Element, Fill, Length};
 use uuid::Uuid;
 
-use crate::app::managers::{TransferItem, TransferStatus};
+use crate::app::managers::{FileWatch, TransferItem, TransferStatus};
+use crate::config::SftpBookmark;
 use crate::icons::{self, icon_with_color};
 use crate::message::Message;
 use crate::theme::{STATUS_FAILURE, STATUS_PARTIAL, STATUS_SUCCESS, ScaledFonts, Theme};
@@ -38,20 +40,30 @@ use pane::single_pane_view;
 const MAX_VISIBLE_TRANSFER_ROWS: usize = 4;
 
 /// Build the dual-pane SFTP browser view
+///
+/// `bookmarks` holds the left and right panes' bookmarks, or `None` for a
+/// pane whose source cannot be bookmarked.
+#[allow(clippy::too_many_arguments)]
 pub fn dual_pane_sftp_view<'a>(
     state: &'a DualPaneSftpState,
     available_hosts: Vec<(Uuid, String)>,
+    bookmarks: [Option<Vec<SftpBookmark>>; 2],
     transfers: Vec<TransferItem>,
+    watches: Vec<&FileWatch>,
+    modifiers: iced::keyboard::Modifiers,
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'a, Message> {
+    let [left_bookmarks, right_bookmarks] = bookmarks;
     let left_pane = single_pane_view(
         &state.left_pane,
         PaneId::Left,
         state.tab_id,
         available_hosts.clone(),
+        left_bookmarks,
         state.active_pane == PaneId::Left,
         state.context_menu.visible,
+        modifiers,
         &state.left_pane.column_widths,
         theme,
         fonts,
@@ -62,8 +74,10 @@ pub fn dual_pane_sftp_view<'a>(
         PaneId::Right,
         state.tab_id,
         available_hosts,
+        right_bookmarks,
         state.active_pane == PaneId::Right,
         state.context_menu.visible,
+        modifiers,
         &state.right_pane.column_widths,
         theme,
         fonts,
@@ -79,13 +93,13 @@ pub fn dual_pane_sftp_view<'a>(
         });
 
     let panes = row![left_pane, divider, right_pane];
-    let content: Element<'_, Message> = if transfers.is_empty() {
-        panes.into()
-    } else {
-        column![panes, transfer_panel(transfers, theme, fonts)]
-            .height(Fill)
-            .into()
-    };
+    let mut content = column![panes].height(Fill);
+    if !watches.is_empty() {
+        content = content.push(watch_panel(&watches, theme, fonts));
+    }
+    if !transfers.is_empty() {
+        content = content.push(transfer_panel(state.tab_id, transfers, theme, fonts));
+    }
 
     let main = container(content)
         .width(Fill)
@@ -104,18 +118,14 @@ pub fn dual_pane_sftp_view<'a>(
 }
 
 fn transfer_panel(
+    tab_id: Uuid,
     transfers: Vec<TransferItem>,
     theme: Theme,
     fonts: ScaledFonts,
 ) -> Element<'static, Message> {
     let active_count = active_transfer_count(&transfers);
-    let header = row![
-        text("Transfers").size(fonts.body).color(theme.text_primary),
-        text(active_transfer_label(active_count))
-            .size(fonts.label)
-            .color(theme.text_muted),
-        Space::new().width(Fill),
-        button(text("Clear").size(fonts.label).color(theme.text_secondary))
+    let header_button = move |label: &'static str, message: crate::message::SftpMessage| {
+        button(text(label).size(fonts.label).color(theme.text_secondary))
             .padding([3, 8])
             .style(move |_theme, status| {
                 let bg = match status {
@@ -132,12 +142,27 @@ fn transfer_panel(
                     ..Default::default()
                 }
             })
-            .on_press(Message::Sftp(
-                crate::message::SftpMessage::TransferClearFinished
-            )),
+            .on_press(Message::Sftp(message))
+    };
+    let mut header = row![
+        text("Transfers").size(fonts.body).color(theme.text_primary),
+        text(active_transfer_label(active_count))
+            .size(fonts.label)
+            .color(theme.text_muted),
+        Space::new().width(Fill),
     ]
     .spacing(8)
     .align_y(iced::Alignment::Center);
+    if active_count > 1 {
+        header = header.push(header_button(
+            "Cancel all",
+            crate::message::SftpMessage::TransferCancelAll(tab_id),
+        ));
+    }
+    let header = header.push(header_button(
+        "Clear",
+        crate::message::SftpMessage::TransferClearFinished,
+    ));
 
     let rows = transfers
         .iter()
@@ -243,6 +268,125 @@ fn transfer_row(
     .into()
 }
 
+/// Files being watched for changes and uploaded automatically
+fn watch_panel(
+    watches: &[&FileWatch],
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let header = text("Watched and opened files")
+        .size(fonts.body)
+        .color(theme.text_primary);
+    let rows = watches
+        .iter()
+        .map(|watch| watch_row(watch, theme, fonts))
+        .collect::<Vec<_>>();
+
+    container(column![header, column(rows).spacing(4)].spacing(8))
+        .width(Fill)
+        .padding([10, 14])
+        .style(move |_| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+fn watch_row(watch: &FileWatch, theme: Theme, fonts: ScaledFonts) -> Element<'static, Message> {
+    let status_color = if watch.last_error.is_some() {
+        STATUS_FAILURE
+    } else {
+        theme.text_muted
+    };
+
+    let stop_button = button(icon_with_color(icons::ui::X, 14, theme.text_secondary))
+        .padding(6)
+        .width(30)
+        .height(30)
+        .style(move |_theme, status| {
+            let bg = match status {
+                iced::widget::button::Status::Hovered => Some(theme.hover.into()),
+                _ => None,
+            };
+            iced::widget::button::Style {
+                background: bg,
+                text_color: theme.text_secondary,
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .on_press(Message::Sftp(crate::message::SftpMessage::WatchStop(
+            watch.id,
+        )));
+
+    let upload_button: Element<'static, Message> = if watch.pending_change {
+        button(text("Upload").size(fonts.label))
+            .padding([4, 10])
+            .style(move |_theme, status| {
+                let bg = match status {
+                    iced::widget::button::Status::Hovered => Color {
+                        a: 0.85,
+                        ..theme.accent
+                    },
+                    _ => theme.accent,
+                };
+                iced::widget::button::Style {
+                    background: Some(bg.into()),
+                    text_color: theme.text_on_accent(),
+                    border: iced::Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            })
+            .on_press(Message::Sftp(crate::message::SftpMessage::WatchUpload(
+                watch.id,
+            )))
+            .into()
+    } else {
+        Space::new().into()
+    };
+
+    row![
+        text(watch.label())
+            .size(fonts.label)
+            .color(theme.text_primary),
+        text(format!("→ {}", watch.remote_path.display()))
+            .size(fonts.label)
+            .color(theme.text_muted)
+            .wrapping(text::Wrapping::None),
+        Space::new().width(Fill),
+        text(watch_status_text(watch))
+            .size(fonts.small)
+            .color(status_color)
+            .wrapping(text::Wrapping::None),
+        upload_button,
+        stop_button,
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+fn watch_status_text(watch: &FileWatch) -> String {
+    match (&watch.last_error, watch.last_synced) {
+        (Some(error), _) => error.clone(),
+        _ if watch.pending_change => "Changed, not uploaded".to_string(),
+        (None, Some(synced)) => format!("Synced {}", synced.format("%H:%M:%S")),
+        (None, None) if watch.is_edit() => "Opened, waiting for changes".to_string(),
+        (None, None) => "Waiting for changes".to_string(),
+    }
+}
+
 fn active_transfer_count(transfers: &[TransferItem]) -> usize {
     transfers
         .iter()
@@ -290,18 +434,23 @@ fn transfer_count_text(transfer: &TransferItem) -> String {
 }
 
 /// Build the context menu overlay - should be rendered at app level for correct window positioning
+///
+/// `watching` tells whether the selected local file is already being watched.
 pub fn sftp_context_menu_overlay(
     state: &DualPaneSftpState,
+    watching: bool,
     theme: Theme,
     fonts: ScaledFonts,
     window_size: iced::Size,
 ) -> Element<'_, Message> {
-    context_menu_view(state, theme, fonts, window_size)
+    context_menu_view(state, watching, theme, fonts, window_size)
 }
 
-/// Check if any actions menu is open in the SFTP state
+/// Check if any actions or bookmarks menu is open in the SFTP state
 pub fn has_actions_menu_open(state: &DualPaneSftpState) -> bool {
-    state.left_pane.actions_menu_open || state.right_pane.actions_menu_open
+    [&state.left_pane, &state.right_pane]
+        .iter()
+        .any(|pane| pane.actions_menu_open || pane.bookmarks_menu_open)
 }
 
 /// Build a window-wide dismiss background for actions menus
@@ -309,11 +458,18 @@ pub fn has_actions_menu_open(state: &DualPaneSftpState) -> bool {
 pub fn sftp_actions_menu_dismiss_overlay(state: &DualPaneSftpState) -> Element<'_, Message> {
     use crate::widgets::mouse_area;
 
+    use crate::message::SftpMessage;
+
     // Determine which pane's menu is open (if any) to send the correct toggle message
-    let (tab_id, pane_id) = if state.left_pane.actions_menu_open {
-        (state.tab_id, PaneId::Left)
+    let tab_id = state.tab_id;
+    let dismiss = if state.left_pane.actions_menu_open {
+        SftpMessage::ToggleActionsMenu(tab_id, PaneId::Left)
     } else if state.right_pane.actions_menu_open {
-        (state.tab_id, PaneId::Right)
+        SftpMessage::ToggleActionsMenu(tab_id, PaneId::Right)
+    } else if state.left_pane.bookmarks_menu_open {
+        SftpMessage::ToggleBookmarksMenu(tab_id, PaneId::Left)
+    } else if state.right_pane.bookmarks_menu_open {
+        SftpMessage::ToggleBookmarksMenu(tab_id, PaneId::Right)
     } else {
         return Space::new().into();
     };
@@ -323,9 +479,7 @@ pub fn sftp_actions_menu_dismiss_overlay(state: &DualPaneSftpState) -> Element<'
             .width(Fill)
             .height(Fill),
     )
-    .on_press(Message::Sftp(
-        crate::message::SftpMessage::ToggleActionsMenu(tab_id, pane_id),
-    ))
+    .on_press(Message::Sftp(dismiss))
     .into()
 }
 
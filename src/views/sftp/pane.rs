@@ -12,11 +12,13 @@ use iced::widget::{
 use iced::{Alignment, Color, Element, Fill, Length, Padding};
 use uuid::Uuid;
 
+use crate::config::SftpBookmark;
 use crate::icons::{self, icon_with_color};
 use crate::message::{Message, SessionId, SftpMessage};
-use crate::sftp::{FileEntry, FileIcon, format_size};
+use crate::sftp::{DirSize, FileEntry, FileIcon, format_size};
 use crate::theme::{ScaledFonts, Theme};
 use crate::views::components::skeleton_rows;
+use crate::views::dialogs::common::ERROR_COLOR;
 use crate::widgets::{column_resize_handle, mouse_area};
 
 use super::state::FilePaneState;
@@ -27,7 +29,7 @@ fn file_icon_data(icon_type: FileIcon) -> &'static [u8] {
     match icon_type {
         FileIcon::ParentDir => icons::ui::CHEVRON_LEFT,
         FileIcon::Folder => icons::files::FOLDER,
-        FileIcon::Symlink => icons::files::FILE,
+        FileIcon::Symlink => icons::files::FILE_SYMLINK,
         FileIcon::Code => icons::files::FILE_CODE,
         FileIcon::Text => icons::files::FILE_TEXT,
         FileIcon::Image => icons::files::IMAGE,
@@ -47,8 +49,10 @@ pub fn single_pane_view<'a>(
     pane_id: PaneId,
     tab_id: SessionId,
     available_hosts: Vec<(Uuid, String)>,
+    bookmarks: Option<Vec<SftpBookmark>>,
     is_active: bool,
     context_menu_open: bool,
+    modifiers: iced::keyboard::Modifiers,
     column_widths: &'a ColumnWidths,
     theme: Theme,
     fonts: ScaledFonts,
@@ -58,6 +62,7 @@ pub fn single_pane_view<'a>(
         pane_id,
         tab_id,
         available_hosts,
+        bookmarks.as_deref(),
         is_active,
         theme,
         fonts,
@@ -68,6 +73,7 @@ pub fn single_pane_view<'a>(
         pane_id,
         tab_id,
         context_menu_open,
+        modifiers,
         column_widths,
         theme,
         fonts,
@@ -80,28 +86,38 @@ pub fn single_pane_view<'a>(
         .width(Length::FillPortion(1))
         .height(Fill);
 
-    // Overlay actions menu if open
+    // Overlay actions or bookmarks menu if open
     if state.actions_menu_open {
         iced::widget::stack![
             main,
             actions_menu_overlay(state, pane_id, tab_id, theme, fonts)
         ]
         .into()
+    } else if state.bookmarks_menu_open
+        && let Some(bookmarks) = bookmarks
+    {
+        iced::widget::stack![
+            main,
+            bookmarks_menu_overlay(bookmarks, pane_id, tab_id, theme, fonts)
+        ]
+        .into()
     } else {
         main.into()
     }
 }
 
-/// Header with source dropdown, filter input, and actions menu
-pub fn pane_header(
-    state: &FilePaneState,
+/// Header with source dropdown, bookmarks, filter input, and actions menu
+#[allow(clippy::too_many_arguments)]
+pub fn pane_header<'a>(
+    state: &'a FilePaneState,
     pane_id: PaneId,
     tab_id: SessionId,
     available_hosts: Vec<(Uuid, String)>,
+    bookmarks: Option<&[SftpBookmark]>,
     _is_active: bool,
     theme: Theme,
     fonts: ScaledFonts,
-) -> Element<'_, Message> {
+) -> Element<'a, Message> {
     // Build source options: Local + all configured hosts
     let mut source_options: Vec<String> = vec!["Local".to_string()];
     for (_host_id, host_name) in &available_hosts {
@@ -160,6 +176,52 @@ pub fn pane_header(
             selection: theme.accent,
         });
 
+    // Hidden files toggle
+    let (hidden_icon, hidden_hint) = if state.show_hidden {
+        (icons::ui::EYE, "Hide hidden files (Ctrl+H)")
+    } else {
+        (icons::ui::EYE_OFF, "Show hidden files (Ctrl+H)")
+    };
+    let hidden_btn = tooltip(
+        button(icon_with_color(hidden_icon, 14, theme.text_primary))
+            .style(move |_theme, status| {
+                let bg = match status {
+                    iced::widget::button::Status::Hovered => Some(theme.hover.into()),
+                    _ => Some(theme.surface.into()),
+                };
+                iced::widget::button::Style {
+                    background: bg,
+                    text_color: theme.text_primary,
+                    border: iced::Border {
+                        color: theme.border,
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    ..Default::default()
+                }
+            })
+            .padding([6, 8])
+            .on_press(Message::Sftp(SftpMessage::ToggleShowHidden(
+                tab_id, pane_id,
+            ))),
+        container(
+            text(hidden_hint)
+                .size(fonts.label)
+                .color(theme.text_primary),
+        )
+        .padding(6),
+        tooltip::Position::Bottom,
+    )
+    .style(move |_theme| container::Style {
+        background: Some(theme.surface.into()),
+        border: iced::Border {
+            color: theme.border,
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..Default::default()
+    });
+
     // Actions dropdown button
     let actions_btn = button(
         row![
@@ -190,16 +252,22 @@ pub fn pane_header(
         tab_id, pane_id,
     )));
 
+    let mut header_row = row![source_picker];
+    if let Some(bookmarks) = bookmarks {
+        header_row = header_row.push(bookmark_buttons(
+            state, bookmarks, pane_id, tab_id, theme, fonts,
+        ));
+    }
+
     container(
-        row![
-            source_picker,
-            Space::new().width(Fill),
-            filter_input,
-            actions_btn
-        ]
-        .spacing(8)
-        .padding(8)
-        .align_y(Alignment::Center),
+        header_row
+            .push(Space::new().width(Fill))
+            .push(filter_input)
+            .push(hidden_btn)
+            .push(actions_btn)
+            .spacing(8)
+            .padding(8)
+            .align_y(Alignment::Center),
     )
     .width(Fill)
     .style(move |_theme| container::Style {
@@ -209,6 +277,190 @@ pub fn pane_header(
     .into()
 }
 
+/// Star toggle for the current directory and the bookmarks dropdown button
+fn bookmark_buttons(
+    state: &FilePaneState,
+    bookmarks: &[SftpBookmark],
+    pane_id: PaneId,
+    tab_id: SessionId,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let header_button_style = move |_theme: &iced::Theme, status: iced::widget::button::Status| {
+        let bg = match status {
+            iced::widget::button::Status::Hovered => Some(theme.hover.into()),
+            _ => Some(theme.surface.into()),
+        };
+        iced::widget::button::Style {
+            background: bg,
+            text_color: theme.text_primary,
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        }
+    };
+
+    let is_bookmarked = bookmarks
+        .iter()
+        .any(|bookmark| bookmark.path == state.current_path);
+    let (star_color, star_hint) = if is_bookmarked {
+        (theme.accent, "Remove bookmark")
+    } else {
+        (theme.text_primary, "Bookmark this folder")
+    };
+    let star_btn = tooltip(
+        button(icon_with_color(icons::ui::STAR, 14, star_color))
+            .style(header_button_style)
+            .padding([6, 8])
+            .on_press(Message::Sftp(SftpMessage::ToggleBookmark(tab_id, pane_id))),
+        container(text(star_hint).size(fonts.label).color(theme.text_primary)).padding(6),
+        tooltip::Position::Bottom,
+    )
+    .style(move |_theme| container::Style {
+        background: Some(theme.surface.into()),
+        border: iced::Border {
+            color: theme.border,
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..Default::default()
+    });
+
+    let menu_btn = button(icon_with_color(
+        icons::ui::CHEVRON_DOWN,
+        14,
+        theme.text_primary,
+    ))
+    .style(header_button_style)
+    .padding([6, 6])
+    .on_press(Message::Sftp(SftpMessage::ToggleBookmarksMenu(
+        tab_id, pane_id,
+    )));
+
+    row![star_btn, menu_btn]
+        .spacing(2)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+/// Bookmarks dropdown: jump to, rename or remove a bookmark
+fn bookmarks_menu_overlay(
+    bookmarks: Vec<SftpBookmark>,
+    pane_id: PaneId,
+    tab_id: SessionId,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let icon_button = move |icon: &'static [u8], message: SftpMessage| {
+        button(icon_with_color(icon, 12, theme.text_secondary))
+            .padding([6, 6])
+            .style(move |_theme, status| iced::widget::button::Style {
+                background: match status {
+                    iced::widget::button::Status::Hovered => Some(theme.hover.into()),
+                    _ => None,
+                },
+                text_color: theme.text_secondary,
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .on_press(Message::Sftp(message))
+    };
+
+    let mut items = Column::new().spacing(2);
+    if bookmarks.is_empty() {
+        items = items.push(
+            container(
+                text("No bookmarks yet - use the star to add this folder")
+                    .size(fonts.label)
+                    .color(theme.text_muted),
+            )
+            .padding([8, 12]),
+        );
+    }
+    for bookmark in bookmarks {
+        let jump = button(
+            column![
+                text(bookmark.name.clone())
+                    .size(fonts.button_small)
+                    .color(theme.text_primary)
+                    .wrapping(text::Wrapping::None),
+                text(bookmark.path.display().to_string())
+                    .size(fonts.label)
+                    .color(theme.text_muted)
+                    .wrapping(text::Wrapping::None),
+            ]
+            .spacing(2),
+        )
+        .padding([6, 12])
+        .width(Fill)
+        .style(move |_theme, status| iced::widget::button::Style {
+            background: match status {
+                iced::widget::button::Status::Hovered => Some(theme.hover.into()),
+                _ => None,
+            },
+            text_color: theme.text_primary,
+            border: iced::Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .on_press(Message::Sftp(SftpMessage::PaneNavigate(
+            tab_id,
+            pane_id,
+            bookmark.path.clone(),
+        )));
+
+        items = items.push(
+            row![
+                container(jump).width(Fill).clip(true),
+                icon_button(
+                    icons::ui::PENCIL,
+                    SftpMessage::BookmarkRename(tab_id, pane_id, bookmark.id),
+                ),
+                icon_button(
+                    icons::ui::X,
+                    SftpMessage::BookmarkDelete(tab_id, bookmark.id),
+                ),
+            ]
+            .spacing(2)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    let menu = container(scrollable(items).height(Length::Shrink))
+        .padding(8)
+        .width(Length::Fixed(300.0))
+        .max_height(360.0)
+        .style(move |_| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: 12.0.into(),
+            },
+            shadow: iced::Shadow {
+                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
+                offset: iced::Vector::new(0.0, 4.0),
+                blur_radius: 16.0,
+            },
+            ..Default::default()
+        });
+
+    // Position menu below the bookmark buttons, next to the source picker
+    // Note: dismiss background is rendered at app level for window-wide click handling
+    container(menu)
+        .width(Fill)
+        .padding(Padding::new(0.0).top(40.0).left(158.0))
+        .into()
+}
+
 /// Breadcrumb navigation bar with back/forward buttons and clickable path segments
 pub fn pane_breadcrumb_bar(
     state: &FilePaneState,
@@ -328,15 +580,23 @@ pub fn pane_breadcrumb_bar(
         .spacing(4)
         .align_y(Alignment::Center);
 
-    // Wrap breadcrumbs in scrollable for long paths
+    // Wrap breadcrumbs in scrollable for long paths. Clicking the empty
+    // space next to the segments switches to a typed path.
     let scrollable_breadcrumbs = scrollable(breadcrumb_row)
         .direction(scrollable::Direction::Horizontal(
             scrollable::Scrollbar::new().width(0).scroller_width(0),
         ))
         .width(Fill);
+    let path_area: Element<'_, Message> = match &state.path_input {
+        Some(value) => path_input_field(value, state, pane_id, tab_id, theme, fonts),
+        None => mouse_area(scrollable_breadcrumbs)
+            .width(Fill)
+            .on_press(Message::Sftp(SftpMessage::PathEditStart(tab_id, pane_id)))
+            .into(),
+    };
 
     container(
-        row![back_btn, forward_btn, scrollable_breadcrumbs]
+        row![back_btn, forward_btn, path_area]
             .spacing(4)
             .padding([4, 8])
             .align_y(Alignment::Center),
@@ -349,12 +609,65 @@ pub fn pane_breadcrumb_bar(
     .into()
 }
 
+/// ID of a pane's path bar input, for focusing it when editing starts
+pub fn path_input_id(tab_id: SessionId, pane_id: PaneId) -> iced::widget::Id {
+    iced::widget::Id::from(format!("sftp-path-{tab_id}-{pane_id:?}"))
+}
+
+/// Editable path bar with an inline error for paths that could not be opened
+fn path_input_field<'a>(
+    value: &str,
+    state: &'a FilePaneState,
+    pane_id: PaneId,
+    tab_id: SessionId,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'a, Message> {
+    let has_error = state.path_input_error.is_some();
+    let input = text_input("Type a path and press Enter", value)
+        .id(path_input_id(tab_id, pane_id))
+        .on_input(move |value| Message::Sftp(SftpMessage::PathInputChanged(tab_id, pane_id, value)))
+        .on_submit(Message::Sftp(SftpMessage::PathSubmit(tab_id, pane_id)))
+        .padding([2, 6])
+        .size(fonts.button_small)
+        .width(Fill)
+        .style(move |_theme, _status| text_input::Style {
+            background: theme.background.into(),
+            border: iced::Border {
+                color: if has_error { ERROR_COLOR } else { theme.accent },
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            icon: theme.text_muted,
+            placeholder: theme.text_muted,
+            value: theme.text_primary,
+            selection: theme.accent,
+        });
+
+    match &state.path_input_error {
+        Some(error) => row![
+            input,
+            text(error)
+                .size(fonts.button_small)
+                .color(ERROR_COLOR)
+                .wrapping(text::Wrapping::None),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .width(Fill)
+        .into(),
+        None => input.into(),
+    }
+}
+
 /// File list for a single pane
+#[allow(clippy::too_many_arguments)]
 pub fn pane_file_list<'a>(
     state: &'a FilePaneState,
     pane_id: PaneId,
     tab_id: SessionId,
     context_menu_open: bool,
+    modifiers: iced::keyboard::Modifiers,
     column_widths: &ColumnWidths,
     theme: Theme,
     fonts: ScaledFonts,
@@ -470,11 +783,14 @@ pub fn pane_file_list<'a>(
         .map(|(original_index, entry)| {
             pane_file_entry_row(
                 entry,
+                state.dir_size(&entry.path),
                 original_index,
                 state.is_selected(original_index),
+                state.cursor_index == Some(original_index),
                 tab_id,
                 pane_id,
                 context_menu_open,
+                modifiers,
                 column_widths,
                 theme,
                 fonts,
@@ -490,6 +806,14 @@ pub fn pane_file_list<'a>(
         .id(state.scrollable_id.clone())
         .height(Fill)
         .width(Fill)
+        .on_scroll(move |viewport| {
+            Message::Sftp(SftpMessage::PaneScrolled(
+                tab_id,
+                pane_id,
+                viewport.absolute_offset().y,
+                viewport.bounds().height,
+            ))
+        })
         .direction(scrollable::Direction::Vertical(
             scrollable::Scrollbar::new().width(6).scroller_width(6),
         ))
@@ -622,11 +946,14 @@ fn table_header_cell<'a>(
 #[allow(clippy::too_many_arguments)]
 pub fn pane_file_entry_row(
     entry: &FileEntry,
+    dir_size: Option<DirSize>,
     index: usize,
     is_selected: bool,
+    is_cursor: bool,
     tab_id: SessionId,
     pane_id: PaneId,
     context_menu_open: bool,
+    modifiers: iced::keyboard::Modifiers,
     column_widths: &ColumnWidths,
     theme: Theme,
     fonts: ScaledFonts,
@@ -635,7 +962,7 @@ pub fn pane_file_entry_row(
     let icon_data = file_icon_data(icon_type);
     let name = entry.name.clone();
     let size = if entry.is_dir {
-        "—".to_string()
+        dir_size.map_or_else(|| "—".to_string(), |size| size.label())
     } else {
         format_size(entry.size)
     };
@@ -652,17 +979,28 @@ pub fn pane_file_entry_row(
         theme.text_primary
     };
 
+    // Broken symlinks are flagged in the error color
+    let is_broken_symlink = entry.is_broken_symlink();
     let icon_color = if is_selected {
         theme.background
+    } else if is_broken_symlink {
+        ERROR_COLOR
     } else {
         theme.text_secondary
     };
 
     let path = entry.path.clone();
-    let is_navigable_dir = entry.is_navigable_dir();
+    let navigation_path = entry.navigation_path().map(PathBuf::from);
+    let is_parent = entry.is_parent();
+    // Folders open on a single click; files open in the viewer on a double click
+    let open_on_double_click = navigation_path.is_none() && !is_parent && !entry.is_dir;
 
     let modified = entry.formatted_modified();
-    let kind = entry.kind_description();
+    let kind = if is_broken_symlink {
+        format!("{} (broken)", entry.kind_label())
+    } else {
+        entry.kind_label()
+    };
 
     let name_row = row![
         icon_with_color(icon_data, 16, icon_color),
@@ -682,7 +1020,9 @@ pub fn pane_file_entry_row(
             .size(fonts.label)
             .color(theme.text_muted),
         row![
-            text(kind).size(fonts.label).color(theme.text_secondary),
+            text(kind.clone())
+                .size(fonts.label)
+                .color(theme.text_secondary),
             text("·").size(fonts.label).color(theme.text_muted),
             text(size.clone())
                 .size(fonts.label)
@@ -718,6 +1058,11 @@ pub fn pane_file_entry_row(
     } else {
         theme.text_secondary
     };
+    let kind_color = if is_broken_symlink && !is_selected {
+        ERROR_COLOR
+    } else {
+        secondary_color
+    };
 
     let content = row![
         container(name_with_tooltip).width(Length::Fixed(column_widths.name)),
@@ -740,7 +1085,7 @@ pub fn pane_file_entry_row(
         container(
             text(kind)
                 .size(fonts.body)
-                .color(secondary_color)
+                .color(kind_color)
                 .wrapping(text::Wrapping::None),
         )
         .width(Fill)
@@ -765,7 +1110,16 @@ pub fn pane_file_entry_row(
         iced::widget::button::Style {
             background: Some(background.into()),
             text_color,
-            border: iced::Border::default(),
+            // Outline the keyboard cursor when it rests on an unselected row
+            border: if is_cursor && !is_selected {
+                iced::Border {
+                    color: theme.accent,
+                    width: 1.0,
+                    radius: 0.0.into(),
+                }
+            } else {
+                iced::Border::default()
+            },
             ..Default::default()
         }
     })
@@ -774,9 +1128,19 @@ pub fn pane_file_entry_row(
 
     let btn = if context_menu_open {
         btn
-    } else if is_navigable_dir {
+    } else if modifiers.shift() && !is_parent {
+        btn.on_press(Message::Sftp(SftpMessage::PaneSelectRange(
+            tab_id, pane_id, index,
+        )))
+    } else if modifiers.command() && !is_parent {
+        btn.on_press(Message::Sftp(SftpMessage::PaneToggleSelect(
+            tab_id, pane_id, index,
+        )))
+    } else if let Some(navigation_path) = navigation_path {
         btn.on_press(Message::Sftp(SftpMessage::PaneNavigate(
-            tab_id, pane_id, path,
+            tab_id,
+            pane_id,
+            navigation_path,
         )))
     } else {
         btn.on_press(Message::Sftp(SftpMessage::PaneSelect(
@@ -789,6 +1153,13 @@ pub fn pane_file_entry_row(
     if context_menu_open {
         area.into()
     } else {
+        let area = if open_on_double_click {
+            area.on_double_click(Message::Sftp(SftpMessage::PaneOpenFile(
+                tab_id, pane_id, index,
+            )))
+        } else {
+            area
+        };
         area.on_right_press(move |x, y| {
             Message::Sftp(SftpMessage::ShowContextMenu(
                 tab_id,
@@ -819,7 +1190,7 @@ pub fn pane_footer<'a>(
     } else if selected_count > 0 {
         format!("{} of {} items selected", selected_count, visible_count)
     } else if visible_count != total_count {
-        format!("{} of {} items (filtered)", visible_count, total_count)
+        format!("{} of {} items", visible_count, total_count)
     } else {
         format!("{} items", total_count)
     };
@@ -2,14 +2,19 @@
 //!
 //! This module contains state structs for the SFTP dual-pane browser.
 
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 use iced::widget::Id;
+use secrecy::SecretString;
+use uuid::Uuid;
 
 use crate::message::SessionId;
-use crate::sftp::{FileEntry, SortOrder, is_safe_sftp_entry_name};
+use crate::sftp::chmod::ChmodProgress;
+use crate::sftp::{DirSize, FileEntry, SortOrder, SyncOptions, is_safe_sftp_entry_name};
 
 use super::types::{
     ColumnWidths, ContextMenuState, PaneId, PaneSource, PermissionBit, PermissionBits, SftpColumn,
@@ -19,6 +24,21 @@ use super::types::{
 const SFTP_VISIBLE_REBUILD_LOG_ENTRY_THRESHOLD: usize = 1_000;
 const SFTP_VISIBLE_REBUILD_LOG_DURATION_THRESHOLD: Duration = Duration::from_millis(8);
 
+/// A directory size calculation still running in a pane
+#[derive(Debug, Clone)]
+struct DirSizeWalk {
+    id: Uuid,
+    cancel: Arc<AtomicBool>,
+}
+
+/// A recursive permission change started from the permissions dialog
+#[derive(Debug, Clone)]
+struct PermissionWalk {
+    id: Uuid,
+    cancel: Arc<AtomicBool>,
+    progress: ChmodProgress,
+}
+
 /// State for a single file browser pane (can be local or remote)
 #[derive(Debug, Clone)]
 pub struct FilePaneState {
@@ -28,6 +48,11 @@ pub struct FilePaneState {
     visible_entry_indices: Vec<usize>,
     pub selected_indices: HashSet<usize>,
     pub last_selected_index: Option<usize>, // For shift-click range selection
+    /// Entry the keyboard cursor is on; may sit outside the selection
+    pub cursor_index: Option<usize>,
+    /// Scroll position and height of the file list, for keeping the cursor visible
+    pub scroll_offset: f32,
+    pub viewport_height: f32,
     pub sort_order: SortOrder,
     pub loading: bool,
     pub error: Option<String>,
@@ -35,7 +60,16 @@ pub struct FilePaneState {
     pub filter_text: String,
     pub scrollable_id: Id,
     pub actions_menu_open: bool,
+    pub bookmarks_menu_open: bool,
+    /// Text typed into the path bar; `Some` while it is being edited
+    pub path_input: Option<String>,
+    /// Why the typed path could not be opened
+    pub path_input_error: Option<String>,
     pub column_widths: ColumnWidths,
+    /// Calculated directory sizes for this pane's source, kept across
+    /// refreshes and navigation
+    dir_sizes: HashMap<PathBuf, DirSize>,
+    dir_size_walks: HashMap<PathBuf, DirSizeWalk>,
 }
 
 impl FilePaneState {
@@ -50,6 +84,9 @@ impl FilePaneState {
             visible_entry_indices: Vec::new(),
             selected_indices: HashSet::new(),
             last_selected_index: None,
+            cursor_index: None,
+            scroll_offset: 0.0,
+            viewport_height: 0.0,
             sort_order: SortOrder::default(),
             loading: true,
             error: None,
@@ -57,7 +94,12 @@ impl FilePaneState {
             filter_text: String::new(),
             scrollable_id: Id::unique(),
             actions_menu_open: false,
+            bookmarks_menu_open: false,
+            path_input: None,
+            path_input_error: None,
             column_widths: ColumnWidths::default(),
+            dir_sizes: HashMap::new(),
+            dir_size_walks: HashMap::new(),
         }
     }
 
@@ -72,6 +114,9 @@ impl FilePaneState {
             visible_entry_indices: Vec::new(),
             selected_indices: HashSet::new(),
             last_selected_index: None,
+            cursor_index: None,
+            scroll_offset: 0.0,
+            viewport_height: 0.0,
             sort_order: SortOrder::default(),
             loading: true,
             error: None,
@@ -79,7 +124,12 @@ impl FilePaneState {
             filter_text: String::new(),
             scrollable_id: Id::unique(),
             actions_menu_open: false,
+            bookmarks_menu_open: false,
+            path_input: None,
+            path_input_error: None,
             column_widths,
+            dir_sizes: HashMap::new(),
+            dir_size_walks: HashMap::new(),
         }
     }
 
@@ -92,6 +142,73 @@ impl FilePaneState {
         self.error = None;
     }
 
+    /// Calculated (or partially calculated) size of a directory
+    pub fn dir_size(&self, path: &Path) -> Option<DirSize> {
+        self.dir_sizes.get(path).copied()
+    }
+
+    pub fn is_calculating_size(&self, path: &Path) -> bool {
+        self.dir_size_walks.contains_key(path)
+    }
+
+    /// Register a new size walk for `path`, cancelling any running one.
+    /// Returns the walk id and its cancel flag.
+    pub fn start_dir_size(&mut self, path: PathBuf) -> (Uuid, Arc<AtomicBool>) {
+        self.cancel_dir_size(&path);
+        let walk = DirSizeWalk {
+            id: Uuid::new_v4(),
+            cancel: Arc::new(AtomicBool::new(false)),
+        };
+        let started = (walk.id, walk.cancel.clone());
+        self.dir_sizes.insert(path.clone(), DirSize::default());
+        self.dir_size_walks.insert(path, walk);
+        started
+    }
+
+    /// Apply a progress update or final result from walk `walk_id`. Updates
+    /// from cancelled or superseded walks are ignored; returns whether the
+    /// update was applied.
+    pub fn update_dir_size(
+        &mut self,
+        walk_id: Uuid,
+        path: &Path,
+        result: &Result<DirSize, String>,
+    ) -> bool {
+        if self.dir_size_walks.get(path).map(|walk| walk.id) != Some(walk_id) {
+            return false;
+        }
+        match result {
+            Ok(size) => {
+                self.dir_sizes.insert(path.to_path_buf(), *size);
+                if size.complete {
+                    self.dir_size_walks.remove(path);
+                }
+            }
+            Err(_) => {
+                self.dir_sizes.remove(path);
+                self.dir_size_walks.remove(path);
+            }
+        }
+        true
+    }
+
+    /// Stop a running size walk and drop its partial total
+    pub fn cancel_dir_size(&mut self, path: &Path) {
+        if let Some(walk) = self.dir_size_walks.remove(path) {
+            walk.cancel.store(true, Ordering::Relaxed);
+            self.dir_sizes.remove(path);
+        }
+    }
+
+    /// Forget all sizes, e.g. when the pane switches to another host
+    pub fn reset_dir_sizes(&mut self) {
+        for walk in self.dir_size_walks.values() {
+            walk.cancel.store(true, Ordering::Relaxed);
+        }
+        self.dir_size_walks.clear();
+        self.dir_sizes.clear();
+    }
+
     pub fn clear_entries(&mut self) {
         self.entries.clear();
         self.visible_entry_indices.clear();
@@ -110,8 +227,86 @@ impl FilePaneState {
         if index < self.entries.len() {
             self.selected_indices.insert(index);
             self.last_selected_index = Some(index);
+            self.cursor_index = Some(index);
         } else {
             self.last_selected_index = None;
+            self.cursor_index = None;
+        }
+    }
+
+    /// Add or remove one item from the selection (Ctrl/Cmd+click)
+    pub fn toggle_select(&mut self, index: usize) {
+        if index >= self.entries.len() {
+            return;
+        }
+        if !self.selected_indices.remove(&index) {
+            self.selected_indices.insert(index);
+        }
+        self.last_selected_index = Some(index);
+        self.cursor_index = Some(index);
+    }
+
+    /// Select the visible items between the last clicked item and `index`,
+    /// inclusive (Shift+click). Without an anchor this is a plain select.
+    pub fn select_range(&mut self, index: usize) {
+        let Some(anchor) = self.last_selected_index else {
+            self.select(index);
+            return;
+        };
+        if index >= self.entries.len() {
+            return;
+        }
+        let (start, end) = (anchor.min(index), anchor.max(index));
+        self.selected_indices = self
+            .visible_entry_indices
+            .iter()
+            .copied()
+            .filter(|&i| (start..=end).contains(&i) && !self.entries[i].is_parent())
+            .collect();
+        self.cursor_index = Some(index);
+    }
+
+    /// Visible row the keyboard cursor is on, falling back to the last
+    /// clicked item.
+    pub fn cursor_position(&self) -> Option<usize> {
+        let cursor = self.cursor_index.or(self.last_selected_index)?;
+        self.visible_entry_indices
+            .iter()
+            .position(|&index| index == cursor)
+    }
+
+    /// Move the keyboard cursor to visible row `position`, updating the
+    /// selection according to `selection`.
+    pub fn move_cursor(&mut self, position: usize, selection: CursorSelection) {
+        let Some(&index) = self.visible_entry_indices.get(position) else {
+            return;
+        };
+        match selection {
+            CursorSelection::Replace => self.select(index),
+            CursorSelection::Extend => self.select_range(index),
+            CursorSelection::Keep => self.cursor_index = Some(index),
+        }
+    }
+
+    /// Add or remove the entry under the keyboard cursor from the selection.
+    pub fn toggle_cursor_selection(&mut self) {
+        if let Some(index) = self.cursor_index {
+            self.toggle_select(index);
+        }
+    }
+
+    /// Scroll offset that brings visible row `position` fully into view, or
+    /// `None` when it is already visible.
+    pub fn reveal_offset(&self, position: usize, row_height: f32) -> Option<f32> {
+        let top = position as f32 * row_height;
+        let bottom = top + row_height;
+        if self.viewport_height <= 0.0 || top < self.scroll_offset {
+            // Before the first scroll event the viewport is unknown; align the row to the top.
+            Some(top)
+        } else if bottom > self.scroll_offset + self.viewport_height {
+            Some(bottom - self.viewport_height)
+        } else {
+            None
         }
     }
 
@@ -208,10 +403,87 @@ impl FilePaneState {
         filter_text.is_empty() || entry.name.to_lowercase().contains(filter_text)
     }
 
+    /// Switch the path bar to an editable input holding the current path
+    pub fn start_path_edit(&mut self) {
+        let mut path = self.current_path.display().to_string();
+        if !path.ends_with(['/', std::path::MAIN_SEPARATOR]) {
+            path.push(std::path::MAIN_SEPARATOR);
+        }
+        self.path_input = Some(path);
+        self.path_input_error = None;
+    }
+
+    pub fn cancel_path_edit(&mut self) {
+        self.path_input = None;
+        self.path_input_error = None;
+    }
+
+    /// Complete the last component of the typed path against the entries of
+    /// the current directory. Returns true when the input changed.
+    pub fn complete_path_input(&mut self) -> bool {
+        let Some(input) = self.path_input.as_deref() else {
+            return false;
+        };
+        match complete_path(input, &self.current_path, &self.entries) {
+            Some(completed) => {
+                self.path_input = Some(completed);
+                self.path_input_error = None;
+                true
+            }
+            None => false,
+        }
+    }
+
     fn clear_selection(&mut self) {
         self.selected_indices.clear();
         self.last_selected_index = None;
+        self.cursor_index = None;
+    }
+}
+
+/// Tab-complete the last component of `input` against `entries`, the listing
+/// of `current_dir`. Only names inside `current_dir` can be completed, since
+/// those are the only ones known without another round trip. A unique folder
+/// match gets a trailing separator so the next component can be typed.
+fn complete_path(input: &str, current_dir: &Path, entries: &[FileEntry]) -> Option<String> {
+    let split = input.rfind(['/', std::path::MAIN_SEPARATOR])?;
+    let (dir, separator, prefix) = (&input[..=split], &input[split..=split], &input[split + 1..]);
+    if Path::new(dir) != current_dir {
+        return None;
     }
+
+    let matches: Vec<&FileEntry> = entries
+        .iter()
+        .filter(|entry| !entry.is_parent() && entry.name.starts_with(prefix))
+        .collect();
+    let completed = match matches.as_slice() {
+        [] => return None,
+        [only] if only.is_dir => format!("{}{separator}", only.name),
+        [only] => only.name.clone(),
+        [first, rest @ ..] => rest.iter().fold(first.name.clone(), |common, entry| {
+            common
+                .chars()
+                .zip(entry.name.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        }),
+    };
+    if completed == prefix {
+        return None;
+    }
+    Some(format!("{dir}{completed}"))
+}
+
+/// How moving the keyboard cursor changes the selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorSelection {
+    /// Select only the row under the cursor (plain arrow keys)
+    Replace,
+    /// Select from the anchor to the cursor (Shift)
+    Extend,
+    /// Leave the selection as it is (Ctrl/Cmd)
+    Keep,
 }
 
 /// State for SFTP dialogs (New Folder, Rename, etc.)
@@ -222,6 +494,9 @@ pub struct SftpDialogState {
     pub input_value: String,
     pub error: Option<String>,
     pub delete_hold_started: Option<Instant>,
+    /// Sudo password entry, present once a remote operation was refused with
+    /// "permission denied" and the dialog offers a privileged retry
+    pub sudo_password: Option<SecretString>,
 }
 
 impl SftpDialogState {
@@ -232,6 +507,18 @@ impl SftpDialogState {
             input_value: String::new(),
             error: None,
             delete_hold_started: None,
+            sudo_password: None,
+        }
+    }
+
+    pub fn new_file(pane_id: PaneId) -> Self {
+        Self {
+            dialog_type: SftpDialogType::NewFile { open_after: true },
+            target_pane: pane_id,
+            input_value: String::new(),
+            error: None,
+            delete_hold_started: None,
+            sudo_password: None,
         }
     }
 
@@ -244,6 +531,18 @@ impl SftpDialogState {
             input_value: original_name,
             error: None,
             delete_hold_started: None,
+            sudo_password: None,
+        }
+    }
+
+    pub fn rename_bookmark(pane_id: PaneId, bookmark_id: Uuid, name: String) -> Self {
+        Self {
+            dialog_type: SftpDialogType::RenameBookmark { bookmark_id },
+            target_pane: pane_id,
+            input_value: name,
+            error: None,
+            delete_hold_started: None,
+            sudo_password: None,
         }
     }
 
@@ -254,6 +553,7 @@ impl SftpDialogState {
             input_value: String::new(),
             error: None,
             delete_hold_started: None,
+            sudo_password: None,
         }
     }
 
@@ -262,17 +562,88 @@ impl SftpDialogState {
         name: String,
         path: PathBuf,
         permissions: PermissionBits,
+        is_dir: bool,
     ) -> Self {
         Self {
             dialog_type: SftpDialogType::EditPermissions {
                 name,
                 path,
                 permissions,
+                is_dir,
+                recursive: false,
+                searchable_dirs: true,
             },
             target_pane: pane_id,
             input_value: String::new(),
             error: None,
             delete_hold_started: None,
+            sudo_password: None,
+        }
+    }
+
+    pub fn open_with(pane_id: PaneId, name: String, path: PathBuf) -> Self {
+        Self {
+            dialog_type: SftpDialogType::OpenWith { name, path },
+            target_pane: pane_id,
+            input_value: String::new(),
+            error: None,
+            delete_hold_started: None,
+            sudo_password: None,
+        }
+    }
+
+    pub fn properties(pane_id: PaneId, name: String, path: PathBuf) -> Self {
+        Self {
+            dialog_type: SftpDialogType::Properties {
+                name,
+                path,
+                properties: None,
+            },
+            target_pane: pane_id,
+            input_value: String::new(),
+            error: None,
+            delete_hold_started: None,
+            sudo_password: None,
+        }
+    }
+
+    pub fn change_owner(
+        pane_id: PaneId,
+        name: String,
+        path: PathBuf,
+        is_dir: bool,
+        owner: String,
+        group: String,
+    ) -> Self {
+        Self {
+            dialog_type: SftpDialogType::ChangeOwner {
+                name,
+                path,
+                is_dir,
+                owner,
+                group,
+                recursive: false,
+            },
+            target_pane: pane_id,
+            input_value: String::new(),
+            error: None,
+            delete_hold_started: None,
+            sudo_password: None,
+        }
+    }
+
+    pub fn sync(source_pane: PaneId, target_pane: PaneId, options: SyncOptions) -> Self {
+        Self {
+            dialog_type: SftpDialogType::Sync {
+                source_pane,
+                options,
+                plan: None,
+            },
+            target_pane,
+            input_value: String::new(),
+            error: None,
+            delete_hold_started: None,
+            sudo_password: None,
         }
     }
 
@@ -280,6 +651,14 @@ impl SftpDialogState {
         match &self.dialog_type {
             SftpDialogType::Delete { entries } => !entries.is_empty(),
             SftpDialogType::EditPermissions { .. } => true, // Always valid
+            SftpDialogType::Sync { plan, .. } => plan.as_ref().is_some_and(|p| !p.is_empty()),
+            SftpDialogType::RenameBookmark { .. } | SftpDialogType::OpenWith { .. } => {
+                !self.input_value.trim().is_empty()
+            }
+            SftpDialogType::Properties { .. } => true,
+            SftpDialogType::ChangeOwner { owner, group, .. } => {
+                !owner.trim().is_empty() || !group.trim().is_empty()
+            }
             _ => {
                 let name = self.input_value.trim();
                 is_safe_sftp_entry_name(name)
@@ -287,6 +666,52 @@ impl SftpDialogState {
         }
     }
 
+    /// Ask for the sudo password after the operation was refused (or the
+    /// previous password was rejected)
+    pub fn prompt_for_sudo(&mut self, error: String) {
+        self.sudo_password = Some(SecretString::from(String::new()));
+        self.error = Some(error);
+    }
+
+    /// Toggle applying permissions or ownership to a folder's whole tree
+    pub fn toggle_recursive_permissions(&mut self) {
+        if let SftpDialogType::EditPermissions {
+            is_dir: true,
+            recursive,
+            ..
+        }
+        | SftpDialogType::ChangeOwner {
+            is_dir: true,
+            recursive,
+            ..
+        } = &mut self.dialog_type
+        {
+            *recursive = !*recursive;
+        }
+    }
+
+    /// Update the typed owner or group of the Change Owner dialog
+    pub fn set_ownership_input(&mut self, is_group: bool, value: String) {
+        if let SftpDialogType::ChangeOwner { owner, group, .. } = &mut self.dialog_type {
+            if is_group {
+                *group = value;
+            } else {
+                *owner = value;
+            }
+            self.error = None;
+        }
+    }
+
+    /// Toggle giving folders execute where readable in a recursive change
+    pub fn toggle_searchable_dirs(&mut self) {
+        if let SftpDialogType::EditPermissions {
+            searchable_dirs, ..
+        } = &mut self.dialog_type
+        {
+            *searchable_dirs = !*searchable_dirs;
+        }
+    }
+
     /// Update a permission bit (for EditPermissions dialog)
     pub fn set_permission(&mut self, bit: PermissionBit, value: bool) {
         if let SftpDialogType::EditPermissions { permissions, .. } = &mut self.dialog_type {
@@ -328,6 +753,8 @@ pub struct DualPaneSftpState {
     pub context_menu: ContextMenuState,
     pub dialog: Option<SftpDialogState>,
     pub column_resize_drag: Option<ColumnResizeDrag>,
+    /// Recursive chmod running for the open permissions dialog
+    permission_walk: Option<PermissionWalk>,
 }
 
 impl DualPaneSftpState {
@@ -340,6 +767,7 @@ impl DualPaneSftpState {
             context_menu: ContextMenuState::default(),
             dialog: None,
             column_resize_drag: None,
+            permission_walk: None,
         }
     }
 
@@ -352,9 +780,17 @@ impl DualPaneSftpState {
             context_menu: ContextMenuState::default(),
             dialog: None,
             column_resize_drag: None,
+            permission_walk: None,
         }
     }
 
+    /// Start both panes with dotfiles shown or hidden
+    pub fn with_show_hidden(mut self, show_hidden: bool) -> Self {
+        self.left_pane.show_hidden = show_hidden;
+        self.right_pane.show_hidden = show_hidden;
+        self
+    }
+
     pub fn show_context_menu(&mut self, pane_id: PaneId, x: f32, y: f32) {
         self.context_menu.visible = true;
         self.context_menu.position = iced::Point::new(x, y);
@@ -371,11 +807,37 @@ impl DualPaneSftpState {
         self.hide_context_menu();
     }
 
+    pub fn show_new_file_dialog(&mut self) {
+        self.dialog = Some(SftpDialogState::new_file(self.active_pane));
+        self.hide_context_menu();
+    }
+
+    pub fn show_open_with_dialog(&mut self, name: String, path: PathBuf) {
+        self.dialog = Some(SftpDialogState::open_with(self.active_pane, name, path));
+        self.hide_context_menu();
+    }
+
     pub fn show_rename_dialog(&mut self, original_name: String) {
         self.dialog = Some(SftpDialogState::rename(self.active_pane, original_name));
         self.hide_context_menu();
     }
 
+    pub fn show_rename_bookmark_dialog(
+        &mut self,
+        pane_id: PaneId,
+        bookmark_id: Uuid,
+        name: String,
+    ) {
+        self.dialog = Some(SftpDialogState::rename_bookmark(pane_id, bookmark_id, name));
+        self.close_actions_menus();
+        self.hide_context_menu();
+    }
+
+    pub fn show_properties_dialog(&mut self, name: String, path: PathBuf) {
+        self.dialog = Some(SftpDialogState::properties(self.active_pane, name, path));
+        self.hide_context_menu();
+    }
+
     pub fn show_delete_dialog(&mut self, entries: Vec<(String, PathBuf, bool)>) {
         self.dialog = Some(SftpDialogState::delete(self.active_pane, entries));
         self.hide_context_menu();
@@ -386,24 +848,122 @@ impl DualPaneSftpState {
         name: String,
         path: PathBuf,
         permissions: PermissionBits,
+        is_dir: bool,
     ) {
         self.dialog = Some(SftpDialogState::edit_permissions(
             self.active_pane,
             name,
             path,
             permissions,
+            is_dir,
         ));
         self.hide_context_menu();
     }
 
+    /// Sync runs between one local and one remote pane
+    pub fn can_sync_panes(&self) -> bool {
+        self.left_pane.source.session_id().is_some()
+            != self.right_pane.source.session_id().is_some()
+    }
+
+    /// The local file that can be watched and uploaded into the other pane:
+    /// a single regular file selected in a local pane opposite a remote one
+    pub fn watch_candidate(&self, pane_id: PaneId) -> Option<&FileEntry> {
+        let other = match pane_id {
+            PaneId::Left => PaneId::Right,
+            PaneId::Right => PaneId::Left,
+        };
+        if self.pane(pane_id).source.session_id().is_some()
+            || self.pane(other).source.session_id().is_none()
+        {
+            return None;
+        }
+        match self.pane(pane_id).selected_entries().as_slice() {
+            [entry] if !entry.is_dir && !entry.is_symlink && !entry.is_parent() => Some(entry),
+            _ => None,
+        }
+    }
+
+    /// The files to compare: a single regular file selected in each pane,
+    /// left first
+    pub fn comparison_files(&self) -> Option<(&FileEntry, &FileEntry)> {
+        let single_file = |pane: &FilePaneState| match pane.selected_entries().as_slice() {
+            [entry] if !entry.is_dir && !entry.is_symlink && !entry.is_parent() => Some(*entry),
+            _ => None,
+        };
+        Some((
+            single_file(&self.left_pane)?,
+            single_file(&self.right_pane)?,
+        ))
+    }
+
+    /// Open the sync preview for syncing the active pane into the other one
+    pub fn show_sync_dialog(&mut self, options: SyncOptions) {
+        let target = match self.active_pane {
+            PaneId::Left => PaneId::Right,
+            PaneId::Right => PaneId::Left,
+        };
+        self.dialog = Some(SftpDialogState::sync(self.active_pane, target, options));
+        self.hide_context_menu();
+    }
+
+    /// Close the open dialog, stopping a recursive permission change it
+    /// started
     pub fn close_dialog(&mut self) {
         self.dialog = None;
+        if let Some(walk) = self.permission_walk.take() {
+            walk.cancel.store(true, Ordering::Relaxed);
+        }
     }
 
-    /// Close actions menus in both panes
+    /// Register a recursive permission change for the open dialog. Returns
+    /// the walk id and its cancel flag.
+    pub fn start_permission_walk(&mut self) -> (Uuid, Arc<AtomicBool>) {
+        if let Some(walk) = self.permission_walk.take() {
+            walk.cancel.store(true, Ordering::Relaxed);
+        }
+        let walk = PermissionWalk {
+            id: Uuid::new_v4(),
+            cancel: Arc::new(AtomicBool::new(false)),
+            progress: ChmodProgress::default(),
+        };
+        let started = (walk.id, walk.cancel.clone());
+        self.permission_walk = Some(walk);
+        started
+    }
+
+    /// Running count of the recursive permission change, if one is running
+    pub fn permission_progress(&self) -> Option<ChmodProgress> {
+        self.permission_walk.as_ref().map(|walk| walk.progress)
+    }
+
+    /// Record progress from walk `walk_id`; stale walks are ignored
+    pub fn update_permission_walk(&mut self, walk_id: Uuid, progress: ChmodProgress) {
+        if let Some(walk) = self.permission_walk.as_mut()
+            && walk.id == walk_id
+        {
+            walk.progress = progress;
+        }
+    }
+
+    /// Forget walk `walk_id` once it has finished. Returns false when it was
+    /// cancelled or superseded, so its result should be dropped.
+    pub fn finish_permission_walk(&mut self, walk_id: Uuid) -> bool {
+        if self.permission_walk.as_ref().map(|walk| walk.id) != Some(walk_id) {
+            return false;
+        }
+        self.permission_walk = None;
+        true
+    }
+
+    /// Close actions and bookmarks menus in both panes, and stop editing
+    /// either path bar
     pub fn close_actions_menus(&mut self) {
-        self.left_pane.actions_menu_open = false;
-        self.right_pane.actions_menu_open = false;
+        for pane in [&mut self.left_pane, &mut self.right_pane] {
+            pane.actions_menu_open = false;
+            pane.bookmarks_menu_open = false;
+            pane.cancel_path_edit();
+        }
     }
 
     pub fn pane_mut(&mut self, pane_id: PaneId) -> &mut FilePaneState {
@@ -450,11 +1010,41 @@ mod tests {
             path: PathBuf::from(name),
             is_dir: false,
             is_symlink: false,
+            symlink_target: None,
             size: 0,
             modified: Some(Utc::now()),
         }
     }
 
+    #[test]
+    fn dir_size_updates_from_superseded_walks_are_ignored() {
+        let mut state = FilePaneState::new_local();
+        let path = PathBuf::from("/srv/data");
+        let (first, first_cancel) = state.start_dir_size(path.clone());
+        let (second, _) = state.start_dir_size(path.clone());
+        assert!(first_cancel.load(Ordering::Relaxed));
+
+        let partial = DirSize {
+            bytes: 10,
+            files: 1,
+            complete: false,
+        };
+        assert!(!state.update_dir_size(first, &path, &Ok(partial)));
+        assert!(state.update_dir_size(second, &path, &Ok(partial)));
+        assert!(state.is_calculating_size(&path));
+
+        let done = DirSize {
+            complete: true,
+            ..partial
+        };
+        assert!(state.update_dir_size(second, &path, &Ok(done)));
+        assert!(!state.is_calculating_size(&path));
+        assert_eq!(state.dir_size(&path), Some(done));
+
+        state.reset_dir_sizes();
+        assert_eq!(state.dir_size(&path), None);
+    }
+
     #[test]
     fn visible_entries_keeps_parent_and_filters_hidden() {
         let mut state = FilePaneState::new_local();
@@ -500,6 +1090,37 @@ mod tests {
         assert_eq!(state.visible_entry_count(), 2);
     }
 
+    #[test]
+    fn path_completion_extends_to_unique_folder_or_common_prefix() {
+        let dir = |name: &str| FileEntry {
+            is_dir: true,
+            ..entry(name)
+        };
+        let mut state = FilePaneState::new_local();
+        state.current_path = PathBuf::from("/srv");
+        state.set_entries(vec![
+            dir(".."),
+            dir("www"),
+            entry("backup-1.tar"),
+            entry("backup-2.tar"),
+        ]);
+
+        state.path_input = Some("/srv/w".to_string());
+        assert!(state.complete_path_input());
+        assert_eq!(state.path_input.as_deref(), Some("/srv/www/"));
+
+        state.path_input = Some("/srv/b".to_string());
+        assert!(state.complete_path_input());
+        assert_eq!(state.path_input.as_deref(), Some("/srv/backup-"));
+        assert!(!state.complete_path_input());
+
+        // Only the current directory's entries are known
+        state.path_input = Some("/etc/w".to_string());
+        assert!(!state.complete_path_input());
+        state.path_input = Some("/srv/zzz".to_string());
+        assert!(!state.complete_path_input());
+    }
+
     #[test]
     fn set_error_clears_stale_entries_and_selection() {
         let mut state = FilePaneState::new_local();
@@ -527,6 +1148,90 @@ mod tests {
         assert_eq!(state.last_selected_index, Some(2));
     }
 
+    #[test]
+    fn toggle_select_adds_and_removes_items() {
+        let mut state = FilePaneState::new_local();
+        state.set_entries(vec![entry("one"), entry("two"), entry("three")]);
+        state.select(0);
+        state.toggle_select(2);
+
+        assert!(state.is_selected(0));
+        assert!(state.is_selected(2));
+        assert_eq!(state.last_selected_index, Some(2));
+
+        state.toggle_select(0);
+        assert!(!state.is_selected(0));
+        assert_eq!(state.selected_indices.len(), 1);
+    }
+
+    #[test]
+    fn select_range_covers_items_between_anchor_and_click() {
+        let mut state = FilePaneState::new_local();
+        state.set_entries(vec![entry("a"), entry("b"), entry("c"), entry("d")]);
+        let index_of = |state: &FilePaneState, name: &str| {
+            state
+                .entries
+                .iter()
+                .position(|entry| entry.name == name)
+                .unwrap()
+        };
+        let (b, d) = (index_of(&state, "b"), index_of(&state, "d"));
+
+        state.select(d);
+        state.select_range(b);
+
+        let mut selected: Vec<_> = state
+            .selected_entries()
+            .into_iter()
+            .map(|entry| entry.name.clone())
+            .collect();
+        selected.sort();
+        assert_eq!(selected, vec!["b", "c", "d"]);
+        assert_eq!(state.last_selected_index, Some(d));
+    }
+
+    #[test]
+    fn select_range_without_anchor_selects_single_item() {
+        let mut state = FilePaneState::new_local();
+        state.set_entries(vec![entry("one"), entry("two")]);
+
+        state.select_range(1);
+
+        assert!(state.is_selected(1));
+        assert_eq!(state.selected_indices.len(), 1);
+    }
+
+    #[test]
+    fn move_cursor_keep_leaves_selection_alone() {
+        let mut state = FilePaneState::new_local();
+        state.set_entries(vec![entry("a"), entry("b"), entry("c")]);
+        state.select(0);
+
+        state.move_cursor(2, CursorSelection::Keep);
+        assert_eq!(state.cursor_position(), Some(2));
+        assert_eq!(state.selected_indices.len(), 1);
+        assert!(state.is_selected(0));
+
+        state.toggle_cursor_selection();
+        assert!(state.is_selected(0));
+        assert!(state.is_selected(2));
+
+        state.move_cursor(1, CursorSelection::Replace);
+        assert!(state.is_selected(1));
+        assert_eq!(state.selected_indices.len(), 1);
+    }
+
+    #[test]
+    fn reveal_offset_scrolls_only_when_row_is_out_of_view() {
+        let mut state = FilePaneState::new_local();
+        state.scroll_offset = 100.0;
+        state.viewport_height = 200.0;
+
+        assert_eq!(state.reveal_offset(4, 35.0), None);
+        assert_eq!(state.reveal_offset(2, 35.0), Some(70.0));
+        assert_eq!(state.reveal_offset(8, 35.0), Some(115.0));
+    }
+
     #[test]
     fn select_ignores_out_of_range_index() {
         let mut state = FilePaneState::new_local();
@@ -562,6 +1267,103 @@ mod tests {
         assert!(!dialog.is_valid());
     }
 
+    #[test]
+    fn prompt_for_sudo_clears_rejected_password() {
+        use secrecy::ExposeSecret;
+
+        let mut dialog = SftpDialogState::new_folder(PaneId::Right);
+        assert!(dialog.sudo_password.is_none());
+
+        dialog.prompt_for_sudo("Permission denied".to_string());
+        dialog.sudo_password = Some(SecretString::from("wrong".to_string()));
+        dialog.prompt_for_sudo("Incorrect sudo password".to_string());
+
+        assert_eq!(dialog.error.as_deref(), Some("Incorrect sudo password"));
+        assert_eq!(
+            dialog.sudo_password.as_ref().map(|p| p.expose_secret()),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn closing_the_dialog_cancels_a_recursive_permission_change() {
+        let mut state = DualPaneSftpState::new(uuid::Uuid::new_v4());
+        state.show_permissions_dialog(
+            "site".to_string(),
+            PathBuf::from("/srv/site"),
+            PermissionBits::from_mode(0o644),
+            true,
+        );
+        let (walk_id, cancel) = state.start_permission_walk();
+        let progress = ChmodProgress {
+            changed: 12,
+            failed: 1,
+        };
+        state.update_permission_walk(walk_id, progress);
+        assert_eq!(state.permission_progress(), Some(progress));
+
+        state.close_dialog();
+        assert!(cancel.load(Ordering::Relaxed));
+        assert!(state.permission_progress().is_none());
+        assert!(!state.finish_permission_walk(walk_id));
+    }
+
+    #[test]
+    fn change_owner_needs_an_owner_or_group() {
+        let mut dialog = SftpDialogState::change_owner(
+            PaneId::Left,
+            "site".to_string(),
+            PathBuf::from("/srv/site"),
+            true,
+            String::new(),
+            String::new(),
+        );
+        assert!(!dialog.is_valid());
+
+        dialog.set_ownership_input(true, "www-data".to_string());
+        dialog.toggle_recursive_permissions();
+        assert!(dialog.is_valid());
+        assert!(matches!(
+            dialog.dialog_type,
+            SftpDialogType::ChangeOwner {
+                ref group,
+                recursive: true,
+                ..
+            } if group == "www-data"
+        ));
+    }
+
+    #[test]
+    fn comparison_needs_one_regular_file_in_each_pane() {
+        let mut state = DualPaneSftpState::new(uuid::Uuid::new_v4());
+        let dir = FileEntry {
+            is_dir: true,
+            ..entry("src")
+        };
+        state
+            .left_pane
+            .set_entries(vec![entry("a.conf"), entry("b.conf"), dir.clone()]);
+        state.right_pane.set_entries(vec![entry("a.conf"), dir]);
+
+        // Folders sort first
+        state.left_pane.select(1);
+        assert!(state.comparison_files().is_none());
+
+        state.right_pane.select(1);
+        let (left, right) = state.comparison_files().unwrap();
+        assert_eq!(
+            (left.name.as_str(), right.name.as_str()),
+            ("a.conf", "a.conf")
+        );
+
+        state.left_pane.toggle_select(2);
+        assert!(state.comparison_files().is_none());
+
+        state.left_pane.select(1);
+        state.right_pane.select(0);
+        assert!(state.comparison_files().is_none());
+    }
+
     #[test]
     fn dialog_is_valid_for_delete() {
         let empty_delete = SftpDialogState::delete(PaneId::Left, Vec::new());
@@ -4,8 +4,10 @@
 
 use iced::Point;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::message::SessionId;
+use crate::sftp::{FileProperties, SyncOptions, SyncPlan};
 
 /// Identifies which pane an action targets
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -45,12 +47,37 @@ impl PaneSource {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ContextMenuAction {
     Open,
+    /// Run a chosen program on the selected file
+    OpenWith,
     CopyToTarget,
+    /// Copy the selection next to itself with a " copy" suffix
+    Duplicate,
     Rename,
     Delete,
     Refresh,
     NewFolder,
+    /// Create an empty file in the pane's current directory
+    NewFile,
     EditPermissions,
+    SyncToTarget,
+    /// Put a small file's text on the clipboard
+    CopyContents,
+    /// Put a small file's bytes on the clipboard as base64
+    CopyBase64,
+    /// Start or stop uploading the selected local file whenever it changes
+    ToggleWatch,
+    /// Start or stop calculating the total size of the selected folders
+    CalculateSize,
+    /// Put the selected symlink's target on the clipboard
+    CopyLinkTarget,
+    /// Open the folder holding the selected local entry in the file manager
+    RevealInFileManager,
+    /// Put the selected local entry's absolute path on the clipboard
+    CopyPath,
+    /// Diff the file selected in each pane
+    Compare,
+    /// Show the selected entry's metadata
+    Properties,
 }
 
 /// State for the context menu
@@ -75,16 +102,59 @@ impl Default for ContextMenuState {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SftpDialogType {
     NewFolder,
+    /// Create an empty file, then open it in the file viewer when
+    /// `open_after` is set
+    NewFile {
+        open_after: bool,
+    },
     Rename {
         original_name: String,
     },
     Delete {
         entries: Vec<(String, std::path::PathBuf, bool)>,
     }, // (name, path, delete_recursively)
+    /// Change permission bits; folders can apply them to their whole tree
+    /// (`recursive`), optionally keeping subfolders searchable
     EditPermissions {
         name: String,
         path: std::path::PathBuf,
         permissions: PermissionBits,
+        is_dir: bool,
+        recursive: bool,
+        searchable_dirs: bool,
+    },
+    /// One-way sync preview from `source_pane` into the dialog's target
+    /// pane; `plan` is `None` while both trees are being scanned
+    Sync {
+        source_pane: PaneId,
+        options: SyncOptions,
+        plan: Option<SyncPlan>,
+    },
+    /// Rename a bookmark; the new name is the dialog's input value
+    RenameBookmark {
+        bookmark_id: Uuid,
+    },
+    /// Change owner and group (chown); each takes a name, a numeric id, or
+    /// is left blank to keep the current value
+    ChangeOwner {
+        name: String,
+        path: std::path::PathBuf,
+        is_dir: bool,
+        owner: String,
+        group: String,
+        recursive: bool,
+    },
+    /// Run the command typed as the input value on a file; remote files
+    /// are downloaded first and edits can be uploaded back
+    OpenWith {
+        name: String,
+        path: std::path::PathBuf,
+    },
+    /// Read-only metadata overview; `properties` is `None` while loading
+    Properties {
+        name: String,
+        path: std::path::PathBuf,
+        properties: Option<FileProperties>,
     },
 }
 
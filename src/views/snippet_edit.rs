@@ -3,8 +3,10 @@
 //! Full-page edit form for creating and editing snippets,
 //! including host selection for multi-host execution.
 
+use std::fmt;
+
 use iced::widget::{
-    Column, Row, Space, button, column, container, row, scrollable, text, text_input,
+    Column, Row, Space, button, column, container, pick_list, row, scrollable, text, text_input,
 };
 use iced::{Alignment, Element, Fill, Length};
 use uuid::Uuid;
@@ -14,7 +16,39 @@ use crate::config::DetectedOs;
 use crate::icons::{self, icon_with_color};
 use crate::message::{Message, SnippetField, SnippetMessage};
 use crate::theme::{BORDER_RADIUS, ScaledFonts, Theme};
-use crate::views::host_grid::os_icon_data;
+
+/// Entry of the OS filter dropdown
+#[derive(Debug, Clone, PartialEq)]
+struct OsFilterOption(Option<DetectedOs>);
+
+impl fmt::Display for OsFilterOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            None => f.write_str("Any OS"),
+            Some(DetectedOs::Linux) => f.write_str("Any Linux"),
+            Some(os) => f.write_str(os.display_name()),
+        }
+    }
+}
+
+/// "Any OS", "Any Linux", then the systems detected on saved hosts
+fn os_filter_options(
+    hosts: &[(Uuid, String, Option<DetectedOs>)],
+    current: Option<&DetectedOs>,
+) -> Vec<OsFilterOption> {
+    let mut options = vec![
+        OsFilterOption(None),
+        OsFilterOption(Some(DetectedOs::Linux)),
+    ];
+    let detected = hosts.iter().filter_map(|(_, _, os)| os.as_ref());
+    for os in detected.chain(current) {
+        let option = OsFilterOption(Some(os.clone()));
+        if !options.contains(&option) {
+            options.push(option);
+        }
+    }
+    options
+}
 
 /// Build the snippet edit form (full-page, replacing grid)
 pub fn snippet_edit_view(
@@ -131,9 +165,11 @@ pub fn snippet_edit_view(
     let hosts_label = text("Target Hosts")
         .size(fonts.body)
         .color(theme.text_secondary);
-    let hosts_help = text("Select which hosts to run this command on")
-        .size(fonts.label)
-        .color(theme.text_muted);
+    let hosts_help = text(
+        "Select which hosts to run this command on. The palette only suggests the snippet in sessions for these hosts.",
+    )
+    .size(fonts.label)
+    .color(theme.text_muted);
 
     let selected_hosts = state.selected_hosts.clone();
     let host_pills: Vec<Element<'static, Message>> = hosts
@@ -144,7 +180,7 @@ pub fn snippet_edit_view(
             let name = host_name.clone();
 
             // Get OS icon and color
-            let os_icon_bytes = os_icon_data(detected_os);
+            let os_icon_bytes = icons::os::for_detected(detected_os.as_ref());
             let os_color = match detected_os {
                 Some(os) => {
                     let (r, g, b) = os.icon_color();
@@ -312,6 +348,51 @@ pub fn snippet_edit_view(
         .into()
     };
 
+    // OS filter
+    let os_label = text("Operating System")
+        .size(fonts.body)
+        .color(theme.text_secondary);
+    let os_help = text("Only suggest this snippet in sessions for hosts running this OS")
+        .size(fonts.label)
+        .color(theme.text_muted);
+    let os_picker = pick_list(
+        os_filter_options(hosts, state.os_filter.as_ref()),
+        Some(OsFilterOption(state.os_filter.clone())),
+        |option: OsFilterOption| Message::Snippet(SnippetMessage::OsFilterChanged(option.0)),
+    )
+    .text_size(fonts.body)
+    .padding(10)
+    .width(Length::Fill)
+    .style(move |_theme, status| {
+        let border_color = match status {
+            pick_list::Status::Opened { .. } => theme.accent,
+            _ => theme.border,
+        };
+        pick_list::Style {
+            background: theme.surface.into(),
+            text_color: theme.text_primary,
+            placeholder_color: theme.text_muted,
+            handle_color: theme.text_muted,
+            border: iced::Border {
+                color: border_color,
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+        }
+    })
+    .menu_style(move |_theme| iced::overlay::menu::Style {
+        background: theme.surface.into(),
+        text_color: theme.text_primary,
+        selected_text_color: theme.text_on_accent(),
+        selected_background: theme.accent.into(),
+        border: iced::Border {
+            color: theme.border,
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        shadow: iced::Shadow::default(),
+    });
+
     // Action buttons
     let is_valid = state.is_valid();
 
@@ -428,6 +509,11 @@ pub fn snippet_edit_view(
         hosts_help,
         Space::new().height(8),
         hosts_list,
+        Space::new().height(16),
+        os_label,
+        os_help,
+        Space::new().height(8),
+        os_picker,
         Space::new().height(24),
         button_row,
     ]
@@ -450,3 +536,21 @@ pub fn snippet_edit_view(
         })
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn os_filter_options_list_detected_systems_once() {
+        let hosts = vec![
+            (Uuid::new_v4(), "a".to_string(), Some(DetectedOs::Ubuntu)),
+            (Uuid::new_v4(), "b".to_string(), Some(DetectedOs::Ubuntu)),
+            (Uuid::new_v4(), "c".to_string(), None),
+        ];
+
+        let options = os_filter_options(&hosts, Some(&DetectedOs::FreeBSD));
+        let labels: Vec<String> = options.iter().map(ToString::to_string).collect();
+        assert_eq!(labels, vec!["Any OS", "Any Linux", "Ubuntu", "FreeBSD"]);
+    }
+}
@@ -0,0 +1,317 @@
+//! Snippet palette overlay: fuzzy search snippets and recent commands and
+//! insert the pick into the active terminal
+
+use iced::widget::{Column, button, column, container, row, scrollable, text, text_input};
+use iced::{Alignment, Element, Fill, Length};
+
+use crate::config::{Host, SnippetHistoryConfig, SnippetsConfig};
+use crate::icons::{self, icon_with_color};
+use crate::message::{Message, SnippetPaletteEntry, UiMessage};
+use crate::theme::{BORDER_RADIUS, CARD_BORDER_RADIUS, ScaledFonts, Theme};
+use crate::views::quick_switcher::fuzzy_score;
+
+/// Recent commands offered below the snippets
+const MAX_RECENT_COMMANDS: usize = 20;
+
+/// Rows shown in the overlay
+const MAX_VISIBLE_ITEMS: usize = 10;
+
+pub fn snippet_palette_input_id() -> iced::widget::Id {
+    iced::widget::Id::new("snippet_palette_input")
+}
+
+#[derive(Debug, Clone)]
+pub struct SnippetPaletteItem {
+    pub title: String,
+    pub subtitle: String,
+    pub entry: SnippetPaletteEntry,
+}
+
+impl SnippetPaletteItem {
+    fn kind_label(&self) -> &'static str {
+        match self.entry {
+            SnippetPaletteEntry::Snippet(_) => "Snippet",
+            SnippetPaletteEntry::Command(_) => "Recent",
+        }
+    }
+
+    fn icon(&self) -> &'static [u8] {
+        match self.entry {
+            SnippetPaletteEntry::Snippet(_) => icons::ui::CODE,
+            SnippetPaletteEntry::Command(_) => icons::ui::TERMINAL,
+        }
+    }
+}
+
+/// The snippets that apply to `snippet_host`, then recently run commands
+/// that are not already a snippet's command
+pub fn available_items(
+    snippets: &SnippetsConfig,
+    snippet_host: Option<&Host>,
+    history: &SnippetHistoryConfig,
+) -> Vec<SnippetPaletteItem> {
+    let mut items: Vec<SnippetPaletteItem> = snippets
+        .snippets_for_host(snippet_host)
+        .map(|snippet| SnippetPaletteItem {
+            title: snippet.name.clone(),
+            subtitle: snippet.command.clone(),
+            entry: SnippetPaletteEntry::Snippet(snippet.id),
+        })
+        .collect();
+
+    for command in history.recent_commands(MAX_RECENT_COMMANDS) {
+        if items.iter().any(|item| item.subtitle == command) {
+            continue;
+        }
+        items.push(SnippetPaletteItem {
+            title: command.to_string(),
+            subtitle: "Recent command".to_string(),
+            entry: SnippetPaletteEntry::Command(command.to_string()),
+        });
+    }
+
+    items
+}
+
+/// Items matching `query`, best match first
+pub fn filter_items(items: &[SnippetPaletteItem], query: &str) -> Vec<SnippetPaletteItem> {
+    let mut scored: Vec<(u32, &SnippetPaletteItem)> = items
+        .iter()
+        .filter_map(|item| {
+            let title = fuzzy_score(query, &item.title);
+            let subtitle = fuzzy_score(query, &item.subtitle).map(|score| score / 2);
+            Some((title.max(subtitle)?, item))
+        })
+        .collect();
+
+    // Stable sort keeps snippets ahead of recent commands between equals.
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, item)| item.clone()).collect()
+}
+
+/// The entry at `selected` among the matches, clamped to the last one
+pub fn selected_entry(
+    items: &[SnippetPaletteItem],
+    query: &str,
+    selected: usize,
+) -> Option<SnippetPaletteEntry> {
+    let filtered = filter_items(items, query);
+    let index = selected.min(filtered.len().checked_sub(1)?);
+    filtered.into_iter().nth(index).map(|item| item.entry)
+}
+
+pub fn snippet_palette_view(
+    query: &str,
+    items: &[SnippetPaletteItem],
+    selected: usize,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let filtered = filter_items(items, query);
+    let selected = selected.min(filtered.len().saturating_sub(1));
+
+    let input = text_input("Insert a snippet or recent command...", query)
+        .id(snippet_palette_input_id())
+        .on_input(|value| Message::Ui(UiMessage::SnippetPaletteChanged(value)))
+        .padding([12, 14])
+        .size(fonts.body)
+        .style(move |_theme, status| {
+            let border_color = match status {
+                text_input::Status::Focused { .. } => theme.accent,
+                _ => theme.border,
+            };
+            text_input::Style {
+                background: theme.background.into(),
+                border: iced::Border {
+                    color: border_color,
+                    width: 1.0,
+                    radius: BORDER_RADIUS.into(),
+                },
+                icon: theme.text_muted,
+                placeholder: theme.text_muted,
+                value: theme.text_primary,
+                selection: theme.selected,
+            }
+        });
+
+    let list: Element<'static, Message> = if filtered.is_empty() {
+        let message = if items.is_empty() {
+            "No snippets or recent commands yet"
+        } else {
+            "No matches"
+        };
+        container(text(message).size(fonts.body).color(theme.text_muted))
+            .padding(20)
+            .width(Fill)
+            .align_x(Alignment::Center)
+            .into()
+    } else {
+        // Keep the selected row inside the visible window.
+        let skip = (selected + 1).saturating_sub(MAX_VISIBLE_ITEMS);
+        let rows: Vec<Element<'static, Message>> = filtered
+            .into_iter()
+            .enumerate()
+            .skip(skip)
+            .take(MAX_VISIBLE_ITEMS)
+            .map(|(index, item)| palette_row(index == selected, item, theme, fonts))
+            .collect();
+        scrollable(Column::with_children(rows).spacing(4))
+            .height(Length::Fixed(420.0))
+            .into()
+    };
+
+    let panel = container(column![input, list].spacing(10))
+        .width(Length::Fixed(560.0))
+        .padding(12)
+        .style(move |_| container::Style {
+            background: Some(theme.surface.into()),
+            border: iced::Border {
+                color: theme.border,
+                width: 1.0,
+                radius: CARD_BORDER_RADIUS.into(),
+            },
+            shadow: iced::Shadow {
+                color: iced::Color {
+                    a: 0.45,
+                    ..iced::Color::BLACK
+                },
+                offset: iced::Vector::new(0.0, 8.0),
+                blur_radius: 28.0,
+            },
+            ..Default::default()
+        });
+
+    button(
+        container(
+            container(panel)
+                .width(Fill)
+                .height(Fill)
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center)
+                .padding([48, 24]),
+        )
+        .width(Fill)
+        .height(Fill)
+        .style(move |_| container::Style {
+            background: Some(iced::Color::from_rgba(0.0, 0.0, 0.0, 0.55).into()),
+            ..Default::default()
+        }),
+    )
+    .padding(0)
+    .width(Fill)
+    .height(Fill)
+    .style(|_, _| button::Style {
+        background: None,
+        ..Default::default()
+    })
+    .on_press(Message::Ui(UiMessage::SnippetPaletteClose))
+    .into()
+}
+
+fn palette_row(
+    selected: bool,
+    item: SnippetPaletteItem,
+    theme: Theme,
+    fonts: ScaledFonts,
+) -> Element<'static, Message> {
+    let background = if selected {
+        theme.selected
+    } else {
+        theme.surface
+    };
+    let icon_color = if selected {
+        theme.text_primary
+    } else {
+        theme.text_secondary
+    };
+    let hint = if selected { "Enter" } else { item.kind_label() };
+
+    button(
+        row![
+            container(icon_with_color(item.icon(), 17, icon_color))
+                .width(32)
+                .height(32)
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center)
+                .style(move |_| container::Style {
+                    background: Some(theme.background.into()),
+                    border: iced::Border {
+                        color: theme.border,
+                        width: 1.0,
+                        radius: 6.0.into(),
+                    },
+                    ..Default::default()
+                }),
+            column![
+                text(item.title.clone())
+                    .size(fonts.body)
+                    .color(theme.text_primary)
+                    .wrapping(text::Wrapping::None),
+                text(item.subtitle.clone())
+                    .size(fonts.label)
+                    .color(theme.text_muted)
+                    .wrapping(text::Wrapping::None),
+            ]
+            .spacing(2)
+            .width(Fill),
+            text(hint).size(fonts.label).color(theme.text_secondary),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center),
+    )
+    .padding([9, 10])
+    .width(Fill)
+    .style(move |_theme, status| {
+        let bg = match status {
+            button::Status::Hovered => theme.hover,
+            _ => background,
+        };
+        button::Style {
+            background: Some(bg.into()),
+            text_color: theme.text_primary,
+            border: iced::Border {
+                color: if selected {
+                    theme.accent
+                } else {
+                    iced::Color::TRANSPARENT
+                },
+                width: if selected { 1.0 } else { 0.0 },
+                radius: BORDER_RADIUS.into(),
+            },
+            ..Default::default()
+        }
+    })
+    .on_press(Message::Ui(UiMessage::SnippetPaletteRun(item.entry)))
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn item(title: &str, subtitle: &str, entry: SnippetPaletteEntry) -> SnippetPaletteItem {
+        SnippetPaletteItem {
+            title: title.to_string(),
+            subtitle: subtitle.to_string(),
+            entry,
+        }
+    }
+
+    #[test]
+    fn selection_follows_filtered_order_and_clamps() {
+        let restart = SnippetPaletteEntry::Snippet(Uuid::new_v4());
+        let disk = SnippetPaletteEntry::Command("df -h".to_string());
+        let items = vec![
+            item("Restart nginx", "systemctl restart nginx", restart.clone()),
+            item("df -h", "Recent command", disk.clone()),
+        ];
+
+        assert_eq!(selected_entry(&items, "", 0), Some(restart.clone()));
+        assert_eq!(selected_entry(&items, "", 1), Some(disk.clone()));
+        assert_eq!(selected_entry(&items, "", 5), Some(disk.clone()));
+        assert_eq!(selected_entry(&items, "df", 0), Some(disk));
+        assert_eq!(selected_entry(&items, "nginx", 3), Some(restart));
+        assert_eq!(selected_entry(&items, "xyz", 0), None);
+    }
+}
@@ -10,7 +10,7 @@ use crate::theme::{ScaledFonts, Theme};
 use crate::widgets::mouse_area;
 
 const CONTEXT_MENU_WIDTH: f32 = 220.0;
-const ESTIMATED_MENU_HEIGHT: f32 = 136.0;
+const ESTIMATED_MENU_HEIGHT: f32 = 290.0;
 
 /// State for the terminal tab context menu
 #[derive(Debug, Clone)]
@@ -89,6 +89,7 @@ pub fn tab_context_menu_overlay(
     window_size: iced::Size,
     has_log_file: bool,
     has_log_dir: bool,
+    in_broadcast: bool,
 ) -> Element<'_, Message> {
     if !state.visible {
         return Space::new().into();
@@ -107,6 +108,42 @@ pub fn tab_context_menu_overlay(
             theme,
             fonts,
         ),
+        context_menu_item(
+            "Clear Scrollback",
+            TabContextMenuAction::ClearScrollback,
+            tab_id,
+            true,
+            theme,
+            fonts,
+        ),
+        context_menu_item(
+            "Reset Terminal",
+            TabContextMenuAction::ResetTerminal,
+            tab_id,
+            true,
+            theme,
+            fonts,
+        ),
+        context_menu_item(
+            "Reset and Run `reset`",
+            TabContextMenuAction::SendReset,
+            tab_id,
+            true,
+            theme,
+            fonts,
+        ),
+        context_menu_item(
+            if in_broadcast {
+                "Remove from Broadcast"
+            } else {
+                "Add to Broadcast"
+            },
+            TabContextMenuAction::ToggleBroadcast,
+            tab_id,
+            true,
+            theme,
+            fonts,
+        ),
         context_menu_item(
             "Open Log File",
             TabContextMenuAction::OpenLogFile,
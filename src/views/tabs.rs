@@ -2,6 +2,7 @@
 
 use iced::widget::{Column, Row, Space, button, column, container, row, text, text_input, tooltip};
 use iced::{Alignment, Color, Element, Length, Padding};
+use std::collections::HashSet;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
@@ -9,8 +10,7 @@ use crate::app::{FocusSection, SidebarState, View};
 use crate::config::HostsConfig;
 use crate::icons::{self, icon_with_color};
 use crate::message::{Message, TabMessage, UiMessage};
-use crate::theme::{ScaledFonts, Theme};
-use crate::views::host_grid::os_icon_data;
+use crate::theme::{STATUS_FAILURE, ScaledFonts, Theme};
 use crate::widgets::mouse_area as capture_mouse_area;
 use crate::widgets::{animated_width, drag_tab_row};
 
@@ -24,12 +24,18 @@ pub struct Tab {
     pub host_id: Option<Uuid>,
     /// Whether the tab needs attention because a background terminal event happened.
     pub needs_attention: bool,
+    /// Whether the terminal printed output since the tab was last viewed.
+    pub has_unseen_output: bool,
     /// Agent activity inferred from terminal title updates.
     pub agent_status: Option<TabAgentStatus>,
     /// Stable per-host terminal session number.
     pub session_number: Option<usize>,
     /// Inline rename editor contents while this tab is being renamed.
     pub rename_value: Option<String>,
+    /// Title set by the program running in the terminal (OSC 0/2).
+    pub program_title: Option<String>,
+    /// Whether the user renamed the tab; program titles no longer replace it.
+    pub renamed: bool,
     /// Creation time used by the tab-entry animation.
     pub opened_at: Instant,
 }
@@ -75,6 +81,11 @@ impl TabAgentStatus {
 impl Tab {
     pub const ENTRY_ANIMATION_DURATION: Duration = Duration::from_millis(220);
 
+    /// Name shown in the tab bar: the program's title, else the tab title
+    pub fn label(&self) -> &str {
+        self.program_title.as_deref().unwrap_or(&self.title)
+    }
+
     pub fn new_connection(id: Uuid) -> Self {
         Self {
             id,
@@ -82,9 +93,12 @@ impl Tab {
             tab_type: TabType::NewConnection,
             host_id: None,
             needs_attention: false,
+            has_unseen_output: false,
             agent_status: None,
             session_number: None,
             rename_value: None,
+            program_title: None,
+            renamed: false,
             opened_at: Instant::now(),
         }
     }
@@ -101,9 +115,12 @@ impl Tab {
             tab_type: TabType::Terminal,
             host_id,
             needs_attention: false,
+            has_unseen_output: false,
             agent_status: None,
             session_number: Some(session_number),
             rename_value: None,
+            program_title: None,
+            renamed: false,
             opened_at: Instant::now(),
         }
     }
@@ -115,9 +132,12 @@ impl Tab {
             tab_type: TabType::Sftp,
             host_id,
             needs_attention: false,
+            has_unseen_output: false,
             agent_status: None,
             session_number: None,
             rename_value: None,
+            program_title: None,
+            renamed: false,
             opened_at: Instant::now(),
         }
     }
@@ -129,9 +149,12 @@ impl Tab {
             tab_type: TabType::Vnc,
             host_id,
             needs_attention: false,
+            has_unseen_output: false,
             agent_status: None,
             session_number: None,
             rename_value: None,
+            program_title: None,
+            renamed: false,
             opened_at: Instant::now(),
         }
     }
@@ -143,13 +166,22 @@ impl Tab {
             tab_type: TabType::FileViewer,
             host_id: None,
             needs_attention: false,
+            has_unseen_output: false,
             agent_status: None,
             session_number: None,
             rename_value: None,
+            program_title: None,
+            renamed: false,
             opened_at: Instant::now(),
         }
     }
 
+    /// Clear the activity markers once the user looks at the tab.
+    pub fn mark_seen(&mut self) {
+        self.needs_attention = false;
+        self.has_unseen_output = false;
+    }
+
     pub fn entry_progress(&self) -> f32 {
         let linear = (self.opened_at.elapsed().as_secs_f32()
             / Self::ENTRY_ANIMATION_DURATION.as_secs_f32())
@@ -191,6 +223,7 @@ pub fn tab_bar_view<'a>(
     focus_index: usize,
     active_view: &View,
     hosts_config: &'a HostsConfig,
+    broadcast_group: &HashSet<Uuid>,
 ) -> Element<'a, Message> {
     // Determine if we should use terminal background (seamless look)
     let use_terminal_bg = matches!(
@@ -227,10 +260,12 @@ pub fn tab_bar_view<'a>(
         let is_active = active_tab == Some(tab.id);
         let is_focused = focus_section == FocusSection::TabBar && idx == focus_index;
         let show_session_number = should_show_session_number(tabs, tab);
+        let is_broadcast = broadcast_group.contains(&tab.id);
         let tab_button = tab_button(
             tab,
             is_active,
             is_focused,
+            is_broadcast,
             show_session_number,
             theme,
             fonts,
@@ -284,6 +319,7 @@ fn tab_button<'a>(
     tab: &'a Tab,
     is_active: bool,
     is_focused: bool,
+    is_broadcast: bool,
     show_session_number: bool,
     theme: Theme,
     fonts: ScaledFonts,
@@ -302,7 +338,7 @@ fn tab_button<'a>(
     let icon_data = if let Some(host_id) = tab.host_id {
         if let Some(host) = hosts_config.find_host(host_id) {
             if host.detected_os.is_some() {
-                os_icon_data(&host.detected_os)
+                icons::os::for_detected(host.detected_os.as_ref())
             } else {
                 // Fallback to terminal icon if no detected OS
                 icons::ui::TERMINAL
@@ -350,10 +386,32 @@ fn tab_button<'a>(
             })
             .into()
     } else {
-        text(truncate_title(&tab.title, 20))
-            .size(fonts.body)
-            .color(text_icon_color)
+        let label = tab.label();
+        let truncated = truncate_title(label, 20);
+        let is_truncated = truncated != label;
+        let title = text(truncated).size(fonts.body).color(text_icon_color);
+        if !is_truncated {
+            title.into()
+        } else {
+            tooltip(
+                title,
+                text(label.to_string())
+                    .size(fonts.label)
+                    .color(Color::from_rgb8(0xCD, 0xD6, 0xF4)),
+                tooltip::Position::Bottom,
+            )
+            .style(move |_theme| container::Style {
+                background: Some(Color::from_rgb8(0x1e, 0x1e, 0x2e).into()),
+                border: iced::Border {
+                    color: theme.border,
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            })
+            .padding(8)
             .into()
+        }
     };
 
     let session_number = if show_session_number {
@@ -386,7 +444,12 @@ fn tab_button<'a>(
     .align_x(Alignment::Center)
     .into();
 
-    let status_indicator = agent_status_indicator(tab.agent_status, tab.needs_attention, fonts);
+    let status_indicator = agent_status_indicator(
+        tab.agent_status,
+        tab.needs_attention,
+        tab.has_unseen_output,
+        fonts,
+    );
 
     let content = row![
         status_indicator,
@@ -415,13 +478,16 @@ fn tab_button<'a>(
                 }
                 _ => bg_color,
             };
-            // Focus ring border
+            // Focus ring border; broadcast members get a red border so it
+            // is obvious which tabs receive typed input.
             let border_color = if is_focused {
                 theme.focus_ring
+            } else if is_broadcast {
+                STATUS_FAILURE
             } else {
                 Color::TRANSPARENT
             };
-            let border_width = if is_focused { 2.0 } else { 0.0 };
+            let border_width = if is_focused || is_broadcast { 2.0 } else { 0.0 };
             iced::widget::button::Style {
                 background: Some(background.into()),
                 text_color: text_icon_color,
@@ -463,8 +529,8 @@ fn truncate_title(title: &str, max_chars: usize) -> String {
     let mut chars = title.chars();
     let truncated: String = chars.by_ref().take(max_chars).collect();
     if chars.next().is_some() {
-        let keep = max_chars.saturating_sub(3);
-        format!("{}...", title.chars().take(keep).collect::<String>())
+        let keep = max_chars.saturating_sub(1);
+        format!("{}…", title.chars().take(keep).collect::<String>())
     } else {
         truncated
     }
@@ -473,11 +539,15 @@ fn truncate_title(title: &str, max_chars: usize) -> String {
 fn agent_status_indicator<'a>(
     status: Option<TabAgentStatus>,
     needs_attention: bool,
+    has_unseen_output: bool,
     fonts: ScaledFonts,
 ) -> Element<'a, Message> {
     let Some(status) = status else {
+        // A bell or notification outranks plain output.
         let color = if needs_attention {
             Color::from_rgb8(0xf9, 0xe2, 0xaf)
+        } else if has_unseen_output {
+            Color::from_rgba8(0xa6, 0xad, 0xc8, 0.8)
         } else {
             Color::from_rgba8(0xff, 0xff, 0xff, 0.0)
         };
@@ -651,6 +721,7 @@ mod tests {
             0,
             &View::HostGrid,
             hosts,
+            &HashSet::new(),
         )
     }
 
@@ -1,15 +1,17 @@
 //! Terminal status bar component
 //!
-//! Displays connection duration, hostname, and keyboard shortcut hints
+//! Displays connection duration, hostname, the last command's result, port
+//! forward status, the broadcast input indicator, and keyboard shortcut hints
 //! at the bottom of the terminal view.
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use iced::widget::{Space, container, row, text};
+use iced::widget::{Space, button, container, row, text};
 use iced::{Alignment, Element, Length};
 
-use crate::message::Message;
-use crate::theme::{ScaledFonts, Theme};
+use crate::app::managers::LastCommand;
+use crate::message::{Message, SessionId, SessionMessage};
+use crate::theme::{STATUS_FAILURE, STATUS_SUCCESS, ScaledFonts, Theme};
 use crate::views::components::kbd;
 
 /// Format duration as MM:SS or HH:MM:SS
@@ -27,18 +29,61 @@ fn format_duration(start: Instant) -> String {
     }
 }
 
+/// Format a command's run time compactly: 850ms, 4.2s, 3m 07s, 1h 02m
+fn format_command_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    let secs = duration.as_secs();
+    if millis < 1000 {
+        format!("{}ms", millis)
+    } else if secs < 60 {
+        format!("{:.1}s", duration.as_secs_f32())
+    } else if secs < 3600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Port forward state shown for SSH sessions whose host has forwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForwardSummary {
+    /// Forwards currently running
+    pub active: usize,
+    /// Forwards configured on the host
+    pub total: usize,
+    /// Bound port of the running dynamic (SOCKS) forward, if any
+    pub socks_port: Option<u16>,
+}
+
+/// "2/3 forwards": running forwards out of those configured on the host
+fn format_forwards(active: usize, total: usize) -> String {
+    let noun = if total == 1 { "forward" } else { "forwards" };
+    format!("{}/{} {}", active, total, noun)
+}
+
 /// Build the terminal status bar element
+///
+/// Clicking the `forwards` summary opens the port forwards panel. `broadcast`
+/// is the broadcast group size when this session is a member; clicking the
+/// indicator takes the session out of the group.
+#[allow(clippy::too_many_arguments)]
 pub fn terminal_status_bar<'a>(
     theme: Theme,
     fonts: ScaledFonts,
+    session_id: SessionId,
     host_name: &'a str,
+    auth_method: Option<&'a str>,
     session_start: Instant,
     status_message: Option<String>,
+    last_command: Option<LastCommand>,
+    forwards: Option<ForwardSummary>,
+    broadcast: Option<usize>,
 ) -> Element<'a, Message> {
     let duration = format_duration(session_start);
 
-    // Left side: hostname and duration
-    let left = row![
+    // Left side: hostname, duration, and the last command's result (only
+    // shown when the shell reports commands via OSC 133)
+    let mut left = row![
         text(host_name)
             .size(fonts.caption)
             .color(theme.text_secondary),
@@ -49,6 +94,66 @@ pub fn terminal_status_bar<'a>(
     ]
     .align_y(Alignment::Center);
 
+    if let Some(auth_method) = auth_method {
+        left = left
+            .push(text(" | ").size(fonts.caption).color(theme.text_muted))
+            .push(
+                text(format!("via {}", auth_method))
+                    .size(fonts.caption)
+                    .color(theme.text_secondary),
+            );
+    }
+
+    if let Some(last_command) = last_command {
+        let (label, color) = match last_command.exit_status {
+            Some(0) => ("✓ 0".to_string(), STATUS_SUCCESS),
+            Some(code) => (format!("✗ {}", code), STATUS_FAILURE),
+            None => ("?".to_string(), theme.text_muted),
+        };
+        left = left
+            .push(text(" | ").size(fonts.caption).color(theme.text_muted))
+            .push(text(label).size(fonts.caption).color(color))
+            .push(
+                text(format!(
+                    " {}",
+                    format_command_duration(last_command.duration)
+                ))
+                .size(fonts.caption)
+                .color(theme.text_secondary),
+            );
+    }
+
+    if let Some(forwards) = forwards {
+        if let Some(port) = forwards.socks_port {
+            left = left
+                .push(text(" | ").size(fonts.caption).color(theme.text_muted))
+                .push(
+                    text(format!("SOCKS :{}", port))
+                        .size(fonts.caption)
+                        .color(theme.text_secondary),
+                );
+        }
+        let color = if forwards.active < forwards.total {
+            theme.text_muted
+        } else {
+            theme.text_secondary
+        };
+        left = left
+            .push(text(" | ").size(fonts.caption).color(theme.text_muted))
+            .push(
+                button(
+                    text(format_forwards(forwards.active, forwards.total))
+                        .size(fonts.caption)
+                        .color(color),
+                )
+                .padding(0)
+                .style(button::text)
+                .on_press(Message::Session(SessionMessage::OpenPortForwards(
+                    session_id,
+                ))),
+            );
+    }
+
     // Center: transient status message (if any)
     let center: Element<'_, Message> = if let Some(msg) = status_message {
         text(msg).size(fonts.caption).color(theme.accent).into()
@@ -56,15 +161,35 @@ pub fn terminal_status_bar<'a>(
         Space::new().into()
     };
 
-    // Right side: shortcut hint
-    let right = row![
-        kbd("Ctrl+Shift+K", theme, fonts),
+    // Right side: broadcast indicator and shortcut hint
+    let mut right = row![].spacing(6).align_y(Alignment::Center);
+    if let Some(members) = broadcast {
+        right = right.push(
+            button(
+                text(format!("BROADCAST · {}", members))
+                    .size(fonts.caption)
+                    .color(theme.text_on(STATUS_FAILURE)),
+            )
+            .padding([1, 8])
+            .style(move |_theme, _status| button::Style {
+                background: Some(STATUS_FAILURE.into()),
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .on_press(Message::Session(SessionMessage::ToggleBroadcast(
+                session_id,
+            ))),
+        );
+        right = right.push(Space::new().width(6));
+    }
+    right = right.push(kbd("Ctrl+Shift+K", theme, fonts)).push(
         text("Install SSH Key")
             .size(fonts.small)
             .color(theme.text_secondary),
-    ]
-    .spacing(6)
-    .align_y(Alignment::Center);
+    );
 
     let content = row![
         left,
@@ -89,3 +214,28 @@ pub fn terminal_status_bar<'a>(
         })
         .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_duration_formatting() {
+        assert_eq!(format_command_duration(Duration::from_millis(850)), "850ms");
+        assert_eq!(
+            format_command_duration(Duration::from_millis(4_250)),
+            "4.2s"
+        );
+        assert_eq!(format_command_duration(Duration::from_secs(187)), "3m 07s");
+        assert_eq!(
+            format_command_duration(Duration::from_secs(3_720)),
+            "1h 02m"
+        );
+    }
+
+    #[test]
+    fn forwards_formatting() {
+        assert_eq!(format_forwards(1, 1), "1/1 forward");
+        assert_eq!(format_forwards(2, 3), "2/3 forwards");
+    }
+}
@@ -3,16 +3,19 @@
 //! This wraps the terminal widget with session management.
 
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use iced::widget::{button, column, container, row, stack, text, text_input};
+use iced::widget::{
+    PaneGrid, button, column, container, pane_grid, pick_list, row, stack, text, text_input,
+};
 use iced::{Alignment, Color, Element, Fill};
 use parking_lot::Mutex;
 
-use crate::config::settings::TerminalMetricAdjustments;
+use crate::app::managers::{LastCommand, PaneColorScheme, PaneViewPrefs};
+use crate::config::settings::{TerminalMetricAdjustments, TerminalTextStyle};
 use crate::fonts::TerminalFont;
 use crate::icons::{icon_with_color, ui};
-use crate::keybindings::KeybindingsConfig;
+use crate::keybindings::{Keybinding, KeybindingsConfig};
 use crate::message::{Message, SearchMessage, SessionId, SessionMessage};
 use crate::terminal::TerminalBackend;
 use crate::terminal::backend::{EventProxy, TerminalEvent, TerminalSize};
@@ -20,11 +23,12 @@ use crate::terminal::links::TerminalLink;
 use crate::terminal::metrics::TerminalMetrics;
 use crate::terminal::search::TerminalSearchState;
 use crate::terminal::widget::TerminalWidget;
-use crate::theme::{BORDER_RADIUS, RADIUS_MD, ScaledFonts, Theme};
+use crate::theme::{BORDER_RADIUS, RADIUS_MD, STATUS_FAILURE, ScaledFonts, Theme};
+use crate::views::dialogs::common::{dialog_pick_list_menu_style, dialog_pick_list_style};
 use std::sync::atomic::AtomicU64;
 use tokio::sync::mpsc;
 
-use super::terminal_status_bar::terminal_status_bar;
+use super::terminal_status_bar::{ForwardSummary, terminal_status_bar};
 use alacritty_terminal::term::Term;
 
 /// Widget id of the terminal search bar's text input (for focus handling).
@@ -32,26 +36,46 @@ pub fn terminal_search_input_id() -> iced::widget::Id {
     iced::widget::Id::new("terminal_search_input")
 }
 
+/// How long the visual bell flashes the terminal
+const BELL_FLASH_DURATION: Duration = Duration::from_millis(150);
+
 /// Terminal session state
 pub struct TerminalSession {
     pub backend: TerminalBackend,
+    /// End of the running visual bell flash
+    bell_flash_until: Option<Instant>,
 }
 
 impl TerminalSession {
     /// Create a new terminal session
     pub fn new(_title: impl Into<String>) -> (Self, mpsc::Receiver<TerminalEvent>) {
-        Self::new_with_size(_title, 80, 24)
+        let (backend, event_rx) = TerminalBackend::new(TerminalSize::new(80, 24));
+        (
+            Self {
+                backend,
+                bell_flash_until: None,
+            },
+            event_rx,
+        )
     }
 
-    /// Create a new terminal session with an initial grid size.
+    /// Create a new terminal session with an initial grid size and
+    /// scrollback history length.
     pub fn new_with_size(
         _title: impl Into<String>,
         columns: u16,
         rows: u16,
+        scrollback_lines: usize,
     ) -> (Self, mpsc::Receiver<TerminalEvent>) {
-        let size = TerminalSize::new(columns, rows);
+        let size = TerminalSize::new(columns, rows).with_history_size(scrollback_lines);
         let (backend, event_rx) = TerminalBackend::new(size);
-        (Self { backend }, event_rx)
+        (
+            Self {
+                backend,
+                bell_flash_until: None,
+            },
+            event_rx,
+        )
     }
 
     /// Get the terminal for rendering
@@ -86,14 +110,43 @@ impl TerminalSession {
         self.backend.replace_with_rendered_snapshot(bytes);
     }
 
+    /// Clear the scrollback history
+    pub fn clear_scrollback(&self) {
+        self.backend.clear_history();
+    }
+
+    /// Reset the terminal to its initial state
+    pub fn reset(&self) {
+        self.backend.reset();
+    }
+
+    /// Start a visual bell flash
+    pub fn flash_bell(&mut self, now: Instant) {
+        self.bell_flash_until = Some(now + BELL_FLASH_DURATION);
+    }
+
+    /// Whether a visual bell flash is running
+    pub fn is_bell_flashing(&self) -> bool {
+        self.bell_flash_until.is_some()
+    }
+
+    /// End the visual bell flash once its time is up
+    pub fn expire_bell_flash(&mut self, now: Instant) {
+        if self.bell_flash_until.is_some_and(|until| now >= until) {
+            self.bell_flash_until = None;
+        }
+    }
+
     /// Resize the terminal to new dimensions
     pub fn resize(&mut self, cols: u16, rows: u16) -> bool {
         self.backend.resize(cols, rows)
     }
 }
 
-/// Build a terminal view element
 /// Build a terminal view element with status bar
+///
+/// `split_area` replaces the single terminal with the pane grid of a split
+/// tab; the status bar still describes `session_id`, the focused pane.
 #[allow(clippy::too_many_arguments)]
 pub fn terminal_view_with_status<'a>(
     theme: Theme,
@@ -102,13 +155,85 @@ pub fn terminal_view_with_status<'a>(
     session: &'a TerminalSession,
     session_start: Instant,
     host_name: &'a str,
+    auth_method: Option<&'a str>,
     status_message: Option<String>,
+    last_command: Option<LastCommand>,
+    forwards: Option<ForwardSummary>,
+    broadcast: Option<usize>,
+    split_area: Option<Element<'a, Message>>,
+    font_size: f32,
+    scroll_speed: f32,
+    terminal_font: TerminalFont,
+    terminal_metric_adjustments: TerminalMetricAdjustments,
+    terminal_text_style: TerminalTextStyle,
+    cursor_blink_visible: bool,
+    keybindings: KeybindingsConfig,
+    reserved_keys: Vec<Keybinding>,
+    focus_token: u64,
+    search: &'a TerminalSearchState,
+    on_input: impl Fn(SessionId, Vec<u8>) -> Message + 'a,
+    on_resize: impl Fn(SessionId, u16, u16) -> Message + 'a,
+    on_paste: impl Fn(SessionId) -> Message + 'a,
+    on_open_link: impl Fn(SessionId, TerminalLink) -> Message + 'a,
+) -> Element<'a, Message> {
+    let terminal_area = split_area.unwrap_or_else(|| {
+        terminal_pane(
+            theme,
+            fonts,
+            session_id,
+            session,
+            font_size,
+            scroll_speed,
+            terminal_font,
+            terminal_metric_adjustments,
+            terminal_text_style,
+            cursor_blink_visible,
+            keybindings,
+            reserved_keys,
+            focus_token,
+            true,
+            search,
+            on_input,
+            on_resize,
+            on_paste,
+            on_open_link,
+        )
+    });
+
+    let status_bar = terminal_status_bar(
+        theme,
+        fonts,
+        session_id,
+        host_name,
+        auth_method,
+        session_start,
+        status_message,
+        last_command,
+        forwards,
+        broadcast,
+    );
+
+    column![terminal_area, status_bar].into()
+}
+
+/// A single terminal widget with its search bar overlay. Only the `active`
+/// pane of a tab receives keyboard input.
+#[allow(clippy::too_many_arguments)]
+pub fn terminal_pane<'a>(
+    theme: Theme,
+    fonts: ScaledFonts,
+    session_id: SessionId,
+    session: &'a TerminalSession,
     font_size: f32,
     scroll_speed: f32,
     terminal_font: TerminalFont,
     terminal_metric_adjustments: TerminalMetricAdjustments,
+    terminal_text_style: TerminalTextStyle,
+    cursor_blink_visible: bool,
     keybindings: KeybindingsConfig,
+    reserved_keys: Vec<Keybinding>,
     focus_token: u64,
+    active: bool,
     search: &'a TerminalSearchState,
     on_input: impl Fn(SessionId, Vec<u8>) -> Message + 'a,
     on_resize: impl Fn(SessionId, u16, u16) -> Message + 'a,
@@ -145,9 +270,13 @@ pub fn terminal_view_with_status<'a>(
         .scroll_speed(scroll_speed)
         .font(terminal_font)
         .metric_adjustments(terminal_metric_adjustments)
+        .text_style(terminal_text_style)
         .keybindings(keybindings)
+        .reserved_keys(reserved_keys)
         .focus_token(focus_token)
-        .keyboard_input(!search.open)
+        .cursor_blink_visible(cursor_blink_visible)
+        .bell_flash(session.is_bell_flashing())
+        .keyboard_input(active && !search.open)
         .search_highlights(
             &search.matches,
             search.current,
@@ -166,7 +295,7 @@ pub fn terminal_view_with_status<'a>(
                 ..Default::default()
             });
 
-    let terminal_area: Element<'a, Message> = if search.open {
+    if search.open {
         stack![
             terminal_container,
             container(terminal_search_bar(session_id, search, theme, fonts))
@@ -177,11 +306,117 @@ pub fn terminal_view_with_status<'a>(
         .into()
     } else {
         terminal_container.into()
-    };
+    }
+}
 
-    let status_bar = terminal_status_bar(theme, fonts, host_name, session_start, status_message);
+/// One pane of a split terminal tab, as drawn by [`split_terminal_area`]
+pub struct SplitPane<'a> {
+    pub body: Element<'a, Message>,
+    /// Header holding the pane's font and colour controls
+    pub title_bar: pane_grid::TitleBar<'a, Message>,
+    /// Terminal background of the pane, which may override the theme's
+    pub background: Color,
+}
 
-    column![terminal_area, status_bar].into()
+/// Pane grid of a split terminal tab. Clicking a pane focuses it and the
+/// dividers can be dragged to resize.
+pub fn split_terminal_area<'a>(
+    theme: Theme,
+    grid: &'a pane_grid::State<SessionId>,
+    focused: SessionId,
+    pane: impl Fn(SessionId) -> SplitPane<'a>,
+) -> Element<'a, Message> {
+    PaneGrid::new(grid, move |_pane, session_id, _maximized| {
+        let is_focused = *session_id == focused;
+        let SplitPane {
+            body,
+            title_bar,
+            background,
+        } = pane(*session_id);
+        pane_grid::Content::new(body)
+            .title_bar(title_bar)
+            .style(move |_theme| container::Style {
+                background: Some(background.into()),
+                border: iced::Border {
+                    color: if is_focused {
+                        theme.focus_ring
+                    } else {
+                        theme.border
+                    },
+                    width: 1.0,
+                    radius: 0.0.into(),
+                },
+                ..Default::default()
+            })
+    })
+    .spacing(2)
+    .on_click(move |pane| {
+        grid.get(pane)
+            .map(|session_id| Message::Session(SessionMessage::FocusPane(*session_id)))
+            .unwrap_or(Message::Noop)
+    })
+    .on_resize(6, move |event| {
+        Message::Session(SessionMessage::PaneResized(focused, event))
+    })
+    .into()
+}
+
+/// Header of a split pane: its title, and on hover, font size buttons and
+/// a colour scheme picker that only affect this pane
+pub fn split_pane_title_bar<'a>(
+    theme: Theme,
+    fonts: ScaledFonts,
+    session_id: SessionId,
+    title: &'a str,
+    font_size: f32,
+    prefs: PaneViewPrefs,
+    schemes: &'a [PaneColorScheme],
+) -> pane_grid::TitleBar<'a, Message> {
+    let font_message =
+        |size: Option<f32>| Message::Session(SessionMessage::PaneFontSize(session_id, size));
+    let selected = schemes.iter().find(|scheme| scheme.colors == prefs.colors);
+    let scheme_picker = pick_list(schemes, selected, move |scheme: PaneColorScheme| {
+        Message::Session(SessionMessage::PaneColors(session_id, scheme.colors))
+    })
+    .padding([2, 8])
+    .text_size(fonts.small)
+    .style(dialog_pick_list_style(theme))
+    .menu_style(dialog_pick_list_menu_style(theme));
+
+    let controls = row![
+        search_bar_button(
+            text("A-").size(fonts.small),
+            Some(font_message(Some(font_size - 1.0))),
+            false,
+            theme
+        ),
+        text(format!("{}px", font_size))
+            .size(fonts.small)
+            .color(theme.text_secondary),
+        search_bar_button(
+            text("A+").size(fonts.small),
+            Some(font_message(Some(font_size + 1.0))),
+            false,
+            theme
+        ),
+        search_bar_button(
+            icon_with_color(ui::REFRESH, 12, theme.text_secondary),
+            prefs.font_size.is_some().then(|| font_message(None)),
+            false,
+            theme
+        ),
+        scheme_picker,
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    pane_grid::TitleBar::new(text(title).size(fonts.small).color(theme.text_secondary))
+        .controls(pane_grid::Controls::new(controls))
+        .padding([2, 8])
+        .style(move |_theme| container::Style {
+            background: Some(theme.surface.into()),
+            ..Default::default()
+        })
 }
 
 /// Small icon/text button used inside the terminal search bar.
@@ -261,7 +496,9 @@ fn terminal_search_bar<'a>(
             }
         });
 
-    let counter_color = if search.matches.is_empty() && !search.query.is_empty() {
+    let counter_color = if search.invalid_pattern {
+        STATUS_FAILURE
+    } else if search.matches.is_empty() && !search.query.is_empty() {
         theme.text_muted
     } else {
         theme.text_secondary
@@ -283,6 +520,12 @@ fn terminal_search_bar<'a>(
         false,
         theme,
     );
+    let regex_button = search_bar_button(
+        text(".*").size(fonts.small).color(theme.text_primary),
+        Some(search_message(SearchMessage::RegexToggled(session_id))),
+        search.regex,
+        theme,
+    );
     let case_button = search_bar_button(
         text("Aa").size(fonts.small).color(theme.text_primary),
         Some(search_message(SearchMessage::CaseSensitiveToggled(
@@ -304,6 +547,7 @@ fn terminal_search_bar<'a>(
             counter,
             previous_button,
             next_button,
+            regex_button,
             case_button,
             close_button,
         ]
@@ -355,11 +599,19 @@ mod tests {
             Instant::now(),
             "host",
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
             16.0,
             4.0,
             TerminalFont::default(),
             TerminalMetricAdjustments::default(),
+            TerminalTextStyle::default(),
+            true,
             KeybindingsConfig::default(),
+            Vec::new(),
             0,
             &search,
             |id, bytes| Message::Session(SessionMessage::Input(id, bytes)),
@@ -428,11 +680,19 @@ mod tests {
             Instant::now(),
             "host",
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
             font_size,
             4.0,
             TerminalFont::default(),
             TerminalMetricAdjustments::default(),
+            TerminalTextStyle::default(),
+            true,
             KeybindingsConfig::default(),
+            Vec::new(),
             0,
             &search,
             |id, bytes| Message::Session(SessionMessage::Input(id, bytes)),
@@ -494,11 +754,19 @@ mod tests {
             Instant::now(),
             "host",
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
             16.0,
             4.0,
             TerminalFont::default(),
             TerminalMetricAdjustments::default(),
+            TerminalTextStyle::default(),
+            true,
             KeybindingsConfig::default(),
+            Vec::new(),
             0,
             &search,
             |id, bytes| Message::Session(SessionMessage::Input(id, bytes)),
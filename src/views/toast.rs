@@ -13,6 +13,9 @@ use crate::theme::{BORDER_RADIUS, ScaledFonts, Theme};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToastAction {
     OpenVaultApprovals,
+    RestoreSessions,
+    /// Upload the changed copy of an Open With edit (its watch ID)
+    UploadEditedFile(Uuid),
 }
 
 /// Type of toast notification (determines color and icon)
@@ -2,11 +2,11 @@
 //!
 //! Renders the VNC framebuffer with toolbar and special key dropdown.
 
-use iced::widget::{button, column, container, pick_list, row, stack, text};
+use iced::widget::{Stack, button, column, container, pick_list, row, stack, text};
 use iced::{Element, Fill, Length};
 
 use crate::app::managers::session_manager::VncActiveSession;
-use crate::config::settings::{VncQualityPreset, VncScalingMode};
+use crate::config::settings::{VncKeyboardMode, VncQualityPreset, VncScalingMode};
 use crate::message::{HostMessage, Message, SessionId, VncMessage};
 use crate::theme::{ScaledFonts, Theme};
 use crate::vnc::session::VncStatsSnapshot;
@@ -120,6 +120,28 @@ pub fn vnc_viewer_view<'a>(
         .into()
     };
 
+    // Standard sends US-layout keysyms, Grab KB forwards every key, and
+    // Unicode sends typed characters for non-US layouts
+    let keyboard_mode_picker: Element<'a, Message> =
+        pick_list(VncKeyboardMode::ALL, Some(vnc.keyboard_mode), |mode| {
+            Message::Vnc(VncMessage::KeyboardModeSelected(mode))
+        })
+        .text_size(fonts.small)
+        .padding([2, 6])
+        .style(vnc_pick_list_style(theme))
+        .menu_style(vnc_pick_list_menu_style(theme))
+        .into();
+
+    let quality_picker: Element<'a, Message> =
+        pick_list(VncQualityPreset::ALL, Some(quality_preset), |preset| {
+            Message::Vnc(VncMessage::QualityPresetSelected(preset))
+        })
+        .text_size(fonts.small)
+        .padding([2, 6])
+        .style(vnc_pick_list_style(theme))
+        .menu_style(vnc_pick_list_menu_style(theme))
+        .into();
+
     // Transport indicator: tunneled sessions show the SSH path; direct
     // sessions always show an Unencrypted warning, since RFB traffic
     // (including keystrokes) is plaintext on the wire regardless of how the
@@ -164,27 +186,11 @@ pub fn vnc_viewer_view<'a>(
                 theme,
                 fonts
             ),
-            vnc_action_button_sized(
-                quality_preset.label(),
-                Message::Vnc(VncMessage::CycleQualityPreset),
-                68.0,
-                theme,
-                fonts
-            ),
+            quality_picker,
             text("|").size(fonts.small).color(theme.text_muted),
-            // Send Keys dropdown + Grab KB
+            // Send Keys dropdown + keyboard mode
             send_keys_picker,
-            vnc_action_button_sized(
-                if vnc.keyboard_passthrough {
-                    "Release KB"
-                } else {
-                    "Grab KB"
-                },
-                Message::Vnc(VncMessage::ToggleKeyboardPassthrough),
-                78.0,
-                theme,
-                fonts,
-            ),
+            keyboard_mode_picker,
             vnc_action_button_sized(
                 if vnc.view_only {
                     "View Only"
@@ -335,48 +341,62 @@ pub fn vnc_viewer_view<'a>(
         None
     };
 
-    let framebuffer_stack: Element<'a, Message> = match (waiting_overlay, stats_overlay) {
-        (Some(waiting), Some(stats)) => stack![
-            framebuffer,
+    // Badge so a display-only session is never mistaken for a live one
+    let view_only_badge: Option<Element<'a, Message>> = vnc.view_only.then(|| {
+        container(
+            text("View only — input is not sent")
+                .size(fonts.small)
+                .color(theme.text_on_accent()),
+        )
+        .padding([4, 10])
+        .style(move |_| iced::widget::container::Style {
+            background: Some(theme.accent.into()),
+            border: iced::Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+    });
+
+    let mut layers: Vec<Element<'a, Message>> = vec![framebuffer.into()];
+    if let Some(waiting) = waiting_overlay {
+        layers.push(
             container(waiting)
                 .width(Fill)
                 .height(Fill)
                 .align_x(iced::Alignment::Center)
-                .align_y(iced::Alignment::Center),
+                .align_y(iced::Alignment::Center)
+                .into(),
+        );
+    }
+    if let Some(stats) = stats_overlay {
+        layers.push(
             container(stats)
                 .width(Fill)
                 .height(Fill)
                 .align_x(iced::Alignment::Start)
                 .align_y(iced::Alignment::Start)
-                .padding(10),
-        ]
-        .width(Fill)
-        .height(Fill)
-        .into(),
-        (Some(waiting), None) => stack![
-            framebuffer,
-            container(waiting)
-                .width(Fill)
-                .height(Fill)
-                .align_x(iced::Alignment::Center)
-                .align_y(iced::Alignment::Center),
-        ]
-        .width(Fill)
-        .height(Fill)
-        .into(),
-        (None, Some(stats)) => stack![
-            framebuffer,
-            container(stats)
+                .padding(10)
+                .into(),
+        );
+    }
+    if let Some(badge) = view_only_badge {
+        layers.push(
+            container(badge)
                 .width(Fill)
                 .height(Fill)
-                .align_x(iced::Alignment::Start)
+                .align_x(iced::Alignment::End)
                 .align_y(iced::Alignment::Start)
-                .padding(10),
-        ]
-        .width(Fill)
-        .height(Fill)
-        .into(),
-        (None, None) => framebuffer.into(),
+                .padding(10)
+                .into(),
+        );
+    }
+    let framebuffer_stack: Element<'a, Message> = if layers.len() == 1 {
+        layers.remove(0)
+    } else {
+        Stack::with_children(layers).width(Fill).height(Fill).into()
     };
 
     if is_fullscreen {
@@ -7,14 +7,25 @@ pub fn build_encodings(
     is_private: bool,
     allow_tight: bool,
     include_cursor: bool,
+    extended_clipboard: bool,
+    jpeg_quality: Option<u8>,
 ) -> Vec<VncEncoding> {
     let mut encodings = Vec::new();
 
-    // Pseudo-encodings for better behavior (cursor + desktop size updates)
+    // Pseudo-encodings for better behavior (cursor + desktop size updates,
+    // UTF-8 clipboard)
     if include_cursor {
         encodings.push(VncEncoding::CursorPseudo);
     }
     encodings.push(VncEncoding::DesktopSizePseudo);
+    if extended_clipboard {
+        encodings.push(VncEncoding::ExtendedClipboardPseudo);
+    }
+    // JPEG is only used by Tight; without a level the server stays lossless
+    // or picks its own default
+    if let Some(level) = jpeg_quality.filter(|_| allow_tight) {
+        encodings.push(VncEncoding::jpeg_quality(level));
+    }
 
     let mut order = match preference {
         VncEncodingPreference::Auto => {
@@ -25,6 +25,17 @@ pub fn key_to_keysym(key: &Key) -> Option<u32> {
     }
 }
 
+/// Unicode keysym (`0x01000000 + codepoint`) for the character a key typed,
+/// independent of the local keyboard layout. Returns None for named keys and
+/// control characters.
+pub fn unicode_keysym(key: &Key) -> Option<u32> {
+    let Key::Character(c) = key else {
+        return None;
+    };
+    let ch = c.chars().next()?;
+    (!ch.is_control()).then(|| 0x0100_0000 + ch as u32)
+}
+
 fn named_key_to_keysym(key: &Named) -> Option<u32> {
     // X11 keysym constants
     Some(match key {
@@ -63,3 +74,26 @@ fn named_key_to_keysym(key: &Named) -> Option<u32> {
         _ => return None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unicode_keysyms_carry_the_typed_character() {
+        assert_eq!(
+            unicode_keysym(&Key::Character("a".into())),
+            Some(0x0100_0061)
+        );
+        assert_eq!(
+            unicode_keysym(&Key::Character("ß".into())),
+            Some(0x0100_00DF)
+        );
+        assert_eq!(
+            unicode_keysym(&Key::Character("€".into())),
+            Some(0x0100_20AC)
+        );
+        assert_eq!(unicode_keysym(&Key::Character("\u{7f}".into())), None);
+        assert_eq!(unicode_keysym(&Key::Named(Named::Enter)), None);
+    }
+}
@@ -635,6 +635,11 @@ pub struct VncSession {
     host: String,
     port: u16,
     disconnect_logged: Arc<AtomicBool>,
+    /// Whether the server is on a private network, which favors ZRLE
+    is_private: bool,
+    /// Whether the negotiated pixel format is 32-bit, which Tight and the
+    /// cursor pseudo-encoding need
+    true_color: bool,
 }
 
 impl std::fmt::Debug for VncSession {
@@ -732,13 +737,14 @@ impl VncSession {
 
         let pw = password.clone();
         let pixel_format = pixel_format_from_depth(vnc_settings.effective_color_depth());
-        let allow_tight = pixel_format.bits_per_pixel == 32;
-        let include_cursor = pixel_format.bits_per_pixel == 32;
+        let true_color = pixel_format.bits_per_pixel == 32;
         let encodings = build_encodings(
             vnc_settings.effective_encoding(),
             is_private,
-            allow_tight,
-            include_cursor,
+            true_color,
+            true_color,
+            vnc_settings.clipboard_sharing,
+            vnc_settings.effective_jpeg_quality(),
         );
         let mut connector = vnc::client::VncConnector::new(stream)
             .set_auth_method(async move { Ok(pw) })
@@ -785,6 +791,8 @@ impl VncSession {
             host: hostname.to_string(),
             port,
             disconnect_logged: disconnect_logged.clone(),
+            is_private,
+            true_color,
         });
 
         // Spawn the VNC event polling loop
@@ -1085,6 +1093,22 @@ impl VncSession {
             .try_send(X11Event::SetDesktopSize { width, height });
     }
 
+    /// Offer the encodings and JPEG quality of the current quality preset
+    /// and redraw the whole screen with them. The pixel format is fixed once
+    /// connected, so a preset's color depth only applies to new sessions.
+    pub fn apply_quality(&self, vnc_settings: &VncSettings) {
+        let encodings = build_encodings(
+            vnc_settings.effective_encoding(),
+            self.is_private,
+            self.true_color,
+            self.true_color,
+            vnc_settings.clipboard_sharing,
+            vnc_settings.effective_jpeg_quality(),
+        );
+        let _ = self.input_tx.try_send(X11Event::SetEncodings(encodings));
+        let _ = self.input_tx.try_send(X11Event::FullRefresh);
+    }
+
     /// Request a framebuffer refresh (best-effort, rate-limited)
     pub fn try_request_full_refresh(&self) {
         let _ = self.input_tx.try_send(X11Event::FullRefresh);
@@ -1220,6 +1244,8 @@ mod tests {
             host: "127.0.0.1".to_string(),
             port: 5900,
             disconnect_logged: Arc::new(AtomicBool::new(true)),
+            is_private: true,
+            true_color: true,
         }
     }
 
@@ -1293,6 +1319,29 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[test]
+    fn applying_quality_renegotiates_then_redraws() {
+        use crate::config::settings::VncQualityPreset;
+
+        let (tx, mut rx) = mpsc::channel(8);
+        let session = test_session(tx, Duration::from_secs(1));
+        let settings = VncSettings {
+            quality_preset: VncQualityPreset::Speed,
+            ..VncSettings::default()
+        };
+
+        session.apply_quality(&settings);
+        match rx.try_recv().expect("expected encodings") {
+            X11Event::SetEncodings(encodings) => {
+                assert_eq!(encodings.first(), Some(&vnc::VncEncoding::CursorPseudo));
+                assert!(encodings.contains(&vnc::VncEncoding::JpegQuality3));
+                assert!(encodings.contains(&vnc::VncEncoding::Tight));
+            }
+            other => panic!("expected encodings, got {other:?}"),
+        }
+        assert!(matches!(rx.try_recv(), Ok(X11Event::FullRefresh)));
+    }
+
     #[tokio::test(start_paused = true)]
     async fn frame_notifier_coalesces_bursts_and_emits_trailing_frame() {
         let notify = Arc::new(tokio::sync::Notify::new());
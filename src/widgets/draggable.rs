@@ -0,0 +1,308 @@
+//! A wrapper that lets its content be dragged and dropped onto.
+//!
+//! The content keeps working as usual for plain clicks. Once a left-button
+//! press moves past a small threshold the wrapper publishes its drag message,
+//! and the release publishes the drop message instead of reaching the
+//! content, so a dragged button does not also fire its click. Wrappers set up
+//! as drop targets report the cursor entering and leaving them; the app keeps
+//! track of which target is under the cursor.
+
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Widget};
+use iced::advanced::{Clipboard, Shell, mouse};
+use iced::{Element, Event, Length, Point, Rectangle, Size, Vector};
+
+/// Movement (px) required before a press turns into a drag, so plain clicks
+/// keep working.
+const DRAG_THRESHOLD: f32 = 5.0;
+
+#[derive(Default)]
+struct State {
+    press: Option<Point>,
+    dragging: bool,
+    hovered: bool,
+}
+
+/// A wrapper widget that turns presses on its content into drags and can
+/// act as a drop target.
+pub struct Draggable<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    content: Element<'a, Message, Theme, Renderer>,
+    on_drag: Option<Message>,
+    on_drop: Option<Message>,
+    on_enter: Option<Message>,
+    on_leave: Option<Message>,
+}
+
+impl<'a, Message, Theme, Renderer> Draggable<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    /// Creates a new [`Draggable`] around `content`.
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        Self {
+            content: content.into(),
+            on_drag: None,
+            on_drop: None,
+            on_enter: None,
+            on_leave: None,
+        }
+    }
+
+    /// Sets the message emitted once a press on the content becomes a drag.
+    pub fn on_drag(mut self, message: Message) -> Self {
+        self.on_drag = Some(message);
+        self
+    }
+
+    /// Sets the message emitted when a drag started here is released.
+    pub fn on_drop(mut self, message: Message) -> Self {
+        self.on_drop = Some(message);
+        self
+    }
+
+    /// Makes the wrapper a drop target, emitting `enter` and `leave` as the
+    /// cursor moves over and off it.
+    pub fn drop_target(mut self, enter: Message, leave: Message) -> Self {
+        self.on_enter = Some(enter);
+        self.on_leave = Some(leave);
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Draggable<'a, Message, Theme, Renderer>
+where
+    Message: Clone,
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> widget::tree::Tag {
+        widget::tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> widget::tree::State {
+        widget::tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<widget::Tree> {
+        vec![widget::Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut widget::Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut widget::Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+
+        if let Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) = event
+            && state.dragging
+        {
+            state.press = None;
+            state.dragging = false;
+            if let Some(on_drop) = &self.on_drop {
+                shell.publish(on_drop.clone());
+            }
+            // The content still sees the release so a pressed button resets,
+            // but without a cursor it cannot count it as a click.
+            self.content.as_widget_mut().update(
+                &mut tree.children[0],
+                event,
+                layout,
+                mouse::Cursor::Unavailable,
+                renderer,
+                clipboard,
+                shell,
+                viewport,
+            );
+            shell.capture_event();
+            return;
+        }
+
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+
+        match event {
+            // Cards and headers are buttons that capture presses, so record
+            // a potential drag even when the event is already captured.
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                // A release lost outside the window still ends the drag
+                if state.dragging
+                    && let Some(on_drop) = &self.on_drop
+                {
+                    shell.publish(on_drop.clone());
+                }
+                state.press = cursor.position_over(layout.bounds());
+                state.dragging = false;
+            }
+            Event::Mouse(mouse::Event::CursorLeft) => {
+                if state.hovered {
+                    state.hovered = false;
+                    if let Some(on_leave) = &self.on_leave {
+                        shell.publish(on_leave.clone());
+                    }
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                if let Some(press) = state.press
+                    && !state.dragging
+                    && let Some(position) = cursor.position()
+                    && press.distance(position) >= DRAG_THRESHOLD
+                {
+                    state.dragging = true;
+                    if let Some(on_drag) = &self.on_drag {
+                        shell.publish(on_drag.clone());
+                    }
+                }
+
+                // Not captured, so targets after this one still see the move
+                let hovered = cursor.is_over(layout.bounds());
+                if hovered != state.hovered {
+                    state.hovered = hovered;
+                    let message = if hovered {
+                        &self.on_enter
+                    } else {
+                        &self.on_leave
+                    };
+                    if let Some(message) = message {
+                        shell.publish(message.clone());
+                    }
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.press = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &widget::Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+        if state.dragging {
+            return mouse::Interaction::Grabbing;
+        }
+
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation,
+    ) {
+        self.content
+            .as_widget_mut()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut widget::Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<iced::advanced::overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Draggable<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: Clone + 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(draggable: Draggable<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(draggable)
+    }
+}
+
+/// Helper function to create a [`Draggable`].
+pub fn draggable<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> Draggable<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    Draggable::new(content)
+}
@@ -3,9 +3,11 @@
 mod animated_width;
 mod column_resize_handle;
 mod drag_tab_row;
+mod draggable;
 mod mouse_area;
 
 pub use animated_width::animated_width;
 pub use column_resize_handle::column_resize_handle;
 pub use drag_tab_row::drag_tab_row;
+pub use draggable::draggable;
 pub use mouse_area::mouse_area;
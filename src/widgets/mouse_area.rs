@@ -4,12 +4,13 @@ use iced::advanced::layout::{self, Layout};
 use iced::advanced::renderer;
 use iced::advanced::widget::{self, Widget};
 use iced::advanced::{Clipboard, Shell, mouse};
-use iced::{Element, Event, Length, Rectangle, Size, Vector};
+use iced::{Element, Event, Length, Point, Rectangle, Size, Vector, keyboard};
 
 /// Local state of the [`MouseArea`].
 #[derive(Default)]
 struct State {
     previous_click: Option<mouse::Click>,
+    modifiers: keyboard::Modifiers,
 }
 
 /// A wrapper widget that detects mouse clicks and modifier keys
@@ -23,6 +24,7 @@ where
     on_right_press: Option<Box<dyn Fn(f32, f32) -> Message + 'a>>,
     on_ctrl_press: Option<Message>,
     on_shift_press: Option<Message>,
+    on_command_scroll: Option<Box<dyn Fn(mouse::ScrollDelta, Point) -> Message + 'a>>,
     capture_all_events: bool,
     width: Length,
     height: Length,
@@ -41,6 +43,7 @@ where
             on_right_press: None,
             on_ctrl_press: None,
             on_shift_press: None,
+            on_command_scroll: None,
             capture_all_events: false,
             width: Length::Fill,
             height: Length::Shrink,
@@ -92,6 +95,17 @@ where
         self
     }
 
+    /// Sets the function to call on Ctrl+wheel (Cmd+wheel on macOS), passing
+    /// the cursor position relative to the area. The wheel event does not
+    /// reach the content.
+    pub fn on_command_scroll<F>(mut self, f: F) -> Self
+    where
+        F: Fn(mouse::ScrollDelta, Point) -> Message + 'a,
+    {
+        self.on_command_scroll = Some(Box::new(f));
+        self
+    }
+
     /// Captures all mouse events while the cursor is over the area.
     pub fn capture_all_events(mut self, capture: bool) -> Self {
         self.capture_all_events = capture;
@@ -183,6 +197,21 @@ where
             state.previous_click = Some(click);
         }
 
+        if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            tree.state.downcast_mut::<State>().modifiers = *modifiers;
+        }
+
+        // Wheel zoom must win over scrolling content
+        if let Event::Mouse(mouse::Event::WheelScrolled { delta }) = event
+            && tree.state.downcast_ref::<State>().modifiers.command()
+            && let Some(on_command_scroll) = &self.on_command_scroll
+            && let Some(position) = cursor.position_in(layout.bounds())
+        {
+            shell.publish(on_command_scroll(*delta, position));
+            shell.capture_event();
+            return;
+        }
+
         // First, let the content handle the event
         self.content.as_widget_mut().update(
             &mut tree.children[0],
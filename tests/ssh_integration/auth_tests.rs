@@ -259,6 +259,7 @@ async fn test_connection_timeout() {
         port: 22,
         username: "testuser".to_string(),
         auth: AuthMethod::Password,
+        auth_fallbacks: Vec::new(),
         protocol: portal::config::Protocol::Ssh,
         vnc_port: None,
         vnc_password_id: None,
@@ -267,6 +268,10 @@ async fn test_connection_timeout() {
         port_forwards: Vec::new(),
         hub_routing: portal::config::hosts::HubRouting::Auto,
         jump_host_id: None,
+        keepalive_interval: None,
+        keepalive_count_max: None,
+        sftp_start_path: None,
+        last_sftp_path: None,
         group_id: None,
         notes: None,
         tags: vec![],
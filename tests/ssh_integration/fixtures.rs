@@ -226,6 +226,7 @@ impl SshTestEnvironment {
             port: self.server.port,
             username: self.server.username.clone(),
             auth,
+            auth_fallbacks: Vec::new(),
             protocol: portal::config::Protocol::Ssh,
             vnc_port: None,
             vnc_password_id: None,
@@ -234,6 +235,10 @@ impl SshTestEnvironment {
             port_forwards: Vec::new(),
             hub_routing: portal::config::hosts::HubRouting::Auto,
             jump_host_id: None,
+            keepalive_interval: None,
+            keepalive_count_max: None,
+            sftp_start_path: None,
+            last_sftp_path: None,
             group_id: None,
             notes: None,
             tags: vec![],
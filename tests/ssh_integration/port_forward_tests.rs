@@ -155,10 +155,11 @@ async fn test_dynamic_forward_socks5_to_remote_sshd_banner() {
         description: Some("test dynamic forward".to_string()),
     };
 
-    session
+    let bound_port = session
         .create_dynamic_forward(forward.clone())
         .await
         .expect("create_dynamic_forward failed");
+    assert_eq!(bound_port, bind_port);
 
     let mut socks = tokio::time::timeout(
         Duration::from_secs(3),